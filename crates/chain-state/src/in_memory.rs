@@ -916,6 +916,8 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
     }
 
     /// Converts the new chain into a notification that will be emitted to listeners
+    ///
+    /// A reorg without new blocks is converted into a [`CanonStateNotification::Revert`].
     pub fn to_chain_notification(&self) -> CanonStateNotification<N> {
         match self {
             Self::Commit { new } => {
@@ -928,6 +930,16 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
                 }));
                 CanonStateNotification::Commit { new }
             }
+            Self::Reorg { new, old } if new.is_empty() => {
+                let old = Arc::new(old.iter().fold(Chain::default(), |mut chain, exec| {
+                    chain.append_block(
+                        exec.recovered_block().clone(),
+                        exec.execution_outcome().clone(),
+                    );
+                    chain
+                }));
+                CanonStateNotification::Revert { old }
+            }
             Self::Reorg { new, old } => {
                 let new = Arc::new(new.iter().fold(Chain::default(), |mut chain, exec| {
                     chain.append_block(
@@ -1553,6 +1565,23 @@ mod tests {
                 )),
                 new: Arc::new(Chain::new(
                     vec![block1a.recovered_block().clone(), block2a.recovered_block().clone()],
+                    sample_execution_outcome.clone(),
+                    None
+                ))
+            }
+        );
+
+        // Test revert notification
+        let chain_revert = NewCanonicalChain::Reorg {
+            new: Vec::new(),
+            old: vec![block1.block.clone(), block2.block.clone()],
+        };
+
+        assert_eq!(
+            chain_revert.to_chain_notification(),
+            CanonStateNotification::Revert {
+                old: Arc::new(Chain::new(
+                    vec![block1.recovered_block().clone(), block2.recovered_block().clone()],
                     sample_execution_outcome,
                     None
                 ))
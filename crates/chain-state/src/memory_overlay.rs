@@ -4,8 +4,8 @@ use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, StorageKey, Stora
 use reth_errors::ProviderResult;
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives};
 use reth_storage_api::{
-    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
-    StateProvider, StateRootProvider, StorageRootProvider,
+    AccountRange, AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider,
+    StateProofProvider, StateProvider, StateRootProvider, StorageRange, StorageRootProvider,
 };
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
@@ -230,6 +230,61 @@ impl<N: NodePrimitives> StateProvider for MemoryOverlayStateProviderRef<'_, N> {
 
         self.historical.storage(address, storage_key)
     }
+
+    fn account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+        with_addresses: bool,
+    ) -> ProviderResult<AccountRange> {
+        let mut state = self.trie_input().state.clone();
+        state.extend(hashed_state);
+        let mut range =
+            self.historical.account_range(state, start, limit, storage_limit, with_addresses)?;
+
+        // Resolve the preimages of accounts that were only written in memory.
+        if with_addresses {
+            for account in range.accounts.iter_mut().filter(|account| account.address.is_none()) {
+                account.address = self
+                    .in_memory
+                    .iter()
+                    .flat_map(|block| block.execution_output.bundle.state.keys())
+                    .find(|address| keccak256(address) == account.hashed_address)
+                    .copied();
+            }
+        }
+
+        Ok(range)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let state = &self.trie_input().state;
+        let mut hashed_storage =
+            state.storages.get(&keccak256(address)).cloned().unwrap_or_default();
+        hashed_storage.extend(&storage);
+        let mut range = self.historical.storage_range(address, hashed_storage, start, limit)?;
+
+        // Resolve the preimages of slots that were only written in memory.
+        for slot in range.slots.iter_mut().filter(|slot| slot.key.is_none()) {
+            slot.key = self
+                .in_memory
+                .iter()
+                .filter_map(|block| block.execution_output.bundle.account(&address))
+                .flat_map(|account| account.storage.keys())
+                .map(|key| B256::from(*key))
+                .find(|key| keccak256(key) == slot.hashed_key);
+        }
+
+        Ok(range)
+    }
 }
 
 impl<N: NodePrimitives> BytecodeReader for MemoryOverlayStateProviderRef<'_, N> {
@@ -79,7 +79,8 @@ impl<N: NodePrimitives> Stream for CanonStateNotificationStream<N> {
 /// A notification that is sent when a new block is imported, or an old block is reverted.
 ///
 /// The notification contains at least one [`Chain`] with the imported segment. If some blocks were
-/// reverted (e.g. during a reorg), the old chain is also returned.
+/// reverted (e.g. during a reorg), the old chain is also returned. If blocks were only reverted,
+/// the notification only contains the old chain.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(bound = ""))]
@@ -104,6 +105,13 @@ pub enum CanonStateNotification<N: NodePrimitives = reth_ethereum_primitives::Et
         /// In the case of a revert, not a reorg, this chain segment is empty.
         new: Arc<Chain<N>>,
     },
+    /// A chain segment was reverted without adding any blocks, e.g. by `debug_setHead`.
+    ///
+    /// The parent of the first reverted block is the new canonical head.
+    Revert {
+        /// The chain segment that was reverted.
+        old: Arc<Chain<N>>,
+    },
 }
 
 impl<N: NodePrimitives> CanonStateNotification<N> {
@@ -111,14 +119,17 @@ impl<N: NodePrimitives> CanonStateNotification<N> {
     pub fn reverted(&self) -> Option<Arc<Chain<N>>> {
         match self {
             Self::Commit { .. } => None,
-            Self::Reorg { old, .. } => Some(old.clone()),
+            Self::Reorg { old, .. } | Self::Revert { old } => Some(old.clone()),
         }
     }
 
-    /// Get the newly imported chain segment, if any.
+    /// Get the newly imported chain segment.
+    ///
+    /// The chain segment is empty for [`Self::Revert`].
     pub fn committed(&self) -> Arc<Chain<N>> {
         match self {
             Self::Commit { new } | Self::Reorg { new, .. } => new.clone(),
+            Self::Revert { .. } => Default::default(),
         }
     }
 
@@ -129,10 +140,11 @@ impl<N: NodePrimitives> CanonStateNotification<N> {
     ///
     /// # Panics
     ///
-    /// If chain doesn't have any blocks.
+    /// If chain doesn't have any blocks, which is always the case for [`Self::Revert`].
     pub fn tip(&self) -> &RecoveredBlock<N::Block> {
         match self {
             Self::Commit { new } | Self::Reorg { new, .. } => new.tip(),
+            Self::Revert { .. } => panic!("reverted chains don't commit a new tip"),
         }
     }
 
@@ -149,6 +161,7 @@ impl<N: NodePrimitives> CanonStateNotification<N> {
                     Some(new.tip())
                 }
             }
+            Self::Revert { .. } => None,
         }
     }
 
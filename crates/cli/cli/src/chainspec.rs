@@ -56,6 +56,18 @@ pub trait ChainSpecParser: Clone + Send + Sync + 'static {
     /// chain spec.
     fn parse(s: &str) -> eyre::Result<Arc<Self::ChainSpec>>;
 
+    /// Returns the given chain spec with its chain id replaced.
+    ///
+    /// This is used to run a dev chain forked from a remote chain with the chain id of the remote
+    /// chain. By default, this returns an error.
+    fn with_chain_id(
+        chain_spec: Arc<Self::ChainSpec>,
+        chain_id: u64,
+    ) -> eyre::Result<Arc<Self::ChainSpec>> {
+        let _ = chain_spec;
+        eyre::bail!("changing the chain id to {chain_id} is not supported for this chain")
+    }
+
     /// Produces a [`TypedValueParser`] for this chain spec parser.
     fn parser() -> impl TypedValueParser<Value = Arc<Self::ChainSpec>> {
         Parser(std::marker::PhantomData::<Self>)
@@ -1,14 +1,13 @@
-use crate::{
-    common::CliNodeTypes,
-    db::get::{maybe_json_value_parser, table_key},
-};
+use crate::{common::CliNodeTypes, db::get::maybe_json_value_parser};
 use alloy_primitives::map::foldhash::fast::FixedState;
 use clap::Parser;
 use reth_chainspec::EthereumHardforks;
 use reth_db::DatabaseEnv;
 use reth_db_api::{
-    cursor::DbCursorRO, table::Table, transaction::DbTx, RawKey, RawTable, RawValue, TableViewer,
-    Tables,
+    cursor::DbCursorRO,
+    table::{table_key, Table},
+    transaction::DbTx,
+    RawKey, RawTable, RawValue, TableViewer, Tables,
 };
 use reth_db_common::DbTool;
 use reth_node_builder::{NodeTypesWithDB, NodeTypesWithDBAdapter};
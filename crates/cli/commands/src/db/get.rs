@@ -8,7 +8,7 @@ use reth_db::{
     RawDupSort,
 };
 use reth_db_api::{
-    table::{Decompress, DupSort, Table},
+    table::{table_key, table_subkey, Decompress, DupSort, Table},
     tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
 use reth_db_common::DbTool;
@@ -130,16 +130,6 @@ impl Command {
     }
 }
 
-/// Get an instance of key for given table
-pub(crate) fn table_key<T: Table>(key: &str) -> Result<T::Key, eyre::Error> {
-    serde_json::from_str(key).map_err(|e| eyre::eyre!(e))
-}
-
-/// Get an instance of subkey for given dupsort table
-fn table_subkey<T: DupSort>(subkey: Option<&str>) -> Result<T::SubKey, eyre::Error> {
-    serde_json::from_str(subkey.unwrap_or_default()).map_err(|e| eyre::eyre!(e))
-}
-
 struct GetValueViewer<'a, N: NodeTypesWithDB> {
     tool: &'a DbTool<N>,
     key: String,
@@ -178,7 +168,7 @@ impl<N: ProviderNodeTypes> TableViewer<()> for GetValueViewer<'_, N> {
         let key = table_key::<T>(&self.key)?;
 
         // process dupsort table
-        let subkey = table_subkey::<T>(self.subkey.as_deref())?;
+        let subkey = table_subkey::<T>(self.subkey.as_deref().unwrap_or_default())?;
 
         let content = if self.raw {
             self.tool
@@ -212,69 +202,3 @@ pub(crate) fn maybe_json_value_parser(value: &str) -> Result<String, eyre::Error
         serde_json::to_string(&value).map_err(|e| eyre::eyre!(e))
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloy_primitives::{address, B256};
-    use clap::{Args, Parser};
-    use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, ShardedKey},
-        AccountsHistory, HashedAccounts, Headers, StageCheckpoints, StoragesHistory,
-    };
-    use std::str::FromStr;
-
-    /// A helper type to parse Args more easily
-    #[derive(Parser)]
-    struct CommandParser<T: Args> {
-        #[command(flatten)]
-        args: T,
-    }
-
-    #[test]
-    fn parse_numeric_key_args() {
-        assert_eq!(table_key::<Headers>("123").unwrap(), 123);
-        assert_eq!(
-            table_key::<HashedAccounts>(
-                "\"0x0ac361fe774b78f8fc4e86c1916930d150865c3fc2e21dca2e58833557608bac\""
-            )
-            .unwrap(),
-            B256::from_str("0x0ac361fe774b78f8fc4e86c1916930d150865c3fc2e21dca2e58833557608bac")
-                .unwrap()
-        );
-    }
-
-    #[test]
-    fn parse_string_key_args() {
-        assert_eq!(
-            table_key::<StageCheckpoints>("\"MerkleExecution\"").unwrap(),
-            "MerkleExecution"
-        );
-    }
-
-    #[test]
-    fn parse_json_key_args() {
-        assert_eq!(
-            table_key::<StoragesHistory>(r#"{ "address": "0x01957911244e546ce519fbac6f798958fafadb41", "sharded_key": { "key": "0x0000000000000000000000000000000000000000000000000000000000000003", "highest_block_number": 18446744073709551615 } }"#).unwrap(),
-            StorageShardedKey::new(
-                address!("0x01957911244e546ce519fbac6f798958fafadb41"),
-                B256::from_str(
-                    "0x0000000000000000000000000000000000000000000000000000000000000003"
-                )
-                .unwrap(),
-                18446744073709551615
-            )
-        );
-    }
-
-    #[test]
-    fn parse_json_key_for_account_history() {
-        assert_eq!(
-            table_key::<AccountsHistory>(r#"{ "key": "0x4448e1273fd5a8bfdb9ed111e96889c960eee145", "highest_block_number": 18446744073709551615 }"#).unwrap(),
-            ShardedKey::new(
-                address!("0x4448e1273fd5a8bfdb9ed111e96889c960eee145"),
-                18446744073709551615
-            )
-        );
-    }
-}
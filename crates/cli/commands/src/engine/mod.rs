@@ -0,0 +1,32 @@
+//! `reth engine` command
+
+use clap::{Parser, Subcommand};
+use reth_payload_primitives::PayloadTypes;
+
+pub mod replay;
+
+/// `reth engine` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    pub command: Subcommands,
+}
+
+/// `reth engine` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Replay engine API messages stored with `--debug.engine-api-store` against a running node.
+    Replay(replay::Command),
+}
+
+impl Command {
+    /// Execute `engine` command
+    pub async fn execute<T>(self) -> eyre::Result<()>
+    where
+        T: PayloadTypes<ExecutionData = alloy_rpc_types_engine::ExecutionData>,
+    {
+        match self.command {
+            Subcommands::Replay(command) => command.execute::<T>().await,
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Command that replays stored engine API messages against a node.
+
+use alloy_rpc_types_engine::{
+    ExecutionData, ExecutionPayload, ForkchoiceState, ForkchoiceUpdated, PayloadStatus,
+};
+use clap::Parser;
+use eyre::OptionExt;
+use jsonrpsee::{core::client::ClientT, http_client::HttpClientBuilder, rpc_params};
+use reth_engine_util::{
+    engine_store::EngineMessageKind,
+    replay::{
+        forkchoice_updated_version, EngineMessageReplay, EngineReplayTarget, ReplayFilter,
+        ReplayReport,
+    },
+};
+use reth_node_core::args::{parse_engine_api_replay_speed, EngineApiMessageType};
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadTypes};
+use reth_rpc_layer::{AuthClientLayer, JwtSecret};
+use std::path::PathBuf;
+use tracing::info;
+
+/// `reth engine replay` command
+///
+/// Replays the engine API messages stored in a directory against the authenticated engine API
+/// endpoint of a node, in the order they were received, and reports every response that differs
+/// from the expected one.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The directory with the engine API messages stored by `--debug.engine-api-store`.
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// The URL of the authenticated engine API endpoint to replay the messages against.
+    #[arg(long = "engine.url", value_name = "URL", default_value = "http://localhost:8551")]
+    engine_url: String,
+
+    /// The path to the JWT secret used to authenticate with the engine API endpoint.
+    #[arg(long = "engine.jwtsecret", value_name = "PATH")]
+    jwt_secret: PathBuf,
+
+    /// Only replay payloads with a block number greater than or equal to this one.
+    ///
+    /// Forkchoice updates are only replayed if their head block is a replayed payload.
+    #[arg(long, value_name = "BLOCK")]
+    from: Option<u64>,
+
+    /// Only replay payloads with a block number less than or equal to this one.
+    ///
+    /// Forkchoice updates are only replayed if their head block is a replayed payload.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<u64>,
+
+    /// Only replay messages of this type.
+    #[arg(long = "message-type", value_name = "TYPE")]
+    message_type: Option<EngineApiMessageType>,
+
+    /// Preserve the recorded delays between messages, divided by this factor.
+    ///
+    /// Messages are sent back to back if not set.
+    #[arg(long, value_name = "FACTOR", value_parser = parse_engine_api_replay_speed)]
+    speed: Option<f64>,
+
+    /// Write the responses of the node to this file as JSON.
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+
+    /// Expect the responses recorded in this report of a previous replay instead of expecting
+    /// every message to be valid.
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `engine replay` command
+    pub async fn execute<T>(self) -> eyre::Result<()>
+    where
+        T: PayloadTypes<ExecutionData = ExecutionData>,
+    {
+        let baseline = self.baseline.as_deref().map(ReplayReport::read).transpose()?;
+
+        let secret = JwtSecret::from_file(&self.jwt_secret)?;
+        let middleware = tower::ServiceBuilder::default().layer(AuthClientLayer::new(secret));
+        let client =
+            HttpClientBuilder::default().set_http_middleware(middleware).build(&self.engine_url)?;
+
+        let mut replay = EngineMessageReplay::new(self.path).with_filter(ReplayFilter {
+            kind: self.message_type.map(message_kind),
+            from_block: self.from,
+            to_block: self.to,
+        });
+        if let Some(speed) = self.speed {
+            replay = replay.with_speed(speed);
+        }
+
+        info!(target: "reth::cli", url = %self.engine_url, "Replaying engine API messages");
+        let report = replay.run::<T, _>(&RpcEngine { client }).await?;
+
+        if let Some(path) = &self.report {
+            report.write(path)?;
+            info!(target: "reth::cli", path = %path.display(), "Wrote replay report");
+        }
+
+        let divergences = report.divergences(baseline.as_ref());
+        for divergence in &divergences {
+            let message = divergence.message;
+            println!(
+                "{} {} {}: expected {:?}, got {:?}",
+                message.file, message.kind, message.block_hash, divergence.expected, message.status
+            );
+        }
+
+        if !divergences.is_empty() {
+            eyre::bail!(
+                "{} of {} replayed messages diverged",
+                divergences.len(),
+                report.messages.len()
+            )
+        }
+
+        info!(target: "reth::cli", messages = report.messages.len(), "Replay finished without divergences");
+        Ok(())
+    }
+}
+
+/// Sends replayed messages to an engine API endpoint, using the method version that matches each
+/// message.
+#[derive(Debug)]
+struct RpcEngine<C> {
+    client: C,
+}
+
+impl<T, C> EngineReplayTarget<T> for RpcEngine<C>
+where
+    T: PayloadTypes<ExecutionData = ExecutionData>,
+    C: ClientT + Send + Sync,
+{
+    async fn new_payload(&self, data: ExecutionData) -> eyre::Result<PayloadStatus> {
+        let ExecutionData { payload, sidecar } = data;
+        let (version, params) = match payload {
+            ExecutionPayload::V1(payload) => (EngineApiMessageVersion::V1, rpc_params![payload]),
+            ExecutionPayload::V2(payload) => (EngineApiMessageVersion::V2, rpc_params![payload]),
+            ExecutionPayload::V3(payload) => {
+                let cancun = sidecar.cancun().ok_or_eyre("V3 payload without cancun fields")?;
+                match sidecar.prague() {
+                    Some(prague) => (
+                        EngineApiMessageVersion::V4,
+                        rpc_params![
+                            payload,
+                            &cancun.versioned_hashes,
+                            cancun.parent_beacon_block_root,
+                            &prague.requests
+                        ],
+                    ),
+                    None => (
+                        EngineApiMessageVersion::V3,
+                        rpc_params![
+                            payload,
+                            &cancun.versioned_hashes,
+                            cancun.parent_beacon_block_root
+                        ],
+                    ),
+                }
+            }
+        };
+
+        Ok(self.client.request(version.method_name(), params).await?)
+    }
+
+    async fn fork_choice_updated(
+        &self,
+        state: ForkchoiceState,
+        payload_attrs: Option<T::PayloadAttributes>,
+    ) -> eyre::Result<PayloadStatus> {
+        let method = match forkchoice_updated_version(payload_attrs.as_ref()) {
+            EngineApiMessageVersion::V1 => "engine_forkchoiceUpdatedV1",
+            EngineApiMessageVersion::V2 => "engine_forkchoiceUpdatedV2",
+            _ => "engine_forkchoiceUpdatedV3",
+        };
+
+        let updated: ForkchoiceUpdated =
+            self.client.request(method, rpc_params![state, payload_attrs]).await?;
+        Ok(updated.payload_status)
+    }
+}
+
+/// Returns the kind of the stored engine API messages of the given type.
+const fn message_kind(message_type: EngineApiMessageType) -> EngineMessageKind {
+    match message_type {
+        EngineApiMessageType::Fcu => EngineMessageKind::ForkchoiceUpdated,
+        EngineApiMessageType::NewPayload => EngineMessageKind::NewPayload,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_replay_args() {
+        let cmd = Command::try_parse_from([
+            "reth",
+            "/tmp/engine-messages",
+            "--engine.jwtsecret",
+            "/tmp/jwt.hex",
+            "--from",
+            "10",
+            "--message-type",
+            "new-payload",
+            "--speed",
+            "2.5",
+        ])
+        .unwrap();
+        assert_eq!(cmd.engine_url, "http://localhost:8551");
+        assert_eq!(cmd.from, Some(10));
+        assert_eq!(cmd.to, None);
+        assert_eq!(cmd.message_type, Some(EngineApiMessageType::NewPayload));
+        assert_eq!(cmd.speed, Some(2.5));
+
+        assert!(Command::try_parse_from([
+            "reth",
+            "/tmp/engine-messages",
+            "--engine.jwtsecret",
+            "/tmp/jwt.hex",
+            "--speed",
+            "0"
+        ])
+        .is_err());
+    }
+}
@@ -24,6 +24,13 @@ pub struct ImportEraCommand<C: ChainSpecParser> {
 
     #[clap(flatten)]
     import: ImportArgs,
+
+    /// The path to a file with the known accumulator roots of the ERA1 files.
+    ///
+    /// The file contains one hex encoded root per line, in the order of the eras. Every imported
+    /// ERA1 file is verified against the root of its era.
+    #[arg(long, value_name = "HISTORICAL_ROOTS_PATH", verbatim_doc_comment)]
+    historical_roots: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
@@ -32,6 +39,9 @@ pub struct ImportArgs {
     /// The path to a directory for import.
     ///
     /// The ERA1 files are read from the local directory parsing headers and bodies.
+    ///
+    /// Post-merge ERA files are read after them, taking headers and bodies from the execution
+    /// payloads of the beacon blocks.
     #[arg(long, value_name = "IMPORT_ERA_PATH", verbatim_doc_comment)]
     path: Option<PathBuf>,
 
@@ -80,10 +90,14 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
             .unwrap_or_default() +
             1;
 
+        let historical_roots =
+            self.historical_roots.as_deref().map(era::read_historical_roots).transpose()?;
+        let historical_roots = historical_roots.as_deref();
+
         if let Some(path) = self.import.path {
             let stream = read_dir(path, next_block)?;
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+            era::import(stream, &provider_factory, &mut hash_collector, historical_roots)?;
         } else {
             let url = match self.import.url {
                 Some(url) => url,
@@ -98,7 +112,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
             let client = EraClient::new(Client::new(), url, folder);
             let stream = EraStream::new(client, config);
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+            era::import(stream, &provider_factory, &mut hash_collector, historical_roots)?;
         }
 
         Ok(())
@@ -1,7 +1,9 @@
 //! Main node command for launching a node
 
 use crate::launcher::Launcher;
+use alloy_provider::{network::AnyNetwork, Provider, RootProvider};
 use clap::{value_parser, Args, Parser};
+use eyre::Context;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
@@ -10,8 +12,8 @@ use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, ForkArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -101,6 +103,10 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten)]
     pub dev: DevArgs,
 
+    /// All arguments for forking the dev chain from a remote node
+    #[command(flatten)]
+    pub fork: ForkArgs,
+
     /// All pruning related arguments
     #[command(flatten)]
     pub pruning: PruningArgs,
@@ -164,12 +170,25 @@ where
             debug,
             db,
             dev,
+            fork,
             pruning,
             ext,
             engine,
             era,
         } = self;
 
+        // a forked dev chain continues the remote chain, so it runs with the remote chain id
+        let chain = match &fork.fork_url {
+            Some(fork_url) => {
+                let chain_id = RootProvider::<AnyNetwork>::new_http(fork_url.clone())
+                    .get_chain_id()
+                    .await
+                    .wrap_err("failed to fetch the chain id of the fork")?;
+                C::with_chain_id(chain, chain_id)?
+            }
+            None => chain,
+        };
+
         // set up node config
         let mut node_config = NodeConfig {
             datadir,
@@ -184,6 +203,7 @@ where
             debug,
             db,
             dev,
+            fork,
             pruning,
             engine,
             era,
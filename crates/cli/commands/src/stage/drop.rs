@@ -147,6 +147,24 @@ impl<C: ChainSpecParser> Command<C> {
 
                 insert_genesis_history(&provider_rw, self.env.chain.genesis().alloc.iter())?;
             }
+            StageEnum::AddressAppearances => {
+                tx.clear::<tables::AddressAppearances>()?;
+                reset_prune_checkpoint(tx, PruneSegment::AddressAppearances)?;
+
+                remove_stage_checkpoint(tx, StageId::IndexAddressAppearances)?;
+            }
+            StageEnum::LogIndex => {
+                tx.clear::<tables::LogAddressIndex>()?;
+                tx.clear::<tables::LogTopicIndex>()?;
+                reset_prune_checkpoint(tx, PruneSegment::LogIndex)?;
+
+                remove_stage_checkpoint(tx, StageId::IndexLogs)?;
+            }
+            StageEnum::TraceIndex => {
+                tx.clear::<tables::TraceAddressIndex>()?;
+
+                remove_stage_checkpoint(tx, StageId::IndexTraces)?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 reset_prune_checkpoint(tx, PruneSegment::TransactionLookup)?;
@@ -189,3 +207,15 @@ fn reset_stage_checkpoint(
 
     Ok(())
 }
+
+/// Removes the checkpoint of an opt-in index stage, which disables the index until the stage is
+/// enabled and runs again.
+fn remove_stage_checkpoint(
+    tx: &Tx<reth_db::mdbx::RW>,
+    stage_id: StageId,
+) -> Result<(), DatabaseError> {
+    tx.delete::<tables::StageCheckpoints>(stage_id.to_string(), None)?;
+    tx.delete::<tables::StageCheckpointProgresses>(stage_id.to_string(), None)?;
+
+    Ok(())
+}
@@ -36,8 +36,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexAddressAppearancesStage, IndexLogsStage, IndexStorageHistoryStage, IndexTracesStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -316,6 +316,30 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     )),
                     None,
                 ),
+                StageEnum::AddressAppearances => (
+                    Box::new(IndexAddressAppearancesStage::new(
+                        config.stages.index_address_appearances,
+                        etl_config,
+                        prune_modes.address_appearances,
+                    )),
+                    None,
+                ),
+                StageEnum::LogIndex => (
+                    Box::new(IndexLogsStage::new(
+                        config.stages.index_logs,
+                        etl_config,
+                        prune_modes.log_index,
+                    )),
+                    None,
+                ),
+                StageEnum::TraceIndex => (
+                    Box::new(IndexTracesStage::new(
+                        components.evm_config().clone(),
+                        config.stages.index_traces,
+                        etl_config,
+                    )),
+                    None,
+                ),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -14,7 +14,6 @@ const EXTENSION: &str = "toml";
 /// The default prune block interval
 pub const DEFAULT_BLOCK_INTERVAL: usize = 5;
 
-
 /// Configuration for the reth node.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -126,6 +125,12 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Appearances stage configuration.
+    pub index_address_appearances: IndexAddressAppearancesConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Index Traces stage configuration.
+    pub index_traces: IndexTracesConfig,
     /// Snap Sync stage configuration.
     pub snap_sync: SnapSyncConfig,
     /// Common ETL related configuration.
@@ -162,6 +167,11 @@ pub struct EraConfig {
     ///
     /// Required for `url`.
     pub folder: Option<PathBuf>,
+    /// Path to a file with the known accumulator roots of the ERA1 files, one hex encoded root per
+    /// line in the order of the eras.
+    ///
+    /// If set, every imported ERA1 file is verified against the root of its era.
+    pub historical_roots: Option<PathBuf>,
 }
 
 impl EraConfig {
@@ -289,7 +299,7 @@ impl Default for SnapSyncConfig {
             max_response_bytes: 2 * 1024 * 1024, // 2MB
             request_timeout_seconds: 30,
             range_size: 0x10, // 16 hash values (very small default for testing)
-            max_retries: 3, // 3 retries by default
+            max_retries: 3,   // 3 retries by default
         }
     }
 }
@@ -465,6 +475,62 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Index Address Appearances stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexAddressAppearancesConfig {
+    /// Enable the address appearance index, used by `ots_searchTransactionsBefore` and
+    /// `ots_searchTransactionsAfter`.
+    pub enabled: bool,
+    /// Also index log emitters and address-shaped log topics.
+    pub index_logs: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressAppearancesConfig {
+    fn default() -> Self {
+        Self { enabled: false, index_logs: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Index Logs stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexLogsConfig {
+    /// Enable the log index, used by `eth_getLogs` and log filters instead of scanning the header
+    /// blooms.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Index Traces stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexTracesConfig {
+    /// Enable the trace index, used by `trace_filter` to only re-execute the blocks with traces
+    /// touching the filtered addresses.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexTracesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 10_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -503,6 +569,8 @@ impl PruneConfig {
                     account_history,
                     storage_history,
                     bodies_history,
+                    address_appearances,
+                    log_index,
                     receipts_log_filter,
                 },
         } = other;
@@ -519,6 +587,9 @@ impl PruneConfig {
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
         self.segments.bodies_history = self.segments.bodies_history.or(bodies_history);
+        self.segments.address_appearances =
+            self.segments.address_appearances.or(address_appearances);
+        self.segments.log_index = self.segments.log_index.or(log_index);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -1040,6 +1111,8 @@ receipts = 'full'
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
                 bodies_history: None,
+                address_appearances: None,
+                log_index: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -1056,6 +1129,8 @@ receipts = 'full'
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
                 bodies_history: None,
+                address_appearances: None,
+                log_index: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
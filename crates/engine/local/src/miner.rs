@@ -1,25 +1,30 @@
 //! Contains the implementation of the mining mode for the local engine.
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{BlockNumber, TxHash, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use eyre::OptionExt;
 use futures_util::{stream::Fuse, StreamExt};
-use reth_engine_primitives::ConsensusEngineHandle;
+use reth_engine_primitives::{ConsensusEngineHandle, SetHeadHandle};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
 };
 use reth_provider::BlockReader;
+use reth_revm::state_changes::PendingStateChanges;
 use reth_transaction_pool::TransactionPool;
 use std::{
     collections::VecDeque,
     future::Future,
     pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
-use tokio::time::Interval;
+use tokio::{
+    sync::{mpsc, oneshot, watch},
+    time::Interval,
+};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::error;
 
@@ -43,6 +48,8 @@ pub enum MiningMode<Pool: TransactionPool + Unpin> {
     },
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode blocks are only built on request through a [`LocalMinerHandle`].
+    Manual,
 }
 
 impl<Pool: TransactionPool + Unpin> MiningMode<Pool> {
@@ -91,10 +98,237 @@ impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
                 }
                 Poll::Pending
             }
+            Self::Manual => Poll::Pending,
         }
     }
 }
 
+/// Commands sent to a [`LocalMiner`] through a [`LocalMinerHandle`].
+#[derive(Debug)]
+enum MinerCommand {
+    /// Mines the given number of blocks, optionally with the given number of seconds between
+    /// their timestamps.
+    Mine { blocks: u64, interval: Option<u64>, tx: oneshot::Sender<eyre::Result<Vec<B256>>> },
+    /// Returns whether blocks are mined on new transactions.
+    GetAutomine(oneshot::Sender<bool>),
+    /// Enables or disables mining blocks on new transactions.
+    SetAutomine(bool),
+    /// Mines blocks at the given interval, or only on request if `None`.
+    SetIntervalMining(Option<Duration>),
+    /// Resets the head of the chain to the given block.
+    Revert { block_number: BlockNumber, timestamp: u64, tx: oneshot::Sender<eyre::Result<()>> },
+}
+
+/// Timestamp settings for the blocks mined by a [`LocalMiner`].
+#[derive(Debug, Default)]
+struct BlockTimestamps {
+    /// Offset in seconds added to the current time.
+    offset: i64,
+    /// Timestamp of the next block.
+    next: Option<u64>,
+    /// Fixed number of seconds between the timestamps of consecutive blocks.
+    interval: Option<u64>,
+}
+
+/// How long the miner waits for the engine to insert an executed block built by the payload
+/// builder.
+const EXECUTED_BLOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of executed blocks inserted by the engine that the miner keeps track of.
+const MAX_INSERTED_BLOCKS: usize = 64;
+
+/// Handle to control a [`LocalMiner`], e.g. from the `anvil` RPC namespace.
+///
+/// The handle also holds the [`PendingStateChanges`] applied to the next mined block, which the
+/// payload builder must be configured with. They are cleared once a block has been mined.
+///
+/// Blocks built with state changes can't be re-executed, so the engine must insert the executed
+/// block built by the payload builder before it receives the block as a new payload. The engine
+/// reports inserted blocks with [`LocalMinerHandle::on_executed_block_inserted`], which the miner
+/// waits for. If the engine doesn't insert the executed block, e.g. because the block is outdated,
+/// mining fails with an error instead of sending a block that would be re-executed.
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    /// Sender for commands to the miner.
+    to_miner: mpsc::UnboundedSender<MinerCommand>,
+    /// Receiver for commands, taken by the miner the handle is attached to.
+    from_handle: Arc<Mutex<Option<mpsc::UnboundedReceiver<MinerCommand>>>>,
+    /// Timestamp settings for the next blocks.
+    timestamps: Arc<Mutex<BlockTimestamps>>,
+    /// State changes applied to the next mined block.
+    state_changes: PendingStateChanges,
+    /// Hashes of the last executed blocks inserted by the engine.
+    inserted_blocks: Arc<watch::Sender<VecDeque<B256>>>,
+}
+
+impl Default for LocalMinerHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalMinerHandle {
+    /// Creates a new handle, to be attached to a miner with [`LocalMiner::with_handle`].
+    pub fn new() -> Self {
+        let (to_miner, from_handle) = mpsc::unbounded_channel();
+        Self {
+            to_miner,
+            from_handle: Arc::new(Mutex::new(Some(from_handle))),
+            timestamps: Default::default(),
+            state_changes: Default::default(),
+            inserted_blocks: Arc::new(watch::Sender::new(VecDeque::new())),
+        }
+    }
+
+    /// Returns the state changes applied to the next mined block.
+    pub const fn state_changes(&self) -> &PendingStateChanges {
+        &self.state_changes
+    }
+
+    fn timestamps(&self) -> MutexGuard<'_, BlockTimestamps> {
+        self.timestamps.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn send(&self, command: MinerCommand) -> eyre::Result<()> {
+        // the receiver is only taken once the handle is attached to a miner
+        if self.from_handle.lock().unwrap_or_else(PoisonError::into_inner).is_some() {
+            eyre::bail!("local miner is not running")
+        }
+        self.to_miner.send(command).map_err(|_| eyre::eyre!("local miner is not running"))
+    }
+
+    /// Notifies the miner that the engine inserted the executed block with the given hash.
+    pub fn on_executed_block_inserted(&self, block_hash: B256) {
+        self.inserted_blocks.send_modify(|inserted| {
+            inserted.push_back(block_hash);
+            if inserted.len() > MAX_INSERTED_BLOCKS {
+                inserted.pop_front();
+            }
+        });
+    }
+
+    /// Waits until the engine inserted the executed block with the given hash.
+    ///
+    /// Returns an error if the block isn't inserted in time, it can't be sent as a new payload
+    /// because it can't be re-executed.
+    async fn wait_for_executed_block(&self, block_hash: B256) -> eyre::Result<()> {
+        let mut inserted_blocks = self.inserted_blocks.subscribe();
+        tokio::time::timeout(
+            EXECUTED_BLOCK_TIMEOUT,
+            inserted_blocks.wait_for(|inserted| inserted.contains(&block_hash)),
+        )
+        .await
+        .map_err(|_| {
+            eyre::eyre!(
+                "block {block_hash} built with state changes was not inserted by the engine and \
+                 can't be re-executed"
+            )
+        })??;
+        Ok(())
+    }
+
+    /// Mines the given number of blocks, optionally with the given number of seconds between their
+    /// timestamps, regardless of the mining mode.
+    ///
+    /// Returns the hashes of the mined blocks, once they are canonical.
+    pub async fn mine(&self, blocks: u64, interval: Option<u64>) -> eyre::Result<Vec<B256>> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::Mine { blocks, interval, tx })?;
+        rx.await?
+    }
+
+    /// Returns whether blocks are mined as soon as transactions reach the pool.
+    pub async fn automine(&self) -> eyre::Result<bool> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::GetAutomine(tx))?;
+        Ok(rx.await?)
+    }
+
+    /// Enables or disables mining blocks as soon as transactions reach the pool.
+    ///
+    /// Once disabled, blocks are only mined on request.
+    pub fn set_automine(&self, enabled: bool) -> eyre::Result<()> {
+        self.send(MinerCommand::SetAutomine(enabled))
+    }
+
+    /// Mines blocks at the given interval, or only on request if `None`.
+    pub fn set_interval_mining(&self, interval: Option<Duration>) -> eyre::Result<()> {
+        self.send(MinerCommand::SetIntervalMining(interval))
+    }
+
+    /// Resets the head of the chain to the canonical block with the given number and timestamp,
+    /// the blocks above it are reverted.
+    pub async fn revert(&self, block_number: BlockNumber, timestamp: u64) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::Revert { block_number, timestamp, tx })?;
+        rx.await?
+    }
+
+    /// Sets the timestamp of the next mined block.
+    pub fn set_next_block_timestamp(&self, timestamp: u64) {
+        self.timestamps().next = Some(timestamp);
+    }
+
+    /// Moves the time used for block timestamps forward by the given number of seconds, and
+    /// returns the total offset to the current time.
+    pub fn increase_time(&self, seconds: u64) -> i64 {
+        let mut timestamps = self.timestamps();
+        timestamps.offset = timestamps.offset.saturating_add_unsigned(seconds);
+        timestamps.offset
+    }
+
+    /// Sets the time used for block timestamps to the given timestamp.
+    pub fn set_time(&self, timestamp: u64) {
+        self.timestamps().offset = (timestamp as i64).saturating_sub(unix_timestamp() as i64);
+    }
+
+    /// Resets the time used for block timestamps to the current time, and removes the timestamp of
+    /// the next block and the interval between blocks.
+    pub fn reset_time(&self) {
+        *self.timestamps() = Default::default();
+    }
+
+    /// Sets a fixed number of seconds between the timestamps of consecutive blocks, or removes it
+    /// if `None`.
+    ///
+    /// Returns `true` if an interval was set before.
+    pub fn set_block_timestamp_interval(&self, interval: Option<u64>) -> bool {
+        std::mem::replace(&mut self.timestamps().interval, interval).is_some()
+    }
+
+    /// Returns the timestamp of the next block mined on top of a block with the given timestamp.
+    fn next_block_timestamp(&self, last_timestamp: u64) -> u64 {
+        let timestamps = self.timestamps();
+        if let Some(next) = timestamps.next {
+            return next
+        }
+        if let Some(interval) = timestamps.interval {
+            return last_timestamp.saturating_add(interval)
+        }
+        std::cmp::max(
+            last_timestamp.saturating_add(1),
+            unix_timestamp().saturating_add_signed(timestamps.offset),
+        )
+    }
+
+    /// Consumes the timestamp of the next block once a block with it has been mined, time continues
+    /// from it for the following blocks.
+    fn on_block_mined(&self, timestamp: u64) {
+        let mut timestamps = self.timestamps();
+        if timestamps.next.take().is_some() {
+            timestamps.offset = (timestamp as i64).saturating_sub(unix_timestamp() as i64);
+        }
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("cannot be earlier than UNIX_EPOCH")
+        .as_secs()
+}
+
 /// Local miner advancing the chain
 #[derive(Debug)]
 pub struct LocalMiner<T: PayloadTypes, B, Pool: TransactionPool + Unpin> {
@@ -110,6 +344,14 @@ pub struct LocalMiner<T: PayloadTypes, B, Pool: TransactionPool + Unpin> {
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: VecDeque<B256>,
+    /// Handle controlling the miner, if any.
+    handle: Option<LocalMinerHandle>,
+    /// Handle to unwind the canonical chain when the miner is reverted.
+    set_head: Option<SetHeadHandle>,
+    /// Commands received through the handle.
+    commands: Option<mpsc::UnboundedReceiver<MinerCommand>>,
+    /// The pool used to mine blocks on new transactions once automine is enabled.
+    pool: Option<Pool>,
 }
 
 impl<T, B, Pool> LocalMiner<T, B, Pool>
@@ -136,7 +378,32 @@ where
             payload_builder,
             last_timestamp: latest_header.timestamp(),
             last_block_hashes: VecDeque::from([latest_header.hash()]),
+            handle: None,
+            set_head: None,
+            commands: None,
+            pool: None,
+        }
+    }
+
+    /// Attaches the given handle to the miner, the given pool is used to mine blocks on new
+    /// transactions once automine is enabled through the handle, and the chain is unwound with
+    /// the given set head handle when the miner is reverted.
+    ///
+    /// Only one miner can be controlled by a handle, the handle is ignored if it was already
+    /// attached.
+    pub fn with_handle(
+        mut self,
+        handle: LocalMinerHandle,
+        pool: Pool,
+        set_head: SetHeadHandle,
+    ) -> Self {
+        self.commands = handle.from_handle.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if self.commands.is_some() {
+            self.handle = Some(handle);
+            self.pool = Some(pool);
+            self.set_head = Some(set_head);
         }
+        self
     }
 
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
@@ -156,10 +423,84 @@ where
                         error!(target: "engine::local", "Error updating fork choice: {:?}", e);
                     }
                 }
+                Some(command) = next_command(&mut self.commands) => {
+                    self.on_command(command).await;
+                }
             }
         }
     }
 
+    /// Handles a command received through the handle.
+    async fn on_command(&mut self, command: MinerCommand) {
+        match command {
+            MinerCommand::Mine { blocks, interval, tx } => {
+                let _ = tx.send(self.mine(blocks, interval).await);
+            }
+            MinerCommand::GetAutomine(tx) => {
+                let _ = tx.send(matches!(self.mode, MiningMode::Instant { .. }));
+            }
+            MinerCommand::SetAutomine(enabled) => {
+                if !enabled {
+                    self.mode = MiningMode::Manual;
+                } else if let (Some(pool), false) =
+                    (&self.pool, matches!(self.mode, MiningMode::Instant { .. }))
+                {
+                    self.mode = MiningMode::instant(pool.clone(), None);
+                }
+            }
+            MinerCommand::SetIntervalMining(interval) => {
+                self.mode = interval.map_or(MiningMode::Manual, MiningMode::interval);
+            }
+            MinerCommand::Revert { block_number, timestamp, tx } => {
+                let _ = tx.send(self.revert(block_number, timestamp).await);
+            }
+        }
+    }
+
+    /// Mines the given number of blocks, optionally with the given number of seconds between their
+    /// timestamps, and returns their hashes.
+    async fn mine(&mut self, blocks: u64, interval: Option<u64>) -> eyre::Result<Vec<B256>> {
+        let mut hashes = Vec::new();
+        for i in 0..blocks {
+            if let (Some(handle), Some(interval)) = (&self.handle, interval) &&
+                i > 0
+            {
+                handle.set_next_block_timestamp(self.last_timestamp.saturating_add(interval));
+            }
+            hashes.push(self.advance().await?);
+        }
+        // the mined blocks are canonical once the command returns
+        self.update_forkchoice_state().await?;
+        Ok(hashes)
+    }
+
+    /// Resets the head of the chain to the given canonical block.
+    ///
+    /// Reverted blocks that are already persisted are unwound by the pipeline, this waits until
+    /// the engine accepts the new head again.
+    async fn revert(&mut self, block_number: BlockNumber, timestamp: u64) -> eyre::Result<()> {
+        let set_head = self.set_head.as_ref().ok_or_eyre("Reverting is not supported")?;
+        let head = set_head.set_head(block_number).await?;
+
+        self.last_timestamp = timestamp;
+        self.last_block_hashes = VecDeque::from([head.hash]);
+
+        let state = self.forkchoice_state();
+        loop {
+            let res = self
+                .to_engine
+                .fork_choice_updated(state, None, EngineApiMessageVersion::default())
+                .await?;
+            if res.is_valid() {
+                return Ok(())
+            }
+            if !res.is_syncing() {
+                eyre::bail!("Invalid fork choice update {state:?}: {res:?}")
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -192,14 +533,16 @@ where
 
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp.saturating_add(1),
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+    ///
+    /// Returns the hash of the new block.
+    async fn advance(&mut self) -> eyre::Result<B256> {
+        let timestamp = match &self.handle {
+            Some(handle) => handle.next_block_timestamp(self.last_timestamp),
+            None => std::cmp::max(self.last_timestamp.saturating_add(1), unix_timestamp()),
+        };
+        // the state changes queued until now are included in the block
+        let state_changes_version =
+            self.handle.as_ref().map(|handle| handle.state_changes().version());
 
         let res = self
             .to_engine
@@ -224,6 +567,14 @@ where
 
         let block = payload.block();
 
+        // blocks built with state changes can't be re-executed, the engine must insert the executed
+        // block before it receives the new payload
+        if let Some(handle) = &self.handle &&
+            payload.executed_block().is_some()
+        {
+            handle.wait_for_executed_block(block.hash()).await?;
+        }
+
         let payload = T::block_to_payload(payload.block().clone());
         let res = self.to_engine.new_payload(payload).await?;
 
@@ -238,6 +589,78 @@ where
             self.last_block_hashes.pop_front();
         }
 
-        Ok(())
+        if let (Some(handle), Some(version)) = (&self.handle, state_changes_version) {
+            handle.on_block_mined(timestamp);
+            handle.state_changes().clear_up_to(version);
+        }
+
+        Ok(block.hash())
+    }
+}
+
+/// Returns the next command received through the handle, never resolves without a handle.
+async fn next_command(
+    commands: &mut Option<mpsc::UnboundedReceiver<MinerCommand>>,
+) -> Option<MinerCommand> {
+    match commands {
+        Some(commands) => commands.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_block_timestamp() {
+        let handle = LocalMinerHandle::new();
+
+        // time continues from the last block if it's ahead of the current time
+        let last_timestamp = unix_timestamp() + 1_000;
+        assert_eq!(handle.next_block_timestamp(last_timestamp), last_timestamp + 1);
+
+        // a fixed interval between blocks
+        assert!(!handle.set_block_timestamp_interval(Some(12)));
+        assert_eq!(handle.next_block_timestamp(100), 112);
+        assert!(handle.set_block_timestamp_interval(None));
+
+        // the timestamp of the next block takes precedence, and time continues from it
+        handle.set_next_block_timestamp(last_timestamp + 100);
+        assert_eq!(handle.next_block_timestamp(last_timestamp), last_timestamp + 100);
+        handle.on_block_mined(last_timestamp + 100);
+        assert!(handle.next_block_timestamp(0) >= last_timestamp + 100);
+
+        handle.reset_time();
+        assert!(handle.next_block_timestamp(0) < last_timestamp);
+
+        // moving the time forward
+        assert_eq!(handle.increase_time(1_000), 1_000);
+        assert!(handle.next_block_timestamp(0) >= last_timestamp);
+    }
+
+    #[tokio::test]
+    async fn wait_for_executed_block() {
+        let handle = LocalMinerHandle::new();
+        let block_hash = B256::with_last_byte(1);
+
+        let waiter = handle.clone();
+        let wait = tokio::spawn(async move { waiter.wait_for_executed_block(block_hash).await });
+        tokio::task::yield_now().await;
+
+        // the block is found even if other blocks are inserted before the waiter is polled
+        handle.on_executed_block_inserted(block_hash);
+        handle.on_executed_block_inserted(B256::with_last_byte(2));
+        wait.await.unwrap().unwrap();
+
+        // blocks inserted before waiting are found right away
+        handle.wait_for_executed_block(block_hash).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn handle_without_miner() {
+        let handle = LocalMinerHandle::new();
+        assert!(handle.mine(1, None).await.is_err());
+        assert!(handle.set_automine(false).is_err());
     }
 }
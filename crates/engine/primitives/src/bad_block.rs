@@ -0,0 +1,200 @@
+//! On-disk store of blocks rejected by the consensus engine.
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, Bytes, B256};
+use parking_lot::Mutex;
+use reth_fs_util::{self as fs, FsPathError};
+use reth_primitives_traits::{Block, SealedBlock};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+};
+
+/// A block that was rejected by the consensus engine.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The RLP-encoded block.
+    pub rlp: Bytes,
+    /// Why the block was rejected.
+    pub reason: String,
+}
+
+impl BadBlock {
+    /// Creates a new [`BadBlock`] from the rejected block and the reason it was rejected.
+    pub fn new<B: Block>(block: &SealedBlock<B>, reason: String) -> Self {
+        Self {
+            hash: block.hash(),
+            number: block.number(),
+            rlp: alloy_rlp::encode(block).into(),
+            reason,
+        }
+    }
+}
+
+/// A bounded, on-disk store of the most recent [`BadBlock`]s.
+///
+/// Each block is kept as a JSON file named after its hash. Like geth, only the
+/// [`max_blocks`](Self::max_blocks) blocks with the highest numbers are kept.
+///
+/// All methods do blocking file system I/O.
+#[derive(Clone, Debug)]
+pub struct BadBlockStore {
+    /// The directory the blocks are stored in.
+    dir: PathBuf,
+    /// The maximum number of blocks to keep.
+    max_blocks: usize,
+    /// The numbers and hashes of the stored blocks in eviction order, read from the directory on
+    /// the first insert.
+    index: Arc<Mutex<Option<BadBlockIndex>>>,
+}
+
+/// The numbers and hashes of stored bad blocks, oldest first.
+type BadBlockIndex = Vec<(BlockNumber, B256)>;
+
+impl BadBlockStore {
+    /// The default maximum number of blocks to keep, same as geth.
+    pub const DEFAULT_MAX_BLOCKS: usize = 10;
+
+    /// Creates a new store in the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), max_blocks: Self::DEFAULT_MAX_BLOCKS, index: Default::default() }
+    }
+
+    /// Sets the maximum number of blocks to keep.
+    pub const fn with_max_blocks(mut self, max_blocks: usize) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Returns the directory the blocks are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the maximum number of blocks to keep.
+    pub const fn max_blocks(&self) -> usize {
+        self.max_blocks
+    }
+
+    /// Stores the block, evicting the blocks with the lowest numbers if the store is full.
+    pub fn insert(&self, bad_block: &BadBlock) -> Result<(), FsPathError> {
+        let mut index = self.index.lock();
+        if index.is_none() {
+            let blocks = self.blocks()?;
+            *index = Some(blocks.into_iter().map(|block| (block.number, block.hash)).collect());
+        }
+        let index = index.as_mut().expect("index is loaded");
+
+        fs::create_dir_all(&self.dir)?;
+        fs::atomic_write_file(&self.block_path(bad_block.hash), |file| {
+            serde_json::to_writer(file, bad_block)
+        })?;
+
+        let key = (bad_block.number, bad_block.hash);
+        if let Err(position) = index.binary_search_by_key(&sort_key(key), |entry| sort_key(*entry))
+        {
+            index.insert(position, key);
+        }
+        for (_, hash) in index.drain(self.max_blocks.min(index.len())..) {
+            fs::remove_file(self.block_path(hash))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the block with the given hash, if it's stored.
+    pub fn get(&self, hash: B256) -> Result<Option<BadBlock>, FsPathError> {
+        let path = self.block_path(hash);
+        if !path.exists() {
+            return Ok(None)
+        }
+        fs::read_json_file(&path).map(Some)
+    }
+
+    /// Returns all stored blocks, ordered by descending block number.
+    ///
+    /// Files that can't be read or decoded are skipped.
+    pub fn blocks(&self) -> Result<Vec<BadBlock>, FsPathError> {
+        if !self.dir.exists() {
+            return Ok(Vec::new())
+        }
+
+        let mut blocks = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry.map_err(|err| FsPathError::read_dir(err, &self.dir))?.path();
+            if path.extension().is_some_and(|extension| extension == "json") &&
+                let Ok(block) = fs::read_json_file::<BadBlock>(&path)
+            {
+                blocks.push(block);
+            }
+        }
+        blocks.sort_unstable_by_key(|block| sort_key((block.number, block.hash)));
+
+        Ok(blocks)
+    }
+
+    /// Returns the path of the file the block with the given hash is stored in.
+    fn block_path(&self, hash: B256) -> PathBuf {
+        self.dir.join(format!("{hash}.json"))
+    }
+}
+
+/// Orders blocks by descending number, then by hash.
+const fn sort_key((number, hash): (BlockNumber, B256)) -> (Reverse<BlockNumber>, B256) {
+    (Reverse(number), hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bad_block(number: BlockNumber) -> BadBlock {
+        BadBlock {
+            hash: B256::with_last_byte(number as u8),
+            number,
+            rlp: Bytes::from_static(&[0xc0]),
+            reason: format!("bad block {number}"),
+        }
+    }
+
+    #[test]
+    fn insert_evicts_lowest_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BadBlockStore::new(dir.path().join("bad_blocks")).with_max_blocks(2);
+        assert_eq!(store.blocks().unwrap(), Vec::new());
+
+        for number in [2, 3, 1] {
+            store.insert(&bad_block(number)).unwrap();
+        }
+        assert_eq!(store.blocks().unwrap(), vec![bad_block(3), bad_block(2)]);
+        assert_eq!(store.get(bad_block(2).hash).unwrap(), Some(bad_block(2)));
+        assert_eq!(store.get(bad_block(1).hash).unwrap(), None);
+
+        store.insert(&bad_block(4)).unwrap();
+        assert_eq!(store.blocks().unwrap(), vec![bad_block(4), bad_block(3)]);
+
+        // inserting a stored block again doesn't evict anything
+        store.insert(&bad_block(3)).unwrap();
+        assert_eq!(store.blocks().unwrap(), vec![bad_block(4), bad_block(3)]);
+    }
+
+    #[test]
+    fn insert_skips_unreadable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BadBlockStore::new(dir.path()).with_max_blocks(2);
+        std::fs::write(dir.path().join("garbage.json"), "not a block").unwrap();
+        store.insert(&bad_block(1)).unwrap();
+        store.insert(&bad_block(2)).unwrap();
+
+        // a new instance picks up the stored blocks
+        let store = BadBlockStore::new(dir.path()).with_max_blocks(2);
+        store.insert(&bad_block(3)).unwrap();
+        assert_eq!(store.blocks().unwrap(), vec![bad_block(3), bad_block(2)]);
+    }
+}
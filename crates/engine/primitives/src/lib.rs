@@ -25,6 +25,11 @@ use serde::{de::DeserializeOwned, Serialize};
 pub use reth_evm::{ConfigureEngineEvm, ExecutableTxIterator};
 pub use reth_payload_primitives::ExecutionPayload;
 
+#[cfg(feature = "std")]
+mod bad_block;
+#[cfg(feature = "std")]
+pub use bad_block::{BadBlock, BadBlockStore};
+
 mod error;
 pub use error::*;
 
@@ -1,6 +1,8 @@
 use crate::{
     error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
 };
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::{
     ForkChoiceUpdateResult, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId,
     PayloadStatus, PayloadStatusEnum,
@@ -12,7 +14,7 @@ use core::{
     task::{ready, Context, Poll},
 };
 use futures::{future::Either, FutureExt, TryFutureExt};
-use reth_errors::RethResult;
+use reth_errors::{RethError, RethResult};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::{EngineApiMessageVersion, PayloadTypes};
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
@@ -258,3 +260,38 @@ where
         rx
     }
 }
+
+/// A request to unwind the canonical chain to one of its blocks.
+#[derive(Debug)]
+pub struct SetHeadRequest {
+    /// The number of the canonical block that becomes the new head.
+    pub block_number: BlockNumber,
+    /// The sender for returning the new head.
+    pub tx: oneshot::Sender<RethResult<BlockNumHash>>,
+}
+
+/// A cloneable sender type that can be used to unwind the canonical chain of a running node.
+///
+/// This backs `debug_setHead`.
+#[derive(Debug, Clone)]
+pub struct SetHeadHandle {
+    to_engine: UnboundedSender<SetHeadRequest>,
+}
+
+impl SetHeadHandle {
+    /// Creates a new set head handle.
+    pub const fn new(to_engine: UnboundedSender<SetHeadRequest>) -> Self {
+        Self { to_engine }
+    }
+
+    /// Unwinds the canonical chain to the canonical block with the given number and waits for the
+    /// new head.
+    ///
+    /// This returns once the in-memory state is unwound, reverted blocks that are already persisted
+    /// are unwound by the pipeline in the background.
+    pub async fn set_head(&self, block_number: BlockNumber) -> RethResult<BlockNumHash> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(SetHeadRequest { block_number, tx });
+        rx.await.map_err(|_| RethError::msg("consensus engine task stopped"))?
+    }
+}
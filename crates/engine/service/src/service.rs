@@ -7,7 +7,7 @@ use reth_engine_tree::{
     backfill::PipelineSync,
     download::BasicBlockDownloader,
     engine::{EngineApiKind, EngineApiRequest, EngineApiRequestHandler, EngineHandler},
-    persistence::PersistenceHandle,
+    persistence::{PersistenceHandle, PersistenceStage},
     tree::{EngineApiTreeHandler, EngineValidator, TreeConfig},
 };
 pub use reth_engine_tree::{
@@ -79,6 +79,7 @@ where
         provider: ProviderFactory<N>,
         blockchain_db: BlockchainProvider<N>,
         pruner: PrunerWithFactory<ProviderFactory<N>>,
+        persistence_stages: Vec<PersistenceStage<N>>,
         payload_builder: PayloadBuilderHandle<N::Payload>,
         payload_validator: V,
         tree_config: TreeConfig,
@@ -94,8 +95,12 @@ where
 
         let downloader = BasicBlockDownloader::new(client, consensus.clone());
 
-        let persistence_handle =
-            PersistenceHandle::<EthPrimitives>::spawn_service(provider, pruner, sync_metrics_tx);
+        let persistence_handle = PersistenceHandle::<EthPrimitives>::spawn_service(
+            provider,
+            pruner,
+            persistence_stages,
+            sync_metrics_tx,
+        );
 
         let canonical_in_memory_state = blockchain_db.canonical_in_memory_state();
 
@@ -209,6 +214,7 @@ mod tests {
             provider_factory,
             blockchain_db,
             pruner,
+            Vec::new(),
             PayloadBuilderHandle::new(tx),
             engine_validator,
             TreeConfig::default(),
@@ -8,7 +8,7 @@ use crate::{
 use alloy_primitives::B256;
 use futures::{Stream, StreamExt};
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
-use reth_engine_primitives::{BeaconEngineMessage, ConsensusEngineEvent};
+use reth_engine_primitives::{BeaconEngineMessage, ConsensusEngineEvent, SetHeadRequest};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_payload_primitives::PayloadTypes;
 use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock};
@@ -247,6 +247,8 @@ pub enum EngineApiRequest<T: PayloadTypes, N: NodePrimitives> {
     Beacon(BeaconEngineMessage<T>),
     /// Request to insert an already executed block, e.g. via payload building.
     InsertExecutedBlock(ExecutedBlockWithTrieUpdates<N>),
+    /// Request to unwind the canonical chain, e.g. via `debug_setHead`.
+    SetHead(SetHeadRequest),
 }
 
 impl<T: PayloadTypes, N: NodePrimitives> Display for EngineApiRequest<T, N> {
@@ -256,6 +258,7 @@ impl<T: PayloadTypes, N: NodePrimitives> Display for EngineApiRequest<T, N> {
             Self::InsertExecutedBlock(block) => {
                 write!(f, "InsertExecutedBlock({:?})", block.recovered_block().num_hash())
             }
+            Self::SetHead(request) => write!(f, "SetHead({})", request.block_number),
         }
     }
 }
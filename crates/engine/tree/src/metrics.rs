@@ -1,5 +1,5 @@
 use reth_metrics::{
-    metrics::{Gauge, Histogram},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 
@@ -21,4 +21,8 @@ pub(crate) struct PersistenceMetrics {
     pub(crate) save_blocks_duration_seconds: Histogram,
     /// How long it took for blocks to be pruned
     pub(crate) prune_before_duration_seconds: Histogram,
+    /// How long it took for the stages to catch up with the saved blocks
+    pub(crate) run_stages_duration_seconds: Histogram,
+    /// How many times a stage failed to catch up with the saved blocks
+    pub(crate) failed_stage_runs: Counter,
 }
@@ -1,24 +1,43 @@
 use crate::metrics::PersistenceMetrics;
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
+use parking_lot::Mutex;
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_errors::ProviderError;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::ProviderNodeTypes, BlockExecutionWriter, BlockHashReader, ChainStateBlockWriter,
-    DBProvider, DatabaseProviderFactory, ProviderFactory,
+    providers::ProviderNodeTypes, BlockExecutionWriter, BlockHashReader, BlockNumReader,
+    ChainStateBlockWriter, DBProvider, DatabaseProviderFactory, ProviderFactory,
+    StageCheckpointReader, StageCheckpointWriter,
 };
 use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
-use reth_stages_api::{MetricEvent, MetricEventsSender};
+use reth_stages_api::{
+    ExecInput, MetricEvent, MetricEventsSender, Stage, StageError, StageId, UnwindInput,
+};
 use std::{
-    sync::mpsc::{Receiver, SendError, Sender},
+    sync::{
+        mpsc::{Receiver, SendError, Sender},
+        Arc,
+    },
     time::Instant,
 };
 use thiserror::Error;
 use tokio::sync::oneshot;
 use tracing::{debug, error};
 
+/// The maximum number of blocks a [`PersistenceStage`] executes in a single transaction.
+///
+/// Saving blocks waits for the write transaction of a running batch, so batches are kept small.
+const STAGE_BATCH_BLOCKS: u64 = 10;
+
+/// A stage that indexes persisted blocks.
+///
+/// The stages follow the committed blocks on their own thread, see [`StageRunner`]. The
+/// [`PersistenceService`] unwinds them before blocks are removed.
+pub type PersistenceStage<N> =
+    Box<dyn Stage<<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW>>;
+
 /// Writes parts of reth's in memory tree state to the database and static files.
 ///
 /// This is meant to be a spawned service that listens for various incoming persistence operations,
@@ -26,7 +45,7 @@ use tracing::{debug, error};
 ///
 /// This should be spawned in its own thread with [`std::thread::spawn`], since this performs
 /// blocking I/O operations in an endless loop.
-#[derive(Debug)]
+#[derive(derive_more::Debug)]
 pub struct PersistenceService<N>
 where
     N: ProviderNodeTypes,
@@ -37,6 +56,11 @@ where
     incoming: Receiver<PersistenceAction<N::Primitives>>,
     /// The pruner
     pruner: PrunerWithFactory<ProviderFactory<N>>,
+    /// Stages kept up to date with the persisted blocks, unwound before blocks are removed
+    #[debug(skip)]
+    stages: Arc<Mutex<Vec<PersistenceStage<N>>>>,
+    /// Notifies the [`StageRunner`] of committed blocks
+    stages_tx: Sender<u64>,
     /// metrics
     metrics: PersistenceMetrics,
     /// Sender for sync metrics - we only submit sync metrics for persisted blocks
@@ -52,9 +76,19 @@ where
         provider: ProviderFactory<N>,
         incoming: Receiver<PersistenceAction<N::Primitives>>,
         pruner: PrunerWithFactory<ProviderFactory<N>>,
+        stages: Arc<Mutex<Vec<PersistenceStage<N>>>>,
+        stages_tx: Sender<u64>,
         sync_metrics_tx: MetricEventsSender,
     ) -> Self {
-        Self { provider, incoming, pruner, metrics: PersistenceMetrics::default(), sync_metrics_tx }
+        Self {
+            provider,
+            incoming,
+            pruner,
+            stages,
+            stages_tx,
+            metrics: PersistenceMetrics::default(),
+            sync_metrics_tx,
+        }
     }
 
     /// Prunes block data before the given block number according to the configured prune
@@ -100,6 +134,10 @@ where
                             .sync_metrics_tx
                             .send(MetricEvent::SyncHeight { height: block_number });
 
+                        // Block data is only readable from static files once committed, so the
+                        // stages follow the committed blocks in separate transactions.
+                        let _ = self.stages_tx.send(block_number);
+
                         if self.pruner.is_pruning_needed(block_number) {
                             // We log `PrunerOutput` inside the `Pruner`
                             let _ = self.prune_before(block_number)?;
@@ -127,9 +165,32 @@ where
     ) -> Result<Option<BlockNumHash>, PersistenceError> {
         debug!(target: "engine::persistence", ?new_tip_num, "Removing blocks");
         let start_time = Instant::now();
+        // The stages are locked before the write transaction is opened, like the stage runner
+        // does, so it can't run a batch in between.
+        let mut stages = self.stages.lock();
         let provider_rw = self.provider.database_provider_rw()?;
 
         let new_tip_hash = provider_rw.block_hash(new_tip_num)?;
+        // The stages may need the removed blocks to unwind, so they go first. Unwinding only
+        // prunes the index data of the removed blocks.
+        for stage in stages.iter_mut().rev() {
+            let stage_id = stage.id();
+            let Some(mut checkpoint) = provider_rw.get_stage_checkpoint(stage_id)? else {
+                continue
+            };
+            while checkpoint.block_number > new_tip_num {
+                let input = UnwindInput { checkpoint, unwind_to: new_tip_num, bad_block: None };
+                let unwound = stage.unwind(&provider_rw, input)?.checkpoint;
+                if unwound.block_number >= checkpoint.block_number {
+                    return Err(PersistenceError::StageUnwindNoProgress {
+                        stage_id,
+                        block_number: checkpoint.block_number,
+                    })
+                }
+                checkpoint = unwound;
+                provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+            }
+        }
         provider_rw.remove_block_and_execution_above(new_tip_num)?;
         provider_rw.commit()?;
 
@@ -151,7 +212,6 @@ where
 
         if last_block_hash_num.is_some() {
             let provider_rw = self.provider.database_provider_rw()?;
-
             provider_rw.save_blocks(blocks)?;
             provider_rw.commit()?;
         }
@@ -160,6 +220,120 @@ where
     }
 }
 
+/// Keeps the [`PersistenceStage`]s up to date with the blocks committed by the
+/// [`PersistenceService`].
+///
+/// Stages like the trace index re-execute blocks, so they run on their own thread instead of
+/// holding up the persistence service. The stages are only locked for a single batch, which lets
+/// the persistence service unwind them in between.
+#[derive(derive_more::Debug)]
+struct StageRunner<N>
+where
+    N: ProviderNodeTypes,
+{
+    /// The provider factory to use
+    provider: ProviderFactory<N>,
+    /// Receives the number of the last committed block
+    persisted: Receiver<u64>,
+    /// Stages kept up to date with the persisted blocks
+    #[debug(skip)]
+    stages: Arc<Mutex<Vec<PersistenceStage<N>>>>,
+    /// metrics
+    metrics: PersistenceMetrics,
+    /// Sender for sync metrics
+    sync_metrics_tx: MetricEventsSender,
+}
+
+impl<N> StageRunner<N>
+where
+    N: ProviderNodeTypes,
+{
+    /// Runs the stages whenever blocks are committed, until the persistence service is dropped.
+    fn run(self) {
+        while let Ok(last_number) = self.persisted.recv() {
+            // only the latest committed block is of interest
+            let last_number = self.persisted.try_iter().last().unwrap_or(last_number);
+            self.run_stages(last_number);
+        }
+    }
+
+    /// Advances the stages towards the last persisted block.
+    ///
+    /// Only stages that have run before are advanced, starting from their checkpoint. Every
+    /// committed batch is reported as a [`MetricEvent::StageCheckpoint`]. A stage that fails is
+    /// counted and logged, and retried once the next blocks are persisted.
+    fn run_stages(&self, last_number: u64) {
+        let start_time = Instant::now();
+        let num_stages = self.stages.lock().len();
+        for index in 0..num_stages {
+            loop {
+                let mut stages = self.stages.lock();
+                let stage = &mut stages[index];
+                match self.run_stage_batch(stage, last_number) {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    Err(err) => {
+                        self.metrics.failed_stage_runs.increment(1);
+                        error!(
+                            target: "engine::persistence",
+                            stage = %stage.id(),
+                            %err,
+                            "Failed to run stage"
+                        );
+                        break
+                    }
+                }
+            }
+        }
+        self.metrics.run_stages_duration_seconds.record(start_time.elapsed());
+    }
+
+    /// Advances the stage by at most [`STAGE_BATCH_BLOCKS`] towards the given block, and returns
+    /// whether it may have blocks left to execute.
+    ///
+    /// The target is capped at the last block on disk, in case blocks were removed since the
+    /// given block was committed.
+    fn run_stage_batch(
+        &self,
+        stage: &mut PersistenceStage<N>,
+        last_number: u64,
+    ) -> Result<bool, PersistenceError> {
+        let stage_id = stage.id();
+        let provider_rw = self.provider.database_provider_rw()?;
+        let Some(checkpoint) = provider_rw.get_stage_checkpoint(stage_id)? else {
+            return Ok(false)
+        };
+        let end = last_number.min(provider_rw.last_block_number()?);
+        if checkpoint.block_number >= end {
+            return Ok(false)
+        }
+
+        let batch_start = Instant::now();
+        let target = end.min(checkpoint.block_number.saturating_add(STAGE_BATCH_BLOCKS));
+        let input = ExecInput { target: Some(target), checkpoint: Some(checkpoint) };
+        let output = stage.execute(&provider_rw, input)?;
+        provider_rw.save_stage_checkpoint(stage_id, output.checkpoint)?;
+        provider_rw.commit()?;
+        let _ = self.sync_metrics_tx.send(MetricEvent::StageCheckpoint {
+            stage_id,
+            checkpoint: output.checkpoint,
+            max_block_number: Some(end),
+            elapsed: batch_start.elapsed(),
+        });
+
+        if output.checkpoint.block_number <= checkpoint.block_number {
+            debug!(
+                target: "engine::persistence",
+                stage = %stage_id,
+                ?checkpoint,
+                "Stage made no progress"
+            );
+            return Ok(false)
+        }
+        Ok(true)
+    }
+}
+
 /// One of the errors that can happen when using the persistence service.
 #[derive(Debug, Error)]
 pub enum PersistenceError {
@@ -170,6 +344,19 @@ pub enum PersistenceError {
     /// A provider error
     #[error(transparent)]
     ProviderError(#[from] ProviderError),
+
+    /// A stage error
+    #[error(transparent)]
+    StageError(#[from] StageError),
+
+    /// A stage didn't move its checkpoint back when unwinding
+    #[error("stage {stage_id} made no progress unwinding from block {block_number}")]
+    StageUnwindNoProgress {
+        /// The stage that was unwound
+        stage_id: StageId,
+        /// The checkpoint of the stage
+        block_number: u64,
+    },
 }
 
 /// A signal to the persistence service that part of the tree state can be persisted.
@@ -209,9 +396,12 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
     }
 
     /// Create a new [`PersistenceHandle`], and spawn the persistence service.
+    ///
+    /// If there are any stages, they are run by a [`StageRunner`] spawned on its own thread.
     pub fn spawn_service<N>(
         provider_factory: ProviderFactory<N>,
         pruner: PrunerWithFactory<ProviderFactory<N>>,
+        stages: Vec<PersistenceStage<N>>,
         sync_metrics_tx: MetricEventsSender,
     ) -> PersistenceHandle<N::Primitives>
     where
@@ -223,9 +413,34 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
         // construct persistence handle
         let persistence_handle = PersistenceHandle::new(db_service_tx);
 
+        let (stages_tx, stages_rx) = std::sync::mpsc::channel();
+        let has_stages = !stages.is_empty();
+        let stages = Arc::new(Mutex::new(stages));
+
+        // spawn the stage runner, without stages the notifications are dropped
+        if has_stages {
+            let stage_runner = StageRunner {
+                provider: provider_factory.clone(),
+                persisted: stages_rx,
+                stages: stages.clone(),
+                metrics: PersistenceMetrics::default(),
+                sync_metrics_tx: sync_metrics_tx.clone(),
+            };
+            std::thread::Builder::new()
+                .name("Persistence Stages".to_string())
+                .spawn(|| stage_runner.run())
+                .unwrap();
+        }
+
         // spawn the persistence service
-        let db_service =
-            PersistenceService::new(provider_factory, db_service_rx, pruner, sync_metrics_tx);
+        let db_service = PersistenceService::new(
+            provider_factory,
+            db_service_rx,
+            pruner,
+            stages,
+            stages_tx,
+            sync_metrics_tx,
+        );
         std::thread::Builder::new()
             .name("Persistence Service".to_string())
             .spawn(|| {
@@ -296,16 +511,87 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::B256;
+    use alloy_consensus::Transaction;
+    use alloy_primitives::{Address, BlockNumber, Bytes, Log, TxKind, B256};
     use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_ethereum_primitives::{Receipt, TxType};
     use reth_exex_types::FinishedExExHeight;
-    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_provider::{
+        log_position, log_position_block,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        AddressAppearancesProvider, BlockReader, LogIndexProvider,
+    };
     use reth_prune::Pruner;
+    use reth_stages::stages::{IndexAddressAppearancesStage, IndexLogsStage};
+    use reth_stages_api::{ExecOutput, StageCheckpoint, StageId, UnwindOutput};
+    use std::{collections::BTreeMap, time::Duration};
     use tokio::sync::mpsc::unbounded_channel;
 
+    /// A stage that records the blocks it could read from the provider.
+    #[derive(Debug, Default)]
+    struct BlockRecordingStage {
+        blocks: Arc<Mutex<Vec<BlockNumber>>>,
+    }
+
+    impl<Provider: BlockReader> Stage<Provider> for BlockRecordingStage {
+        fn id(&self) -> StageId {
+            StageId::IndexTraces
+        }
+
+        fn execute(
+            &mut self,
+            provider: &Provider,
+            input: ExecInput,
+        ) -> Result<ExecOutput, StageError> {
+            let range = input.next_block_range();
+            let blocks = provider.recovered_block_range(range)?;
+            self.blocks.lock().extend(blocks.iter().map(|block| block.header().number()));
+            Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
+        fn unwind(
+            &mut self,
+            _provider: &Provider,
+            input: UnwindInput,
+        ) -> Result<UnwindOutput, StageError> {
+            Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+        }
+    }
+
+    /// A stage that keeps its checkpoint when unwound.
+    #[derive(Debug)]
+    struct StuckUnwindStage;
+
+    impl<Provider> Stage<Provider> for StuckUnwindStage {
+        fn id(&self) -> StageId {
+            StageId::IndexTraces
+        }
+
+        fn execute(
+            &mut self,
+            _provider: &Provider,
+            input: ExecInput,
+        ) -> Result<ExecOutput, StageError> {
+            Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
+        fn unwind(
+            &mut self,
+            _provider: &Provider,
+            input: UnwindInput,
+        ) -> Result<UnwindOutput, StageError> {
+            Ok(UnwindOutput { checkpoint: input.checkpoint })
+        }
+    }
+
     fn default_persistence_handle() -> PersistenceHandle<EthPrimitives> {
-        let provider = create_test_provider_factory();
+        persistence_handle(create_test_provider_factory(), Vec::new())
+    }
 
+    fn persistence_handle(
+        provider: ProviderFactory<MockNodeTypesWithDB>,
+        stages: Vec<PersistenceStage<MockNodeTypesWithDB>>,
+    ) -> PersistenceHandle<EthPrimitives> {
         let (_finished_exex_height_tx, finished_exex_height_rx) =
             tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
 
@@ -313,7 +599,26 @@ mod tests {
             Pruner::new_with_factory(provider.clone(), vec![], 5, 0, None, finished_exex_height_rx);
 
         let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
-        PersistenceHandle::<EthPrimitives>::spawn_service(provider, pruner, sync_metrics_tx)
+        PersistenceHandle::<EthPrimitives>::spawn_service(provider, pruner, stages, sync_metrics_tx)
+    }
+
+    /// Waits until the stage runner advanced the stage to the given block.
+    async fn wait_for_stage(
+        provider: &ProviderFactory<MockNodeTypesWithDB>,
+        stage_id: StageId,
+        block_number: BlockNumber,
+    ) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let checkpoint = provider.provider().unwrap().get_stage_checkpoint(stage_id);
+                if checkpoint.unwrap().is_some_and(|c| c.block_number >= block_number) {
+                    return
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("stage didn't reach the block");
     }
 
     #[tokio::test]
@@ -388,4 +693,230 @@ mod tests {
             assert_eq!(last_hash, actual_hash);
         }
     }
+
+    #[tokio::test]
+    async fn test_save_blocks_runs_stages_on_committed_blocks() {
+        reth_tracing::init_test_tracing();
+        let provider = create_test_provider_factory();
+        let stage = BlockRecordingStage::default();
+        let blocks = stage.blocks.clone();
+        let persistence_handle = persistence_handle(provider.clone(), vec![Box::new(stage)]);
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let genesis = test_block_builder.get_executed_blocks(0..1).collect::<Vec<_>>();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(genesis, tx).unwrap();
+        rx.await.unwrap().unwrap();
+        // the stage hasn't run yet, so it's left to the pipeline
+        assert!(blocks.lock().is_empty());
+
+        let provider_rw = provider.database_provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexTraces, StageCheckpoint::new(0)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let saved = test_block_builder.get_executed_blocks(1..5).collect::<Vec<_>>();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(saved, tx).unwrap();
+        rx.await.unwrap().unwrap();
+        wait_for_stage(&provider, StageId::IndexTraces, 4).await;
+
+        assert_eq!(*blocks.lock(), vec![1, 2, 3, 4]);
+        let checkpoint = provider.provider().unwrap().get_stage_checkpoint(StageId::IndexTraces);
+        assert_eq!(checkpoint.unwrap(), Some(StageCheckpoint::new(4)));
+    }
+
+    #[tokio::test]
+    async fn test_save_blocks_catches_up_stages() {
+        reth_tracing::init_test_tracing();
+        let provider = create_test_provider_factory();
+        let stage = BlockRecordingStage::default();
+        let blocks = stage.blocks.clone();
+        let persistence_handle = persistence_handle(provider.clone(), vec![Box::new(stage)]);
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let saved = test_block_builder.get_executed_blocks(0..3).collect::<Vec<_>>();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(saved, tx).unwrap();
+        rx.await.unwrap().unwrap();
+        assert!(blocks.lock().is_empty());
+
+        // the stage is enabled behind the persisted blocks
+        let provider_rw = provider.database_provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexTraces, StageCheckpoint::new(0)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let saved = test_block_builder.get_executed_blocks(3..5).collect::<Vec<_>>();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(saved, tx).unwrap();
+        rx.await.unwrap().unwrap();
+        wait_for_stage(&provider, StageId::IndexTraces, 4).await;
+
+        assert_eq!(*blocks.lock(), vec![1, 2, 3, 4]);
+        let checkpoint = provider.provider().unwrap().get_stage_checkpoint(StageId::IndexTraces);
+        assert_eq!(checkpoint.unwrap(), Some(StageCheckpoint::new(4)));
+    }
+
+    #[tokio::test]
+    async fn test_save_blocks_indexes_address_appearances() {
+        reth_tracing::init_test_tracing();
+        let provider = create_test_provider_factory();
+        let stage = IndexAddressAppearancesStage::default();
+        let persistence_handle = persistence_handle(provider.clone(), vec![Box::new(stage)]);
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let genesis = test_block_builder.get_executed_blocks(0..1).collect::<Vec<_>>();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(genesis, tx).unwrap();
+        rx.await.unwrap().unwrap();
+
+        let provider_rw = provider.database_provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexAddressAppearances, StageCheckpoint::new(0))
+            .unwrap();
+        provider_rw
+            .save_stage_checkpoint_progress(StageId::IndexAddressAppearances, vec![0])
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let saved = test_block_builder.get_executed_blocks(1..5).collect::<Vec<_>>();
+        let mut expected = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        for block in &saved {
+            let block = block.recovered_block();
+            for (sender, tx) in block.transactions_with_sender() {
+                let TxKind::Call(to) = tx.kind() else { unreachable!() };
+                for address in [*sender, to] {
+                    let blocks = expected.entry(address).or_default();
+                    if blocks.last() != Some(&block.header().number()) {
+                        blocks.push(block.header().number());
+                    }
+                }
+            }
+        }
+
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(saved, tx).unwrap();
+        rx.await.unwrap().unwrap();
+        wait_for_stage(&provider, StageId::IndexAddressAppearances, 4).await;
+
+        let provider = provider.provider().unwrap();
+        for (address, blocks) in expected {
+            assert_eq!(provider.address_appearances_after(address, 0, usize::MAX).unwrap(), blocks);
+        }
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::IndexAddressAppearances).unwrap(),
+            Some(StageCheckpoint::new(4))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_blocks_indexes_log_positions() {
+        reth_tracing::init_test_tracing();
+        let provider = create_test_provider_factory();
+        let stage = IndexLogsStage::default();
+        let persistence_handle = persistence_handle(provider.clone(), vec![Box::new(stage)]);
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        // the genesis block can have transactions, which need receipts to be indexed
+        let genesis = test_block_builder
+            .get_executed_blocks(0..1)
+            .map(|mut executed| {
+                let receipts = (0..executed.recovered_block().body().transactions.len())
+                    .map(|_| Receipt {
+                        tx_type: TxType::Legacy,
+                        success: true,
+                        ..Default::default()
+                    })
+                    .collect();
+                let mut execution_output = (*executed.block.execution_output).clone();
+                execution_output.receipts = vec![receipts];
+                executed.block.execution_output = Arc::new(execution_output);
+                executed
+            })
+            .collect::<Vec<_>>();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(genesis, tx).unwrap();
+        rx.await.unwrap().unwrap();
+
+        let provider_rw = provider.database_provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(0)).unwrap();
+        provider_rw.commit().unwrap();
+
+        // Every transaction emits two logs of the same contract, the second one with a topic
+        // unique to its block.
+        let contract = Address::with_last_byte(1);
+        let mut expected = Vec::new();
+        let saved = test_block_builder
+            .get_executed_blocks(1..5)
+            .map(|mut executed| {
+                let number = executed.recovered_block().header().number();
+                let topic = B256::with_last_byte(number as u8);
+                let receipts = (0..executed.recovered_block().body().transactions.len())
+                    .map(|_| Receipt {
+                        tx_type: TxType::Legacy,
+                        success: true,
+                        cumulative_gas_used: 0,
+                        logs: vec![
+                            Log::new_unchecked(contract, vec![], Bytes::new()),
+                            Log::new_unchecked(contract, vec![topic], Bytes::new()),
+                        ],
+                    })
+                    .collect::<Vec<_>>();
+                for log_index in 0..receipts.len() as u64 * 2 {
+                    expected.push(log_position(number, log_index));
+                }
+
+                let mut execution_output = (*executed.block.execution_output).clone();
+                execution_output.receipts = vec![receipts];
+                executed.block.execution_output = Arc::new(execution_output);
+                executed
+            })
+            .collect::<Vec<_>>();
+
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(saved, tx).unwrap();
+        rx.await.unwrap().unwrap();
+        wait_for_stage(&provider, StageId::IndexLogs, 4).await;
+
+        let provider = provider.provider().unwrap();
+        assert_eq!(provider.log_index_range().unwrap(), Some(0..=4));
+        assert_eq!(provider.address_log_positions(contract, 0..=4).unwrap(), expected);
+        for number in 1..5 {
+            let positions =
+                provider.topic_log_positions(B256::with_last_byte(number as u8), 0..=4).unwrap();
+            assert_eq!(
+                positions,
+                expected
+                    .iter()
+                    .copied()
+                    .filter(|position| log_position_block(*position) == number && position % 2 == 1)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_blocks_above_stops_on_stuck_stage() {
+        reth_tracing::init_test_tracing();
+        let provider = create_test_provider_factory();
+        let persistence_handle =
+            persistence_handle(provider.clone(), vec![Box::new(StuckUnwindStage)]);
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let saved = test_block_builder.get_executed_blocks(0..3).collect::<Vec<_>>();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(saved, tx).unwrap();
+        rx.await.unwrap().unwrap();
+
+        let provider_rw = provider.database_provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexTraces, StageCheckpoint::new(2)).unwrap();
+        provider_rw.commit().unwrap();
+
+        // the service fails instead of unwinding the stage forever
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.remove_blocks_above(0, tx).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(10), rx)
+            .await
+            .expect("test timed out")
+            .unwrap_err();
+    }
 }
@@ -15,15 +15,15 @@ use alloy_rpc_types_engine::{
 use error::{InsertBlockError, InsertBlockFatalError};
 use persistence_state::CurrentPersistenceAction;
 use reth_chain_state::{
-    CanonicalInMemoryState, ExecutedBlock, ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates,
-    MemoryOverlayStateProvider, NewCanonicalChain,
+    CanonStateNotification, CanonicalInMemoryState, ExecutedBlock, ExecutedBlockWithTrieUpdates,
+    ExecutedTrieUpdates, MemoryOverlayStateProvider, NewCanonicalChain,
 };
 use reth_consensus::{Consensus, FullConsensus};
 use reth_engine_primitives::{
     BeaconEngineMessage, BeaconOnNewPayloadError, ConsensusEngineEvent, ExecutionPayload,
-    ForkchoiceStateTracker, OnForkChoiceUpdated,
+    ForkchoiceStateTracker, OnForkChoiceUpdated, SetHeadRequest,
 };
-use reth_errors::{ConsensusError, ProviderResult};
+use reth_errors::{ConsensusError, ProviderResult, RethError, RethResult};
 use reth_evm::{ConfigureEvm, OnStateHook};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
@@ -36,7 +36,7 @@ use reth_provider::{
     StateRootProvider, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_stages_api::ControlFlow;
+use reth_stages_api::{ControlFlow, PipelineTarget};
 use reth_trie::{HashedPostState, TrieInput};
 use reth_trie_db::DatabaseHashedPostState;
 use revm::state::EvmState;
@@ -93,6 +93,12 @@ pub mod state;
 /// backfill this gap.
 pub(crate) const MIN_BLOCKS_FOR_PIPELINE_RUN: u64 = EPOCH_SLOTS;
 
+/// The maximum number of blocks `debug_setHead` unwinds.
+///
+/// All reverted blocks are loaded for the reorg notification, deeper unwinds have to be done
+/// offline with `reth stage unwind`.
+pub(crate) const MAX_SET_HEAD_UNWIND_DEPTH: u64 = 1024;
+
 /// A builder for creating state providers that can be used across threads.
 #[derive(Clone, Debug)]
 pub struct StateProviderBuilder<N: NodePrimitives, P> {
@@ -268,6 +274,12 @@ where
     persistence_state: PersistenceState,
     /// Flag indicating the state of the node's backfill synchronization process.
     backfill_sync_state: BackfillSyncState,
+    /// The target of a `debug_setHead` pipeline unwind and the revert notification that is sent
+    /// once the pipeline finished it.
+    pending_set_head_revert: Option<(u64, CanonStateNotification<N>)>,
+    /// `debug_setHead` requests received while blocks were persisted, they're handled once the
+    /// persistence task finished.
+    pending_set_head_requests: Vec<SetHeadRequest>,
     /// Keeps track of the state of the canonical chain that isn't persisted yet.
     /// This is intended to be accessed from external sources, such as rpc.
     canonical_in_memory_state: CanonicalInMemoryState<N>,
@@ -353,6 +365,8 @@ where
             persistence,
             persistence_state,
             backfill_sync_state: BackfillSyncState::Idle,
+            pending_set_head_revert: None,
+            pending_set_head_requests: Vec::new(),
             state,
             canonical_in_memory_state,
             payload_builder,
@@ -1256,6 +1270,11 @@ where
         }
 
         if !self.persistence_state.in_progress() {
+            // the chain is unwound before any more blocks are persisted
+            for request in std::mem::take(&mut self.pending_set_head_requests) {
+                self.on_set_head_request(request);
+            }
+
             if let Some(new_tip_num) = self.find_disk_reorg()? {
                 self.remove_blocks(new_tip_num)
             } else if self.should_persist() {
@@ -1309,6 +1328,14 @@ where
                             ConsensusEngineEvent::CanonicalBlockAdded(block, now.elapsed()),
                         ));
                     }
+                    EngineApiRequest::SetHead(request) => {
+                        // the blocks that are being persisted could be above the new head
+                        if self.persistence_state.in_progress() {
+                            self.pending_set_head_requests.push(request);
+                        } else {
+                            self.on_set_head_request(request);
+                        }
+                    }
                     EngineApiRequest::Beacon(request) => {
                         match request {
                             BeaconEngineMessage::ForkchoiceUpdated {
@@ -1417,6 +1444,13 @@ where
         // backfill height is the block number that the backfill finished at
         let Some(backfill_height) = backfill_height else { return Ok(()) };
 
+        // the reverted blocks of a `debug_setHead` unwind are removed from disk now
+        if let Some((target, notification)) = self.pending_set_head_revert.take() &&
+            target == backfill_height
+        {
+            self.canonical_in_memory_state.notify_canon_state(notification);
+        }
+
         // state house keeping after backfill sync
         // remove all executed blocks below the backfill height
         //
@@ -1513,6 +1547,141 @@ where
         Ok(())
     }
 
+    /// Handles a `debug_setHead` request and sends the new head to the caller.
+    fn on_set_head_request(&mut self, request: SetHeadRequest) {
+        let SetHeadRequest { block_number, tx } = request;
+        let output = self.on_set_head(block_number);
+        if let Err(err) = tx.send(output) {
+            error!(target: "engine::tree", "Failed to send event: {err:?}");
+        }
+    }
+
+    /// Unwinds the canonical chain to the canonical block with the given number.
+    ///
+    /// If the new head is not below the last persisted block, the reverted blocks are removed from
+    /// the in-memory state and a [`CanonStateNotification::Revert`] is sent for them. Otherwise the
+    /// in-memory state is cleared and the persisted blocks above the new head are unwound by
+    /// the pipeline, like `reth stage unwind` does, see [`PipelineTarget::Unwind`].
+    ///
+    /// Syncing resumes with the next forkchoice update.
+    fn on_set_head(&mut self, block_number: u64) -> RethResult<BlockNumHash> {
+        let current_head = *self.state.tree_state.canonical_head();
+        if block_number > current_head.number {
+            return Err(RethError::msg(format!(
+                "block {block_number} is above the canonical head {}",
+                current_head.number
+            )))
+        }
+        if !self.backfill_sync_state.is_idle() {
+            return Err(RethError::msg("cannot set the head during backfill sync"))
+        }
+        if block_number == current_head.number {
+            return Ok(current_head)
+        }
+
+        let start = Instant::now();
+        let canonical_hash = |number: u64| -> ProviderResult<B256> {
+            match self.canonical_in_memory_state.hash_by_number(number) {
+                Some(hash) => Ok(hash),
+                None => self
+                    .provider
+                    .block_hash(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into())),
+            }
+        };
+        let new_head = self
+            .sealed_header_by_hash(canonical_hash(block_number)?)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+
+        if block_number < self.persistence_state.last_persisted_block.number {
+            if current_head.number - block_number > MAX_SET_HEAD_UNWIND_DEPTH {
+                return Err(RethError::msg(format!(
+                    "cannot unwind more than {MAX_SET_HEAD_UNWIND_DEPTH} blocks, use `reth stage unwind` instead"
+                )))
+            }
+            // the reverted blocks and their state are loaded before they're removed from memory
+            // and disk
+            let mut reverted = Vec::new();
+            for number in block_number + 1..=current_head.number {
+                let hash = canonical_hash(number)?;
+                reverted.push(
+                    self.canonical_block_by_hash(hash)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(hash.into()))?,
+                );
+            }
+            debug!(target: "engine::tree", head=?new_head.num_hash(), reverted=reverted.len(), "Unwinding persisted blocks");
+
+            // all blocks kept in memory are above the new head
+            self.state.tree_state.reset(new_head.num_hash());
+            self.canonical_in_memory_state.clear_state();
+            self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+            self.emit_event(EngineApiEvent::BackfillAction(BackfillAction::Start(
+                PipelineTarget::Unwind(block_number),
+            )));
+
+            // listeners are notified about the reverted blocks once the pipeline unwound them, see
+            // `on_backfill_sync_finished`
+            let chain_update = NewCanonicalChain::Reorg { new: Vec::new(), old: reverted };
+            self.pending_set_head_revert =
+                Some((block_number, chain_update.to_chain_notification()));
+        } else {
+            // all reverted blocks are in memory
+            let mut reverted = Vec::new();
+            for number in block_number + 1..=current_head.number {
+                let hash = canonical_hash(number)?;
+                let block = self
+                    .state
+                    .tree_state
+                    .executed_block_by_hash(hash)
+                    .ok_or_else(|| ProviderError::HeaderNotFound(hash.into()))?;
+                reverted.push(block.block.clone());
+            }
+            debug!(target: "engine::tree", head=?new_head.num_hash(), reverted=reverted.len(), "Setting canonical head");
+
+            let chain_update = NewCanonicalChain::Reorg { new: Vec::new(), old: reverted };
+            let notification = chain_update.to_chain_notification();
+
+            self.state.tree_state.set_canonical_head(new_head.num_hash());
+            self.canonical_in_memory_state.update_chain(chain_update);
+            self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+
+            // sends an event to all active listeners about the reverted blocks
+            self.canonical_in_memory_state.notify_canon_state(notification);
+        }
+
+        // the safe and finalized blocks can't be ahead of the head
+        if self
+            .canonical_in_memory_state
+            .get_safe_num_hash()
+            .is_some_and(|safe| safe.number > block_number)
+        {
+            self.persistence.save_safe_block_number(block_number).map_err(|err| {
+                RethError::msg(format!("failed to save the safe block number: {err}"))
+            })?;
+            self.canonical_in_memory_state.set_safe(new_head.clone());
+        }
+        if self
+            .canonical_in_memory_state
+            .get_finalized_num_hash()
+            .is_some_and(|finalized| finalized.number > block_number)
+        {
+            self.persistence.save_finalized_block_number(block_number).map_err(|err| {
+                RethError::msg(format!("failed to save the finalized block number: {err}"))
+            })?;
+            self.canonical_in_memory_state.set_finalized(new_head.clone());
+        }
+
+        self.metrics.tree.canonical_chain_height.set(block_number as f64);
+
+        let new_head_num_hash = new_head.num_hash();
+        self.emit_event(ConsensusEngineEvent::CanonicalChainCommitted(
+            Box::new(new_head),
+            start.elapsed(),
+        ));
+
+        Ok(new_head_num_hash)
+    }
+
     /// Convenience function to handle an optional tree event.
     fn on_maybe_tree_event(&mut self, event: Option<TreeEvent>) -> ProviderResult<()> {
         if let Some(event) = event {
@@ -1868,7 +2037,10 @@ where
 
         // insert the head block into the invalid header cache
         self.state.invalid_headers.insert_with_invalid_ancestor(head.hash(), invalid);
-        self.emit_event(ConsensusEngineEvent::InvalidBlock(Box::new(head)));
+        self.emit_event(ConsensusEngineEvent::InvalidBlock(
+            Box::new(head),
+            format!("links to previously rejected block {}", invalid.block.hash),
+        ));
 
         Ok(status)
     }
@@ -2620,6 +2792,7 @@ where
         self.state.invalid_headers.insert(block.block_with_parent());
         self.emit_event(EngineApiEvent::BeaconConsensus(ConsensusEngineEvent::InvalidBlock(
             Box::new(block),
+            validation_err.to_string(),
         )));
 
         Ok(PayloadStatus::new(
@@ -654,9 +654,7 @@ mod tests {
     use super::ExecutionCache;
     use crate::tree::{
         cached_state::{CachedStateMetrics, ExecutionCacheBuilder, SavedCache},
-        payload_processor::{
-            evm_state_to_hashed_post_state, executor::WorkloadExecutor, PayloadProcessor,
-        },
+        payload_processor::{executor::WorkloadExecutor, PayloadProcessor},
         precompile_cache::PrecompileCacheMap,
         StateProviderBuilder, TreeConfig,
     };
@@ -674,7 +672,7 @@ mod tests {
         ChainSpecProvider, HashingWriter,
     };
     use reth_testing_utils::generators;
-    use reth_trie::{test_utils::state_root, HashedPostState, TrieInput};
+    use reth_trie::{test_utils::state_root, HashedPostState, KeccakKeyHasher, TrieInput};
     use revm_primitives::{Address, HashMap, B256, KECCAK_EMPTY, U256};
     use revm_state::{AccountInfo, AccountStatus, EvmState, EvmStorageSlot};
     use std::sync::Arc;
@@ -835,7 +833,7 @@ mod tests {
         }
 
         for update in &state_updates {
-            hashed_state.extend(evm_state_to_hashed_post_state(update.clone()));
+            hashed_state.extend(HashedPostState::from_evm_state::<KeccakKeyHasher>(update.clone()));
 
             for (address, account) in update {
                 let storage: HashMap<B256, U256> = account
@@ -1103,6 +1103,147 @@ async fn test_fcu_with_canonical_ancestor_updates_latest_block() {
     );
 }
 
+#[tokio::test]
+async fn test_set_head_reverts_in_memory_blocks() {
+    reth_tracing::init_test_tracing();
+    let chain_spec = MAINNET.clone();
+
+    let mut test_harness = TestHarness::new(chain_spec.clone());
+    let mut test_block_builder = TestBlockBuilder::eth().with_chain_spec((*chain_spec).clone());
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+    let mut canon_state = test_harness.tree.canonical_in_memory_state.subscribe_canon_state();
+
+    let new_head = blocks[1].recovered_block().clone();
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(EngineApiRequest::SetHead(SetHeadRequest {
+            block_number: new_head.number(),
+            tx,
+        })))
+        .unwrap();
+    assert_eq!(rx.await.unwrap().unwrap(), new_head.num_hash());
+
+    assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &new_head.num_hash());
+    assert_eq!(
+        test_harness.tree.canonical_in_memory_state.get_canonical_head().num_hash(),
+        new_head.num_hash()
+    );
+    assert!(test_harness.tree.canonical_in_memory_state.state_by_number(3).is_none());
+    assert!(test_harness.tree.canonical_in_memory_state.pending_state().is_none());
+
+    // only the reverted blocks are sent, the new head isn't committed again
+    let notification = canon_state.recv().await.unwrap();
+    assert_matches!(notification, CanonStateNotification::Revert { .. });
+    let reverted = notification.reverted().unwrap();
+    assert_eq!(reverted.first().parent_hash(), new_head.hash());
+    assert_eq!(
+        reverted.blocks().keys().copied().collect::<Vec<_>>(),
+        vec![blocks[2].recovered_block().number(), blocks[3].recovered_block().number()]
+    );
+
+    // the head can't be moved forward
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(EngineApiRequest::SetHead(SetHeadRequest {
+            block_number: blocks[3].recovered_block().number(),
+            tx,
+        })))
+        .unwrap();
+    assert!(rx.await.unwrap().is_err());
+}
+
+#[tokio::test]
+async fn test_set_head_waits_for_persistence() {
+    reth_tracing::init_test_tracing();
+    let chain_spec = MAINNET.clone();
+
+    let mut test_harness = TestHarness::new(chain_spec.clone());
+    let mut test_block_builder = TestBlockBuilder::eth().with_chain_spec((*chain_spec).clone());
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+    let (persisted_tx, persisted_rx) = oneshot::channel();
+    test_harness
+        .tree
+        .persistence_state
+        .start_save(blocks[0].recovered_block().num_hash(), persisted_rx);
+
+    let new_head = blocks[1].recovered_block().clone();
+    let (tx, mut rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(EngineApiRequest::SetHead(SetHeadRequest {
+            block_number: new_head.number(),
+            tx,
+        })))
+        .unwrap();
+
+    // the request is handled once the blocks are persisted
+    assert!(rx.try_recv().is_err());
+    assert_eq!(
+        test_harness.tree.state.tree_state.canonical_head(),
+        &blocks[3].recovered_block().num_hash()
+    );
+    persisted_tx.send(Some(blocks[0].recovered_block().num_hash())).unwrap();
+    test_harness.tree.advance_persistence().unwrap();
+    assert_eq!(rx.await.unwrap().unwrap(), new_head.num_hash());
+    assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &new_head.num_hash());
+}
+
+#[tokio::test]
+async fn test_set_head_unwinds_persisted_blocks_with_pipeline() {
+    reth_tracing::init_test_tracing();
+    let chain_spec = MAINNET.clone();
+
+    let mut test_harness = TestHarness::new(chain_spec.clone());
+    let mut test_block_builder = TestBlockBuilder::eth().with_chain_spec((*chain_spec).clone());
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+    test_harness.tree.persistence_state.last_persisted_block =
+        blocks[2].recovered_block().num_hash();
+    let mut canon_state = test_harness.tree.canonical_in_memory_state.subscribe_canon_state();
+
+    let new_head = blocks[0].recovered_block().clone();
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(EngineApiRequest::SetHead(SetHeadRequest {
+            block_number: new_head.number(),
+            tx,
+        })))
+        .unwrap();
+    assert_eq!(rx.await.unwrap().unwrap(), new_head.num_hash());
+
+    // the in-memory state is cleared and the pipeline unwinds the persisted blocks
+    assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &new_head.num_hash());
+    assert_eq!(test_harness.tree.state.tree_state.block_count(), 0);
+    assert!(test_harness.tree.canonical_in_memory_state.state_by_number(2).is_none());
+    assert_eq!(test_harness.tree.backfill_sync_state, BackfillSyncState::Pending);
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    assert_matches!(
+        event,
+        EngineApiEvent::BackfillAction(BackfillAction::Start(PipelineTarget::Unwind(number)))
+            if number == new_head.number()
+    );
+
+    // the reverted blocks are only sent once the pipeline unwound them
+    assert!(canon_state.try_recv().is_err());
+    let backfill_finished = FromOrchestrator::BackfillSyncFinished(ControlFlow::Continue {
+        block_number: new_head.number(),
+    });
+    test_harness.tree.on_engine_message(FromEngine::Event(backfill_finished)).unwrap();
+
+    // only the persisted and in-memory blocks are sent, the new head isn't committed again
+    let notification = canon_state.recv().await.unwrap();
+    assert_matches!(notification, CanonStateNotification::Revert { .. });
+    assert_eq!(
+        notification.reverted().unwrap().blocks().keys().copied().collect::<Vec<_>>(),
+        blocks[1..].iter().map(|block| block.recovered_block().number()).collect::<Vec<_>>()
+    );
+}
+
 /// Test that verifies the happy path where a new payload extends the canonical chain
 #[test]
 fn test_on_new_payload_canonical_insertion() {
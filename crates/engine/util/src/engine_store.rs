@@ -8,7 +8,8 @@ use reth_payload_primitives::PayloadTypes;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    fmt,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
@@ -34,6 +35,40 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
     },
 }
 
+impl<T: PayloadTypes> StoredEngineApiMessage<T> {
+    /// Reads and decodes a stored message from the given file.
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Returns the [`EngineMessageKind`] of this message.
+    pub const fn kind(&self) -> EngineMessageKind {
+        match self {
+            Self::ForkchoiceUpdated { .. } => EngineMessageKind::ForkchoiceUpdated,
+            Self::NewPayload { .. } => EngineMessageKind::NewPayload,
+        }
+    }
+}
+
+/// The kind of a stored engine API message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineMessageKind {
+    /// An `engine_forkchoiceUpdated` call.
+    ForkchoiceUpdated,
+    /// An `engine_newPayload` call.
+    NewPayload,
+}
+
+impl fmt::Display for EngineMessageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ForkchoiceUpdated => f.write_str("forkchoiceUpdated"),
+            Self::NewPayload => f.write_str("newPayload"),
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
 #[derive(Debug)]
 pub struct EngineMessageStore {
@@ -90,8 +125,21 @@ impl EngineMessageStore {
         Ok(())
     }
 
+    /// Returns the path to the directory that stores the engine API messages.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
+        Ok(self.timestamped_engine_messages_iter()?.map(|(_, path)| path))
+    }
+
+    /// Finds and iterates through any stored engine API message files together with the time in
+    /// milliseconds at which they were received, ordered by timestamp.
+    pub fn timestamped_engine_messages_iter(
+        &self,
+    ) -> eyre::Result<impl Iterator<Item = (u64, PathBuf)>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
@@ -107,7 +155,9 @@ impl EngineMessageStore {
                 tracing::warn!(target: "engine::store", ?filename, "Skipping non json file");
             }
         }
-        Ok(filenames_by_ts.into_iter().flat_map(|(_, paths)| paths))
+        Ok(filenames_by_ts
+            .into_iter()
+            .flat_map(|(timestamp, paths)| paths.into_iter().map(move |path| (timestamp, path))))
     }
 }
 
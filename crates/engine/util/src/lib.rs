@@ -17,6 +17,8 @@ use tokio_util::either::Either;
 pub mod engine_store;
 use engine_store::EngineStoreStream;
 
+pub mod replay;
+
 pub mod skip_fcu;
 use skip_fcu::EngineSkipFcu;
 
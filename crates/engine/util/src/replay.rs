@@ -0,0 +1,428 @@
+//! Replays engine API messages stored by [`EngineMessageStore`].
+
+use crate::engine_store::{EngineMessageKind, EngineMessageStore, StoredEngineApiMessage};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus, PayloadStatusEnum};
+use eyre::WrapErr;
+use reth_engine_primitives::{ConsensusEngineHandle, ExecutionPayload};
+use reth_fs_util as fs;
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadAttributes, PayloadTypes};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::*;
+
+/// An engine API implementation that stored messages can be replayed against.
+pub trait EngineReplayTarget<T: PayloadTypes>: Send + Sync {
+    /// Submits the payload of a stored `engine_newPayload` call.
+    fn new_payload(
+        &self,
+        payload: T::ExecutionData,
+    ) -> impl Future<Output = eyre::Result<PayloadStatus>> + Send;
+
+    /// Submits a stored `engine_forkchoiceUpdated` call and returns the resulting payload status.
+    fn fork_choice_updated(
+        &self,
+        state: ForkchoiceState,
+        payload_attrs: Option<T::PayloadAttributes>,
+    ) -> impl Future<Output = eyre::Result<PayloadStatus>> + Send;
+}
+
+/// Replays messages directly into the engine tree of the node that owns the handle.
+impl<T: PayloadTypes> EngineReplayTarget<T> for ConsensusEngineHandle<T> {
+    async fn new_payload(&self, payload: T::ExecutionData) -> eyre::Result<PayloadStatus> {
+        Ok(Self::new_payload(self, payload).await?)
+    }
+
+    async fn fork_choice_updated(
+        &self,
+        state: ForkchoiceState,
+        payload_attrs: Option<T::PayloadAttributes>,
+    ) -> eyre::Result<PayloadStatus> {
+        let version = forkchoice_updated_version(payload_attrs.as_ref());
+        Ok(Self::fork_choice_updated(self, state, payload_attrs, version).await?.payload_status)
+    }
+}
+
+/// Returns the `engine_forkchoiceUpdated` version matching the given payload attributes.
+///
+/// Calls without attributes use the latest version, since it accepts any forkchoice state.
+pub fn forkchoice_updated_version<A: PayloadAttributes>(
+    payload_attrs: Option<&A>,
+) -> EngineApiMessageVersion {
+    match payload_attrs {
+        Some(attrs) if attrs.parent_beacon_block_root().is_none() => {
+            if attrs.withdrawals().is_some() {
+                EngineApiMessageVersion::V2
+            } else {
+                EngineApiMessageVersion::V1
+            }
+        }
+        _ => EngineApiMessageVersion::V3,
+    }
+}
+
+/// Selects which of the stored messages are replayed.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayFilter {
+    /// Only replay messages of this kind.
+    pub kind: Option<EngineMessageKind>,
+    /// Only replay payloads with a block number greater than or equal to this one.
+    pub from_block: Option<u64>,
+    /// Only replay payloads with a block number less than or equal to this one.
+    pub to_block: Option<u64>,
+}
+
+impl ReplayFilter {
+    /// Returns `true` if a block range was configured.
+    const fn has_block_range(&self) -> bool {
+        self.from_block.is_some() || self.to_block.is_some()
+    }
+
+    /// Returns `true` if the given block number is within the configured block range.
+    fn contains_block(&self, number: u64) -> bool {
+        self.from_block.is_none_or(|from| number >= from) &&
+            self.to_block.is_none_or(|to| number <= to)
+    }
+}
+
+/// Replays the engine API messages stored in a directory in the order they were received.
+///
+/// Forkchoice updates carry no block number, so if a block range is configured they are only
+/// replayed if their head block is a stored payload within that range.
+#[derive(Debug)]
+pub struct EngineMessageReplay {
+    /// The store to read the messages from.
+    store: EngineMessageStore,
+    /// Selects the messages to replay.
+    filter: ReplayFilter,
+    /// Factor by which the recorded delays between messages are divided.
+    ///
+    /// Messages are sent back to back if unset.
+    speed: Option<f64>,
+}
+
+impl EngineMessageReplay {
+    /// Creates a new [`EngineMessageReplay`] for the messages stored in the given directory.
+    pub const fn new(path: PathBuf) -> Self {
+        Self {
+            store: EngineMessageStore::new(path),
+            filter: ReplayFilter { kind: None, from_block: None, to_block: None },
+            speed: None,
+        }
+    }
+
+    /// Sets the filter that selects the replayed messages.
+    pub const fn with_filter(mut self, filter: ReplayFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Preserves the recorded delays between messages, divided by the given factor.
+    pub const fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Replays the selected messages against the given target and returns the responses.
+    pub async fn run<T, Target>(&self, target: &Target) -> eyre::Result<ReplayReport>
+    where
+        T: PayloadTypes,
+        Target: EngineReplayTarget<T>,
+    {
+        let mut report = ReplayReport::default();
+        let mut payloads_in_range = HashSet::<B256>::default();
+        let mut last_timestamp = None;
+
+        for (timestamp, path) in self.store.timestamped_engine_messages_iter()? {
+            let message = StoredEngineApiMessage::<T>::from_file(&path)
+                .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+
+            let (block_hash, block_number) = match &message {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, .. } => {
+                    if self.filter.has_block_range() &&
+                        !payloads_in_range.contains(&state.head_block_hash)
+                    {
+                        continue
+                    }
+                    (state.head_block_hash, None)
+                }
+                StoredEngineApiMessage::NewPayload { payload } => {
+                    if !self.filter.contains_block(payload.block_number()) {
+                        continue
+                    }
+                    payloads_in_range.insert(payload.block_hash());
+                    (payload.block_hash(), Some(payload.block_number()))
+                }
+            };
+            let kind = message.kind();
+            if self.filter.kind.is_some_and(|filter| filter != kind) {
+                continue
+            }
+
+            if let (Some(speed), Some(last)) = (self.speed, last_timestamp) {
+                let delay = Duration::from_millis(timestamp.saturating_sub(last));
+                tokio::time::sleep(delay.div_f64(speed)).await;
+            }
+            last_timestamp = Some(timestamp);
+
+            let status = match message {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                    target.fork_choice_updated(state, payload_attrs).await
+                }
+                StoredEngineApiMessage::NewPayload { payload } => target.new_payload(payload).await,
+            }
+            .wrap_err_with(|| format!("failed to replay {}", path.display()))?
+            .status;
+
+            debug!(target: "engine::replay", %kind, %block_hash, ?status, "Replayed engine API message");
+            report.messages.push(ReplayedMessage {
+                file: path.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+                kind,
+                block_hash,
+                block_number,
+                status,
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+/// The response to a replayed engine API message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayedMessage {
+    /// The name of the file the message was read from.
+    pub file: String,
+    /// The kind of the message.
+    pub kind: EngineMessageKind,
+    /// The hash of the payload, or the head block hash of the forkchoice update.
+    pub block_hash: B256,
+    /// The number of the payload, if the message is a `engine_newPayload` call.
+    pub block_number: Option<u64>,
+    /// The status the engine responded with.
+    pub status: PayloadStatusEnum,
+}
+
+/// The responses to all messages of a replay.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayReport {
+    /// The replayed messages in the order they were sent.
+    pub messages: Vec<ReplayedMessage>,
+}
+
+impl ReplayReport {
+    /// Reads a report previously written with [`ReplayReport::write`].
+    pub fn read(path: &Path) -> eyre::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Writes the report to the given file as JSON.
+    pub fn write(&self, path: &Path) -> eyre::Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the messages whose response differs from the expected one.
+    ///
+    /// Without a baseline every message is expected to be `VALID`, since the stored messages were
+    /// all accepted by the node that recorded them. With a baseline, the response is expected to
+    /// match the one in the baseline for the same file, and messages missing from it are ignored.
+    pub fn divergences(&self, baseline: Option<&Self>) -> Vec<Divergence<'_>> {
+        let expected = baseline.map(|baseline| {
+            baseline
+                .messages
+                .iter()
+                .map(|message| (message.file.as_str(), &message.status))
+                .collect::<HashMap<_, _>>()
+        });
+
+        self.messages
+            .iter()
+            .filter_map(|message| {
+                let expected = match &expected {
+                    Some(expected) => (*expected.get(message.file.as_str())?).clone(),
+                    None => PayloadStatusEnum::Valid,
+                };
+                (message.status != expected).then_some(Divergence { message, expected })
+            })
+            .collect()
+    }
+}
+
+/// A replayed message whose response differs from the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence<'a> {
+    /// The replayed message.
+    pub message: &'a ReplayedMessage,
+    /// The expected response status.
+    pub expected: PayloadStatusEnum,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Bloom, Bytes, U256};
+    use alloy_rpc_types_engine::{ExecutionData, ExecutionPayloadSidecar, ExecutionPayloadV1};
+    use reth_engine_primitives::BeaconEngineMessage;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use std::{sync::Mutex, time::UNIX_EPOCH};
+
+    /// Records the messages it receives and responds with `VALID` unless the block is `invalid`.
+    #[derive(Default)]
+    struct RecordingTarget {
+        invalid: Option<B256>,
+        received: Mutex<Vec<(EngineMessageKind, B256)>>,
+    }
+
+    impl RecordingTarget {
+        fn respond(&self, kind: EngineMessageKind, block_hash: B256) -> PayloadStatus {
+            self.received.lock().unwrap().push((kind, block_hash));
+            let status = if self.invalid == Some(block_hash) {
+                PayloadStatusEnum::Invalid { validation_error: "bad block".to_string() }
+            } else {
+                PayloadStatusEnum::Valid
+            };
+            PayloadStatus::from_status(status)
+        }
+    }
+
+    impl EngineReplayTarget<EthEngineTypes> for RecordingTarget {
+        async fn new_payload(&self, payload: ExecutionData) -> eyre::Result<PayloadStatus> {
+            Ok(self.respond(EngineMessageKind::NewPayload, payload.block_hash()))
+        }
+
+        async fn fork_choice_updated(
+            &self,
+            state: ForkchoiceState,
+            _payload_attrs: Option<alloy_rpc_types_engine::PayloadAttributes>,
+        ) -> eyre::Result<PayloadStatus> {
+            Ok(self.respond(EngineMessageKind::ForkchoiceUpdated, state.head_block_hash))
+        }
+    }
+
+    fn payload(number: u64) -> ExecutionData {
+        let payload = ExecutionPayloadV1 {
+            parent_hash: B256::with_last_byte(number.saturating_sub(1) as u8),
+            fee_recipient: Address::ZERO,
+            state_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            logs_bloom: Bloom::ZERO,
+            prev_randao: B256::ZERO,
+            block_number: number,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            timestamp: number * 12,
+            extra_data: Bytes::new(),
+            base_fee_per_gas: U256::from(7),
+            block_hash: B256::with_last_byte(number as u8),
+            transactions: Vec::new(),
+        };
+        ExecutionData::new(
+            alloy_rpc_types_engine::ExecutionPayload::V1(payload),
+            ExecutionPayloadSidecar::none(),
+        )
+    }
+
+    fn store_messages(dir: &Path, numbers: std::ops::RangeInclusive<u64>) {
+        let store = EngineMessageStore::new(dir.to_path_buf());
+        for number in numbers {
+            let payload = payload(number);
+            let state =
+                ForkchoiceState { head_block_hash: payload.block_hash(), ..Default::default() };
+            let received_at = UNIX_EPOCH + Duration::from_millis(number * 10);
+            let (tx, _rx) = tokio::sync::oneshot::channel();
+            store
+                .on_message::<EthEngineTypes>(
+                    &BeaconEngineMessage::NewPayload { payload, tx },
+                    received_at,
+                )
+                .unwrap();
+            let (tx, _rx) = tokio::sync::oneshot::channel();
+            store
+                .on_message::<EthEngineTypes>(
+                    &BeaconEngineMessage::ForkchoiceUpdated {
+                        state,
+                        payload_attrs: None,
+                        tx,
+                        version: EngineApiMessageVersion::default(),
+                    },
+                    received_at + Duration::from_millis(5),
+                )
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_filtered_messages_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        store_messages(dir.path(), 1..=5);
+
+        let target = RecordingTarget::default();
+        let report = EngineMessageReplay::new(dir.path().to_path_buf())
+            .with_filter(ReplayFilter { kind: None, from_block: Some(2), to_block: Some(3) })
+            .run(&target)
+            .await
+            .unwrap();
+
+        let expected = vec![
+            (EngineMessageKind::NewPayload, B256::with_last_byte(2)),
+            (EngineMessageKind::ForkchoiceUpdated, B256::with_last_byte(2)),
+            (EngineMessageKind::NewPayload, B256::with_last_byte(3)),
+            (EngineMessageKind::ForkchoiceUpdated, B256::with_last_byte(3)),
+        ];
+        assert_eq!(*target.received.lock().unwrap(), expected);
+        assert_eq!(report.messages.len(), 4);
+        assert_eq!(report.messages[0].block_number, Some(2));
+        assert!(report.divergences(None).is_empty());
+
+        let target = RecordingTarget::default();
+        let report = EngineMessageReplay::new(dir.path().to_path_buf())
+            .with_filter(ReplayFilter {
+                kind: Some(EngineMessageKind::NewPayload),
+                ..Default::default()
+            })
+            .run(&target)
+            .await
+            .unwrap();
+        assert_eq!(report.messages.len(), 5);
+        assert!(report
+            .messages
+            .iter()
+            .all(|message| message.kind == EngineMessageKind::NewPayload));
+    }
+
+    #[tokio::test]
+    async fn reports_divergences() {
+        let dir = tempfile::tempdir().unwrap();
+        store_messages(dir.path(), 1..=3);
+
+        let baseline = EngineMessageReplay::new(dir.path().to_path_buf())
+            .run(&RecordingTarget::default())
+            .await
+            .unwrap();
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        baseline.write(file.path()).unwrap();
+        assert_eq!(ReplayReport::read(file.path()).unwrap(), baseline);
+
+        let target =
+            RecordingTarget { invalid: Some(B256::with_last_byte(2)), ..Default::default() };
+        let report = EngineMessageReplay::new(dir.path().to_path_buf()).run(&target).await.unwrap();
+
+        for baseline in [None, Some(&baseline)] {
+            let divergences = report.divergences(baseline);
+            assert_eq!(divergences.len(), 2);
+            assert!(divergences.iter().all(|divergence| {
+                divergence.message.block_hash == B256::with_last_byte(2) &&
+                    divergence.expected == PayloadStatusEnum::Valid
+            }));
+        }
+        assert!(report.divergences(Some(&report)).is_empty());
+    }
+}
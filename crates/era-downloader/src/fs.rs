@@ -2,22 +2,30 @@ use crate::{EraMeta, BLOCKS_PER_FILE};
 use alloy_primitives::{hex, hex::ToHexExt, BlockNumber};
 use eyre::{eyre, OptionExt};
 use futures_util::{stream, Stream};
+use reth_era::era_file::EraReader;
 use reth_fs_util as fs;
 use sha2::{Digest, Sha256};
 use std::{fmt::Debug, io, io::BufRead, path::Path, str::FromStr};
 
-/// Creates a new ordered asynchronous [`Stream`] of ERA1 files read from `dir`.
+/// Creates a new ordered asynchronous [`Stream`] of ERA1 files read from `dir`, followed by the
+/// post-merge ERA files.
+///
+/// ERA1 files are verified against `checksums.txt`, which is required if there are any. ERA files
+/// are numbered by slot rather than block, so an ERA file is skipped only if the first block of
+/// the next one is before the merge or not after `start_from`.
 pub fn read_dir(
     dir: impl AsRef<Path> + Send + Sync + 'static,
     start_from: BlockNumber,
 ) -> eyre::Result<impl Stream<Item = eyre::Result<EraLocalMeta>> + Send + Sync + 'static + Unpin> {
     let mut checksums = None;
+    let mut era_entries = Vec::new();
     let mut entries = fs::read_dir(dir)?
         .filter_map(|entry| {
             (|| {
                 let path = entry?.path();
 
-                if path.extension() == Some("era1".as_ref()) &&
+                if let Some(extension @ ("era1" | "era")) =
+                    path.extension().and_then(|extension| extension.to_str()) &&
                     let Some(last) = path.components().next_back()
                 {
                     let str = last.as_os_str().to_string_lossy().to_string();
@@ -26,6 +34,11 @@ pub fn read_dir(
                     if parts.len() == 3 {
                         let number = usize::from_str(parts[1])?;
 
+                        if extension == "era" {
+                            era_entries.push((number, path.into_boxed_path()));
+                            return Ok(None);
+                        }
+
                         return Ok(Some((number, path.into_boxed_path())));
                     }
                 }
@@ -41,14 +54,22 @@ pub fn read_dir(
             .transpose()
         })
         .collect::<eyre::Result<Vec<_>>>()?;
-    let mut checksums = checksums.ok_or_eyre("Missing file `checksums.txt` in the `dir`")?;
+    let mut checksums = match checksums {
+        Some(checksums) => Some(checksums),
+        None if entries.is_empty() => None,
+        None => return Err(eyre!("Missing file `checksums.txt` in the `dir`")),
+    };
 
     entries.sort_by(|(left, _), (right, _)| left.cmp(right));
-
-    Ok(stream::iter(entries.into_iter().skip(start_from as usize / BLOCKS_PER_FILE).map(
-        move |(_, path)| {
-            let expected_checksum =
-                checksums.next().transpose()?.ok_or_eyre("Got less checksums than ERA files")?;
+    era_entries.sort_by_key(|(number, _)| *number);
+
+    let era1_files =
+        entries.into_iter().skip(start_from as usize / BLOCKS_PER_FILE).map(move |(_, path)| {
+            let expected_checksum = checksums
+                .as_mut()
+                .and_then(|checksums| checksums.next())
+                .transpose()?
+                .ok_or_eyre("Got less checksums than ERA files")?;
             let expected_checksum = hex::decode(expected_checksum)?;
 
             let mut hasher = Sha256::new();
@@ -66,8 +87,33 @@ pub fn read_dir(
             }
 
             Ok(EraLocalMeta::new(path))
-        },
-    )))
+        });
+    let mut skip = 0;
+    while let Some((_, next)) = era_entries.get(skip + 1) &&
+        let Ok(number) = first_block_number(next) &&
+        number.is_none_or(|number| number <= start_from)
+    {
+        skip += 1;
+    }
+    let era_files =
+        era_entries.into_iter().skip(skip).map(|(_, path)| Ok(EraLocalMeta::new(path)));
+
+    Ok(stream::iter(era1_files.chain(era_files)))
+}
+
+/// Returns the execution block number of the first beacon block in the ERA file at `path`, or
+/// `None` if the block is before the merge.
+///
+/// Only the first block is read, which is cheap compared to reading the whole file. Fails if the
+/// file cannot be read, in which case the ERA file before it is not skipped.
+fn first_block_number(path: &Path) -> eyre::Result<Option<BlockNumber>> {
+    let block = EraReader::new(fs::open(path)?)
+        .iter()
+        .next()
+        .transpose()?
+        .ok_or_eyre("Missing beacon blocks in the ERA file")?;
+
+    Ok(block.execution_payload()?.map(|execution| execution.payload.block_number()))
 }
 
 /// Contains information about an ERA file that is on the local file-system and is read-only.
@@ -1,5 +1,6 @@
 use alloy_primitives::hex::ToHexExt;
 use futures_util::StreamExt;
+use reth_era::{consensus_types::CompressedSignedBeaconBlock, e2s_file::E2StoreWriter};
 use reth_era_downloader::read_dir;
 use sha2::Digest;
 use tokio::fs;
@@ -102,3 +103,84 @@ async fn test_streaming_from_local_directory(
         },
     }
 }
+
+#[tokio::test]
+async fn test_streaming_era_files_after_era1_files() {
+    let folder = tempfile::tempdir().unwrap();
+    let folder = folder.path().to_owned();
+
+    // ERA files don't need checksums
+    fs::write(folder.join("mainnet-01600-00000000.era"), CONTENTS_1).await.unwrap();
+    fs::write(folder.join("mainnet-01599-00000000.era"), CONTENTS_0).await.unwrap();
+
+    let actual = read_dir(folder.clone(), 0).unwrap().collect::<Vec<_>>().await;
+    let actual = actual.into_iter().map(|meta| meta.unwrap()).collect::<Vec<_>>();
+    assert_eq!(
+        actual,
+        [folder.join("mainnet-01599-00000000.era"), folder.join("mainnet-01600-00000000.era")]
+    );
+
+    // ERA files are streamed after ERA1 files, regardless of the starting block
+    fs::write(folder.join("checksums.txt"), sha2::Sha256::digest(CONTENTS_0).encode_hex())
+        .await
+        .unwrap();
+    fs::write(folder.join("mainnet-00000-5ec1ffb8.era1"), CONTENTS_0).await.unwrap();
+
+    let actual = read_dir(folder.clone(), 0).unwrap().collect::<Vec<_>>().await;
+    let actual = actual.into_iter().map(|meta| meta.unwrap()).collect::<Vec<_>>();
+    assert_eq!(
+        actual,
+        [
+            folder.join("mainnet-00000-5ec1ffb8.era1"),
+            folder.join("mainnet-01599-00000000.era"),
+            folder.join("mainnet-01600-00000000.era")
+        ]
+    );
+
+    let actual = read_dir(folder.clone(), 8192).unwrap().collect::<Vec<_>>().await;
+    assert_eq!(actual.len(), 2);
+}
+
+/// Encodes an ERA file with a single beacon block from before the merge, whose body ends before the
+/// `execution_payload` offset
+fn pre_merge_era_file() -> Vec<u8> {
+    let mut body = vec![0u8; 204];
+    body[200..].copy_from_slice(&204u32.to_le_bytes());
+
+    let mut message = vec![0u8; 84];
+    message[80..].copy_from_slice(&84u32.to_le_bytes());
+    message.extend_from_slice(&body);
+
+    let mut signed_block = vec![0u8; 100];
+    signed_block[..4].copy_from_slice(&100u32.to_le_bytes());
+    signed_block.extend_from_slice(&message);
+    let block = CompressedSignedBeaconBlock::from_ssz(&signed_block).unwrap();
+
+    let mut file = Vec::new();
+    let mut writer = E2StoreWriter::new(&mut file);
+    writer.write_version().unwrap();
+    writer.write_entry(&block.to_entry()).unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+
+    file
+}
+
+#[tokio::test]
+async fn test_skipping_era_files_before_the_merge() {
+    let folder = tempfile::tempdir().unwrap();
+    let folder = folder.path().to_owned();
+
+    fs::write(folder.join("mainnet-00100-00000000.era"), pre_merge_era_file()).await.unwrap();
+    fs::write(folder.join("mainnet-00101-00000000.era"), pre_merge_era_file()).await.unwrap();
+    fs::write(folder.join("mainnet-00102-00000000.era"), CONTENTS_0).await.unwrap();
+
+    // The first file is before the merge because the next one is, and the last file cannot be
+    // read, so the file before it is kept
+    let actual = read_dir(folder.clone(), 0).unwrap().collect::<Vec<_>>().await;
+    let actual = actual.into_iter().map(|meta| meta.unwrap()).collect::<Vec<_>>();
+    assert_eq!(
+        actual,
+        [folder.join("mainnet-00101-00000000.era"), folder.join("mainnet-00102-00000000.era")]
+    );
+}
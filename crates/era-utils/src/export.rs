@@ -2,7 +2,7 @@
 //! and injecting them into era1 files with `Era1Writer`.
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{BlockNumber, B256, U256};
+use alloy_primitives::{BlockNumber, Sealable, U256};
 use eyre::{eyre, Result};
 use reth_era::{
     e2s_types::IndexEntry,
@@ -11,10 +11,11 @@ use reth_era::{
     era_file_ops::{EraFileId, StreamWriter},
     execution_types::{
         Accumulator, BlockTuple, CompressedBody, CompressedHeader, CompressedReceipts,
-        TotalDifficulty, MAX_BLOCKS_PER_ERA1,
+        HeaderRecord, TotalDifficulty, MAX_BLOCKS_PER_ERA1,
     },
 };
 use reth_fs_util as fs;
+use reth_primitives_traits::Block;
 use reth_storage_api::{BlockNumReader, BlockReader, HeaderProvider};
 use std::{
     path::PathBuf,
@@ -135,14 +136,20 @@ where
 
         let headers = provider.headers_range(start_block..=end_block)?;
 
-        // Extract first 4 bytes of last block's state root as historical identifier
-        let historical_root = headers
-            .last()
+        // The file name contains the accumulator root, so compute it before writing any block
+        let mut header_total_difficulty = total_difficulty;
+        let header_records = headers
+            .iter()
             .map(|header| {
-                let state_root = header.state_root();
-                [state_root[0], state_root[1], state_root[2], state_root[3]]
+                header_total_difficulty += header.difficulty();
+                HeaderRecord::new(header.hash_slow(), header_total_difficulty)
             })
-            .unwrap_or([0u8; 4]);
+            .collect::<Vec<_>>();
+        let accumulator = Accumulator::from_header_records(&header_records)?;
+
+        // First 4 bytes of the accumulator root as historical identifier
+        let historical_root =
+            [accumulator.root[0], accumulator.root[1], accumulator.root[2], accumulator.root[3]];
 
         let era1_id = Era1Id::new(&config.network, start_block, block_count as u32)
             .with_hash(historical_root);
@@ -156,7 +163,6 @@ where
         let mut offsets = Vec::<i64>::with_capacity(block_count);
         let mut position = VERSION_ENTRY_SIZE as i64;
         let mut blocks_written = 0;
-        let mut final_header_data = Vec::new();
 
         for (i, header) in headers.into_iter().enumerate() {
             let expected_block_number = start_block + i as u64;
@@ -168,11 +174,6 @@ where
                 &mut total_difficulty,
             )?;
 
-            // Save last block's header data for accumulator
-            if expected_block_number == end_block {
-                final_header_data = compressed_header.data.clone();
-            }
-
             let difficulty = TotalDifficulty::new(total_difficulty);
 
             let header_size = compressed_header.data.len() + ENTRY_HEADER_SIZE;
@@ -208,9 +209,6 @@ where
             }
         }
         if blocks_written > 0 {
-            let accumulator_hash =
-                B256::from_slice(&final_header_data[0..32.min(final_header_data.len())]);
-            let accumulator = Accumulator::new(accumulator_hash);
             let block_index = BlockIndex::new(start_block, offsets);
 
             writer.write_accumulator(&accumulator)?;
@@ -286,7 +284,7 @@ where
         return Err(eyre!("Expected block {expected_block_number}, got {actual_block_number}"));
     }
 
-    let body = provider
+    let block = provider
         .block_by_number(actual_block_number)?
         .ok_or_else(|| eyre!("Block body not found for block {}", actual_block_number))?;
 
@@ -297,7 +295,7 @@ where
     *total_difficulty += header.difficulty();
 
     let compressed_header = CompressedHeader::from_header(&header)?;
-    let compressed_body = CompressedBody::from_body(&body)?;
+    let compressed_body = CompressedBody::from_body(block.body())?;
     let compressed_receipts = CompressedReceipts::from_encodable_list(&receipts)
         .map_err(|e| eyre!("Failed to compress receipts: {}", e))?;
 
@@ -1,4 +1,4 @@
-use alloy_primitives::{BlockHash, BlockNumber, U256};
+use alloy_primitives::{keccak256, BlockHash, BlockNumber, B256, U256};
 use futures_util::{Stream, StreamExt};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
@@ -8,10 +8,12 @@ use reth_db_api::{
     RawKey, RawTable, RawValue,
 };
 use reth_era::{
+    consensus_types::CompressedSignedBeaconBlock,
     e2s_types::E2sError,
-    era1_file::{BlockTupleIterator, Era1Reader},
+    era1_file::Era1Reader,
+    era_file::EraReader,
     era_file_ops::StreamReader,
-    execution_types::BlockTuple,
+    execution_types::{Accumulator, BlockTuple, HeaderRecord, MAX_BLOCKS_PER_ERA1},
     DecodeCompressed,
 };
 use reth_era_downloader::EraMeta;
@@ -34,19 +36,22 @@ use std::{
     error::Error,
     fmt::{Display, Formatter},
     io::{Read, Seek},
-    iter::Map,
     ops::RangeBounds,
+    path::Path,
     sync::mpsc,
 };
 use tracing::info;
 
 /// Imports blocks from `downloader` using `provider`.
 ///
+/// The accumulator of every era1 file is verified, see [`verify_accumulator`] for details.
+///
 /// Returns current block height.
 pub fn import<Downloader, Era, PF, B, BB, BH>(
     mut downloader: Downloader,
     provider_factory: &PF,
     hash_collector: &mut Collector<BlockHash, BlockNumber>,
+    historical_roots: Option<&[B256]>,
 ) -> eyre::Result<BlockNumber>
 where
     B: Block<Header = BH, Body = BB>,
@@ -98,6 +103,7 @@ where
             hash_collector,
             &mut td,
             height..,
+            historical_roots,
         )?;
 
         save_stage_checkpoints(&provider, from, height, height, height)?;
@@ -151,6 +157,10 @@ where
 /// Skips all blocks below the [`start_bound`] of `block_numbers` and stops when reaching past the
 /// [`end_bound`] or the end of the file.
 ///
+/// Post-merge `.era` files are decoded with [`decode_beacon_block`], skipping the beacon blocks
+/// before the merge. For `.era1` files, the accumulator is verified against the accumulator entry
+/// of the file and `historical_roots` before any block is processed, see [`verify_accumulator`].
+///
 /// Returns last block height.
 ///
 /// [`start_bound`]: RangeBounds::start_bound
@@ -162,6 +172,7 @@ pub fn process<Era, P, B, BB, BH>(
     hash_collector: &mut Collector<BlockHash, BlockNumber>,
     total_difficulty: &mut U256,
     block_numbers: impl RangeBounds<BlockNumber>,
+    historical_roots: Option<&[B256]>,
 ) -> eyre::Result<BlockNumber>
 where
     B: Block<Header = BH, Body = BB>,
@@ -174,50 +185,41 @@ where
     P: DBProvider<Tx: DbTxMut> + NodePrimitivesProvider + BlockWriter<Block = B>,
     <P as NodePrimitivesProvider>::Primitives: NodePrimitives<BlockHeader = BH, BlockBody = BB>,
 {
-    let reader = open(meta)?;
-    let iter =
-        reader
-            .iter()
-            .map(Box::new(decode)
-                as Box<dyn Fn(Result<BlockTuple, E2sError>) -> eyre::Result<(BH, BB)>>);
+    if is_era_file(meta.path()) {
+        let reader = EraReader::new(fs::open(meta.path())?);
+        let iter = reader.iter().filter_map(|block| decode_beacon_block(block).transpose());
+        let iter = ProcessIter { iter, era: meta };
+
+        return process_iter(iter, writer, provider, hash_collector, total_difficulty, block_numbers)
+    }
+
+    let reader = open(meta, historical_roots)?;
+    let iter = reader.iter().map(decode::<BH, BB, E2sError>);
     let iter = ProcessIter { iter, era: meta };
 
     process_iter(iter, writer, provider, hash_collector, total_difficulty, block_numbers)
 }
 
-type ProcessInnerIter<R, BH, BB> =
-    Map<BlockTupleIterator<R>, Box<dyn Fn(Result<BlockTuple, E2sError>) -> eyre::Result<(BH, BB)>>>;
-
 /// An iterator that wraps era file extraction. After the final item [`EraMeta::mark_as_processed`]
 /// is called to ensure proper cleanup.
 #[derive(Debug)]
-pub struct ProcessIter<'a, Era: ?Sized, R: Read, BH, BB>
-where
-    BH: FullBlockHeader + Value,
-    BB: FullBlockBody<OmmerHeader = BH>,
-{
-    iter: ProcessInnerIter<R, BH, BB>,
+pub struct ProcessIter<'a, Era: ?Sized, I> {
+    iter: I,
     era: &'a Era,
 }
 
-impl<'a, Era: EraMeta + ?Sized, R: Read, BH, BB> Display for ProcessIter<'a, Era, R, BH, BB>
-where
-    BH: FullBlockHeader + Value,
-    BB: FullBlockBody<OmmerHeader = BH>,
-{
+impl<'a, Era: EraMeta + ?Sized, I> Display for ProcessIter<'a, Era, I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Display::fmt(&self.era.path().to_string_lossy(), f)
     }
 }
 
-impl<'a, Era, R, BH, BB> Iterator for ProcessIter<'a, Era, R, BH, BB>
+impl<'a, Era, I, T> Iterator for ProcessIter<'a, Era, I>
 where
-    R: Read + Seek,
     Era: EraMeta + ?Sized,
-    BH: FullBlockHeader + Value,
-    BB: FullBlockBody<OmmerHeader = BH>,
+    I: Iterator<Item = eyre::Result<T>>,
 {
-    type Item = eyre::Result<(BH, BB)>;
+    type Item = eyre::Result<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.iter.next() {
@@ -230,17 +232,118 @@ where
     }
 }
 
-/// Opens the era file described by `meta`.
-pub fn open<Era>(meta: &Era) -> eyre::Result<Era1Reader<std::fs::File>>
+/// Returns whether `path` is a post-merge `.era` file rather than an `.era1` file.
+pub fn is_era_file(path: &Path) -> bool {
+    path.extension() == Some("era".as_ref())
+}
+
+/// Opens the era1 file described by `meta` and verifies its accumulator against
+/// `historical_roots`, see [`verify_accumulator`].
+pub fn open<Era>(
+    meta: &Era,
+    historical_roots: Option<&[B256]>,
+) -> eyre::Result<Era1Reader<std::fs::File>>
 where
     Era: EraMeta + ?Sized,
 {
-    let file = fs::open(meta.path())?;
+    let mut file = fs::open(meta.path())?;
+    verify_accumulator(&mut file, historical_roots)?;
+    file.rewind()?;
     let reader = Era1Reader::new(file);
 
     Ok(reader)
 }
 
+/// Extracts a pair of [`FullBlockHeader`] and [`FullBlockBody`] from the execution payload of a
+/// [`CompressedSignedBeaconBlock`].
+///
+/// Returns `None` for beacon blocks before the merge, which have no execution payload.
+pub fn decode_beacon_block<BH, BB, E>(
+    block: Result<CompressedSignedBeaconBlock, E>,
+) -> eyre::Result<Option<(BH, BB)>>
+where
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<OmmerHeader = BH>,
+    E: From<E2sError> + Error + Send + Sync + 'static,
+{
+    let Some(payload) = block?.execution_payload()? else { return Ok(None) };
+    let block = payload.try_into_block::<BB::Transaction>()?;
+
+    // Convert to the header and body types through their RLP encoding, same as `.era1` blocks
+    let header = BH::decode(&mut alloy_rlp::encode(&block.header).as_slice())?;
+    let body = BB::decode(&mut alloy_rlp::encode(&block.body).as_slice())?;
+
+    Ok(Some((header, body)))
+}
+
+/// Verifies the [`Accumulator`] of the era1 file read from `reader`.
+///
+/// The accumulator is computed from the hash and total difficulty of every block. It must match the
+/// accumulator entry of the file and, if `historical_roots` are given, the known root of the era
+/// the file starts in, where `historical_roots` are indexed by era number.
+///
+/// This reads the whole file, so it is done when opening the file rather than while importing its
+/// blocks, which may stop before the end of the file.
+pub fn verify_accumulator<R: Read + Seek>(
+    reader: R,
+    historical_roots: Option<&[B256]>,
+) -> eyre::Result<()> {
+    let mut iter = Era1Reader::new(reader).iter();
+    let mut records = Vec::new();
+    let mut first_block = None;
+
+    for block in &mut iter {
+        let block = block?;
+        if first_block.is_none() {
+            first_block = Some(block.header.decode_header()?.number);
+        }
+        records.push(HeaderRecord::new(
+            keccak256(block.header.decompress()?),
+            block.total_difficulty.value,
+        ));
+    }
+    let Some(first_block) = first_block else { return Ok(()) };
+
+    let expected = iter
+        .accumulator()
+        .ok_or_else(|| eyre::eyre!("Era1 file is missing the accumulator entry"))?;
+    let accumulator = Accumulator::from_header_records(&records)?;
+    if accumulator != *expected {
+        return Err(eyre::eyre!(
+            "Accumulator mismatch for blocks starting at {first_block}: computed {}, era1 file has {}",
+            accumulator.root,
+            expected.root
+        ));
+    }
+
+    let era = first_block / MAX_BLOCKS_PER_ERA1 as u64;
+    if let Some(historical_root) = historical_roots.and_then(|roots| roots.get(era as usize)) &&
+        accumulator.root != *historical_root
+    {
+        return Err(eyre::eyre!(
+            "Accumulator mismatch for era {era}: computed {}, historical root is {historical_root}",
+            accumulator.root
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reads the known accumulator roots of era1 files from the file at `path`.
+///
+/// The file contains one hex encoded root per line, in the order of the eras, as expected by
+/// [`verify_accumulator`].
+pub fn read_historical_roots(path: impl AsRef<Path>) -> eyre::Result<Vec<B256>> {
+    fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.parse::<B256>().map_err(|e| eyre::eyre!("Invalid historical root {line}: {e}"))
+        })
+        .collect()
+}
+
 /// Extracts a pair of [`FullBlockHeader`] and [`FullBlockBody`] from [`BlockTuple`].
 pub fn decode<BH, BB, E>(block: Result<BlockTuple, E>) -> eyre::Result<(BH, BB)>
 where
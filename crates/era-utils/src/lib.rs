@@ -1,7 +1,8 @@
 //! Utilities to store history from downloaded ERA files with storage-api
 //!  and export it to recreate era1 files.
 //!
-//! The import is downloaded using [`reth_era_downloader`] and parsed using [`reth_era`].
+//! The import is downloaded using [`reth_era_downloader`] and parsed using [`reth_era`]. Besides
+//! pre-merge `.era1` files, the execution payloads of post-merge `.era` files can be imported.
 
 mod history;
 
@@ -14,5 +15,6 @@ pub use export::{export, ExportConfig};
 
 /// Imports history from ERA files.
 pub use history::{
-    build_index, decode, import, open, process, process_iter, save_stage_checkpoints, ProcessIter,
+    build_index, decode, decode_beacon_block, import, is_era_file, open, process, process_iter,
+    read_historical_roots, save_stage_checkpoints, verify_accumulator, ProcessIter,
 };
@@ -1,5 +1,7 @@
+use alloy_primitives::{hex, B256};
 use reth_db_common::init::init_genesis;
-use reth_era_utils::{export, ExportConfig};
+use reth_era::{era1_file::Era1Reader, era_file_ops::StreamReader};
+use reth_era_utils::{export, verify_accumulator, ExportConfig};
 use reth_fs_util as fs;
 use reth_provider::{test_utils::create_test_provider_factory, BlockReader};
 use tempfile::tempdir;
@@ -30,4 +32,19 @@ fn test_export_with_genesis_only() {
     assert!(file_name.ends_with(".era1"), "File should have correct extension");
     let metadata = fs::metadata(file_path).unwrap();
     assert!(metadata.len() > 0, "Exported file should not be empty");
+
+    // The file name contains the first 4 bytes of the accumulator root
+    let accumulator = Era1Reader::new(fs::open(file_path).unwrap())
+        .read("mainnet".to_string())
+        .unwrap()
+        .group
+        .accumulator;
+    assert!(file_name.ends_with(&format!("-{}.era1", hex::encode(&accumulator.root[..4]))));
+
+    // The accumulator is verified against the exported block and the historical roots
+    let verify = |historical_roots: &[B256]| {
+        verify_accumulator(fs::open(file_path).unwrap(), Some(historical_roots))
+    };
+    verify(&[accumulator.root]).unwrap();
+    assert!(verify(&[B256::ZERO]).unwrap_err().to_string().contains("historical root"));
 }
@@ -1,4 +1,5 @@
 use crate::{ClientWithFakeIndex, ITHACA_ERA_INDEX_URL};
+use alloy_primitives::B256;
 use reqwest::{Client, Url};
 use reth_db_common::init::init_genesis;
 use reth_era::execution_types::MAX_BLOCKS_PER_ERA1;
@@ -38,11 +39,36 @@ async fn test_history_imports_from_fresh_state_successfully() {
     let mut hash_collector = Collector::new(4096, folder);
 
     let expected_block_number = 8191;
-    let actual_block_number = import(stream, &pf, &mut hash_collector).unwrap();
+    let actual_block_number = import(stream, &pf, &mut hash_collector, None).unwrap();
 
     assert_eq!(actual_block_number, expected_block_number);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_history_import_rejects_wrong_historical_root() {
+    let url = Url::from_str(ITHACA_ERA_INDEX_URL).unwrap();
+    let folder = tempdir().unwrap();
+    let folder = folder.path();
+
+    let client = EraClient::new(ClientWithFakeIndex(Client::new()), url, folder);
+    let config = EraStreamConfig::default().with_max_files(1).with_max_concurrent_downloads(1);
+    let stream = EraStream::new(client, config);
+    let pf = create_test_provider_factory();
+    init_genesis(&pf).unwrap();
+
+    let folder = tempdir().unwrap();
+    let folder = Some(folder.path().to_owned());
+    let mut hash_collector = Collector::new(4096, folder);
+
+    // The file is verified before any of its blocks is imported
+    let historical_roots = [B256::ZERO];
+    let err = import(stream, &pf, &mut hash_collector, Some(&historical_roots)).unwrap_err();
+
+    assert!(err.to_string().contains("historical root"), "Unexpected error: {err}");
+    let provider = pf.provider().unwrap();
+    assert_eq!(provider.best_block_number().unwrap(), 0, "No block should be imported");
+}
+
 /// Test that verifies the complete roundtrip from importing to exporting era1 files.
 /// It validates :
 /// - Downloads the first era1 file from ithaca's url and import the file data, into the database
@@ -67,7 +93,7 @@ async fn test_roundtrip_export_after_import() {
     let mut hash_collector = Collector::new(4096, folder);
 
     // Import blocks from one era1 file into database
-    let last_imported_block_height = import(stream, &pf, &mut hash_collector).unwrap();
+    let last_imported_block_height = import(stream, &pf, &mut hash_collector, None).unwrap();
 
     assert_eq!(last_imported_block_height, 8191);
     let provider_ref = pf.provider_rw().unwrap().0;
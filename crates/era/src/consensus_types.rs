@@ -4,6 +4,12 @@ use crate::{
     e2s_types::{E2sError, Entry},
     DecodeCompressedSsz,
 };
+use alloy_consensus::Block;
+use alloy_eips::{eip2718::Decodable2718, eip4844::kzg_to_versioned_hash, eip7685::Requests};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionPayload, ExecutionPayloadSidecar, PraguePayloadFields,
+};
 use snap::{read::FrameDecoder, write::FrameEncoder};
 use ssz::Decode;
 use std::io::{Read, Write};
@@ -14,6 +20,38 @@ pub const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
 /// `CompressedBeaconState` record type: [0x02, 0x00]
 pub const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
 
+/// Position of the `body` offset in a `BeaconBlock`, after `slot`, `proposer_index`,
+/// `parent_root` and `state_root`
+const BEACON_BLOCK_BODY_OFFSET: usize = 80;
+
+/// Position of the first offset in a `BeaconBlockBody`, after `randao_reveal`, `eth1_data` and
+/// `graffiti`
+const BODY_FIRST_OFFSET: usize = 200;
+
+/// Position of the `execution_payload` offset in a `BeaconBlockBody`
+const BODY_EXECUTION_PAYLOAD_OFFSET: usize = 380;
+
+/// Position of the `blob_kzg_commitments` offset in a `BeaconBlockBody`
+const BODY_BLOB_KZG_COMMITMENTS_OFFSET: usize = 388;
+
+/// Position of the `execution_requests` offset in a `BeaconBlockBody`
+const BODY_EXECUTION_REQUESTS_OFFSET: usize = 392;
+
+/// Size of the fixed part of a bellatrix `BeaconBlockBody`
+const BELLATRIX_BODY_FIXED_SIZE: usize = 384;
+
+/// Size of the fixed part of a capella `BeaconBlockBody`
+const CAPELLA_BODY_FIXED_SIZE: usize = 388;
+
+/// Size of the fixed part of a deneb `BeaconBlockBody`
+const DENEB_BODY_FIXED_SIZE: usize = 392;
+
+/// Size of the fixed part of an electra `BeaconBlockBody`, unchanged in fulu
+const ELECTRA_BODY_FIXED_SIZE: usize = 396;
+
+/// Size of a KZG commitment
+const KZG_COMMITMENT_SIZE: usize = 48;
+
 /// Compressed signed beacon block
 ///
 /// See also <https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md#compressedsignedbeaconblock>.
@@ -81,6 +119,92 @@ impl CompressedSignedBeaconBlock {
     pub fn decode_to_ssz(&self) -> Result<Vec<u8>, E2sError> {
         self.decompress()
     }
+
+    /// Decode the execution payload of the signed beacon block, along with the sidecar fields
+    /// needed to turn it into an execution block.
+    ///
+    /// Returns `None` for blocks without an execution payload, i.e. blocks before the merge.
+    pub fn execution_payload(&self) -> Result<Option<BeaconExecutionPayload>, E2sError> {
+        let signed_block = self.decompress()?;
+
+        // SignedBeaconBlock { message: BeaconBlock, signature: BLSSignature }
+        let message = ssz_slice(&signed_block, ssz_offset(&signed_block, 0)?, signed_block.len())?;
+
+        // BeaconBlock { slot, proposer_index, parent_root, state_root, body: BeaconBlockBody }
+        let slot = u64::from_le_bytes(ssz_slice(message, 0, 8)?.try_into().expect("8 bytes"));
+        let parent_root = B256::from_slice(ssz_slice(message, 16, 48)?);
+        let body =
+            ssz_slice(message, ssz_offset(message, BEACON_BLOCK_BODY_OFFSET)?, message.len())?;
+
+        // The first offset points right after the fixed part of the body, which grows with the
+        // fields added by each fork
+        let fixed_size = ssz_offset(body, BODY_FIRST_OFFSET)?;
+        if fixed_size < BELLATRIX_BODY_FIXED_SIZE {
+            return Ok(None);
+        }
+
+        let payload_start = ssz_offset(body, BODY_EXECUTION_PAYLOAD_OFFSET)?;
+        let payload_end = if fixed_size == BELLATRIX_BODY_FIXED_SIZE {
+            body.len()
+        } else {
+            ssz_offset(body, BELLATRIX_BODY_FIXED_SIZE)?
+        };
+        let payload = ssz_slice(body, payload_start, payload_end)?;
+
+        let (payload, sidecar) = match fixed_size {
+            BELLATRIX_BODY_FIXED_SIZE => {
+                (ExecutionPayload::V1(ssz_decode(payload)?), ExecutionPayloadSidecar::none())
+            }
+            CAPELLA_BODY_FIXED_SIZE => {
+                (ExecutionPayload::V2(ssz_decode(payload)?), ExecutionPayloadSidecar::none())
+            }
+            DENEB_BODY_FIXED_SIZE | ELECTRA_BODY_FIXED_SIZE => {
+                let commitments_start = ssz_offset(body, BODY_BLOB_KZG_COMMITMENTS_OFFSET)?;
+                let commitments_end = if fixed_size == DENEB_BODY_FIXED_SIZE {
+                    body.len()
+                } else {
+                    ssz_offset(body, BODY_EXECUTION_REQUESTS_OFFSET)?
+                };
+                let commitments = ssz_slice(body, commitments_start, commitments_end)?;
+                if commitments.len() % KZG_COMMITMENT_SIZE != 0 {
+                    return Err(E2sError::Ssz(format!(
+                        "Invalid blob KZG commitments length: {}",
+                        commitments.len()
+                    )));
+                }
+                let versioned_hashes = commitments
+                    .chunks_exact(KZG_COMMITMENT_SIZE)
+                    .map(kzg_to_versioned_hash)
+                    .collect();
+                let cancun = CancunPayloadFields::new(parent_root, versioned_hashes);
+
+                let sidecar = if fixed_size == DENEB_BODY_FIXED_SIZE {
+                    ExecutionPayloadSidecar::v3(cancun)
+                } else {
+                    let requests_start = ssz_offset(body, BODY_EXECUTION_REQUESTS_OFFSET)?;
+                    let requests = ssz_slice(body, requests_start, body.len())?;
+                    ExecutionPayloadSidecar::v4(
+                        cancun,
+                        PraguePayloadFields::new(decode_execution_requests(requests)?),
+                    )
+                };
+
+                (ExecutionPayload::V3(ssz_decode(payload)?), sidecar)
+            }
+            _ => {
+                return Err(E2sError::Ssz(format!(
+                    "Unsupported beacon block body with fixed size {fixed_size}"
+                )))
+            }
+        };
+
+        // Bellatrix blocks before the merge have an empty execution payload
+        if payload.block_hash().is_zero() {
+            return Ok(None);
+        }
+
+        Ok(Some(BeaconExecutionPayload { slot, payload, sidecar }))
+    }
 }
 
 impl DecodeCompressedSsz for CompressedSignedBeaconBlock {
@@ -92,6 +216,78 @@ impl DecodeCompressedSsz for CompressedSignedBeaconBlock {
     }
 }
 
+/// The execution payload of a signed beacon block
+#[derive(Debug, Clone)]
+pub struct BeaconExecutionPayload {
+    /// Slot of the beacon block
+    pub slot: u64,
+
+    /// Execution payload of the beacon block
+    pub payload: ExecutionPayload,
+
+    /// Fields of the beacon block needed to turn the payload into an execution block
+    pub sidecar: ExecutionPayloadSidecar,
+}
+
+impl BeaconExecutionPayload {
+    /// Convert to an `alloy_consensus::Block`, checking that its hash matches the payload
+    pub fn try_into_block<T: Decodable2718>(self) -> Result<Block<T>, E2sError> {
+        let expected = self.payload.block_hash();
+        let block = self
+            .payload
+            .try_into_block_with_sidecar(&self.sidecar)
+            .map_err(|e| E2sError::Ssz(format!("Invalid execution payload: {e}")))?;
+
+        let hash = block.header.hash_slow();
+        if hash != expected {
+            return Err(E2sError::Ssz(format!(
+                "Block hash mismatch at slot {}: expected {expected}, got {hash}",
+                self.slot
+            )));
+        }
+
+        Ok(block)
+    }
+}
+
+/// Returns the offset at `position` of ssz-encoded `bytes`
+fn ssz_offset(bytes: &[u8], position: usize) -> Result<usize, E2sError> {
+    let offset = ssz_slice(bytes, position, position + 4)?;
+    Ok(u32::from_le_bytes(offset.try_into().expect("4 bytes")) as usize)
+}
+
+/// Returns the `start..end` range of ssz-encoded `bytes`
+fn ssz_slice(bytes: &[u8], start: usize, end: usize) -> Result<&[u8], E2sError> {
+    bytes.get(start..end).ok_or_else(|| {
+        E2sError::Ssz(format!("Range {start}..{end} out of bounds of {} bytes", bytes.len()))
+    })
+}
+
+/// Decode ssz-encoded `bytes` into the given type
+fn ssz_decode<T: Decode>(bytes: &[u8]) -> Result<T, E2sError> {
+    T::from_ssz_bytes(bytes)
+        .map_err(|e| E2sError::Ssz(format!("Failed to decode SSZ data into target type: {e:?}")))
+}
+
+/// Decode the ssz-encoded `ExecutionRequests { deposits, withdrawals, consolidations }` of an
+/// electra beacon block body into [`Requests`]
+///
+/// The lists are in request type order, and each non-empty list is prefixed with its type.
+fn decode_execution_requests(bytes: &[u8]) -> Result<Requests, E2sError> {
+    let offsets =
+        [ssz_offset(bytes, 0)?, ssz_offset(bytes, 4)?, ssz_offset(bytes, 8)?, bytes.len()];
+
+    let mut requests = Requests::default();
+    for (request_type, range) in offsets.windows(2).enumerate() {
+        let list = ssz_slice(bytes, range[0], range[1])?;
+        if !list.is_empty() {
+            requests.push_request_with_type(request_type as u8, list.iter().copied());
+        }
+    }
+
+    Ok(requests)
+}
+
 /// Compressed beacon state
 ///
 /// See also <https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md#compressedbeaconstate>.
@@ -173,6 +369,205 @@ impl DecodeCompressedSsz for CompressedBeaconState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_consensus::{BlockBody, Header};
+    use alloy_eips::eip4895::Withdrawals;
+    use alloy_primitives::Bytes;
+    use alloy_rpc_types_engine::{ExecutionPayloadV2, ExecutionPayloadV3};
+    use reth_ethereum_primitives::TransactionSigned;
+    use ssz::Encode;
+
+    fn put_offset(bytes: &mut [u8], position: usize, offset: usize) {
+        bytes[position..position + 4].copy_from_slice(&(offset as u32).to_le_bytes());
+    }
+
+    /// Encodes a `SignedBeaconBlock` with the given body fixed part size, `execution_payload` and
+    /// the `lists` following it, and empty lists otherwise
+    fn encode_signed_beacon_block(fixed_size: usize, payload: &[u8], lists: &[&[u8]]) -> Vec<u8> {
+        let mut body = vec![0u8; fixed_size];
+        // Slashings, attestations, deposits and voluntary exits
+        for position in (BODY_FIRST_OFFSET..BODY_FIRST_OFFSET + 20).step_by(4) {
+            put_offset(&mut body, position, fixed_size);
+        }
+        if fixed_size >= BELLATRIX_BODY_FIXED_SIZE {
+            put_offset(&mut body, BODY_EXECUTION_PAYLOAD_OFFSET, fixed_size);
+            // Lists added after bellatrix
+            let mut offset = fixed_size + payload.len();
+            for (i, position) in (BELLATRIX_BODY_FIXED_SIZE..fixed_size).step_by(4).enumerate() {
+                put_offset(&mut body, position, offset);
+                offset += lists.get(i).map_or(0, |list| list.len());
+            }
+        }
+        body.extend_from_slice(payload);
+        for list in lists {
+            body.extend_from_slice(list);
+        }
+
+        let mut message = vec![0u8; BEACON_BLOCK_BODY_OFFSET + 4];
+        message[..8].copy_from_slice(&42u64.to_le_bytes());
+        put_offset(&mut message, BEACON_BLOCK_BODY_OFFSET, BEACON_BLOCK_BODY_OFFSET + 4);
+        message.extend_from_slice(&body);
+
+        let mut signed_block = vec![0u8; 100];
+        put_offset(&mut signed_block, 0, 100);
+        signed_block.extend_from_slice(&message);
+        signed_block
+    }
+
+    #[test]
+    fn test_execution_payload_from_capella_block() {
+        let block = Block::<TransactionSigned> {
+            header: Header {
+                number: 17_034_870,
+                timestamp: 1_681_338_479,
+                base_fee_per_gas: Some(7),
+                withdrawals_root: Some(alloy_consensus::EMPTY_ROOT_HASH),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: vec![],
+                ommers: vec![],
+                withdrawals: Some(Withdrawals::default()),
+            },
+        };
+        let payload = ExecutionPayloadV2::from_block_slow(&block);
+
+        let ssz = encode_signed_beacon_block(CAPELLA_BODY_FIXED_SIZE, &payload.as_ssz_bytes(), &[]);
+        let compressed = CompressedSignedBeaconBlock::from_ssz(&ssz).unwrap();
+        let execution_payload = compressed.execution_payload().unwrap().unwrap();
+        assert_eq!(execution_payload.slot, 42);
+
+        let decoded = execution_payload.try_into_block::<TransactionSigned>().unwrap();
+        assert_eq!(decoded.header.hash_slow(), block.header.hash_slow());
+    }
+
+    #[test]
+    fn test_execution_payload_from_deneb_block() {
+        let block = Block::<TransactionSigned> {
+            header: Header {
+                number: 19_426_587,
+                timestamp: 1_710_338_135,
+                base_fee_per_gas: Some(7),
+                withdrawals_root: Some(alloy_consensus::EMPTY_ROOT_HASH),
+                blob_gas_used: Some(0),
+                excess_blob_gas: Some(0),
+                parent_beacon_block_root: Some(B256::ZERO),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: vec![],
+                ommers: vec![],
+                withdrawals: Some(Withdrawals::default()),
+            },
+        };
+        let payload = ExecutionPayloadV3::from_block_slow(&block);
+
+        let commitments = [[1u8; KZG_COMMITMENT_SIZE], [2u8; KZG_COMMITMENT_SIZE]].concat();
+        let ssz = encode_signed_beacon_block(
+            DENEB_BODY_FIXED_SIZE,
+            &payload.as_ssz_bytes(),
+            &[&[], &commitments],
+        );
+        let compressed = CompressedSignedBeaconBlock::from_ssz(&ssz).unwrap();
+        let execution_payload = compressed.execution_payload().unwrap().unwrap();
+
+        assert_eq!(execution_payload.sidecar.parent_beacon_block_root(), Some(B256::ZERO));
+        assert_eq!(
+            execution_payload.sidecar.versioned_hashes(),
+            Some(&vec![
+                kzg_to_versioned_hash(&[1u8; KZG_COMMITMENT_SIZE]),
+                kzg_to_versioned_hash(&[2u8; KZG_COMMITMENT_SIZE])
+            ])
+        );
+        assert!(execution_payload.sidecar.requests().is_none());
+
+        let decoded = execution_payload.try_into_block::<TransactionSigned>().unwrap();
+        assert_eq!(decoded.header.hash_slow(), block.header.hash_slow());
+    }
+
+    #[test]
+    fn test_execution_payload_from_electra_block() {
+        // A single deposit request and no withdrawal or consolidation requests
+        let deposit = [0xabu8; 192];
+        let mut execution_requests = Vec::new();
+        for offset in [12, 12 + deposit.len(), 12 + deposit.len()] {
+            execution_requests.extend_from_slice(&(offset as u32).to_le_bytes());
+        }
+        execution_requests.extend_from_slice(&deposit);
+
+        let mut requests = Requests::default();
+        requests.push_request_with_type(0, deposit);
+
+        let block = Block::<TransactionSigned> {
+            header: Header {
+                number: 22_431_084,
+                timestamp: 1_746_612_311,
+                base_fee_per_gas: Some(7),
+                withdrawals_root: Some(alloy_consensus::EMPTY_ROOT_HASH),
+                blob_gas_used: Some(0),
+                excess_blob_gas: Some(0),
+                parent_beacon_block_root: Some(B256::ZERO),
+                requests_hash: Some(requests.requests_hash()),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: vec![],
+                ommers: vec![],
+                withdrawals: Some(Withdrawals::default()),
+            },
+        };
+        let payload = ExecutionPayloadV3::from_block_slow(&block);
+
+        let commitments = [3u8; KZG_COMMITMENT_SIZE];
+        let ssz = encode_signed_beacon_block(
+            ELECTRA_BODY_FIXED_SIZE,
+            &payload.as_ssz_bytes(),
+            &[&[], &commitments, &execution_requests],
+        );
+        let compressed = CompressedSignedBeaconBlock::from_ssz(&ssz).unwrap();
+        let execution_payload = compressed.execution_payload().unwrap().unwrap();
+
+        assert_eq!(
+            execution_payload.sidecar.versioned_hashes(),
+            Some(&vec![kzg_to_versioned_hash(&commitments)])
+        );
+        assert_eq!(execution_payload.sidecar.requests(), Some(&requests));
+
+        let decoded = execution_payload.try_into_block::<TransactionSigned>().unwrap();
+        assert_eq!(decoded.header.hash_slow(), block.header.hash_slow());
+    }
+
+    #[test]
+    fn test_execution_payload_before_merge() {
+        // Altair blocks have no execution payload
+        let altair = encode_signed_beacon_block(BODY_EXECUTION_PAYLOAD_OFFSET, &[], &[]);
+        let compressed = CompressedSignedBeaconBlock::from_ssz(&altair).unwrap();
+        assert!(compressed.execution_payload().unwrap().is_none());
+
+        // Bellatrix blocks before the merge have an empty execution payload
+        let empty_payload = alloy_rpc_types_engine::ExecutionPayloadV1 {
+            parent_hash: B256::ZERO,
+            fee_recipient: Default::default(),
+            state_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            logs_bloom: Default::default(),
+            prev_randao: B256::ZERO,
+            block_number: 0,
+            gas_limit: 0,
+            gas_used: 0,
+            timestamp: 0,
+            extra_data: Bytes::new(),
+            base_fee_per_gas: Default::default(),
+            block_hash: B256::ZERO,
+            transactions: vec![],
+        };
+        let bellatrix = encode_signed_beacon_block(
+            BELLATRIX_BODY_FIXED_SIZE,
+            &empty_payload.as_ssz_bytes(),
+            &[],
+        );
+        let compressed = CompressedSignedBeaconBlock::from_ssz(&bellatrix).unwrap();
+        assert!(compressed.execution_payload().unwrap().is_none());
+    }
 
     #[test]
     fn test_signed_beacon_block_compression_roundtrip() {
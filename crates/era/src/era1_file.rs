@@ -109,6 +109,11 @@ impl<R: Read> BlockTupleIterator<R> {
             block_index: None,
         }
     }
+
+    /// Returns the accumulator of the file, once the iterator has reached it after the last block.
+    pub const fn accumulator(&self) -> Option<&Accumulator> {
+        self.accumulator.as_ref()
+    }
 }
 
 impl<R: Read + Seek> Iterator for BlockTupleIterator<R> {
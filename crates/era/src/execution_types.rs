@@ -5,6 +5,7 @@
 //! - [`CompressedBody`] - Block body
 //! - [`CompressedReceipts`] - Block receipts
 //! - [`TotalDifficulty`] - Block total difficulty
+//! - [`Accumulator`] - Root of the header records of the file
 //!
 //! These types use Snappy compression to match the specification.
 //!
@@ -78,6 +79,7 @@ use crate::{
 use alloy_consensus::{Block, BlockBody, Header};
 use alloy_primitives::{B256, U256};
 use alloy_rlp::{Decodable, Encodable};
+use sha2::{Digest, Sha256};
 use snap::{read::FrameDecoder, write::FrameEncoder};
 use std::{
     io::{Read, Write},
@@ -103,6 +105,9 @@ pub const ACCUMULATOR: [u8; 2] = [0x07, 0x00];
 /// Maximum number of blocks in an Era1 file, limited by accumulator size
 pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
 
+/// Depth of the merkle tree of the [`Accumulator`], i.e. `log2(MAX_BLOCKS_PER_ERA1)`
+const ACCUMULATOR_DEPTH: usize = MAX_BLOCKS_PER_ERA1.trailing_zeros() as usize;
+
 /// Generic codec for Snappy-compressed RLP data
 #[derive(Debug, Clone, Default)]
 pub struct SnappyRlpCodec<T> {
@@ -463,9 +468,31 @@ impl TotalDifficulty {
     }
 }
 
+/// A header record of the [`Accumulator`], the SSZ container
+/// `HeaderRecord { block_hash: Bytes32, total_difficulty: Uint256 }`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// The block hash
+    pub block_hash: B256,
+    /// The total difficulty at the block
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Create a new [`HeaderRecord`]
+    pub const fn new(block_hash: B256, total_difficulty: U256) -> Self {
+        Self { block_hash, total_difficulty }
+    }
+
+    /// Returns the SSZ `hash_tree_root` of the record
+    pub fn tree_hash_root(&self) -> B256 {
+        hash_pair(&self.block_hash, &B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
 /// Accumulator is computed by constructing an SSZ list of header-records
 /// and calculating the `hash_tree_root`
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Accumulator {
     /// The accumulator root hash
     pub root: B256,
@@ -477,6 +504,34 @@ impl Accumulator {
         Self { root }
     }
 
+    /// Compute the [`Accumulator`] of the given header records, i.e. the `hash_tree_root` of
+    /// `List[HeaderRecord, MAX_BLOCKS_PER_ERA1]`
+    pub fn from_header_records(records: &[HeaderRecord]) -> Result<Self, E2sError> {
+        if records.len() > MAX_BLOCKS_PER_ERA1 {
+            return Err(E2sError::Ssz(format!(
+                "Too many header records for Accumulator: expected at most {MAX_BLOCKS_PER_ERA1}, got {}",
+                records.len()
+            )));
+        }
+
+        // Merkleize the records, padding each layer with the root of an empty subtree
+        let mut layer = records.iter().map(HeaderRecord::tree_hash_root).collect::<Vec<_>>();
+        let mut zero_hash = B256::ZERO;
+        for _ in 0..ACCUMULATOR_DEPTH {
+            if layer.len() % 2 == 1 {
+                layer.push(zero_hash);
+            }
+            layer = layer.chunks_exact(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+            zero_hash = hash_pair(&zero_hash, &zero_hash);
+        }
+        let root = layer.first().copied().unwrap_or(zero_hash);
+
+        // Mix in the length of the list
+        let length = B256::from(U256::from(records.len()).to_le_bytes::<32>());
+
+        Ok(Self { root: hash_pair(&root, &length) })
+    }
+
     /// Convert to an [`Entry`]
     pub fn to_entry(&self) -> Entry {
         Entry::new(ACCUMULATOR, self.root.to_vec())
@@ -505,6 +560,14 @@ impl Accumulator {
     }
 }
 
+/// Returns the SHA-256 hash of the concatenation of `left` and `right`
+fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
 /// A block tuple in an Era1 file, containing all components for a single block
 #[derive(Debug, Clone)]
 pub struct BlockTuple {
@@ -610,6 +673,33 @@ mod tests {
         assert_eq!(recovered.value, value);
     }
 
+    #[test]
+    fn test_accumulator_from_header_records() {
+        // Roots of empty subtrees, from the leaves up
+        let mut zero_hashes = vec![B256::ZERO];
+        for depth in 0..ACCUMULATOR_DEPTH {
+            zero_hashes.push(hash_pair(&zero_hashes[depth], &zero_hashes[depth]));
+        }
+
+        let empty = Accumulator::from_header_records(&[]).unwrap();
+        assert_eq!(empty.root, hash_pair(&zero_hashes[ACCUMULATOR_DEPTH], &B256::ZERO));
+
+        let record = HeaderRecord::new(B256::repeat_byte(0x11), U256::from(17_179_869_184u64));
+        let mut root = record.tree_hash_root();
+        for zero_hash in &zero_hashes[..ACCUMULATOR_DEPTH] {
+            root = hash_pair(&root, zero_hash);
+        }
+        // The length is mixed in as a little-endian uint256
+        let mut length = B256::ZERO;
+        length[0] = 1;
+        let single = Accumulator::from_header_records(&[record]).unwrap();
+        assert_eq!(single.root, hash_pair(&root, &length));
+
+        let records = vec![record; MAX_BLOCKS_PER_ERA1 + 1];
+        assert!(Accumulator::from_header_records(&records[..MAX_BLOCKS_PER_ERA1]).is_ok());
+        assert!(Accumulator::from_header_records(&records).is_err());
+    }
+
     #[test]
     fn test_compression_roundtrip() {
         let rlp_data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
@@ -0,0 +1,50 @@
+//! Accumulator tests for `.era1` files.
+//!
+//! These tests compute the accumulator of real files from their block hashes and total
+//! difficulties, and compare it against the accumulator entry of the file and the accumulator root
+//! prefix in the file name.
+
+use crate::{
+    Era1TestDownloader, ERA1_MAINNET_FILES_NAMES, ERA1_SEPOLIA_FILES_NAMES, MAINNET, SEPOLIA,
+};
+use alloy_primitives::{hex, keccak256};
+use reth_era::execution_types::{Accumulator, HeaderRecord};
+
+async fn test_accumulators(filenames: &[&str], network: &str) -> eyre::Result<()> {
+    let downloader = Era1TestDownloader::new().await?;
+
+    for &filename in filenames {
+        println!("\nTesting file: {filename}");
+        let file = downloader.open_era1_file(filename, network).await?;
+
+        let mut records = Vec::with_capacity(file.group.blocks.len());
+        for block in &file.group.blocks {
+            let hash = keccak256(block.header.decompress()?);
+            records.push(HeaderRecord::new(hash, block.total_difficulty.value));
+        }
+        let accumulator = Accumulator::from_header_records(&records)?;
+
+        assert_eq!(
+            accumulator, file.group.accumulator,
+            "Computed accumulator should match the accumulator entry of {filename}"
+        );
+
+        // The file name ends with the first 4 bytes of the accumulator root
+        let expected_prefix = filename.trim_end_matches(".era1").rsplit('-').next().unwrap();
+        assert_eq!(hex::encode(&accumulator.root[..4]), expected_prefix);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "download intensive"]
+async fn test_mainnet_accumulators() -> eyre::Result<()> {
+    test_accumulators(&ERA1_MAINNET_FILES_NAMES, MAINNET).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "download intensive"]
+async fn test_sepolia_accumulators() -> eyre::Result<()> {
+    test_accumulators(&ERA1_SEPOLIA_FILES_NAMES, SEPOLIA).await
+}
@@ -0,0 +1,83 @@
+//! Tests for post-merge `.era` files.
+//!
+//! These tests decode the execution payloads of real files into blocks checking their hashes, and
+//! check that local `.era` files with blocks before the starting block are skipped.
+
+use crate::{Era1TestDownloader, ERA_MAINNET_FILES_NUMBERS};
+use futures::StreamExt;
+use reth_era::era_file::EraReader;
+use reth_era_downloader::read_dir;
+use reth_ethereum_primitives::TransactionSigned;
+use std::{fs::File, path::Path};
+
+/// Forks of the blocks in [`ERA_MAINNET_FILES_NUMBERS`]
+const FORKS: [&str; 4] = ["bellatrix", "capella", "deneb", "electra"];
+
+/// Returns the execution block number of the first block in the `.era` file at `path`
+fn first_block_number(path: &Path) -> eyre::Result<u64> {
+    let block = EraReader::new(File::open(path)?).iter().next().expect("at least one block")?;
+    Ok(block.execution_payload()?.expect("blocks after the merge").payload.block_number())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "download intensive"]
+async fn test_mainnet_era_execution_payloads() -> eyre::Result<()> {
+    let downloader = Era1TestDownloader::new().await?;
+
+    for (number, expected_fork) in ERA_MAINNET_FILES_NUMBERS.into_iter().zip(FORKS) {
+        println!("\nTesting era: {number}");
+        let path = downloader.download_era_file(number).await?;
+
+        let mut last_number = None;
+        for block in EraReader::new(File::open(&path)?).iter() {
+            let execution = block?.execution_payload()?.expect("blocks after the merge");
+
+            let fork = if execution.sidecar.requests().is_some() {
+                "electra"
+            } else if execution.payload.as_v3().is_some() {
+                "deneb"
+            } else if execution.payload.withdrawals().is_some() {
+                "capella"
+            } else {
+                "bellatrix"
+            };
+            assert_eq!(fork, expected_fork, "Unexpected fork at slot {}", execution.slot);
+
+            // Empty slots have no block, so execution blocks are consecutive
+            let block_number = execution.payload.block_number();
+            if let Some(last_number) = last_number {
+                assert_eq!(block_number, last_number + 1);
+            }
+            last_number = Some(block_number);
+
+            // Fails if the hash of the block doesn't match the payload
+            let block = execution.try_into_block::<TransactionSigned>()?;
+            assert_eq!(block.header.number, block_number);
+        }
+        assert!(last_number.is_some(), "Era {number} should have blocks");
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "download intensive"]
+async fn test_read_dir_skips_era_files_before_start() -> eyre::Result<()> {
+    let downloader = Era1TestDownloader::new().await?;
+    let first = downloader.download_era_file(ERA_MAINNET_FILES_NUMBERS[1]).await?;
+    let second = downloader.download_era_file(ERA_MAINNET_FILES_NUMBERS[2]).await?;
+    let start = first_block_number(&second)?;
+    let folder = downloader.temp_dir.path().to_owned();
+
+    // Some blocks of the first file are after `start - 1`, so both files are kept
+    let actual = read_dir(folder.clone(), start - 1)?.collect::<Vec<_>>().await;
+    let actual = actual.into_iter().collect::<eyre::Result<Vec<_>>>()?;
+    assert_eq!(actual, [first, second.clone()]);
+
+    // All blocks of the first file are before `start`
+    let actual = read_dir(folder, start)?.collect::<Vec<_>>().await;
+    let actual = actual.into_iter().collect::<eyre::Result<Vec<_>>>()?;
+    assert_eq!(actual, [second]);
+
+    Ok(())
+}
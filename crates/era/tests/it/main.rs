@@ -4,7 +4,8 @@
 //! These tests use the `reth-era-downloader` client to download `.era1` files temporarily
 //! and verify that we can correctly read and decompress their data.
 //!
-//! Files are downloaded from [`MAINNET_URL`] and [`SEPOLIA_URL`].
+//! Files are downloaded from [`MAINNET_URL`] and [`SEPOLIA_URL`], and post-merge `.era` files
+//! from [`MAINNET_ERA_URL`].
 
 use reqwest::{Client, Url};
 use reth_era::{
@@ -15,6 +16,7 @@ use reth_era::{
 use reth_era_downloader::EraClient;
 use std::{
     collections::HashMap,
+    io::Write,
     path::{Path, PathBuf},
     str::FromStr,
     sync::{Arc, Mutex},
@@ -23,7 +25,9 @@ use std::{
 use eyre::{eyre, Result};
 use tempfile::TempDir;
 
+mod accumulator;
 mod dd;
+mod era;
 mod genesis;
 mod roundtrip;
 
@@ -49,6 +53,15 @@ const ERA1_MAINNET_FILES_NAMES: [&str; 8] = [
     "mainnet-01895-3f81607c.era1",
 ];
 
+/// Default mainnet url
+/// for downloading mainnet post-merge `.era` files
+const MAINNET_ERA_URL: &str = "https://mainnet.era.nimbus.team/";
+
+/// Era numbers of the mainnet `.era` files we want to download
+/// from <https://mainnet.era.nimbus.team/>
+/// for testing purposes, covering bellatrix after the merge, capella, deneb and electra
+const ERA_MAINNET_FILES_NUMBERS: [usize; 4] = [600, 800, 1100, 1450];
+
 /// Sepolia network name
 const SEPOLIA: &str = "sepolia";
 
@@ -63,7 +76,7 @@ const ERA1_SEPOLIA_FILES_NAMES: [&str; 4] = [
     "sepolia-00000-643a00f7.era1",
     "sepolia-00074-0e81003c.era1",
     "sepolia-00173-b6924da5.era1",
-    "sepolia-00182-a4f0a8a1.era1 ",
+    "sepolia-00182-a4f0a8a1.era1",
 ];
 
 /// Utility for downloading `.era1` files for tests
@@ -151,6 +164,53 @@ impl Era1TestDownloader {
         Ok(downloaded_path.to_path_buf())
     }
 
+    /// Download the mainnet post-merge `.era` file of era `number`, looking up its name in the
+    /// file list
+    pub(crate) async fn download_era_file(&self, number: usize) -> Result<PathBuf> {
+        if !ERA_MAINNET_FILES_NUMBERS.contains(&number) {
+            return Err(eyre!(
+                "Unknown era: {}. Only the following eras are supported: {:?}",
+                number,
+                ERA_MAINNET_FILES_NUMBERS
+            ));
+        }
+
+        let client = Client::new();
+        let index = client.get(MAINNET_ERA_URL).send().await?.error_for_status()?.text().await?;
+
+        // file names are `<network>-<era number>-<short root>.era`
+        let prefix = format!("{MAINNET}-{number:05}-");
+        let filename = index
+            .split(['"', '<', '>', '/'])
+            .find(|name| name.starts_with(&prefix) && name.ends_with(".era"))
+            .ok_or_else(|| eyre!("Missing era {} in the file list", number))?
+            .to_string();
+
+        // check cache first
+        {
+            let cache = self.file_cache.lock().unwrap();
+            if let Some(path) = cache.get(&filename) {
+                return Ok(path.clone());
+            }
+        }
+
+        let mut response =
+            client.get(format!("{MAINNET_ERA_URL}{filename}")).send().await?.error_for_status()?;
+        let path = self.temp_dir.path().join(&filename);
+        let mut file = std::fs::File::create(&path)?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk)?;
+        }
+
+        // update the cache
+        {
+            let mut cache = self.file_cache.lock().unwrap();
+            cache.insert(filename, path.clone());
+        }
+
+        Ok(path)
+    }
+
     /// open .era1 file, downloading it if necessary
     async fn open_era1_file(&self, filename: &str, network: &str) -> Result<Era1File> {
         let path = self.download_file(filename, network).await?;
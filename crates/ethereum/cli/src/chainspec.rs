@@ -1,4 +1,4 @@
-use reth_chainspec::{ChainSpec, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA};
+use reth_chainspec::{Chain, ChainSpec, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA};
 use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
 use std::sync::Arc;
 
@@ -33,6 +33,13 @@ impl ChainSpecParser for EthereumChainSpecParser {
     fn parse(s: &str) -> eyre::Result<Arc<ChainSpec>> {
         chain_value_parser(s)
     }
+
+    fn with_chain_id(chain_spec: Arc<ChainSpec>, chain_id: u64) -> eyre::Result<Arc<ChainSpec>> {
+        let mut chain_spec = Arc::unwrap_or_clone(chain_spec);
+        chain_spec.chain = Chain::from_id(chain_id);
+        chain_spec.genesis.config.chain_id = chain_id;
+        Ok(Arc::new(chain_spec))
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +54,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn chain_spec_with_chain_id() {
+        let chain_spec = EthereumChainSpecParser::with_chain_id(DEV.clone(), 1).unwrap();
+        assert_eq!(chain_spec.chain, Chain::mainnet());
+        assert_eq!(chain_spec.genesis_hash(), DEV.genesis_hash());
+        assert_eq!(chain_spec.hardforks, DEV.hardforks);
+    }
+
     #[test]
     fn parse_raw_chainspec_hardforks() {
         let s = r#"{
@@ -15,6 +15,7 @@ use alloy_rpc_types_engine::{
     ExecutionPayloadV1, ExecutionPayloadV3, PayloadAttributes, PayloadId,
 };
 use core::convert::Infallible;
+use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_primitives_traits::{NodePrimitives, SealedBlock};
@@ -39,6 +40,8 @@ pub struct EthBuiltPayload<N: NodePrimitives = EthPrimitives> {
     pub(crate) sidecars: BlobSidecars,
     /// The requests of the payload
     pub(crate) requests: Option<Requests>,
+    /// The executed block, if it should be inserted as is instead of being re-executed.
+    pub(crate) executed_block: Option<ExecutedBlockWithTrieUpdates<N>>,
 }
 
 // === impl BuiltPayload ===
@@ -53,7 +56,7 @@ impl<N: NodePrimitives> EthBuiltPayload<N> {
         fees: U256,
         requests: Option<Requests>,
     ) -> Self {
-        Self { id, block, fees, requests, sidecars: BlobSidecars::Empty }
+        Self { id, block, fees, requests, sidecars: BlobSidecars::Empty, executed_block: None }
     }
 
     /// Returns the identifier of the payload.
@@ -81,6 +84,15 @@ impl<N: NodePrimitives> EthBuiltPayload<N> {
         self.sidecars = sidecars.into();
         self
     }
+
+    /// Sets the executed block of the payload.
+    ///
+    /// This is required for blocks that can't be re-executed from their transactions alone, e.g.
+    /// blocks with state changes applied by a dev chain.
+    pub fn with_executed_block(mut self, executed_block: ExecutedBlockWithTrieUpdates<N>) -> Self {
+        self.executed_block = Some(executed_block);
+        self
+    }
 }
 
 impl EthBuiltPayload {
@@ -171,6 +183,10 @@ impl<N: NodePrimitives> BuiltPayload for EthBuiltPayload<N> {
         self.fees
     }
 
+    fn executed_block(&self) -> Option<ExecutedBlockWithTrieUpdates<N>> {
+        self.executed_block.clone()
+    }
+
     fn requests(&self) -> Option<Requests> {
         self.requests.clone()
     }
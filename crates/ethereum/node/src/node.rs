@@ -36,17 +36,21 @@ use reth_node_builder::{
     BuilderContext, DebugNode, Node, NodeAdapter, PayloadBuilderConfig,
 };
 use reth_payload_primitives::PayloadTypes;
-use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
+use reth_provider::{providers::ProviderFactoryBuilder, CanonStateSubscriptions, EthStorage};
 use reth_rpc::{
     eth::core::{EthApiFor, EthRpcConverterFor},
-    ValidationApi,
+    AnvilApi, EthBundleSubmitter, EthTransactionConditional, ValidationApi,
+};
+use reth_rpc_api::servers::{
+    AnvilApiServer, BlockSubmissionValidationApiServer, EthSendBundleApiServer, L2EthApiExtServer,
+    MevSendBundleApiServer,
 };
-use reth_rpc_api::servers::BlockSubmissionValidationApiServer;
 use reth_rpc_builder::{config::RethRpcServerConfig, middleware::RethRpcMiddleware};
 use reth_rpc_eth_api::{
     helpers::{
         config::{EthConfigApiServer, EthConfigHandler},
         pending_block::BuildPendingEnv,
+        AddDevSigners,
     },
     RpcConvert, RpcTypes, SignableTxRequest,
 };
@@ -304,6 +308,22 @@ where
         let eth_config =
             EthConfigHandler::new(ctx.node.provider().clone(), ctx.node.evm_config().clone());
 
+        // the senders of impersonated transactions are only known from the senders table
+        let senders_pruned = ctx
+            .config
+            .prune_config()
+            .is_some_and(|config| config.segments.sender_recovery.is_some());
+        let anvil_api = ctx.local_miner.clone().map(|local_miner| {
+            AnvilApi::new(ctx.node.provider().clone(), ctx.node.pool().clone(), local_miner)
+                .with_impersonation(!senders_pruned)
+        });
+
+        let bundle_pool = ctx.bundle_pool.clone();
+
+        let tx_conditional_api = ctx.config.txpool.enable_tx_conditional.then(|| {
+            EthTransactionConditional::new(ctx.node.pool().clone(), ctx.node.provider().clone())
+        });
+
         self.inner
             .launch_add_ons_with(ctx, move |container| {
                 container.modules.merge_if_module_configured(
@@ -315,6 +335,38 @@ where
                     .modules
                     .merge_if_module_configured(RethRpcModule::Eth, eth_config.into_rpc())?;
 
+                if let Some(anvil_api) = anvil_api {
+                    // transactions of impersonated accounts are sent through the `eth` API
+                    container
+                        .registry
+                        .eth_api()
+                        .with_impersonated_accounts(anvil_api.impersonated_accounts().clone());
+                    container
+                        .modules
+                        .merge_if_module_configured(RethRpcModule::Anvil, anvil_api.into_rpc())?;
+                }
+
+                // bundles are only accepted if the payload builder includes them
+                if let Some(bundle_pool) = bundle_pool {
+                    let bundle_api =
+                        EthBundleSubmitter::new(container.registry.eth_api().clone(), bundle_pool);
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Eth,
+                        EthSendBundleApiServer::into_rpc(bundle_api.clone()),
+                    )?;
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Mev,
+                        MevSendBundleApiServer::into_rpc(bundle_api),
+                    )?;
+                }
+
+                if let Some(tx_conditional_api) = tx_conditional_api {
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Eth,
+                        tx_conditional_api.into_rpc(),
+                    )?;
+                }
+
                 Ok(())
             })
             .await
@@ -504,6 +556,19 @@ where
         info!(target: "reth::cli", "Transaction pool initialized");
         debug!(target: "reth::cli", "Spawned txpool maintenance task");
 
+        if ctx.config().txpool.enable_tx_conditional {
+            let chain_events = ctx.provider().canonical_state_stream();
+            ctx.task_executor().spawn_critical(
+                "txpool conditional maintenance task",
+                reth_transaction_pool::maintain::maintain_transaction_pool_conditional_future(
+                    ctx.provider().clone(),
+                    transaction_pool.clone(),
+                    chain_events,
+                ),
+            );
+            debug!(target: "reth::cli", "Spawned conditional txpool maintenance task");
+        }
+
         Ok(transaction_pool)
     }
 }
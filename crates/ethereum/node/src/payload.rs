@@ -4,7 +4,7 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_ethereum_payload_builder::{BundleRefundSigner, EthereumBuilderConfig};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
@@ -48,11 +48,25 @@ where
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
 
+        let mut builder_config = EthereumBuilderConfig::new().with_gas_limit(gas_limit);
+        // apply the state changes requested through the `anvil` namespace in dev mode
+        if let Some(local_miner) = ctx.local_miner() {
+            builder_config = builder_config.with_state_changes(local_miner.state_changes().clone());
+        }
+        if let Some(bundle_pool) = ctx.bundle_pool() {
+            builder_config = builder_config.with_bundles(bundle_pool.clone());
+
+            if let Some(path) = &ctx.config().builder.refund_key {
+                let refund_signer = std::fs::read_to_string(path)?.parse::<BundleRefundSigner>()?;
+                builder_config = builder_config.with_refund_signer(refund_signer);
+            }
+        }
+
         Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
+            builder_config,
         ))
     }
 }
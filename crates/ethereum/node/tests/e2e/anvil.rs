@@ -0,0 +1,126 @@
+use crate::dev::custom_chain;
+use alloy_primitives::{b256, hex, Address, U256};
+use alloy_rpc_types_eth::{BlockNumberOrTag, TransactionRequest};
+use reth_node_builder::{NodeBuilder, NodeConfig, NodeHandle};
+use reth_node_core::args::DevArgs;
+use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
+use reth_provider::providers::BlockchainProvider;
+use reth_rpc::AnvilApi;
+use reth_rpc_api::AnvilApiServer;
+use reth_rpc_eth_api::{helpers::AddDevSigners, EthApiServer};
+use reth_tasks::TaskManager;
+
+#[tokio::test]
+async fn can_use_anvil_api_on_dev_node() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config)
+        .testing_node(exec)
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_debug_capabilities()
+        .await?;
+
+    let anvil = AnvilApi::new(
+        node.provider.clone(),
+        node.pool.clone(),
+        node.local_miner.clone().expect("dev node has a local miner"),
+    );
+    let eth_api = node.rpc_registry.eth_api();
+
+    anvil.anvil_set_automine(false).await?;
+    assert!(!anvil.anvil_get_automine().await?);
+    let snapshot = anvil.anvil_snapshot().await?;
+
+    let raw_tx = hex!(
+        "02f876820a28808477359400847735940082520894ab0840c0e43688012c1adb0f5e3fc665188f83d28a029d394a5d630544000080c080a0a044076b7e67b5deecc63f61a8d7913fab86ca365b344b5759d1fe3563b4c39ea019eab979dd000da04dfc72bb0377c092d30fd9e1cab5ae487de49586cc8b0090"
+    );
+    let tx_hash = EthApiServer::send_raw_transaction(eth_api, raw_tx.into()).await?;
+    assert_eq!(
+        tx_hash,
+        b256!("0xb1c6512f4fc202c04355fbda66755e0e344b152e633010e8fd75ecec09b63398")
+    );
+
+    // account changes and block environment overrides are applied to the next mined block
+    let address = Address::random();
+    anvil.anvil_set_balance(address, U256::from(1)).await?;
+    let coinbase = Address::random();
+    anvil.anvil_set_coinbase(coinbase).await?;
+    assert!(anvil.anvil_set_block_gas_limit(U256::from(50_000)).await?);
+    anvil.anvil_set_next_block_base_fee_per_gas(U256::from(1_000_000)).await?;
+    assert_eq!(EthApiServer::balance(eth_api, address, None).await?, U256::ZERO);
+    let block = EthApiServer::block_by_number(eth_api, BlockNumberOrTag::Latest, false)
+        .await?
+        .expect("latest block exists");
+    assert_eq!(block.header.number, 0);
+
+    let blocks = anvil.anvil_mine_detailed(None).await?;
+    assert_eq!(blocks.len(), 1);
+    let block = &blocks[0];
+    assert_eq!(block.header.number, 1);
+    assert_eq!(EthApiServer::balance(eth_api, address, None).await?, U256::from(1));
+    assert_eq!(block.header.beneficiary, coinbase);
+    assert_eq!(block.header.gas_limit, 50_000);
+    assert_eq!(block.header.base_fee_per_gas, Some(1_000_000));
+    assert_eq!(block.transactions.hashes().collect::<Vec<_>>(), vec![tx_hash]);
+
+    // the next block base fee only applies to a single block
+    let blocks = anvil.anvil_mine_detailed(None).await?;
+    assert_eq!(blocks[0].header.beneficiary, coinbase);
+    assert_ne!(blocks[0].header.base_fee_per_gas, Some(1_000_000));
+
+    // the next block mined after reverting to the snapshot reorgs the mined blocks
+    assert!(anvil.anvil_revert(snapshot).await?);
+    assert!(!anvil.anvil_revert(snapshot).await?);
+    let blocks = anvil.anvil_mine_detailed(None).await?;
+    assert_eq!(blocks[0].header.number, 1);
+    assert_eq!(EthApiServer::balance(eth_api, address, None).await?, U256::ZERO);
+
+    // resetting goes back to genesis
+    anvil.anvil_mine(Some(U256::from(3)), None).await?;
+    anvil.anvil_reset(None).await?;
+    let blocks = anvil.anvil_mine_detailed(None).await?;
+    assert_eq!(blocks[0].header.number, 1);
+
+    // transactions of impersonated accounts are sent without their private key
+    eth_api.with_impersonated_accounts(anvil.impersonated_accounts().clone());
+    let impersonated = Address::random();
+    anvil.anvil_set_balance(impersonated, U256::from(1_000_000_000_000_000_000u128)).await?;
+    anvil.anvil_mine(None, None).await?;
+    let request = TransactionRequest::default()
+        .from(impersonated)
+        .to(address)
+        .value(U256::from(1))
+        .max_fee_per_gas(10_000_000_000)
+        .max_priority_fee_per_gas(1_000_000_000);
+    assert!(EthApiServer::send_transaction(eth_api, request.clone()).await.is_err());
+
+    anvil.anvil_impersonate_account(impersonated).await?;
+    let tx_hash = EthApiServer::send_transaction(eth_api, request.clone()).await?;
+    let blocks = anvil.anvil_mine_detailed(None).await?;
+    assert_eq!(blocks[0].transactions.hashes().collect::<Vec<_>>(), vec![tx_hash]);
+    assert_eq!(EthApiServer::balance(eth_api, address, None).await?, U256::from(1));
+
+    anvil.anvil_stop_impersonating_account(impersonated).await?;
+    assert!(EthApiServer::send_transaction(eth_api, request).await.is_err());
+
+    // a dumped state is applied on top of the latest state when it's loaded
+    let state = anvil.anvil_dump_state().await?;
+    anvil.anvil_set_balance(address, U256::from(2)).await?;
+    anvil.anvil_mine(None, None).await?;
+    assert_eq!(EthApiServer::balance(eth_api, address, None).await?, U256::from(2));
+    assert!(anvil.anvil_load_state(state).await?);
+    anvil.anvil_mine(None, None).await?;
+    assert_eq!(EthApiServer::balance(eth_api, address, None).await?, U256::from(1));
+
+    // methods that don't fit a dev chain are rejected
+    assert!(anvil.anvil_set_chain_id(1).await.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::dev::custom_chain;
+use alloy_primitives::hex;
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use reth_node_builder::{NodeBuilder, NodeConfig, NodeHandle};
+use reth_node_core::args::DevArgs;
+use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
+use reth_provider::providers::BlockchainProvider;
+use reth_rpc::{AnvilApi, EthTransactionConditional};
+use reth_rpc_api::{servers::L2EthApiExtServer, AnvilApiServer};
+use reth_tasks::TaskManager;
+use reth_transaction_pool::TransactionPool;
+
+#[tokio::test]
+async fn skips_conditional_transactions_until_met() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config)
+        .testing_node(exec)
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_debug_capabilities()
+        .await?;
+
+    let anvil = AnvilApi::new(
+        node.provider.clone(),
+        node.pool.clone(),
+        node.local_miner.clone().expect("dev node has a local miner"),
+    );
+    anvil.anvil_set_automine(false).await?;
+
+    let tx_conditional = EthTransactionConditional::new(node.pool.clone(), node.provider.clone());
+    let raw_tx = hex!(
+        "02f876820a28808477359400847735940082520894ab0840c0e43688012c1adb0f5e3fc665188f83d28a029d394a5d630544000080c080a0a044076b7e67b5deecc63f61a8d7913fab86ca365b344b5759d1fe3563b4c39ea019eab979dd000da04dfc72bb0377c092d30fd9e1cab5ae487de49586cc8b0090"
+    );
+    let condition = TransactionConditional { block_number_min: Some(2), ..Default::default() };
+    let tx_hash = tx_conditional.send_raw_transaction_conditional(raw_tx.into(), condition).await?;
+
+    // the conditional isn't met by the first block, the transaction stays in the pool
+    let blocks = anvil.anvil_mine_detailed(None).await?;
+    assert_eq!(blocks[0].header.number, 1);
+    assert!(blocks[0].transactions.is_empty());
+    assert!(node.pool.contains(&tx_hash));
+
+    let blocks = anvil.anvil_mine_detailed(None).await?;
+    assert_eq!(blocks[0].header.number, 2);
+    assert_eq!(blocks[0].transactions.hashes().collect::<Vec<_>>(), vec![tx_hash]);
+
+    Ok(())
+}
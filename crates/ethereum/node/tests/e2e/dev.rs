@@ -1,11 +1,16 @@
+use crate::utils::eth_payload_attributes;
+use alloy_consensus::Transaction as _;
 use alloy_eips::eip2718::Encodable2718;
 use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex, Address};
+use alloy_primitives::{b256, bytes, hex, Address, B256, U256};
+use alloy_provider::{Provider, ProviderBuilder};
+use alloy_rpc_types_eth::{TransactionInput, TransactionRequest};
 use futures::StreamExt;
-use reth_chainspec::ChainSpec;
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+use reth_e2e_test_utils::{setup, transaction::TransactionTestContext, wallet::Wallet};
 use reth_node_api::{BlockBody, FullNodeComponents, FullNodePrimitives, NodeTypes};
 use reth_node_builder::{rpc::RethRpcAddOns, FullNode, NodeBuilder, NodeConfig, NodeHandle};
-use reth_node_core::args::DevArgs;
+use reth_node_core::args::{DevArgs, ForkArgs, RpcServerArgs};
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
 use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
 use reth_rpc_eth_api::{helpers::EthTransactions, EthApiServer};
@@ -77,6 +82,72 @@ async fn can_run_dev_node_custom_attributes() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_fork_remote_chain() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    // a remote chain with two transfers to fresh accounts
+    let (mut nodes, _tasks, _) =
+        setup::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let mut remote = nodes.pop().unwrap();
+    // every transfer is sent from a separate signer with nonce zero
+    let mut signers = Wallet::new(3).wallet_gen().into_iter();
+    let mut blocks = Vec::new();
+    for signer in signers.by_ref().take(2) {
+        remote.rpc.inject_tx(TransactionTestContext::transfer_tx_bytes(1, signer).await).await?;
+        blocks.push(remote.advance_block().await?.block().clone());
+    }
+    let recipient = blocks[1].body().transactions[0].to().unwrap();
+
+    let tasks = TaskManager::current();
+    let node_config = NodeConfig::test()
+        .with_chain(chain_spec)
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_fork(ForkArgs { fork_url: Some(remote.rpc_url()), fork_block: None });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config)
+        .testing_node(tasks.executor())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_debug_capabilities()
+        .await?;
+    let url = node.rpc_server_handle().http_url().unwrap();
+    let provider = ProviderBuilder::new().connect_http(url.parse()?);
+
+    // the fork continues from the remote head, with the remote state
+    assert_eq!(provider.get_chain_id().await?, 1);
+    assert_eq!(provider.get_block_number().await?, 2);
+    let head = provider.get_block_by_number(2.into()).await?.unwrap();
+    assert_eq!(head.header.hash, blocks[1].hash());
+    assert_eq!(provider.get_balance(recipient).await?, U256::from(100));
+
+    // BLOCKHASH of the remote blocks before the fork block
+    let blockhash = bytes!("60014060005260206000f3");
+    let request =
+        TransactionRequest { input: TransactionInput::new(blockhash), ..Default::default() };
+    let hash = provider.call(request).await?;
+    assert_eq!(B256::from_slice(&hash), blocks[0].hash());
+
+    // new blocks build on the remote head
+    let signer = signers.next().unwrap();
+    let tx = TransactionTestContext::transfer_tx_bytes(1, signer).await;
+    let receipt = provider.send_raw_transaction(&tx).await?.get_receipt().await?;
+    assert_eq!(receipt.block_number, Some(3));
+    let block = provider.get_block_by_number(3.into()).await?.unwrap();
+    assert_eq!(block.header.parent_hash, blocks[1].hash());
+
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: &FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
@@ -106,7 +177,7 @@ where
     println!("mined transaction: {hash}");
 }
 
-fn custom_chain() -> Arc<ChainSpec> {
+pub(crate) fn custom_chain() -> Arc<ChainSpec> {
     let custom_genesis = r#"
 {
 
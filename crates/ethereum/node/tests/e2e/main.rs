@@ -1,6 +1,8 @@
 #![allow(missing_docs)]
 
+mod anvil;
 mod blobs;
+mod conditional;
 mod dev;
 mod eth;
 mod p2p;
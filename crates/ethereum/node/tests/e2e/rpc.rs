@@ -1,18 +1,24 @@
-use crate::utils::eth_payload_attributes;
+use crate::utils::{eth_payload_attributes, pre_cancun_payload_attributes};
 use alloy_eips::{eip2718::Encodable2718, eip7910::EthConfig};
 use alloy_primitives::{Address, B256, U256};
 use alloy_provider::{network::EthereumWallet, Provider, ProviderBuilder, SendableTx};
 use alloy_rpc_types_beacon::relay::{
-    BidTrace, BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
-    SignedBidSubmissionV3, SignedBidSubmissionV4,
+    BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2,
+    BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4, SignedBidSubmissionV1,
+    SignedBidSubmissionV2, SignedBidSubmissionV3, SignedBidSubmissionV4,
 };
-use alloy_rpc_types_engine::{BlobsBundleV1, ExecutionPayloadV3};
-use alloy_rpc_types_eth::TransactionRequest;
+use alloy_rpc_types_engine::{
+    BlobsBundleV1, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+};
+use alloy_rpc_types_eth::{BlockNumberOrTag, TransactionRequest};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use reth_chainspec::{ChainSpecBuilder, EthChainSpec, MAINNET};
-use reth_e2e_test_utils::setup_engine;
+use reth_e2e_test_utils::{
+    setup, setup_engine, transaction::TransactionTestContext, wallet::Wallet,
+};
 use reth_node_ethereum::EthereumNode;
 use reth_payload_primitives::BuiltPayload;
+use reth_rpc_api::{DebugApiServer, DebugSubscriptionKind};
 use std::{
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
@@ -121,6 +127,199 @@ async fn test_fee_history() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_flashbots_validate_v1() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .paris_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        pre_cancun_payload_attributes,
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    let proposer = Address::with_last_byte(0x42);
+    let payment = U256::from(1_000_000_000u64);
+    let _ =
+        provider.send_transaction(TransactionRequest::default().to(proposer).value(payment)).await?;
+    let payload = node.new_payload().await?;
+
+    let mut request = BuilderBlockValidationRequest {
+        request: SignedBidSubmissionV1 {
+            message: BidTrace {
+                parent_hash: payload.block().parent_hash,
+                block_hash: payload.block().hash(),
+                gas_used: payload.block().gas_used,
+                gas_limit: payload.block().gas_limit,
+                proposer_fee_recipient: proposer,
+                value: payment,
+                ..Default::default()
+            },
+            execution_payload: ExecutionPayloadV1::from_block_unchecked(
+                payload.block().hash(),
+                &payload.block().clone().into_block(),
+            ),
+            signature: Default::default(),
+        },
+        registered_gas_limit: payload.block().gas_limit,
+    };
+
+    provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .expect("request should validate");
+
+    // wrong parent
+    request.request.message.parent_hash = B256::ZERO;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    request.request.message.parent_hash = payload.block().parent_hash;
+
+    // gas limit not matching the registered gas limit
+    request.registered_gas_limit -= 1;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    request.registered_gas_limit += 1;
+
+    // gas limit not matching the block
+    request.request.message.gas_limit += 1;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    request.request.message.gas_limit -= 1;
+
+    // proposer paid less than the bid value
+    request.request.message.value = payment + U256::from(1);
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    request.request.message.value = payment;
+
+    // payment to a different proposer
+    request.request.message.proposer_fee_recipient = Address::with_last_byte(0x43);
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV1".into(), (&request,))
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flashbots_validate_v2() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .shanghai_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        pre_cancun_payload_attributes,
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    let proposer = Address::with_last_byte(0x42);
+    let payment = U256::from(1_000_000_000u64);
+    let _ =
+        provider.send_transaction(TransactionRequest::default().to(proposer).value(payment)).await?;
+    let payload = node.new_payload().await?;
+
+    let mut request = BuilderBlockValidationRequestV2 {
+        request: SignedBidSubmissionV2 {
+            message: BidTrace {
+                parent_hash: payload.block().parent_hash,
+                block_hash: payload.block().hash(),
+                gas_used: payload.block().gas_used,
+                gas_limit: payload.block().gas_limit,
+                proposer_fee_recipient: proposer,
+                value: payment,
+                ..Default::default()
+            },
+            execution_payload: ExecutionPayloadV2::from_block_unchecked(
+                payload.block().hash(),
+                &payload.block().clone().into_block(),
+            ),
+            signature: Default::default(),
+        },
+        registered_gas_limit: payload.block().gas_limit,
+    };
+
+    provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .expect("request should validate");
+
+    // wrong parent
+    request.request.message.parent_hash = B256::ZERO;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    request.request.message.parent_hash = payload.block().parent_hash;
+
+    // gas limit not matching the registered gas limit
+    request.registered_gas_limit -= 1;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    request.registered_gas_limit += 1;
+
+    // proposer paid less than the bid value
+    request.request.message.value = payment + U256::from(1);
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    request.request.message.value = payment;
+
+    // payment to a different proposer
+    request.request.message.proposer_fee_recipient = Address::with_last_byte(0x43);
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    request.request.message.proposer_fee_recipient = proposer;
+
+    request.request.execution_payload.payload_inner.state_root = B256::ZERO;
+    assert!(provider
+        .raw_request::<_, ()>("flashbots_validateBuilderSubmissionV2".into(), (&request,))
+        .await
+        .is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_flashbots_validate_v3() -> eyre::Result<()> {
     reth_tracing::init_test_tracing();
@@ -329,3 +528,90 @@ async fn test_eth_config() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_debug_intermediate_roots() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    // a block with two transfers from different senders
+    let (mut nodes, _tasks, _) =
+        setup::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+    for signer in Wallet::new(2).wallet_gen() {
+        node.rpc.inject_tx(TransactionTestContext::transfer_tx_bytes(1, signer).await).await?;
+    }
+    let payload = node.advance_block().await?;
+    let block = payload.block();
+    assert_eq!(block.body().transactions.len(), 2);
+
+    // the same parent with only the first transfer of that block
+    let (mut nodes, _tasks, _) =
+        setup::<EthereumNode>(1, chain_spec, false, eth_payload_attributes).await?;
+    let mut other = nodes.pop().unwrap();
+    other.rpc.inject_tx(block.body().transactions[0].encoded_2718().into()).await?;
+    let first = other.advance_block().await?;
+    assert_eq!(first.block().body().transactions.len(), 1);
+
+    let roots = node.rpc.inner.debug_api().debug_intermediate_roots(block.hash()).await?;
+    assert_eq!(roots, vec![first.block().state_root, block.state_root]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_debug_trace_chain_subscription() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, _) =
+        setup::<EthereumNode>(1, chain_spec, false, eth_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+    let mut hashes = Vec::new();
+    for signer in Wallet::new(3).wallet_gen() {
+        node.rpc.inject_tx(TransactionTestContext::transfer_tx_bytes(1, signer).await).await?;
+        hashes.push(node.advance_block().await?.block().hash());
+    }
+
+    let module = node.rpc.inner.debug_api().into_rpc();
+    let kind = DebugSubscriptionKind::TraceChain;
+    let mut sub = module
+        .subscribe_unbounded(
+            "debug_subscribe",
+            (kind, BlockNumberOrTag::Earliest, BlockNumberOrTag::Latest),
+        )
+        .await?;
+    for (number, hash) in (1u64..).zip(hashes) {
+        let (item, _) = sub.next::<serde_json::Value>().await.unwrap().unwrap();
+        assert_eq!(item["block"], format!("{number:#x}"));
+        assert_eq!(item["hash"], hash.to_string());
+        assert_eq!(item["traces"].as_array().map(Vec::len), Some(1));
+    }
+    // the subscription is closed once all blocks are traced
+    assert!(sub.next::<serde_json::Value>().await.is_none());
+
+    // an empty range is rejected
+    assert!(module
+        .subscribe_unbounded(
+            "debug_subscribe",
+            (kind, BlockNumberOrTag::Latest, BlockNumberOrTag::Latest),
+        )
+        .await
+        .is_err());
+
+    Ok(())
+}
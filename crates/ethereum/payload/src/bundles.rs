@@ -0,0 +1,496 @@
+//! Inclusion of bundles from the [`BundlePool`](reth_transaction_pool::BundlePool) in built
+//! payloads.
+
+use crate::BundleRefundSigner;
+use alloy_consensus::{SignableTransaction, Transaction, TxEip1559};
+use alloy_primitives::{Address, TxKind, U256};
+use alloy_rlp::Encodable;
+use alloy_signer::SignerSync;
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_evm::{
+    block::BlockExecutor, execute::BlockBuilder, ConfigureEvm, Database, Evm, EvmEnvFor, EvmError,
+};
+use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_primitives_traits::Recovered;
+use reth_revm::db::State;
+use reth_transaction_pool::bundle::{Bundle, BundleRefund, BundleTransaction};
+use revm::{context_interface::result::ResultAndState, Database as _, DatabaseCommit};
+use tracing::{debug, trace};
+
+/// The gas limit of the transfers that pay the refunds of bundles.
+const REFUND_GAS_LIMIT: u64 = 30_000;
+
+/// A bundle that was simulated on top of the state of the block being built.
+#[derive(Debug)]
+pub(crate) struct SimulatedBundle {
+    /// The transactions of the bundle that are included, without the dropped ones, followed by
+    /// the transfers that pay its refunds.
+    pub(crate) transactions: Vec<Recovered<TransactionSigned>>,
+    /// The gas used by the bundle, including its refunds.
+    pub(crate) gas_used: u64,
+    /// The value the bundle pays to the fee recipient, after refunds.
+    pub(crate) value: U256,
+}
+
+impl SimulatedBundle {
+    /// Returns the value of the bundle per unit of gas.
+    pub(crate) fn mev_gas_price(&self) -> U256 {
+        if self.gas_used == 0 {
+            return U256::ZERO
+        }
+        self.value / U256::from(self.gas_used)
+    }
+
+    /// Returns the encoded length of the included transactions.
+    pub(crate) fn rlp_length(&self) -> usize {
+        self.transactions.iter().map(|tx| tx.inner().length()).sum()
+    }
+}
+
+/// The bundles considered for inclusion in a payload.
+///
+/// Bundles are simulated on top of the state at the start of the block and ordered by the value
+/// they pay to the fee recipient per gas. A bundle is simulated again right before it is
+/// included, and is only included if all of its transactions that can't revert succeed.
+///
+/// The refunds of a bundle are paid with transfers from the fee recipient that are included
+/// right after the bundle, so bundles that request a refund are only included if the refund
+/// signer is the fee recipient of the block.
+pub(crate) struct PayloadBundles<EvmConfig: ConfigureEvm> {
+    /// The type responsible for creating the evm.
+    evm_config: EvmConfig,
+    /// The environment of the block being built.
+    evm_env: EvmEnvFor<EvmConfig>,
+    /// The signer of the refund transfers, if it is the fee recipient of the block.
+    refund_signer: Option<BundleRefundSigner>,
+    /// The pending bundles with their simulated value per gas, the most valuable last.
+    bundles: Vec<(U256, Bundle<TransactionSigned>)>,
+}
+
+impl<EvmConfig> PayloadBundles<EvmConfig>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+{
+    /// Simulates the given bundles on top of the current state of the block being built, and
+    /// orders the ones that can be included by their value per gas.
+    pub(crate) fn new<Builder>(
+        evm_config: EvmConfig,
+        evm_env: EvmEnvFor<EvmConfig>,
+        refund_signer: Option<BundleRefundSigner>,
+        bundles: Vec<Bundle<TransactionSigned>>,
+        builder: &mut Builder,
+        available_gas: u64,
+    ) -> Result<Self, PayloadBuilderError>
+    where
+        Builder: BlockBuilder<Primitives = EthPrimitives>,
+        <<Builder::Executor as BlockExecutor>::Evm as Evm>::DB: Database,
+    {
+        let refund_signer =
+            refund_signer.filter(|signer| signer.address() == evm_env.block_env.beneficiary);
+
+        let mut simulated = Vec::with_capacity(bundles.len());
+        for bundle in bundles {
+            let db = builder.evm_mut().db_mut();
+            if let Some(result) = simulate_bundle(
+                &evm_config,
+                evm_env.clone(),
+                refund_signer.as_ref(),
+                db,
+                &bundle,
+                available_gas,
+            )? {
+                simulated.push((result.mev_gas_price(), bundle));
+            }
+        }
+        simulated.sort_by_key(|(mev_gas_price, _)| *mev_gas_price);
+
+        Ok(Self { evm_config, evm_env, refund_signer, bundles: simulated })
+    }
+
+    /// Includes the pending bundles that pay at least `min_mev_gas_price` per gas, most
+    /// valuable first.
+    ///
+    /// Returns the bundles that were included.
+    pub(crate) fn execute<Builder>(
+        &mut self,
+        builder: &mut Builder,
+        min_mev_gas_price: U256,
+        mut available_gas: u64,
+        mut available_size: usize,
+    ) -> Result<Vec<SimulatedBundle>, PayloadBuilderError>
+    where
+        Builder: BlockBuilder<Primitives = EthPrimitives>,
+        <<Builder::Executor as BlockExecutor>::Evm as Evm>::DB: Database,
+    {
+        let mut included = Vec::new();
+        while self
+            .bundles
+            .last()
+            .is_some_and(|(mev_gas_price, _)| *mev_gas_price >= min_mev_gas_price)
+        {
+            let Some((_, bundle)) = self.bundles.pop() else { break };
+
+            let evm_env = self.evm_env.clone();
+            let db = builder.evm_mut().db_mut();
+            let Some(simulated) = simulate_bundle(
+                &self.evm_config,
+                evm_env,
+                self.refund_signer.as_ref(),
+                db,
+                &bundle,
+                available_gas,
+            )?
+            else {
+                continue
+            };
+            if simulated.rlp_length() > available_size {
+                trace!(target: "payload_builder", bundle=?bundle.hash(), "skipping bundle exceeding the block size");
+                continue
+            }
+
+            // the bundle was simulated on the same state, so its transactions are valid
+            for tx in &simulated.transactions {
+                builder.execute_transaction(tx.clone())?;
+            }
+
+            debug!(target: "payload_builder", bundle=?bundle.hash(), gas_used=simulated.gas_used, value=%simulated.value, "included bundle");
+            available_gas -= simulated.gas_used;
+            available_size -= simulated.rlp_length();
+            included.push(simulated);
+        }
+
+        Ok(included)
+    }
+}
+
+/// Simulates the bundle on top of the given database, without committing any changes to it.
+///
+/// The refunds of the bundle are paid out of its value with transfers from the refund signer,
+/// which is the fee recipient of the block. Each refund is reduced by the maximum fee of its
+/// transfer.
+///
+/// Returns `None` if the bundle can't be included, i.e. if a transaction that isn't allowed to
+/// revert reverts, if a transaction that can't be dropped is invalid or doesn't fit into the
+/// available gas, or if its refunds can't be paid.
+fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    evm_env: EvmEnvFor<EvmConfig>,
+    refund_signer: Option<&BundleRefundSigner>,
+    db: DB,
+    bundle: &Bundle<TransactionSigned>,
+    available_gas: u64,
+) -> Result<Option<SimulatedBundle>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: Database,
+{
+    let coinbase = evm_env.block_env.beneficiary;
+    let base_fee = evm_env.block_env.basefee;
+    let chain_id = evm_env.cfg_env.chain_id;
+    let mut db = State::builder().with_database(db).build();
+    let initial_balance = db
+        .basic(coinbase)
+        .map_err(PayloadBuilderError::other)?
+        .map(|account| account.balance)
+        .unwrap_or_default();
+
+    let mut evm = evm_config.evm_with_env(db, evm_env);
+    let mut transactions = Vec::with_capacity(bundle.transactions.len());
+    let mut gas_used = 0;
+
+    for BundleTransaction { tx, can_revert, can_drop } in &bundle.transactions {
+        // blob sidecars can't be submitted with bundles
+        if tx.as_eip4844().is_some() {
+            trace!(target: "payload_builder", tx=?tx.hash(), "skipping bundle with blob transaction");
+            return Ok(None)
+        }
+
+        if gas_used + tx.gas_limit() > available_gas {
+            if *can_drop {
+                continue
+            }
+            trace!(target: "payload_builder", tx=?tx.hash(), "skipping bundle exceeding the available gas");
+            return Ok(None)
+        }
+
+        let ResultAndState { result, state } = match evm.transact(evm_config.tx_env(tx)) {
+            Ok(result) => result,
+            Err(err) if err.is_invalid_tx_err() => {
+                if *can_drop {
+                    continue
+                }
+                trace!(target: "payload_builder", %err, tx=?tx.hash(), "skipping bundle with invalid transaction");
+                return Ok(None)
+            }
+            Err(err) => return Err(PayloadBuilderError::evm(err)),
+        };
+
+        if !result.is_success() && !can_revert {
+            trace!(target: "payload_builder", tx=?tx.hash(), "skipping bundle with reverted transaction");
+            return Ok(None)
+        }
+
+        gas_used += result.gas_used();
+        evm.db_mut().commit(state);
+        transactions.push(tx.clone());
+    }
+
+    let mut balance = evm
+        .db_mut()
+        .basic(coinbase)
+        .map_err(PayloadBuilderError::other)?
+        .map(|account| account.balance)
+        .unwrap_or_default();
+
+    if !bundle.refunds.is_empty() {
+        let Some(signer) = refund_signer else {
+            trace!(target: "payload_builder", bundle=?bundle.hash(), "skipping bundle with refunds that can't be paid");
+            return Ok(None)
+        };
+
+        let value = balance.saturating_sub(initial_balance);
+        let refund_fee = U256::from(base_fee) * U256::from(REFUND_GAS_LIMIT);
+        let nonce = evm
+            .db_mut()
+            .basic(signer.address())
+            .map_err(PayloadBuilderError::other)?
+            .map(|account| account.nonce)
+            .unwrap_or_default();
+
+        for (nonce, BundleRefund { recipient, percent }) in (nonce..).zip(&bundle.refunds) {
+            let Some(amount) = (value * U256::from(*percent) / U256::from(100))
+                .checked_sub(refund_fee)
+                .filter(|amount| !amount.is_zero())
+            else {
+                trace!(target: "payload_builder", bundle=?bundle.hash(), "skipping bundle with refund that doesn't cover the transfer fee");
+                return Ok(None)
+            };
+            if gas_used + REFUND_GAS_LIMIT > available_gas {
+                trace!(target: "payload_builder", bundle=?bundle.hash(), "skipping bundle with refunds exceeding the available gas");
+                return Ok(None)
+            }
+
+            let tx = sign_refund(signer, chain_id, nonce, base_fee, *recipient, amount)?;
+            let ResultAndState { result, state } = match evm.transact(evm_config.tx_env(&tx)) {
+                Ok(result) => result,
+                Err(err) if err.is_invalid_tx_err() => {
+                    trace!(target: "payload_builder", %err, bundle=?bundle.hash(), "skipping bundle with invalid refund");
+                    return Ok(None)
+                }
+                Err(err) => return Err(PayloadBuilderError::evm(err)),
+            };
+            if !result.is_success() {
+                trace!(target: "payload_builder", bundle=?bundle.hash(), %recipient, "skipping bundle with reverted refund");
+                return Ok(None)
+            }
+
+            gas_used += result.gas_used();
+            evm.db_mut().commit(state);
+            transactions.push(tx);
+        }
+
+        balance = evm
+            .db_mut()
+            .basic(coinbase)
+            .map_err(PayloadBuilderError::other)?
+            .map(|account| account.balance)
+            .unwrap_or_default();
+    }
+    let value = balance.saturating_sub(initial_balance);
+
+    Ok(Some(SimulatedBundle { transactions, gas_used, value }))
+}
+
+/// Signs the transfer of a refund from the refund signer, paying exactly the base fee.
+fn sign_refund(
+    signer: &BundleRefundSigner,
+    chain_id: u64,
+    nonce: u64,
+    base_fee: u64,
+    recipient: Address,
+    amount: U256,
+) -> Result<Recovered<TransactionSigned>, PayloadBuilderError> {
+    let tx = TxEip1559 {
+        chain_id,
+        nonce,
+        gas_limit: REFUND_GAS_LIMIT,
+        max_fee_per_gas: base_fee.into(),
+        max_priority_fee_per_gas: 0,
+        to: TxKind::Call(recipient),
+        value: amount,
+        ..Default::default()
+    };
+    let signature =
+        signer.signer().sign_hash_sync(&tx.signature_hash()).map_err(PayloadBuilderError::other)?;
+    let tx = TransactionSigned::new_unhashed(tx.into(), signature);
+    Ok(Recovered::new_unchecked(tx, signer.address()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{constants::ETH_TO_WEI, Header, Transaction as _, TxLegacy};
+    use alloy_primitives::{address, bytes, Signature, B256};
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_ethereum_primitives::Transaction;
+    use reth_evm::NextBlockEnvAttributes;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::SealedHeader;
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::{AccountInfo, Bytecode},
+    };
+    use std::sync::Arc;
+
+    const COINBASE: Address = address!("0x1000000000000000000000000000000000000000");
+    const SENDER: Address = address!("0x2000000000000000000000000000000000000000");
+    /// A contract that always reverts.
+    const REVERTER: Address = address!("0x3000000000000000000000000000000000000000");
+    const RECIPIENT: Address = address!("0x4000000000000000000000000000000000000000");
+
+    const VALUE: u64 = 1_000_000_000;
+
+    fn tx(nonce: u64, to: Address, value: u64) -> BundleTransaction<TransactionSigned> {
+        let tx = TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy {
+                nonce,
+                gas_limit: 100_000,
+                to: TxKind::Call(to),
+                value: U256::from(value),
+                ..Default::default()
+            }),
+            Signature::test_signature(),
+        );
+        BundleTransaction {
+            tx: Recovered::new_unchecked(tx, SENDER),
+            can_revert: false,
+            can_drop: false,
+        }
+    }
+
+    fn bundle(
+        transactions: Vec<BundleTransaction<TransactionSigned>>,
+    ) -> Bundle<TransactionSigned> {
+        Bundle {
+            transactions,
+            block_number: 1,
+            max_block_number: 1,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+            refunds: Vec::new(),
+        }
+    }
+
+    fn tx_hashes<'a>(txs: impl IntoIterator<Item = &'a Recovered<TransactionSigned>>) -> Vec<B256> {
+        txs.into_iter().map(|tx| *tx.hash()).collect()
+    }
+
+    /// Simulates the bundles on top of the first block and includes all of them.
+    ///
+    /// The refund signer is the fee recipient of the block if given.
+    fn include_bundles(
+        bundles: Vec<Bundle<TransactionSigned>>,
+        refund_signer: Option<BundleRefundSigner>,
+    ) -> Vec<SimulatedBundle> {
+        let chain_spec = ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build();
+        let evm_config = EthEvmConfig::new(Arc::new(chain_spec));
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            SENDER,
+            AccountInfo { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+        );
+        db.insert_account_info(
+            REVERTER,
+            AccountInfo::from_bytecode(Bytecode::new_raw(bytes!("0x60006000fd"))),
+        );
+        let mut db = State::builder().with_database(db).with_bundle_update().build();
+
+        let parent = SealedHeader::seal_slow(Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(0),
+            ..Default::default()
+        });
+        let attributes = NextBlockEnvAttributes {
+            timestamp: 1,
+            suggested_fee_recipient: refund_signer
+                .as_ref()
+                .map_or(COINBASE, BundleRefundSigner::address),
+            prev_randao: B256::ZERO,
+            gas_limit: parent.gas_limit,
+            parent_beacon_block_root: None,
+            withdrawals: None,
+        };
+        let evm_env = evm_config.next_evm_env(&parent, &attributes).unwrap();
+        let evm = evm_config.evm_with_env(&mut db, evm_env.clone());
+        let ctx = evm_config.context_for_next_block(&parent, attributes).unwrap();
+        let mut builder = evm_config.create_block_builder(evm, &parent, ctx);
+
+        let mut bundles = PayloadBundles::new(
+            evm_config.clone(),
+            evm_env,
+            refund_signer,
+            bundles,
+            &mut builder,
+            parent.gas_limit,
+        )
+        .unwrap();
+        bundles.execute(&mut builder, U256::ZERO, parent.gas_limit, usize::MAX).unwrap()
+    }
+
+    #[test]
+    fn skips_reverting_bundles() {
+        let reverting = bundle(vec![tx(0, COINBASE, VALUE), tx(1, REVERTER, 0)]);
+
+        let mut can_revert = bundle(vec![tx(0, COINBASE, VALUE), tx(1, REVERTER, 0)]);
+        can_revert.transactions[1].can_revert = true;
+        let expected = tx_hashes(can_revert.transactions.iter().map(|tx| &tx.tx));
+
+        let included = include_bundles(vec![reverting, can_revert], None);
+        assert_eq!(included.len(), 1);
+        assert_eq!(tx_hashes(&included[0].transactions), expected);
+        assert_eq!(included[0].value, U256::from(VALUE));
+    }
+
+    #[test]
+    fn drops_invalid_transactions() {
+        // the transaction with the nonce gap is invalid and can't be dropped
+        let invalid = bundle(vec![tx(0, COINBASE, VALUE), tx(5, COINBASE, VALUE)]);
+
+        let mut can_drop =
+            bundle(vec![tx(0, COINBASE, VALUE), tx(5, COINBASE, VALUE), tx(1, COINBASE, VALUE)]);
+        can_drop.transactions[1].can_drop = true;
+        let expected = tx_hashes([&can_drop.transactions[0].tx, &can_drop.transactions[2].tx]);
+
+        let included = include_bundles(vec![invalid, can_drop], None);
+        assert_eq!(included.len(), 1);
+        assert_eq!(tx_hashes(&included[0].transactions), expected);
+        assert_eq!(included[0].value, U256::from(2 * VALUE));
+        assert_eq!(included[0].gas_used, 2 * 21_000);
+    }
+
+    #[test]
+    fn pays_refunds() {
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(1)).unwrap();
+        let signer = BundleRefundSigner::new(signer);
+        let coinbase = signer.address();
+
+        let mut refunded = bundle(vec![tx(0, coinbase, VALUE)]);
+        refunded.refunds = vec![BundleRefund { recipient: RECIPIENT, percent: 40 }];
+
+        // refunds can only be paid if the refund signer is the fee recipient
+        assert!(include_bundles(vec![refunded.clone()], None).is_empty());
+
+        let included = include_bundles(vec![refunded], Some(signer));
+        assert_eq!(included.len(), 1);
+        assert_eq!(included[0].transactions.len(), 2);
+        let refund = &included[0].transactions[1];
+        assert_eq!(refund.signer(), coinbase);
+        assert_eq!(refund.to(), Some(RECIPIENT));
+        assert_eq!(refund.value(), U256::from(VALUE * 40 / 100));
+        assert_eq!(included[0].value, U256::from(VALUE * 60 / 100));
+        assert_eq!(included[0].gas_used, 2 * 21_000);
+    }
+}
@@ -1,5 +1,11 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+use alloy_primitives::Address;
+use alloy_signer_local::{LocalSignerError, PrivateKeySigner};
+use reth_ethereum_primitives::TransactionSigned;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
+use reth_revm::state_changes::PendingStateChanges;
+use reth_transaction_pool::BundlePool;
+use std::str::FromStr;
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -9,6 +15,12 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// State changes applied on top of the parent state of built blocks, used by dev chains.
+    pub state_changes: Option<PendingStateChanges>,
+    /// The pool of bundles that are included in built blocks alongside pool transactions.
+    pub bundles: Option<BundlePool<TransactionSigned>>,
+    /// The signer of the transfers that pay the refunds of included bundles.
+    pub refund_signer: Option<BundleRefundSigner>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +32,13 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            state_changes: None,
+            bundles: None,
+            refund_signer: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +53,24 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Configures the state changes applied on top of the parent state of built blocks.
+    pub fn with_state_changes(mut self, state_changes: PendingStateChanges) -> Self {
+        self.state_changes = Some(state_changes);
+        self
+    }
+
+    /// Configures the pool of bundles that are included in built blocks.
+    pub fn with_bundles(mut self, bundles: BundlePool<TransactionSigned>) -> Self {
+        self.bundles = Some(bundles);
+        self
+    }
+
+    /// Configures the signer of the transfers that pay the refunds of included bundles.
+    pub fn with_refund_signer(mut self, refund_signer: BundleRefundSigner) -> Self {
+        self.refund_signer = Some(refund_signer);
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -45,6 +81,48 @@ impl EthereumBuilderConfig {
     }
 }
 
+/// The signer of the transfers that pay the refunds of bundles.
+///
+/// Refunds are paid from the fee recipient of the block that receives the value of the bundles, so
+/// bundles that request a refund are only included in blocks whose fee recipient is the address of
+/// this signer.
+#[derive(Clone, Debug)]
+pub struct BundleRefundSigner(PrivateKeySigner);
+
+impl BundleRefundSigner {
+    /// Creates a new refund signer from the given signer.
+    pub const fn new(signer: PrivateKeySigner) -> Self {
+        Self(signer)
+    }
+
+    /// Returns the address the refunds are paid from.
+    pub const fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    /// Returns the underlying signer.
+    pub const fn signer(&self) -> &PrivateKeySigner {
+        &self.0
+    }
+}
+
+impl PartialEq for BundleRefundSigner {
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl Eq for BundleRefundSigner {}
+
+impl FromStr for BundleRefundSigner {
+    type Err = LocalSignerError;
+
+    /// Parses the hex encoded private key of the signer.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse().map(Self)
+    }
+}
+
 /// Calculate the gas limit for the next block based on parent and desired gas limits.
 /// Ref: <https://github.com/ethereum/go-ethereum/blob/88cbfab332c96edfbe99d161d9df6a40721bd786/core/block_validator.go#L166>
 pub fn calculate_block_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
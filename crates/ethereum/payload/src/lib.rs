@@ -9,13 +9,14 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
-use alloy_consensus::Transaction;
+use alloy_consensus::{conditional::BlockConditionalAttributes, BlockHeader, Transaction};
 use alloy_primitives::U256;
 use alloy_rlp::Encodable;
 use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
 };
+use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_consensus_common::validation::MAX_RLP_BLOCK_SIZE;
 use reth_errors::{BlockExecutionError, BlockValidationError, ConsensusError};
@@ -25,13 +26,17 @@ use reth_evm::{
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
 use reth_evm_ethereum::EthEvmConfig;
+use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
-use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_revm::{
+    database::StateProviderDatabase, db::State, state_changes::IMPERSONATED_SIGNATURE,
+};
 use reth_storage_api::StateProviderFactory;
 use reth_transaction_pool::{
+    conditional::ConditionalNotMetError,
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
@@ -40,6 +45,9 @@ use revm::context_interface::Block as _;
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
+mod bundles;
+use bundles::PayloadBundles;
+
 mod config;
 pub use config::*;
 
@@ -157,20 +165,45 @@ where
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-            },
-        )
+    // Blocks with state changes, transactions of impersonated accounts, or a gas limit or base fee
+    // that breaks the consensus rules, can't be re-executed from their transactions, so the
+    // executed block is attached to the payload to be inserted as is.
+    let (mut has_state_changes, block_env) = match &builder_config.state_changes {
+        Some(state_changes) => {
+            let block_env = state_changes.block_env();
+            (state_changes.apply(&mut db)? || block_env.is_consensus_breaking(), block_env)
+        }
+        None => (false, Default::default()),
+    };
+
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: block_env
+            .coinbase
+            .unwrap_or_else(|| attributes.suggested_fee_recipient()),
+        prev_randao: attributes.prev_randao(),
+        gas_limit: block_env
+            .gas_limit
+            .unwrap_or_else(|| builder_config.gas_limit(parent_header.gas_limit)),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+    };
+
+    let mut evm_env = evm_config
+        .next_evm_env(&parent_header, &next_block_attributes)
+        .map_err(PayloadBuilderError::other)?;
+    if let Some(base_fee) = block_env.base_fee {
+        evm_env.block_env.basefee = base_fee;
+    }
+
+    // bundles are simulated in the environment of the block being built
+    let bundles_evm_env = builder_config.bundles.as_ref().map(|_| evm_env.clone());
+
+    let evm = evm_config.evm_with_env(&mut db, evm_env);
+    let ctx = evm_config
+        .context_for_next_block(&parent_header, next_block_attributes)
         .map_err(PayloadBuilderError::other)?;
+    let mut builder = evm_config.create_block_builder(evm, &parent_header, ctx);
 
     let chain_spec = client.chain_spec();
 
@@ -202,8 +235,66 @@ where
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
     let is_osaka = chain_spec.is_osaka_active_at_timestamp(attributes.timestamp);
+    // the size that is still available for transactions in the block
+    let available_block_size = |block_transactions_rlp_length: usize| {
+        if is_osaka {
+            MAX_RLP_BLOCK_SIZE.saturating_sub(
+                block_transactions_rlp_length + attributes.withdrawals().length() + 1024,
+            )
+        } else {
+            usize::MAX
+        }
+    };
+
+    let mut bundles = match (&builder_config.bundles, bundles_evm_env) {
+        (Some(bundle_pool), Some(evm_env)) => Some(PayloadBundles::new(
+            evm_config.clone(),
+            evm_env,
+            builder_config.refund_signer.clone(),
+            bundle_pool.bundles_for(parent_header.number + 1, attributes.timestamp()),
+            &mut builder,
+            block_gas_limit,
+        )?),
+        _ => None,
+    };
+
+    let block_attributes = BlockConditionalAttributes {
+        number: parent_header.number + 1,
+        timestamp: attributes.timestamp(),
+    };
 
     while let Some(pool_tx) = best_txs.next() {
+        // skip transactions submitted with a conditional that isn't met by this block
+        if pool_tx
+            .transaction
+            .conditional()
+            .is_some_and(|conditional| !conditional.matches_block_attributes(&block_attributes))
+        {
+            best_txs.mark_invalid(
+                &pool_tx,
+                InvalidPoolTransactionError::Other(Box::new(ConditionalNotMetError {
+                    number: block_attributes.number,
+                    timestamp: block_attributes.timestamp,
+                })),
+            );
+            continue
+        }
+
+        // include the bundles that pay more per gas than the next pool transaction
+        if let Some(bundles) = &mut bundles {
+            let tip = pool_tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            for bundle in bundles.execute(
+                &mut builder,
+                U256::from(tip),
+                block_gas_limit - cumulative_gas_used,
+                available_block_size(block_transactions_rlp_length),
+            )? {
+                cumulative_gas_used += bundle.gas_used;
+                block_transactions_rlp_length += bundle.rlp_length();
+                total_fees += bundle.value;
+            }
+        }
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -318,6 +409,10 @@ where
             Err(err) => return Err(PayloadBuilderError::evm(err)),
         };
 
+        if builder_config.state_changes.is_some() && *tx.signature() == IMPERSONATED_SIGNATURE {
+            has_state_changes = true;
+        }
+
         // add to the total blob gas used if the transaction successfully executed
         if let Some(blob_tx) = tx.as_eip4844() {
             block_blob_count += blob_tx.tx().blob_versioned_hashes.len() as u64;
@@ -342,6 +437,18 @@ where
         }
     }
 
+    // include the remaining bundles at the end of the block
+    if let Some(bundles) = &mut bundles {
+        for bundle in bundles.execute(
+            &mut builder,
+            U256::ZERO,
+            block_gas_limit - cumulative_gas_used,
+            available_block_size(block_transactions_rlp_length),
+        )? {
+            total_fees += bundle.value;
+        }
+    }
+
     // check if we have a better block
     if !is_better_payload(best_payload.as_ref(), total_fees) {
         // Release db
@@ -350,7 +457,8 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
-    let BlockBuilderOutcome { execution_result, block, .. } = builder.finish(&state_provider)?;
+    let BlockBuilderOutcome { execution_result, block, hashed_state, trie_updates } =
+        builder.finish(&state_provider)?;
 
     let requests = chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
@@ -366,9 +474,26 @@ where
         }));
     }
 
-    let payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests)
+    let mut payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests)
         // add blob sidecars from the executed txs
         .with_sidecars(blob_sidecars);
 
+    if has_state_changes {
+        let execution_outcome = ExecutionOutcome::new(
+            db.take_bundle(),
+            vec![execution_result.receipts],
+            block.number(),
+            Vec::new(),
+        );
+        payload = payload.with_executed_block(ExecutedBlockWithTrieUpdates {
+            block: ExecutedBlock {
+                recovered_block: Arc::new(block),
+                execution_output: Arc::new(execution_outcome),
+                hashed_state: Arc::new(hashed_state),
+            },
+            trie: ExecutedTrieUpdates::Present(Arc::new(trie_updates)),
+        });
+    }
+
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
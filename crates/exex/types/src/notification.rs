@@ -66,6 +66,7 @@ impl<P: NodePrimitives> From<CanonStateNotification<P>> for ExExNotification<P>
         match notification {
             CanonStateNotification::Commit { new } => Self::ChainCommitted { new },
             CanonStateNotification::Reorg { old, new } => Self::ChainReorged { old, new },
+            CanonStateNotification::Revert { old } => Self::ChainReverted { old },
         }
     }
 }
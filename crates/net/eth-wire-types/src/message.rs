@@ -14,7 +14,7 @@ use super::{
 };
 use crate::{
     status::StatusMessage, BlockRangeUpdate, EthNetworkPrimitives, EthVersion, NetworkPrimitives,
-    RawCapabilityMessage, Receipts69, SharedTransactions,
+    RawCapabilityMessage, Receipts69, SharedTransactions, SnapProtocolMessage,
 };
 use alloc::{boxed::Box, string::String, sync::Arc};
 use alloy_primitives::{
@@ -382,6 +382,50 @@ impl<N: NetworkPrimitives> Encodable for EthMessage<N> {
     }
 }
 
+/// Combined message type that include either eth or snap protocol messages.
+///
+/// This is used by connections that negotiated both `eth` and `snap/1`, see also
+/// [`SnapProtocolMessage`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EthSnapMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
+    /// An Ethereum protocol message
+    Eth(EthMessage<N>),
+    /// A snap protocol message
+    Snap(SnapProtocolMessage),
+}
+
+// === impl EthSnapMessage ===
+
+impl<N: NetworkPrimitives> EthSnapMessage<N> {
+    /// Returns true if the message variant is a request.
+    pub const fn is_request(&self) -> bool {
+        match self {
+            Self::Eth(msg) => msg.is_request(),
+            Self::Snap(msg) => msg.is_request(),
+        }
+    }
+
+    /// Returns true if the message variant is a response to a request.
+    pub const fn is_response(&self) -> bool {
+        match self {
+            Self::Eth(msg) => msg.is_response(),
+            Self::Snap(msg) => msg.is_response(),
+        }
+    }
+}
+
+impl<N: NetworkPrimitives> From<EthMessage<N>> for EthSnapMessage<N> {
+    fn from(msg: EthMessage<N>) -> Self {
+        Self::Eth(msg)
+    }
+}
+
+impl<N: NetworkPrimitives> From<SnapProtocolMessage> for EthSnapMessage<N> {
+    fn from(msg: SnapProtocolMessage) -> Self {
+        Self::Snap(msg)
+    }
+}
+
 /// Represents broadcast messages of [`EthMessage`] with the same object that can be sent to
 /// multiple peers.
 ///
@@ -5,7 +5,8 @@
 
 use super::message::MAX_MESSAGE_SIZE;
 use crate::{
-    message::{EthBroadcastMessage, ProtocolBroadcastMessage},
+    errors::{EthHandshakeError, EthStreamError, P2PStreamError},
+    message::{EthBroadcastMessage, MessageError, ProtocolBroadcastMessage},
     EthMessage, EthMessageID, EthNetworkPrimitives, EthVersion, NetworkPrimitives, ProtocolMessage,
     RawCapabilityMessage, SnapMessageId, SnapProtocolMessage,
 };
@@ -13,6 +14,7 @@ use alloy_rlp::{Bytes, BytesMut, Encodable};
 use core::fmt::Debug;
 use futures::{Sink, SinkExt};
 use pin_project::pin_project;
+pub use reth_eth_wire_types::EthSnapMessage;
 use std::{
     marker::PhantomData,
     pin::Pin,
@@ -42,15 +44,29 @@ pub enum EthSnapStreamError {
     /// Status message received outside handshake
     #[error("status message received outside handshake")]
     StatusNotInHandshake,
+
+    /// Error of the underlying P2P connection.
+    #[error(transparent)]
+    P2PStreamError(#[from] P2PStreamError),
 }
 
-/// Combined message type that include either eth or snap protocol messages
-#[derive(Debug)]
-pub enum EthSnapMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
-    /// An Ethereum protocol message
-    Eth(EthMessage<N>),
-    /// A snap protocol message
-    Snap(SnapProtocolMessage),
+impl From<EthSnapStreamError> for EthStreamError {
+    fn from(err: EthSnapStreamError) -> Self {
+        match err {
+            EthSnapStreamError::InvalidMessage(_, msg) => {
+                Self::InvalidMessage(MessageError::Other(msg))
+            }
+            EthSnapStreamError::UnknownMessageId(message_id) => {
+                Self::UnsupportedMessage { message_id }
+            }
+            EthSnapStreamError::MessageTooLarge(size, _) => Self::MessageTooBig(size),
+            EthSnapStreamError::Rlp(err) => Self::InvalidMessage(MessageError::RlpError(err)),
+            EthSnapStreamError::StatusNotInHandshake => {
+                Self::EthHandshakeError(EthHandshakeError::StatusNotInHandshake)
+            }
+            EthSnapStreamError::P2PStreamError(err) => Self::P2PStreamError(err),
+        }
+    }
 }
 
 /// A stream implementation that can handle both eth and snap protocol messages
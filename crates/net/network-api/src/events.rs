@@ -1,8 +1,14 @@
 //! API related to listening for network events.
 
 use reth_eth_wire_types::{
-    message::RequestPair, BlockBodies, BlockHeaders, Capabilities, DisconnectReason, EthMessage,
-    EthNetworkPrimitives, EthVersion, GetBlockBodies, GetBlockHeaders, GetNodeData,
+    message::RequestPair,
+    snap::{
+        AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+        GetStorageRangesMessage, GetTrieNodesMessage, SnapProtocolMessage, StorageRangesMessage,
+        TrieNodesMessage,
+    },
+    BlockBodies, BlockHeaders, Capabilities, DisconnectReason, EthMessage, EthNetworkPrimitives,
+    EthSnapMessage, EthVersion, GetBlockBodies, GetBlockHeaders, GetNodeData,
     GetPooledTransactions, GetReceipts, NetworkPrimitives, NodeData, PooledTransactions, Receipts,
     Receipts69, UnifiedStatus,
 };
@@ -238,6 +244,42 @@ pub enum PeerRequest<N: NetworkPrimitives = EthNetworkPrimitives> {
         /// The channel to send the response for receipts.
         response: oneshot::Sender<RequestResult<Receipts69<N::Receipt>>>,
     },
+    /// Requests a range of accounts from the peer's state trie (`snap/1`).
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The request for the account range.
+        request: GetAccountRangeMessage,
+        /// The channel to send the response for the account range.
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    },
+    /// Requests storage slots of multiple accounts from the peer (`snap/1`).
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The request for the storage ranges.
+        request: GetStorageRangesMessage,
+        /// The channel to send the response for the storage ranges.
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    },
+    /// Requests contract bytecodes by hash from the peer (`snap/1`).
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The request for the bytecodes.
+        request: GetByteCodesMessage,
+        /// The channel to send the response for the bytecodes.
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    },
+    /// Requests state trie nodes by path from the peer (`snap/1`).
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The request for the trie nodes.
+        request: GetTrieNodesMessage,
+        /// The channel to send the response for the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    },
 }
 
 // === impl PeerRequest ===
@@ -257,29 +299,76 @@ impl<N: NetworkPrimitives> PeerRequest<N> {
             Self::GetNodeData { response, .. } => response.send(Err(err)).ok(),
             Self::GetReceipts { response, .. } => response.send(Err(err)).ok(),
             Self::GetReceipts69 { response, .. } => response.send(Err(err)).ok(),
+            Self::GetAccountRange { response, .. } => response.send(Err(err)).ok(),
+            Self::GetStorageRanges { response, .. } => response.send(Err(err)).ok(),
+            Self::GetByteCodes { response, .. } => response.send(Err(err)).ok(),
+            Self::GetTrieNodes { response, .. } => response.send(Err(err)).ok(),
         };
     }
 
-    /// Returns the [`EthMessage`] for this type
-    pub fn create_request_message(&self, request_id: u64) -> EthMessage<N> {
+    /// Returns `true` if this is a `snap` protocol request.
+    pub const fn is_snap_request(&self) -> bool {
+        matches!(
+            self,
+            Self::GetAccountRange { .. } |
+                Self::GetStorageRanges { .. } |
+                Self::GetByteCodes { .. } |
+                Self::GetTrieNodes { .. }
+        )
+    }
+
+    /// Returns the [`EthSnapMessage`] for this type
+    ///
+    /// The `request_id` of `snap` requests is overwritten with the given `request_id`.
+    pub fn create_request_message(&self, request_id: u64) -> EthSnapMessage<N> {
         match self {
             Self::GetBlockHeaders { request, .. } => {
-                EthMessage::GetBlockHeaders(RequestPair { request_id, message: *request })
+                EthMessage::GetBlockHeaders(RequestPair { request_id, message: *request }).into()
             }
             Self::GetBlockBodies { request, .. } => {
                 EthMessage::GetBlockBodies(RequestPair { request_id, message: request.clone() })
+                    .into()
             }
             Self::GetPooledTransactions { request, .. } => {
                 EthMessage::GetPooledTransactions(RequestPair {
                     request_id,
                     message: request.clone(),
                 })
+                .into()
             }
             Self::GetNodeData { request, .. } => {
-                EthMessage::GetNodeData(RequestPair { request_id, message: request.clone() })
+                EthMessage::GetNodeData(RequestPair { request_id, message: request.clone() }).into()
             }
             Self::GetReceipts { request, .. } | Self::GetReceipts69 { request, .. } => {
-                EthMessage::GetReceipts(RequestPair { request_id, message: request.clone() })
+                EthMessage::GetReceipts(RequestPair { request_id, message: request.clone() }).into()
+            }
+            Self::GetAccountRange { request, .. } => {
+                SnapProtocolMessage::GetAccountRange(GetAccountRangeMessage {
+                    request_id,
+                    ..request.clone()
+                })
+                .into()
+            }
+            Self::GetStorageRanges { request, .. } => {
+                SnapProtocolMessage::GetStorageRanges(GetStorageRangesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+                .into()
+            }
+            Self::GetByteCodes { request, .. } => {
+                SnapProtocolMessage::GetByteCodes(GetByteCodesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+                .into()
+            }
+            Self::GetTrieNodes { request, .. } => {
+                SnapProtocolMessage::GetTrieNodes(GetTrieNodesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+                .into()
             }
         }
     }
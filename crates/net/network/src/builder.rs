@@ -3,7 +3,7 @@
 use std::fmt::Debug;
 
 use crate::{
-    eth_requests::EthRequestHandler,
+    eth_requests::{EthRequestHandler, SnapRequestHandler},
     transactions::{
         config::{StrictEthAnnouncementFilter, TransactionPropagationKind},
         policy::NetworkPolicies,
@@ -72,6 +72,15 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         NetworkBuilder { network, request_handler, transactions }
     }
 
+    /// Creates a new [`SnapRequestHandler`] and wires it to the network.
+    ///
+    /// The returned handler must be spawned in order to serve `snap` requests.
+    pub fn snap_request_handler<Client>(&mut self, client: Client) -> SnapRequestHandler<Client> {
+        let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
+        self.network.set_snap_request_handler(tx);
+        SnapRequestHandler::new(client, rx)
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
@@ -13,6 +13,7 @@ use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{
     handshake::{EthHandshake, EthRlpxHandshake},
+    protocol::Protocol,
     EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols, NetworkPrimitives,
     UnifiedStatus,
 };
@@ -226,6 +227,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     handshake: Arc<dyn EthRlpxHandshake>,
     /// List of block hashes to check for required blocks.
     required_block_hashes: Vec<B256>,
+    /// Whether to announce `snap/1` in the hello message.
+    snap: bool,
 }
 
 impl NetworkConfigBuilder<EthNetworkPrimitives> {
@@ -267,6 +270,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             nat: None,
             handshake: Arc::new(EthHandshake::default()),
             required_block_hashes: Vec::new(),
+            snap: false,
         }
     }
 
@@ -545,6 +549,15 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Announces `snap/1` next to `eth` in the hello message.
+    ///
+    /// Sessions with peers that also support `snap/1` can then be used for `snap` requests, see
+    /// [`FetchClient`](crate::FetchClient).
+    pub const fn with_snap(mut self) -> Self {
+        self.snap = true;
+        self
+    }
+
     /// Sets whether tx gossip is disabled.
     pub const fn disable_tx_gossip(mut self, disable_tx_gossip: bool) -> Self {
         self.tx_gossip_disabled = disable_tx_gossip;
@@ -620,6 +633,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             nat,
             handshake,
             required_block_hashes,
+            snap,
         } = self;
 
         let head = head.unwrap_or_else(|| Head {
@@ -644,6 +658,10 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         let mut hello_message =
             hello_message.unwrap_or_else(|| HelloMessage::builder(peer_id).build());
         hello_message.port = listener_addr.port();
+        if snap {
+            // this is a noop if `snap/1` is already part of the hello message
+            let _ = hello_message.try_add_protocol(Protocol::snap_1());
+        }
 
         // set the status
         let status = UnifiedStatus::spec_builder(&chain_spec, &head);
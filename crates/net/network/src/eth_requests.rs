@@ -1,25 +1,39 @@
-//! Blocks/Headers management for the p2p network.
+//! Blocks/Headers and state management for the p2p network.
 
 use crate::{
-    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, metered_poll_nested_stream_with_budget,
-    metrics::EthRequestHandlerMetrics,
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+    metered_poll_nested_stream_with_budget,
+    metrics::{EthRequestHandlerMetrics, SnapRequestHandlerMetrics},
 };
 use alloy_consensus::{BlockHeader, ReceiptWithBloom};
 use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::{BlockNumber, Bytes, B256, KECCAK256_EMPTY};
 use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{
     BlockBodies, BlockHeaders, EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, GetNodeData,
     GetReceipts, HeadersDirection, NetworkPrimitives, NodeData, Receipts, Receipts69,
 };
+use reth_eth_wire_types::snap::{
+    AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, StorageData,
+    StorageRangesMessage, TrieNodesMessage,
+};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::error::RequestResult;
 use reth_network_peers::PeerId;
-use reth_primitives_traits::Block;
-use reth_storage_api::{BlockReader, HeaderProvider};
+use reth_primitives_traits::{Account, Block};
+use reth_storage_api::{
+    errors::provider::ProviderResult, BlockReader, HeaderProvider, SnapStateOverlay,
+    SnapStateProvider,
+};
+use reth_trie_common::{MultiProofTargets, Nibbles, EMPTY_ROOT_HASH};
 use std::{
+    collections::{BTreeMap, HashMap},
     future::Future,
+    iter,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -47,6 +61,21 @@ pub const MAX_BODIES_SERVE: usize = 1024;
 /// Maximum size of replies to data retrievals: 2MB
 pub const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 
+// Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/snap/handler.go#L34-L48>
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_BYTECODES_SERVE: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_TRIE_NODES_SERVE: usize = 1024;
+
+/// Default number of recently persisted state roots that are served over `snap`.
+pub const DEFAULT_SNAP_STATE_WINDOW: u64 = 128;
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -360,3 +389,823 @@ pub enum IncomingEthRequest<N: NetworkPrimitives = EthNetworkPrimitives> {
         response: oneshot::Sender<RequestResult<Receipts69<N::Receipt>>>,
     },
 }
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// Requests are answered from the persisted hashed state and tries of the most recently persisted
+/// blocks, see [`SnapRequestHandler::with_state_window`]. Requests for any other state root are
+/// answered with an empty response.
+///
+/// Requests are answered synchronously from the database, so this is supposed to be spawned as a
+/// blocking background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can access the state.
+    client: C,
+    /// Number of recently persisted state roots that are served.
+    state_window: u64,
+    /// The state roots of the served blocks.
+    state_roots: SnapStateRoots,
+    /// Incoming request from the [`NetworkManager`](crate::NetworkManager).
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            client,
+            state_window: DEFAULT_SNAP_STATE_WINDOW,
+            state_roots: Default::default(),
+            incoming_requests: ReceiverStream::new(incoming),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Sets the number of recently persisted state roots that are served.
+    ///
+    /// Defaults to [`DEFAULT_SNAP_STATE_WINDOW`].
+    pub const fn with_state_window(mut self, state_window: u64) -> Self {
+        self.state_window = state_window;
+        self
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: SnapStateProvider,
+{
+    /// Returns the overlay of the state with the given root if it can be served.
+    fn state_overlay(&mut self, state_root: B256) -> ProviderResult<Option<Arc<SnapStateOverlay>>> {
+        let Some(block_number) = self.state_block(state_root)? else { return Ok(None) };
+        self.state_roots.overlay(&self.client, block_number).map(Some)
+    }
+
+    /// Returns the number of the block with the given state root if it can be served.
+    fn state_block(&mut self, state_root: B256) -> ProviderResult<Option<BlockNumber>> {
+        let tip = self.client.snap_tip()?;
+        self.state_roots.update(&self.client, tip, self.state_window)?;
+        let Some(block_number) = self.state_roots.block_number(state_root) else { return Ok(None) };
+
+        // the persisted chain can be reorged without changing the tip, in which case the window
+        // is rebuilt
+        let current = self.client.snap_state_roots(block_number..=block_number)?;
+        if current.first().map(|(_, root)| *root) != Some(state_root) {
+            self.state_roots = SnapStateRoots::default();
+            self.state_roots.update(&self.client, tip, self.state_window)?;
+            return Ok(self.state_roots.block_number(state_root))
+        }
+
+        Ok(Some(block_number))
+    }
+
+    fn on_account_range_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetAccountRangeMessage,
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    ) {
+        self.metrics.snap_account_range_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let (accounts, proof) = self.get_account_range_response(request).unwrap_or_default();
+        let _ = response.send(Ok(AccountRangeMessage { request_id, accounts, proof }));
+    }
+
+    fn get_account_range_response(
+        &mut self,
+        request: GetAccountRangeMessage,
+    ) -> ProviderResult<(Vec<AccountData>, Vec<Bytes>)> {
+        let Some(overlay) = self.state_overlay(request.root_hash)? else {
+            return Ok(Default::default())
+        };
+        let limit = response_limit(request.response_bytes);
+
+        // the storage roots are not known yet, so the size of every account is estimated with a
+        // non-empty storage root
+        let mut accounts = Vec::new();
+        let mut total_bytes = 0;
+        for (hashed_address, account) in self.client.snap_account_range(
+            &overlay,
+            request.starting_hash,
+            request.limit_hash,
+            limit / B256::len_bytes() + 1,
+        )? {
+            total_bytes += B256::len_bytes() + slim_account_body(account, B256::ZERO).len();
+            accounts.push((hashed_address, account));
+
+            if total_bytes > limit {
+                break
+            }
+        }
+
+        // only the boundaries of the range are proven, like geth does
+        let boundaries =
+            iter::once(request.starting_hash).chain(accounts.last().map(|(hash, _)| *hash));
+        let multiproof = self
+            .client
+            .snap_multiproof(&overlay, MultiProofTargets::accounts(boundaries.clone()))?;
+        let proof = boundaries
+            .flat_map(|hash| multiproof.account_proof_nodes(&Nibbles::unpack(hash)))
+            .collect();
+
+        let hashes = accounts.iter().map(|(hash, _)| *hash).collect::<Vec<_>>();
+        let storage_roots = self.client.snap_storage_roots(&overlay, &hashes)?;
+        let accounts = accounts
+            .into_iter()
+            .zip(storage_roots)
+            .map(|((hash, account), storage_root)| AccountData {
+                hash,
+                body: slim_account_body(account, storage_root),
+            })
+            .collect();
+
+        Ok((accounts, dedup_proof_nodes(proof)))
+    }
+
+    fn on_storage_ranges_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetStorageRangesMessage,
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    ) {
+        self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let (slots, proof) = self.get_storage_ranges_response(request).unwrap_or_default();
+        let _ = response.send(Ok(StorageRangesMessage { request_id, slots, proof }));
+    }
+
+    fn get_storage_ranges_response(
+        &mut self,
+        request: GetStorageRangesMessage,
+    ) -> ProviderResult<(Vec<Vec<StorageData>>, Vec<Bytes>)> {
+        let Some(overlay) = self.state_overlay(request.root_hash)? else {
+            return Ok(Default::default())
+        };
+        let limit = response_limit(request.response_bytes);
+
+        let mut slots = Vec::new();
+        let mut proof = Vec::new();
+        let mut total_bytes = 0;
+
+        for hashed_address in request.account_hashes {
+            if total_bytes >= limit {
+                break
+            }
+
+            // the requested range only applies to the first account
+            let (start, end) = if slots.is_empty() {
+                (request.starting_hash, request.limit_hash)
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut account_slots = Vec::new();
+            let mut truncated = false;
+            for (hash, value) in self.client.snap_storage_range(
+                &overlay,
+                hashed_address,
+                start,
+                end,
+                (limit - total_bytes) / B256::len_bytes() + 1,
+            )? {
+                let data = Bytes::from(alloy_rlp::encode(value));
+                total_bytes += B256::len_bytes() + data.len();
+                account_slots.push(StorageData { hash, data });
+
+                if total_bytes > limit {
+                    truncated = true;
+                    break
+                }
+            }
+
+            // partial storage ranges must be proven, which is only supported for the last slot set
+            // of the response
+            if !start.is_zero() || truncated {
+                let boundaries =
+                    iter::once(start).chain(account_slots.last().map(|slot| slot.hash));
+                let multiproof = self.client.snap_multiproof(
+                    &overlay,
+                    MultiProofTargets::account_with_slots(hashed_address, boundaries.clone()),
+                )?;
+                proof = dedup_proof_nodes(
+                    multiproof
+                        .storage_proof_nodes(hashed_address, boundaries)
+                        .into_iter()
+                        .flat_map(|(_, nodes)| nodes)
+                        .collect(),
+                );
+                slots.push(account_slots);
+                break
+            }
+
+            slots.push(account_slots);
+        }
+
+        Ok((slots, proof))
+    }
+
+    fn on_byte_codes_request(
+        &self,
+        _peer_id: PeerId,
+        request: GetByteCodesMessage,
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    ) {
+        self.metrics.snap_byte_codes_requests_received_total.increment(1);
+
+        let limit = response_limit(request.response_bytes);
+        let mut codes = Vec::new();
+        let mut total_bytes = 0;
+
+        for code_hash in request.hashes.into_iter().take(MAX_BYTECODES_SERVE) {
+            let code = if code_hash == KECCAK256_EMPTY {
+                Bytes::new()
+            } else if let Some(bytecode) = self.client.snap_bytecode(code_hash).unwrap_or_default()
+            {
+                bytecode.original_bytes()
+            } else {
+                // unknown bytecodes are skipped
+                continue
+            };
+
+            total_bytes += code.len();
+            codes.push(code);
+
+            if total_bytes > limit {
+                break
+            }
+        }
+
+        let _ = response.send(Ok(ByteCodesMessage { request_id: request.request_id, codes }));
+    }
+
+    fn on_trie_nodes_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetTrieNodesMessage,
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    ) {
+        self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let nodes = self.get_trie_nodes_response(request).unwrap_or_default();
+        let _ = response.send(Ok(TrieNodesMessage { request_id, nodes }));
+    }
+
+    fn get_trie_nodes_response(
+        &mut self,
+        request: GetTrieNodesMessage,
+    ) -> ProviderResult<Vec<Bytes>> {
+        let Some(overlay) = self.state_overlay(request.root_hash)? else { return Ok(Vec::new()) };
+        let limit = response_limit(request.response_bytes);
+
+        // Every node is looked up by proving a key that is prefixed by the node's path. Lookups
+        // are collected until the first malformed path.
+        let mut lookups = Vec::new();
+        let mut targets = MultiProofTargets::default();
+        'paths: for path in request.paths {
+            if path.slot_paths.is_empty() {
+                let Some(account_path) = decode_compact_path(&path.account_path) else { break };
+                targets.entry(padded_path_key(&account_path)).or_default();
+                lookups.push((None, account_path));
+            } else {
+                // storage trie paths are prefixed by the full hashed address of the account
+                if path.account_path.len() != B256::len_bytes() {
+                    break
+                }
+                let hashed_address = B256::from_slice(&path.account_path);
+                for slot_path in path.slot_paths {
+                    let Some(slot_path) = decode_compact_path(&slot_path) else { break 'paths };
+                    targets.entry(hashed_address).or_default().insert(padded_path_key(&slot_path));
+                    lookups.push((Some(hashed_address), slot_path));
+                }
+            }
+
+            if lookups.len() >= MAX_TRIE_NODES_SERVE {
+                break
+            }
+        }
+        lookups.truncate(MAX_TRIE_NODES_SERVE);
+
+        if lookups.is_empty() {
+            return Ok(Vec::new())
+        }
+        let multiproof = self.client.snap_multiproof(&overlay, targets)?;
+
+        let mut nodes = Vec::new();
+        let mut total_bytes = 0;
+        for (hashed_address, path) in lookups {
+            let node = match hashed_address {
+                None => multiproof.account_subtree.get(&path),
+                Some(hashed_address) => multiproof
+                    .storages
+                    .get(&hashed_address)
+                    .and_then(|storage| storage.subtree.get(&path)),
+            };
+            // the response must not contain gaps
+            let Some(node) = node else { break };
+
+            total_bytes += node.len();
+            nodes.push(node.clone());
+
+            if total_bytes > limit {
+                break
+            }
+        }
+
+        Ok(nodes)
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: SnapStateProvider + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| {
+                match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_byte_codes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                }
+            },
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// The state roots of the most recently persisted blocks that are served over `snap`.
+#[derive(Debug, Default)]
+struct SnapStateRoots {
+    /// The persisted tip the window was last updated for.
+    tip: Option<BlockNumber>,
+    /// The state roots by block number.
+    roots: BTreeMap<BlockNumber, B256>,
+    /// The block numbers by state root.
+    ///
+    /// If multiple blocks have the same state root, the most recent one is tracked.
+    blocks: HashMap<B256, BlockNumber>,
+    /// The overlay of the most recently served state, which is reused until the tip changes.
+    overlay: Option<Arc<SnapStateOverlay>>,
+}
+
+impl SnapStateRoots {
+    /// Returns the number of the block with the given state root if it is in the window.
+    fn block_number(&self, state_root: B256) -> Option<BlockNumber> {
+        self.blocks.get(&state_root).copied()
+    }
+
+    /// Moves the window to end at the given persisted tip.
+    ///
+    /// Only the state roots of blocks that were not in the window yet are fetched.
+    fn update<C: SnapStateProvider>(
+        &mut self,
+        client: &C,
+        tip: BlockNumber,
+        window: u64,
+    ) -> ProviderResult<()> {
+        if self.tip == Some(tip) {
+            return Ok(())
+        }
+        // the overlay reverts the persisted state, so it depends on the tip
+        self.overlay = None;
+
+        // drop blocks that left the window or were unwound
+        let start = (tip + 1).saturating_sub(window);
+        self.roots.retain(|number, _| (start..=tip).contains(number));
+
+        // the retained blocks were replaced if the persisted chain was reorged in the meantime
+        if let Some((&number, &root)) = self.roots.last_key_value() &&
+            client.snap_state_roots(number..=number)?.first() != Some(&(number, root))
+        {
+            self.roots.clear();
+        }
+        let roots = &self.roots;
+        self.blocks.retain(|root, number| roots.get(number) == Some(root));
+
+        let from = self.roots.last_key_value().map_or(start, |(number, _)| number + 1);
+        if window > 0 && from <= tip {
+            for (number, root) in client.snap_state_roots(from..=tip)? {
+                self.roots.insert(number, root);
+                self.blocks.insert(root, number);
+            }
+        }
+        self.tip = Some(tip);
+
+        Ok(())
+    }
+
+    /// Returns the overlay of the state after the given block, which is only built if it isn't
+    /// cached yet.
+    fn overlay<C: SnapStateProvider>(
+        &mut self,
+        client: &C,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Arc<SnapStateOverlay>> {
+        if let Some(overlay) = &self.overlay &&
+            overlay.block_number == block_number
+        {
+            return Ok(Arc::clone(overlay))
+        }
+        let overlay = Arc::new(client.snap_state_overlay(block_number)?);
+        self.overlay = Some(Arc::clone(&overlay));
+        Ok(overlay)
+    }
+}
+
+/// All `snap` requests delegated by the network.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request an account range from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that requested the account range.
+        peer_id: PeerId,
+        /// The specific account range requested.
+        request: GetAccountRangeMessage,
+        /// The channel sender for the response containing the account range.
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    },
+    /// Request storage ranges from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that requested the storage ranges.
+        peer_id: PeerId,
+        /// The specific storage ranges requested.
+        request: GetStorageRangesMessage,
+        /// The channel sender for the response containing the storage ranges.
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    },
+    /// Request bytecodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that requested the bytecodes.
+        peer_id: PeerId,
+        /// The specific bytecodes requested.
+        request: GetByteCodesMessage,
+        /// The channel sender for the response containing the bytecodes.
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that requested the trie nodes.
+        peer_id: PeerId,
+        /// The specific trie nodes requested.
+        request: GetTrieNodesMessage,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    },
+}
+
+/// Returns the response size limit for the requested number of bytes.
+fn response_limit(response_bytes: u64) -> usize {
+    usize::try_from(response_bytes).unwrap_or(usize::MAX).min(SOFT_RESPONSE_LIMIT)
+}
+
+/// Encodes the account in the slim format used by `snap`, where an empty storage root and an
+/// empty code hash are replaced by empty bytes.
+fn slim_account_body(account: Account, storage_root: B256) -> Bytes {
+    let storage_root: &[u8] =
+        if storage_root == EMPTY_ROOT_HASH { &[] } else { storage_root.as_slice() };
+    let code_hash = account.bytecode_hash.filter(|hash| *hash != KECCAK256_EMPTY);
+    let code_hash: &[u8] = code_hash.as_ref().map_or(&[], |hash| hash.as_slice());
+
+    let header = alloy_rlp::Header {
+        list: true,
+        payload_length: account.nonce.length() +
+            account.balance.length() +
+            storage_root.length() +
+            code_hash.length(),
+    };
+    let mut out = Vec::with_capacity(header.length_with_payload());
+    header.encode(&mut out);
+    account.nonce.encode(&mut out);
+    account.balance.encode(&mut out);
+    storage_root.encode(&mut out);
+    code_hash.encode(&mut out);
+    out.into()
+}
+
+/// Sorts the proof nodes by their path and removes duplicates.
+fn dedup_proof_nodes(mut nodes: Vec<(Nibbles, Bytes)>) -> Vec<Bytes> {
+    nodes.sort_unstable_by_key(|(path, _)| *path);
+    nodes.dedup_by(|a, b| a.0 == b.0);
+    nodes.into_iter().map(|(_, node)| node).collect()
+}
+
+/// Decodes a hex-prefix encoded trie path of a `GetTrieNodes` request.
+///
+/// Returns `None` if the path is malformed.
+fn decode_compact_path(path: &[u8]) -> Option<Nibbles> {
+    let (&first, rest) = path.split_first()?;
+    let odd = first & 0x10 != 0;
+    if first >> 4 > 3 || rest.len() * 2 + usize::from(odd) > 64 {
+        return None
+    }
+
+    let mut nibbles = Nibbles::default();
+    if odd {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(&Nibbles::unpack(rest));
+    Some(nibbles)
+}
+
+/// Returns the key that is prefixed by the given path and padded with zeroes.
+fn padded_path_key(path: &Nibbles) -> B256 {
+    B256::right_padding_from(&path.pack())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, U256};
+    use reth_primitives_traits::Bytecode;
+    use reth_trie_common::MultiProof;
+    use std::{
+        ops::RangeInclusive,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use tokio::sync::mpsc;
+
+    /// Serves a fixed state for all blocks.
+    #[derive(Debug, Default)]
+    struct MockSnapState {
+        /// The state roots, indexed by block number.
+        roots: Vec<B256>,
+        accounts: Vec<(B256, Account)>,
+        /// The storage roots of accounts with storage.
+        storage_roots: HashMap<B256, B256>,
+        codes: HashMap<B256, Bytecode>,
+        /// Number of state roots that were looked up.
+        root_lookups: AtomicUsize,
+        /// Number of accounts that were proven.
+        proven_accounts: AtomicUsize,
+        /// Number of state overlays that were built.
+        overlay_builds: AtomicUsize,
+    }
+
+    impl SnapStateProvider for MockSnapState {
+        fn snap_tip(&self) -> ProviderResult<BlockNumber> {
+            Ok(self.roots.len() as u64 - 1)
+        }
+
+        fn snap_state_roots(
+            &self,
+            range: RangeInclusive<BlockNumber>,
+        ) -> ProviderResult<Vec<(BlockNumber, B256)>> {
+            let roots = range
+                .filter_map(|number| self.roots.get(number as usize).map(|root| (number, *root)))
+                .collect::<Vec<_>>();
+            self.root_lookups.fetch_add(roots.len(), Ordering::Relaxed);
+            Ok(roots)
+        }
+
+        fn snap_state_overlay(
+            &self,
+            block_number: BlockNumber,
+        ) -> ProviderResult<SnapStateOverlay> {
+            self.overlay_builds.fetch_add(1, Ordering::Relaxed);
+            Ok(SnapStateOverlay { block_number, ..Default::default() })
+        }
+
+        fn snap_account_range(
+            &self,
+            _overlay: &SnapStateOverlay,
+            start: B256,
+            limit: B256,
+            max_accounts: usize,
+        ) -> ProviderResult<Vec<(B256, Account)>> {
+            Ok(self
+                .accounts
+                .iter()
+                .filter(|(hash, _)| (start..=limit).contains(hash))
+                .take(max_accounts)
+                .copied()
+                .collect())
+        }
+
+        fn snap_storage_range(
+            &self,
+            _overlay: &SnapStateOverlay,
+            _hashed_address: B256,
+            _start: B256,
+            _limit: B256,
+            _max_slots: usize,
+        ) -> ProviderResult<Vec<(B256, U256)>> {
+            Ok(Vec::new())
+        }
+
+        fn snap_storage_roots(
+            &self,
+            _overlay: &SnapStateOverlay,
+            hashed_addresses: &[B256],
+        ) -> ProviderResult<Vec<B256>> {
+            Ok(hashed_addresses
+                .iter()
+                .map(|hash| self.storage_roots.get(hash).copied().unwrap_or(EMPTY_ROOT_HASH))
+                .collect())
+        }
+
+        fn snap_multiproof(
+            &self,
+            _overlay: &SnapStateOverlay,
+            targets: MultiProofTargets,
+        ) -> ProviderResult<MultiProof> {
+            self.proven_accounts.fetch_add(targets.len(), Ordering::Relaxed);
+            Ok(MultiProof::default())
+        }
+
+        fn snap_bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+            Ok(self.codes.get(&code_hash).cloned())
+        }
+    }
+
+    /// Spawns a handler for the given state and returns the channel to send requests to it.
+    fn spawn_snap_handler(
+        state: Arc<MockSnapState>,
+        state_window: u64,
+    ) -> mpsc::Sender<IncomingSnapRequest> {
+        let (tx, rx) = mpsc::channel(1);
+        tokio::spawn(SnapRequestHandler::new(state, rx).with_state_window(state_window));
+        tx
+    }
+
+    async fn request_account_range(
+        handler: &mpsc::Sender<IncomingSnapRequest>,
+        root_hash: B256,
+    ) -> AccountRangeMessage {
+        let (response, rx) = oneshot::channel();
+        let request = GetAccountRangeMessage {
+            request_id: 1,
+            root_hash,
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: SOFT_RESPONSE_LIMIT as u64,
+        };
+        handler
+            .send(IncomingSnapRequest::GetAccountRange {
+                peer_id: PeerId::random(),
+                request,
+                response,
+            })
+            .await
+            .unwrap();
+        rx.await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_snap_account_range_state_window() {
+        let account = Account { nonce: 1, ..Default::default() };
+        let state = Arc::new(MockSnapState {
+            roots: (0..4).map(|i| B256::with_last_byte(i + 1)).collect(),
+            accounts: vec![(B256::with_last_byte(1), account), (B256::with_last_byte(2), account)],
+            ..Default::default()
+        });
+        let handler = spawn_snap_handler(state.clone(), 2);
+
+        // the state root of the tip is served
+        let response = request_account_range(&handler, state.roots[3]).await;
+        assert_eq!(response.request_id, 1);
+        assert_eq!(
+            response.accounts.iter().map(|account| account.hash).collect::<Vec<_>>(),
+            vec![B256::with_last_byte(1), B256::with_last_byte(2)]
+        );
+        assert_eq!(response.accounts[0].body, slim_account_body(account, EMPTY_ROOT_HASH));
+
+        // state roots outside of the window and unknown state roots are not served
+        assert!(request_account_range(&handler, state.roots[1]).await.accounts.is_empty());
+        assert!(request_account_range(&handler, B256::repeat_byte(0xaa)).await.accounts.is_empty());
+        assert!(!request_account_range(&handler, state.roots[2]).await.accounts.is_empty());
+        assert!(!request_account_range(&handler, state.roots[2]).await.accounts.is_empty());
+
+        // the window is only fetched once, every served request verifies a single state root
+        assert_eq!(state.root_lookups.load(Ordering::Relaxed), 2 + 3);
+        // the overlay of the most recently served state is reused
+        assert_eq!(state.overlay_builds.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_snap_account_range_proves_boundaries() {
+        let account = Account { nonce: 1, ..Default::default() };
+        let storage_root = B256::repeat_byte(0x22);
+        let state = Arc::new(MockSnapState {
+            roots: vec![B256::ZERO],
+            accounts: (1..=100).map(|i| (B256::with_last_byte(i), account)).collect(),
+            storage_roots: HashMap::from_iter([(B256::with_last_byte(2), storage_root)]),
+            ..Default::default()
+        });
+        let handler = spawn_snap_handler(state.clone(), DEFAULT_SNAP_STATE_WINDOW);
+
+        let response = request_account_range(&handler, state.roots[0]).await;
+        assert_eq!(response.accounts.len(), 100);
+        assert_eq!(response.accounts[0].body, slim_account_body(account, EMPTY_ROOT_HASH));
+        assert_eq!(response.accounts[1].body, slim_account_body(account, storage_root));
+
+        // only the first and the last account are proven
+        assert_eq!(state.proven_accounts.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_snap_byte_codes() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let code_hash = keccak256(code.original_byte_slice());
+        let state = Arc::new(MockSnapState {
+            roots: vec![B256::ZERO],
+            codes: HashMap::from_iter([(code_hash, code.clone())]),
+            ..Default::default()
+        });
+        let handler = spawn_snap_handler(state, DEFAULT_SNAP_STATE_WINDOW);
+
+        let (response, rx) = oneshot::channel();
+        let request = GetByteCodesMessage {
+            request_id: 7,
+            hashes: vec![code_hash, B256::repeat_byte(0xaa), KECCAK256_EMPTY],
+            response_bytes: SOFT_RESPONSE_LIMIT as u64,
+        };
+        handler
+            .send(IncomingSnapRequest::GetByteCodes {
+                peer_id: PeerId::random(),
+                request,
+                response,
+            })
+            .await
+            .unwrap();
+
+        // unknown bytecodes are skipped
+        let response = rx.await.unwrap().unwrap();
+        assert_eq!(response.request_id, 7);
+        assert_eq!(response.codes, vec![code.original_bytes(), Bytes::new()]);
+    }
+
+    #[test]
+    fn test_decode_compact_path() {
+        assert_eq!(decode_compact_path(&[0x00]), Some(Nibbles::default()));
+        assert_eq!(decode_compact_path(&[0x00, 0xab]), Some(Nibbles::from_nibbles([0xa, 0xb])));
+        assert_eq!(
+            decode_compact_path(&[0x1a, 0xbc]),
+            Some(Nibbles::from_nibbles([0xa, 0xb, 0xc]))
+        );
+        assert_eq!(
+            decode_compact_path(&[0x3a, 0xbc]),
+            Some(Nibbles::from_nibbles([0xa, 0xb, 0xc]))
+        );
+
+        assert_eq!(decode_compact_path(&[]), None);
+        assert_eq!(decode_compact_path(&[0x40]), None);
+        assert!(decode_compact_path(&[[0x00].as_slice(), &[0xff; 32]].concat()).is_some());
+        assert!(decode_compact_path(&[[0x1f].as_slice(), &[0xff; 32]].concat()).is_none());
+    }
+
+    #[test]
+    fn test_slim_account_body() {
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        let body = slim_account_body(account, EMPTY_ROOT_HASH);
+        assert_eq!(body[..], [0xc4, 0x01, 0x02, 0x80, 0x80]);
+
+        let code_hash = B256::repeat_byte(0x11);
+        let storage_root = B256::repeat_byte(0x22);
+        let account = Account { bytecode_hash: Some(code_hash), ..account };
+        let body = slim_account_body(account, storage_root);
+        assert_eq!(body, Bytes::from(alloy_rlp::encode(account.into_trie_account(storage_root))));
+    }
+}
@@ -3,7 +3,13 @@
 use crate::{fetch::DownloadRequest, flattened_response::FlattenedResponse};
 use alloy_primitives::B256;
 use futures::{future, future::Either};
-use reth_eth_wire::{EthNetworkPrimitives, NetworkPrimitives};
+use reth_eth_wire::{
+    snap::{
+        AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+        GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
+    },
+    EthNetworkPrimitives, NetworkPrimitives,
+};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::{
     bodies::client::{BodiesClient, BodiesFut},
@@ -11,6 +17,7 @@ use reth_network_p2p::{
     error::{PeerRequestResult, RequestError},
     headers::client::{HeadersClient, HeadersRequest},
     priority::Priority,
+    snap::client::SnapClient,
     BlockClient,
 };
 use reth_network_peers::PeerId;
@@ -105,3 +112,86 @@ impl<N: NetworkPrimitives> BodiesClient for FetchClient<N> {
 impl<N: NetworkPrimitives> BlockClient for FetchClient<N> {
     type Block = N::Block;
 }
+
+// The `Output` futures of the [SnapClient] impl of [FetchClient] that either return a response or
+// an error.
+type SnapClientFuture<T> = Either<FlattenedResponse<T>, future::Ready<T>>;
+
+impl<N: NetworkPrimitives> SnapClient for FetchClient<N> {
+    type AccountRangeOutput = SnapClientFuture<PeerRequestResult<AccountRangeMessage>>;
+    type StorageRangesOutput = SnapClientFuture<PeerRequestResult<StorageRangesMessage>>;
+    type ByteCodesOutput = SnapClientFuture<PeerRequestResult<ByteCodesMessage>>;
+    type TrieNodesOutput = SnapClientFuture<PeerRequestResult<TrieNodesMessage>>;
+
+    /// Sends a `GetAccountRange` request to an available `snap` peer.
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRangeMessage,
+        priority: Priority,
+    ) -> Self::AccountRangeOutput {
+        let (response, rx) = oneshot::channel();
+        if self
+            .request_tx
+            .send(DownloadRequest::GetAccountRange { request, response, priority })
+            .is_ok()
+        {
+            Either::Left(FlattenedResponse::from(rx))
+        } else {
+            Either::Right(future::err(RequestError::ChannelClosed))
+        }
+    }
+
+    /// Sends a `GetStorageRanges` request to an available `snap` peer.
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRangesMessage,
+        priority: Priority,
+    ) -> Self::StorageRangesOutput {
+        let (response, rx) = oneshot::channel();
+        if self
+            .request_tx
+            .send(DownloadRequest::GetStorageRanges { request, response, priority })
+            .is_ok()
+        {
+            Either::Left(FlattenedResponse::from(rx))
+        } else {
+            Either::Right(future::err(RequestError::ChannelClosed))
+        }
+    }
+
+    /// Sends a `GetByteCodes` request to an available `snap` peer.
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodesMessage,
+        priority: Priority,
+    ) -> Self::ByteCodesOutput {
+        let (response, rx) = oneshot::channel();
+        if self
+            .request_tx
+            .send(DownloadRequest::GetByteCodes { request, response, priority })
+            .is_ok()
+        {
+            Either::Left(FlattenedResponse::from(rx))
+        } else {
+            Either::Right(future::err(RequestError::ChannelClosed))
+        }
+    }
+
+    /// Sends a `GetTrieNodes` request to an available `snap` peer.
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodesMessage,
+        priority: Priority,
+    ) -> Self::TrieNodesOutput {
+        let (response, rx) = oneshot::channel();
+        if self
+            .request_tx
+            .send(DownloadRequest::GetTrieNodes { request, response, priority })
+            .is_ok()
+        {
+            Either::Left(FlattenedResponse::from(rx))
+        } else {
+            Either::Right(future::err(RequestError::ChannelClosed))
+        }
+    }
+}
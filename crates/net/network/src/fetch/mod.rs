@@ -4,10 +4,16 @@ mod client;
 
 pub use client::FetchClient;
 
-use crate::{message::BlockRequest, session::BlockRangeInfo};
-use alloy_primitives::B256;
+use crate::{eth_requests::SOFT_RESPONSE_LIMIT, message::BlockRequest, session::BlockRangeInfo};
+use alloy_primitives::{keccak256, B256};
 use futures::StreamExt;
-use reth_eth_wire::{EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives};
+use reth_eth_wire::{
+    snap::{
+        AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+        GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
+    },
+    EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives,
+};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
@@ -31,6 +37,11 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 type InflightHeadersRequest<H> = Request<HeadersRequest, PeerRequestResult<Vec<H>>>;
 type InflightBodiesRequest<B> = Request<Vec<B256>, PeerRequestResult<Vec<B>>>;
 
+/// The response size budget for `snap` requests that don't specify a budget or exceed it.
+///
+/// This matches the soft limit we apply when serving requests.
+pub(crate) const SNAP_RESPONSE_BYTES_LIMIT: u64 = SOFT_RESPONSE_LIMIT as u64;
+
 /// Manages data fetching operations.
 ///
 /// This type is hooked into the staged sync pipeline and delegates download request to available
@@ -43,6 +54,8 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     inflight_headers_requests: HashMap<PeerId, InflightHeadersRequest<N::BlockHeader>>,
     /// Currently active [`GetBlockBodies`] requests
     inflight_bodies_requests: HashMap<PeerId, InflightBodiesRequest<N::BlockBody>>,
+    /// Currently active `snap` requests
+    inflight_snap_requests: HashMap<PeerId, InflightSnapRequest>,
     /// The list of _available_ peers for requests.
     peers: HashMap<PeerId, Peer>,
     /// The handle to the peers manager
@@ -65,6 +78,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         Self {
             inflight_headers_requests: Default::default(),
             inflight_bodies_requests: Default::default(),
+            inflight_snap_requests: Default::default(),
             peers: Default::default(),
             peers_handle,
             num_active_peers,
@@ -82,6 +96,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         best_number: u64,
         timeout: Arc<AtomicU64>,
         range_info: Option<BlockRangeInfo>,
+        supports_snap: bool,
     ) {
         self.peers.insert(
             peer_id,
@@ -92,6 +107,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 timeout,
                 last_response_likely_bad: false,
                 range_info,
+                supports_snap,
             },
         );
     }
@@ -110,6 +126,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(req) = self.inflight_bodies_requests.remove(peer) {
             let _ = req.response.send(Err(RequestError::ConnectionDropped));
         }
+        if let Some(req) = self.inflight_snap_requests.remove(peer) {
+            req.send_err_response(RequestError::ConnectionDropped);
+        }
     }
 
     /// Updates the block information for the peer.
@@ -137,7 +156,17 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     /// prioritizing those with the lowest timeout/latency and those that recently responded with
     /// adequate data.
     fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+        self.next_best_peer_by(|_| true)
+    }
+
+    /// Returns the _next_ idle peer that negotiated `snap/1`, see also [`Self::next_best_peer`].
+    fn next_best_snap_peer(&self) -> Option<PeerId> {
+        self.next_best_peer_by(|peer| peer.supports_snap)
+    }
+
+    /// Returns the _next_ best idle peer that matches the given filter.
+    fn next_best_peer_by(&self, filter: impl Fn(&Peer) -> bool) -> Option<PeerId> {
+        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle() && filter(peer));
 
         let mut best_peer = idle.next()?;
 
@@ -168,7 +197,22 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
         let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
 
-        let request = self.queued_requests.pop_front().expect("not empty");
+        // `snap` requests can only be served by peers that negotiated `snap/1`, so we pick the
+        // first queued request that can be served by one of the idle peers.
+        let snap_peer_id = self.next_best_snap_peer();
+        let Some((idx, peer_id)) =
+            self.queued_requests.iter().enumerate().find_map(|(idx, req)| {
+                if req.is_snap() {
+                    snap_peer_id.map(|snap_peer_id| (idx, snap_peer_id))
+                } else {
+                    Some((idx, peer_id))
+                }
+            })
+        else {
+            return PollAction::NoPeersAvailable
+        };
+
+        let request = self.queued_requests.remove(idx).expect("exists");
         let request = self.prepare_block_request(peer_id, request);
 
         PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
@@ -241,14 +285,50 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
+            DownloadRequest::GetAccountRange { request, response, .. } => {
+                let response_bytes = snap_response_bytes(request.response_bytes);
+                let request = GetAccountRangeMessage { response_bytes, ..request };
+                let inflight = Request { request: request.clone(), response };
+                self.inflight_snap_requests
+                    .insert(peer_id, InflightSnapRequest::AccountRange(inflight));
+                BlockRequest::GetAccountRange(request)
+            }
+            DownloadRequest::GetStorageRanges { request, response, .. } => {
+                let response_bytes = snap_response_bytes(request.response_bytes);
+                let request = GetStorageRangesMessage { response_bytes, ..request };
+                let inflight = Request { request: request.clone(), response };
+                self.inflight_snap_requests
+                    .insert(peer_id, InflightSnapRequest::StorageRanges(inflight));
+                BlockRequest::GetStorageRanges(request)
+            }
+            DownloadRequest::GetByteCodes { request, response, .. } => {
+                let response_bytes = snap_response_bytes(request.response_bytes);
+                let request = GetByteCodesMessage { response_bytes, ..request };
+                let inflight = Request { request: request.clone(), response };
+                self.inflight_snap_requests
+                    .insert(peer_id, InflightSnapRequest::ByteCodes(inflight));
+                BlockRequest::GetByteCodes(request)
+            }
+            DownloadRequest::GetTrieNodes { request, response, .. } => {
+                let response_bytes = snap_response_bytes(request.response_bytes);
+                let request = GetTrieNodesMessage { response_bytes, ..request };
+                let inflight = Request { request: request.clone(), response };
+                self.inflight_snap_requests
+                    .insert(peer_id, InflightSnapRequest::TrieNodes(inflight));
+                BlockRequest::GetTrieNodes(request)
+            }
         }
     }
 
     /// Returns a new followup request for the peer.
     ///
+    /// This picks the first queued request the peer is able to serve.
+    ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
-        let req = self.queued_requests.pop_front()?;
+        let supports_snap = self.peers.get(&peer_id).is_some_and(|peer| peer.supports_snap);
+        let idx = self.queued_requests.iter().position(|req| supports_snap || !req.is_snap())?;
+        let req = self.queued_requests.remove(idx)?;
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
     }
@@ -315,6 +395,87 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         None
     }
 
+    /// Called on a `GetAccountRange` response from a peer.
+    pub(crate) fn on_account_range_response(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<AccountRangeMessage>,
+    ) -> Option<BlockResponseOutcome> {
+        self.on_snap_response(peer_id, res)
+    }
+
+    /// Called on a `GetStorageRanges` response from a peer.
+    pub(crate) fn on_storage_ranges_response(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<StorageRangesMessage>,
+    ) -> Option<BlockResponseOutcome> {
+        self.on_snap_response(peer_id, res)
+    }
+
+    /// Called on a `GetByteCodes` response from a peer.
+    pub(crate) fn on_byte_codes_response(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<ByteCodesMessage>,
+    ) -> Option<BlockResponseOutcome> {
+        self.on_snap_response(peer_id, res)
+    }
+
+    /// Called on a `GetTrieNodes` response from a peer.
+    pub(crate) fn on_trie_nodes_response(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<TrieNodesMessage>,
+    ) -> Option<BlockResponseOutcome> {
+        self.on_snap_response(peer_id, res)
+    }
+
+    /// Delegates a `snap` response to the requester.
+    ///
+    /// Responses that don't match the request are delegated as [`RequestError::BadResponse`] and
+    /// the peer is reported. Empty responses are not penalized, since the peer may have pruned
+    /// the requested state root, but the peer is de-ranked.
+    fn on_snap_response<R: SnapResponse>(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<R>,
+    ) -> Option<BlockResponseOutcome> {
+        let mut reputation_change =
+            matches!(res, Err(RequestError::Timeout)).then_some(ReputationChangeKind::Timeout);
+
+        let inflight = self.inflight_snap_requests.remove(&peer_id).and_then(R::take_inflight);
+
+        let res = match res {
+            Ok(resp) if inflight.as_ref().is_some_and(|req| !resp.is_valid_for(&req.request)) => {
+                reputation_change = Some(ReputationChangeKind::BadMessage);
+                Err(RequestError::BadResponse)
+            }
+            res => res,
+        };
+
+        let is_likely_bad_response = res.as_ref().map_or(true, |resp| resp.is_empty());
+
+        if let Some(inflight) = inflight {
+            let _ = inflight.response.send(res.map(|resp| (peer_id, resp).into()));
+        }
+
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            // update the peer's response state
+            peer.last_response_likely_bad = is_likely_bad_response;
+
+            if peer.state.on_request_finished() &&
+                reputation_change.is_none() &&
+                !is_likely_bad_response
+            {
+                return self.followup_request(peer_id)
+            }
+        }
+
+        reputation_change
+            .map(|reputation_change| BlockResponseOutcome::BadResponse(peer_id, reputation_change))
+    }
+
     /// Returns a new [`FetchClient`] that can send requests to this type.
     pub(crate) fn client(&self) -> FetchClient<N> {
         FetchClient {
@@ -353,6 +514,8 @@ struct Peer {
     /// Tracks the range info for the peer.
     #[allow(dead_code)]
     range_info: Option<BlockRangeInfo>,
+    /// Whether the peer's session negotiated `snap/1`.
+    supports_snap: bool,
 }
 
 impl Peer {
@@ -370,6 +533,14 @@ enum PeerState {
     GetBlockHeaders,
     /// Peer is handling a `GetBlockBodies` request.
     GetBlockBodies,
+    /// Peer is handling a `GetAccountRange` request.
+    GetAccountRange,
+    /// Peer is handling a `GetStorageRanges` request.
+    GetStorageRanges,
+    /// Peer is handling a `GetByteCodes` request.
+    GetByteCodes,
+    /// Peer is handling a `GetTrieNodes` request.
+    GetTrieNodes,
     /// Peer session is about to close
     Closing,
 }
@@ -406,8 +577,164 @@ struct Request<Req, Resp> {
     response: oneshot::Sender<Resp>,
 }
 
+/// An inflight `snap` request.
+///
+/// A peer only handles one request at a time, so all `snap` request kinds share a single map.
+#[derive(Debug)]
+enum InflightSnapRequest {
+    AccountRange(Request<GetAccountRangeMessage, PeerRequestResult<AccountRangeMessage>>),
+    StorageRanges(Request<GetStorageRangesMessage, PeerRequestResult<StorageRangesMessage>>),
+    ByteCodes(Request<GetByteCodesMessage, PeerRequestResult<ByteCodesMessage>>),
+    TrieNodes(Request<GetTrieNodesMessage, PeerRequestResult<TrieNodesMessage>>),
+}
+
+// === impl InflightSnapRequest ===
+
+impl InflightSnapRequest {
+    /// Sends the error to the receiver of the request.
+    fn send_err_response(self, err: RequestError) {
+        let _ = match self {
+            Self::AccountRange(req) => req.response.send(Err(err)).ok(),
+            Self::StorageRanges(req) => req.response.send(Err(err)).ok(),
+            Self::ByteCodes(req) => req.response.send(Err(err)).ok(),
+            Self::TrieNodes(req) => req.response.send(Err(err)).ok(),
+        };
+    }
+}
+
+/// Helper trait to handle the different `snap` responses uniformly.
+trait SnapResponse: Sized {
+    /// The request type this is a response to.
+    type Request;
+
+    /// Returns the inflight request if it matches this response type.
+    fn take_inflight(
+        inflight: InflightSnapRequest,
+    ) -> Option<Request<Self::Request, PeerRequestResult<Self>>>;
+
+    /// Returns `true` if the response does not violate the request.
+    ///
+    /// This only performs cheap sanity checks, proofs are verified by the requester.
+    fn is_valid_for(&self, request: &Self::Request) -> bool;
+
+    /// Returns `true` if the response contains no data at all.
+    fn is_empty(&self) -> bool;
+}
+
+impl SnapResponse for AccountRangeMessage {
+    type Request = GetAccountRangeMessage;
+
+    fn take_inflight(
+        inflight: InflightSnapRequest,
+    ) -> Option<Request<Self::Request, PeerRequestResult<Self>>> {
+        match inflight {
+            InflightSnapRequest::AccountRange(req) => Some(req),
+            _ => None,
+        }
+    }
+
+    fn is_valid_for(&self, request: &Self::Request) -> bool {
+        // accounts must be strictly increasing and start at the requested origin
+        self.accounts.first().is_none_or(|account| account.hash >= request.starting_hash) &&
+            self.accounts.windows(2).all(|w| w[0].hash < w[1].hash)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.proof.is_empty()
+    }
+}
+
+impl SnapResponse for StorageRangesMessage {
+    type Request = GetStorageRangesMessage;
+
+    fn take_inflight(
+        inflight: InflightSnapRequest,
+    ) -> Option<Request<Self::Request, PeerRequestResult<Self>>> {
+        match inflight {
+            InflightSnapRequest::StorageRanges(req) => Some(req),
+            _ => None,
+        }
+    }
+
+    fn is_valid_for(&self, request: &Self::Request) -> bool {
+        // the origin only applies to the first account
+        self.slots.len() <= request.account_hashes.len() &&
+            self.slots
+                .first()
+                .and_then(|slots| slots.first())
+                .is_none_or(|slot| slot.hash >= request.starting_hash) &&
+            self.slots.iter().all(|slots| slots.windows(2).all(|w| w[0].hash < w[1].hash))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.slots.is_empty() && self.proof.is_empty()
+    }
+}
+
+impl SnapResponse for ByteCodesMessage {
+    type Request = GetByteCodesMessage;
+
+    fn take_inflight(
+        inflight: InflightSnapRequest,
+    ) -> Option<Request<Self::Request, PeerRequestResult<Self>>> {
+        match inflight {
+            InflightSnapRequest::ByteCodes(req) => Some(req),
+            _ => None,
+        }
+    }
+
+    fn is_valid_for(&self, request: &Self::Request) -> bool {
+        // codes must be delivered in request order, but unavailable codes may be skipped
+        let mut hashes = request.hashes.iter();
+        self.codes.iter().all(|code| {
+            let hash = keccak256(code);
+            hashes.any(|requested| *requested == hash)
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+}
+
+impl SnapResponse for TrieNodesMessage {
+    type Request = GetTrieNodesMessage;
+
+    fn take_inflight(
+        inflight: InflightSnapRequest,
+    ) -> Option<Request<Self::Request, PeerRequestResult<Self>>> {
+        match inflight {
+            InflightSnapRequest::TrieNodes(req) => Some(req),
+            _ => None,
+        }
+    }
+
+    fn is_valid_for(&self, request: &Self::Request) -> bool {
+        // a path set without slot paths requests the account trie node itself
+        let requested =
+            request.paths.iter().map(|path| path.slot_paths.len().max(1)).sum::<usize>();
+        self.nodes.len() <= requested
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+/// Returns the response size budget for a `snap` request.
+///
+/// A budget of zero or above [`SNAP_RESPONSE_BYTES_LIMIT`] is capped to the limit.
+const fn snap_response_bytes(response_bytes: u64) -> u64 {
+    if response_bytes == 0 || response_bytes > SNAP_RESPONSE_BYTES_LIMIT {
+        SNAP_RESPONSE_BYTES_LIMIT
+    } else {
+        response_bytes
+    }
+}
+
 /// Requests that can be sent to the Syncer from a [`FetchClient`]
 #[derive(Debug)]
+#[expect(clippy::enum_variant_names)]
 pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
     /// Download the requested headers and send response through channel
     GetBlockHeaders {
@@ -423,6 +750,30 @@ pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
         #[allow(dead_code)]
         range_hint: Option<RangeInclusive<u64>>,
     },
+    /// Download an account range from a `snap` peer and send response through channel
+    GetAccountRange {
+        request: GetAccountRangeMessage,
+        response: oneshot::Sender<PeerRequestResult<AccountRangeMessage>>,
+        priority: Priority,
+    },
+    /// Download storage ranges from a `snap` peer and send response through channel
+    GetStorageRanges {
+        request: GetStorageRangesMessage,
+        response: oneshot::Sender<PeerRequestResult<StorageRangesMessage>>,
+        priority: Priority,
+    },
+    /// Download bytecodes from a `snap` peer and send response through channel
+    GetByteCodes {
+        request: GetByteCodesMessage,
+        response: oneshot::Sender<PeerRequestResult<ByteCodesMessage>>,
+        priority: Priority,
+    },
+    /// Download trie nodes from a `snap` peer and send response through channel
+    GetTrieNodes {
+        request: GetTrieNodesMessage,
+        response: oneshot::Sender<PeerRequestResult<TrieNodesMessage>>,
+        priority: Priority,
+    },
 }
 
 // === impl DownloadRequest ===
@@ -433,18 +784,36 @@ impl<N: NetworkPrimitives> DownloadRequest<N> {
         match self {
             Self::GetBlockHeaders { .. } => PeerState::GetBlockHeaders,
             Self::GetBlockBodies { .. } => PeerState::GetBlockBodies,
+            Self::GetAccountRange { .. } => PeerState::GetAccountRange,
+            Self::GetStorageRanges { .. } => PeerState::GetStorageRanges,
+            Self::GetByteCodes { .. } => PeerState::GetByteCodes,
+            Self::GetTrieNodes { .. } => PeerState::GetTrieNodes,
         }
     }
 
     /// Returns the requested priority of this request
     const fn get_priority(&self) -> &Priority {
         match self {
-            Self::GetBlockHeaders { priority, .. } | Self::GetBlockBodies { priority, .. } => {
-                priority
-            }
+            Self::GetBlockHeaders { priority, .. } |
+            Self::GetBlockBodies { priority, .. } |
+            Self::GetAccountRange { priority, .. } |
+            Self::GetStorageRanges { priority, .. } |
+            Self::GetByteCodes { priority, .. } |
+            Self::GetTrieNodes { priority, .. } => priority,
         }
     }
 
+    /// Returns `true` if this is a `snap` request that requires a `snap/1` peer.
+    const fn is_snap(&self) -> bool {
+        matches!(
+            self,
+            Self::GetAccountRange { .. } |
+                Self::GetStorageRanges { .. } |
+                Self::GetByteCodes { .. } |
+                Self::GetTrieNodes { .. }
+        )
+    }
+
     /// Returns `true` if this request is normal priority.
     const fn is_normal_priority(&self) -> bool {
         self.get_priority().is_normal()
@@ -511,8 +880,8 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)), None);
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)), None);
+        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)), None, false);
+        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)), None, false);
 
         let first_peer = fetcher.next_best_peer().unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
@@ -539,9 +908,23 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)), None);
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout), None);
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)), None);
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(30)),
+            None,
+            false,
+        );
+        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout), None, false);
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            3,
+            Arc::new(AtomicU64::new(50)),
+            None,
+            false,
+        );
 
         // Must always get peer1 (lowest timeout)
         assert_eq!(fetcher.next_best_peer(), Some(peer1));
@@ -611,6 +994,7 @@ mod tests {
             Default::default(),
             Default::default(),
             None,
+            false,
         );
 
         let (req, header) = request_pair();
@@ -639,4 +1023,122 @@ mod tests {
 
         assert!(fetcher.peers[&peer_id].state.is_idle());
     }
+
+    #[tokio::test]
+    async fn test_snap_request_requires_snap_peer() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let eth_peer = B512::random();
+        let snap_peer = B512::random();
+
+        // the eth-only peer has the lower timeout and would be preferred otherwise
+        fetcher.new_active_peer(
+            eth_peer,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(1)),
+            None,
+            false,
+        );
+
+        let (tx, _rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetAccountRange {
+            request: GetAccountRangeMessage {
+                request_id: 0,
+                root_hash: B256::random(),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 0,
+            },
+            response: tx,
+            priority: Priority::default(),
+        });
+        assert!(matches!(fetcher.poll_action(), PollAction::NoPeersAvailable));
+
+        fetcher.new_active_peer(
+            snap_peer,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(50)),
+            None,
+            true,
+        );
+
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, request }) =
+            fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, snap_peer);
+        let BlockRequest::GetAccountRange(request) = request else { unreachable!() };
+        assert_eq!(request.response_bytes, SNAP_RESPONSE_BYTES_LIMIT);
+        assert!(fetcher.queued_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bad_account_range_response() {
+        use reth_eth_wire::snap::AccountData;
+
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer_id = B512::random();
+        fetcher.new_active_peer(peer_id, B256::random(), 1, Default::default(), None, true);
+
+        let request = GetAccountRangeMessage {
+            request_id: 0,
+            root_hash: B256::random(),
+            starting_hash: B256::repeat_byte(0x10),
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: SNAP_RESPONSE_BYTES_LIMIT,
+        };
+        let account =
+            |byte| AccountData { hash: B256::repeat_byte(byte), body: Default::default() };
+
+        // accounts out of order
+        let (tx, rx) = oneshot::channel();
+        fetcher.inflight_snap_requests.insert(
+            peer_id,
+            InflightSnapRequest::AccountRange(Request { request: request.clone(), response: tx }),
+        );
+        let response = AccountRangeMessage {
+            request_id: 0,
+            accounts: vec![account(0x30), account(0x20)],
+            proof: vec![],
+        };
+        assert_eq!(
+            fetcher.on_account_range_response(peer_id, Ok(response)),
+            Some(BlockResponseOutcome::BadResponse(peer_id, ReputationChangeKind::BadMessage))
+        );
+        assert_eq!(rx.await.unwrap().unwrap_err(), RequestError::BadResponse);
+
+        // accounts before the requested origin
+        let (tx, rx) = oneshot::channel();
+        fetcher.inflight_snap_requests.insert(
+            peer_id,
+            InflightSnapRequest::AccountRange(Request { request: request.clone(), response: tx }),
+        );
+        let response =
+            AccountRangeMessage { request_id: 0, accounts: vec![account(0x01)], proof: vec![] };
+        assert_eq!(
+            fetcher.on_account_range_response(peer_id, Ok(response)),
+            Some(BlockResponseOutcome::BadResponse(peer_id, ReputationChangeKind::BadMessage))
+        );
+        assert!(rx.await.unwrap().is_err());
+
+        // valid response
+        let (tx, rx) = oneshot::channel();
+        fetcher
+            .inflight_snap_requests
+            .insert(peer_id, InflightSnapRequest::AccountRange(Request { request, response: tx }));
+        let response = AccountRangeMessage {
+            request_id: 0,
+            accounts: vec![account(0x20), account(0x30)],
+            proof: vec![],
+        };
+        assert_eq!(fetcher.on_account_range_response(peer_id, Ok(response.clone())), None);
+        assert_eq!(rx.await.unwrap().unwrap().into_data(), response);
+        assert!(fetcher.peers[&peer_id].state.is_idle());
+    }
 }
@@ -20,7 +20,7 @@ use crate::{
     config::NetworkConfig,
     discovery::Discovery,
     error::{NetworkError, ServiceKind},
-    eth_requests::IncomingEthRequest,
+    eth_requests::{IncomingEthRequest, IncomingSnapRequest},
     import::{BlockImport, BlockImportEvent, BlockImportOutcome, BlockValidation, NewBlockEvent},
     listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage},
@@ -47,6 +47,7 @@ use reth_network_api::{
     test_utils::PeersHandle,
     EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
 };
+use reth_network_p2p::error::RequestError;
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::ReputationChangeKind;
 use reth_storage_api::BlockNumReader;
@@ -130,6 +131,11 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// requests. This channel size is set at
     /// [`ETH_REQUEST_CHANNEL_CAPACITY`](crate::builder::ETH_REQUEST_CHANNEL_CAPACITY)
     to_eth_request_handler: Option<mpsc::Sender<IncomingEthRequest<N>>>,
+    /// Sender half to send events to the
+    /// [`SnapRequestHandler`](crate::eth_requests::SnapRequestHandler) task, if configured.
+    ///
+    /// Bounded for the same reasons as the channel to the eth request handler.
+    to_snap_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
     /// Tracks the number of active session (connected peers).
     ///
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
@@ -191,6 +197,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         self.to_eth_request_handler = Some(tx);
     }
 
+    /// Sets the dedicated channel for events intended for the
+    /// [`SnapRequestHandler`](crate::eth_requests::SnapRequestHandler).
+    pub fn with_snap_request_handler(mut self, tx: mpsc::Sender<IncomingSnapRequest>) -> Self {
+        self.set_snap_request_handler(tx);
+        self
+    }
+
+    /// Sets the dedicated channel for events intended for the
+    /// [`SnapRequestHandler`](crate::eth_requests::SnapRequestHandler).
+    pub fn set_snap_request_handler(&mut self, tx: mpsc::Sender<IncomingSnapRequest>) {
+        self.to_snap_request_handler = Some(tx);
+    }
+
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
     pub fn add_rlpx_sub_protocol(&mut self, protocol: impl IntoRlpxSubProtocol) {
         self.swarm.add_rlpx_sub_protocol(protocol)
@@ -351,6 +370,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             event_sender,
             to_transactions_manager: None,
             to_eth_request_handler: None,
+            to_snap_request_handler: None,
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
@@ -494,6 +514,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         }
     }
 
+    /// Sends an event to the [`SnapRequestHandler`](crate::eth_requests::SnapRequestHandler) if
+    /// configured.
+    fn delegate_snap_request(&self, event: IncomingSnapRequest) {
+        if let Some(ref reqs) = self.to_snap_request_handler {
+            let _ = reqs.try_send(event).map_err(|e| {
+                if let TrySendError::Full(_) = e {
+                    debug!(target:"net", "SnapRequestHandler channel is full!");
+                    self.metrics.total_dropped_snap_requests_at_full_capacity.increment(1);
+                }
+            });
+        }
+    }
+
     /// Handle an incoming request from the peer
     fn on_eth_request(&self, peer_id: PeerId, req: PeerRequest<N>) {
         match req {
@@ -539,6 +572,43 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     response,
                 });
             }
+            req @ (PeerRequest::GetAccountRange { .. } |
+            PeerRequest::GetStorageRanges { .. } |
+            PeerRequest::GetByteCodes { .. } |
+            PeerRequest::GetTrieNodes { .. })
+                if self.to_snap_request_handler.is_none() =>
+            {
+                // serving `snap` requests is not configured
+                req.send_err_response(RequestError::UnsupportedCapability);
+            }
+            PeerRequest::GetAccountRange { request, response } => {
+                self.delegate_snap_request(IncomingSnapRequest::GetAccountRange {
+                    peer_id,
+                    request,
+                    response,
+                })
+            }
+            PeerRequest::GetStorageRanges { request, response } => {
+                self.delegate_snap_request(IncomingSnapRequest::GetStorageRanges {
+                    peer_id,
+                    request,
+                    response,
+                })
+            }
+            PeerRequest::GetByteCodes { request, response } => {
+                self.delegate_snap_request(IncomingSnapRequest::GetByteCodes {
+                    peer_id,
+                    request,
+                    response,
+                })
+            }
+            PeerRequest::GetTrieNodes { request, response } => {
+                self.delegate_snap_request(IncomingSnapRequest::GetTrieNodes {
+                    peer_id,
+                    request,
+                    response,
+                })
+            }
         }
     }
 
@@ -13,7 +13,14 @@ use reth_eth_wire::{
     NewBlockHashes, NewBlockPayload, NewPooledTransactionHashes, NodeData, PooledTransactions,
     Receipts, SharedTransactions, Transactions,
 };
-use reth_eth_wire_types::RawCapabilityMessage;
+use reth_eth_wire_types::{
+    snap::{
+        AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+        GetStorageRangesMessage, GetTrieNodesMessage, SnapProtocolMessage, StorageRangesMessage,
+        TrieNodesMessage,
+    },
+    EthSnapMessage, RawCapabilityMessage,
+};
 use reth_network_api::PeerRequest;
 use reth_network_p2p::error::{RequestError, RequestResult};
 use reth_primitives_traits::Block;
@@ -65,7 +72,7 @@ pub enum PeerMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
     Other(RawCapabilityMessage),
 }
 
-/// Request Variants that only target block related data.
+/// Request Variants that only target block related data or `snap` state data.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BlockRequest {
     /// Requests block headers from the peer.
@@ -77,6 +84,26 @@ pub enum BlockRequest {
     ///
     /// The response should be sent through the channel.
     GetBlockBodies(GetBlockBodies),
+
+    /// Requests an account range from a `snap` peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange(GetAccountRangeMessage),
+
+    /// Requests storage ranges from a `snap` peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges(GetStorageRangesMessage),
+
+    /// Requests bytecodes from a `snap` peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes(GetByteCodesMessage),
+
+    /// Requests trie nodes from a `snap` peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes(GetTrieNodesMessage),
 }
 
 /// Corresponding variant for [`PeerRequest`].
@@ -116,6 +143,26 @@ pub enum PeerResponse<N: NetworkPrimitives = EthNetworkPrimitives> {
         /// The receiver channel for the response to a receipts request.
         response: oneshot::Receiver<RequestResult<Receipts69<N::Receipt>>>,
     },
+    /// Represents a response to a `snap` request for an account range.
+    AccountRange {
+        /// The receiver channel for the response to an account range request.
+        response: oneshot::Receiver<RequestResult<AccountRangeMessage>>,
+    },
+    /// Represents a response to a `snap` request for storage ranges.
+    StorageRanges {
+        /// The receiver channel for the response to a storage ranges request.
+        response: oneshot::Receiver<RequestResult<StorageRangesMessage>>,
+    },
+    /// Represents a response to a `snap` request for bytecodes.
+    ByteCodes {
+        /// The receiver channel for the response to a bytecodes request.
+        response: oneshot::Receiver<RequestResult<ByteCodesMessage>>,
+    },
+    /// Represents a response to a `snap` request for trie nodes.
+    TrieNodes {
+        /// The receiver channel for the response to a trie nodes request.
+        response: oneshot::Receiver<RequestResult<TrieNodesMessage>>,
+    },
 }
 
 // === impl PeerResponse ===
@@ -132,6 +179,16 @@ impl<N: NetworkPrimitives> PeerResponse<N> {
             };
         }
 
+        // `snap` responses are not wrapped and carry the request id themselves
+        macro_rules! poll_snap_request {
+            ($response:ident, $item:ident, $cx:ident) => {
+                match ready!($response.poll_unpin($cx)) {
+                    Ok(res) => PeerResponseResult::$item(res),
+                    Err(err) => PeerResponseResult::$item(Err(err.into())),
+                }
+            };
+        }
+
         let res = match self {
             Self::BlockHeaders { response } => {
                 poll_request!(response, BlockHeaders, cx)
@@ -151,6 +208,18 @@ impl<N: NetworkPrimitives> PeerResponse<N> {
             Self::Receipts69 { response } => {
                 poll_request!(response, Receipts69, cx)
             }
+            Self::AccountRange { response } => {
+                poll_snap_request!(response, AccountRange, cx)
+            }
+            Self::StorageRanges { response } => {
+                poll_snap_request!(response, StorageRanges, cx)
+            }
+            Self::ByteCodes { response } => {
+                poll_snap_request!(response, ByteCodes, cx)
+            }
+            Self::TrieNodes { response } => {
+                poll_snap_request!(response, TrieNodes, cx)
+            }
         };
         Poll::Ready(res)
     }
@@ -171,19 +240,39 @@ pub enum PeerResponseResult<N: NetworkPrimitives = EthNetworkPrimitives> {
     Receipts(RequestResult<Vec<Vec<ReceiptWithBloom<N::Receipt>>>>),
     /// Represents a result containing receipts or an error for eth/69.
     Receipts69(RequestResult<Vec<Vec<N::Receipt>>>),
+    /// Represents a result containing an account range or an error.
+    AccountRange(RequestResult<AccountRangeMessage>),
+    /// Represents a result containing storage ranges or an error.
+    StorageRanges(RequestResult<StorageRangesMessage>),
+    /// Represents a result containing bytecodes or an error.
+    ByteCodes(RequestResult<ByteCodesMessage>),
+    /// Represents a result containing trie nodes or an error.
+    TrieNodes(RequestResult<TrieNodesMessage>),
 }
 
 // === impl PeerResponseResult ===
 
 impl<N: NetworkPrimitives> PeerResponseResult<N> {
-    /// Converts this response into an [`EthMessage`]
-    pub fn try_into_message(self, id: u64) -> RequestResult<EthMessage<N>> {
+    /// Converts this response into an [`EthSnapMessage`]
+    pub fn try_into_message(self, id: u64) -> RequestResult<EthSnapMessage<N>> {
         macro_rules! to_message {
             ($response:ident, $item:ident, $request_id:ident) => {
                 match $response {
                     Ok(res) => {
                         let request = RequestPair { request_id: $request_id, message: $item(res) };
-                        Ok(EthMessage::$item(request))
+                        Ok(EthMessage::$item(request).into())
+                    }
+                    Err(err) => Err(err),
+                }
+            };
+        }
+
+        macro_rules! to_snap_message {
+            ($response:ident, $item:ident, $message:ident, $request_id:ident) => {
+                match $response {
+                    Ok(res) => {
+                        let message = $message { request_id: $request_id, ..res };
+                        Ok(SnapProtocolMessage::$item(message).into())
                     }
                     Err(err) => Err(err),
                 }
@@ -208,6 +297,18 @@ impl<N: NetworkPrimitives> PeerResponseResult<N> {
             Self::Receipts69(resp) => {
                 to_message!(resp, Receipts69, id)
             }
+            Self::AccountRange(resp) => {
+                to_snap_message!(resp, AccountRange, AccountRangeMessage, id)
+            }
+            Self::StorageRanges(resp) => {
+                to_snap_message!(resp, StorageRanges, StorageRangesMessage, id)
+            }
+            Self::ByteCodes(resp) => {
+                to_snap_message!(resp, ByteCodes, ByteCodesMessage, id)
+            }
+            Self::TrieNodes(resp) => {
+                to_snap_message!(resp, TrieNodes, TrieNodesMessage, id)
+            }
         }
     }
 
@@ -220,6 +321,10 @@ impl<N: NetworkPrimitives> PeerResponseResult<N> {
             Self::NodeData(res) => res.as_ref().err(),
             Self::Receipts(res) => res.as_ref().err(),
             Self::Receipts69(res) => res.as_ref().err(),
+            Self::AccountRange(res) => res.as_ref().err(),
+            Self::StorageRanges(res) => res.as_ref().err(),
+            Self::ByteCodes(res) => res.as_ref().err(),
+            Self::TrieNodes(res) => res.as_ref().err(),
         }
     }
 
@@ -28,7 +28,7 @@ use reth_eth_wire::{
     message::{EthBroadcastMessage, MessageError, RequestPair},
     Capabilities, DisconnectP2P, DisconnectReason, EthMessage, NetworkPrimitives, NewBlockPayload,
 };
-use reth_eth_wire_types::RawCapabilityMessage;
+use reth_eth_wire_types::{snap::SnapProtocolMessage, EthSnapMessage, RawCapabilityMessage};
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequest;
 use reth_network_p2p::error::RequestError;
@@ -318,8 +318,97 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
         }
     }
 
+    /// Handle a `snap` message read from the connection.
+    ///
+    /// This is only reachable if the session negotiated `snap/1` next to `eth`.
+    fn on_incoming_snap_message(
+        &mut self,
+        msg: SnapProtocolMessage,
+    ) -> OnIncomingMessageOutcome<N> {
+        /// Handles an incoming `snap` request, see also `on_request` in
+        /// [`Self::on_incoming_message`].
+        macro_rules! on_request {
+            ($req:ident, $resp_item:ident, $req_item:ident) => {{
+                let (tx, response) = oneshot::channel();
+                let received = ReceivedRequest {
+                    request_id: $req.request_id,
+                    rx: PeerResponse::$resp_item { response },
+                    received: Instant::now(),
+                };
+                self.received_requests_from_remote.push(received);
+                self.try_emit_request(PeerMessage::EthRequest(PeerRequest::$req_item {
+                    request: $req,
+                    response: tx,
+                }))
+                .into()
+            }};
+        }
+
+        /// Processes a `snap` response received from the peer
+        macro_rules! on_response {
+            ($resp:ident, $item:ident) => {{
+                let request_id = $resp.request_id;
+                if let Some(req) = self.inflight_requests.remove(&request_id) {
+                    match req.request {
+                        RequestState::Waiting(PeerRequest::$item { response, .. }) => {
+                            trace!(peer_id=?self.remote_peer_id, ?request_id, "received snap response from peer");
+                            let _ = response.send(Ok($resp));
+                            self.update_request_timeout(req.timestamp, Instant::now());
+                        }
+                        RequestState::Waiting(request) => {
+                            request.send_bad_response();
+                        }
+                        RequestState::TimedOut => {
+                            // request was already timed out internally
+                            self.update_request_timeout(req.timestamp, Instant::now());
+                        }
+                    }
+                } else {
+                    trace!(peer_id=?self.remote_peer_id, ?request_id, "received snap response to unknown request");
+                    // we received a response to a request we never sent
+                    self.on_bad_message();
+                }
+
+                OnIncomingMessageOutcome::Ok
+            }};
+        }
+
+        match msg {
+            SnapProtocolMessage::GetAccountRange(req) => {
+                on_request!(req, AccountRange, GetAccountRange)
+            }
+            SnapProtocolMessage::AccountRange(resp) => {
+                on_response!(resp, GetAccountRange)
+            }
+            SnapProtocolMessage::GetStorageRanges(req) => {
+                on_request!(req, StorageRanges, GetStorageRanges)
+            }
+            SnapProtocolMessage::StorageRanges(resp) => {
+                on_response!(resp, GetStorageRanges)
+            }
+            SnapProtocolMessage::GetByteCodes(req) => {
+                on_request!(req, ByteCodes, GetByteCodes)
+            }
+            SnapProtocolMessage::ByteCodes(resp) => {
+                on_response!(resp, GetByteCodes)
+            }
+            SnapProtocolMessage::GetTrieNodes(req) => {
+                on_request!(req, TrieNodes, GetTrieNodes)
+            }
+            SnapProtocolMessage::TrieNodes(resp) => {
+                on_response!(resp, GetTrieNodes)
+            }
+        }
+    }
+
     /// Handle an internal peer request that will be sent to the remote.
     fn on_internal_peer_request(&mut self, request: PeerRequest<N>, deadline: Instant) {
+        if request.is_snap_request() && !self.conn.supports_snap() {
+            trace!(?request, peer_id=?self.remote_peer_id, "snap request to peer without snap/1");
+            request.send_err_response(RequestError::UnsupportedCapability);
+            return
+        }
+
         let request_id = self.next_id();
 
         trace!(?request, peer_id=?self.remote_peer_id, ?request_id, "sending request to peer");
@@ -640,6 +729,7 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
                         OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
                         OutgoingMessage::Raw(msg) => this.conn.start_send_raw(msg),
+                        OutgoingMessage::Snap(msg) => this.conn.start_send_snap(msg),
                     };
                     if let Err(err) = res {
                         debug!(target: "net::session", %err, remote_peer_id=?this.remote_peer_id, "failed to send message");
@@ -713,9 +803,18 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                     Poll::Ready(Some(res)) => {
                         match res {
                             Ok(msg) => {
-                                trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
                                 // decode and handle message
-                                match this.on_incoming_message(msg) {
+                                let outcome = match msg {
+                                    EthSnapMessage::Eth(msg) => {
+                                        trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                        this.on_incoming_message(msg)
+                                    }
+                                    EthSnapMessage::Snap(msg) => {
+                                        trace!(target: "net::session", request_id=msg.request_id(), remote_peer_id=?this.remote_peer_id, "received snap message");
+                                        this.on_incoming_snap_message(msg)
+                                    }
+                                };
+                                match outcome {
                                     OnIncomingMessageOutcome::Ok => {
                                         // handled successfully
                                         progress = true;
@@ -864,6 +963,8 @@ pub(crate) enum OutgoingMessage<N: NetworkPrimitives> {
     Broadcast(EthBroadcastMessage<N>),
     /// A raw capability message
     Raw(RawCapabilityMessage),
+    /// A `snap` protocol message
+    Snap(SnapProtocolMessage),
 }
 
 impl<N: NetworkPrimitives> OutgoingMessage<N> {
@@ -871,11 +972,21 @@ impl<N: NetworkPrimitives> OutgoingMessage<N> {
     const fn is_response(&self) -> bool {
         match self {
             Self::Eth(msg) => msg.is_response(),
+            Self::Snap(msg) => msg.is_response(),
             _ => false,
         }
     }
 }
 
+impl<N: NetworkPrimitives> From<EthSnapMessage<N>> for OutgoingMessage<N> {
+    fn from(value: EthSnapMessage<N>) -> Self {
+        match value {
+            EthSnapMessage::Eth(msg) => Self::Eth(msg),
+            EthSnapMessage::Snap(msg) => Self::Snap(msg),
+        }
+    }
+}
+
 impl<N: NetworkPrimitives> From<EthMessage<N>> for OutgoingMessage<N> {
     fn from(value: EthMessage<N>) -> Self {
         Self::Eth(value)
@@ -4,11 +4,13 @@ use futures::{Sink, Stream};
 use reth_ecies::stream::ECIESStream;
 use reth_eth_wire::{
     errors::EthStreamError,
+    eth_snap_stream::EthSnapStream,
     message::EthBroadcastMessage,
     multiplex::{ProtocolProxy, RlpxSatelliteStream},
-    EthMessage, EthNetworkPrimitives, EthStream, EthVersion, NetworkPrimitives, P2PStream,
+    EthMessage, EthNetworkPrimitives, EthSnapMessage, EthStream, EthVersion, NetworkPrimitives,
+    P2PStream,
 };
-use reth_eth_wire_types::RawCapabilityMessage;
+use reth_eth_wire_types::{RawCapabilityMessage, SnapProtocolMessage};
 use std::{
     pin::Pin,
     task::{Context, Poll},
@@ -18,6 +20,9 @@ use tokio::net::TcpStream;
 /// The type of the underlying peer network connection.
 pub type EthPeerConnection<N> = EthStream<P2PStream<ECIESStream<TcpStream>>, N>;
 
+/// The type of the underlying peer network connection that negotiated both `eth` and `snap/1`.
+pub type EthSnapPeerConnection<N> = EthSnapStream<P2PStream<ECIESStream<TcpStream>>, N>;
+
 /// Various connection types that at least support the ETH protocol.
 pub type EthSatelliteConnection<N = EthNetworkPrimitives> =
     RlpxSatelliteStream<ECIESStream<TcpStream>, EthStream<ProtocolProxy, N>>;
@@ -26,6 +31,7 @@ pub type EthSatelliteConnection<N = EthNetworkPrimitives> =
 ///
 /// This can be either:
 /// - A connection that only supports the ETH protocol
+/// - A connection that supports the ETH and the SNAP protocol
 /// - A connection that supports the ETH protocol and at least one other `RLPx` protocol
 // This type is boxed because the underlying stream is ~6KB,
 // mostly coming from `P2PStream`'s `snap::Encoder` (2072), and `ECIESStream` (3600).
@@ -33,6 +39,8 @@ pub type EthSatelliteConnection<N = EthNetworkPrimitives> =
 pub enum EthRlpxConnection<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// A connection that only supports the ETH protocol.
     EthOnly(Box<EthPeerConnection<N>>),
+    /// A connection that supports the ETH protocol and `snap/1`.
+    EthSnap(Box<EthSnapPeerConnection<N>>),
     /// A connection that supports the ETH protocol and __at least one other__ `RLPx` protocol.
     Satellite(Box<EthSatelliteConnection<N>>),
 }
@@ -43,6 +51,7 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
     pub(crate) const fn version(&self) -> EthVersion {
         match self {
             Self::EthOnly(conn) => conn.version(),
+            Self::EthSnap(conn) => conn.eth_version(),
             Self::Satellite(conn) => conn.primary().version(),
         }
     }
@@ -52,6 +61,7 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
     pub(crate) fn into_inner(self) -> P2PStream<ECIESStream<TcpStream>> {
         match self {
             Self::EthOnly(conn) => conn.into_inner(),
+            Self::EthSnap(conn) => conn.into_inner(),
             Self::Satellite(conn) => conn.into_inner(),
         }
     }
@@ -61,6 +71,7 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
     pub(crate) fn inner_mut(&mut self) -> &mut P2PStream<ECIESStream<TcpStream>> {
         match self {
             Self::EthOnly(conn) => conn.inner_mut(),
+            Self::EthSnap(conn) => conn.inner_mut(),
             Self::Satellite(conn) => conn.inner_mut(),
         }
     }
@@ -70,10 +81,17 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
     pub(crate) const fn inner(&self) -> &P2PStream<ECIESStream<TcpStream>> {
         match self {
             Self::EthOnly(conn) => conn.inner(),
+            Self::EthSnap(conn) => conn.inner(),
             Self::Satellite(conn) => conn.inner(),
         }
     }
 
+    /// Returns `true` if the connection negotiated `snap/1`.
+    #[inline]
+    pub(crate) const fn supports_snap(&self) -> bool {
+        matches!(self, Self::EthSnap(_))
+    }
+
     /// Same as [`Sink::start_send`] but accepts a [`EthBroadcastMessage`] instead.
     #[inline]
     pub fn start_send_broadcast(
@@ -82,6 +100,7 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
     ) -> Result<(), EthStreamError> {
         match self {
             Self::EthOnly(conn) => conn.start_send_broadcast(item),
+            Self::EthSnap(conn) => Ok(conn.start_send_broadcast(item)?),
             Self::Satellite(conn) => conn.primary_mut().start_send_broadcast(item),
         }
     }
@@ -90,9 +109,22 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
     pub fn start_send_raw(&mut self, msg: RawCapabilityMessage) -> Result<(), EthStreamError> {
         match self {
             Self::EthOnly(conn) => conn.start_send_raw(msg),
+            Self::EthSnap(conn) => Ok(conn.start_send_raw(msg)?),
             Self::Satellite(conn) => conn.primary_mut().start_send_raw(msg),
         }
     }
+
+    /// Same as [`Sink::start_send`] but accepts a [`SnapProtocolMessage`] instead.
+    ///
+    /// Returns an error if the connection did not negotiate `snap/1`.
+    pub fn start_send_snap(&mut self, msg: SnapProtocolMessage) -> Result<(), EthStreamError> {
+        match self {
+            Self::EthSnap(conn) => Ok(Pin::new(conn.as_mut()).start_send(msg.into())?),
+            Self::EthOnly(_) | Self::Satellite(_) => {
+                Err(EthStreamError::UnsupportedMessage { message_id: msg.message_id() as u8 })
+            }
+        }
+    }
 }
 
 impl<N: NetworkPrimitives> From<EthPeerConnection<N>> for EthRlpxConnection<N> {
@@ -102,6 +134,13 @@ impl<N: NetworkPrimitives> From<EthPeerConnection<N>> for EthRlpxConnection<N> {
     }
 }
 
+impl<N: NetworkPrimitives> From<EthSnapPeerConnection<N>> for EthRlpxConnection<N> {
+    #[inline]
+    fn from(conn: EthSnapPeerConnection<N>) -> Self {
+        Self::EthSnap(Box::new(conn))
+    }
+}
+
 impl<N: NetworkPrimitives> From<EthSatelliteConnection<N>> for EthRlpxConnection<N> {
     #[inline]
     fn from(conn: EthSatelliteConnection<N>) -> Self {
@@ -114,6 +153,7 @@ macro_rules! delegate_call {
         unsafe {
             match $self.get_unchecked_mut() {
                 Self::EthOnly(l) => Pin::new_unchecked(l).$method($($args),+),
+                Self::EthSnap(s) => Pin::new_unchecked(s).$method($($args),+).map_err(Into::into),
                 Self::Satellite(r) => Pin::new_unchecked(r).$method($($args),+),
             }
         }
@@ -121,10 +161,22 @@ macro_rules! delegate_call {
 }
 
 impl<N: NetworkPrimitives> Stream for EthRlpxConnection<N> {
-    type Item = Result<EthMessage<N>, EthStreamError>;
+    type Item = Result<EthSnapMessage<N>, EthStreamError>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        delegate_call!(self.poll_next(cx))
+        unsafe {
+            match self.get_unchecked_mut() {
+                Self::EthOnly(l) => Pin::new_unchecked(l)
+                    .poll_next(cx)
+                    .map(|msg| msg.map(|msg| msg.map(EthSnapMessage::Eth))),
+                Self::EthSnap(s) => {
+                    Pin::new_unchecked(s).poll_next(cx).map(|msg| msg.map(|msg| Ok(msg?)))
+                }
+                Self::Satellite(r) => Pin::new_unchecked(r)
+                    .poll_next(cx)
+                    .map(|msg| msg.map(|msg| msg.map(EthSnapMessage::Eth))),
+            }
+        }
     }
 }
 
@@ -136,7 +188,13 @@ impl<N: NetworkPrimitives> Sink<EthMessage<N>> for EthRlpxConnection<N> {
     }
 
     fn start_send(self: Pin<&mut Self>, item: EthMessage<N>) -> Result<(), Self::Error> {
-        delegate_call!(self.start_send(item))
+        unsafe {
+            match self.get_unchecked_mut() {
+                Self::EthOnly(l) => Pin::new_unchecked(l).start_send(item),
+                Self::EthSnap(s) => Ok(Pin::new_unchecked(s).start_send(item.into())?),
+                Self::Satellite(r) => Pin::new_unchecked(r).start_send(item),
+            }
+        }
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
@@ -159,9 +217,16 @@ mod tests {
     {
     }
 
+    const fn assert_eth_snap_stream<N, St>()
+    where
+        N: NetworkPrimitives,
+        St: Stream<Item = Result<EthSnapMessage<N>, EthStreamError>> + Sink<EthMessage<N>>,
+    {
+    }
+
     #[test]
     const fn test_eth_stream_variants() {
         assert_eth_stream::<EthNetworkPrimitives, EthSatelliteConnection<EthNetworkPrimitives>>();
-        assert_eth_stream::<EthNetworkPrimitives, EthRlpxConnection<EthNetworkPrimitives>>();
+        assert_eth_snap_stream::<EthNetworkPrimitives, EthRlpxConnection<EthNetworkPrimitives>>();
     }
 }
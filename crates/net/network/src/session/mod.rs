@@ -18,10 +18,10 @@ use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
-    errors::EthStreamError, handshake::EthRlpxHandshake, multiplex::RlpxProtocolMultiplexer,
-    BlockRangeUpdate, Capabilities, DisconnectReason, EthStream, EthVersion,
-    HelloMessageWithProtocols, NetworkPrimitives, UnauthedP2PStream, UnifiedStatus,
-    HANDSHAKE_TIMEOUT,
+    errors::EthStreamError, eth_snap_stream::EthSnapStream, handshake::EthRlpxHandshake,
+    multiplex::RlpxProtocolMultiplexer, BlockRangeUpdate, Capabilities, Capability,
+    DisconnectReason, EthStream, EthVersion, HelloMessageWithProtocols, NetworkPrimitives,
+    UnauthedP2PStream, UnifiedStatus, HANDSHAKE_TIMEOUT,
 };
 use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
@@ -538,6 +538,9 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 // negotiated version
                 let version = conn.version();
 
+                // whether the session can handle `snap/1` requests
+                let supports_snap = conn.supports_snap();
+
                 // Configure the interval at which the range information is updated, starting with
                 // ETH69
                 let range_update_interval = (conn.version() >= EthVersion::Eth69).then(|| {
@@ -609,6 +612,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     direction,
                     timeout,
                     range_info: None,
+                    supports_snap,
                 })
             }
             PendingSessionEvent::Disconnected { remote_addr, session_id, direction, error } => {
@@ -744,6 +748,8 @@ pub enum SessionEvent<N: NetworkPrimitives> {
         timeout: Arc<AtomicU64>,
         /// The range info for the peer.
         range_info: Option<BlockRangeInfo>,
+        /// Whether the session negotiated `snap/1` and can serve snap requests.
+        supports_snap: bool,
     },
     /// The peer was already connected with another session.
     AlreadyConnected {
@@ -1113,7 +1119,14 @@ async fn authenticate_stream<N: NetworkPrimitives>(
     // Before trying status handshake, set up the version to negotiated shared version
     status.set_eth_version(eth_version);
 
-    let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 {
+    // `snap/1` is handled by the session itself if it's the only protocol shared next to `eth`,
+    // in which case the snap message ids directly follow the eth message ids.
+    let snap_1 = Capability::snap_1();
+    let eth_snap_only = p2p_stream.shared_capabilities().len() == 2 &&
+        p2p_stream.shared_capabilities().contains(&snap_1) &&
+        !extra_handlers.iter().any(|handler| handler.protocol().cap == snap_1);
+
+    let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 || eth_snap_only {
         // if the shared caps are 1, we know both support the eth version
         // if the hello handshake was successful we can try status handshake
 
@@ -1122,6 +1135,10 @@ async fn authenticate_stream<N: NetworkPrimitives>(
             .handshake(&mut p2p_stream, status, fork_filter.clone(), HANDSHAKE_TIMEOUT)
             .await
         {
+            Ok(their_status) if eth_snap_only => {
+                let eth_snap_stream = EthSnapStream::new(p2p_stream, eth_version);
+                (eth_snap_stream.into(), their_status)
+            }
             Ok(their_status) => {
                 let eth_stream = EthStream::new(eth_version, p2p_stream);
                 (eth_stream.into(), their_status)
@@ -143,6 +143,7 @@ impl<N: NetworkPrimitives> NetworkState<N> {
     ///
     /// Returns `Ok` if the session is valid, returns an `Err` if the session is not accepted and
     /// should be rejected.
+    #[expect(clippy::too_many_arguments)]
     pub(crate) fn on_session_activated(
         &mut self,
         peer: PeerId,
@@ -151,6 +152,7 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         request_tx: PeerRequestSender<PeerRequest<N>>,
         timeout: Arc<AtomicU64>,
         range_info: Option<BlockRangeInfo>,
+        supports_snap: bool,
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
@@ -163,6 +165,7 @@ impl<N: NetworkPrimitives> NetworkState<N> {
             block_number,
             timeout,
             range_info,
+            supports_snap,
         );
 
         self.active_peers.insert(
@@ -386,6 +389,30 @@ impl<N: NetworkPrimitives> NetworkState<N> {
                     let response = PeerResponse::BlockBodies { response: rx };
                     (request, response)
                 }
+                BlockRequest::GetAccountRange(request) => {
+                    let (response, rx) = oneshot::channel();
+                    let request = PeerRequest::GetAccountRange { request, response };
+                    let response = PeerResponse::AccountRange { response: rx };
+                    (request, response)
+                }
+                BlockRequest::GetStorageRanges(request) => {
+                    let (response, rx) = oneshot::channel();
+                    let request = PeerRequest::GetStorageRanges { request, response };
+                    let response = PeerResponse::StorageRanges { response: rx };
+                    (request, response)
+                }
+                BlockRequest::GetByteCodes(request) => {
+                    let (response, rx) = oneshot::channel();
+                    let request = PeerRequest::GetByteCodes { request, response };
+                    let response = PeerResponse::ByteCodes { response: rx };
+                    (request, response)
+                }
+                BlockRequest::GetTrieNodes(request) => {
+                    let (response, rx) = oneshot::channel();
+                    let request = PeerRequest::GetTrieNodes { request, response };
+                    let response = PeerResponse::TrieNodes { response: rx };
+                    (request, response)
+                }
             };
             let _ = peer.request_tx.to_session_tx.try_send(request);
             peer.pending_response = Some(response);
@@ -417,6 +444,18 @@ impl<N: NetworkPrimitives> NetworkState<N> {
             PeerResponseResult::BlockBodies(res) => {
                 self.state_fetcher.on_block_bodies_response(peer, res)
             }
+            PeerResponseResult::AccountRange(res) => {
+                self.state_fetcher.on_account_range_response(peer, res)
+            }
+            PeerResponseResult::StorageRanges(res) => {
+                self.state_fetcher.on_storage_ranges_response(peer, res)
+            }
+            PeerResponseResult::ByteCodes(res) => {
+                self.state_fetcher.on_byte_codes_response(peer, res)
+            }
+            PeerResponseResult::TrieNodes(res) => {
+                self.state_fetcher.on_trie_nodes_response(peer, res)
+            }
             _ => None,
         };
 
@@ -622,6 +661,7 @@ mod tests {
             peer_tx,
             Arc::new(AtomicU64::new(1)),
             None,
+            false,
         );
 
         assert!(state.active_peers.contains_key(&peer_id));
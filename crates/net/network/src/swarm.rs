@@ -123,6 +123,7 @@ impl<N: NetworkPrimitives> Swarm<N> {
                 direction,
                 timeout,
                 range_info,
+                supports_snap,
             } => {
                 self.state.on_session_activated(
                     peer_id,
@@ -131,6 +132,7 @@ impl<N: NetworkPrimitives> Swarm<N> {
                     messages.clone(),
                     timeout,
                     range_info,
+                    supports_snap,
                 );
                 Some(SwarmEvent::SessionEstablished {
                     peer_id,
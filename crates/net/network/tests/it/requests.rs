@@ -2,10 +2,16 @@
 //! Tests for eth related requests
 
 use alloy_consensus::Header;
+use alloy_primitives::{Bytes, B256};
 use rand::Rng;
-use reth_eth_wire::{EthVersion, HeadersDirection};
+use reth_eth_wire::{
+    protocol::Protocol,
+    snap::{AccountData, AccountRangeMessage, GetAccountRangeMessage},
+    EthVersion, HeadersDirection,
+};
 use reth_ethereum_primitives::Block;
 use reth_network::{
+    eth_requests::IncomingSnapRequest,
     test_utils::{NetworkEventStream, PeerConfig, Testnet},
     BlockDownloaderProvider, NetworkEventListenerProvider,
 };
@@ -13,11 +19,12 @@ use reth_network_api::{NetworkInfo, Peers};
 use reth_network_p2p::{
     bodies::client::BodiesClient,
     headers::client::{HeadersClient, HeadersRequest},
+    snap::client::SnapClient,
 };
 use reth_provider::test_utils::MockEthProvider;
 use reth_transaction_pool::test_utils::{TestPool, TransactionGenerator};
 use std::sync::Arc;
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_get_body() {
@@ -526,3 +533,74 @@ async fn test_eth69_get_receipts() {
         assert_eq!(receipts_response.0[0][1].cumulative_gas_used, 42000);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snap_get_account_range() {
+    reth_tracing::init_test_tracing();
+    let mock_provider = Arc::new(MockEthProvider::default());
+
+    let mut net: Testnet<Arc<MockEthProvider>, TestPool> = Testnet::default();
+
+    // both peers negotiate eth and snap/1
+    for _ in 0..2 {
+        let config = PeerConfig::with_protocols(
+            mock_provider.clone(),
+            [Protocol::eth_68(), Protocol::snap_1()],
+        );
+        net.add_peer_with_config(config).await.unwrap();
+    }
+
+    // serve the snap requests of the second peer from the test
+    let (snap_tx, mut snap_rx) = mpsc::channel(1);
+    net.peers_mut()[1].network_mut().set_snap_request_handler(snap_tx);
+
+    let handle0 = net.peers()[0].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    let handle1 = net.peers()[1].handle();
+
+    let _handle = net.spawn();
+
+    let fetch0 = handle0.fetch_client().await.unwrap();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    let request = GetAccountRangeMessage {
+        request_id: 0,
+        root_hash: B256::random(),
+        starting_hash: B256::ZERO,
+        limit_hash: B256::repeat_byte(0xff),
+        response_bytes: 1024,
+    };
+    let accounts = vec![
+        AccountData { hash: B256::repeat_byte(0x10), body: Bytes::from_static(&[0xc0]) },
+        AccountData { hash: B256::repeat_byte(0x20), body: Bytes::from_static(&[0xc0]) },
+    ];
+
+    let served_accounts = accounts.clone();
+    let server = tokio::spawn(async move {
+        let Some(IncomingSnapRequest::GetAccountRange { peer_id, request, response }) =
+            snap_rx.recv().await
+        else {
+            panic!("expected an account range request")
+        };
+        let _ = response.send(Ok(AccountRangeMessage {
+            request_id: request.request_id,
+            accounts: served_accounts,
+            proof: vec![],
+        }));
+        (peer_id, request)
+    });
+
+    let response = fetch0.get_account_range(request.clone()).await.unwrap();
+    assert_eq!(response.peer_id(), *handle1.peer_id());
+    assert_eq!(response.into_data().accounts, accounts);
+
+    let (peer_id, served) = server.await.unwrap();
+    assert_eq!(peer_id, *handle0.peer_id());
+    assert_eq!(served.root_hash, request.root_hash);
+    assert_eq!(served.starting_hash, request.starting_hash);
+    assert_eq!(served.limit_hash, request.limit_hash);
+}
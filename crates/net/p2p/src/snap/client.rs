@@ -1,19 +1,39 @@
-use crate::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
-use futures::Future;
+use crate::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    priority::Priority,
+};
+use futures::{future::Ready, Future};
 use reth_eth_wire_types::snap::{
-    AccountRangeMessage, GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage,
-    GetTrieNodesMessage,
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
 };
+use reth_network_peers::PeerId;
 
 /// The snap sync downloader client
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait SnapClient: DownloadClient {
     /// The output future type for account range requests
-    type Output: Future<Output = PeerRequestResult<AccountRangeMessage>> + Send + Sync + Unpin;
+    type AccountRangeOutput: Future<Output = PeerRequestResult<AccountRangeMessage>>
+        + Send
+        + Sync
+        + Unpin;
+
+    /// The output future type for storage ranges requests
+    type StorageRangesOutput: Future<Output = PeerRequestResult<StorageRangesMessage>>
+        + Send
+        + Sync
+        + Unpin;
+
+    /// The output future type for byte codes requests
+    type ByteCodesOutput: Future<Output = PeerRequestResult<ByteCodesMessage>> + Send + Sync + Unpin;
+
+    /// The output future type for trie nodes requests
+    type TrieNodesOutput: Future<Output = PeerRequestResult<TrieNodesMessage>> + Send + Sync + Unpin;
 
     /// Sends the account range request to the p2p network and returns the account range
     /// response received from a peer.
-    fn get_account_range(&self, request: GetAccountRangeMessage) -> Self::Output {
+    fn get_account_range(&self, request: GetAccountRangeMessage) -> Self::AccountRangeOutput {
         self.get_account_range_with_priority(request, Priority::Normal)
     }
 
@@ -23,11 +43,13 @@ pub trait SnapClient: DownloadClient {
         &self,
         request: GetAccountRangeMessage,
         priority: Priority,
-    ) -> Self::Output;
+    ) -> Self::AccountRangeOutput;
 
     /// Sends the storage ranges request to the p2p network and returns the storage ranges
     /// response received from a peer.
-    fn get_storage_ranges(&self, request: GetStorageRangesMessage) -> Self::Output;
+    fn get_storage_ranges(&self, request: GetStorageRangesMessage) -> Self::StorageRangesOutput {
+        self.get_storage_ranges_with_priority(request, Priority::Normal)
+    }
 
     /// Sends the storage ranges request to the p2p network with priority set and returns
     /// the storage ranges response received from a peer.
@@ -35,11 +57,13 @@ pub trait SnapClient: DownloadClient {
         &self,
         request: GetStorageRangesMessage,
         priority: Priority,
-    ) -> Self::Output;
+    ) -> Self::StorageRangesOutput;
 
     /// Sends the byte codes request to the p2p network and returns the byte codes
     /// response received from a peer.
-    fn get_byte_codes(&self, request: GetByteCodesMessage) -> Self::Output;
+    fn get_byte_codes(&self, request: GetByteCodesMessage) -> Self::ByteCodesOutput {
+        self.get_byte_codes_with_priority(request, Priority::Normal)
+    }
 
     /// Sends the byte codes request to the p2p network with priority set and returns
     /// the byte codes response received from a peer.
@@ -47,11 +71,13 @@ pub trait SnapClient: DownloadClient {
         &self,
         request: GetByteCodesMessage,
         priority: Priority,
-    ) -> Self::Output;
+    ) -> Self::ByteCodesOutput;
 
     /// Sends the trie nodes request to the p2p network and returns the trie nodes
     /// response received from a peer.
-    fn get_trie_nodes(&self, request: GetTrieNodesMessage) -> Self::Output;
+    fn get_trie_nodes(&self, request: GetTrieNodesMessage) -> Self::TrieNodesOutput {
+        self.get_trie_nodes_with_priority(request, Priority::Normal)
+    }
 
     /// Sends the trie nodes request to the p2p network with priority set and returns
     /// the trie nodes response received from a peer.
@@ -59,5 +85,59 @@ pub trait SnapClient: DownloadClient {
         &self,
         request: GetTrieNodesMessage,
         priority: Priority,
-    ) -> Self::Output;
+    ) -> Self::TrieNodesOutput;
+}
+
+/// A snap client that isn't connected to any peers and fails every request.
+///
+/// This is used where a [`SnapClient`] is required but snap sync isn't available.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct NoopSnapClient;
+
+impl DownloadClient for NoopSnapClient {
+    fn report_bad_message(&self, _peer_id: PeerId) {}
+
+    fn num_connected_peers(&self) -> usize {
+        0
+    }
+}
+
+impl SnapClient for NoopSnapClient {
+    type AccountRangeOutput = Ready<PeerRequestResult<AccountRangeMessage>>;
+    type StorageRangesOutput = Ready<PeerRequestResult<StorageRangesMessage>>;
+    type ByteCodesOutput = Ready<PeerRequestResult<ByteCodesMessage>>;
+    type TrieNodesOutput = Ready<PeerRequestResult<TrieNodesMessage>>;
+
+    fn get_account_range_with_priority(
+        &self,
+        _request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::AccountRangeOutput {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        _request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::StorageRangesOutput {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        _request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::ByteCodesOutput {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        _request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::TrieNodesOutput {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
 }
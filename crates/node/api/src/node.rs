@@ -5,7 +5,8 @@ use alloy_rpc_types_engine::JwtSecret;
 use reth_basic_payload_builder::PayloadBuilder;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
-use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle};
+use reth_engine_local::LocalMinerHandle;
+use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle, SetHeadHandle};
 use reth_evm::ConfigureEvm;
 use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
@@ -14,7 +15,7 @@ use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::FullProvider;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
-use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use reth_transaction_pool::{BundlePool, PoolTransaction, TransactionPool};
 use std::{fmt::Debug, future::Future, marker::PhantomData};
 
 /// A helper trait that is downstream of the [`NodeTypes`] trait and adds stateful
@@ -118,6 +119,14 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<ConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Handle to the local miner, set when the node runs in dev mode.
+    pub local_miner: Option<LocalMinerHandle>,
+    /// Handle to unwind the canonical chain, e.g. via `debug_setHead`.
+    pub set_head_handle: SetHeadHandle,
+    /// The node's database, e.g. for inspecting it via `debug_dbGet`.
+    pub database: N::DB,
+    /// The pool of submitted bundles, set when enabled with `--builder.bundles`.
+    pub bundle_pool: Option<BundlePool<TxTy<N::Types>>>,
 }
 
 /// Customizable node add-on types.
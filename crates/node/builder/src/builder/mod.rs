@@ -14,6 +14,7 @@ use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli_util::get_secret_key;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
+use reth_engine_local::LocalMinerHandle;
 use reth_exex::ExExContext;
 use reth_network::{
     transactions::{TransactionPropagationPolicy, TransactionsManagerConfig},
@@ -22,7 +23,7 @@ use reth_network::{
 };
 use reth_node_api::{
     FullNodePrimitives, FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes,
-    NodeTypesWithDBAdapter,
+    NodeTypesWithDBAdapter, TxTy,
 };
 use reth_node_core::{
     cli::config::{PayloadBuilderConfig, RethTransactionPoolConfig},
@@ -35,7 +36,7 @@ use reth_provider::{
     ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{BundlePool, PoolConfig, PoolTransaction, TransactionPool};
 use secp256k1::SecretKey;
 use std::{fmt::Debug, sync::Arc};
 use tracing::{info, trace, warn};
@@ -703,6 +704,10 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    /// Handle to the local miner, if the node runs in dev mode.
+    pub(crate) local_miner: Option<LocalMinerHandle>,
+    /// The pool of submitted bundles, if enabled with `--builder.bundles`.
+    pub(crate) bundle_pool: Option<BundlePool<TxTy<Node::Types>>>,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
@@ -713,7 +718,14 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         executor: TaskExecutor,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self {
+            head,
+            provider,
+            executor,
+            config_container,
+            local_miner: None,
+            bundle_pool: None,
+        }
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -753,6 +765,21 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
+    /// Returns the handle to the local miner, if the node runs in dev mode.
+    ///
+    /// Components that modify the blocks mined in dev mode, e.g. the payload builder, can use it
+    /// to access the changes requested through the miner.
+    pub const fn local_miner(&self) -> Option<&LocalMinerHandle> {
+        self.local_miner.as_ref()
+    }
+
+    /// Returns the pool of submitted bundles, if enabled with `--builder.bundles`.
+    ///
+    /// The payload builder can use it to include bundles in built payloads.
+    pub const fn bundle_pool(&self) -> Option<&BundlePool<TxTy<Node::Types>>> {
+        self.bundle_pool.as_ref()
+    }
+
     /// Returns the transaction pool config of the node.
     pub fn pool_config(&self) -> PoolConfig {
         self.config().txpool.pool_config()
@@ -821,10 +848,19 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         Node::Provider: BlockReaderFor<N>,
         Policy: TransactionPropagationPolicy + Debug,
     {
-        let (handle, network, txpool, eth) = builder
+        let mut builder = builder
             .transactions_with_policy(pool, tx_config, propagation_policy)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+            .request_handler(self.provider().clone());
+
+        if self.config().network.snap {
+            let snap = builder
+                .snap_request_handler(self.provider().clone())
+                .with_state_window(self.config().network.snap_state_window);
+            // requests are answered from the database, which must not block the network runtime
+            self.executor.spawn_critical_blocking("p2p snap request handler", Box::pin(snap));
+        }
+
+        let (handle, network, txpool, eth) = builder.split_with_handle();
 
         self.executor.spawn_critical("p2p txpool", Box::pin(txpool));
         self.executor.spawn_critical("p2p eth request handler", Box::pin(eth));
@@ -4,8 +4,9 @@ use alloy_primitives::Address;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_node_api::TxTy;
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit,
-    TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
+    blobstore::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore},
+    CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit, TransactionPool,
+    TransactionValidationTaskExecutor, TransactionValidator,
 };
 use std::{collections::HashSet, future::Future};
 
@@ -167,10 +168,8 @@ pub fn create_blob_store<Node: FullNodeTypes>(
     ctx: &BuilderContext<Node>,
 ) -> eyre::Result<DiskFileBlobStore> {
     let data_dir = ctx.config().datadir();
-    Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(
-        data_dir.blobstore(),
-        Default::default(),
-    )?)
+    let config = blob_store_disk_config(ctx, Default::default());
+    Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(data_dir.blobstore(), config)?)
 }
 
 /// Create blob store with custom cache size configuration.
@@ -185,10 +184,27 @@ pub fn create_blob_store_with_cache<Node: FullNodeTypes>(
     } else {
         Default::default()
     };
+    let config = blob_store_disk_config(ctx, config);
 
     Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(data_dir.blobstore(), config)?)
 }
 
+/// Applies the configured disk quota and open mode of the blob store.
+fn blob_store_disk_config<Node: FullNodeTypes>(
+    ctx: &BuilderContext<Node>,
+    config: DiskFileBlobStoreConfig,
+) -> DiskFileBlobStoreConfig {
+    let txpool = &ctx.config().txpool;
+    let open = if txpool.blobstore_persist {
+        OpenDiskFileBlobStore::ReIndex
+    } else {
+        OpenDiskFileBlobStore::Clear
+    };
+    config
+        .with_max_disk_size(txpool.blobstore_max_size.map(|size| size.saturating_mul(1024 * 1024)))
+        .with_open(open)
+}
+
 /// Spawn local transaction backup task if enabled.
 fn spawn_local_backup_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
 where
@@ -223,6 +239,35 @@ where
     Ok(())
 }
 
+/// Spawn transaction pool snapshot task if enabled.
+fn spawn_snapshot_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool + Clone + 'static,
+{
+    if ctx.config().txpool.snapshot {
+        let data_dir = ctx.config().datadir();
+        let snapshot_path =
+            ctx.config().txpool.snapshot_path.clone().unwrap_or_else(|| data_dir.txpool_snapshot());
+
+        let snapshot_config =
+            reth_transaction_pool::maintain::TransactionPoolSnapshotConfig::new(snapshot_path)
+                .with_interval(ctx.config().txpool.snapshot_interval);
+
+        ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            "transaction pool snapshot task",
+            |shutdown| {
+                reth_transaction_pool::maintain::snapshot_transactions_task(
+                    shutdown,
+                    pool,
+                    snapshot_config,
+                )
+            },
+        );
+    }
+    Ok(())
+}
+
 /// Spawn the main maintenance task for transaction pool.
 fn spawn_pool_maintenance_task<Node, Pool>(
     ctx: &BuilderContext<Node>,
@@ -255,7 +300,7 @@ where
     Ok(())
 }
 
-/// Spawn all maintenance tasks for a transaction pool (backup + main maintenance).
+/// Spawn all maintenance tasks for a transaction pool (backup + snapshot + main maintenance).
 pub fn spawn_maintenance_tasks<Node, Pool>(
     ctx: &BuilderContext<Node>,
     pool: Pool,
@@ -267,6 +312,7 @@ where
     Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
 {
     spawn_local_backup_task(ctx, pool.clone())?;
+    spawn_snapshot_task(ctx, pool.clone())?;
     spawn_pool_maintenance_task(ctx, pool, pool_config)?;
     Ok(())
 }
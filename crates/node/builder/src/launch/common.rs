@@ -34,23 +34,30 @@ use crate::{
     hooks::OnComponentInitializedHook,
     BuilderContext, ExExLauncher, NodeAdapter, PrimitivesTy,
 };
-use alloy_consensus::BlockHeader as _;
+use alloy_consensus::{BlockHeader as _, Header};
 use alloy_eips::eip2124::Head;
-use alloy_primitives::{BlockNumber, B256};
+use alloy_primitives::{BlockNumber, B256, U256};
+use alloy_provider::{
+    network::{primitives::HeaderResponse, AnyNetwork, BlockResponse},
+    Provider, RootProvider,
+};
+use alloy_rlp::Decodable;
 use eyre::Context;
 use rayon::ThreadPoolBuilder;
 use reth_chainspec::{Chain, EthChainSpec, EthereumHardfork, EthereumHardforks};
 use reth_config::{config::EtlConfig, PruneConfig};
 use reth_consensus::noop::NoopConsensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
-use reth_db_common::init::{init_genesis, InitStorageError};
+use reth_db_common::init::{init_genesis, setup_without_evm, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
-use reth_engine_local::MiningMode;
+use reth_engine_local::{LocalMinerHandle, MiningMode};
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
 use reth_network_p2p::headers::client::HeadersClient;
-use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
+use reth_node_api::{
+    FullNodeTypes, HeaderTy, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter, TxTy,
+};
 use reth_node_core::{
     args::DefaultEraHost,
     dirs::{ChainPath, DataDirPath},
@@ -65,10 +72,12 @@ use reth_node_metrics::{
     server::{MetricServer, MetricServerConfig},
     version::VersionInfo,
 };
+use reth_primitives_traits::SealedHeader;
 use reth_provider::{
     providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
-    BlockHashReader, BlockNumReader, BlockReaderIdExt, ProviderError, ProviderFactory,
-    ProviderResult, StageCheckpointReader, StaticFileProviderFactory,
+    BlockHashReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider, DBProvider,
+    DatabaseProviderFactory, ProviderError, ProviderFactory, ProviderResult, StageCheckpointReader,
+    StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_builder::config::RethRpcServerConfig;
@@ -78,9 +87,10 @@ use reth_stages::{
     StageId,
 };
 use reth_static_file::StaticFileProducer;
+use reth_storage_rpc_provider::RpcForkState;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{BundlePool, TransactionPool};
 use std::{sync::Arc, thread::available_parallelism};
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedSender},
@@ -326,9 +336,10 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     ///
     /// This includes:
     /// - Making sure the ETL dir is set to the datadir
+    /// - Setting the ERA historical roots from the CLI arguments
     /// - RPC settings are adjusted to the correct port
     pub fn with_adjusted_configs(self) -> Self {
-        self.ensure_etl_datadir().with_adjusted_instance_ports()
+        self.ensure_etl_datadir().with_era_historical_roots().with_adjusted_instance_ports()
     }
 
     /// Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
@@ -347,6 +358,15 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         self
     }
 
+    /// Overrides the ERA historical roots of the config with the CLI arguments, if given.
+    pub fn with_era_historical_roots(mut self) -> Self {
+        if let Some(path) = self.node_config().era.historical_roots.clone() {
+            self.toml_config_mut().stages.era.historical_roots = Some(path);
+        }
+
+        self
+    }
+
     /// Change rpc port numbers based on the instance number.
     pub fn with_adjusted_instance_ports(mut self) -> Self {
         self.node_config_mut().adjust_instance_ports();
@@ -461,12 +481,14 @@ where
     /// Returns the [`ProviderFactory`] for the attached storage after executing a consistent check
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
+    ///
+    /// If `--fork-url` is set, the storage is set up to continue the remote chain.
     pub async fn create_provider_factory<N, Evm>(&self) -> eyre::Result<ProviderFactory<N>>
     where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
         Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
     {
-        let factory = ProviderFactory::new(
+        let mut factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
             StaticFileProvider::read_write(self.data_dir().static_files())?,
@@ -529,9 +551,90 @@ where
             })?;
         }
 
+        if self.node_config().fork.fork_url.is_some() {
+            factory = self.init_fork(factory).await?;
+        }
+
         Ok(factory)
     }
 
+    /// Forks the dev chain from the remote node at `--fork-url`.
+    ///
+    /// On a fresh datadir, the chain is set up to continue the remote chain from the fork block,
+    /// with placeholders for the remote blocks before it. Later launches keep building on the same
+    /// fork block. The remote state is cached in a database per fork block hash, so restarts don't
+    /// refetch it.
+    async fn init_fork<N>(&self, factory: ProviderFactory<N>) -> eyre::Result<ProviderFactory<N>>
+    where
+        N: ProviderNodeTypes<DB = DB>,
+    {
+        let Some(fork_url) = self.node_config().fork.fork_url.clone() else { return Ok(factory) };
+        let provider = RootProvider::<AnyNetwork>::new_http(fork_url);
+
+        // the header type of the chain spec is only known through the node types
+        let chain_spec_id = factory.chain_spec().chain().id();
+        let chain_id = provider.get_chain_id().await.wrap_err("failed to fetch fork chain id")?;
+        if chain_id != chain_spec_id {
+            eyre::bail!(
+                "chain id {chain_spec_id} of the chain spec does not match chain id {chain_id} of the fork"
+            )
+        }
+
+        let fork_dir = self.data_dir().fork();
+        let fork_block_path = fork_dir.join("block");
+        let forked_block = if fork_block_path.exists() {
+            Some(fs::read_to_string(&fork_block_path)?.trim().parse::<BlockNumber>()?)
+        } else {
+            None
+        };
+        let fork_block = match self.node_config().fork.fork_block.or(forked_block) {
+            Some(fork_block) => fork_block,
+            None => provider.get_block_number().await.wrap_err("failed to fetch fork block")?,
+        };
+        if fork_block == 0 {
+            eyre::bail!("cannot fork from the genesis block")
+        }
+
+        let block = provider
+            .get_block_by_number(fork_block.into())
+            .await
+            .wrap_err("failed to fetch fork block")?
+            .ok_or_else(|| eyre::eyre!("fork block {fork_block} not found"))?;
+        let fork_block_hash = block.header().hash();
+        let header = block
+            .header()
+            .inner
+            .clone()
+            .try_into_header()
+            .map_err(|_| eyre::eyre!("fork block {fork_block} is not an Ethereum block"))?;
+        let header = SealedHeader::seal_slow(convert_header::<HeaderTy<N>>(&header)?);
+        if header.hash() != fork_block_hash {
+            eyre::bail!("fork block {fork_block} can't be represented by the node's header type")
+        }
+
+        init_genesis(&factory)?;
+        let provider_rw = factory.database_provider_rw()?;
+        if provider_rw.last_block_number()? == 0 {
+            info!(target: "reth::cli", fork_block, %fork_block_hash, "Forking remote chain");
+            setup_without_evm(&provider_rw, header, U256::ZERO, |number| {
+                convert_header(&Header { number, ..Default::default() })
+                    .expect("placeholder header is a valid header")
+            })?;
+            provider_rw.commit()?;
+
+            fs::create_dir_all(&fork_dir)?;
+            fs::write(&fork_block_path, fork_block.to_string())?;
+        } else if provider_rw.block_hash(fork_block)? != Some(fork_block_hash) {
+            eyre::bail!("datadir was not forked from block {fork_block} ({fork_block_hash})")
+        } else {
+            info!(target: "reth::cli", fork_block, %fork_block_hash, "Continuing forked chain");
+        }
+
+        let cache_dir = fork_dir.join(fork_block_hash.to_string());
+        let fork_state = RpcForkState::<_, AnyNetwork>::new(provider, fork_block, cache_dir)?;
+        Ok(factory.with_fork_state(Arc::new(fork_state)))
+    }
+
     /// Creates a new [`ProviderFactory`] and attaches it to the launch context.
     pub async fn with_provider_factory<N, Evm>(
         self,
@@ -757,12 +860,14 @@ where
         // fetch the head block from the database
         let head = self.lookup_head()?;
 
-        let builder_ctx = BuilderContext::new(
+        let mut builder_ctx = BuilderContext::new(
             head,
             self.blockchain_db().clone(),
             self.task_executor().clone(),
             self.configs().clone(),
         );
+        builder_ctx.local_miner = self.is_dev().then(LocalMinerHandle::new);
+        builder_ctx.bundle_pool = self.node_config().builder.bundles.then(BundlePool::default);
 
         debug!(target: "reth::cli", "creating components");
         let components = components_builder.build_components(&builder_ctx).await?;
@@ -785,6 +890,8 @@ where
             },
             node_adapter,
             head,
+            local_miner: builder_ctx.local_miner,
+            bundle_pool: builder_ctx.bundle_pool,
         };
 
         let ctx = LaunchContextWith {
@@ -847,6 +954,16 @@ where
         &mut self.right_mut().node_adapter
     }
 
+    /// Returns the handle to the local miner, if the node runs in dev mode.
+    pub const fn local_miner(&self) -> Option<&LocalMinerHandle> {
+        self.right().local_miner.as_ref()
+    }
+
+    /// Returns the pool of submitted bundles, if enabled with `--builder.bundles`.
+    pub const fn bundle_pool(&self) -> Option<&BundlePool<TxTy<T::Types>>> {
+        self.right().bundle_pool.as_ref()
+    }
+
     /// Returns a reference to the blockchain provider.
     pub const fn blockchain_db(&self) -> &T::Provider {
         &self.node_adapter().provider
@@ -1165,6 +1282,13 @@ where
     db_provider_container: WithMeteredProvider<NodeTypesWithDBAdapter<T::Types, T::DB>>,
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
+    local_miner: Option<LocalMinerHandle>,
+    bundle_pool: Option<BundlePool<TxTy<T::Types>>>,
+}
+
+/// Converts an Ethereum header into the node's header type through its RLP encoding.
+fn convert_header<H: Decodable>(header: &Header) -> alloy_rlp::Result<H> {
+    H::decode(&mut alloy_rlp::encode(header).as_slice())
 }
 
 #[cfg(test)]
@@ -1206,6 +1330,12 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    address_appearances_full: false,
+                    address_appearances_distance: None,
+                    address_appearances_before: None,
+                    log_index_full: false,
+                    log_index_distance: None,
+                    log_index_before: None,
                     bodies_pre_merge: false,
                     bodies_distance: None,
                     receipts_log_filter: None,
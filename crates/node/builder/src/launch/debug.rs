@@ -236,18 +236,18 @@ where
                 Either::Right(builder)
             };
 
-            let dev_mining_mode = handle.node.config.dev_mining_mode(pool);
-            handle.node.task_executor.spawn_critical("local engine", async move {
-                LocalMiner::new(
-                    blockchain_db,
-                    builder,
-                    beacon_engine_handle,
-                    dev_mining_mode,
-                    payload_builder_handle,
-                )
-                .run()
-                .await
-            });
+            let dev_mining_mode = handle.node.config.dev_mining_mode(pool.clone());
+            let mut miner = LocalMiner::new(
+                blockchain_db,
+                builder,
+                beacon_engine_handle,
+                dev_mining_mode,
+                payload_builder_handle,
+            );
+            if let Some(local_miner) = handle.node.local_miner.clone() {
+                miner = miner.with_handle(local_miner, pool, handle.node.set_head_handle.clone());
+            }
+            handle.node.task_executor.spawn_critical("local engine", miner.run());
         }
 
         Ok(handle)
@@ -14,16 +14,23 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
+    persistence::PersistenceStage,
     tree::TreeConfig,
 };
-use reth_engine_util::EngineMessageStreamExt;
+use reth_engine_util::{
+    engine_store::EngineMessageKind,
+    replay::{EngineMessageReplay, ReplayFilter},
+    EngineMessageStreamExt,
+};
 use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
-    BuiltPayload, ConsensusEngineHandle, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
+    BadBlock, BadBlockStore, BuiltPayload, ConsensusEngineEvent, ConsensusEngineHandle,
+    FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter, SetHeadHandle,
 };
 use reth_node_core::{
+    args::EngineApiMessageType,
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
     primitives::Head,
@@ -33,10 +40,11 @@ use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader,
 };
+use reth_stages::stages::{IndexAddressAppearancesStage, IndexLogsStage, IndexTracesStage};
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, error, info};
-use std::{future::Future, pin::Pin, sync::Arc};
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::{future::Future, path::PathBuf, pin::Pin, sync::Arc};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
@@ -170,10 +178,63 @@ impl EngineNodeLauncher {
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
+        // The opt-in indices follow the blocks written to disk, their stages run on a separate
+        // thread and are unwound by the persistence service before blocks are removed.
+        let stages_config = &ctx.toml_config().stages;
+        let prune_modes = ctx.prune_modes();
+        let mut persistence_stages = Vec::<PersistenceStage<_>>::new();
+        if stages_config.index_address_appearances.enabled {
+            persistence_stages.push(Box::new(IndexAddressAppearancesStage::new(
+                stages_config.index_address_appearances,
+                stages_config.etl.clone(),
+                prune_modes.address_appearances,
+            )));
+        }
+        if stages_config.index_logs.enabled {
+            persistence_stages.push(Box::new(IndexLogsStage::new(
+                stages_config.index_logs,
+                stages_config.etl.clone(),
+                prune_modes.log_index,
+            )));
+        }
+        if stages_config.index_traces.enabled {
+            persistence_stages.push(Box::new(IndexTracesStage::new(
+                ctx.components().evm_config().clone(),
+                stages_config.index_traces,
+                stages_config.etl.clone(),
+            )));
+        }
+
         let event_sender = EventSender::default();
 
+        // keep the most recent invalid blocks around, so they can be inspected over RPC
+        let bad_block_store = BadBlockStore::new(ctx.data_dir().bad_blocks());
+        let mut invalid_blocks = event_sender.new_listener();
+        ctx.task_executor().spawn(Box::pin(async move {
+            while let Some(event) = invalid_blocks.next().await {
+                if let ConsensusEngineEvent::InvalidBlock(block, reason) = event {
+                    let bad_block = BadBlock::new(&block, reason);
+                    let store = bad_block_store.clone();
+                    match tokio::task::spawn_blocking(move || store.insert(&bad_block)).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(err)) => {
+                            warn!(target: "reth::cli", %err, "Failed to store bad block")
+                        }
+                        Err(err) => {
+                            warn!(target: "reth::cli", %err, "Failed to spawn bad block insert")
+                        }
+                    }
+                }
+            }
+        }));
+
         let beacon_engine_handle = ConsensusEngineHandle::new(consensus_engine_tx.clone());
 
+        // requests to unwind the canonical chain are forwarded to the engine tree
+        let (set_head_tx, set_head_rx) = unbounded_channel();
+        let set_head_handle = SetHeadHandle::new(set_head_tx);
+        let mut set_head_requests = UnboundedReceiverStream::new(set_head_rx);
+
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
@@ -183,6 +244,10 @@ impl EngineNodeLauncher {
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            local_miner: ctx.local_miner().cloned(),
+            set_head_handle: set_head_handle.clone(),
+            database: ctx.provider_factory().db_ref().clone(),
+            bundle_pool: ctx.bundle_pool().cloned(),
         };
         let validator_builder = add_ons.engine_validator_builder();
 
@@ -192,6 +257,19 @@ impl EngineNodeLauncher {
             .build_tree_validator(&add_ons_ctx, engine_tree_config.clone())
             .await?;
 
+        // replayed messages are sent through the consensus engine stream, so they would be stored
+        // again in the directory they are replayed from
+        if let (Some(replay), Some(store)) =
+            (&node_config.debug.engine_api_replay, &node_config.debug.engine_api_store)
+        {
+            let canonical = |path: &PathBuf| path.canonicalize().unwrap_or_else(|_| path.clone());
+            if canonical(replay) == canonical(store) {
+                eyre::bail!(
+                    "--debug.engine-api-replay and --debug.engine-api-store must be different paths"
+                )
+            }
+        }
+
         // Create the consensus engine stream with optional reorg
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
             .maybe_skip_fcu(node_config.debug.skip_fcu)
@@ -219,6 +297,7 @@ impl EngineNodeLauncher {
             ctx.provider_factory().clone(),
             ctx.blockchain_db().clone(),
             pruner,
+            persistence_stages,
             ctx.components().payload_builder_handle().clone(),
             engine_validator,
             engine_tree_config,
@@ -248,6 +327,52 @@ impl EngineNodeLauncher {
         let RpcHandle { rpc_server_handles, rpc_registry, engine_events, beacon_engine_handle } =
             add_ons.launch_add_ons(add_ons_ctx).await?;
 
+        // replay stored engine API messages into the engine once it is running
+        if let Some(path) = ctx.node_config().debug.engine_api_replay.clone() {
+            let debug = &ctx.node_config().debug;
+            let mut replay = EngineMessageReplay::new(path.clone()).with_filter(ReplayFilter {
+                kind: debug.engine_api_replay_message_type.map(|message_type| match message_type {
+                    EngineApiMessageType::Fcu => EngineMessageKind::ForkchoiceUpdated,
+                    EngineApiMessageType::NewPayload => EngineMessageKind::NewPayload,
+                }),
+                from_block: debug.engine_api_replay_from,
+                to_block: debug.engine_api_replay_to,
+            });
+            if let Some(speed) = debug.engine_api_replay_speed {
+                replay = replay.with_speed(speed);
+            }
+
+            let engine_handle = beacon_engine_handle.clone();
+            ctx.task_executor().spawn(Box::pin(async move {
+                info!(target: "reth::cli", path = %path.display(), "Replaying engine API messages");
+                match replay.run(&engine_handle).await {
+                    Ok(report) => {
+                        let divergences = report.divergences(None);
+                        for divergence in &divergences {
+                            let message = divergence.message;
+                            warn!(
+                                target: "reth::cli",
+                                file = %message.file,
+                                kind = %message.kind,
+                                block_hash = %message.block_hash,
+                                status = ?message.status,
+                                "Replayed engine API message diverged"
+                            );
+                        }
+                        info!(
+                            target: "reth::cli",
+                            messages = report.messages.len(),
+                            divergences = divergences.len(),
+                            "Finished replaying engine API messages"
+                        );
+                    }
+                    Err(err) => {
+                        error!(target: "reth::cli", %err, "Failed to replay engine API messages")
+                    }
+                }
+            }));
+        }
+
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
         let mut built_payloads = ctx
@@ -261,6 +386,7 @@ impl EngineNodeLauncher {
 
         let chainspec = ctx.chain_spec();
         let provider = ctx.blockchain_db().clone();
+        let local_miner = ctx.local_miner().cloned();
         let (exit, rx) = oneshot::channel();
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
 
@@ -282,6 +408,10 @@ impl EngineNodeLauncher {
                             engine_service.orchestrator_mut().handler_mut().handler_mut().on_event(EngineApiRequest::InsertExecutedBlock(executed_block).into());
                         }
                     }
+                    Some(request) = set_head_requests.next() => {
+                        debug!(target: "reth::cli", block_number=request.block_number, "setting canonical head");
+                        engine_service.orchestrator_mut().handler_mut().handler_mut().on_event(EngineApiRequest::SetHead(request).into());
+                    }
                     event = engine_service.next() => {
                         let Some(event) = event else { break };
                         debug!(target: "reth::cli", "Event: {event}");
@@ -320,6 +450,11 @@ impl EngineNodeLauncher {
                                     };
                                     network_handle.update_block_range(updated);
                                 }
+                                // the local miner waits for executed blocks built by the payload
+                                // builder to be inserted before sending them as new payloads
+                                if let (Some(local_miner), ConsensusEngineEvent::CanonicalBlockAdded(block, _)) = (&local_miner, &ev) {
+                                    local_miner.on_executed_block_inserted(block.recovered_block().hash());
+                                }
                                 event_sender.notify(ev);
                             }
                         }
@@ -345,6 +480,8 @@ impl EngineNodeLauncher {
                 engine_events,
                 beacon_engine_handle,
             },
+            local_miner: ctx.local_miner().cloned(),
+            set_head_handle,
         };
         // Notify on node started
         on_node_started.on_event(FullNode::clone(&full_node))?;
@@ -6,7 +6,8 @@ use crate::{
     components::NodeComponentsBuilder, rpc::RethRpcAddOns, NodeAdapter, NodeAddOns, NodeHandle,
     RethFullAdapter,
 };
-use reth_node_api::{EngineTypes, FullNodeComponents, PayloadTypes};
+use reth_engine_local::LocalMinerHandle;
+use reth_node_api::{EngineTypes, FullNodeComponents, PayloadTypes, SetHeadHandle};
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
@@ -123,6 +124,10 @@ pub struct FullNode<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub data_dir: ChainPath<DataDirPath>,
     /// The handle to launched add-ons
     pub add_ons_handle: AddOns::Handle,
+    /// Handle to the local miner, set when the node runs in dev mode.
+    pub local_miner: Option<LocalMinerHandle>,
+    /// Handle to unwind the canonical chain.
+    pub set_head_handle: SetHeadHandle,
 }
 
 impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node, AddOns> {
@@ -137,6 +142,8 @@ impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node
             config: self.config.clone(),
             data_dir: self.data_dir.clone(),
             add_ons_handle: self.add_ons_handle.clone(),
+            local_miner: self.local_miner.clone(),
+            set_head_handle: self.set_head_handle.clone(),
         }
     }
 }
@@ -23,7 +23,11 @@ use reth_node_core::{
     version::{version_metadata, CLIENT_CODE},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
-use reth_rpc::eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer};
+use reth_provider::StaticFileProviderFactory;
+use reth_rpc::{
+    eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer},
+    DbInspector, DebugApiHandles,
+};
 use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
@@ -953,7 +957,16 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            set_head_handle,
+            database,
+            ..
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -977,17 +990,27 @@ where
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
 
         let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-        let module_config = config.rpc.transport_rpc_module_config();
+        let mut module_config = config.rpc.transport_rpc_module_config();
+        let debug_config = module_config.config_mut().get_or_insert_default().debug_mut();
+        debug_config.bad_blocks_dir = Some(config.datadir().bad_blocks());
+        debug_config.standard_traces_dir = Some(config.datadir().standard_traces());
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
-        let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
+        let debug_handles = DebugApiHandles {
+            set_head_handle: Some(set_head_handle),
+            db_inspector: Some(DbInspector::new(database, node.provider().static_file_provider())),
+        };
+        let mut registry = RpcModuleBuilder::default()
             .with_provider(node.provider().clone())
             .with_pool(node.pool().clone())
             .with_network(node.network().clone())
             .with_executor(Box::new(node.task_executor().clone()))
             .with_evm_config(node.evm_config().clone())
             .with_consensus(node.consensus().clone())
-            .build_with_auth_server(module_config, engine_api, eth_api);
+            .into_registry(module_config.config().cloned().unwrap_or_default(), eth_api)
+            .with_debug_handles(debug_handles);
+        let mut modules = registry.create_transport_rpc_modules(module_config);
+        let mut auth_module = registry.create_auth_module(engine_api);
 
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
@@ -3,13 +3,13 @@
 use alloy_primitives::B256;
 use clap::{
     builder::{PossibleValue, TypedValueParser},
-    Arg, Args, Command,
+    Arg, Args, Command, ValueEnum,
 };
 use std::{collections::HashSet, ffi::OsStr, fmt, path::PathBuf, str::FromStr};
 use strum::{AsRefStr, EnumIter, IntoStaticStr, ParseError, VariantArray, VariantNames};
 
 /// Parameters for debugging purposes
-#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[derive(Debug, Clone, Args, PartialEq)]
 #[command(next_help_heading = "Debug")]
 pub struct DebugArgs {
     /// Flag indicating whether the node should be terminated after the pipeline sync.
@@ -72,6 +72,57 @@ pub struct DebugArgs {
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
+    /// The path to replay engine API messages from.
+    /// If specified, the messages stored there by `--debug.engine-api-store`
+    /// are sent to the engine once the node has started.
+    /// Must not be the path of `--debug.engine-api-store`.
+    #[arg(long = "debug.engine-api-replay", help_heading = "Debug", value_name = "PATH")]
+    pub engine_api_replay: Option<PathBuf>,
+
+    /// Only replay payloads with a block number greater than or equal to this one.
+    ///
+    /// Forkchoice updates are only replayed if their head block is a replayed payload.
+    #[arg(
+        long = "debug.engine-api-replay-from",
+        help_heading = "Debug",
+        value_name = "BLOCK",
+        requires = "engine_api_replay"
+    )]
+    pub engine_api_replay_from: Option<u64>,
+
+    /// Only replay payloads with a block number less than or equal to this one.
+    ///
+    /// Forkchoice updates are only replayed if their head block is a replayed payload.
+    #[arg(
+        long = "debug.engine-api-replay-to",
+        help_heading = "Debug",
+        value_name = "BLOCK",
+        requires = "engine_api_replay"
+    )]
+    pub engine_api_replay_to: Option<u64>,
+
+    /// Only replay engine API messages of this type.
+    #[arg(
+        long = "debug.engine-api-replay-message-type",
+        help_heading = "Debug",
+        value_name = "TYPE",
+        requires = "engine_api_replay"
+    )]
+    pub engine_api_replay_message_type: Option<EngineApiMessageType>,
+
+    /// Preserve the recorded delays between replayed engine API messages, divided by this
+    /// factor.
+    ///
+    /// Messages are sent back to back if not set.
+    #[arg(
+        long = "debug.engine-api-replay-speed",
+        help_heading = "Debug",
+        value_name = "FACTOR",
+        value_parser = parse_engine_api_replay_speed,
+        requires = "engine_api_replay"
+    )]
+    pub engine_api_replay_speed: Option<f64>,
+
     /// Determines which type of invalid block hook to install
     ///
     /// Example: `witness,prestate`
@@ -116,6 +167,11 @@ impl Default for DebugArgs {
             reorg_frequency: None,
             reorg_depth: None,
             engine_api_store: None,
+            engine_api_replay: None,
+            engine_api_replay_from: None,
+            engine_api_replay_to: None,
+            engine_api_replay_message_type: None,
+            engine_api_replay_speed: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
             ethstats: None,
@@ -273,6 +329,24 @@ impl TypedValueParser for InvalidBlockSelectionValueParser {
     }
 }
 
+/// The types of engine API messages stored by `--debug.engine-api-store`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EngineApiMessageType {
+    /// `engine_forkchoiceUpdated` calls.
+    Fcu,
+    /// `engine_newPayload` calls.
+    NewPayload,
+}
+
+/// Parses a positive engine API replay speed factor.
+pub fn parse_engine_api_replay_speed(value: &str) -> Result<f64, String> {
+    let speed = value.parse::<f64>().map_err(|err| err.to_string())?;
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err("speed must be a positive number".to_string())
+    }
+    Ok(speed)
+}
+
 /// The type of invalid block hook to install
 #[derive(
     Debug,
@@ -349,6 +423,46 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_parse_engine_api_replay_args() {
+        let expected_args = DebugArgs {
+            engine_api_replay: Some(PathBuf::from("/tmp/engine-messages")),
+            engine_api_replay_from: Some(10),
+            engine_api_replay_message_type: Some(EngineApiMessageType::NewPayload),
+            engine_api_replay_speed: Some(2.5),
+            ..Default::default()
+        };
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.engine-api-replay",
+            "/tmp/engine-messages",
+            "--debug.engine-api-replay-from",
+            "10",
+            "--debug.engine-api-replay-message-type",
+            "new-payload",
+            "--debug.engine-api-replay-speed",
+            "2.5",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+
+        // the filters require a replay path
+        assert!(CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.engine-api-replay-from",
+            "10",
+        ])
+        .is_err());
+        assert!(CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.engine-api-replay",
+            "/tmp/engine-messages",
+            "--debug.engine-api-replay-speed",
+            "0",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_invalid_block_args() {
         let expected_args = DebugArgs {
@@ -1,6 +1,6 @@
 use clap::Args;
 use reth_chainspec::{ChainKind, NamedChain};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use url::Url;
 
 /// Syncs ERA1 encoded blocks from a local or remote source.
@@ -18,6 +18,13 @@ pub struct EraArgs {
     /// Describes where to get the ERA files to import from.
     #[clap(flatten)]
     pub source: EraSourceArgs,
+
+    /// The path to a file with the known accumulator roots of the ERA1 files.
+    ///
+    /// The file contains one hex encoded root per line, in the order of the eras. Every imported
+    /// ERA1 file is verified against the root of its era.
+    #[arg(long = "era.historical-roots", value_name = "ERA_HISTORICAL_ROOTS", verbatim_doc_comment)]
+    pub historical_roots: Option<PathBuf>,
 }
 
 /// Arguments for the block history import based on ERA1 encoded files.
@@ -27,6 +34,9 @@ pub struct EraSourceArgs {
     /// The path to a directory for import.
     ///
     /// The ERA1 files are read from the local directory parsing headers and bodies.
+    ///
+    /// Post-merge ERA files are read after them, taking headers and bodies from the execution
+    /// payloads of the beacon blocks.
     #[arg(long = "era.path", value_name = "ERA_PATH", verbatim_doc_comment)]
     pub path: Option<Box<Path>>,
 
@@ -0,0 +1,74 @@
+//! clap [Args](clap::Args) for forking the dev chain from a remote node
+
+use clap::Args;
+use url::Url;
+
+/// Parameters for forking the dev chain from a remote node
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
+#[command(next_help_heading = "Dev testnet")]
+pub struct ForkArgs {
+    /// The URL of a remote node to fork the dev chain from.
+    ///
+    /// The dev chain continues the remote chain from the fork block, with the chain id of the
+    /// remote chain. Remote accounts, storage, code and block hashes are fetched on demand and
+    /// cached in the datadir.
+    #[arg(long = "fork-url", help_heading = "Dev testnet", requires = "dev", value_name = "URL")]
+    pub fork_url: Option<Url>,
+
+    /// The remote block to fork from.
+    ///
+    /// Defaults to the latest block of the remote node, or to the block the datadir was forked
+    /// from before.
+    #[arg(
+        long = "fork-block",
+        help_heading = "Dev testnet",
+        requires = "fork_url",
+        value_name = "BLOCK_NUMBER"
+    )]
+    pub fork_block: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::DevArgs;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser {
+        #[command(flatten)]
+        dev: DevArgs,
+        #[command(flatten)]
+        fork: ForkArgs,
+    }
+
+    #[test]
+    fn test_parse_fork_args() {
+        let args = CommandParser::parse_from(["reth"]).fork;
+        assert_eq!(args, ForkArgs::default());
+
+        let args = CommandParser::parse_from([
+            "reth",
+            "--dev",
+            "--fork-url",
+            "http://localhost:8545",
+            "--fork-block",
+            "100",
+        ])
+        .fork;
+        assert_eq!(
+            args,
+            ForkArgs {
+                fork_url: Some("http://localhost:8545".parse().unwrap()),
+                fork_block: Some(100)
+            }
+        );
+
+        let args = CommandParser::try_parse_from(["reth", "--fork-url", "http://localhost:8545"]);
+        assert!(args.is_err());
+
+        let args = CommandParser::try_parse_from(["reth", "--dev", "--fork-block", "1"]);
+        assert!(args.is_err());
+    }
+}
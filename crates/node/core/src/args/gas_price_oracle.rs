@@ -29,18 +29,25 @@ pub struct GasPriceOracleArgs {
     /// The default gas price to use if there are no blocks to use
     #[arg(long = "gpo.default-suggested-fee")]
     pub default_suggested_fee: Option<U256>,
+
+    /// Raise the suggested priority fee to the tip required for inclusion in the next block,
+    /// estimated from the pending transactions in the pool
+    #[arg(long = "gpo.mempool")]
+    pub mempool: bool,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [`GasPriceOracleConfig`] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile, default_suggested_fee } = self;
+        let Self { blocks, ignore_price, max_price, percentile, default_suggested_fee, mempool } =
+            self;
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
             default_suggested_fee: *default_suggested_fee,
+            mempool: *mempool,
             ..Default::default()
         }
     }
@@ -54,6 +61,7 @@ impl Default for GasPriceOracleArgs {
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
             default_suggested_fee: None,
+            mempool: false,
         }
     }
 }
@@ -80,6 +88,7 @@ mod tests {
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
                 default_suggested_fee: None,
+                mempool: false,
             }
         );
     }
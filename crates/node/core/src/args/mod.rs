@@ -14,7 +14,10 @@ pub use rpc_state_cache::RpcStateCacheArgs;
 
 /// DebugArgs struct for debugging purposes
 mod debug;
-pub use debug::{DebugArgs, InvalidBlockHookType, InvalidBlockSelection};
+pub use debug::{
+    parse_engine_api_replay_speed, DebugArgs, EngineApiMessageType, InvalidBlockHookType,
+    InvalidBlockSelection,
+};
 
 /// DatabaseArgs struct for configuring the database
 mod database;
@@ -44,6 +47,10 @@ pub use txpool::TxPoolArgs;
 mod dev;
 pub use dev::DevArgs;
 
+/// ForkArgs for forking the dev chain from a remote node
+mod fork;
+pub use fork::ForkArgs;
+
 /// PruneArgs for configuring the pruning and full node
 mod pruning;
 pub use pruning::PruningArgs;
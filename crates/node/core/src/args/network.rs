@@ -18,6 +18,7 @@ use reth_discv5::{
 };
 use reth_net_nat::{NatResolver, DEFAULT_NET_IF_NAME};
 use reth_network::{
+    eth_requests::DEFAULT_SNAP_STATE_WINDOW,
     transactions::{
         config::TransactionPropagationKind,
         constants::{
@@ -184,6 +185,14 @@ pub struct NetworkArgs {
     /// Peers that don't have these blocks will be filtered out.
     #[arg(long = "required-block-hashes", value_delimiter = ',')]
     pub required_block_hashes: Vec<B256>,
+
+    /// Serve `snap/1` requests from the persisted state and announce the protocol to peers.
+    #[arg(long = "snap.serve")]
+    pub snap: bool,
+
+    /// Number of the most recently persisted blocks whose state is served over `snap/1`.
+    #[arg(long = "snap.state-window", value_name = "BLOCKS", default_value_t = DEFAULT_SNAP_STATE_WINDOW)]
+    pub snap_state_window: u64,
 }
 
 impl NetworkArgs {
@@ -297,6 +306,7 @@ impl NetworkArgs {
             ))
             .disable_tx_gossip(self.disable_tx_gossip)
             .required_block_hashes(self.required_block_hashes.clone())
+            .apply(|builder| if self.snap { builder.with_snap() } else { builder })
     }
 
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
@@ -371,6 +381,8 @@ impl Default for NetworkArgs {
             disable_tx_gossip: false,
             propagation_mode: TransactionPropagationMode::Sqrt,
             required_block_hashes: vec![],
+            snap: false,
+            snap_state_window: DEFAULT_SNAP_STATE_WINDOW,
         }
     }
 }
@@ -591,6 +603,23 @@ mod tests {
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
     }
 
+    #[test]
+    fn parse_snap_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert!(!args.snap);
+        assert_eq!(args.snap_state_window, DEFAULT_SNAP_STATE_WINDOW);
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--snap.serve",
+            "--snap.state-window",
+            "64",
+        ])
+        .args;
+        assert!(args.snap);
+        assert_eq!(args.snap_state_window, 64);
+    }
+
     #[test]
     fn parse_peer_args() {
         let args =
@@ -6,7 +6,7 @@ use clap::{
     Arg, Args, Command,
 };
 use reth_cli_util::{parse_duration_from_secs, parse_duration_from_secs_or_ms};
-use std::{borrow::Cow, ffi::OsStr, time::Duration};
+use std::{borrow::Cow, ffi::OsStr, path::PathBuf, time::Duration};
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -35,6 +35,18 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Enables the bundle pool and includes bundles submitted via `eth_sendBundle` and
+    /// `mev_sendBundle` in built payloads.
+    #[arg(long = "builder.bundles", default_value_t = false)]
+    pub bundles: bool,
+
+    /// Path to a file containing the hex encoded private key that pays the refunds of bundles.
+    ///
+    /// Refunds are paid from the fee recipient of the block, so bundles that request a refund
+    /// are only included in blocks whose fee recipient is the address of this key.
+    #[arg(long = "builder.refund-key", value_name = "PATH", requires = "bundles")]
+    pub refund_key: Option<PathBuf>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +57,8 @@ impl Default for PayloadBuilderArgs {
             gas_limit: None,
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            bundles: false,
+            refund_key: None,
         }
     }
 }
@@ -158,6 +172,33 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_args_with_bundles() {
+        let args =
+            CommandParser::<PayloadBuilderArgs>::parse_from(["reth", "--builder.bundles"]).args;
+        assert!(args.bundles)
+    }
+
+    #[test]
+    fn test_args_with_refund_key() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.bundles",
+            "--builder.refund-key",
+            "refund.key",
+        ])
+        .args;
+        assert_eq!(args.refund_key, Some(PathBuf::from("refund.key")));
+
+        // refunds are only paid for bundles
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.refund-key",
+            "refund.key",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_args_with_s_interval() {
         let args =
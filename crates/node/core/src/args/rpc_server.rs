@@ -174,6 +174,11 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
 
+    /// Maximum number of blocks that could be looked up in the log index per filter request.
+    /// (0 = entire chain)
+    #[arg(long = "rpc.max-indexed-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_INDEXED_BLOCKS_PER_FILTER))]
+    pub rpc_max_indexed_blocks_per_filter: ZeroAsNoneU64,
+
     /// Maximum number of logs that can be returned in a single response. (0 = no limit)
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
@@ -406,6 +411,8 @@ impl Default for RpcServerArgs {
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
+            rpc_max_indexed_blocks_per_filter:
+                constants::DEFAULT_MAX_INDEXED_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_tx_fee_cap: constants::DEFAULT_TX_FEE_CAP_WEI,
@@ -50,4 +50,16 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The opt-in address appearances stage within the pipeline.
+    ///
+    /// Indexes the blocks in which each address appears.
+    AddressAppearances,
+    /// The opt-in log index stage within the pipeline.
+    ///
+    /// Indexes the blocks containing logs of each address and topic.
+    LogIndex,
+    /// The opt-in trace index stage within the pipeline.
+    ///
+    /// Indexes the blocks containing traces touching each address.
+    TraceIndex,
 }
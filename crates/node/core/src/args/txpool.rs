@@ -7,7 +7,7 @@ use clap::Args;
 use reth_cli_util::parse_duration_from_secs_or_ms;
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
-    maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
+    maintain::{DEFAULT_TXPOOL_SNAPSHOT_INTERVAL, MAX_QUEUED_TRANSACTION_LIFETIME},
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
@@ -53,6 +53,16 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.blob-cache-size", alias = "txpool.blob_cache_size")]
     pub blob_cache_size: Option<u32>,
 
+    /// Hard limit on the total size of the blob files on disk in megabytes. The oldest blobs of
+    /// transactions that were removed from the pool are evicted to make room for new blobs, and
+    /// blob transactions whose blobs still don't fit are rejected.
+    #[arg(long = "txpool.blobstore-max-size")]
+    pub blobstore_max_size: Option<usize>,
+
+    /// Keeps the blob store on disk across restarts instead of clearing it on startup.
+    #[arg(long = "txpool.blobstore-persist")]
+    pub blobstore_persist: bool,
+
     /// Max number of executable transaction slots guaranteed per account
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
@@ -133,9 +143,27 @@ pub struct TxPoolArgs {
     )]
     pub disable_transactions_backup: bool,
 
+    /// Enables periodic snapshots of all transactions in the pool to disk, which are reinserted
+    /// into the pool after a restart.
+    #[arg(long = "txpool.snapshot")]
+    pub snapshot: bool,
+
+    /// Path to store the transaction pool snapshot at.
+    #[arg(long = "txpool.snapshot-path", value_name = "PATH", requires = "snapshot")]
+    pub snapshot_path: Option<std::path::PathBuf>,
+
+    /// Interval at which the transaction pool snapshot is written to disk.
+    #[arg(long = "txpool.snapshot-interval", value_parser = parse_duration_from_secs_or_ms, default_value = "60", value_name = "DURATION")]
+    pub snapshot_interval: Duration,
+
     /// Max batch size for transaction pool insertions
     #[arg(long = "txpool.max-batch-size", default_value_t = 1)]
     pub max_batch_size: usize,
+
+    /// Enables `eth_sendRawTransactionConditional`, transactions submitted with a conditional are
+    /// kept in the pool until the conditional can no longer be met.
+    #[arg(long = "txpool.enable-tx-conditional")]
+    pub enable_tx_conditional: bool,
 }
 
 impl TxPoolArgs {
@@ -168,6 +196,8 @@ impl Default for TxPoolArgs {
             blobpool_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             blobpool_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             blob_cache_size: None,
+            blobstore_max_size: None,
+            blobstore_persist: false,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
@@ -187,7 +217,11 @@ impl Default for TxPoolArgs {
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             transactions_backup_path: None,
             disable_transactions_backup: false,
+            snapshot: false,
+            snapshot_path: None,
+            snapshot_interval: DEFAULT_TXPOOL_SNAPSHOT_INTERVAL,
             max_batch_size: 1,
+            enable_tx_conditional: false,
         }
     }
 }
@@ -289,4 +323,41 @@ mod tests {
 
         assert!(result.is_err(), "Expected an error for invalid duration");
     }
+
+    #[test]
+    fn txpool_parse_snapshot() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.snapshot",
+            "--txpool.snapshot-path",
+            "snapshot.bin",
+            "--txpool.snapshot-interval",
+            "30",
+        ])
+        .args;
+        assert!(args.snapshot);
+        assert_eq!(args.snapshot_path, Some("snapshot.bin".into()));
+        assert_eq!(args.snapshot_interval, Duration::from_secs(30));
+
+        // a snapshot path requires snapshots to be enabled
+        let result = CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.snapshot-path",
+            "snapshot.bin",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn txpool_parse_blobstore() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.blobstore-max-size",
+            "2048",
+            "--txpool.blobstore-persist",
+        ])
+        .args;
+        assert_eq!(args.blobstore_max_size, Some(2048));
+        assert!(args.blobstore_persist);
+    }
 }
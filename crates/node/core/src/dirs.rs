@@ -330,6 +330,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the transaction pool snapshot file
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-snapshot.bin`
+    pub fn txpool_snapshot(&self) -> PathBuf {
+        self.data_dir().join("txpool-snapshot.bin")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -351,6 +358,27 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the bad blocks directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/bad_blocks`
+    pub fn bad_blocks(&self) -> PathBuf {
+        self.data_dir().join("bad_blocks")
+    }
+
+    /// Returns the path to the standard traces directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/standard_traces`
+    pub fn standard_traces(&self) -> PathBuf {
+        self.data_dir().join("standard_traces")
+    }
+
+    /// Returns the path to the directory of the remote state caches of forked dev chains.
+    ///
+    /// `<DIR>/<CHAIN_ID>/fork`
+    pub fn fork(&self) -> PathBuf {
+        self.data_dir().join("fork")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, ForkArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -146,6 +146,9 @@ pub struct NodeConfig<ChainSpec> {
     /// All dev related arguments with --dev prefix
     pub dev: DevArgs,
 
+    /// All arguments for forking the dev chain from a remote node
+    pub fork: ForkArgs,
+
     /// All pruning related arguments
     pub pruning: PruningArgs,
 
@@ -180,6 +183,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             debug: DebugArgs::default(),
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
+            fork: ForkArgs::default(),
             pruning: PruningArgs::default(),
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
@@ -283,6 +287,12 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the fork args for the node
+    pub fn with_fork(mut self, fork: ForkArgs) -> Self {
+        self.fork = fork;
+        self
+    }
+
     /// Set the pruning args for the node
     pub fn with_pruning(mut self, pruning: PruningArgs) -> Self {
         self.pruning = pruning;
@@ -487,6 +497,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             debug: self.debug,
             db: self.db,
             dev: self.dev,
+            fork: self.fork,
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
@@ -526,6 +537,7 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             debug: self.debug.clone(),
             db: self.db,
             dev: self.dev,
+            fork: self.fork.clone(),
             pruning: self.pruning.clone(),
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
@@ -3,24 +3,23 @@
 use crate::{error::TxConditionalErr, OpEthApiError, SequencerClient};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Bytes, StorageKey, B256, U256};
-use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use jsonrpsee_core::RpcResult;
 use reth_optimism_txpool::conditional::MaybeConditionalTransaction;
 use reth_rpc_eth_api::L2EthApiExtServer;
 use reth_rpc_eth_types::utils::recover_raw_transaction;
 use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
 use reth_transaction_pool::{
+    conditional::{
+        known_accounts_match, KnownAccountsError, MAX_CONCURRENT_CONDITIONAL_VALIDATIONS,
+        MAX_CONDITIONAL_EXECUTION_COST,
+    },
     AddedTransactionOutcome, PoolTransaction, TransactionOrigin, TransactionPool,
 };
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 
-/// Maximum execution const for conditional transactions.
-const MAX_CONDITIONAL_EXECUTION_COST: u64 = 5000;
-
-const MAX_CONCURRENT_CONDITIONAL_VALIDATIONS: usize = 3;
-
 /// OP-Reth `Eth` API extensions implementation.
 ///
 /// Separate from [`super::OpEthApi`] to allow to enable it conditionally,
@@ -74,33 +73,11 @@ where
             .state_by_block_number_or_tag(BlockNumberOrTag::Latest)
             .map_err(TxConditionalErr::internal)?;
 
-        for (address, storage) in &condition.known_accounts {
-            match storage {
-                AccountStorage::Slots(slots) => {
-                    for (slot, expected_value) in slots {
-                        let current = state
-                            .storage(*address, StorageKey::from(*slot))
-                            .map_err(TxConditionalErr::internal)?
-                            .unwrap_or_default();
-
-                        if current != U256::from_be_bytes(**expected_value) {
-                            return Err(TxConditionalErr::StorageValueMismatch);
-                        }
-                    }
-                }
-                AccountStorage::RootHash(expected_root) => {
-                    let actual_root = state
-                        .storage_root(*address, Default::default())
-                        .map_err(TxConditionalErr::internal)?;
-
-                    if *expected_root != actual_root {
-                        return Err(TxConditionalErr::StorageRootMismatch);
-                    }
-                }
-            }
-        }
-
-        Ok(())
+        known_accounts_match(condition, &*state).map_err(|err| match err {
+            KnownAccountsError::StorageValueMismatch => TxConditionalErr::StorageValueMismatch,
+            KnownAccountsError::StorageRootMismatch => TxConditionalErr::StorageRootMismatch,
+            KnownAccountsError::Provider(err) => TxConditionalErr::internal(err),
+        })
     }
 }
 
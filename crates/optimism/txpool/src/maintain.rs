@@ -8,11 +8,10 @@ const OFFSET_TIME: u64 = 60;
 const MAX_SUPERVISOR_QUERIES: usize = 10;
 
 use crate::{
-    conditional::MaybeConditionalTransaction,
     interop::{is_stale_interop, is_valid_interop, MaybeInteropTransaction},
     supervisor::SupervisorClient,
 };
-use alloy_consensus::{conditional::BlockConditionalAttributes, BlockHeader};
+use alloy_consensus::BlockHeader;
 use futures_util::{future::BoxFuture, FutureExt, Stream, StreamExt};
 use metrics::{Gauge, Histogram};
 use reth_chain_state::CanonStateNotification;
@@ -22,21 +21,10 @@ use reth_transaction_pool::{error::PoolTransactionError, PoolTransaction, Transa
 use std::time::Instant;
 use tracing::warn;
 
-/// Transaction pool maintenance metrics
-#[derive(Metrics)]
-#[metrics(scope = "transaction_pool")]
-struct MaintainPoolConditionalMetrics {
-    /// Counter indicating the number of conditional transactions removed from
-    /// the pool because of exceeded block attributes.
-    removed_tx_conditional: Counter,
-}
-
-impl MaintainPoolConditionalMetrics {
-    #[inline]
-    fn inc_removed_tx_conditional(&self, count: usize) {
-        self.removed_tx_conditional.increment(count as u64);
-    }
-}
+// conditional transactions are maintained the same way as in the Ethereum pool
+pub use reth_transaction_pool::maintain::{
+    maintain_transaction_pool_conditional, maintain_transaction_pool_conditional_future,
+};
 
 /// Transaction pool maintenance metrics
 #[derive(Metrics)]
@@ -76,58 +64,6 @@ impl MaintainPoolInteropMetrics {
         self.supervisor_revalidation_duration_seconds.record(duration.as_secs_f64());
     }
 }
-/// Returns a spawnable future for maintaining the state of the conditional txs in the transaction
-/// pool.
-pub fn maintain_transaction_pool_conditional_future<N, Pool, St>(
-    pool: Pool,
-    events: St,
-) -> BoxFuture<'static, ()>
-where
-    N: NodePrimitives,
-    Pool: TransactionPool + 'static,
-    Pool::Transaction: MaybeConditionalTransaction,
-    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
-{
-    async move {
-        maintain_transaction_pool_conditional(pool, events).await;
-    }
-    .boxed()
-}
-
-/// Maintains the state of the conditional tx in the transaction pool by handling new blocks and
-/// reorgs.
-///
-/// This listens for any new blocks and reorgs and updates the conditional txs in the
-/// transaction pool's state accordingly
-pub async fn maintain_transaction_pool_conditional<N, Pool, St>(pool: Pool, mut events: St)
-where
-    N: NodePrimitives,
-    Pool: TransactionPool,
-    Pool::Transaction: MaybeConditionalTransaction,
-    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
-{
-    let metrics = MaintainPoolConditionalMetrics::default();
-    loop {
-        let Some(event) = events.next().await else { break };
-        if let CanonStateNotification::Commit { new } = event {
-            let block_attr = BlockConditionalAttributes {
-                number: new.tip().number(),
-                timestamp: new.tip().timestamp(),
-            };
-            let mut to_remove = Vec::new();
-            for tx in &pool.pooled_transactions() {
-                if tx.transaction.has_exceeded_block_attributes(&block_attr) {
-                    to_remove.push(*tx.hash());
-                }
-            }
-            if !to_remove.is_empty() {
-                let removed = pool.remove_transactions(to_remove);
-                metrics.inc_removed_tx_conditional(removed.len());
-            }
-        }
-    }
-}
-
 /// Returns a spawnable future for maintaining the state of the interop tx in the transaction pool.
 pub fn maintain_transaction_pool_interop_future<N, Pool, St>(
     pool: Pool,
@@ -91,14 +91,14 @@ impl<Cons: SignedTransaction, Pooled> OpPooledTransaction<Cons, Pooled> {
     }
 }
 
-impl<Cons, Pooled> MaybeConditionalTransaction for OpPooledTransaction<Cons, Pooled> {
+impl<Cons, Pooled> MaybeConditionalTransaction for OpPooledTransaction<Cons, Pooled>
+where
+    Cons: SignedTransaction + From<Pooled>,
+    Pooled: SignedTransaction + TryFrom<Cons, Error: core::error::Error>,
+{
     fn set_conditional(&mut self, conditional: TransactionConditional) {
         self.conditional = Some(Box::new(conditional))
     }
-
-    fn conditional(&self) -> Option<&TransactionConditional> {
-        self.conditional.as_deref()
-    }
 }
 
 impl<Cons, Pooled> MaybeInteropTransaction for OpPooledTransaction<Cons, Pooled> {
@@ -167,6 +167,15 @@ where
     fn encoded_length(&self) -> usize {
         self.inner.encoded_length
     }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+
+    fn try_set_conditional(&mut self, conditional: TransactionConditional) -> bool {
+        self.set_conditional(conditional);
+        true
+    }
 }
 
 impl<Cons: Typed2718, Pooled> Typed2718 for OpPooledTransaction<Cons, Pooled> {
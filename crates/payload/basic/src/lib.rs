@@ -186,6 +186,12 @@ where
     }
 
     fn on_new_state<N: NodePrimitives>(&mut self, new_state: CanonStateNotification<N>) {
+        // the cached state of reverted blocks doesn't belong to the new head
+        let Some(tip) = new_state.tip_checked() else {
+            self.pre_cached = None;
+            return
+        };
+        let tip = tip.hash();
         let mut cached = CachedReads::default();
 
         // extract the state from the notification and put it into the cache
@@ -201,7 +207,7 @@ where
             }
         }
 
-        self.pre_cached = Some(PrecachedState { block: committed.tip().hash(), cached });
+        self.pre_cached = Some(PrecachedState { block: tip, cached });
     }
 }
 
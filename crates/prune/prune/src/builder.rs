@@ -6,9 +6,9 @@ use reth_db_api::{table::Value, transaction::DbTxMut};
 use reth_exex_types::FinishedExExHeight;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, DatabaseProviderFactory,
-    NodePrimitivesProvider, PruneCheckpointReader, PruneCheckpointWriter,
-    StaticFileProviderFactory,
+    providers::StaticFileProvider, AddressAppearancesProvider, BlockReader, DBProvider,
+    DatabaseProviderFactory, LogIndexProvider, NodePrimitivesProvider, PruneCheckpointReader,
+    PruneCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 use std::time::Duration;
@@ -83,6 +83,8 @@ impl PrunerBuilder {
                 ProviderRW: PruneCheckpointWriter
                                 + PruneCheckpointReader
                                 + BlockReader<Transaction: Encodable2718>
+                                + AddressAppearancesProvider
+                                + LogIndexProvider
                                 + StaticFileProviderFactory<
                     Primitives: NodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>,
                 >,
@@ -114,7 +116,9 @@ impl PrunerBuilder {
             > + DBProvider<Tx: DbTxMut>
             + BlockReader<Transaction: Encodable2718>
             + PruneCheckpointWriter
-            + PruneCheckpointReader,
+            + PruneCheckpointReader
+            + AddressAppearancesProvider
+            + LogIndexProvider,
     {
         let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
 
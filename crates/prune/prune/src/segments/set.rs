@@ -1,13 +1,13 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, AddressAppearances, LogIndex, ReceiptsByLogs, Segment, SenderRecovery,
+    StorageHistory, TransactionLookup, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db_api::{table::Value, transaction::DbTxMut};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, PruneCheckpointReader,
-    PruneCheckpointWriter, StaticFileProviderFactory,
+    providers::StaticFileProvider, AddressAppearancesProvider, BlockReader, DBProvider,
+    LogIndexProvider, PruneCheckpointReader, PruneCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 
@@ -52,7 +52,9 @@ where
         > + DBProvider<Tx: DbTxMut>
         + PruneCheckpointWriter
         + PruneCheckpointReader
-        + BlockReader<Transaction: Encodable2718>,
+        + BlockReader<Transaction: Encodable2718>
+        + AddressAppearancesProvider
+        + LogIndexProvider,
 {
     /// Creates a [`SegmentSet`] from an existing components, such as [`StaticFileProvider`] and
     /// [`PruneModes`].
@@ -67,6 +69,8 @@ where
             account_history,
             storage_history,
             bodies_history: _,
+            address_appearances,
+            log_index,
             receipts_log_filter,
         } = prune_modes;
 
@@ -81,6 +85,10 @@ where
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Address appearances
+            .segment_opt(address_appearances.map(AddressAppearances::new))
+            // Log index, pruned before the receipts it's looked up from
+            .segment_opt(log_index.map(LogIndex::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
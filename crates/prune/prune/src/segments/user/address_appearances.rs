@@ -0,0 +1,188 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::BlockNumber;
+use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
+use reth_provider::{AddressAppearancesProvider, DBProvider};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use std::collections::BTreeMap;
+use tracing::{instrument, trace};
+
+/// Number of blocks to look up the appearing addresses for at once.
+const BLOCKS_PER_BATCH: u64 = 1000;
+
+/// Prunes the [`tables::AddressAppearances`] index.
+///
+/// The index doesn't have changesets, so the addresses to prune are looked up from the blocks
+/// themselves. Appearances in blocks whose bodies were already pruned are kept.
+#[derive(Debug)]
+pub struct AddressAppearances {
+    mode: PruneMode,
+}
+
+impl AddressAppearances {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for AddressAppearances
+where
+    Provider: DBProvider<Tx: DbTxMut> + AddressAppearancesProvider,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressAppearances
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address appearances to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut last_pruned_block: Option<BlockNumber> = None;
+        let mut pruned = 0;
+        let mut done = true;
+        for batch_start in range.step_by(BLOCKS_PER_BATCH as usize) {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+            let batch_end = (batch_start + BLOCKS_PER_BATCH - 1).min(range_end);
+
+            // Highest appearance block number in the batch for each address. Log appearances are
+            // opt-in, but looking them up anyway is harmless: addresses that are not indexed have
+            // no shards to prune.
+            let mut highest_appearances = BTreeMap::new();
+            for (block_number, addresses) in
+                provider.block_address_appearances(batch_start..=batch_end, true)?
+            {
+                for address in addresses {
+                    highest_appearances.insert(address, block_number);
+                }
+            }
+
+            let outcomes = prune_history_indices::<Provider, tables::AddressAppearances, _>(
+                provider,
+                highest_appearances
+                    .into_iter()
+                    .map(|(address, block_number)| ShardedKey::new(address, block_number)),
+                |a, b| a.key == b.key,
+            )?;
+            trace!(target: "pruner", ?outcomes, batch = ?batch_start..=batch_end, "Pruned address appearances");
+
+            limiter.increment_deleted_entries_count_by(outcomes.deleted + outcomes.updated);
+            pruned += outcomes.deleted;
+            last_pruned_block = Some(batch_end);
+        }
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressAppearances, PruneInput, PruneLimiter, Segment};
+    use alloy_primitives::{Address, B256};
+    use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut, BlockNumberList};
+    use reth_provider::{DBProvider, DatabaseProviderFactory};
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..2, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // Index the recipient of every block's first transaction.
+        let provider = db.factory.database_provider_rw().unwrap();
+        let recipients = blocks
+            .iter()
+            .map(|block| {
+                let tx = &block.body().transactions[0];
+                (block.number, alloy_consensus::Transaction::to(tx).unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+        for (block_number, address) in &recipients {
+            provider
+                .tx_ref()
+                .put::<tables::AddressAppearances>(
+                    ShardedKey::last(*address),
+                    BlockNumberList::new([*block_number]).unwrap(),
+                )
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        let to_block = 49;
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(1000),
+        };
+        let segment = AddressAppearances::new(PruneMode::Before(to_block + 1));
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(result.progress, PruneProgress::Finished);
+        assert_eq!(result.pruned, 50);
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(to_block));
+
+        let remaining = db
+            .table::<tables::AddressAppearances>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key.key)
+            .collect::<Vec<Address>>();
+        let expected = recipients
+            .iter()
+            .filter(|(block_number, _)| *block_number > to_block)
+            .map(|(_, address)| *address)
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(remaining, expected);
+    }
+}
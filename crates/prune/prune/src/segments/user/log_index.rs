@@ -0,0 +1,204 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::BlockNumber;
+use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
+use reth_provider::{DBProvider, LogIndexProvider};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use std::collections::BTreeMap;
+use tracing::{instrument, trace};
+
+/// Number of blocks to look up the log addresses and topics for at once.
+const BLOCKS_PER_BATCH: u64 = 1000;
+
+/// Prunes the [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`] indices.
+///
+/// The indices don't have changesets, so the keys to prune are looked up from the block receipts.
+/// Entries of blocks whose receipts were already pruned are kept.
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for LogIndex
+where
+    Provider: DBProvider<Tx: DbTxMut> + LogIndexProvider,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No log index to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut last_pruned_block: Option<BlockNumber> = None;
+        let mut pruned = 0;
+        let mut done = true;
+        for batch_start in range.step_by(BLOCKS_PER_BATCH as usize) {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+            let batch_end = (batch_start + BLOCKS_PER_BATCH - 1).min(range_end);
+
+            // Highest log position in the batch for each address and topic.
+            let mut highest_addresses = BTreeMap::new();
+            let mut highest_topics = BTreeMap::new();
+            for (_, keys) in provider.block_log_keys(batch_start..=batch_end)? {
+                highest_addresses.extend(keys.addresses);
+                highest_topics.extend(keys.topics);
+            }
+
+            let address_outcomes = prune_history_indices::<Provider, tables::LogAddressIndex, _>(
+                provider,
+                highest_addresses
+                    .into_iter()
+                    .map(|(address, position)| ShardedKey::new(address, position)),
+                |a, b| a.key == b.key,
+            )?;
+            let topic_outcomes = prune_history_indices::<Provider, tables::LogTopicIndex, _>(
+                provider,
+                highest_topics
+                    .into_iter()
+                    .map(|(topic, position)| ShardedKey::new(topic, position)),
+                |a, b| a.key == b.key,
+            )?;
+            trace!(target: "pruner", ?address_outcomes, ?topic_outcomes, batch = ?batch_start..=batch_end, "Pruned log index");
+
+            limiter.increment_deleted_entries_count_by(
+                address_outcomes.deleted +
+                    address_outcomes.updated +
+                    topic_outcomes.deleted +
+                    topic_outcomes.updated,
+            );
+            pruned += address_outcomes.deleted + topic_outcomes.deleted;
+            last_pruned_block = Some(batch_end);
+        }
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{LogIndex, PruneInput, PruneLimiter, Segment};
+    use alloy_primitives::{Address, B256};
+    use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut, BlockNumberList};
+    use reth_provider::{log_position, DBProvider, DatabaseProviderFactory};
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_log, random_receipt, BlockRangeParams,
+    };
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..2, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // One receipt with a single log with a single topic per block.
+        let receipts = blocks
+            .iter()
+            .map(|block| {
+                let mut receipt =
+                    random_receipt(&mut rng, &block.body().transactions[0], Some(0), None);
+                receipt.success = true;
+                receipt.logs = vec![random_log(&mut rng, None, Some(1))];
+                receipt
+            })
+            .collect::<Vec<_>>();
+        db.insert_receipts(receipts.iter().cloned().enumerate().map(|(i, r)| (i as u64, r)))
+            .expect("insert receipts");
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        for (block_number, receipt) in receipts.iter().enumerate() {
+            let log = &receipt.logs[0];
+            let list = BlockNumberList::new([log_position(block_number as u64, 0)]).unwrap();
+            provider
+                .tx_ref()
+                .put::<tables::LogAddressIndex>(ShardedKey::last(log.address), list.clone())
+                .unwrap();
+            provider
+                .tx_ref()
+                .put::<tables::LogTopicIndex>(ShardedKey::last(log.topics()[0]), list)
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        let to_block = 49;
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(1000),
+        };
+        let segment = LogIndex::new(PruneMode::Before(to_block + 1));
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(result.progress, PruneProgress::Finished);
+        assert_eq!(result.pruned, 100);
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(to_block));
+
+        let remaining = db
+            .table::<tables::LogAddressIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key.key)
+            .collect::<Vec<Address>>();
+        let mut expected =
+            receipts[to_block as usize + 1..].iter().map(|r| r.logs[0].address).collect::<Vec<_>>();
+        expected.sort_unstable();
+        assert_eq!(remaining, expected);
+        assert_eq!(db.table::<tables::LogTopicIndex>().unwrap().len(), expected.len());
+    }
+}
@@ -106,7 +106,7 @@ pub async fn maintain_pending_state<P>(
                 trace!(target: "reth::ress_provider", block = ? block.recovered_block().num_hash(), "Insert block into pending state");
                 pending_state.insert_block(block);
             }
-            ConsensusEngineEvent::InvalidBlock(block) => {
+            ConsensusEngineEvent::InvalidBlock(block, _) => {
                 if let Ok(block) = block.try_recover() {
                     trace!(target: "reth::ress_provider", block = ?block.num_hash(), "Insert invalid block into pending state");
                     pending_state.insert_invalid_block(Arc::new(block));
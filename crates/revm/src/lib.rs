@@ -21,6 +21,10 @@ pub mod cancelled;
 /// Contains glue code for integrating reth database into revm's [Database].
 pub mod database;
 
+/// State changes applied on top of the parent state of the next built block.
+#[cfg(feature = "std")]
+pub mod state_changes;
+
 pub use revm::{database as db, inspector};
 
 /// Common test helpers
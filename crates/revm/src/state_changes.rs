@@ -0,0 +1,264 @@
+use alloy_primitives::{keccak256, map::HashMap, Address, Signature, U256};
+use revm::{
+    bytecode::Bytecode,
+    database::State,
+    state::{Account, EvmState, EvmStorageSlot},
+    Database, DatabaseCommit,
+};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Signature of transactions sent from impersonated accounts on dev chains.
+///
+/// These transactions are accepted with the sender they were sent from instead of a sender
+/// recovered from their signature, so blocks including them can't be re-executed either.
+pub const IMPERSONATED_SIGNATURE: Signature = Signature::new(U256::ONE, U256::ONE, false);
+
+/// Changes to a single account queued with [`PendingStateChanges`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountChanges {
+    /// The new balance of the account.
+    pub balance: Option<U256>,
+    /// The new nonce of the account.
+    pub nonce: Option<u64>,
+    /// The new code of the account.
+    pub code: Option<Bytecode>,
+    /// The new values of storage slots of the account.
+    pub storage: HashMap<U256, U256>,
+}
+
+/// Overrides of the environment of built blocks queued with [`PendingStateChanges`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockEnvOverrides {
+    /// The beneficiary of the block.
+    pub coinbase: Option<Address>,
+    /// The gas limit of the block.
+    pub gas_limit: Option<u64>,
+    /// The base fee of the block.
+    pub base_fee: Option<u64>,
+}
+
+impl BlockEnvOverrides {
+    /// Returns `true` if the block can't be re-executed from its parent and transactions alone,
+    /// because it breaks the rules for deriving its gas limit or base fee.
+    pub const fn is_consensus_breaking(&self) -> bool {
+        self.gas_limit.is_some() || self.base_fee.is_some()
+    }
+}
+
+/// State changes applied on top of the parent state of the next built block.
+///
+/// This is used by dev chains to modify accounts outside of transactions. The changes are shared
+/// between the component queueing them and the payload builder, and are kept until they are
+/// cleared with the version of the last mined block. Overrides of the block environment are
+/// queued either for all built blocks or for the next mined block only.
+#[derive(Debug, Clone, Default)]
+pub struct PendingStateChanges(Arc<Mutex<PendingStateChangesInner>>);
+
+#[derive(Debug, Default)]
+struct PendingStateChangesInner {
+    /// Incremented on every queued change.
+    version: u64,
+    /// The changes of every account, with the version of their last change.
+    accounts: HashMap<Address, (u64, AccountChanges)>,
+    /// Overrides of the environment of all built blocks.
+    block_env: BlockEnvOverrides,
+    /// Overrides of the environment of the next mined block, with the version of their last
+    /// change.
+    next_block_env: (u64, BlockEnvOverrides),
+}
+
+// === impl PendingStateChanges ===
+
+impl PendingStateChanges {
+    fn inner(&self) -> MutexGuard<'_, PendingStateChangesInner> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queues changes to the given account.
+    pub fn update(&self, address: Address, f: impl FnOnce(&mut AccountChanges)) {
+        let mut inner = self.inner();
+        inner.version += 1;
+        let version = inner.version;
+        let (account_version, changes) = inner.accounts.entry(address).or_default();
+        *account_version = version;
+        f(changes);
+    }
+
+    /// Overrides the environment of all built blocks.
+    pub fn update_block_env(&self, f: impl FnOnce(&mut BlockEnvOverrides)) {
+        let mut inner = self.inner();
+        inner.version += 1;
+        f(&mut inner.block_env);
+    }
+
+    /// Overrides the environment of the next mined block only, on top of the overrides of all
+    /// blocks.
+    pub fn update_next_block_env(&self, f: impl FnOnce(&mut BlockEnvOverrides)) {
+        let mut inner = self.inner();
+        inner.version += 1;
+        let version = inner.version;
+        inner.next_block_env.0 = version;
+        f(&mut inner.next_block_env.1);
+    }
+
+    /// Returns the overrides of the environment of the next built block.
+    pub fn block_env(&self) -> BlockEnvOverrides {
+        let inner = self.inner();
+        let (all, next) = (inner.block_env, inner.next_block_env.1);
+        BlockEnvOverrides {
+            coinbase: next.coinbase.or(all.coinbase),
+            gas_limit: next.gas_limit.or(all.gas_limit),
+            base_fee: next.base_fee.or(all.base_fee),
+        }
+    }
+
+    /// Returns the version of the last queued change.
+    pub fn version(&self) -> u64 {
+        self.inner().version
+    }
+
+    /// Returns `true` if there are no queued changes.
+    pub fn is_empty(&self) -> bool {
+        self.inner().accounts.is_empty()
+    }
+
+    /// Removes the changes to accounts and the overrides of the next block that haven't been
+    /// changed after the given version.
+    pub fn clear_up_to(&self, version: u64) {
+        let mut inner = self.inner();
+        inner.accounts.retain(|_, (account_version, _)| *account_version > version);
+        if inner.next_block_env.0 <= version {
+            inner.next_block_env = Default::default();
+        }
+    }
+
+    /// Removes all queued changes and overrides.
+    pub fn clear(&self) {
+        let mut inner = self.inner();
+        inner.accounts.clear();
+        inner.block_env = Default::default();
+        inner.next_block_env = Default::default();
+    }
+
+    /// Commits the queued changes to the given database.
+    ///
+    /// Returns `true` if any changes were applied.
+    pub fn apply<DB: Database>(&self, db: &mut State<DB>) -> Result<bool, DB::Error> {
+        let accounts = self
+            .inner()
+            .accounts
+            .iter()
+            .map(|(address, (_, changes))| (*address, changes.clone()))
+            .collect::<Vec<_>>();
+        if accounts.is_empty() {
+            return Ok(false)
+        }
+
+        let mut state = EvmState::default();
+        for (address, changes) in accounts {
+            let mut info = db.basic(address)?.unwrap_or_default();
+            if let Some(balance) = changes.balance {
+                info.balance = balance;
+            }
+            if let Some(nonce) = changes.nonce {
+                info.nonce = nonce;
+            }
+            if let Some(code) = changes.code {
+                info.code_hash = keccak256(code.original_byte_slice());
+                info.code = Some(code);
+            }
+
+            let mut account = Account::from(info);
+            for (slot, value) in changes.storage {
+                let original = db.storage(address, slot)?;
+                account.storage.insert(slot, EvmStorageSlot::new_changed(original, value, 0));
+            }
+            account.mark_touch();
+            state.insert(address, account);
+        }
+        db.commit(state);
+
+        Ok(true)
+    }
+}
+
+impl PartialEq for PendingStateChanges {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PendingStateChanges {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+    use revm::database::{states::bundle_state::BundleRetention, CacheDB, EmptyDB};
+
+    #[test]
+    fn apply_and_clear() {
+        let changes = PendingStateChanges::default();
+        let address = address!("0x1000000000000000000000000000000000000000");
+        changes.update(address, |account| account.balance = Some(U256::from(1)));
+        changes.update(address, |account| {
+            account.code = Some(Bytecode::new_raw(bytes!("0x6000")));
+            account.storage.insert(U256::from(1), U256::from(2));
+        });
+
+        let mut db = State::builder()
+            .with_database(CacheDB::new(EmptyDB::default()))
+            .with_bundle_update()
+            .build();
+        assert!(changes.apply(&mut db).unwrap());
+        db.merge_transitions(BundleRetention::Reverts);
+        let bundle = db.take_bundle();
+
+        let account = bundle.account(&address).unwrap();
+        let info = account.info.as_ref().unwrap();
+        assert_eq!(info.balance, U256::from(1));
+        assert_eq!(info.code_hash, keccak256(bytes!("0x6000")));
+        assert_eq!(account.storage_slot(U256::from(1)), Some(U256::from(2)));
+
+        // changes queued after the mined version are kept
+        let version = changes.version();
+        let other = address!("0x2000000000000000000000000000000000000000");
+        changes.update(other, |account| account.nonce = Some(1));
+        changes.clear_up_to(version);
+        assert!(!changes.is_empty());
+        changes.clear_up_to(changes.version());
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn block_env_overrides() {
+        let changes = PendingStateChanges::default();
+        let coinbase = address!("0x1000000000000000000000000000000000000000");
+        changes.update_block_env(|env| {
+            env.coinbase = Some(coinbase);
+            env.gas_limit = Some(1_000_000);
+        });
+        changes.update_next_block_env(|env| {
+            env.gas_limit = Some(2_000_000);
+            env.base_fee = Some(7);
+        });
+
+        let env = changes.block_env();
+        assert_eq!(env.coinbase, Some(coinbase));
+        assert_eq!(env.gas_limit, Some(2_000_000));
+        assert_eq!(env.base_fee, Some(7));
+        assert!(env.is_consensus_breaking());
+
+        // the overrides of the next block are cleared once it's mined
+        let version = changes.version();
+        changes.clear_up_to(version - 1);
+        assert_eq!(changes.block_env().base_fee, Some(7));
+        changes.clear_up_to(version);
+        let env = changes.block_env();
+        assert_eq!(env.gas_limit, Some(1_000_000));
+        assert_eq!(env.base_fee, None);
+
+        changes.clear();
+        assert_eq!(changes.block_env(), BlockEnvOverrides::default());
+    }
+}
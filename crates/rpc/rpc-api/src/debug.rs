@@ -2,13 +2,67 @@ use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
 use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256};
-use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{Block, Bundle, StateContext};
+use alloy_rpc_types_debug::{AccountState, ExecutionWitness, StorageRangeResult};
+use alloy_rpc_types_eth::{Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+use std::collections::BTreeMap;
+
+/// A page of accounts returned by `debug_accountRange`.
+///
+/// Address preimages are not stored, so like geth does for accounts with an unknown preimage, the
+/// accounts are keyed by `pre(<hashed address>)`.
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRangeResult {
+    /// The state root of the block.
+    pub root: B256,
+    /// The accounts, keyed by `pre(<hashed address>)`.
+    pub accounts: BTreeMap<String, AccountState>,
+    /// The hashed address to continue from, if the range is incomplete.
+    ///
+    /// Base64 encoded, to match the geth response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<Base64>")]
+    pub next: Option<Bytes>,
+}
+
+/// A block rejected by the node, as returned by `debug_getBadBlocks`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadBlockResult {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The RLP-encoded block.
+    pub rlp: Bytes,
+    /// Why the block was rejected.
+    pub reason: String,
+}
+
+/// Options for `debug_standardTraceBlockToFile` and `debug_standardTraceBadBlockToFile`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StandardTraceOptions {
+    /// Only trace the transaction with this hash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
+    /// Omit the storage from the trace.
+    pub disable_storage: bool,
+    /// Omit the memory from the trace.
+    pub disable_memory: bool,
+}
+
+/// The kind of a `debug_subscribe` subscription.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugSubscriptionKind {
+    /// Streams the traces of a range of blocks, see `debug_traceChain`.
+    TraceChain,
+}
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -38,16 +92,25 @@ pub trait DebugApi<TxReq: RpcObject> {
 
     /// Returns an array of recent bad blocks that the client has seen on the network.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlockResult>>;
 
-    /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
-    async fn debug_trace_chain(
+    /// Creates a debug subscription.
+    ///
+    /// Like in geth, `debug_traceChain` is only available as a subscription: it returns the
+    /// structured logs created during the execution of EVM between two blocks (excluding start),
+    /// streamed block by block.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = BlockTraceResult
+    )]
+    async fn debug_subscribe(
         &self,
+        kind: DebugSubscriptionKind,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -166,7 +229,8 @@ pub trait DebugApi<TxReq: RpcObject> {
     /// in the page and the items have keys that come after the `start` key (hashed address).
     ///
     /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
-    /// exist in db are skipped. NB: geth by default does not store preimages.
+    /// exist in db are skipped. NB: geth by default does not store preimages, and reth never does,
+    /// so incompletes must be true.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
@@ -176,7 +240,7 @@ pub trait DebugApi<TxReq: RpcObject> {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<AccountRangeResult>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -193,9 +257,12 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "chainConfig")]
     async fn debug_chain_config(&self) -> RpcResult<ChainConfig>;
 
-    /// Returns leveldb properties of the key-value database.
+    /// Returns a property of the database.
+    ///
+    /// `mdbx.stats` returns the MDBX environment and table stats, `staticfiles.ranges` returns the
+    /// block and transaction ranges of the static files of each segment.
     #[method(name = "chaindbProperty")]
-    async fn debug_chaindb_property(&self, property: String) -> RpcResult<()>;
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<serde_json::Value>;
 
     /// Returns the code associated with a given hash at the specified block ID.
     /// If no block ID is provided, it defaults to the latest block.
@@ -210,19 +277,30 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "cpuProfile")]
     async fn debug_cpu_profile(&self, file: String, seconds: u64) -> RpcResult<()>;
 
-    /// Retrieves an ancient binary blob from the freezer. The freezer is a collection of
-    /// append-only immutable files. The first argument `kind` specifies which table to look up data
-    /// from. The list of all table kinds are as follows:
+    /// Retrieves an ancient binary blob from the static files. The static files are a collection of
+    /// append-only immutable files. The first argument `kind` specifies which segment to look up
+    /// data from, and is one of `headers`, `transactions` or `receipts`. Headers are looked up by
+    /// block number, transactions and receipts by transaction number.
     #[method(name = "dbAncient")]
-    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<()>;
+    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<Option<Bytes>>;
 
-    /// Returns the number of ancient items in the ancient store.
+    /// Returns the number of blocks in the static files.
     #[method(name = "dbAncients")]
-    async fn debug_db_ancients(&self) -> RpcResult<()>;
+    async fn debug_db_ancients(&self) -> RpcResult<u64>;
 
-    /// Returns the raw value of a key stored in the database.
+    /// Returns the value of a key stored in the given database table.
+    ///
+    /// Keys are given in their JSON representation, as for `reth db get`. For dupsort tables, the
+    /// optional `subkey` selects the entry. If `raw` is set, the compressed value is returned as
+    /// hex instead of the decoded value.
     #[method(name = "dbGet")]
-    async fn debug_db_get(&self, key: String) -> RpcResult<()>;
+    async fn debug_db_get(
+        &self,
+        table: String,
+        key: serde_json::Value,
+        subkey: Option<serde_json::Value>,
+        raw: Option<bool>,
+    ) -> RpcResult<Option<serde_json::Value>>;
 
     /// Retrieves the state that corresponds to the block number and returns a list of accounts
     /// (including storage and code).
@@ -260,17 +338,18 @@ pub trait DebugApi<TxReq: RpcObject> {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -283,7 +362,7 @@ pub trait DebugApi<TxReq: RpcObject> {
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -336,22 +415,25 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
+    /// about a block which has been rejected as invalid (for some reason).
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<StandardTraceOptions>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Replays the block with the given hash and writes the EIP-3155 trace of each transaction
+    /// to its own file.
+    ///
+    /// Returns the paths of the written files.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<StandardTraceOptions>,
+    ) -> RpcResult<Vec<String>>;
 
     /// Turns on CPU profiling indefinitely, writing to the given file.
     #[method(name = "startCPUProfile")]
@@ -389,7 +471,7 @@ pub trait DebugApi<TxReq: RpcObject> {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -398,8 +480,8 @@ pub trait DebugApi<TxReq: RpcObject> {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
@@ -30,6 +30,10 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use debug::{
+    AccountRangeResult, BadBlockResult, DebugSubscriptionKind, StandardTraceOptions,
+};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -37,13 +41,14 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
-        mev::{MevFullApiServer, MevSimApiServer},
+        mev::{MevFullApiServer, MevSendBundleApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethFeeApiServer},
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -52,7 +57,7 @@ pub mod servers {
     };
     pub use reth_rpc_eth_api::{
         self as eth, EthApiServer, EthBundleApiServer, EthCallBundleApiServer, EthFilterApiServer,
-        EthPubSubApiServer, L2EthApiExtServer,
+        EthPubSubApiServer, EthSendBundleApiServer, L2EthApiExtServer,
     };
 }
 
@@ -69,11 +74,11 @@ pub mod clients {
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         hardhat::HardhatApiClient,
-        mev::{MevFullApiClient, MevSimApiClient},
+        mev::{MevFullApiClient, MevSendBundleApiClient, MevSimApiClient},
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethApiClient, RethFeeApiClient},
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
@@ -82,6 +87,6 @@ pub mod clients {
     };
     pub use reth_rpc_eth_api::{
         EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthFilterApiClient,
-        L2EthApiExtServer,
+        EthSendBundleApiClient, L2EthApiExtServer,
     };
 }
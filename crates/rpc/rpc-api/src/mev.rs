@@ -15,6 +15,19 @@ pub trait MevSimApi {
     ) -> jsonrpsee::core::RpcResult<SimBundleResponse>;
 }
 
+/// A subset of the [`MevFullApi`] interface that only supports `mev_sendBundle`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "mev"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "mev"))]
+pub trait MevSendBundleApi {
+    /// Submitting bundles to the relay. It takes in a bundle and provides a bundle hash as a
+    /// return value.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(
+        &self,
+        request: MevSendBundle,
+    ) -> jsonrpsee::core::RpcResult<EthBundleHash>;
+}
+
 /// Mev rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "mev"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "mev"))]
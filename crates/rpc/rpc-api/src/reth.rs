@@ -1,6 +1,7 @@
 use alloy_eips::BlockId;
 use alloy_primitives::{Address, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::FeeEstimate;
 use std::collections::HashMap;
 
 // Required for the subscription attribute below
@@ -25,3 +26,19 @@ pub trait RethApi {
     )]
     async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Reth API namespace for fee estimates based on the pending transactions in the pool.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethFeeApi {
+    /// Returns the tips required for inclusion within the given numbers of blocks, estimated by
+    /// simulating the inclusion of the pending transactions in upcoming blocks.
+    ///
+    /// Defaults to the next block, within 3 blocks and within 10 blocks.
+    #[method(name = "feeEstimate")]
+    async fn reth_fee_estimate(
+        &self,
+        blocks: Option<Vec<u64>>,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> RpcResult<FeeEstimate>;
+}
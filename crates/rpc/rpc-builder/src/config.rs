@@ -96,6 +96,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
+            .max_indexed_blocks_per_filter(self.rpc_max_indexed_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .eth_proof_window(self.rpc_eth_proof_window)
             .rpc_gas_cap(self.rpc_gas_cap)
@@ -382,6 +383,8 @@ mod tests {
             "reth",
             "--rpc-max-blocks-per-filter",
             "100",
+            "--rpc.max-indexed-blocks-per-filter",
+            "300",
             "--rpc-max-logs-per-response",
             "200",
         ])
@@ -389,6 +392,7 @@ mod tests {
 
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(100));
+        assert_eq!(config.max_indexed_blocks_per_filter, Some(300));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
 }
@@ -36,8 +36,9 @@ use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
+    AdminApi, DebugApi, DebugApiConfig, DebugApiHandles, EngineEthApi, EthApi, EthApiBuilder,
+    EthBundle, MinerApi, NetApi, OtterscanApi, RPCApi, RethApi, RethFeeApi, TraceApi, TxPoolApi,
+    ValidationApiConfig, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -416,6 +417,8 @@ pub struct RpcModuleConfig {
     eth: EthConfig,
     /// `flashbots` namespace settings
     flashbots: ValidationApiConfig,
+    /// `debug` namespace settings
+    debug: DebugApiConfig,
 }
 
 // === impl RpcModuleConfig ===
@@ -428,7 +431,11 @@ impl RpcModuleConfig {
 
     /// Returns a new RPC module config given the eth namespace config
     pub const fn new(eth: EthConfig, flashbots: ValidationApiConfig) -> Self {
-        Self { eth, flashbots }
+        Self {
+            eth,
+            flashbots,
+            debug: DebugApiConfig { bad_blocks_dir: None, standard_traces_dir: None },
+        }
     }
 
     /// Get a reference to the eth namespace config
@@ -440,6 +447,16 @@ impl RpcModuleConfig {
     pub const fn eth_mut(&mut self) -> &mut EthConfig {
         &mut self.eth
     }
+
+    /// Get a reference to the debug namespace config
+    pub const fn debug(&self) -> &DebugApiConfig {
+        &self.debug
+    }
+
+    /// Get a mutable reference to the debug namespace config
+    pub const fn debug_mut(&mut self) -> &mut DebugApiConfig {
+        &mut self.debug
+    }
 }
 
 /// Configures [`RpcModuleConfig`]
@@ -447,6 +464,7 @@ impl RpcModuleConfig {
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
     flashbots: Option<ValidationApiConfig>,
+    debug: Option<DebugApiConfig>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -464,10 +482,20 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures a custom debug namespace config
+    pub fn debug(mut self, debug: DebugApiConfig) -> Self {
+        self.debug = Some(debug);
+        self
+    }
+
     /// Consumes the type and creates the [`RpcModuleConfig`]
     pub fn build(self) -> RpcModuleConfig {
-        let Self { eth, flashbots } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default(), flashbots: flashbots.unwrap_or_default() }
+        let Self { eth, flashbots, debug } = self;
+        RpcModuleConfig {
+            eth: eth.unwrap_or_default(),
+            flashbots: flashbots.unwrap_or_default(),
+            debug: debug.unwrap_or_default(),
+        }
     }
 
     /// Get a reference to the eth namespace config, if any
@@ -511,6 +539,10 @@ pub struct RpcRegistryInner<
     modules: HashMap<RethRpcModule, Methods>,
     /// eth config settings
     eth_config: EthConfig,
+    /// debug config settings
+    debug_config: DebugApiConfig,
+    /// handles to the node for the `debug_` namespace
+    debug_handles: DebugApiHandles,
 }
 
 // === impl RpcRegistryInner ===
@@ -559,9 +591,19 @@ where
             modules: Default::default(),
             blocking_pool_guard,
             eth_config: config.eth,
+            debug_config: config.debug,
+            debug_handles: Default::default(),
             evm_config,
         }
     }
+
+    /// Configures the handles to the node that the `debug_` namespace changes its state with.
+    ///
+    /// This must be called before the `debug_` module is created.
+    pub fn with_debug_handles(mut self, debug_handles: DebugApiHandles) -> Self {
+        self.debug_handles = debug_handles;
+        self
+    }
 }
 
 impl<Provider, Pool, Network, EthApi, BlockExecutor, Consensus>
@@ -814,7 +856,12 @@ where
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn debug_api(&self) -> DebugApi<EthApi> {
-        DebugApi::new(self.eth_api().clone(), self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.eth_api().clone(),
+            self.blocking_pool_guard.clone(),
+            self.debug_config.clone(),
+            self.debug_handles.clone(),
+        )
     }
 
     /// Instantiates `NetApi`
@@ -932,11 +979,14 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Debug => {
-                            DebugApi::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Debug => DebugApi::new(
+                            eth_api.clone(),
+                            self.blocking_pool_guard.clone(),
+                            self.debug_config.clone(),
+                            self.debug_handles.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Eth => {
                             // merge all eth handlers
                             let mut module = eth_api.clone().into_rpc();
@@ -981,16 +1031,23 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), self.executor.clone())
-                                .into_rpc()
-                                .into()
+                            let mut module =
+                                RethApi::new(self.provider.clone(), self.executor.clone())
+                                    .into_rpc();
+                            module
+                                .merge(RethFeeApi::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+
+                            module.into()
                         }
                         // only relevant for Ethereum and configured in `EthereumAddOns`
-                        // implementation
+                        // implementation, `anvil` only in dev mode
                         // TODO: can we get rid of this here?
                         // Custom modules are not handled here - they should be registered via
                         // extend_rpc_modules
-                        RethRpcModule::Flashbots | RethRpcModule::Other(_) => Default::default(),
+                        RethRpcModule::Flashbots |
+                        RethRpcModule::Anvil |
+                        RethRpcModule::Other(_) => Default::default(),
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
                         RethRpcModule::Mev => {
                             EthSimBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
@@ -46,6 +46,8 @@ pub trait EthFilterApi<T: RpcObject> {
 pub struct QueryLimits {
     /// Maximum number of blocks that could be scanned per filter
     pub max_blocks_per_filter: Option<u64>,
+    /// Maximum number of blocks that could be looked up in the log index per filter
+    pub max_indexed_blocks_per_filter: Option<u64>,
     /// Maximum number of logs that can be returned in a response
     pub max_logs_per_response: Option<usize>,
 }
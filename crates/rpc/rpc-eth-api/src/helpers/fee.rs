@@ -11,7 +11,11 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_primitives_traits::BlockBody;
 use reth_rpc_eth_types::{
     fee_history::calculate_reward_percentiles_for_block, utils::checked_blob_gas_used_ratio,
-    EthApiError, FeeHistoryCache, FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
+    EthApiError, FeeEstimate, FeeHistoryCache, FeeHistoryEntry, GasPriceOracle,
+    RpcInvalidTransactionError,
+};
+use reth_rpc_server_types::constants::gas_oracle::{
+    DEFAULT_FEE_ESTIMATE_BLOCKS, MAX_FEE_ESTIMATE_BLOCKS,
 };
 use reth_storage_api::{BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderHeader};
 use tracing::debug;
@@ -377,6 +381,52 @@ where
     where
         Self: 'static,
     {
-        async move { self.gas_oracle().suggest_tip_cap().await.map_err(Self::Error::from_eth_err) }
+        async move {
+            if self.gas_oracle().config().mempool {
+                return self
+                    .gas_oracle()
+                    .suggest_tip_cap_with_pool(self.pool())
+                    .await
+                    .map_err(Self::Error::from_eth_err)
+            }
+            self.gas_oracle().suggest_tip_cap().await.map_err(Self::Error::from_eth_err)
+        }
+    }
+
+    /// Estimates the tips required for inclusion within the given numbers of blocks, from the
+    /// pending transactions in the pool.
+    ///
+    /// If `reward_percentiles` are provided, the estimates include the tips at these percentiles
+    /// of the pending transactions in the last of the blocks.
+    fn pending_fee_estimate(
+        &self,
+        blocks: Option<Vec<u64>>,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> impl Future<Output = Result<FeeEstimate, Self::Error>> + Send {
+        async move {
+            let blocks = blocks.unwrap_or_else(|| DEFAULT_FEE_ESTIMATE_BLOCKS.to_vec());
+            if blocks.is_empty() ||
+                blocks.iter().any(|count| *count == 0 || *count > MAX_FEE_ESTIMATE_BLOCKS)
+            {
+                return Err(EthApiError::InvalidParams(format!(
+                    "block counts must be between 1 and {MAX_FEE_ESTIMATE_BLOCKS}"
+                ))
+                .into())
+            }
+
+            // the reward percentiles are validated like the ones of `eth_feeHistory`
+            let reward_percentiles = reward_percentiles.unwrap_or_default();
+            if reward_percentiles.len() as u64 >
+                self.gas_oracle().config().max_reward_percentile_count ||
+                reward_percentiles.windows(2).any(|w| w[0] > w[1]) ||
+                reward_percentiles.iter().any(|p| !(0.0..=100.0).contains(p))
+            {
+                return Err(EthApiError::InvalidRewardPercentiles.into())
+            }
+
+            self.gas_oracle()
+                .estimate_pending_fees(self.pool(), &blocks, &reward_percentiles)
+                .map_err(Self::Error::from_eth_err)
+        }
     }
 }
@@ -34,7 +34,7 @@ pub use call::{Call, EthCall};
 pub use fee::{EthFees, LoadFee};
 pub use pending_block::LoadPendingBlock;
 pub use receipt::LoadReceipt;
-pub use signer::{AddDevSigners, EthSigner};
+pub use signer::{AddDevSigners, EthSigner, ImpersonatedAccounts};
 pub use spec::EthApiSpec;
 pub use state::{EthState, LoadState};
 pub use trace::Trace;
@@ -1,11 +1,12 @@
 //! An abstraction over ethereum signers.
 
 use alloy_dyn_abi::TypedData;
-use alloy_primitives::{Address, Signature};
+use alloy_primitives::{map::AddressHashSet, Address, Signature};
 use alloy_rpc_types_eth::TransactionRequest;
 use dyn_clone::DynClone;
+use parking_lot::RwLock;
 use reth_rpc_eth_types::SignError;
-use std::result;
+use std::{result, sync::Arc};
 
 /// Result returned by [`EthSigner`] methods.
 pub type Result<T> = result::Result<T, SignError>;
@@ -39,4 +40,50 @@ pub trait AddDevSigners {
     /// Generates 20 random developer accounts.
     /// Used in DEV mode.
     fn with_dev_accounts(&self);
+
+    /// Adds a signer for the given impersonated accounts, which sends their transactions without
+    /// a valid signature.
+    /// Used in DEV mode.
+    fn with_impersonated_accounts(&self, accounts: ImpersonatedAccounts);
+}
+
+/// Accounts of a dev chain whose transactions are sent without their private key, shared between
+/// the `anvil` API and the signer of the `eth` API.
+#[derive(Debug, Clone, Default)]
+pub struct ImpersonatedAccounts(Arc<RwLock<ImpersonatedAccountsInner>>);
+
+#[derive(Debug, Default)]
+struct ImpersonatedAccountsInner {
+    /// The impersonated accounts.
+    accounts: AddressHashSet,
+    /// Whether all accounts are impersonated.
+    auto: bool,
+}
+
+impl ImpersonatedAccounts {
+    /// Starts impersonating the given account.
+    pub fn impersonate(&self, address: Address) {
+        self.0.write().accounts.insert(address);
+    }
+
+    /// Stops impersonating the given account.
+    pub fn stop_impersonating(&self, address: &Address) {
+        self.0.write().accounts.remove(address);
+    }
+
+    /// Enables or disables impersonating all accounts.
+    pub fn set_auto_impersonate(&self, enabled: bool) {
+        self.0.write().auto = enabled;
+    }
+
+    /// Returns `true` if the given account is impersonated.
+    pub fn is_impersonated(&self, address: &Address) -> bool {
+        let inner = self.0.read();
+        inner.auto || inner.accounts.contains(address)
+    }
+
+    /// Returns the explicitly impersonated accounts.
+    pub fn accounts(&self) -> Vec<Address> {
+        self.0.read().accounts.iter().copied().collect()
+    }
 }
@@ -8,7 +8,8 @@ use reth_node_api::{FullNodeComponents, NodePrimitives, PrimitivesTy};
 use reth_primitives_traits::{BlockTy, HeaderTy, ReceiptTy, TxTy};
 use reth_rpc_eth_types::EthStateCache;
 use reth_storage_api::{
-    BlockReader, BlockReaderIdExt, StageCheckpointReader, StateProviderFactory,
+    AddressAppearancesProvider, BlockReader, BlockReaderIdExt, ChangeSetReader, LogIndexProvider,
+    StageCheckpointReader, StateProviderFactory, StorageChangeSetReader, TraceIndexProvider,
 };
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
@@ -37,6 +38,11 @@ pub trait RpcNodeCore: Clone + Send + Sync + Unpin + 'static {
         > + StateProviderFactory
         + CanonStateSubscriptions<Primitives = Self::Primitives>
         + StageCheckpointReader
+        + AddressAppearancesProvider
+        + LogIndexProvider
+        + TraceIndexProvider
+        + ChangeSetReader
+        + StorageChangeSetReader
         + Send
         + Sync
         + Clone
@@ -130,6 +136,11 @@ where
         > + StateProviderFactory
         + CanonStateSubscriptions<Primitives = Evm::Primitives>
         + StageCheckpointReader
+        + AddressAppearancesProvider
+        + LogIndexProvider
+        + TraceIndexProvider
+        + ChangeSetReader
+        + StorageChangeSetReader
         + Send
         + Sync
         + Unpin
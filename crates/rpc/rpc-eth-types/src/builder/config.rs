@@ -9,8 +9,9 @@ use crate::{
 use reqwest::Url;
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
-    DEFAULT_PROOF_PERMITS, RPC_DEFAULT_SEND_RAW_TX_SYNC_TIMEOUT_SECS,
+    DEFAULT_MAX_INDEXED_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_PROOF_PERMITS,
+    RPC_DEFAULT_SEND_RAW_TX_SYNC_TIMEOUT_SECS,
 };
 use serde::{Deserialize, Serialize};
 
@@ -72,6 +73,9 @@ pub struct EthConfig {
     pub max_trace_filter_blocks: u64,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
+    /// Maximum number of blocks that could be looked up in the log index per filter request in
+    /// `eth_getLogs` calls.
+    pub max_indexed_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
     /// Gas limit for `eth_call` and call tracing RPC methods.
@@ -102,6 +106,7 @@ impl EthConfig {
     pub fn filter_config(&self) -> EthFilterConfig {
         EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
+            .max_indexed_blocks_per_filter(self.max_indexed_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
             .stale_filter_ttl(self.stale_filter_ttl)
     }
@@ -116,6 +121,7 @@ impl Default for EthConfig {
             max_tracing_requests: default_max_tracing_requests(),
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
+            max_indexed_blocks_per_filter: DEFAULT_MAX_INDEXED_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             rpc_max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
@@ -155,6 +161,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum block length to look up in the log index per `eth_getLogs` request
+    pub const fn max_indexed_blocks_per_filter(mut self, max_blocks: u64) -> Self {
+        self.max_indexed_blocks_per_filter = max_blocks;
+        self
+    }
+
     /// Configures the maximum number of blocks for `trace_filter` requests
     pub const fn max_trace_filter_blocks(mut self, max_blocks: u64) -> Self {
         self.max_trace_filter_blocks = max_blocks;
@@ -225,6 +237,10 @@ pub struct EthFilterConfig {
     ///
     /// If `None` then no limit is enforced.
     pub max_blocks_per_filter: Option<u64>,
+    /// Maximum number of blocks that a filter can look up in the log index.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_indexed_blocks_per_filter: Option<u64>,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     ///
     /// If `None` then no limit is enforced.
@@ -243,6 +259,12 @@ impl EthFilterConfig {
         self
     }
 
+    /// Sets the maximum number of blocks that a filter can look up in the log index.
+    pub const fn max_indexed_blocks_per_filter(mut self, num: u64) -> Self {
+        self.max_indexed_blocks_per_filter = Some(num);
+        self
+    }
+
     /// Sets the maximum number of logs that can be returned in a single response in `eth_getLogs`
     /// calls.
     pub const fn max_logs_per_response(mut self, num: usize) -> Self {
@@ -261,6 +283,7 @@ impl Default for EthFilterConfig {
     fn default() -> Self {
         Self {
             max_blocks_per_filter: None,
+            max_indexed_blocks_per_filter: None,
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
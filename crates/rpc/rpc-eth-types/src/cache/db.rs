@@ -169,6 +169,27 @@ impl StateProvider for StateProviderTraitObjWrapper<'_> {
     fn account_nonce(&self, addr: &Address) -> reth_errors::ProviderResult<Option<u64>> {
         self.0.account_nonce(addr)
     }
+
+    fn account_range(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+        with_addresses: bool,
+    ) -> reth_errors::ProviderResult<reth_storage_api::AccountRange> {
+        self.0.account_range(hashed_state, start, limit, storage_limit, with_addresses)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> reth_errors::ProviderResult<reth_storage_api::StorageRange> {
+        self.0.storage_range(address, hashed_storage, start, limit)
+    }
 }
 
 impl BytecodeReader for StateProviderTraitObjWrapper<'_> {
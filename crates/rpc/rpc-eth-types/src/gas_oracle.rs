@@ -1,5 +1,5 @@
 //! An implementation of the eth gas price oracle, used for providing gas price estimates based on
-//! previous blocks.
+//! previous blocks and, optionally, the pending transactions in the pool.
 
 use super::{EthApiError, EthResult, EthStateCache, RpcInvalidTransactionError};
 use alloy_consensus::{constants::GWEI_TO_WEI, BlockHeader, Transaction, TxReceipt};
@@ -16,6 +16,7 @@ use reth_rpc_server_types::{
     },
 };
 use reth_storage_api::{BlockReaderIdExt, NodePrimitivesProvider};
+use reth_transaction_pool::TransactionPool;
 use schnellru::{ByLength, LruMap};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Formatter};
@@ -56,6 +57,11 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// Whether the suggested tip is raised to the tip required for inclusion in the next block,
+    /// estimated from the pending transactions in the pool.
+    #[serde(default)]
+    pub mempool: bool,
 }
 
 impl Default for GasPriceOracleConfig {
@@ -69,6 +75,7 @@ impl Default for GasPriceOracleConfig {
             default_suggested_fee: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            mempool: false,
         }
     }
 }
@@ -215,6 +222,64 @@ where
         Ok(price)
     }
 
+    /// Suggests a tip like [`Self::suggest_tip_cap`], raised to the tip required for inclusion in
+    /// the next block if the pending transactions in the pool fill it up.
+    ///
+    /// During congestion spikes the tips of recent blocks lag behind the tips of the pending
+    /// transactions.
+    pub async fn suggest_tip_cap_with_pool<Pool>(&self, pool: &Pool) -> EthResult<U256>
+    where
+        Pool: TransactionPool,
+    {
+        let suggestion = self.suggest_tip_cap().await?;
+        let estimate = self.estimate_pending_fees(pool, &[1], &[])?;
+        let next_block_tip =
+            estimate.estimates.first().map(|estimate| estimate.min_tip).unwrap_or_default();
+
+        let mut price = suggestion.max(U256::from(next_block_tip));
+
+        // constrain to the max price
+        if let Some(max_price) = self.oracle_config.max_price &&
+            price > max_price
+        {
+            price = max_price;
+        }
+
+        Ok(price)
+    }
+
+    /// Estimates the tips required for inclusion within the given numbers of `blocks`, by
+    /// simulating the inclusion of the pending transactions in the pool in upcoming blocks with
+    /// the gas limit of the latest block.
+    ///
+    /// See also [`estimate_inclusion_fees`].
+    pub fn estimate_pending_fees<Pool>(
+        &self,
+        pool: &Pool,
+        blocks: &[u64],
+        percentiles: &[f64],
+    ) -> EthResult<FeeEstimate>
+    where
+        Pool: TransactionPool,
+    {
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::latest()))?;
+        let target_gas = header.gas_limit();
+        let base_fee = pool.block_info().pending_basefee;
+
+        let pending = pool
+            .best_transactions()
+            .map(|tx| (tx.gas_limit(), tx.effective_tip_per_gas(base_fee).unwrap_or_default()));
+
+        Ok(FeeEstimate {
+            base_fee_per_gas: base_fee,
+            target_gas,
+            estimates: estimate_inclusion_fees(pending, target_gas, blocks, percentiles),
+        })
+    }
+
     /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
     /// configured `ignore_price` threshold, then tip values under that threshold will be ignored
     /// before returning a result.
@@ -421,6 +486,141 @@ impl Default for GasPriceOracleResult {
     }
 }
 
+/// Fee estimate based on the pending transactions in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimate {
+    /// The base fee per gas of the next block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub base_fee_per_gas: u64,
+    /// The gas available to pending transactions in each of the simulated blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub target_gas: u64,
+    /// The estimates for the requested numbers of blocks until inclusion.
+    pub estimates: Vec<InclusionFeeEstimate>,
+}
+
+/// Tips required for the inclusion of a transaction within a number of blocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionFeeEstimate {
+    /// The number of blocks within which the transaction is included, `1` is the next block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub blocks: u64,
+    /// The lowest tip of the pending transactions in the last of the blocks if they fill it up,
+    /// zero otherwise.
+    #[serde(with = "alloy_serde::quantity")]
+    pub min_tip: u128,
+    /// The gas used by pending transactions in the last of the blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The tips at the requested percentiles, weighted by gas, of the pending transactions in the
+    /// last of the blocks.
+    #[serde(with = "alloy_serde::quantity::vec")]
+    pub reward: Vec<u128>,
+}
+
+/// Simulates the inclusion of pending transactions in upcoming blocks, and estimates the tips
+/// required for the inclusion of a transaction within each of the given numbers of `blocks`.
+///
+/// The pending transactions are given as pairs of gas limit and effective tip, in the order they're
+/// included by the block builder. Every simulated block is filled with up to `target_gas`, the
+/// first transaction that doesn't fit anymore starts the next block.
+pub fn estimate_inclusion_fees(
+    pending: impl IntoIterator<Item = (u64, u128)>,
+    target_gas: u64,
+    blocks: &[u64],
+    percentiles: &[f64],
+) -> Vec<InclusionFeeEstimate> {
+    let max_blocks = blocks.iter().copied().max().unwrap_or_default() as usize;
+
+    // the transactions of the simulated blocks, and whether the block is full
+    let mut simulated: Vec<(Vec<(u64, u128)>, bool)> = Vec::with_capacity(max_blocks);
+    let mut current = Vec::new();
+    let mut current_gas = 0;
+    for (gas_limit, tip) in pending {
+        if simulated.len() >= max_blocks {
+            break
+        }
+
+        // transactions exceeding the target gas never fit into a simulated block
+        if gas_limit > target_gas {
+            continue
+        }
+
+        if current_gas + gas_limit > target_gas {
+            simulated.push((std::mem::take(&mut current), true));
+            current_gas = 0;
+        }
+        current_gas += gas_limit;
+        current.push((gas_limit, tip));
+    }
+    if simulated.len() < max_blocks && !current.is_empty() {
+        simulated.push((current, current_gas == target_gas));
+    }
+
+    blocks
+        .iter()
+        .map(|&count| {
+            let Some((transactions, is_full)) =
+                (count as usize).checked_sub(1).and_then(|index| simulated.get(index))
+            else {
+                return InclusionFeeEstimate {
+                    blocks: count,
+                    min_tip: 0,
+                    gas_used: 0,
+                    reward: vec![0; percentiles.len()],
+                }
+            };
+
+            let gas_used = transactions.iter().map(|(gas_limit, _)| gas_limit).sum();
+            let min_tip = if *is_full {
+                transactions.iter().map(|(_, tip)| *tip).min().unwrap_or_default()
+            } else {
+                0
+            };
+
+            InclusionFeeEstimate {
+                blocks: count,
+                min_tip,
+                gas_used,
+                reward: gas_weighted_percentiles(transactions, gas_used, percentiles),
+            }
+        })
+        .collect()
+}
+
+/// Returns the tips at the given percentiles of the gas used by the transactions, given as pairs
+/// of gas limit and tip.
+fn gas_weighted_percentiles(
+    transactions: &[(u64, u128)],
+    gas_used: u64,
+    percentiles: &[f64],
+) -> Vec<u128> {
+    let mut transactions = transactions.to_vec();
+    transactions.sort_by_key(|(_, tip)| *tip);
+
+    // the percentiles are monotonically increasing, so the index is shared across all of them
+    let mut tx_index = 0;
+    let mut cumulative_gas =
+        transactions.first().map(|(gas_limit, _)| *gas_limit).unwrap_or_default();
+    percentiles
+        .iter()
+        .map(|percentile| {
+            if transactions.is_empty() {
+                return 0
+            }
+
+            let threshold = (gas_used as f64 * percentile / 100.) as u64;
+            while cumulative_gas < threshold && tx_index < transactions.len() - 1 {
+                tx_index += 1;
+                cumulative_gas += transactions[tx_index].0;
+            }
+            transactions[tx_index].1
+        })
+        .collect()
+}
+
 /// The wrapper type for gas limit
 #[derive(Debug, Clone, Copy, From, Into)]
 pub struct GasCap(pub u64);
@@ -445,4 +645,48 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn estimate_inclusion_fees_fills_blocks() {
+        // three blocks worth of pending transactions, ordered by descending tip
+        let pending = [(10, 50), (10, 40), (10, 30), (10, 20), (5, 10)];
+        let estimates = estimate_inclusion_fees(pending, 20, &[1, 2, 3, 4], &[0., 50., 100.]);
+
+        assert_eq!(
+            estimates,
+            vec![
+                InclusionFeeEstimate {
+                    blocks: 1,
+                    min_tip: 40,
+                    gas_used: 20,
+                    reward: vec![40, 40, 50],
+                },
+                InclusionFeeEstimate {
+                    blocks: 2,
+                    min_tip: 20,
+                    gas_used: 20,
+                    reward: vec![20, 20, 30],
+                },
+                // the last block isn't full, so any tip is included
+                InclusionFeeEstimate {
+                    blocks: 3,
+                    min_tip: 0,
+                    gas_used: 5,
+                    reward: vec![10, 10, 10],
+                },
+                InclusionFeeEstimate { blocks: 4, min_tip: 0, gas_used: 0, reward: vec![0, 0, 0] },
+            ]
+        );
+    }
+
+    #[test]
+    fn estimate_inclusion_fees_skips_oversized() {
+        // the first transaction never fits, the others fill up the block exactly
+        let pending = [(30, 50), (10, 40), (10, 30)];
+        let estimates = estimate_inclusion_fees(pending, 20, &[1], &[]);
+        assert_eq!(
+            estimates,
+            vec![InclusionFeeEstimate { blocks: 1, min_tip: 30, gas_used: 20, reward: vec![] }]
+        );
+    }
 }
@@ -17,6 +17,7 @@ pub mod gas_oracle;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
+pub mod pubsub;
 pub mod receipt;
 pub mod simulate;
 pub mod transaction;
@@ -31,9 +32,11 @@ pub use cache::{
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
-    GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
+    FeeEstimate, GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult,
+    InclusionFeeEstimate, RPC_DEFAULT_GAS_CAP,
 };
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use pubsub::{PendingTransactionsFilter, SubscriptionParams};
 pub use transaction::TransactionSource;
 pub use tx_forward::ForwardConfig;
@@ -0,0 +1,191 @@
+//! Types for `eth_subscribe` subscriptions.
+
+use alloy_primitives::{Address, Selector};
+use alloy_rpc_types_eth::pubsub::Params;
+use reth_transaction_pool::PoolTransaction;
+use serde::{Deserialize, Serialize};
+
+/// Parameters of an `eth_subscribe` call.
+///
+/// In addition to the standard [`Params`], `newPendingTransactions` subscriptions accept a
+/// [`PendingTransactionsFilter`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SubscriptionParams {
+    /// Filter for `newPendingTransactions` subscriptions.
+    PendingTransactions(PendingTransactionsFilter),
+    /// Standard subscription parameters.
+    Params(Params),
+}
+
+impl From<Params> for SubscriptionParams {
+    fn from(params: Params) -> Self {
+        Self::Params(params)
+    }
+}
+
+impl From<PendingTransactionsFilter> for SubscriptionParams {
+    fn from(filter: PendingTransactionsFilter) -> Self {
+        Self::PendingTransactions(filter)
+    }
+}
+
+/// Server-side filter of a `newPendingTransactions` subscription.
+///
+/// A transaction is sent to the subscriber if it matches all given criteria, an empty list matches
+/// all transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PendingTransactionsFilter {
+    /// Whether full transaction objects are sent instead of transaction hashes.
+    #[serde(default)]
+    pub full_transactions: bool,
+    /// Senders of the transactions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from: Vec<Address>,
+    /// Recipients of the transactions, contract creations never match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<Address>,
+    /// 4-byte function selectors the input of the transactions starts with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selectors: Vec<Selector>,
+    /// EIP-2718 types of the transactions.
+    #[serde(default, with = "alloy_serde::quantity::vec", skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<u8>,
+    /// Minimum priority fee per gas the transactions pay at the base fee of the pending block.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub min_tip: Option<u128>,
+}
+
+impl PendingTransactionsFilter {
+    /// Returns `true` if the filter matches all transactions.
+    pub const fn is_match_all(&self) -> bool {
+        self.from.is_empty() &&
+            self.to.is_empty() &&
+            self.selectors.is_empty() &&
+            self.types.is_empty() &&
+            self.min_tip.is_none()
+    }
+
+    /// Returns `true` if the transaction matches the filter, given the base fee of the pending
+    /// block.
+    pub fn matches<T: PoolTransaction>(&self, tx: &T, base_fee: u64) -> bool {
+        (self.from.is_empty() || self.from.contains(&tx.sender())) &&
+            (self.to.is_empty() || tx.to().is_some_and(|to| self.to.contains(&to))) &&
+            (self.selectors.is_empty() ||
+                tx.input().get(..4).is_some_and(|selector| {
+                    self.selectors.iter().any(|expected| expected.as_slice() == selector)
+                })) &&
+            (self.types.is_empty() || self.types.contains(&tx.ty())) &&
+            self.min_tip.is_none_or(|min_tip| {
+                tx.effective_tip_per_gas(base_fee).is_some_and(|tip| tip >= min_tip)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Transaction;
+    use alloy_primitives::{address, Bytes, TxKind};
+    use reth_transaction_pool::test_utils::MockTransaction;
+
+    #[test]
+    fn match_sender_and_recipient() {
+        let tx = MockTransaction::eip1559();
+        let sender = tx.sender();
+        let recipient = tx.to().unwrap();
+        let from = |from| PendingTransactionsFilter { from, ..Default::default() };
+        let to = |to| PendingTransactionsFilter { to, ..Default::default() };
+
+        assert!(PendingTransactionsFilter::default().matches(&tx, 0));
+        assert!(from(vec![sender]).matches(&tx, 0));
+        assert!(!from(vec![recipient]).matches(&tx, 0));
+        assert!(to(vec![sender, recipient]).matches(&tx, 0));
+        assert!(!to(vec![sender]).matches(&tx, 0));
+
+        // contract creations never match a recipient
+        let mut create = tx;
+        if let MockTransaction::Eip1559 { to: kind, .. } = &mut create {
+            *kind = TxKind::Create;
+        }
+        assert!(!to(vec![recipient]).matches(&create, 0));
+    }
+
+    #[test]
+    fn match_selector() {
+        let filter = PendingTransactionsFilter {
+            selectors: vec![Selector::from([0xa9, 0x05, 0x9c, 0xbb])],
+            ..Default::default()
+        };
+
+        let transfer = MockTransaction::eip1559()
+            .with_input(Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x00, 0x01]));
+        assert!(filter.matches(&transfer, 0));
+
+        let approve = MockTransaction::eip1559()
+            .with_input(Bytes::from_static(&[0x09, 0x5e, 0xa7, 0xb3, 0x00, 0x01]));
+        assert!(!filter.matches(&approve, 0));
+
+        // inputs shorter than a selector never match
+        let short = MockTransaction::eip1559().with_input(Bytes::from_static(&[0xa9, 0x05]));
+        assert!(!filter.matches(&short, 0));
+        assert!(!filter.matches(&MockTransaction::eip1559(), 0));
+    }
+
+    #[test]
+    fn match_type_and_min_tip() {
+        let filter = PendingTransactionsFilter { types: vec![2], ..Default::default() };
+        assert!(filter.matches(&MockTransaction::eip1559(), 0));
+        assert!(!filter.matches(&MockTransaction::legacy(), 0));
+
+        let filter = PendingTransactionsFilter { min_tip: Some(10), ..Default::default() };
+        let tx = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(20);
+        assert!(filter.matches(&tx, 0));
+        // the tip is capped by the max fee at the given base fee
+        assert!(filter.matches(&tx, 90));
+        assert!(!filter.matches(&tx, 91));
+        // transactions that can't pay the base fee never match
+        assert!(!filter.matches(&tx, 101));
+
+        let tx = MockTransaction::legacy().with_gas_price(15);
+        assert!(filter.matches(&tx, 5));
+        assert!(!filter.matches(&tx, 6));
+    }
+
+    #[test]
+    fn deserialize_subscription_params() {
+        let params: SubscriptionParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, SubscriptionParams::Params(Params::Bool(true)));
+
+        let params: SubscriptionParams =
+            serde_json::from_str(r#"{"address":"0x0000000000000000000000000000000000000001"}"#)
+                .unwrap();
+        assert!(matches!(params, SubscriptionParams::Params(Params::Logs(_))));
+
+        let params: SubscriptionParams = serde_json::from_str(
+            r#"{
+                "fullTransactions": true,
+                "from": ["0x0000000000000000000000000000000000000001"],
+                "selectors": ["0xa9059cbb"],
+                "types": ["0x2"],
+                "minTip": "0x3b9aca00"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            SubscriptionParams::PendingTransactions(PendingTransactionsFilter {
+                full_transactions: true,
+                from: vec![address!("0x0000000000000000000000000000000000000001")],
+                to: Vec::new(),
+                selectors: vec![Selector::from([0xa9, 0x05, 0x9c, 0xbb])],
+                types: vec![2],
+                min_tip: Some(1_000_000_000),
+            })
+        );
+
+        let serialized = serde_json::to_string(&params).unwrap();
+        assert_eq!(serde_json::from_str::<SubscriptionParams>(&serialized).unwrap(), params);
+    }
+}
@@ -12,6 +12,9 @@ pub const DEFAULT_AUTH_PORT: u16 = 8551;
 /// The default maximum block range allowed to filter
 pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 
+/// The default maximum block range allowed to filter with the log index
+pub const DEFAULT_MAX_INDEXED_BLOCKS_PER_FILTER: u64 = 10_000_000;
+
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
@@ -90,6 +93,14 @@ pub mod gas_oracle {
     /// The default minimum gas price, under which the sample will be ignored
     pub const DEFAULT_IGNORE_GAS_PRICE: U256 = U256::from_limbs([2u64, 0, 0, 0]);
 
+    /// The default numbers of blocks until inclusion the fee estimate based on the pending
+    /// transactions reports: the next block, within 3 blocks and within 10 blocks.
+    pub const DEFAULT_FEE_ESTIMATE_BLOCKS: [u64; 3] = [1, 3, 10];
+
+    /// The maximum number of blocks until inclusion the fee estimate based on the pending
+    /// transactions simulates.
+    pub const MAX_FEE_ESTIMATE_BLOCKS: u64 = 64;
+
     /// The default gas limit for `eth_call` and adjacent calls.
     ///
     /// This is different from the default to regular 30M block gas limit
@@ -323,6 +323,8 @@ pub enum RethRpcModule {
     Miner,
     /// `mev_` module
     Mev,
+    /// `anvil_` module, only available in dev mode
+    Anvil,
     /// Custom RPC module not part of the standard set
     #[strum(default)]
     #[serde(untagged)]
@@ -347,6 +349,7 @@ impl RethRpcModule {
         Self::Flashbots,
         Self::Miner,
         Self::Mev,
+        Self::Anvil,
     ];
 
     /// Returns the number of standard variants (excludes Other)
@@ -406,6 +409,7 @@ impl AsRef<str> for RethRpcModule {
             Self::Flashbots => "flashbots",
             Self::Miner => "miner",
             Self::Mev => "mev",
+            Self::Anvil => "anvil",
         }
     }
 }
@@ -428,6 +432,7 @@ impl FromStr for RethRpcModule {
             "flashbots" => Self::Flashbots,
             "miner" => Self::Miner,
             "mev" => Self::Mev,
+            "anvil" => Self::Anvil,
             // Any unknown module becomes Other
             other => Self::Other(other.to_string()),
         })
@@ -0,0 +1,598 @@
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader, TxEnvelope};
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
+use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
+use alloy_rpc_types_eth::{Block, BlockTransactionsKind, Header, Transaction};
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use parking_lot::Mutex;
+use reth_engine_local::LocalMinerHandle;
+use reth_revm::revm::bytecode::Bytecode;
+use reth_rpc_api::AnvilApiServer;
+use reth_rpc_eth_api::helpers::ImpersonatedAccounts;
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{
+    BlockReaderIdExt, BytecodeReader, ProviderTx, StateProvider, StateProviderFactory,
+    TransactionVariant,
+};
+use reth_transaction_pool::TransactionPool;
+use reth_trie_common::{HashedPostState, HashedStorage, EMPTY_ROOT_HASH};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display, sync::Arc, time::Duration};
+use tracing::trace;
+
+/// Maximum number of accounts and storage slots in a state dumped with `anvil_dumpState`.
+///
+/// The dumped state is collected in memory, larger states are rejected.
+const MAX_DUMP_STATE_ENTRIES: usize = 1_000_000;
+
+/// `anvil` API implementation for dev chains.
+///
+/// Blocks are mined and modified through the [`LocalMinerHandle`] of the node. Account changes and
+/// block environment overrides are applied to the next mined block.
+///
+/// Unlike anvil, which changes its in-memory state in place, account changes are applied by the
+/// payload builder on top of the parent state. So changes made with the `anvil_set*` methods and
+/// `anvil_loadState` are queued and become visible with the next block, mined on a new transaction
+/// if automine is enabled, or with `anvil_mine` and `evm_mine`.
+///
+/// Transactions of impersonated accounts are sent with `eth_sendTransaction` once the
+/// [`ImpersonatedAccounts`] are added to the signers of the `eth` API. Their senders can't be
+/// recovered from their placeholder signatures, so impersonating accounts requires that the node
+/// keeps the senders of persisted transactions, see [`AnvilApi::with_impersonation`].
+///
+/// Methods that don't fit a chain with a fixed chain spec are not supported: switching forks,
+/// changing the chain id or the minimum gas price, logging, traces and node metadata.
+#[derive(Debug, Clone)]
+pub struct AnvilApi<Provider, Pool> {
+    inner: Arc<AnvilApiInner<Provider, Pool>>,
+    /// Whether accounts can be impersonated.
+    impersonation_enabled: bool,
+}
+
+impl<Provider, Pool> AnvilApi<Provider, Pool> {
+    /// Creates a new instance of `AnvilApi`.
+    pub fn new(provider: Provider, pool: Pool, miner: LocalMinerHandle) -> Self {
+        let inner = AnvilApiInner {
+            provider,
+            pool,
+            miner,
+            impersonated: Default::default(),
+            snapshots: Default::default(),
+        };
+        Self { inner: Arc::new(inner), impersonation_enabled: true }
+    }
+
+    /// Enables or disables impersonating accounts.
+    ///
+    /// This should be disabled if the senders of persisted transactions are pruned, because the
+    /// senders of impersonated transactions would be recovered from their placeholder signatures.
+    pub const fn with_impersonation(mut self, enabled: bool) -> Self {
+        self.impersonation_enabled = enabled;
+        self
+    }
+
+    /// Returns the accounts impersonated with `anvil_impersonateAccount`.
+    pub fn impersonated_accounts(&self) -> &ImpersonatedAccounts {
+        &self.inner.impersonated
+    }
+}
+
+impl<Provider, Pool> AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt<Header = alloy_consensus::Header> + StateProviderFactory + 'static,
+    Pool: TransactionPool + 'static,
+    TxEnvelope: From<ProviderTx<Provider>>,
+{
+    /// Returns an error if accounts can't be impersonated.
+    fn ensure_impersonation_enabled(&self) -> RpcResult<()> {
+        if self.impersonation_enabled {
+            return Ok(())
+        }
+        Err(internal_rpc_err(
+            "impersonating accounts requires that the senders of transactions are not pruned",
+        ))
+    }
+
+    /// Returns the latest block, as it's recorded in snapshots.
+    fn latest_block(&self) -> RpcResult<SnapshotBlock> {
+        let header = self
+            .inner
+            .provider
+            .latest_header()
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::latest()))?;
+        Ok(SnapshotBlock { number: header.number(), timestamp: header.timestamp() })
+    }
+
+    /// Returns all accounts of the latest state, with their code and storage.
+    ///
+    /// Returns an error if the state has more than [`MAX_DUMP_STATE_ENTRIES`] accounts and storage
+    /// slots.
+    fn dump_state(&self) -> RpcResult<DumpedState> {
+        let state = self.inner.provider.latest().map_err(EthApiError::from)?;
+        let range = state
+            .account_range(
+                HashedPostState::default(),
+                B256::ZERO,
+                MAX_DUMP_STATE_ENTRIES + 1,
+                0,
+                true,
+            )
+            .map_err(EthApiError::from)?;
+
+        let mut entries = range.accounts.len();
+        ensure_dump_state_entries(entries)?;
+
+        let mut accounts = BTreeMap::new();
+        for account in range.accounts {
+            let address = account.address.ok_or_else(|| {
+                internal_rpc_err(format!("unknown address of account {}", account.hashed_address))
+            })?;
+
+            let code_hash = account.account.get_bytecode_hash();
+            let code = if code_hash == KECCAK_EMPTY {
+                Bytes::new()
+            } else {
+                state
+                    .bytecode_by_hash(&code_hash)
+                    .map_err(EthApiError::from)?
+                    .map(|code| code.original_bytes())
+                    .unwrap_or_default()
+            };
+
+            let mut storage = BTreeMap::new();
+            if account.storage_root != EMPTY_ROOT_HASH {
+                let range = state
+                    .storage_range(
+                        address,
+                        HashedStorage::default(),
+                        B256::ZERO,
+                        MAX_DUMP_STATE_ENTRIES - entries + 1,
+                    )
+                    .map_err(EthApiError::from)?;
+                entries += range.slots.len();
+                ensure_dump_state_entries(entries)?;
+                for slot in range.slots {
+                    let key = slot.key.ok_or_else(|| {
+                        internal_rpc_err(format!(
+                            "unknown slot {} of account {address}",
+                            slot.hashed_key
+                        ))
+                    })?;
+                    storage.insert(key, B256::from(slot.value));
+                }
+            }
+
+            accounts.insert(
+                address,
+                DumpedAccount {
+                    nonce: account.account.nonce,
+                    balance: account.account.balance,
+                    code,
+                    storage,
+                },
+            );
+        }
+
+        Ok(DumpedState { accounts })
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> AnvilApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt<Header = alloy_consensus::Header> + StateProviderFactory + 'static,
+    Pool: TransactionPool + 'static,
+    TxEnvelope: From<ProviderTx<Provider>>,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_impersonateAccount");
+        self.ensure_impersonation_enabled()?;
+        self.inner.impersonated.impersonate(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_stopImpersonatingAccount");
+        self.inner.impersonated.stop_impersonating(&address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", enabled, "Serving anvil_autoImpersonateAccount");
+        if enabled {
+            self.ensure_impersonation_enabled()?;
+        }
+        self.inner.impersonated.set_auto_impersonate(enabled);
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_getAutomine");
+        self.inner.miner.automine().await.map_err(internal_err)
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?blocks, ?interval, "Serving anvil_mine");
+        let blocks = blocks.map(u64_param).transpose()?.unwrap_or(1);
+        let interval = interval.map(u64_param).transpose()?;
+        self.inner.miner.mine(blocks, interval).await.map_err(internal_err)?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", enabled, "Serving anvil_setAutomine");
+        self.inner.miner.set_automine(enabled).map_err(internal_err)
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", interval, "Serving anvil_setIntervalMining");
+        // an interval of 0 disables interval mining
+        let interval = (interval > 0).then(|| Duration::from_secs(interval));
+        self.inner.miner.set_interval_mining(interval).map_err(internal_err)
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        trace!(target: "rpc::anvil", ?tx_hash, "Serving anvil_dropTransaction");
+        let removed = self.inner.pool.remove_transactions(vec![tx_hash]);
+        Ok((!removed.is_empty()).then_some(tx_hash))
+    }
+
+    /// Handler for `anvil_reset`
+    ///
+    /// Resets the chain to the given block, or to genesis, and drops all snapshots, queued
+    /// changes and time settings. Resetting to another fork is not supported, forking is only
+    /// configured at launch.
+    async fn anvil_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?fork, "Serving anvil_reset");
+        if fork.as_ref().is_some_and(|fork| fork.json_rpc_url.is_some()) {
+            return Err(
+                EthApiError::Unsupported("anvil_reset to another fork is not supported").into()
+            )
+        }
+
+        let number = fork.and_then(|fork| fork.block_number).unwrap_or_default();
+        let header = self
+            .inner
+            .provider
+            .sealed_header(number)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+
+        *self.inner.snapshots.lock() = Default::default();
+        let miner = &self.inner.miner;
+        miner.state_changes().clear();
+        miner.reset_time();
+        miner.revert(header.number(), header.timestamp()).await.map_err(internal_err)
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setRpcUrl is not supported").into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, ?balance, "Serving anvil_setBalance");
+        self.inner.miner.state_changes().update(address, |account| account.balance = Some(balance));
+        Ok(())
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_setCode");
+        let code = Bytecode::new_raw_checked(code)
+            .map_err(|err| invalid_params_rpc_err(format!("invalid code: {err}")))?;
+        self.inner.miner.state_changes().update(address, |account| account.code = Some(code));
+        Ok(())
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, ?nonce, "Serving anvil_setNonce");
+        let nonce = u64_param(nonce)?;
+        self.inner.miner.state_changes().update(address, |account| account.nonce = Some(nonce));
+        Ok(())
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?address, ?slot, ?value, "Serving anvil_setStorageAt");
+        self.inner.miner.state_changes().update(address, |account| {
+            account.storage.insert(slot, value.into());
+        });
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_setCoinbase");
+        self.inner.miner.state_changes().update_block_env(|env| env.coinbase = Some(address));
+        Ok(())
+    }
+
+    /// Handler for `anvil_setChainId`
+    ///
+    /// Not supported, the chain id is part of the chain spec of the node.
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setChainId is not supported").into())
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setLoggingEnabled is not supported").into())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    ///
+    /// Not supported, dev chains always have EIP-1559 active, as anvil does for this method.
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported(
+            "anvil_setMinGasPrice is not supported when EIP-1559 is active",
+        )
+        .into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?base_fee, "Serving anvil_setNextBlockBaseFeePerGas");
+        let base_fee = u64_param(base_fee)?;
+        self.inner.miner.state_changes().update_next_block_env(|env| env.base_fee = Some(base_fee));
+        Ok(())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        trace!(target: "rpc::anvil", timestamp, "Serving anvil_setTime");
+        self.inner.miner.set_time(timestamp);
+        Ok(timestamp)
+    }
+
+    /// Handler for `anvil_dumpState`
+    ///
+    /// Returns the accounts of the latest state as JSON, in the format of anvil without blocks and
+    /// without compression.
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        trace!(target: "rpc::anvil", "Serving anvil_dumpState");
+        let state = self.dump_state()?;
+        Ok(serde_json::to_vec(&state).map_err(internal_err)?.into())
+    }
+
+    /// Handler for `anvil_loadState`
+    ///
+    /// Applies the accounts of a dumped state on top of the latest state, in the next mined block.
+    /// Blocks of states dumped by anvil are ignored.
+    async fn anvil_load_state(&self, state: Bytes) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_loadState");
+        let state: DumpedState = serde_json::from_slice(&state)
+            .map_err(|err| invalid_params_rpc_err(format!("invalid state: {err}")))?;
+
+        let state_changes = self.inner.miner.state_changes();
+        for (address, account) in state.accounts {
+            let code = Bytecode::new_raw_checked(account.code)
+                .map_err(|err| invalid_params_rpc_err(format!("invalid code: {err}")))?;
+            state_changes.update(address, |changes| {
+                changes.nonce = Some(account.nonce);
+                changes.balance = Some(account.balance);
+                changes.code = Some(code);
+                changes.storage.extend(account.storage.into_iter().map(|(slot, value)| {
+                    (U256::from_be_bytes(slot.0), U256::from_be_bytes(value.0))
+                }));
+            });
+        }
+        Ok(true)
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        Err(EthApiError::Unsupported("anvil_nodeInfo is not supported").into())
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        Err(EthApiError::Unsupported("anvil_metadata is not supported").into())
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        trace!(target: "rpc::anvil", "Serving anvil_snapshot");
+        let block = self.latest_block()?;
+        let mut snapshots = self.inner.snapshots.lock();
+        let id = snapshots.next_id;
+        snapshots.next_id += U256::from(1);
+        snapshots.blocks.insert(id, block);
+        Ok(id)
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?id, "Serving anvil_revert");
+        // a snapshot can only be reverted to once, along with all snapshots taken after it
+        let Some(block) = ({
+            let mut snapshots = self.inner.snapshots.lock();
+            let block = snapshots.blocks.get(&id).copied();
+            if block.is_some() {
+                snapshots.blocks.split_off(&id);
+            }
+            block
+        }) else {
+            return Ok(false)
+        };
+
+        self.inner.miner.revert(block.number, block.timestamp).await.map_err(internal_err)?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        trace!(target: "rpc::anvil", ?seconds, "Serving anvil_increaseTime");
+        Ok(self.inner.miner.increase_time(u64_param(seconds)?))
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", seconds, "Serving anvil_setNextBlockTimestamp");
+        let latest = self.latest_block()?;
+        if seconds <= latest.timestamp {
+            return Err(invalid_params_rpc_err(format!(
+                "timestamp {seconds} must be greater than the latest block timestamp {}",
+                latest.timestamp
+            )))
+        }
+        self.inner.miner.set_next_block_timestamp(seconds);
+        Ok(())
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, gas_limit: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?gas_limit, "Serving anvil_setBlockGasLimit");
+        let gas_limit = u64_param(gas_limit)?;
+        self.inner.miner.state_changes().update_block_env(|env| env.gas_limit = Some(gas_limit));
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", seconds, "Serving anvil_setBlockTimestampInterval");
+        self.inner.miner.set_block_timestamp_interval(Some(seconds));
+        Ok(())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_removeBlockTimestampInterval");
+        Ok(self.inner.miner.set_block_timestamp_interval(None))
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        trace!(target: "rpc::anvil", ?opts, "Serving anvil_mine_detailed");
+        let (timestamp, blocks) = match opts {
+            Some(MineOptions::Options { timestamp, blocks }) => (timestamp, blocks),
+            Some(MineOptions::Timestamp(timestamp)) => (timestamp, None),
+            None => (None, None),
+        };
+        if let Some(timestamp) = timestamp {
+            self.anvil_set_next_block_timestamp(timestamp).await?;
+        }
+
+        let hashes =
+            self.inner.miner.mine(blocks.unwrap_or(1), None).await.map_err(internal_err)?;
+        let mut blocks = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let block = self
+                .inner
+                .provider
+                .recovered_block(hash.into(), TransactionVariant::WithHash)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+            let block: Block = block.into_rpc_block(
+                BlockTransactionsKind::Full,
+                |tx, tx_info| Ok(Transaction::from_transaction(tx.convert(), tx_info)),
+                |header, size| {
+                    Ok::<_, EthApiError>(Header::from_consensus(
+                        header.into(),
+                        None,
+                        Some(U256::from(size)),
+                    ))
+                },
+            )?;
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_enableTraces is not supported").into())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_removePoolTransactions");
+        self.inner.pool.remove_transactions_by_sender(address);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct AnvilApiInner<Provider, Pool> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The transaction pool.
+    pool: Pool,
+    /// Handle to the local miner of the dev chain.
+    miner: LocalMinerHandle,
+    /// Accounts whose transactions are sent without their private key.
+    impersonated: ImpersonatedAccounts,
+    /// Snapshots that can be reverted to.
+    snapshots: Mutex<Snapshots>,
+}
+
+/// Snapshots taken with `anvil_snapshot`.
+#[derive(Debug, Default)]
+struct Snapshots {
+    /// Id of the next snapshot.
+    next_id: U256,
+    /// The head block of every snapshot, by id.
+    blocks: BTreeMap<U256, SnapshotBlock>,
+}
+
+/// Head block recorded by a snapshot.
+#[derive(Debug, Clone, Copy)]
+struct SnapshotBlock {
+    number: BlockNumber,
+    timestamp: u64,
+}
+
+/// State dumped with `anvil_dumpState`, in the format of anvil.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DumpedState {
+    /// The accounts, by address.
+    accounts: BTreeMap<Address, DumpedAccount>,
+}
+
+/// Account of a [`DumpedState`].
+#[derive(Debug, Serialize, Deserialize)]
+struct DumpedAccount {
+    nonce: u64,
+    balance: U256,
+    code: Bytes,
+    storage: BTreeMap<B256, B256>,
+}
+
+/// Converts a quantity parameter that must fit into a `u64`.
+fn u64_param(value: U256) -> RpcResult<u64> {
+    value.try_into().map_err(|_| invalid_params_rpc_err(format!("{value} exceeds u64")))
+}
+
+/// Returns an error if a state dump with the given number of accounts and storage slots exceeds
+/// [`MAX_DUMP_STATE_ENTRIES`].
+fn ensure_dump_state_entries(entries: usize) -> RpcResult<()> {
+    if entries > MAX_DUMP_STATE_ENTRIES {
+        return Err(internal_rpc_err(format!(
+            "state too large to dump, more than {MAX_DUMP_STATE_ENTRIES} accounts and storage slots"
+        )))
+    }
+    Ok(())
+}
+
+/// Converts a local miner error into an RPC error.
+fn internal_err(err: impl Display) -> ErrorObject<'static> {
+    internal_rpc_err(err.to_string())
+}
@@ -0,0 +1,353 @@
+//! Read-only access to the raw database and static files of a running node.
+
+use alloy_consensus::Header;
+use alloy_primitives::{hex, Bytes};
+use reth_db::{
+    mdbx::{tx::Tx, Stat, RO},
+    static_file::{iter_static_files, ColumnSelectorOne, HeaderMask, ReceiptMask, TransactionMask},
+};
+use reth_db_api::{
+    cursor::DbDupCursorRO,
+    database::Database,
+    table::{table_key, table_subkey, DupSort, Table},
+    tables,
+    transaction::DbTx,
+    RawDupSort, RawKey, RawTable, TableViewer, Tables,
+};
+use reth_errors::RethError;
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{providers::StaticFileProvider, ProviderError};
+use reth_rpc_eth_types::EthApiError;
+use reth_static_file_types::StaticFileSegment;
+use serde_json::{json, Value};
+use std::{any::Any, fmt, sync::Arc};
+
+/// The `debug_chaindbProperty` property returning the MDBX environment and table stats.
+pub const MDBX_STATS_PROPERTY: &str = "mdbx.stats";
+
+/// The `debug_chaindbProperty` property returning the block and transaction ranges of the static
+/// files per segment.
+pub const STATIC_FILE_RANGES_PROPERTY: &str = "staticfiles.ranges";
+
+/// A cloneable handle for reading the raw database and static files of a running node.
+///
+/// This backs `debug_dbGet`, `debug_dbAncient`, `debug_dbAncients` and `debug_chaindbProperty`.
+#[derive(Clone)]
+pub struct DbInspector {
+    inner: Arc<dyn InspectDatabase>,
+}
+
+impl DbInspector {
+    /// Creates a new inspector for the given database and static files.
+    pub fn new<DB, N>(database: DB, static_file_provider: StaticFileProvider<N>) -> Self
+    where
+        DB: Database + 'static,
+        N: NodePrimitives,
+    {
+        Self { inner: Arc::new(NodeDatabase { database, static_file_provider }) }
+    }
+
+    /// Returns the value stored in the table for the given key, as JSON.
+    ///
+    /// Keys are parsed the same way as by `reth db get`. For dupsort tables, the `subkey` selects
+    /// the first entry at or after it, if it's not set, the first entry of the key is returned.
+    /// If `raw` is set, the compressed value is returned as hex instead of the decoded value.
+    pub fn get(
+        &self,
+        table: &str,
+        key: Value,
+        subkey: Option<Value>,
+        raw: bool,
+    ) -> Result<Option<Value>, EthApiError> {
+        let table = table.parse::<Tables>().map_err(EthApiError::InvalidParams)?;
+        self.inner.get(table, GetValueViewer { key, subkey, raw })
+    }
+
+    /// Returns the raw value stored in the static files of the given segment, keyed by block
+    /// number for headers and by transaction number for transactions and receipts.
+    pub fn ancient(&self, kind: &str, number: u64) -> Result<Option<Bytes>, EthApiError> {
+        let segment = kind
+            .parse::<StaticFileSegment>()
+            .map_err(|_| EthApiError::InvalidParams(format!("unknown ancient kind: {kind:?}")))?;
+        self.inner.ancient(segment, number)
+    }
+
+    /// Returns the number of blocks whose headers are stored in the static files.
+    pub fn ancients(&self) -> u64 {
+        self.inner.ancients()
+    }
+
+    /// Returns the value of the given database property.
+    ///
+    /// See [`MDBX_STATS_PROPERTY`] and [`STATIC_FILE_RANGES_PROPERTY`] for the supported
+    /// properties.
+    pub fn property(&self, property: &str) -> Result<Value, EthApiError> {
+        match property {
+            MDBX_STATS_PROPERTY => self.inner.mdbx_stats(),
+            STATIC_FILE_RANGES_PROPERTY => self.inner.static_file_ranges(),
+            _ => Err(EthApiError::InvalidParams(format!("unknown property: {property:?}"))),
+        }
+    }
+}
+
+impl fmt::Debug for DbInspector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DbInspector").finish_non_exhaustive()
+    }
+}
+
+/// Type erased access to the database and static files of a node.
+trait InspectDatabase: Send + Sync {
+    /// Reads a table value with the given viewer.
+    fn get(&self, table: Tables, viewer: GetValueViewer) -> Result<Option<Value>, EthApiError>;
+
+    /// Reads the first column of a static file segment row.
+    fn ancient(
+        &self,
+        segment: StaticFileSegment,
+        number: u64,
+    ) -> Result<Option<Bytes>, EthApiError>;
+
+    /// Returns the number of blocks in the headers static files.
+    fn ancients(&self) -> u64;
+
+    /// Returns the MDBX environment stats and the stats of every table.
+    fn mdbx_stats(&self) -> Result<Value, EthApiError>;
+
+    /// Returns the block and transaction ranges of all static files.
+    fn static_file_ranges(&self) -> Result<Value, EthApiError>;
+}
+
+/// The database and static files of a node.
+struct NodeDatabase<DB, N: NodePrimitives> {
+    database: DB,
+    static_file_provider: StaticFileProvider<N>,
+}
+
+impl<DB, N> InspectDatabase for NodeDatabase<DB, N>
+where
+    DB: Database + 'static,
+    N: NodePrimitives,
+{
+    fn get(&self, table: Tables, viewer: GetValueViewer) -> Result<Option<Value>, EthApiError> {
+        self.database
+            .view(|tx| table.view(&TxViewer { tx, viewer: &viewer }))
+            .map_err(RethError::from)?
+    }
+
+    fn ancient(
+        &self,
+        segment: StaticFileSegment,
+        number: u64,
+    ) -> Result<Option<Bytes>, EthApiError> {
+        // the column layout doesn't depend on the stored types
+        let mask = match segment {
+            StaticFileSegment::Headers => <HeaderMask<Header>>::MASK,
+            StaticFileSegment::Transactions => {
+                <TransactionMask<<tables::Transactions as Table>::Value>>::MASK
+            }
+            StaticFileSegment::Receipts => <ReceiptMask<<tables::Receipts as Table>::Value>>::MASK,
+        };
+
+        let provider = if segment.is_block_based() {
+            self.static_file_provider.get_segment_provider_from_block(segment, number, None)
+        } else {
+            self.static_file_provider.get_segment_provider_from_transaction(segment, number, None)
+        };
+        let provider = match provider {
+            Ok(provider) => provider,
+            Err(
+                ProviderError::MissingStaticFileBlock(_, _) |
+                ProviderError::MissingStaticFileTx(_, _),
+            ) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut cursor = provider.cursor()?;
+        let columns = cursor.get(number.into(), mask)?;
+        Ok(columns.and_then(|columns| columns.first().map(|column| column.to_vec().into())))
+    }
+
+    fn ancients(&self) -> u64 {
+        self.static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .map_or(0, |block| block + 1)
+    }
+
+    fn mdbx_stats(&self) -> Result<Value, EthApiError> {
+        self.database
+            .view(|tx| {
+                let Some(tx) = (tx as &dyn Any).downcast_ref::<Tx<RO>>() else {
+                    return Err(EthApiError::Unsupported("the database is not an MDBX database"))
+                };
+
+                let mut tables = serde_json::Map::new();
+                for table in Tables::ALL.iter().map(Tables::name) {
+                    let stat = tx
+                        .inner
+                        .open_db(Some(table))
+                        .and_then(|db| tx.inner.db_stat(&db))
+                        .map_err(|err| EthApiError::Internal(RethError::msg(err)))?;
+                    tables.insert(table.to_string(), stat_to_json(&stat));
+                }
+
+                let env = tx
+                    .inner
+                    .env()
+                    .stat()
+                    .map_err(|err| EthApiError::Internal(RethError::msg(err)))?;
+                Ok(json!({ "env": stat_to_json(&env), "tables": tables }))
+            })
+            .map_err(RethError::from)?
+    }
+
+    fn static_file_ranges(&self) -> Result<Value, EthApiError> {
+        let static_files = iter_static_files(self.static_file_provider.directory())
+            .map_err(|err| EthApiError::Internal(RethError::msg(err)))?;
+
+        let mut ranges = serde_json::Map::new();
+        for segment in StaticFileSegment::iter() {
+            let files = static_files
+                .get(&segment)
+                .into_iter()
+                .flatten()
+                .map(|(block_range, tx_range)| {
+                    json!({ "blockRange": block_range, "txRange": tx_range })
+                })
+                .collect();
+            ranges.insert(segment.as_str().to_string(), files);
+        }
+
+        Ok(ranges.into())
+    }
+}
+
+/// The arguments of a `debug_dbGet` request.
+struct GetValueViewer {
+    key: Value,
+    subkey: Option<Value>,
+    raw: bool,
+}
+
+/// A [`TableViewer`] that reads the value requested by a [`GetValueViewer`] in a transaction.
+struct TxViewer<'a, TX> {
+    tx: &'a TX,
+    viewer: &'a GetValueViewer,
+}
+
+impl<TX: DbTx> TableViewer<Option<Value>> for TxViewer<'_, TX> {
+    type Error = EthApiError;
+
+    fn view<T: Table>(&self) -> Result<Option<Value>, Self::Error> {
+        let key = table_key::<T>(&self.viewer.key.to_string()).map_err(invalid_key)?;
+
+        if self.viewer.raw {
+            let value = self.tx.get::<RawTable<T>>(RawKey::from(key)).map_err(RethError::from)?;
+            Ok(value.map(|value| hex::encode_prefixed(value.raw_value()).into()))
+        } else {
+            self.tx.get::<T>(key).map_err(RethError::from)?.map(to_json).transpose()
+        }
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<Option<Value>, Self::Error> {
+        let Some(subkey) = &self.viewer.subkey else { return self.view::<T>() };
+        let key = table_key::<T>(&self.viewer.key.to_string()).map_err(invalid_key)?;
+        let subkey = table_subkey::<T>(&subkey.to_string()).map_err(invalid_key)?;
+
+        if self.viewer.raw {
+            let value = self
+                .tx
+                .cursor_dup_read::<RawDupSort<T>>()
+                .and_then(|mut cursor| {
+                    cursor.seek_by_key_subkey(RawKey::from(key), RawKey::from(subkey))
+                })
+                .map_err(RethError::from)?;
+            Ok(value.map(|value| hex::encode_prefixed(value.raw_value()).into()))
+        } else {
+            self.tx
+                .cursor_dup_read::<T>()
+                .and_then(|mut cursor| cursor.seek_by_key_subkey(key, subkey))
+                .map_err(RethError::from)?
+                .map(to_json)
+                .transpose()
+        }
+    }
+}
+
+/// Serializes the stats of the MDBX environment or a table.
+fn stat_to_json(stat: &Stat) -> Value {
+    json!({
+        "pageSize": stat.page_size(),
+        "depth": stat.depth(),
+        "branchPages": stat.branch_pages(),
+        "leafPages": stat.leaf_pages(),
+        "overflowPages": stat.overflow_pages(),
+        "entries": stat.entries(),
+    })
+}
+
+/// Returns the error for a table key or subkey that can't be parsed.
+fn invalid_key(err: serde_json::Error) -> EthApiError {
+    EthApiError::InvalidParams(format!("invalid key: {err}"))
+}
+
+/// Serializes a decoded table value.
+fn to_json(value: impl serde::Serialize) -> Result<Value, EthApiError> {
+    serde_json::to_value(value).map_err(|err| EthApiError::Internal(RethError::msg(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Address, U256};
+    use reth_db_api::{table::Decompress, transaction::DbTxMut};
+    use reth_primitives_traits::Account;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, DBProvider, DatabaseProviderFactory,
+        StaticFileProviderFactory, StaticFileWriter,
+    };
+    use reth_testing_utils::generators::{self, random_header};
+
+    #[test]
+    fn reads_tables_and_static_files() {
+        let factory = create_test_provider_factory();
+        let address = address!("0x00000000000000000000000000000000000000c0");
+        let account = Account { nonce: 1, balance: U256::from(100), bytecode_hash: None };
+
+        let provider = factory.database_provider_rw().unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(address, account).unwrap();
+        provider.commit().unwrap();
+
+        let header = random_header(&mut generators::rng(), 0, None);
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(header.header(), U256::ZERO, &header.hash()).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let inspector = DbInspector::new(factory.db_ref().clone(), static_file_provider);
+
+        let key = json!(address);
+        let value = inspector.get("PlainAccountState", key.clone(), None, false).unwrap();
+        assert_eq!(value, Some(serde_json::to_value(account).unwrap()));
+        let raw = inspector.get("PlainAccountState", key, None, true).unwrap().unwrap();
+        let raw = hex::decode(raw.as_str().unwrap()).unwrap();
+        assert_eq!(Account::decompress(&raw).unwrap(), account);
+        let missing = inspector.get("PlainAccountState", json!(Address::ZERO), None, false);
+        assert_eq!(missing.unwrap(), None);
+        assert!(inspector.get("Unknown", json!(0), None, false).is_err());
+
+        let ancient = inspector.ancient("headers", 0).unwrap().unwrap();
+        assert_eq!(&Header::decompress(&ancient).unwrap(), header.header());
+        assert_eq!(inspector.ancient("headers", 1).unwrap(), None);
+        assert_eq!(inspector.ancients(), 1);
+
+        let ranges = inspector.property(STATIC_FILE_RANGES_PROPERTY).unwrap();
+        assert_eq!(ranges["headers"][0]["blockRange"], json!({ "start": 0, "end": 0 }));
+        assert_eq!(ranges["transactions"], json!([]));
+        let stats = inspector.property(MDBX_STATS_PROPERTY).unwrap();
+        assert_eq!(stats["tables"]["PlainAccountState"]["entries"], json!(1));
+        assert!(stats["env"]["pageSize"].as_u64().unwrap() > 0);
+        assert!(inspector.property("unknown").is_err());
+    }
+}
@@ -1,32 +1,44 @@
+use crate::DbInspector;
 use alloy_consensus::{
+    constants::KECCAK_EMPTY,
     transaction::{SignerRecoverable, TxHashRef},
     BlockHeader,
 };
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{uint, Address, Bytes, B256};
+use alloy_primitives::{keccak256, map::B256Map, uint, Address, Bytes, B256, U256, U64};
 use alloy_rlp::{Decodable, Encodable};
-use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{
-    state::EvmOverrides, Block as RpcBlock, BlockError, Bundle, StateContext, TransactionInfo,
+use alloy_rpc_types_debug::{
+    AccountState, ExecutionWitness, StorageMap, StorageRangeResult, StorageResult,
 };
+use alloy_rpc_types_eth::{state::EvmOverrides, BlockError, Bundle, StateContext, TransactionInfo};
 use alloy_rpc_types_trace::geth::{
-    call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
-    GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
-    NoopFrame, TraceResult,
+    call::FlatCallFrame, BlockTraceResult, DefaultFrame, FourByteFrame,
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDebugTracingOptions, GethDefaultTracingOptions, GethTrace, NoopFrame, StructLog,
+    TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use futures::{Stream, StreamExt};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    server::SubscriptionMessage,
+    PendingSubscriptionSink, SubscriptionSink,
+};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardfork, EthereumHardforks};
+use reth_engine_primitives::{BadBlock, BadBlockStore, SetHeadHandle};
 use reth_errors::RethError;
-use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
+use reth_evm::{block::StateChangeSource, execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{Block as _, BlockBody, ReceiptWithBloom, RecoveredBlock};
 use reth_revm::{
     database::StateProviderDatabase,
     db::{CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    AccountRangeResult, BadBlockResult, DebugApiServer, DebugSubscriptionKind,
+    StandardTraceOptions,
+};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
@@ -35,18 +47,48 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReaderIdExt, BytecodeReader,
+    ChangeSetReader, HeaderProvider, ProviderBlock, RangeAccount, ReceiptProviderIdExt,
+    StateProofProvider, StateProvider, StateProviderFactory, StateRootProvider,
+    StorageChangeSetReader, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
-use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
+use reth_trie_common::{
+    updates::TrieUpdates, HashedPostState, HashedStorage, KeccakKeyHasher, MultiProofTargets,
+    Nibbles, TrieInput,
+};
+use reth_trie_sparse::{
+    errors::{SparseStateTrieError, SparseStateTrieResult, SparseTrieError, SparseTrieErrorKind},
+    provider::{pad_path_to_key, RevealedNode, TrieNodeProvider, TrieNodeProviderFactory},
+    SerialSparseTrie, SparseStateTrie, SparseTrie,
+};
+use revm::{bytecode::OpCode, context_interface::Transaction, state::EvmState, DatabaseCommit};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{Arc, LazyLock},
+    time::Instant,
+};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// Maximum number of accounts returned by `debug_accountRange`, same as geth.
+const ACCOUNT_RANGE_MAX_RESULTS: u64 = 256;
+
+/// Maximum number of storage slots returned for every account by `debug_accountRange`.
+///
+/// Unlike geth, the storage of large accounts is truncated, the remaining slots can be paged
+/// through with `debug_storageRangeAt`.
+const ACCOUNT_RANGE_MAX_STORAGE_SLOTS: usize = 1024;
+
+/// Maximum number of blocks traced ahead of the subscriber by `debug_traceChain`.
+const TRACE_CHAIN_MAX_BLOCKS_AHEAD: usize = 8;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -58,8 +100,22 @@ pub struct DebugApi<Eth> {
 
 impl<Eth> DebugApi<Eth> {
     /// Create a new instance of the [`DebugApi`]
-    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { eth_api, blocking_task_guard });
+    pub fn new(
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        config: DebugApiConfig,
+        handles: DebugApiHandles,
+    ) -> Self {
+        let DebugApiConfig { bad_blocks_dir, standard_traces_dir } = config;
+        let DebugApiHandles { set_head_handle, db_inspector } = handles;
+        let inner = Arc::new(DebugApiInner {
+            eth_api,
+            blocking_task_guard,
+            bad_blocks: bad_blocks_dir.map(BadBlockStore::new),
+            standard_traces_dir,
+            set_head_handle,
+            db_inspector,
+        });
         Self { inner }
     }
 
@@ -148,9 +204,7 @@ where
         rlp_block: Bytes,
         opts: GethDebugTracingOptions,
     ) -> Result<Vec<TraceResult>, Eth::Error> {
-        let block: ProviderBlock<Eth::Provider> = Decodable::decode(&mut rlp_block.as_ref())
-            .map_err(BlockError::RlpDecodeRawBlock)
-            .map_err(Eth::Error::from_eth_err)?;
+        let block = self.recover_raw_block(rlp_block)?;
 
         let evm_env = self
             .eth_api()
@@ -159,6 +213,18 @@ where
             .map_err(RethError::other)
             .map_err(Eth::Error::from_eth_err)?;
 
+        self.trace_block(Arc::new(block), evm_env, opts).await
+    }
+
+    /// Decodes the rlp encoded block and recovers the senders of its transactions.
+    fn recover_raw_block(
+        &self,
+        rlp_block: Bytes,
+    ) -> Result<RecoveredBlock<ProviderBlock<Eth::Provider>>, Eth::Error> {
+        let block: ProviderBlock<Eth::Provider> = Decodable::decode(&mut rlp_block.as_ref())
+            .map_err(BlockError::RlpDecodeRawBlock)
+            .map_err(Eth::Error::from_eth_err)?;
+
         // Depending on EIP-2 we need to recover the transactions differently
         let senders =
             if self.provider().chain_spec().is_homestead_active_at_block(block.header().number()) {
@@ -181,7 +247,43 @@ where
                     .collect()
             };
 
-        self.trace_block(Arc::new(block.into_recovered_with_signers(senders)), evm_env, opts).await
+        Ok(block.into_recovered_with_signers(senders))
+    }
+
+    /// Returns the bad blocks kept by the node, ordered by descending block number.
+    ///
+    /// Returns no blocks if no bad block store is configured.
+    pub async fn bad_blocks(&self) -> Result<Vec<BadBlock>, Eth::Error> {
+        let Some(store) = self.inner.bad_blocks.clone() else { return Ok(Vec::new()) };
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                store.blocks().map_err(RethError::other).map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns the bad block with the given hash, if it's kept by the node.
+    async fn bad_block(&self, block_hash: B256) -> Result<Option<BadBlock>, Eth::Error> {
+        let Some(store) = self.inner.bad_blocks.clone() else { return Ok(None) };
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                store.get(block_hash).map_err(RethError::other).map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Traces the bad block with the given hash on top of the state of its parent.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let bad_block = self
+            .bad_block(block_hash)
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+
+        self.debug_trace_raw_block(bad_block.rlp, opts).await
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -206,6 +308,222 @@ where
         self.trace_block(block, evm_env, opts).await
     }
 
+    /// Replays the block with the given hash and writes the [EIP-3155] trace of each transaction
+    /// to its own file, see [`Self::standard_trace_block_to_file`].
+    ///
+    /// [EIP-3155]: https://eips.ethereum.org/EIPS/eip-3155
+    pub async fn debug_standard_trace_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: StandardTraceOptions,
+    ) -> Result<Vec<PathBuf>, Eth::Error> {
+        let ((evm_env, _), block) = futures::try_join!(
+            self.eth_api().evm_env_at(block_hash.into()),
+            self.eth_api().recovered_block(block_hash.into()),
+        )?;
+
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+
+        self.standard_trace_block_to_file(block, evm_env, opts).await
+    }
+
+    /// Replays the bad block with the given hash on top of the state of its parent and writes the
+    /// [EIP-3155] trace of each transaction to its own file.
+    ///
+    /// [EIP-3155]: https://eips.ethereum.org/EIPS/eip-3155
+    pub async fn debug_standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: StandardTraceOptions,
+    ) -> Result<Vec<PathBuf>, Eth::Error> {
+        let bad_block = self
+            .bad_block(block_hash)
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let block = self.recover_raw_block(bad_block.rlp)?;
+
+        let evm_env = self
+            .eth_api()
+            .evm_config()
+            .evm_env(block.header())
+            .map_err(RethError::other)
+            .map_err(Eth::Error::from_eth_err)?;
+
+        self.standard_trace_block_to_file(Arc::new(block), evm_env, opts).await
+    }
+
+    /// Returns the name of the latest Ethereum hardfork active at the given header, as written to
+    /// the summary line of [EIP-3155] traces.
+    ///
+    /// [EIP-3155]: https://eips.ethereum.org/EIPS/eip-3155
+    fn fork_name(&self, header: &impl BlockHeader) -> &'static str {
+        let chain_spec = self.provider().chain_spec();
+        EthereumHardfork::VARIANTS
+            .iter()
+            .rev()
+            .find(|fork| {
+                chain_spec
+                    .ethereum_fork_activation(**fork)
+                    .active_at_timestamp_or_number(header.timestamp(), header.number())
+            })
+            .map_or("Frontier", |fork| fork.name())
+    }
+
+    /// Replays the block and writes the trace of each transaction as [EIP-3155] JSON lines to
+    /// `block_<block hash>-<tx index>-<tx hash>.jsonl` in the standard traces directory.
+    ///
+    /// If [`StandardTraceOptions::tx_hash`] is set, only that transaction is traced.
+    ///
+    /// Returns the paths of the written files.
+    ///
+    /// [EIP-3155]: https://eips.ethereum.org/EIPS/eip-3155
+    async fn standard_trace_block_to_file(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+        evm_env: EvmEnvFor<Eth::Evm>,
+        opts: StandardTraceOptions,
+    ) -> Result<Vec<PathBuf>, Eth::Error> {
+        let Some(dir) = self.inner.standard_traces_dir.clone() else {
+            return Err(EthApiError::Unsupported("standard traces are not supported").into())
+        };
+
+        if let Some(tx_hash) = opts.tx_hash &&
+            !block.transactions_recovered().any(|tx| *tx.tx_hash() == tx_hash)
+        {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction {tx_hash} not found in block"
+            ))
+            .into())
+        }
+
+        // the summary line of each trace carries the state root after the transaction
+        let roots = self.intermediate_roots(block.clone()).await?;
+        let fork = self.fork_name(block.header());
+
+        // memory is always recorded so that `memSize` is accurate, even if it's not written
+        let config = GethDefaultTracingOptions {
+            enable_memory: Some(true),
+            disable_storage: Some(opts.disable_storage),
+            ..Default::default()
+        };
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut files = Vec::new();
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                fs::create_dir_all(&dir)
+                    .map_err(RethError::other)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                for (index, tx) in block.transactions_recovered().enumerate() {
+                    let tx_hash = *tx.tx_hash();
+                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+
+                    if opts.tx_hash.is_some_and(|target| target != tx_hash) {
+                        let res = this.eth_api().transact(&mut db, evm_env.clone(), tx_env)?;
+                        db.commit(res.state);
+                        continue
+                    }
+
+                    let mut inspector =
+                        TracingInspector::new(TracingInspectorConfig::from_geth_config(&config));
+                    let gas_limit = tx_env.gas_limit();
+                    let start = Instant::now();
+                    let res =
+                        this.eth_api().inspect(&mut db, evm_env.clone(), tx_env, &mut inspector)?;
+                    let elapsed = start.elapsed();
+                    let gas_used = res.result.gas_used();
+                    let return_value = res.result.into_output().unwrap_or_default();
+                    let frame = inspector
+                        .with_transaction_gas_limit(gas_limit)
+                        .into_geth_builder()
+                        .geth_traces(gas_used, return_value, config);
+                    db.commit(res.state);
+
+                    let path = dir.join(format!("block_{}-{index}-{tx_hash}.jsonl", block.hash()));
+                    let summary = StandardTraceSummary {
+                        state_root: roots[index],
+                        output: &frame.return_value,
+                        gas_used: U64::from(frame.gas),
+                        pass: !frame.failed,
+                        time: elapsed.as_nanos() as u64,
+                        fork,
+                    };
+                    File::create(&path)
+                        .and_then(|file| {
+                            write_standard_trace(
+                                BufWriter::new(file),
+                                &frame,
+                                &summary,
+                                opts.disable_memory,
+                            )
+                        })
+                        .map_err(RethError::other)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    files.push(path);
+
+                    if opts.tx_hash.is_some() {
+                        break
+                    }
+                }
+
+                Ok(files)
+            })
+            .await
+    }
+
+    /// Returns a stream of the traces of all blocks after `start_exclusive` up to and including
+    /// `end_inclusive`, in block order.
+    ///
+    /// Up to [`TRACE_CHAIN_MAX_BLOCKS_AHEAD`] blocks are traced in parallel, and tracing only
+    /// continues as the stream is consumed.
+    pub fn debug_trace_chain(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+    ) -> Result<
+        impl Stream<Item = Result<BlockTraceResult, Eth::Error>> + Send + 'static,
+        Eth::Error,
+    > {
+        let block_number = |number_or_tag: BlockNumberOrTag| -> Result<u64, Eth::Error> {
+            self.provider()
+                .convert_block_number(number_or_tag)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::HeaderNotFound(number_or_tag.into()).into())
+        };
+        let start = block_number(start_exclusive)?;
+        let end = block_number(end_inclusive)?;
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block ({end}) must be greater than start block ({start})"
+            ))
+            .into())
+        }
+
+        let this = self.clone();
+        Ok(futures::stream::iter(start + 1..=end)
+            .map(move |block_number| {
+                let this = this.clone();
+                let opts = opts.clone();
+                async move {
+                    let _permit = this.acquire_trace_permit().await;
+                    let hash = this
+                        .provider()
+                        .block_hash(block_number)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                    let traces = this.debug_trace_block(hash.into(), opts).await?;
+                    Ok(BlockTraceResult { block: U256::from(block_number), hash, traces })
+                }
+            })
+            .buffered(TRACE_CHAIN_MAX_BLOCKS_AHEAD))
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -693,6 +1011,388 @@ where
         Ok(exec_witness)
     }
 
+    /// Returns up to `max_results` accounts of the state at the given block, ordered by hashed
+    /// address and starting at the `start` hashed address.
+    ///
+    /// Like geth, at most 256 accounts are returned, which is also the page size if `max_results`
+    /// is zero. At most 1024 storage slots are returned per account.
+    pub async fn debug_account_range(
+        &self,
+        block: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<AccountRangeResult, Eth::Error> {
+        if !incompletes {
+            return Err(EthApiError::InvalidParams(
+                "address preimages are not stored, incompletes must be true".to_string(),
+            )
+            .into())
+        }
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams("start key too long".to_string()).into())
+        }
+        let start = B256::right_padding_from(&start);
+        let limit = match max_results {
+            0 => ACCOUNT_RANGE_MAX_RESULTS,
+            max_results => max_results.min(ACCOUNT_RANGE_MAX_RESULTS),
+        } as usize;
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let header = this
+                    .provider()
+                    .sealed_header_by_number_or_tag(block)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or(EthApiError::HeaderNotFound(block.into()))?;
+                let state = this
+                    .provider()
+                    .state_by_block_number_or_tag(block)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let storage_limit = if nostorage { 0 } else { ACCOUNT_RANGE_MAX_STORAGE_SLOTS };
+                let range = state
+                    .account_range(HashedPostState::default(), start, limit, storage_limit, false)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let mut accounts = BTreeMap::new();
+                for RangeAccount { hashed_address, account, storage_root, storage, .. } in
+                    range.accounts
+                {
+                    let code_hash = account.get_bytecode_hash();
+                    let code = if nocode || code_hash == KECCAK_EMPTY {
+                        None
+                    } else {
+                        state
+                            .bytecode_by_hash(&code_hash)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .map(|code| code.original_bytes())
+                    };
+                    accounts.insert(
+                        format!("pre({hashed_address})"),
+                        AccountState {
+                            balance: account.balance,
+                            nonce: account.nonce,
+                            root: storage_root,
+                            code_hash,
+                            code,
+                            storage: (!storage.is_empty()).then(|| storage.into_iter().collect()),
+                            address: None,
+                            address_hash: Some(hashed_address),
+                        },
+                    );
+                }
+
+                Ok(AccountRangeResult {
+                    root: header.state_root(),
+                    accounts,
+                    next: range.next.map(|next| Bytes::copy_from_slice(next.as_slice())),
+                })
+            })
+            .await
+    }
+
+    /// Returns up to `max_result` storage slots of the contract, ordered by hashed slot and
+    /// starting at the `key_start` hashed slot, as of right before the transaction at the given
+    /// index in the block is executed.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let ((evm_env, _), block) = futures::try_join!(
+            self.eth_api().evm_env_at(block_hash.into()),
+            self.eth_api().recovered_block(block_hash.into()),
+        )?;
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+
+        // the state before the first transaction is available even if the block is empty
+        if tx_idx > 0 && tx_idx >= block.body().transactions().len() {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            ))
+            .into())
+        }
+        let limit = usize::try_from(max_result).unwrap_or(usize::MAX);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(&state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay all transactions prior to the targeted transaction
+                if let Some(tx) = block.body().transactions().get(tx_idx) {
+                    this.eth_api().replay_transactions_until(
+                        &mut db,
+                        evm_env,
+                        block.transactions_recovered(),
+                        *tx.tx_hash(),
+                    )?;
+                }
+
+                // the storage changes of the replayed transactions, with their preimages
+                let mut hashed_storage = HashedStorage::default();
+                let mut preimages = B256Map::default();
+                if let Some(account) = db.cache.accounts.get(&contract_address) {
+                    hashed_storage.wiped = account.account_state.is_storage_cleared();
+                    for (slot, value) in &account.storage {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        hashed_storage.storage.insert(hashed_slot, *value);
+                        preimages.insert(hashed_slot, slot);
+                    }
+                }
+
+                let range = state
+                    .storage_range(contract_address, hashed_storage, key_start, limit)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let mut storage = StorageMap::default();
+                for slot in range.slots {
+                    let key = slot
+                        .key
+                        .or_else(|| preimages.get(&slot.hashed_key).copied())
+                        .ok_or_else(|| {
+                            EthApiError::Internal(RethError::msg(format!(
+                                "missing preimage of storage slot {}",
+                                slot.hashed_key
+                            )))
+                        })?;
+                    storage
+                        .insert(slot.hashed_key, StorageResult { key, value: slot.value.into() });
+                }
+
+                Ok(StorageRangeResult { storage, next_key: range.next })
+            })
+            .await
+    }
+
+    /// Re-executes the block with the given hash and returns the state root after each of its
+    /// transactions.
+    ///
+    /// The block can be canonical, on a side chain or one of the bad blocks kept by the node. The
+    /// paths changed by the block are revealed once in a sparse trie of the parent state, and the
+    /// root after each transaction only rehashes the paths changed by it.
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let block = match self.eth_api().recovered_block(block_hash.into()).await? {
+            Some(block) => block,
+            None => {
+                let bad_block = self
+                    .bad_block(block_hash)
+                    .await?
+                    .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+                Arc::new(self.recover_raw_block(bad_block.rlp)?)
+            }
+        };
+
+        self.intermediate_roots(block).await
+    }
+
+    /// Replays the block on top of the state of its parent and returns the state root after each
+    /// transaction, see [`Self::debug_intermediate_roots`].
+    async fn intermediate_roots(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                // collect the state changes of the block in execution order
+                let (tx, rx) = std::sync::mpsc::channel();
+                let db = StateProviderDatabase::new(&state);
+                this.eth_api()
+                    .evm_config()
+                    .executor(db)
+                    .execute_with_state_hook(
+                        &block,
+                        move |source: StateChangeSource, state: &EvmState| {
+                            let hashed_state =
+                                HashedPostState::from_evm_state::<KeccakKeyHasher>(state.clone());
+                            let _ = tx.send((source, hashed_state));
+                        },
+                    )
+                    .map_err(|err| EthApiError::Internal(err.into()))?;
+                let changes = rx.try_iter().collect::<Vec<_>>();
+
+                let mut targets = MultiProofTargets::default();
+                for (_, hashed_state) in &changes {
+                    targets.extend(hashed_state.multi_proof_targets());
+                }
+                let multiproof = state
+                    .multiproof(TrieInput::default(), targets)
+                    .map_err(Eth::Error::from_eth_err)?;
+                let mut trie = SparseStateTrie::<SerialSparseTrie>::default();
+                trie.reveal_multiproof(multiproof).map_err(sparse_trie_err)?;
+                let node_provider = StateProofNodeProvider::new(&state);
+
+                let mut roots = Vec::with_capacity(block.body().transactions().len());
+                let mut pending = HashedPostState::default();
+                for (source, hashed_state) in changes {
+                    pending.extend(hashed_state);
+                    // like in geth, pre-block changes are part of the root of the first
+                    // transaction and post-block changes are not part of any root
+                    if !matches!(source, StateChangeSource::Transaction(_)) {
+                        continue
+                    }
+
+                    update_sparse_trie(&mut trie, std::mem::take(&mut pending), &node_provider)
+                        .map_err(sparse_trie_err)?;
+                    roots.push(trie.root(&node_provider).map_err(sparse_trie_err)?);
+                }
+
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns the accounts modified in the blocks after `start` up to and including `end`, or in
+    /// the `start` block if no `end` is given.
+    ///
+    /// An account is modified if its nonce, balance, code or storage changed, which is read from
+    /// the account and storage changesets of the blocks.
+    pub async fn debug_get_modified_accounts(
+        &self,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let range = match end {
+            None if start == 0 => {
+                return Err(EthApiError::InvalidParams("block 0 has no parent".to_string()).into())
+            }
+            None => start..=start,
+            Some(end) if end > start => start + 1..=end,
+            Some(end) => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "start block height ({start}) must be less than end block height ({end})"
+                ))
+                .into())
+            }
+        };
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                let best_block = provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+                if *range.end() > best_block {
+                    return Err(EthApiError::HeaderNotFound((*range.end()).into()).into())
+                }
+
+                let mut accounts = BTreeSet::new();
+                for block_number in range {
+                    let account_changes = provider
+                        .account_block_changeset(block_number)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    accounts.extend(account_changes.into_iter().map(|change| change.address));
+
+                    // accounts with only storage changes are not part of the account changesets
+                    let storage_changes = provider
+                        .storage_changeset(block_number)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    accounts.extend(storage_changes.into_iter().map(|(key, _)| key.address()));
+                }
+
+                Ok(accounts.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Same as [`Self::debug_get_modified_accounts`], for the canonical blocks with the given
+    /// hashes.
+    pub async fn debug_get_modified_accounts_by_hash(
+        &self,
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let block_number = |hash: B256| -> Result<u64, Eth::Error> {
+            self.provider()
+                .block_number(hash)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::HeaderNotFound(hash.into()).into())
+        };
+        let start = block_number(start_hash)?;
+        let end = end_hash.map(block_number).transpose()?;
+
+        self.debug_get_modified_accounts(start, end).await
+    }
+
+    /// Unwinds the canonical chain to the block with the given number.
+    ///
+    /// Reverted blocks that are already persisted are unwound by the pipeline in the background,
+    /// and the node resumes syncing with the next forkchoice update.
+    pub async fn debug_set_head(&self, number: u64) -> Result<(), Eth::Error> {
+        let Some(handle) = &self.inner.set_head_handle else {
+            return Err(EthApiError::Unsupported("debug_setHead is not supported").into())
+        };
+        handle.set_head(number).await.map_err(EthApiError::Internal)?;
+        Ok(())
+    }
+
+    /// Returns the database inspector, or an error if the node doesn't support it.
+    fn db_inspector(&self) -> Result<DbInspector, Eth::Error> {
+        self.inner
+            .db_inspector
+            .clone()
+            .ok_or_else(|| EthApiError::Unsupported("database access is not supported").into())
+    }
+
+    /// Returns the value stored in the database table for the given key.
+    pub async fn debug_db_get(
+        &self,
+        table: String,
+        key: serde_json::Value,
+        subkey: Option<serde_json::Value>,
+        raw: bool,
+    ) -> Result<Option<serde_json::Value>, Eth::Error> {
+        let inspector = self.db_inspector()?;
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                inspector.get(&table, key, subkey, raw).map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns the raw value stored in the static files of the given segment.
+    pub async fn debug_db_ancient(
+        &self,
+        kind: String,
+        number: u64,
+    ) -> Result<Option<Bytes>, Eth::Error> {
+        let inspector = self.db_inspector()?;
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                inspector.ancient(&kind, number).map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns the number of blocks in the static files.
+    pub fn debug_db_ancients(&self) -> Result<u64, Eth::Error> {
+        Ok(self.db_inspector()?.ancients())
+    }
+
+    /// Returns the given property of the database.
+    pub async fn debug_chaindb_property(
+        &self,
+        property: String,
+    ) -> Result<serde_json::Value, Eth::Error> {
+        let inspector = self.db_inspector()?;
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                inspector.property(&property).map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
     /// Returns the code associated with a given hash at the specified block ID. If no code is
     /// found, it returns None. If no block ID is provided, it defaults to the latest block.
     pub async fn debug_code_by_hash(
@@ -983,17 +1683,44 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Ok(vec![])
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlockResult>> {
+        Ok(Self::bad_blocks(self)
+            .await
+            .map_err(Into::into)?
+            .into_iter()
+            .map(|BadBlock { hash, rlp, reason, .. }| BadBlockResult { hash, rlp, reason })
+            .collect())
     }
 
-    /// Handler for `debug_traceChain`
-    async fn debug_trace_chain(
+    /// Handler for `debug_subscribe`
+    async fn debug_subscribe(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        match kind {
+            DebugSubscriptionKind::TraceChain => {
+                let stream = match Self::debug_trace_chain(
+                    self,
+                    start_exclusive,
+                    end_inclusive,
+                    opts.unwrap_or_default(),
+                ) {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        pending.reject(err).await;
+                        return Ok(())
+                    }
+                };
+                let sink = pending.accept().await?;
+                pipe_block_traces(sink, stream).await?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -1089,16 +1816,28 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_account_range(
+            self,
+            block_number,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1113,8 +1852,8 @@ where
         Ok(self.provider().chain_spec().genesis().config.clone())
     }
 
-    async fn debug_chaindb_property(&self, _property: String) -> RpcResult<()> {
-        Ok(())
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<serde_json::Value> {
+        Self::debug_chaindb_property(self, property).await.map_err(Into::into)
     }
 
     async fn debug_code_by_hash(
@@ -1129,16 +1868,24 @@ where
         Ok(())
     }
 
-    async fn debug_db_ancient(&self, _kind: String, _number: u64) -> RpcResult<()> {
-        Ok(())
+    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<Option<Bytes>> {
+        Self::debug_db_ancient(self, kind, number).await.map_err(Into::into)
     }
 
-    async fn debug_db_ancients(&self) -> RpcResult<()> {
-        Ok(())
+    async fn debug_db_ancients(&self) -> RpcResult<u64> {
+        Self::debug_db_ancients(self).map_err(Into::into)
     }
 
-    async fn debug_db_get(&self, _key: String) -> RpcResult<()> {
-        Ok(())
+    async fn debug_db_get(
+        &self,
+        table: String,
+        key: serde_json::Value,
+        subkey: Option<serde_json::Value>,
+        raw: Option<bool>,
+    ) -> RpcResult<Option<serde_json::Value>> {
+        Self::debug_db_get(self, table, key, subkey, raw.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_dump_block(&self, _number: BlockId) -> RpcResult<()> {
@@ -1165,32 +1912,38 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_hash(self, start_hash, end_hash)
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts(self, start_number, end_number).await.map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
+        block_hash: B256,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1221,8 +1974,9 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_setHead`
+    async fn debug_set_head(&self, number: u64) -> RpcResult<()> {
+        Self::debug_set_head(self, number).await.map_err(Into::into)
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
@@ -1237,20 +1991,35 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StandardTraceOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        let files = Self::debug_standard_trace_bad_block_to_file(
+            self,
+            block_hash,
+            opts.unwrap_or_default(),
+        )
+        .await
+        .map_err(Into::into)?;
+        Ok(files.into_iter().map(|path| path.display().to_string()).collect())
     }
 
+    /// Handler for `debug_standardTraceBlockToFile`
     async fn debug_standard_trace_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StandardTraceOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        let files =
+            Self::debug_standard_trace_block_to_file(self, block_hash, opts.unwrap_or_default())
+                .await
+                .map_err(Into::into)?;
+        Ok(files.into_iter().map(|path| path.display().to_string()).collect())
     }
 
     async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
@@ -1277,23 +2046,37 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1317,12 +2100,248 @@ where
     }
 }
 
+/// Pipes the block traces to the subscription sink, until the first error.
+///
+/// Sending waits for the subscriber to catch up, which in turn pauses the tracing of the next
+/// blocks.
+async fn pipe_block_traces<E, St>(sink: SubscriptionSink, stream: St) -> Result<(), E>
+where
+    St: Stream<Item = Result<BlockTraceResult, E>>,
+    E: From<EthApiError>,
+{
+    let mut stream = std::pin::pin!(stream);
+    loop {
+        tokio::select! {
+            _ = sink.closed() => {
+                // connection dropped
+                break Ok(())
+            }
+            maybe_item = stream.next() => {
+                let Some(item) = maybe_item.transpose()? else {
+                    // all blocks traced
+                    break Ok(())
+                };
+                let msg =
+                    SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &item)
+                        .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+
+                if sink.send(msg).await.is_err() {
+                    break Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Applies the state changes of a transaction to a sparse trie whose changed paths are revealed.
+///
+/// Leaf removals are deferred after the updates, like in the sparse trie task of the engine, so
+/// that branch nodes aren't collapsed and re-created.
+fn update_sparse_trie<P: StateProofProvider>(
+    trie: &mut SparseStateTrie,
+    mut state: HashedPostState,
+    node_provider: &StateProofNodeProvider<'_, P>,
+) -> SparseStateTrieResult<()> {
+    let mut removed_accounts = Vec::new();
+    for (address, storage) in state.storages {
+        if storage.wiped {
+            trie.insert_storage_trie(address, SparseTrie::revealed_empty());
+        }
+
+        let mut removed_slots = Vec::new();
+        for (slot, value) in storage.storage {
+            let slot = Nibbles::unpack(slot);
+            if value.is_zero() {
+                removed_slots.push(slot);
+                continue
+            }
+            let value = alloy_rlp::encode_fixed_size(&value).to_vec();
+            trie.update_storage_leaf(address, slot, value, node_provider)?;
+        }
+        for slot in removed_slots {
+            trie.remove_storage_leaf(address, &slot, node_provider)?;
+        }
+
+        let exists = match state.accounts.remove(&address) {
+            Some(account) => {
+                trie.update_account(address, account.unwrap_or_default(), node_provider)?
+            }
+            None if trie.is_account_revealed(address) => {
+                trie.update_account_storage_root(address, node_provider)?
+            }
+            None => true,
+        };
+        if !exists {
+            removed_accounts.push(address);
+        }
+    }
+
+    for (address, account) in state.accounts {
+        if !trie.update_account(address, account.unwrap_or_default(), node_provider)? {
+            removed_accounts.push(address);
+        }
+    }
+    for address in removed_accounts {
+        trie.remove_account_leaf(&Nibbles::unpack(address), node_provider)?;
+    }
+
+    Ok(())
+}
+
+/// Converts a sparse trie error into an [`EthApiError`].
+fn sparse_trie_err(err: SparseStateTrieError) -> EthApiError {
+    EthApiError::Internal(RethError::msg(err))
+}
+
+/// Provides the blinded nodes of a sparse trie from the proofs of a state provider.
+///
+/// The proof of any key below a path contains the node at that path.
+#[derive(Debug)]
+struct StateProofNodeProvider<'a, P> {
+    provider: &'a P,
+    /// Hashed address of the storage trie, or `None` for the account trie.
+    account: Option<B256>,
+}
+
+impl<'a, P> StateProofNodeProvider<'a, P> {
+    /// Creates a provider of account trie nodes.
+    const fn new(provider: &'a P) -> Self {
+        Self { provider, account: None }
+    }
+}
+
+impl<P: StateProofProvider> TrieNodeProviderFactory for StateProofNodeProvider<'_, P> {
+    type AccountNodeProvider = Self;
+    type StorageNodeProvider = Self;
+
+    fn account_node_provider(&self) -> Self::AccountNodeProvider {
+        Self::new(self.provider)
+    }
+
+    fn storage_node_provider(&self, account: B256) -> Self::StorageNodeProvider {
+        Self { provider: self.provider, account: Some(account) }
+    }
+}
+
+impl<P: StateProofProvider> TrieNodeProvider for StateProofNodeProvider<'_, P> {
+    fn trie_node(&self, path: &Nibbles) -> Result<Option<RevealedNode>, SparseTrieError> {
+        let key = pad_path_to_key(path);
+        let targets = match self.account {
+            Some(account) => MultiProofTargets::account_with_slots(account, [key]),
+            None => MultiProofTargets::account(key),
+        };
+        let mut proof = self
+            .provider
+            .multiproof(TrieInput::default(), targets)
+            .map_err(|err| SparseTrieErrorKind::Other(Box::new(err)))?;
+        let nodes = match self.account {
+            Some(account) => {
+                proof.storages.remove(&account).map(|storage| storage.subtree).unwrap_or_default()
+            }
+            None => proof.account_subtree,
+        };
+        Ok(nodes
+            .into_inner()
+            .remove(path)
+            .map(|node| RevealedNode { node, tree_mask: None, hash_mask: None }))
+    }
+}
+
 impl<Eth> std::fmt::Debug for DebugApi<Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
     }
 }
 
+/// A single step of an [EIP-3155] trace.
+///
+/// [EIP-3155]: https://eips.ethereum.org/EIPS/eip-3155
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StandardTraceStep<'a> {
+    pc: u64,
+    op: u8,
+    gas: U64,
+    gas_cost: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<String>,
+    mem_size: u64,
+    stack: &'a [U256],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    return_data: Option<&'a Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<&'a BTreeMap<B256, B256>>,
+    depth: u64,
+    refund: u64,
+    op_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl<'a> StandardTraceStep<'a> {
+    fn new(log: &'a StructLog, opcodes: &HashMap<&str, u8>, disable_memory: bool) -> Self {
+        let memory = log.memory.as_ref().filter(|_| !disable_memory).map(|words| {
+            let hex = words.iter().map(|word| word.trim_start_matches("0x")).collect::<String>();
+            format!("0x{hex}")
+        });
+
+        Self {
+            pc: log.pc,
+            op: opcodes.get(&*log.op).copied().unwrap_or(OpCode::INVALID.get()),
+            gas: U64::from(log.gas),
+            gas_cost: U64::from(log.gas_cost),
+            memory,
+            mem_size: log.memory_size.unwrap_or_default(),
+            stack: log.stack.as_deref().unwrap_or_default(),
+            return_data: log.return_data.as_ref(),
+            storage: log.storage.as_ref(),
+            depth: log.depth,
+            refund: log.refund_counter.unwrap_or_default(),
+            op_name: &log.op,
+            error: log.error.as_deref(),
+        }
+    }
+}
+
+/// The summary line that ends an [EIP-3155] trace.
+///
+/// [EIP-3155]: https://eips.ethereum.org/EIPS/eip-3155
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StandardTraceSummary<'a> {
+    state_root: B256,
+    output: &'a Bytes,
+    gas_used: U64,
+    pass: bool,
+    /// The execution time of the transaction in nanoseconds.
+    time: u64,
+    fork: &'static str,
+}
+
+/// The opcodes by name, struct logs only carry the opcode name.
+static OPCODES: LazyLock<HashMap<&'static str, u8>> = LazyLock::new(|| {
+    (0..=u8::MAX).filter_map(OpCode::new).map(|op| (op.as_str(), op.get())).collect()
+});
+
+/// Writes the struct logs of the frame followed by the summary line as [EIP-3155] JSON lines.
+///
+/// [EIP-3155]: https://eips.ethereum.org/EIPS/eip-3155
+fn write_standard_trace(
+    mut writer: impl Write,
+    frame: &DefaultFrame,
+    summary: &StandardTraceSummary<'_>,
+    disable_memory: bool,
+) -> io::Result<()> {
+    for log in &frame.struct_logs {
+        let step = StandardTraceStep::new(log, &OPCODES, disable_memory);
+        serde_json::to_writer(&mut writer, &step)?;
+        writer.write_all(b"\n")?;
+    }
+    serde_json::to_writer(&mut writer, summary)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
 impl<Eth> Clone for DebugApi<Eth> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
@@ -1334,4 +2353,123 @@ struct DebugApiInner<Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// The store of the bad blocks rejected by the engine, if any.
+    bad_blocks: Option<BadBlockStore>,
+    /// The directory standard traces are written to, if any.
+    standard_traces_dir: Option<PathBuf>,
+    /// The handle to unwind the canonical chain, if the node supports it.
+    set_head_handle: Option<SetHeadHandle>,
+    /// The handle to read the raw database and static files, if the node supports it.
+    db_inspector: Option<DbInspector>,
+}
+
+/// Configuration for the `debug` API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugApiConfig {
+    /// The directory of the [`BadBlockStore`] that `debug_getBadBlocks` and `debug_traceBadBlock`
+    /// are served from.
+    pub bad_blocks_dir: Option<PathBuf>,
+    /// The directory that `debug_standardTraceBlockToFile` and
+    /// `debug_standardTraceBadBlockToFile` write their traces to.
+    pub standard_traces_dir: Option<PathBuf>,
+}
+
+/// Handles to the running node that the `debug` API inspects and changes its state with.
+#[derive(Debug, Clone, Default)]
+pub struct DebugApiHandles {
+    /// The handle to the engine that `debug_setHead` unwinds the canonical chain with.
+    pub set_head_handle: Option<SetHeadHandle>,
+    /// The handle that `debug_dbGet`, `debug_dbAncient`, `debug_dbAncients` and
+    /// `debug_chaindbProperty` read the database and static files with.
+    pub db_inspector: Option<DbInspector>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_write_standard_trace() {
+        let frame = DefaultFrame {
+            failed: false,
+            gas: 21_003,
+            return_value: Bytes::new(),
+            struct_logs: vec![
+                StructLog {
+                    pc: 0,
+                    op: "PUSH1".into(),
+                    gas: 100,
+                    gas_cost: 3,
+                    depth: 1,
+                    stack: Some(Vec::new()),
+                    memory: Some(vec![format!("0x{}", "00".repeat(31) + "2a")]),
+                    memory_size: Some(32),
+                    ..Default::default()
+                },
+                StructLog {
+                    pc: 2,
+                    op: "STOP".into(),
+                    gas: 97,
+                    gas_cost: 0,
+                    depth: 1,
+                    stack: Some(vec![U256::from(1)]),
+                    ..Default::default()
+                },
+            ],
+        };
+        let summary = StandardTraceSummary {
+            state_root: B256::with_last_byte(1),
+            output: &frame.return_value,
+            gas_used: U64::from(frame.gas),
+            pass: !frame.failed,
+            time: 42,
+            fork: "Cancun",
+        };
+
+        let mut out = Vec::new();
+        write_standard_trace(&mut out, &frame, &summary, false).unwrap();
+        let lines = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec![
+                json!({
+                    "pc": 0,
+                    "op": 0x60,
+                    "gas": "0x64",
+                    "gasCost": "0x3",
+                    "memory": format!("0x{}", "00".repeat(31) + "2a"),
+                    "memSize": 32,
+                    "stack": [],
+                    "depth": 1,
+                    "refund": 0,
+                    "opName": "PUSH1",
+                }),
+                json!({
+                    "pc": 2,
+                    "op": 0x00,
+                    "gas": "0x61",
+                    "gasCost": "0x0",
+                    "memSize": 0,
+                    "stack": ["0x1"],
+                    "depth": 1,
+                    "refund": 0,
+                    "opName": "STOP",
+                }),
+                json!({
+                    "stateRoot": B256::with_last_byte(1),
+                    "output": "0x",
+                    "gasUsed": "0x520b",
+                    "pass": true,
+                    "time": 42,
+                    "fork": "Cancun",
+                }),
+            ]
+        );
+    }
 }
@@ -0,0 +1,154 @@
+//! `eth_sendRawTransactionConditional` support for the local transaction pool.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_errors::RethError;
+use reth_rpc_eth_api::L2EthApiExtServer;
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
+use reth_transaction_pool::{
+    conditional::{
+        known_accounts_match, KnownAccountsError, MAX_CONCURRENT_CONDITIONAL_VALIDATIONS,
+        MAX_CONDITIONAL_EXECUTION_COST,
+    },
+    AddedTransactionOutcome, PoolPooledTx, PoolTransaction, TransactionOrigin, TransactionPool,
+};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tracing::trace;
+
+/// `Eth` API implementation of `eth_sendRawTransactionConditional`.
+///
+/// Transactions are validated against the latest state and added to the pool as private
+/// transactions with their [`TransactionConditional`] attached, they're evicted from the pool once
+/// the conditional can no longer be met.
+pub struct EthTransactionConditional<Pool, Provider> {
+    /// All nested fields bundled together.
+    inner: Arc<EthTransactionConditionalInner<Pool, Provider>>,
+}
+
+impl<Pool, Provider> EthTransactionConditional<Pool, Provider> {
+    /// Create a new `EthTransactionConditional` instance.
+    pub fn new(pool: Pool, provider: Provider) -> Self {
+        Self {
+            inner: Arc::new(EthTransactionConditionalInner {
+                pool,
+                provider,
+                validation_semaphore: Semaphore::new(MAX_CONCURRENT_CONDITIONAL_VALIDATIONS),
+            }),
+        }
+    }
+
+    /// Access the transaction pool.
+    pub fn pool(&self) -> &Pool {
+        &self.inner.pool
+    }
+
+    /// Access the provider.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+}
+
+impl<Pool, Provider> EthTransactionConditional<Pool, Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Validates the conditional against the latest state and adds the transaction to the pool.
+    async fn add_conditional_transaction(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> Result<B256, EthApiError> {
+        if condition.cost() > MAX_CONDITIONAL_EXECUTION_COST {
+            return Err(EthApiError::InvalidParams(
+                "conditional cost exceeded maximum allowed".to_string(),
+            ))
+        }
+
+        let recovered = recover_raw_transaction::<PoolPooledTx<Pool>>(&bytes)?;
+        let mut tx = Pool::Transaction::from_pooled(recovered);
+
+        let header = self
+            .provider()
+            .latest_header()?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::Number(BlockNumberOrTag::Latest)))?;
+
+        // the conditional must still be satisfiable by a future block
+        if condition.has_exceeded_block_number(header.header().number()) ||
+            condition.has_exceeded_timestamp(header.header().timestamp())
+        {
+            return Err(EthApiError::InvalidParams("invalid conditional parameters".to_string()))
+        }
+
+        if !condition.known_accounts.is_empty() {
+            let _permit = self
+                .inner
+                .validation_semaphore
+                .acquire()
+                .await
+                .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+
+            let state = self.provider().state_by_block_hash(header.hash())?;
+            known_accounts_match(&condition, &*state).map_err(|err| match err {
+                KnownAccountsError::Provider(err) => err.into(),
+                err => EthApiError::InvalidParams(err.to_string()),
+            })?;
+        }
+
+        if !tx.try_set_conditional(condition) {
+            return Err(EthApiError::Unsupported(
+                "the transaction pool doesn't support conditional transactions",
+            ))
+        }
+        let AddedTransactionOutcome { hash, .. } =
+            self.pool().add_transaction(TransactionOrigin::Private, tx).await?;
+
+        Ok(hash)
+    }
+}
+
+#[async_trait]
+impl<Pool, Provider> L2EthApiExtServer for EthTransactionConditional<Pool, Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `eth_sendRawTransactionConditional`
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        trace!(target: "rpc::eth", ?condition, "Serving eth_sendRawTransactionConditional");
+        Ok(self.add_conditional_transaction(bytes, condition).await?)
+    }
+}
+
+/// Container type for `EthTransactionConditional` internals
+#[derive(Debug)]
+struct EthTransactionConditionalInner<Pool, Provider> {
+    /// The transaction pool the transactions are added to.
+    pool: Pool,
+    /// The provider used to validate the conditionals against the latest state.
+    provider: Provider,
+    /// Limits the number of concurrent validations of known accounts.
+    validation_semaphore: Semaphore,
+}
+
+impl<Pool, Provider> std::fmt::Debug for EthTransactionConditional<Pool, Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthTransactionConditional").finish_non_exhaustive()
+    }
+}
+
+impl<Pool, Provider> Clone for EthTransactionConditional<Pool, Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
@@ -571,7 +571,8 @@ mod tests {
     use reth_network_api::noop::NoopNetwork;
     use reth_provider::{
         test_utils::{MockEthProvider, NoopProvider},
-        StageCheckpointReader,
+        AddressAppearancesProvider, ChangeSetReader, LogIndexProvider, StageCheckpointReader,
+        StorageChangeSetReader, TraceIndexProvider,
     };
     use reth_rpc_eth_api::{node::RpcNodeCoreAdapter, EthApiServer};
     use reth_storage_api::{BlockReader, BlockReaderIdExt, StateProviderFactory};
@@ -594,6 +595,11 @@ mod tests {
             + StateProviderFactory
             + CanonStateSubscriptions<Primitives = reth_ethereum_primitives::EthPrimitives>
             + StageCheckpointReader
+            + AddressAppearancesProvider
+            + LogIndexProvider
+            + TraceIndexProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
             + Unpin
             + Clone
             + 'static,
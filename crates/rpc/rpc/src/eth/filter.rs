@@ -26,13 +26,13 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, ReceiptProvider,
+    log_position_block, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
+    HeaderProvider, LogIndexProvider, ProviderBlock, ProviderReceipt, ReceiptProvider,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeSet, HashMap, VecDeque},
     fmt,
     iter::{Peekable, StepBy},
     ops::RangeInclusive,
@@ -131,8 +131,12 @@ where
     /// let filter = EthFilter::new(eth_api, Default::default(), TokioTaskExecutor::default().boxed());
     /// ```
     pub fn new(eth_api: Eth, config: EthFilterConfig, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_indexed_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+        } = config;
         let inner = EthFilterInner {
             eth_api,
             active_filters: ActiveFilters::new(),
@@ -140,7 +144,11 @@ where
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
             stale_filter_ttl,
-            query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
+            query_limits: QueryLimits {
+                max_blocks_per_filter,
+                max_indexed_blocks_per_filter,
+                max_logs_per_response,
+            },
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -560,8 +568,20 @@ where
             return Err(EthFilterError::InvalidBlockRangeParams)
         }
 
+        // Blocks covered by the log index are looked up instead of scanned, so they count towards
+        // a separate limit.
+        let indexed = self.indexed_log_range(&filter, from_block, to_block)?;
+        if let Some(indexed) = &indexed &&
+            let Some(max_indexed_blocks_per_filter) = limits
+                .max_indexed_blocks_per_filter
+                .filter(|limit| indexed.end() - indexed.start() > *limit)
+        {
+            return Err(EthFilterError::QueryExceedsMaxBlocks(max_indexed_blocks_per_filter))
+        }
+        let scanned_blocks = (to_block - from_block + 1) -
+            indexed.as_ref().map_or(0, |indexed| indexed.end() - indexed.start() + 1);
         if let Some(max_blocks_per_filter) =
-            limits.max_blocks_per_filter.filter(|limit| to_block - from_block > *limit)
+            limits.max_blocks_per_filter.filter(|limit| scanned_blocks.saturating_sub(1) > *limit)
         {
             return Err(EthFilterError::QueryExceedsMaxBlocks(max_blocks_per_filter))
         }
@@ -569,39 +589,108 @@ where
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
         self.task_spawner.spawn_blocking(Box::pin(async move {
-            let res =
-                this.get_logs_in_block_range_inner(&filter, from_block, to_block, limits).await;
+            let res = this
+                .get_logs_in_block_range_inner(&filter, from_block, to_block, indexed, limits)
+                .await;
             let _ = tx.send(res);
         }));
 
         rx.await.map_err(|_| EthFilterError::InternalError)?
     }
 
-    /// Returns all logs in the given _inclusive_ range that match the filter
+    /// Returns the part of the given range that is covered by the log index, if the filter can be
+    /// answered from it.
+    fn indexed_log_range(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Option<RangeInclusive<u64>>, EthFilterError> {
+        // Filters without address and topic constraints match every block with logs.
+        if filter.address.is_empty() && filter.topics.iter().all(|topics| topics.is_empty()) {
+            return Ok(None)
+        }
+
+        let Some(index_range) = self.provider().log_index_range()? else { return Ok(None) };
+        let range = from_block.max(*index_range.start())..=to_block.min(*index_range.end());
+        Ok((!range.is_empty()).then_some(range))
+    }
+
+    /// Returns the numbers of the blocks in the given indexed range that contain a log of any of
+    /// the filter addresses with, for each constrained topic position, any of its topics.
     ///
-    /// Note: This function uses a mix of blocking db operations for fetching indices and header
-    /// ranges and utilizes the rpc cache for optimistically fetching receipts and blocks.
-    /// This function is considered blocking and should thus be spawned on a blocking task.
+    /// The index doesn't record the position of topics in a log, so the logs of the returned
+    /// blocks still have to be matched against the filter.
     ///
-    /// Returns an error if:
-    ///  - underlying database error
-    async fn get_logs_in_block_range_inner(
-        self: Arc<Self>,
+    /// The range is looked up in chunks. The lookup of a multi block query fails as soon as the
+    /// candidate logs exceed the logs limit, so the candidates of a query can't exhaust memory.
+    fn indexed_log_blocks(
+        &self,
         filter: &Filter,
         from_block: u64,
         to_block: u64,
+        range: RangeInclusive<u64>,
         limits: QueryLimits,
-    ) -> Result<Vec<Log>, EthFilterError> {
-        let mut all_logs = Vec::new();
-        let mut matching_headers = Vec::new();
+    ) -> Result<BTreeSet<u64>, EthFilterError> {
+        let max_logs = limits.max_logs_per_response.filter(|_| from_block != to_block);
+        let mut blocks = BTreeSet::new();
+        let mut logs = 0;
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
+            for position in self.indexed_log_positions(filter, from..=to)? {
+                let block_number = log_position_block(position);
+                logs += 1;
+                if let Some(max_logs) = max_logs &&
+                    logs > max_logs
+                {
+                    return Err(EthFilterError::QueryExceedsMaxResults {
+                        max_logs,
+                        from_block,
+                        to_block: block_number.saturating_sub(1),
+                    })
+                }
+                blocks.insert(block_number);
+            }
+        }
+        Ok(blocks)
+    }
 
-        // get current chain tip to determine processing mode
-        let chain_tip = self.provider().best_block_number()?;
+    /// Returns the positions of the logs in the given indexed range that match any of the filter
+    /// addresses and, for each constrained topic position, any of its topics.
+    fn indexed_log_positions(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<BTreeSet<u64>, EthFilterError> {
+        let mut candidates = if filter.address.is_empty() {
+            None
+        } else {
+            let mut positions = BTreeSet::new();
+            for address in filter.address.iter() {
+                positions.extend(self.provider().address_log_positions(*address, range.clone())?);
+            }
+            Some(positions)
+        };
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut positions = BTreeSet::new();
+            for topic in topics.iter() {
+                positions.extend(self.provider().topic_log_positions(*topic, range.clone())?);
+            }
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&positions).copied().collect(),
+                None => positions,
+            });
+        }
+        Ok(candidates.unwrap_or_default())
+    }
 
-        // first collect all headers that match the bloom filter for cached mode decision
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
+    /// Appends the headers of the given range whose bloom matches the filter.
+    fn append_bloom_matching_headers(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+        matching_headers: &mut Vec<SealedHeader<<Eth::Provider as HeaderProvider>::Header>>,
+    ) -> Result<(), EthFilterError> {
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
             let headers = self.provider().headers_range(from..=to)?;
 
             let mut headers_iter = headers.into_iter().peekable();
@@ -627,6 +716,68 @@ where
                 matching_headers.push(SealedHeader::new(header, block_hash));
             }
         }
+        Ok(())
+    }
+
+    /// Returns all logs in the given _inclusive_ range that match the filter
+    ///
+    /// Note: This function uses a mix of blocking db operations for fetching indices and header
+    /// ranges and utilizes the rpc cache for optimistically fetching receipts and blocks.
+    /// This function is considered blocking and should thus be spawned on a blocking task.
+    ///
+    /// Candidate blocks in the `indexed` part of the range are looked up in the log index instead
+    /// of checking the bloom of every header.
+    ///
+    /// Returns an error if:
+    ///  - underlying database error
+    async fn get_logs_in_block_range_inner(
+        self: Arc<Self>,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+        indexed: Option<RangeInclusive<u64>>,
+        limits: QueryLimits,
+    ) -> Result<Vec<Log>, EthFilterError> {
+        let mut all_logs = Vec::new();
+        let mut matching_headers = Vec::new();
+
+        // get current chain tip to determine processing mode
+        let chain_tip = self.provider().best_block_number()?;
+
+        // first collect all headers that match the bloom filter or the log index for cached mode
+        // decision
+        match indexed {
+            Some(indexed) => {
+                if from_block < *indexed.start() {
+                    self.append_bloom_matching_headers(
+                        filter,
+                        from_block..=*indexed.start() - 1,
+                        &mut matching_headers,
+                    )?;
+                }
+                for block_number in
+                    self.indexed_log_blocks(filter, from_block, to_block, indexed.clone(), limits)?
+                {
+                    let header = self
+                        .provider()
+                        .sealed_header(block_number)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                    matching_headers.push(header);
+                }
+                if *indexed.end() < to_block {
+                    self.append_bloom_matching_headers(
+                        filter,
+                        *indexed.end() + 1..=to_block,
+                        &mut matching_headers,
+                    )?;
+                }
+            }
+            None => self.append_bloom_matching_headers(
+                filter,
+                from_block..=to_block,
+                &mut matching_headers,
+            )?,
+        }
 
         // initialize the appropriate range mode based on collected headers
         let mut range_mode = RangeMode::new(
@@ -5,12 +5,15 @@ use std::collections::HashMap;
 use crate::EthApi;
 use alloy_dyn_abi::TypedData;
 use alloy_eips::eip2718::Decodable2718;
+use alloy_consensus::SignableTransaction;
+use alloy_network::TxSigner;
 use alloy_primitives::{eip191_hash_message, Address, Signature, B256};
 use alloy_signer::SignerSync;
 use alloy_signer_local::PrivateKeySigner;
+use reth_revm::state_changes::IMPERSONATED_SIGNATURE;
 use reth_rpc_convert::{RpcConvert, RpcTypes, SignableTxRequest};
 use reth_rpc_eth_api::{
-    helpers::{signer::Result, AddDevSigners, EthSigner},
+    helpers::{signer::Result, AddDevSigners, EthSigner, ImpersonatedAccounts},
     FromEvmError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{EthApiError, SignError};
@@ -27,6 +30,10 @@ where
     fn with_dev_accounts(&self) {
         *self.inner.signers().write() = DevSigner::random_signers(20)
     }
+
+    fn with_impersonated_accounts(&self, accounts: ImpersonatedAccounts) {
+        self.inner.signers().write().push(Box::new(ImpersonatedSigner::new(accounts)))
+    }
 }
 
 /// Holds developer keys
@@ -101,6 +108,69 @@ impl<T: Decodable2718, TxReq: SignableTxRequest<T>> EthSigner<T, TxReq> for DevS
     }
 }
 
+/// Signs transactions of impersonated accounts with the [`IMPERSONATED_SIGNATURE`].
+///
+/// The transactions are submitted to the pool with the impersonated sender, the signature doesn't
+/// recover to it. Messages and typed data can't be signed.
+#[derive(Debug, Clone)]
+pub struct ImpersonatedSigner {
+    accounts: ImpersonatedAccounts,
+}
+
+impl ImpersonatedSigner {
+    /// Creates a signer for the given impersonated accounts.
+    pub const fn new(accounts: ImpersonatedAccounts) -> Self {
+        Self { accounts }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: Decodable2718, TxReq: SignableTxRequest<T>> EthSigner<T, TxReq> for ImpersonatedSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.accounts.accounts()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.accounts.is_impersonated(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    async fn sign_transaction(&self, request: TxReq, address: &Address) -> Result<T> {
+        if !self.accounts.is_impersonated(address) {
+            return Err(SignError::NoAccount)
+        }
+
+        request
+            .try_build_and_sign(ImpersonatedTxSigner(*address))
+            .await
+            .map_err(|_| SignError::InvalidTransactionRequest)
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
+
+/// Transaction signer of an impersonated account.
+struct ImpersonatedTxSigner(Address);
+
+#[async_trait::async_trait]
+impl TxSigner<Signature> for ImpersonatedTxSigner {
+    fn address(&self) -> Address {
+        self.0
+    }
+
+    async fn sign_transaction(
+        &self,
+        _tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy_signer::Result<Signature> {
+        Ok(IMPERSONATED_SIGNATURE)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
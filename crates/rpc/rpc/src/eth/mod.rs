@@ -2,19 +2,23 @@
 
 pub mod builder;
 pub mod bundle;
+pub mod conditional;
 pub mod core;
 pub mod filter;
 pub mod helpers;
 pub mod pubsub;
+pub mod send_bundle;
 pub mod sim_bundle;
 
 /// Implementation of `eth` namespace API.
 pub use builder::EthApiBuilder;
 pub use bundle::EthBundle;
+pub use conditional::EthTransactionConditional;
 pub use core::{EthApi, EthApiFor};
 pub use filter::EthFilter;
 pub use pubsub::EthPubSub;
+pub use send_bundle::EthBundleSubmitter;
 
-pub use helpers::{signer::DevSigner, sync_listener::SyncListener};
+pub use helpers::{signer::{DevSigner, ImpersonatedSigner}, sync_listener::SyncListener};
 
 pub use reth_rpc_eth_api::{EthApiServer, EthApiTypes, FullEthApiServer, RpcNodeCore};
@@ -17,7 +17,7 @@ use reth_primitives_traits::NodePrimitives;
 use reth_rpc_eth_api::{
     pubsub::EthPubSubApiServer, EthApiTypes, RpcConvert, RpcNodeCore, RpcTransaction,
 };
-use reth_rpc_eth_types::logs_utils;
+use reth_rpc_eth_types::{logs_utils, PendingTransactionsFilter, SubscriptionParams};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -29,6 +29,10 @@ use tokio_stream::{
 };
 use tracing::error;
 
+/// The maximum number of pending transactions that are filtered at once for a filtered
+/// `newPendingTransactions` subscription.
+const PENDING_TRANSACTIONS_BATCH_SIZE: usize = 256;
+
 /// `Eth` pubsub RPC implementation.
 ///
 /// This handles `eth_subscribe` RPC calls.
@@ -84,6 +88,15 @@ where
         self.inner.full_pending_transaction_stream()
     }
 
+    /// Returns a stream that yields all transactions emitted by the txpool that match the given
+    /// filter.
+    pub fn filtered_pending_transaction_stream(
+        &self,
+        filter: PendingTransactionsFilter,
+    ) -> impl Stream<Item = NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>> {
+        self.inner.filtered_pending_transaction_stream(filter)
+    }
+
     /// Returns a stream that yields all new RPC blocks.
     pub fn new_headers_stream(&self) -> impl Stream<Item = Header<N::BlockHeader>> {
         self.inner.new_headers_stream()
@@ -99,7 +112,7 @@ where
         &self,
         accepted_sink: SubscriptionSink,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> Result<(), ErrorObject<'static>> {
         match kind {
             SubscriptionKind::NewHeads => {
@@ -108,8 +121,14 @@ where
             SubscriptionKind::Logs => {
                 // if no params are provided, used default filter params
                 let filter = match params {
-                    Some(Params::Logs(filter)) => *filter,
-                    Some(Params::Bool(_)) => {
+                    Some(SubscriptionParams::Params(Params::Logs(filter))) => *filter,
+                    Some(SubscriptionParams::Params(Params::Bool(_))) => {
+                        return Err(invalid_params_rpc_err("Invalid params for logs"))
+                    }
+                    // an empty object is also a valid pending transactions filter
+                    Some(SubscriptionParams::PendingTransactions(filter))
+                        if filter != PendingTransactionsFilter::default() =>
+                    {
                         return Err(invalid_params_rpc_err("Invalid params for logs"))
                     }
                     _ => Default::default(),
@@ -117,42 +136,53 @@ where
                 pipe_from_stream(accepted_sink, self.log_stream(filter)).await
             }
             SubscriptionKind::NewPendingTransactions => {
-                if let Some(params) = params {
-                    match params {
-                        Params::Bool(true) => {
-                            // full transaction objects requested
-                            let stream = self.full_pending_transaction_stream().filter_map(|tx| {
-                                let tx_value = match self
-                                    .inner
-                                    .eth_api
-                                    .tx_resp_builder()
-                                    .fill_pending(tx.transaction.to_consensus())
-                                {
-                                    Ok(tx) => Some(tx),
-                                    Err(err) => {
-                                        error!(target = "rpc",
-                                            %err,
-                                            "Failed to fill transaction with block context"
-                                        );
-                                        None
-                                    }
-                                };
-                                std::future::ready(tx_value)
-                            });
-                            return pipe_from_stream(accepted_sink, stream).await
-                        }
-                        Params::Bool(false) | Params::None => {
-                            // only hashes requested
-                        }
-                        Params::Logs(_) => {
-                            return Err(invalid_params_rpc_err(
-                                "Invalid params for newPendingTransactions",
-                            ))
-                        }
+                let filter = match params {
+                    Some(SubscriptionParams::PendingTransactions(filter)) => filter,
+                    Some(SubscriptionParams::Params(Params::Bool(full_transactions))) => {
+                        PendingTransactionsFilter { full_transactions, ..Default::default() }
+                    }
+                    Some(SubscriptionParams::Params(Params::Logs(_))) => {
+                        return Err(invalid_params_rpc_err(
+                            "Invalid params for newPendingTransactions",
+                        ))
                     }
+                    Some(SubscriptionParams::Params(Params::None)) | None => Default::default(),
+                };
+
+                if filter.full_transactions {
+                    // full transaction objects requested
+                    let stream =
+                        self.filtered_pending_transaction_stream(filter).filter_map(|tx| {
+                            let tx_value = match self
+                                .inner
+                                .eth_api
+                                .tx_resp_builder()
+                                .fill_pending(tx.transaction.to_consensus())
+                            {
+                                Ok(tx) => Some(tx),
+                                Err(err) => {
+                                    error!(target = "rpc",
+                                        %err,
+                                        "Failed to fill transaction with block context"
+                                    );
+                                    None
+                                }
+                            };
+                            std::future::ready(tx_value)
+                        });
+                    return pipe_from_stream(accepted_sink, stream).await
+                }
+
+                if filter.is_match_all() {
+                    // only hashes of all transactions requested
+                    return pipe_from_stream(accepted_sink, self.pending_transaction_hashes_stream())
+                        .await
                 }
 
-                pipe_from_stream(accepted_sink, self.pending_transaction_hashes_stream()).await
+                let stream = self
+                    .filtered_pending_transaction_stream(filter)
+                    .map(|tx| *tx.transaction.hash());
+                pipe_from_stream(accepted_sink, stream).await
             }
             SubscriptionKind::Syncing => {
                 // get new block subscription
@@ -221,7 +251,7 @@ where
         &self,
         pending: PendingSubscriptionSink,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.clone();
@@ -344,6 +374,27 @@ where
     ) -> impl Stream<Item = NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>> {
         self.eth_api.pool().new_pending_pool_transactions_listener()
     }
+
+    /// Returns a stream that yields all transactions emitted by the txpool that match the given
+    /// filter.
+    fn filtered_pending_transaction_stream(
+        &self,
+        filter: PendingTransactionsFilter,
+    ) -> impl Stream<Item = NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>> {
+        let pool = self.eth_api.pool().clone();
+        // transactions are filtered in batches of the ones that are ready, so that the base fee of
+        // the pending block is read once per batch
+        self.full_pending_transaction_stream()
+            .ready_chunks(PENDING_TRANSACTIONS_BATCH_SIZE)
+            .flat_map(move |txs| {
+                let base_fee = pool.block_info().pending_basefee;
+                let txs = txs
+                    .into_iter()
+                    .filter(|tx| filter.matches(&tx.transaction.transaction, base_fee))
+                    .collect::<Vec<_>>();
+                futures::stream::iter(txs)
+            })
+    }
 }
 
 impl<N: NodePrimitives, Eth> EthPubSubInner<Eth>
@@ -387,3 +438,51 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use jsonrpsee::rpc_params;
+    use reth_chainspec::ChainSpecProvider;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::test_utils::NoopProvider;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        PoolTransaction, TransactionOrigin,
+    };
+
+    #[tokio::test]
+    async fn subscribe_filtered_pending_transactions() {
+        let provider = NoopProvider::default();
+        let pool = testing_pool();
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let eth_api =
+            EthApi::builder(provider, pool.clone(), NoopNetwork::default(), evm_config).build();
+        let module = EthPubSub::new(eth_api).into_rpc();
+
+        let low_tip = MockTransaction::eip1559().with_priority_fee(0).with_max_fee(100);
+        let matching = low_tip.next().with_priority_fee(2);
+        let filter = PendingTransactionsFilter {
+            from: vec![low_tip.sender()],
+            min_tip: Some(1),
+            ..Default::default()
+        };
+        let mut subscription = module
+            .subscribe_unbounded("eth_subscribe", rpc_params!["newPendingTransactions", filter])
+            .await
+            .unwrap();
+        // let the spawned subscription task register its pool listener
+        tokio::task::yield_now().await;
+
+        // neither the transaction of another sender nor the one with a tip that is too low match
+        let other = MockTransaction::eip1559().with_priority_fee(2).with_max_fee(100);
+        pool.add_transaction(TransactionOrigin::External, other).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, low_tip).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, matching.clone()).await.unwrap();
+
+        let (hash, _) = subscription.next::<TxHash>().await.unwrap().unwrap();
+        assert_eq!(hash, *matching.hash());
+    }
+}
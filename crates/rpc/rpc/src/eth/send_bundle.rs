@@ -0,0 +1,207 @@
+//! `Eth` bundle submission to the [`BundlePool`] of the local payload builder.
+
+use crate::eth::sim_bundle::parse_and_flatten_bundle;
+use alloy_consensus::{transaction::TxHashRef, Typed2718};
+use alloy_rpc_types_mev::{
+    EthBundleHash, EthCancelBundle, EthSendBundle, MevSendBundle, RefundConfig,
+};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::MevSendBundleApiServer;
+use reth_rpc_eth_api::{EthSendBundleApiServer, RpcNodeCore};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_storage_api::BlockNumReader;
+use reth_transaction_pool::{
+    bundle::{Bundle, BundleRefund, BundleTransaction},
+    BundlePool, PoolConsensusTx, PoolPooledTx, PoolTransaction, TransactionPool,
+};
+use std::sync::Arc;
+use tracing::trace;
+
+/// `Eth` bundle submission implementation.
+///
+/// Bundles submitted with `eth_sendBundle` and `mev_sendBundle` are added to the [`BundlePool`],
+/// from which they are included in payloads built by the node.
+///
+/// Requested refunds are recorded as a share of the value the bundle pays to the fee recipient,
+/// which the payload builder pays out with a transfer right after the bundle. The refund of an
+/// `eth_sendBundle` request is computed from the value of the entire bundle, `refundTxHashes`
+/// are not taken into account.
+pub struct EthBundleSubmitter<Eth: RpcNodeCore> {
+    /// All nested fields bundled together.
+    inner: Arc<EthBundleSubmitterInner<Eth>>,
+}
+
+impl<Eth: RpcNodeCore> EthBundleSubmitter<Eth> {
+    /// Create a new `EthBundleSubmitter` instance.
+    pub fn new(eth_api: Eth, bundle_pool: BundlePool<PoolConsensusTx<Eth::Pool>>) -> Self {
+        Self { inner: Arc::new(EthBundleSubmitterInner { eth_api, bundle_pool }) }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
+    /// Access the pool the bundles are added to.
+    pub fn bundle_pool(&self) -> &BundlePool<PoolConsensusTx<Eth::Pool>> {
+        &self.inner.bundle_pool
+    }
+
+    /// Adds the bundle to the pool, if it can still be included in a future block.
+    fn add_bundle(
+        &self,
+        bundle: Bundle<PoolConsensusTx<Eth::Pool>>,
+    ) -> Result<EthBundleHash, EthApiError> {
+        let best_block_number = self.eth_api().provider().best_block_number()?;
+        if bundle.max_block_number <= best_block_number {
+            return Err(EthApiError::InvalidParams(format!(
+                "bundle targets past block {}",
+                bundle.max_block_number
+            )))
+        }
+
+        let bundle_hash = self
+            .bundle_pool()
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+}
+
+/// Decodes a raw bundle transaction, rejecting blob transactions whose sidecars can't be part of
+/// a bundle.
+fn recover_bundle_transaction<Eth: RpcNodeCore>(
+    tx: &[u8],
+) -> Result<BundleTransaction<PoolConsensusTx<Eth::Pool>>, EthApiError> {
+    let tx = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(tx)?
+        .map(<Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus);
+    if tx.is_eip4844() {
+        return Err(EthApiError::InvalidParams("blob transactions can't be bundled".to_string()))
+    }
+    Ok(BundleTransaction { tx, can_revert: false, can_drop: false })
+}
+
+#[async_trait]
+impl<Eth: RpcNodeCore> EthSendBundleApiServer for EthBundleSubmitter<Eth> {
+    /// Handler for `eth_sendBundle`
+    async fn send_bundle(&self, request: EthSendBundle) -> RpcResult<EthBundleHash> {
+        trace!(target: "rpc::eth", ?request, "Serving eth_sendBundle");
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+            dropping_tx_hashes,
+            refund_percent,
+            refund_recipient,
+            ..
+        } = request;
+
+        let transactions = txs
+            .iter()
+            .map(|tx| {
+                let mut tx = recover_bundle_transaction::<Eth>(tx)?;
+                tx.can_revert = reverting_tx_hashes.contains(tx.tx.tx_hash());
+                tx.can_drop = dropping_tx_hashes.contains(tx.tx.tx_hash());
+                Ok(tx)
+            })
+            .collect::<Result<Vec<_>, EthApiError>>()?;
+
+        // the refund goes to the sender of the first transaction unless a recipient is given
+        let refunds = refund_percent
+            .zip(refund_recipient.or_else(|| transactions.first().map(|tx| tx.tx.signer())))
+            .map(|(percent, recipient)| BundleRefund { recipient, percent: percent.into() })
+            .into_iter()
+            .collect();
+
+        let bundle = Bundle {
+            transactions,
+            block_number,
+            max_block_number: block_number,
+            min_timestamp,
+            max_timestamp,
+            replacement_uuid,
+            refunds,
+        };
+        Ok(self.add_bundle(bundle)?)
+    }
+
+    /// Handler for `eth_cancelBundle`
+    async fn cancel_bundle(&self, request: EthCancelBundle) -> RpcResult<()> {
+        trace!(target: "rpc::eth", ?request, "Serving eth_cancelBundle");
+        self.bundle_pool().cancel(&request.replacement_uuid);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Eth: RpcNodeCore> MevSendBundleApiServer for EthBundleSubmitter<Eth> {
+    /// Handler for `mev_sendBundle`
+    async fn send_bundle(&self, request: MevSendBundle) -> RpcResult<EthBundleHash> {
+        trace!(target: "rpc::eth", ?request, "Serving mev_sendBundle");
+        let items = parse_and_flatten_bundle::<Eth::Pool>(&request)?;
+
+        let mut transactions = Vec::with_capacity(items.len());
+        let mut refunds = Vec::new();
+        for item in items {
+            if item.tx.is_eip4844() {
+                return Err(EthApiError::InvalidParams(
+                    "blob transactions can't be bundled".to_string(),
+                )
+                .into())
+            }
+            if let Some(refund_percent) = item.refund_percent {
+                // the refund goes to the signer of the transaction unless configured otherwise
+                let configs = item.refund_configs.unwrap_or_else(|| {
+                    vec![RefundConfig { address: item.tx.signer(), percent: 100 }]
+                });
+                refunds.extend(configs.into_iter().map(|config| BundleRefund {
+                    recipient: config.address,
+                    percent: refund_percent * config.percent / 100,
+                }));
+            }
+
+            transactions.push(BundleTransaction {
+                tx: item.tx,
+                can_revert: item.can_revert,
+                can_drop: false,
+            });
+        }
+
+        let block_number = request.inclusion.block_number();
+        let bundle = Bundle {
+            transactions,
+            block_number,
+            max_block_number: request.inclusion.max_block_number().unwrap_or(block_number),
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+            refunds,
+        };
+        Ok(self.add_bundle(bundle)?)
+    }
+}
+
+/// Container type for `EthBundleSubmitter` internals
+#[derive(Debug)]
+struct EthBundleSubmitterInner<Eth: RpcNodeCore> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// The pool the bundles are added to.
+    bundle_pool: BundlePool<PoolConsensusTx<Eth::Pool>>,
+}
+
+impl<Eth: RpcNodeCore> std::fmt::Debug for EthBundleSubmitter<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthBundleSubmitter").finish_non_exhaustive()
+    }
+}
+
+impl<Eth: RpcNodeCore> Clone for EthBundleSubmitter<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
@@ -19,9 +19,8 @@ use reth_rpc_eth_api::{
     FromEthApiError, FromEvmError,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
-use reth_storage_api::ProviderTx;
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{PoolConsensusTx, PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::{context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef};
 use std::{sync::Arc, time::Duration};
 use tracing::trace;
@@ -78,145 +77,142 @@ impl<Eth> EthSimBundle<Eth> {
     }
 }
 
-impl<Eth> EthSimBundle<Eth>
-where
-    Eth: EthTransactions + LoadBlock + Call + 'static,
-{
-    /// Flattens a potentially nested bundle into a list of individual transactions in a
-    /// `FlattenedBundleItem` with their associated metadata. This handles recursive bundle
-    /// processing up to `MAX_NESTED_BUNDLE_DEPTH` and `MAX_BUNDLE_BODY_SIZE`, preserving
-    /// inclusion, validity and privacy settings from parent bundles.
-    fn parse_and_flatten_bundle(
-        &self,
-        request: &MevSendBundle,
-    ) -> Result<Vec<FlattenedBundleItem<ProviderTx<Eth::Provider>>>, EthApiError> {
-        let mut items = Vec::new();
-
-        // Stack for processing bundles
-        let mut stack = Vec::new();
-
-        // Start with initial bundle, index 0, and depth 1
-        stack.push((request, 0, 1));
-
-        while let Some((current_bundle, mut idx, depth)) = stack.pop() {
-            // Check max depth
-            if depth > MAX_NESTED_BUNDLE_DEPTH {
-                return Err(EthApiError::InvalidParams(EthSimBundleError::MaxDepth.to_string()));
-            }
+/// Flattens a potentially nested bundle into a list of individual transactions in a
+/// `FlattenedBundleItem` with their associated metadata. This handles recursive bundle
+/// processing up to `MAX_NESTED_BUNDLE_DEPTH` and `MAX_BUNDLE_BODY_SIZE`, preserving
+/// inclusion, validity and privacy settings from parent bundles.
+pub(crate) fn parse_and_flatten_bundle<Pool: TransactionPool>(
+    request: &MevSendBundle,
+) -> Result<Vec<FlattenedBundleItem<PoolConsensusTx<Pool>>>, EthApiError> {
+    let mut items = Vec::new();
+
+    // Stack for processing bundles
+    let mut stack = Vec::new();
+
+    // Start with initial bundle, index 0, and depth 1
+    stack.push((request, 0, 1));
+
+    while let Some((current_bundle, mut idx, depth)) = stack.pop() {
+        // Check max depth
+        if depth > MAX_NESTED_BUNDLE_DEPTH {
+            return Err(EthApiError::InvalidParams(EthSimBundleError::MaxDepth.to_string()));
+        }
 
-            // Determine inclusion, validity, and privacy
-            let inclusion = &current_bundle.inclusion;
-            let validity = &current_bundle.validity;
-            let privacy = &current_bundle.privacy;
+        // Determine inclusion, validity, and privacy
+        let inclusion = &current_bundle.inclusion;
+        let validity = &current_bundle.validity;
+        let privacy = &current_bundle.privacy;
 
-            // Validate inclusion parameters
-            let block_number = inclusion.block_number();
-            let max_block_number = inclusion.max_block_number().unwrap_or(block_number);
+        // Validate inclusion parameters
+        let block_number = inclusion.block_number();
+        let max_block_number = inclusion.max_block_number().unwrap_or(block_number);
 
-            if max_block_number < block_number || block_number == 0 {
-                return Err(EthApiError::InvalidParams(
-                    EthSimBundleError::InvalidInclusion.to_string(),
-                ));
-            }
+        if max_block_number < block_number || block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthSimBundleError::InvalidInclusion.to_string(),
+            ));
+        }
 
-            // Validate bundle body size
-            if current_bundle.bundle_body.len() > MAX_BUNDLE_BODY_SIZE {
-                return Err(EthApiError::InvalidParams(
-                    EthSimBundleError::BundleTooLarge.to_string(),
-                ));
-            }
+        // Validate bundle body size
+        if current_bundle.bundle_body.len() > MAX_BUNDLE_BODY_SIZE {
+            return Err(EthApiError::InvalidParams(
+                EthSimBundleError::BundleTooLarge.to_string(),
+            ));
+        }
 
-            // Validate validity and refund config
-            if let Some(validity) = &current_bundle.validity {
-                // Validate refund entries
-                if let Some(refunds) = &validity.refund {
-                    let mut total_percent = 0;
-                    for refund in refunds {
-                        if refund.body_idx as usize >= current_bundle.bundle_body.len() {
-                            return Err(EthApiError::InvalidParams(
-                                EthSimBundleError::InvalidValidity.to_string(),
-                            ));
-                        }
-                        if 100 - total_percent < refund.percent {
-                            return Err(EthApiError::InvalidParams(
-                                EthSimBundleError::InvalidValidity.to_string(),
-                            ));
-                        }
-                        total_percent += refund.percent;
+        // Validate validity and refund config
+        if let Some(validity) = &current_bundle.validity {
+            // Validate refund entries
+            if let Some(refunds) = &validity.refund {
+                let mut total_percent = 0;
+                for refund in refunds {
+                    if refund.body_idx as usize >= current_bundle.bundle_body.len() {
+                        return Err(EthApiError::InvalidParams(
+                            EthSimBundleError::InvalidValidity.to_string(),
+                        ));
                     }
-                }
-
-                // Validate refund configs
-                if let Some(refund_configs) = &validity.refund_config {
-                    let mut total_percent = 0;
-                    for refund_config in refund_configs {
-                        if 100 - total_percent < refund_config.percent {
-                            return Err(EthApiError::InvalidParams(
-                                EthSimBundleError::InvalidValidity.to_string(),
-                            ));
-                        }
-                        total_percent += refund_config.percent;
+                    if 100 - total_percent < refund.percent {
+                        return Err(EthApiError::InvalidParams(
+                            EthSimBundleError::InvalidValidity.to_string(),
+                        ));
                     }
+                    total_percent += refund.percent;
                 }
             }
 
-            let body = &current_bundle.bundle_body;
-
-            // Process items in the current bundle
-            while idx < body.len() {
-                match &body[idx] {
-                    BundleItem::Tx { tx, can_revert } => {
-                        let tx = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(tx)?;
-                        let tx = tx.map(
-                            <Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus,
-                        );
-
-                        let refund_percent =
-                            validity.as_ref().and_then(|v| v.refund.as_ref()).and_then(|refunds| {
-                                refunds.iter().find_map(|refund| {
-                                    (refund.body_idx as usize == idx).then_some(refund.percent)
-                                })
-                            });
-                        let refund_configs =
-                            validity.as_ref().and_then(|v| v.refund_config.clone());
-
-                        // Create FlattenedBundleItem with current inclusion, validity, and privacy
-                        let flattened_item = FlattenedBundleItem {
-                            tx,
-                            can_revert: *can_revert,
-                            inclusion: inclusion.clone(),
-                            validity: validity.clone(),
-                            privacy: privacy.clone(),
-                            refund_percent,
-                            refund_configs,
-                        };
-
-                        // Add to items
-                        items.push(flattened_item);
-
-                        idx += 1;
-                    }
-                    BundleItem::Bundle { bundle } => {
-                        // Push the current bundle and next index onto the stack to resume later
-                        stack.push((current_bundle, idx + 1, depth));
-
-                        // process the nested bundle next
-                        stack.push((bundle, 0, depth + 1));
-                        break;
-                    }
-                    BundleItem::Hash { hash: _ } => {
-                        // Hash-only items are not allowed
+            // Validate refund configs
+            if let Some(refund_configs) = &validity.refund_config {
+                let mut total_percent = 0;
+                for refund_config in refund_configs {
+                    if 100 - total_percent < refund_config.percent {
                         return Err(EthApiError::InvalidParams(
-                            EthSimBundleError::InvalidBundle.to_string(),
+                            EthSimBundleError::InvalidValidity.to_string(),
                         ));
                     }
+                    total_percent += refund_config.percent;
                 }
             }
         }
 
-        Ok(items)
+        let body = &current_bundle.bundle_body;
+
+        // Process items in the current bundle
+        while idx < body.len() {
+            match &body[idx] {
+                BundleItem::Tx { tx, can_revert } => {
+                    let tx = recover_raw_transaction::<PoolPooledTx<Pool>>(tx)?;
+                    let tx = tx.map(Pool::Transaction::pooled_into_consensus);
+
+                    let refund_percent =
+                        validity.as_ref().and_then(|v| v.refund.as_ref()).and_then(|refunds| {
+                            refunds.iter().find_map(|refund| {
+                                (refund.body_idx as usize == idx).then_some(refund.percent)
+                            })
+                        });
+                    let refund_configs =
+                        validity.as_ref().and_then(|v| v.refund_config.clone());
+
+                    // Create FlattenedBundleItem with current inclusion, validity, and privacy
+                    let flattened_item = FlattenedBundleItem {
+                        tx,
+                        can_revert: *can_revert,
+                        inclusion: inclusion.clone(),
+                        validity: validity.clone(),
+                        privacy: privacy.clone(),
+                        refund_percent,
+                        refund_configs,
+                    };
+
+                    // Add to items
+                    items.push(flattened_item);
+
+                    idx += 1;
+                }
+                BundleItem::Bundle { bundle } => {
+                    // Push the current bundle and next index onto the stack to resume later
+                    stack.push((current_bundle, idx + 1, depth));
+
+                    // process the nested bundle next
+                    stack.push((bundle, 0, depth + 1));
+                    break;
+                }
+                BundleItem::Hash { hash: _ } => {
+                    // Hash-only items are not allowed
+                    return Err(EthApiError::InvalidParams(
+                        EthSimBundleError::InvalidBundle.to_string(),
+                    ));
+                }
+            }
+        }
     }
 
+    Ok(items)
+}
+
+impl<Eth> EthSimBundle<Eth>
+where
+    Eth: EthTransactions + LoadBlock + Call + 'static,
+{
     async fn sim_bundle_inner(
         &self,
         request: MevSendBundle,
@@ -227,7 +223,7 @@ where
 
         // Parse and validate bundle
         // Also, flatten the bundle here so that its easier to process
-        let flattened_bundle = self.parse_and_flatten_bundle(&request)?;
+        let flattened_bundle = parse_and_flatten_bundle::<Eth::Pool>(&request)?;
 
         let block_id = parent_block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
         let (mut evm_env, current_block_id) = self.eth_api().evm_env_at(block_id).await?;
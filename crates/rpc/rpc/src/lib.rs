@@ -34,6 +34,8 @@ use tower as _;
 
 mod admin;
 mod aliases;
+mod anvil;
+mod db_inspector;
 mod debug;
 mod engine;
 pub mod eth;
@@ -49,13 +51,18 @@ mod web3;
 
 pub use admin::AdminApi;
 pub use aliases::*;
-pub use debug::DebugApi;
+pub use anvil::AnvilApi;
+pub use db_inspector::{DbInspector, MDBX_STATS_PROPERTY, STATIC_FILE_RANGES_PROPERTY};
+pub use debug::{DebugApi, DebugApiConfig, DebugApiHandles};
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use eth::{
+    helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthBundleSubmitter, EthFilter,
+    EthPubSub, EthTransactionConditional,
+};
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethApi, RethFeeApi};
 pub use reth_rpc_convert::RpcTypes;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
@@ -1,8 +1,9 @@
-use alloy_consensus::{BlockHeader, Typed2718};
+use alloy_consensus::{BlockHeader, TxReceipt, Typed2718};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, Log, TxHash, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
+use std::collections::BTreeMap;
 use alloy_rpc_types_trace::{
     otterscan::{
         BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
@@ -12,6 +13,7 @@ use alloy_rpc_types_trace::{
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_errors::ProviderError;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
@@ -20,6 +22,10 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_stages_types::StageId;
+use reth_storage_api::{
+    AddressAppearancesProvider, ReceiptProvider, StageCheckpointReader, TraceIndexProvider,
+};
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
@@ -63,6 +69,210 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTxReq<Eth::NetworkTypes>,
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + EthTransactions
+        + TraceExt
+        + 'static,
+{
+    /// Searches the transactions the address appears in using the address appearance index.
+    ///
+    /// Whole blocks are returned until at least `page_size` transactions are collected, in
+    /// descending order. Searching backwards from block `0` starts at the tip.
+    ///
+    /// If the trace index is enabled, the blocks in which the address is touched by internal calls
+    /// are searched as well.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        page_size: usize,
+        before: bool,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let provider = self.eth.provider();
+        let Some(checkpoint) = provider
+            .get_stage_checkpoint(StageId::IndexAddressAppearances)
+            .map_err(EthApiError::from)?
+        else {
+            return Err(internal_rpc_err("address appearance index is not enabled"))
+        };
+        let index_logs = provider
+            .get_stage_checkpoint_progress(StageId::IndexAddressAppearances)
+            .map_err(EthApiError::from)?
+            .is_some_and(|progress| progress.first() == Some(&1));
+        let trace_range = provider.trace_index_range().map_err(EthApiError::from)?;
+
+        // Returns the next blocks the address appears in, and whether it was found by the trace
+        // index, in search order.
+        let next_blocks = |block: BlockNumber, limit| {
+            let mut blocks = BTreeMap::new();
+            let appearances = if before {
+                provider.address_appearances_before(address, block, limit)?
+            } else {
+                provider.address_appearances_after(address, block, limit)?
+            };
+            blocks.extend(appearances.into_iter().map(|number| (number, false)));
+
+            if let Some(range) = &trace_range {
+                // blocks outside of the index range may still be indexed if their state history
+                // was pruned afterwards
+                let traced = if before {
+                    let block = block.min(range.end().saturating_add(1));
+                    provider
+                        .address_trace_blocks_before(address, block, limit)?
+                        .into_iter()
+                        .take_while(|number| number >= range.start())
+                        .collect::<Vec<_>>()
+                } else {
+                    let block = block.max(range.start().saturating_sub(1));
+                    provider
+                        .address_trace_blocks_after(address, block, limit)?
+                        .into_iter()
+                        .take_while(|number| number <= range.end())
+                        .collect()
+                };
+                blocks.extend(traced.into_iter().map(|number| (number, true)));
+            }
+
+            Ok::<_, ProviderError>(if before {
+                blocks.into_iter().rev().take(limit).collect::<Vec<_>>()
+            } else {
+                blocks.into_iter().take(limit).collect()
+            })
+        };
+
+        let mut block =
+            if before && block_number == 0 { checkpoint.block_number + 1 } else { block_number };
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let mut has_more = true;
+        'search: while txs.len() < page_size {
+            let blocks = next_blocks(block, page_size).map_err(EthApiError::from)?;
+            if blocks.is_empty() {
+                has_more = false;
+                break
+            }
+
+            for (number, traced) in blocks {
+                block = number;
+                let (block_txs, block_receipts) = self
+                    .address_transactions_in_block(address, number, index_logs, traced)
+                    .await?;
+                if before {
+                    txs.extend(block_txs.into_iter().rev());
+                    receipts.extend(block_receipts.into_iter().rev());
+                } else {
+                    txs.extend(block_txs);
+                    receipts.extend(block_receipts);
+                }
+                if txs.len() >= page_size {
+                    break 'search
+                }
+            }
+        }
+        if has_more {
+            has_more = !next_blocks(block, 1).map_err(EthApiError::from)?.is_empty();
+        }
+
+        if before {
+            Ok(TransactionsWithReceipts {
+                txs,
+                receipts,
+                first_page: block_number == 0,
+                last_page: !has_more,
+            })
+        } else {
+            txs.reverse();
+            receipts.reverse();
+            Ok(TransactionsWithReceipts {
+                txs,
+                receipts,
+                first_page: !has_more,
+                last_page: block_number == 0,
+            })
+        }
+    }
+
+    /// Returns the transactions of the block the address is involved in, in ascending order.
+    ///
+    /// If `traced` is set, the transactions whose internal calls touch the address are included
+    /// as well.
+    async fn address_transactions_in_block(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        index_logs: bool,
+        traced: bool,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>)> {
+        let block_id = BlockId::number(block_number);
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+        let logs = if index_logs {
+            self.eth
+                .provider()
+                .receipts_by_block(block_number.into())
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::ReceiptsNotFound(block_id))?
+                .iter()
+                .map(|receipt| receipt.logs().iter().any(|log| log_involves(log, address)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let calls = if traced {
+            self.eth
+                .trace_block_with(
+                    block_number.into(),
+                    None,
+                    TracingInspectorConfig::default_parity(),
+                    move |_tx_info, mut ctx| {
+                        Ok(ctx.take_inspector().into_traces().nodes().iter().any(|node| {
+                            node.trace.caller == address ||
+                                node.trace.address == address ||
+                                node.trace.selfdestruct_refund_target == Some(address)
+                        }))
+                    },
+                )
+                .await
+                .map_err(Into::into)?
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let timestamp = Some(block.header.timestamp());
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .enumerate()
+            .filter(|(index, (_, receipt))| {
+                receipt.from() == address ||
+                    receipt.to() == Some(address) ||
+                    receipt.contract_address() == Some(address) ||
+                    logs.get(*index).copied().unwrap_or_default() ||
+                    calls.get(*index).copied().unwrap_or_default()
+            })
+            .map(|(_, (tx, receipt))| {
+                let receipt = ots_receipt(&receipt, tx.ty(), timestamp);
+                (tx, receipt)
+            })
+            .unzip())
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -247,32 +457,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(&receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -284,21 +469,33 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        self.search_transactions(
+            address,
+            block_number.into_inner().as_number().unwrap_or_default(),
+            page_size,
+            true,
+        )
+        .await
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        self.search_transactions(
+            address,
+            block_number.into_inner().as_number().unwrap_or_default(),
+            page_size,
+            false,
+        )
+        .await
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -382,3 +579,42 @@ where
         Ok(found)
     }
 }
+
+/// Converts an RPC receipt into an [`OtsTransactionReceipt`] without logs.
+fn ots_receipt<R: ReceiptResponse>(
+    receipt: &R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
+/// Returns whether the log was emitted by the address or has it as a topic, the same way the
+/// address appearance index records logs.
+fn log_involves(log: &Log, address: Address) -> bool {
+    log.address == address ||
+        log.topics().iter().any(|topic| topic[12..] == address[..] && topic[..12] == [0u8; 12])
+}
@@ -9,8 +9,9 @@ use jsonrpsee_types::ErrorObject;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
 use reth_errors::RethResult;
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
-use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_api::{RethApiServer, RethFeeApiServer};
+use reth_rpc_eth_api::helpers::LoadFee;
+use reth_rpc_eth_types::{EthApiError, EthResult, FeeEstimate};
 use reth_rpc_server_types::result::internal_rpc_err;
 use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -170,3 +171,50 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+/// `reth` API implementation of fee estimates based on the pending transactions in the pool.
+pub struct RethFeeApi<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+}
+
+// === impl RethFeeApi ===
+
+impl<Eth> RethFeeApi<Eth> {
+    /// Create a new instance of the [`RethFeeApi`]
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+}
+
+#[async_trait]
+impl<Eth> RethFeeApiServer for RethFeeApi<Eth>
+where
+    Eth: LoadFee + 'static,
+{
+    /// Handler for `reth_feeEstimate`
+    async fn reth_fee_estimate(
+        &self,
+        blocks: Option<Vec<u64>>,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> RpcResult<FeeEstimate> {
+        self.eth_api.pending_fee_estimate(blocks, reward_percentiles).await.map_err(Into::into)
+    }
+}
+
+impl<Eth> std::fmt::Debug for RethFeeApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethFeeApi").finish_non_exhaustive()
+    }
+}
+
+impl<Eth: Clone> Clone for RethFeeApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { eth_api: self.eth_api.clone() }
+    }
+}
@@ -10,7 +10,7 @@ use alloy_rpc_types_eth::{
     BlockOverrides, Index,
 };
 use alloy_rpc_types_trace::{
-    filter::TraceFilter,
+    filter::{TraceFilter, TraceFilterMode},
     opcode::{BlockOpcodeGas, TransactionOpcodeGas},
     parity::*,
     tracerequest::TraceCallRequest,
@@ -28,7 +28,7 @@ use reth_rpc_eth_api::{
     FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
-use reth_storage_api::{BlockNumReader, BlockReader};
+use reth_storage_api::{BlockNumReader, BlockReader, TraceIndexProvider, TransactionVariant};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::DatabaseCommit;
@@ -38,7 +38,7 @@ use revm_inspectors::{
     tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{collections::BTreeSet, ops::RangeInclusive, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `trace` API implementation.
@@ -379,23 +379,50 @@ where
             .into())
         }
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
-        if distance > self.inner.eth_config.max_trace_filter_blocks {
-            return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
-            )
-            .into())
-        }
+        let blocks = match self.indexed_trace_filter_blocks(&filter, start..=end)? {
+            Some(block_numbers) => {
+                // ensure that there are not too many blocks to trace, the index only narrows down
+                // the blocks that need to be re-executed
+                let max_blocks = self.inner.eth_config.max_trace_filter_blocks;
+                if block_numbers.len() as u64 > max_blocks {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "Too many blocks matching the filter; currently limited to {max_blocks} blocks"
+                    ))
+                    .into())
+                }
 
-        // fetch all blocks in that range
-        let blocks = self
-            .provider()
-            .recovered_block_range(start..=end)
-            .map_err(Eth::Error::from_eth_err)?
-            .into_iter()
-            .map(Arc::new)
-            .collect::<Vec<_>>();
+                let mut blocks = Vec::with_capacity(block_numbers.len());
+                for block_number in block_numbers {
+                    if let Some(block) = self
+                        .provider()
+                        .recovered_block(block_number.into(), TransactionVariant::WithHash)
+                        .map_err(Eth::Error::from_eth_err)?
+                    {
+                        blocks.push(Arc::new(block));
+                    }
+                }
+                blocks
+            }
+            None => {
+                // ensure that the range is not too large, since we need to fetch all blocks in the
+                // range
+                let distance = end.saturating_sub(start);
+                if distance > self.inner.eth_config.max_trace_filter_blocks {
+                    return Err(EthApiError::InvalidParams(
+                        "Block range too large; currently limited to 100 blocks".to_string(),
+                    )
+                    .into())
+                }
+
+                // fetch all blocks in that range
+                self.provider()
+                    .recovered_block_range(start..=end)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .into_iter()
+                    .map(Arc::new)
+                    .collect::<Vec<_>>()
+            }
+        };
 
         // trace all blocks
         let mut block_traces = Vec::with_capacity(blocks.len());
@@ -466,6 +493,70 @@ where
         Ok(all_traces)
     }
 
+    /// Returns the blocks of the range that may contain traces matching the address constraints
+    /// of the filter, or `None` if the trace index can't narrow down the range.
+    ///
+    /// The indexed part of the range is narrowed down to the blocks with traces touching the
+    /// filtered addresses, the blocks outside of the index are all returned. The range itself isn't
+    /// limited, only the blocks outside of the index count towards the trace filter block limit
+    /// before they're collected.
+    fn indexed_trace_filter_blocks(
+        &self,
+        filter: &TraceFilter,
+        range: RangeInclusive<u64>,
+    ) -> Result<Option<Vec<u64>>, Eth::Error> {
+        if filter.from_address.is_empty() && filter.to_address.is_empty() {
+            return Ok(None)
+        }
+        let Some(index_range) =
+            self.provider().trace_index_range().map_err(Eth::Error::from_eth_err)?
+        else {
+            return Ok(None)
+        };
+        let indexed =
+            (*range.start()).max(*index_range.start())..=(*range.end()).min(*index_range.end());
+        if indexed.is_empty() {
+            return Ok(None)
+        }
+        let unindexed = (indexed.start() - range.start()) + (range.end() - indexed.end());
+        let max_blocks = self.inner.eth_config.max_trace_filter_blocks;
+        if unindexed > max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "Too many blocks matching the filter; currently limited to {max_blocks} blocks"
+            ))
+            .into())
+        }
+
+        let address_blocks = |addresses: &[Address]| {
+            let mut blocks = BTreeSet::new();
+            for address in addresses {
+                blocks.extend(
+                    self.provider()
+                        .address_trace_blocks(*address, indexed.clone())
+                        .map_err(Eth::Error::from_eth_err)?,
+                );
+            }
+            Ok::<_, Eth::Error>(blocks)
+        };
+        let from_blocks = address_blocks(&filter.from_address)?;
+        let to_blocks = address_blocks(&filter.to_address)?;
+        let candidates = match filter.mode {
+            TraceFilterMode::Intersection
+                if !filter.from_address.is_empty() && !filter.to_address.is_empty() =>
+            {
+                from_blocks.intersection(&to_blocks).copied().collect::<Vec<_>>()
+            }
+            _ => from_blocks.union(&to_blocks).copied().collect(),
+        };
+
+        Ok(Some(
+            (*range.start()..*indexed.start())
+                .chain(candidates)
+                .chain(*indexed.end() + 1..=*range.end())
+                .collect(),
+        ))
+    }
+
     /// Returns traces created at given block.
     pub async fn trace_block(
         &self,
@@ -796,3 +887,64 @@ fn reward_trace<H: BlockHeader>(header: &H, reward: RewardAction) -> LocalizedTr
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use reth_chainspec::ChainSpecProvider;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory, BlockWriter,
+        DBProvider, DatabaseProviderFactory, StageCheckpointWriter,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn trace_filter_range_is_limited_by_indexed_blocks() {
+        let factory = create_test_provider_factory();
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=20,
+            BlockRangeParams { tx_count: 0..1, ..Default::default() },
+        );
+        let provider_rw = factory.database_provider_rw().unwrap();
+        for block in blocks {
+            provider_rw.insert_block(block.try_recover().unwrap()).unwrap();
+        }
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(20)).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexTraces, StageCheckpoint::new(20)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = BlockchainProvider::new(factory).unwrap();
+        let eth_api = EthApi::builder(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        let trace_api = TraceApi::new(
+            eth_api,
+            BlockingTaskGuard::new(10),
+            EthConfig::default().max_trace_filter_blocks(5),
+        );
+
+        // none of the indexed blocks has traces of the address
+        let filter = TraceFilter {
+            from_block: Some(0),
+            to_block: Some(20),
+            from_address: vec![Address::with_last_byte(1)],
+            ..Default::default()
+        };
+        assert_eq!(trace_api.trace_filter(filter).await.unwrap(), vec![]);
+
+        // without addresses the index can't narrow down the range
+        let filter = TraceFilter { from_block: Some(0), to_block: Some(20), ..Default::default() };
+        let err = trace_api.trace_filter(filter).await.unwrap_err();
+        assert!(err.to_string().contains("Block range too large"), "{err}");
+    }
+}
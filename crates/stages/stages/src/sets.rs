@@ -39,9 +39,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
-        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        HeaderStage, IndexAccountHistoryStage, IndexAddressAppearancesStage, IndexLogsStage,
+        IndexStorageHistoryStage, IndexTracesStage, MerkleStage, PruneSenderRecoveryStage,
+        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -49,7 +49,11 @@ use alloy_primitives::B256;
 use reth_config::config::StageConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_evm::ConfigureEvm;
-use reth_network_p2p::{bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, snap::client::SnapClient};
+use reth_network_p2p::{
+    bodies::downloader::BodyDownloader,
+    headers::downloader::HeaderDownloader,
+    snap::client::{NoopSnapClient, SnapClient},
+};
 use reth_primitives_traits::{Block, NodePrimitives};
 use reth_provider::HeaderSyncGapProvider;
 use reth_prune_types::PruneModes;
@@ -79,6 +83,9 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexAddressAppearancesStage`] (if enabled)
+/// - [`IndexLogsStage`] (if enabled)
+/// - [`IndexTracesStage`] (if enabled)
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -271,7 +278,10 @@ where
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
-            .add_stage(EraStage::new(self.era_import_source, self.stages_config.etl.clone()))
+            .add_stage(
+                EraStage::new(self.era_import_source, self.stages_config.etl.clone())
+                    .with_historical_roots(self.stages_config.era.historical_roots.clone()),
+            )
             .add_stage(HeaderStage::new(
                 self.provider,
                 self.header_downloader,
@@ -290,6 +300,7 @@ where
 /// - [`PruneSenderRecoveryStage`]
 /// - [`HashingStages`]
 /// - [`HistoryIndexingStages`]
+/// - [`IndexTracesStage`] (if enabled)
 /// - [`PruneStage`]
 #[derive(Debug)]
 #[non_exhaustive]
@@ -318,14 +329,25 @@ impl<E: ConfigureEvm> OfflineStages<E> {
 
 impl<E, Provider> StageSet<Provider> for OfflineStages<E>
 where
-    E: ConfigureEvm,
+    E: ConfigureEvm + 'static,
     ExecutionStages<E>: StageSet<Provider>,
     PruneSenderRecoveryStage: Stage<Provider>,
     HashingStages: StageSet<Provider>,
     HistoryIndexingStages: StageSet<Provider>,
+    IndexTracesStage<E>: Stage<Provider>,
     PruneStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
+        // Traces are indexed by re-executing the blocks on top of the state history, so the stage
+        // runs after the history indexing stages.
+        let index_traces = self.stages_config.index_traces.enabled.then(|| {
+            IndexTracesStage::new(
+                self.evm_config.clone(),
+                self.stages_config.index_traces,
+                self.stages_config.etl.clone(),
+            )
+        });
+
         ExecutionStages::new(self.evm_config, self.consensus, self.stages_config.clone())
             .builder()
             // If sender recovery prune mode is set, add the prune sender recovery stage.
@@ -337,6 +359,7 @@ where
                 stages_config: self.stages_config.clone(),
                 prune_modes: self.prune_modes.clone(),
             })
+            .add_stage_opt(index_traces)
             // If any prune modes are set, add the prune stage.
             .add_stage_opt(self.prune_modes.is_empty().not().then(|| {
                 // Prune stage should be added after all hashing stages, because otherwise it will
@@ -349,7 +372,7 @@ where
 /// A set containing all stages that are required to execute pre-existing block data.
 #[derive(Debug)]
 #[non_exhaustive]
-pub struct ExecutionStages<E: ConfigureEvm, S = ()> {
+pub struct ExecutionStages<E: ConfigureEvm, S = NoopSnapClient> {
     /// Executor factory that will create executors.
     evm_config: E,
     /// Consensus instance for validating blocks.
@@ -488,6 +511,8 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexAddressAppearancesStage: Stage<Provider>,
+    IndexLogsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -506,5 +531,19 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.account_history,
             ))
+            .add_stage_opt(self.stages_config.index_address_appearances.enabled.then(|| {
+                IndexAddressAppearancesStage::new(
+                    self.stages_config.index_address_appearances,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.address_appearances,
+                )
+            }))
+            .add_stage_opt(self.stages_config.index_logs.enabled.then(|| {
+                IndexLogsStage::new(
+                    self.stages_config.index_logs,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.log_index,
+                )
+            }))
     }
 }
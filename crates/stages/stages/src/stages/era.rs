@@ -1,10 +1,10 @@
 use crate::{StageCheckpoint, StageId};
-use alloy_primitives::{BlockHash, BlockNumber};
+use alloy_primitives::{BlockHash, BlockNumber, B256};
 use futures_util::{Stream, StreamExt};
 use reqwest::{Client, Url};
 use reth_config::config::EtlConfig;
 use reth_db_api::{table::Value, transaction::DbTxMut};
-use reth_era::{era1_file::Era1Reader, era_file_ops::StreamReader};
+use reth_era::{era_file::EraReader, era_file_ops::StreamReader};
 use reth_era_downloader::{read_dir, EraClient, EraMeta, EraStream, EraStreamConfig};
 use reth_era_utils as era;
 use reth_etl::Collector;
@@ -19,7 +19,8 @@ use reth_storage_errors::ProviderError;
 use std::{
     fmt::{Debug, Formatter},
     iter,
-    path::Path,
+    path::{Path, PathBuf},
+    sync::Arc,
     task::{ready, Context, Poll},
 };
 
@@ -29,10 +30,12 @@ type ThreadSafeEraStream<Header, Body> =
     Box<dyn Stream<Item = eyre::Result<Item<Header, Body>>> + Send + Sync + Unpin>;
 
 /// The [ERA1](https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md)
-/// pre-merge history stage.
+/// pre-merge and [ERA](https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era.md)
+/// post-merge history stage.
 ///
-/// Imports block headers and bodies from genesis up to the last pre-merge block. Receipts are
-/// generated by execution. Execution is not done in this stage.
+/// Imports block headers and bodies from genesis up to the last pre-merge block, and past it from
+/// the execution payloads of post-merge `.era` files. Receipts are generated by execution.
+/// Execution is not done in this stage.
 pub struct EraStage<Header, Body, StreamFactory> {
     /// The `source` creates `stream`.
     source: Option<StreamFactory>,
@@ -43,10 +46,16 @@ pub struct EraStage<Header, Body, StreamFactory> {
     item: Option<Item<Header, Body>>,
     /// A stream of [`Item`]s, i.e. iterators over block `Header` and `Body` pairs.
     stream: Option<ThreadSafeEraStream<Header, Body>>,
+    /// Path to a file with the known accumulator roots to verify `.era1` files against.
+    historical_roots: Option<PathBuf>,
 }
 
 trait EraStreamFactory<Header, Body> {
-    fn create(self, input: ExecInput) -> Result<ThreadSafeEraStream<Header, Body>, StageError>;
+    fn create(
+        self,
+        input: ExecInput,
+        historical_roots: Option<Arc<[B256]>>,
+    ) -> Result<ThreadSafeEraStream<Header, Body>, StageError>;
 }
 
 impl<Header, Body> EraStreamFactory<Header, Body> for EraImportSource
@@ -54,19 +63,27 @@ where
     Header: FullBlockHeader + Value,
     Body: FullBlockBody<OmmerHeader = Header>,
 {
-    fn create(self, input: ExecInput) -> Result<ThreadSafeEraStream<Header, Body>, StageError> {
+    fn create(
+        self,
+        input: ExecInput,
+        historical_roots: Option<Arc<[B256]>>,
+    ) -> Result<ThreadSafeEraStream<Header, Body>, StageError> {
         match self {
             Self::Path(path) => Self::convert(
                 read_dir(path, input.next_block()).map_err(|e| StageError::Fatal(e.into()))?,
+                historical_roots,
             ),
             Self::Url(url, folder) => {
                 let _ = reth_fs_util::create_dir_all(&folder);
                 let client = EraClient::new(Client::new(), url, folder);
 
-                Self::convert(EraStream::new(
-                    client,
-                    EraStreamConfig::default().start_from(input.next_block()),
-                ))
+                Self::convert(
+                    EraStream::new(
+                        client,
+                        EraStreamConfig::default().start_from(input.next_block()),
+                    ),
+                    historical_roots,
+                )
             }
         }
     }
@@ -79,17 +96,25 @@ impl EraImportSource {
             + Sync
             + 'static
             + Unpin,
+        historical_roots: Option<Arc<[B256]>>,
     ) -> Result<ThreadSafeEraStream<Header, Body>, StageError>
     where
         Header: FullBlockHeader + Value,
         Body: FullBlockBody<OmmerHeader = Header>,
     {
-        Ok(Box::new(Box::pin(stream.map(|meta| {
+        Ok(Box::new(Box::pin(stream.map(move |meta| {
             meta.and_then(|meta| {
-                let file = reth_fs_util::open(meta.path())?;
-                let reader = Era1Reader::new(file);
-                let iter = reader.iter();
-                let iter = iter.map(era::decode);
+                let iter = if era::is_era_file(meta.path()) {
+                    let reader = EraReader::new(reth_fs_util::open(meta.path())?);
+                    Box::new(
+                        reader
+                            .iter()
+                            .filter_map(|block| era::decode_beacon_block(block).transpose()),
+                    ) as Item<Header, Body>
+                } else {
+                    let reader = era::open(&meta, historical_roots.as_deref())?;
+                    Box::new(reader.iter().map(era::decode))
+                };
                 let iter = iter.chain(
                     iter::once_with(move || match meta.mark_as_processed() {
                         Ok(..) => None,
@@ -111,6 +136,7 @@ impl<Header: Debug, Body: Debug, F: Debug> Debug for EraStage<Header, Body, F> {
             .field("hash_collector", &self.hash_collector)
             .field("item", &self.item.is_some())
             .field("stream", &"dyn Stream")
+            .field("historical_roots", &self.historical_roots)
             .finish()
     }
 }
@@ -123,8 +149,16 @@ impl<Header, Body, F> EraStage<Header, Body, F> {
             item: None,
             stream: None,
             hash_collector: Collector::new(etl_config.file_size, etl_config.dir),
+            historical_roots: None,
         }
     }
+
+    /// Sets the path to a file with the known accumulator roots to verify `.era1` files against,
+    /// see [`era::read_historical_roots`].
+    pub fn with_historical_roots(mut self, historical_roots: Option<PathBuf>) -> Self {
+        self.historical_roots = historical_roots;
+        self
+    }
 }
 
 impl<Provider, N, F> Stage<Provider> for EraStage<N::BlockHeader, N::BlockBody, F>
@@ -153,7 +187,13 @@ where
         if self.stream.is_none() &&
             let Some(source) = self.source.clone()
         {
-            self.stream.replace(source.create(input)?);
+            let historical_roots = self
+                .historical_roots
+                .as_deref()
+                .map(era::read_historical_roots)
+                .transpose()
+                .map_err(|e| StageError::Fatal(e.into()))?;
+            self.stream.replace(source.create(input, historical_roots.map(Arc::from))?);
         }
         if let Some(stream) = &mut self.stream &&
             let Some(next) = ready!(stream.poll_next_unpin(cx))
@@ -599,6 +639,7 @@ mod tests {
             fn create(
                 self,
                 _input: ExecInput,
+                _historical_roots: Option<Arc<[B256]>>,
             ) -> Result<ThreadSafeEraStream<Header, BlockBody<TransactionSigned>>, StageError>
             {
                 let stream = stream::iter(vec![self.0]);
@@ -0,0 +1,285 @@
+use super::load_history_indices;
+use alloy_primitives::{Address, BlockNumber};
+use reth_config::config::{EtlConfig, IndexAddressAppearancesConfig};
+use reth_db_api::{
+    models::ShardedKey, table::Decode, tables, transaction::DbTxMut, BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{
+    AddressAppearancesProvider, DBProvider, HistoryWriter, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+
+/// Number of blocks to read at once when collecting appearances.
+const BLOCKS_PER_BATCH: u64 = 1_000;
+
+/// Stage indexing the blocks in which each address appears as a transaction sender, recipient or
+/// created contract, and optionally as a log emitter or an address-like log topic. For more
+/// information on index sharding take a look at [`tables::AddressAppearances`].
+///
+/// Whether logs were indexed is stored in the stage checkpoint progress. If it doesn't match the
+/// configuration, the index is rebuilt from scratch.
+#[derive(Debug)]
+pub struct IndexAddressAppearancesStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Whether to index log emitters and address-like log topics.
+    pub index_logs: bool,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexAddressAppearancesStage {
+    /// Create new instance of [`IndexAddressAppearancesStage`].
+    pub const fn new(
+        config: IndexAddressAppearancesConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self {
+            commit_threshold: config.commit_threshold,
+            index_logs: config.index_logs,
+            prune_mode,
+            etl_config,
+        }
+    }
+}
+
+impl Default for IndexAddressAppearancesStage {
+    fn default() -> Self {
+        Self {
+            commit_threshold: 100_000,
+            index_logs: false,
+            prune_mode: None,
+            etl_config: EtlConfig::default(),
+        }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexAddressAppearancesStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HistoryWriter
+        + AddressAppearancesProvider
+        + PruneCheckpointReader
+        + PruneCheckpointWriter
+        + StageCheckpointReader
+        + StageCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressAppearances
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let index_logs = provider
+            .get_stage_checkpoint_progress(StageId::IndexAddressAppearances)?
+            .and_then(|progress| progress.first().copied());
+        if index_logs != Some(self.index_logs as u8) {
+            if input.checkpoint().block_number > 0 {
+                info!(target: "sync::stages::index_address_appearances::exec", index_logs = self.index_logs, "Log indexing setting changed, rebuilding index");
+            }
+            provider.tx_ref().clear::<tables::AddressAppearances>()?;
+            input.checkpoint = Some(StageCheckpoint::new(0));
+            provider.save_stage_checkpoint_progress(
+                StageId::IndexAddressAppearances,
+                vec![self.index_logs as u8],
+            )?;
+        }
+
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressAppearances,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten() &&
+            target_prunable_block > input.checkpoint().block_number
+        {
+            input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+            // Save prune checkpoint only if we don't have one already.
+            // Otherwise, pruner may skip the unpruned range of blocks.
+            if provider.get_prune_checkpoint(PruneSegment::AddressAppearances)?.is_none() {
+                provider.save_prune_checkpoint(
+                    PruneSegment::AddressAppearances,
+                    PruneCheckpoint {
+                        block_number: Some(target_prunable_block),
+                        tx_number: None,
+                        prune_mode,
+                    },
+                )?;
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (mut range, is_final_range) =
+            input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync the genesis block is indexed as well.
+        if first_sync {
+            range = 0..=*range.end();
+        }
+
+        info!(target: "sync::stages::index_address_appearances::exec", ?first_sync, ?range, "Collecting indices");
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        for batch_start in range.clone().step_by(BLOCKS_PER_BATCH as usize) {
+            let batch_end = (batch_start + BLOCKS_PER_BATCH - 1).min(*range.end());
+
+            let mut cache = HashMap::<Address, Vec<BlockNumber>>::default();
+            for (block_number, addresses) in
+                provider.block_address_appearances(batch_start..=batch_end, self.index_logs)?
+            {
+                for address in addresses {
+                    cache.entry(address).or_default().push(block_number);
+                }
+            }
+            for (address, indices) in cache {
+                let last = *indices.last().expect("qed");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+        }
+
+        info!(target: "sync::stages::index_address_appearances::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressAppearances, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        // The lowest unwound block of each address.
+        let mut appearances = BTreeMap::new();
+        for (block_number, addresses) in
+            provider.block_address_appearances(range, self.index_logs)?
+        {
+            for address in addresses {
+                appearances.entry(address).or_insert(block_number);
+            }
+        }
+        provider.unwind_address_appearance_indices(appearances)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::B256;
+    use reth_provider::DatabaseProviderFactory;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    /// Builds the expected index contents from the blocks' own appearances.
+    fn expected_index(
+        db: &TestStageDB,
+        range: std::ops::RangeInclusive<BlockNumber>,
+    ) -> BTreeMap<Address, Vec<BlockNumber>> {
+        let provider = db.factory.provider().unwrap();
+        let mut expected = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        for (block_number, addresses) in provider.block_address_appearances(range, false).unwrap() {
+            for address in addresses {
+                expected.entry(address).or_default().push(block_number);
+            }
+        }
+        expected
+    }
+
+    fn index(db: &TestStageDB) -> BTreeMap<Address, Vec<BlockNumber>> {
+        db.table::<tables::AddressAppearances>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| (key.key, list.iter().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let mut stage = IndexAddressAppearancesStage { commit_threshold: 15, ..Default::default() };
+
+        // Execute in two commits.
+        let mut checkpoint = None;
+        loop {
+            let input = ExecInput { target: Some(20), checkpoint };
+            let provider = db.factory.database_provider_rw().unwrap();
+            let out = stage.execute(&provider, input).unwrap();
+            provider.commit().unwrap();
+            checkpoint = Some(out.checkpoint);
+            if out.done {
+                break
+            }
+        }
+        assert_eq!(checkpoint, Some(StageCheckpoint::new(20)));
+        assert_eq!(index(&db), expected_index(&db, 0..=20));
+
+        // Unwind to block 10.
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(20), unwind_to: 10, bad_block: None };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(out.checkpoint, StageCheckpoint::new(10));
+        assert_eq!(index(&db), expected_index(&db, 0..=10));
+
+        // Changing the log setting rebuilds the index from scratch.
+        stage.index_logs = true;
+        let input = ExecInput { target: Some(10), checkpoint: Some(StageCheckpoint::new(10)) };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out.checkpoint, StageCheckpoint::new(10));
+        assert_eq!(
+            provider.get_stage_checkpoint_progress(StageId::IndexAddressAppearances).unwrap(),
+            Some(vec![1])
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use super::load_history_indices;
+use alloy_primitives::{Address, B256};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db_api::{
+    models::ShardedKey, table::Decode, tables, transaction::DbTxMut, BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{
+    DBProvider, HistoryWriter, LogIndexProvider, PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+
+/// Number of blocks to read at once when collecting log keys.
+const BLOCKS_PER_BATCH: u64 = 1_000;
+
+/// Stage indexing the positions of the logs of each contract address and topic, so that log
+/// queries don't have to scan the header blooms of every block in their range. For more
+/// information on index sharding take a look at [`tables::LogAddressIndex`] and
+/// [`tables::LogTopicIndex`], positions are described in
+/// [`log_position`](reth_provider::log_position).
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(
+        config: IndexLogsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, prune_mode, etl_config }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HistoryWriter
+        + LogIndexProvider
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::LogIndex, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten() &&
+            target_prunable_block > input.checkpoint().block_number
+        {
+            input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+            // Save prune checkpoint only if we don't have one already.
+            // Otherwise, pruner may skip the unpruned range of blocks.
+            if provider.get_prune_checkpoint(PruneSegment::LogIndex)?.is_none() {
+                provider.save_prune_checkpoint(
+                    PruneSegment::LogIndex,
+                    PruneCheckpoint {
+                        block_number: Some(target_prunable_block),
+                        tx_number: None,
+                        prune_mode,
+                    },
+                )?;
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (mut range, is_final_range) =
+            input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync the genesis block is indexed as well.
+        if first_sync {
+            range = 0..=*range.end();
+        }
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, ?range, "Collecting indices");
+        let mut addresses =
+            Collector::new(self.etl_config.file_size / 2, self.etl_config.dir.clone());
+        let mut topics = Collector::new(self.etl_config.file_size / 2, self.etl_config.dir.clone());
+        for batch_start in range.clone().step_by(BLOCKS_PER_BATCH as usize) {
+            let batch_end = (batch_start + BLOCKS_PER_BATCH - 1).min(*range.end());
+
+            let mut address_cache = HashMap::<Address, Vec<u64>>::default();
+            let mut topic_cache = HashMap::<B256, Vec<u64>>::default();
+            for (_, keys) in provider.block_log_keys(batch_start..=batch_end)? {
+                for (address, position) in keys.addresses {
+                    address_cache.entry(address).or_default().push(position);
+                }
+                for (topic, position) in keys.topics {
+                    topic_cache.entry(topic).or_default().push(position);
+                }
+            }
+            for (address, indices) in address_cache {
+                let last = *indices.last().expect("qed");
+                addresses.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+            for (topic, indices) in topic_cache {
+                let last = *indices.last().expect("qed");
+                topics.insert(
+                    ShardedKey::new(topic, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+        }
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            addresses,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topics,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        // The lowest unwound log position of each address and topic.
+        let mut addresses = BTreeMap::new();
+        let mut topics = BTreeMap::new();
+        for (_, keys) in provider.block_log_keys(range)? {
+            for (address, position) in keys.addresses {
+                addresses.entry(address).or_insert(position);
+            }
+            for (topic, position) in keys.topics {
+                topics.entry(topic).or_insert(position);
+            }
+        }
+        provider.unwind_log_indices(addresses, topics)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::BlockNumber;
+    use reth_provider::{log_position, DatabaseProviderFactory, ReceiptProvider};
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_receipt, BlockRangeParams,
+    };
+
+    /// Builds the expected index contents from the blocks' own logs.
+    fn expected_index(
+        db: &TestStageDB,
+        range: std::ops::RangeInclusive<BlockNumber>,
+    ) -> (BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>) {
+        let provider = db.factory.provider().unwrap();
+        let mut addresses = BTreeMap::<Address, Vec<u64>>::new();
+        let mut topics = BTreeMap::<B256, Vec<u64>>::new();
+        for (block_number, receipts) in
+            range.clone().zip(provider.receipts_by_block_range(range).unwrap())
+        {
+            for (log_index, log) in receipts.iter().flat_map(|r| &r.logs).enumerate() {
+                let position = log_position(block_number, log_index as u64);
+                addresses.entry(log.address).or_default().push(position);
+                for topic in log.topics() {
+                    let positions = topics.entry(*topic).or_default();
+                    if positions.last() != Some(&position) {
+                        positions.push(position);
+                    }
+                }
+            }
+        }
+        (addresses, topics)
+    }
+
+    fn index(db: &TestStageDB) -> (BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>) {
+        (
+            db.table::<tables::LogAddressIndex>()
+                .unwrap()
+                .into_iter()
+                .map(|(key, list)| (key.key, list.iter().collect()))
+                .collect(),
+            db.table::<tables::LogTopicIndex>()
+                .unwrap()
+                .into_iter()
+                .map(|(key, list)| (key.key, list.iter().collect()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let receipts = blocks
+            .iter()
+            .flat_map(|block| block.body().transactions.iter())
+            .map(|tx| random_receipt(&mut rng, tx, Some(2), Some(2)))
+            .enumerate()
+            .map(|(tx_number, receipt)| (tx_number as u64, receipt))
+            .collect::<Vec<_>>();
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let mut stage = IndexLogsStage { commit_threshold: 15, ..Default::default() };
+
+        // Execute in two commits.
+        let mut checkpoint = None;
+        loop {
+            let input = ExecInput { target: Some(20), checkpoint };
+            let provider = db.factory.database_provider_rw().unwrap();
+            let out = stage.execute(&provider, input).unwrap();
+            provider.commit().unwrap();
+            checkpoint = Some(out.checkpoint);
+            if out.done {
+                break
+            }
+        }
+        assert_eq!(checkpoint, Some(StageCheckpoint::new(20)));
+        let expected = expected_index(&db, 0..=20);
+        assert!(!expected.0.is_empty() && !expected.1.is_empty());
+        assert_eq!(index(&db), expected);
+
+        let provider = db.factory.provider().unwrap();
+        let (address, positions) = expected.0.first_key_value().unwrap();
+        assert_eq!(&provider.address_log_positions(*address, 0..=20).unwrap(), positions);
+        let (topic, positions) = expected.1.last_key_value().unwrap();
+        assert_eq!(&provider.topic_log_positions(*topic, 0..=20).unwrap(), positions);
+        drop(provider);
+
+        // Unwind to block 10.
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(20), unwind_to: 10, bad_block: None };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(out.checkpoint, StageCheckpoint::new(10));
+        assert_eq!(index(&db), expected_index(&db, 0..=10));
+    }
+}
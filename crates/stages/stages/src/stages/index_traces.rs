@@ -0,0 +1,396 @@
+use super::load_history_indices;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{map::AddressHashSet, Address, BlockNumber, U256};
+use reth_config::config::{EtlConfig, IndexTracesConfig};
+use reth_db_api::{
+    models::ShardedKey, table::Decode, tables, transaction::DbTxMut, BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_evm::{
+    block::{BlockExecutionError, BlockExecutor},
+    ConfigureEvm, Database, Evm,
+};
+use reth_primitives_traits::{BlockBody, NodePrimitives, RecoveredBlock};
+use reth_provider::{
+    BlockReader, DBProvider, HistoricalStateProviderRef, HistoryWriter, PruneCheckpointReader,
+};
+use reth_prune_types::PruneSegment;
+use reth_revm::{
+    database::StateProviderDatabase,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    Inspector, State,
+};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, ops::RangeInclusive};
+use tracing::info;
+
+/// Number of blocks to re-execute on top of the same state cache.
+const BLOCKS_PER_BATCH: u64 = 100;
+
+/// Stage indexing the blocks containing traces that touch each address, so that `trace_filter`
+/// only has to re-execute the blocks matching its address constraints. For more information on
+/// index sharding take a look at [`tables::TraceAddressIndex`].
+///
+/// The traces are collected by re-executing the blocks on top of the historical state, so blocks
+/// whose account or storage history was pruned are skipped.
+///
+/// Unwinding doesn't re-execute the unwound blocks to find the addresses they touched. Instead,
+/// the shards of every address touched by the re-indexed blocks are truncated to the start of the
+/// range before the new blocks are appended. Unwound blocks of addresses that aren't touched again
+/// stay in the index, which is fine since `trace_filter` re-executes every matched block.
+#[derive(Debug)]
+pub struct IndexTracesStage<E> {
+    /// The EVM configuration used to re-execute blocks.
+    evm_config: E,
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl<E> IndexTracesStage<E> {
+    /// Create new instance of [`IndexTracesStage`].
+    pub const fn new(evm_config: E, config: IndexTracesConfig, etl_config: EtlConfig) -> Self {
+        Self { evm_config, commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl<E: ConfigureEvm> IndexTracesStage<E> {
+    /// Re-executes the blocks of the given range and returns the addresses touched by the traces
+    /// of each block that has any, in ascending order.
+    ///
+    /// The addresses are the ones `trace_filter` matches on: the caller, target and code address
+    /// of every call, the caller and deployed address of every create, the contract and
+    /// beneficiary of every selfdestruct, and the block and ommer beneficiaries of pre-merge
+    /// blocks, which receive reward traces.
+    fn block_trace_addresses<Provider>(
+        &self,
+        provider: &Provider,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<(BlockNumber, Vec<Address>)>, StageError>
+    where
+        Provider: DBProvider + BlockReader<Block = <E::Primitives as NodePrimitives>::Block>,
+    {
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(HistoricalStateProviderRef::new(
+                provider,
+                *range.start(),
+            )))
+            .build();
+
+        let mut blocks = Vec::new();
+        for block in provider.recovered_block_range(range)? {
+            let mut addresses = self
+                .execute_block(&mut db, &block)
+                .map_err(|error| StageError::Fatal(Box::new(error)))?;
+
+            if block.header().difficulty() != U256::ZERO {
+                addresses.insert(block.header().beneficiary());
+                for ommer in block.body().ommers().unwrap_or_default() {
+                    addresses.insert(ommer.beneficiary());
+                }
+            }
+
+            if !addresses.is_empty() {
+                let mut addresses = addresses.into_iter().collect::<Vec<_>>();
+                addresses.sort_unstable();
+                blocks.push((block.number(), addresses));
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Executes the block on top of the given state and returns the addresses touched by the
+    /// traces of its transactions.
+    fn execute_block<DB: Database>(
+        &self,
+        db: &mut State<DB>,
+        block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+    ) -> Result<AddressHashSet, BlockExecutionError> {
+        let evm_env =
+            self.evm_config.evm_env(block.header()).map_err(BlockExecutionError::other)?;
+        let evm = self.evm_config.evm_with_env_and_inspector(
+            db,
+            evm_env,
+            TraceAddressInspector::default(),
+        );
+        let ctx = self
+            .evm_config
+            .context_for_block(block.sealed_block())
+            .map_err(BlockExecutionError::other)?;
+        let mut executor = self.evm_config.create_executor(evm, ctx);
+
+        executor.apply_pre_execution_changes()?;
+        // System calls are not part of the traces of a block.
+        executor.evm_mut().inspector_mut().addresses.clear();
+        for tx in block.transactions_recovered() {
+            executor.execute_transaction(tx)?;
+        }
+        let addresses = std::mem::take(&mut executor.evm_mut().inspector_mut().addresses);
+        executor.finish()?;
+
+        Ok(addresses)
+    }
+}
+
+/// Returns the lowest block that can be re-executed, because the account and storage history
+/// needed to recreate its state is available.
+fn lowest_executable_block<Provider: PruneCheckpointReader>(
+    provider: &Provider,
+) -> Result<BlockNumber, StageError> {
+    let mut lowest = 0;
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if let Some(block_number) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            lowest = lowest.max(block_number + 1);
+        }
+    }
+    Ok(lowest)
+}
+
+impl<E, Provider> Stage<Provider> for IndexTracesStage<E>
+where
+    E: ConfigureEvm,
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader<Block = <E::Primitives as NodePrimitives>::Block>
+        + HistoryWriter
+        + PruneCheckpointReader,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexTraces
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        // Skip the blocks that can't be re-executed anymore.
+        let lowest_block = lowest_executable_block(provider)?;
+        if lowest_block > input.checkpoint().block_number + 1 {
+            input.checkpoint = Some(StageCheckpoint::new(lowest_block - 1));
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        info!(target: "sync::stages::index_traces::exec", ?first_sync, ?range, "Collecting indices");
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut touched = AddressHashSet::default();
+        for batch_start in range.clone().step_by(BLOCKS_PER_BATCH as usize) {
+            let batch_end = (batch_start + BLOCKS_PER_BATCH - 1).min(*range.end());
+
+            let mut cache = HashMap::<Address, Vec<BlockNumber>>::default();
+            for (block_number, addresses) in
+                self.block_trace_addresses(provider, batch_start..=batch_end)?
+            {
+                for address in addresses {
+                    cache.entry(address).or_default().push(block_number);
+                }
+            }
+            if !first_sync {
+                touched.extend(cache.keys().copied());
+            }
+            for (address, indices) in cache {
+                let last = *indices.last().expect("qed");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+        }
+
+        // Drop the blocks left over by previous unwinds, so that the new ones are appended in
+        // order.
+        provider
+            .unwind_trace_indices(touched.into_iter().map(|address| (address, *range.start())))?;
+
+        info!(target: "sync::stages::index_traces::exec", "Loading indices into database");
+        load_history_indices::<_, tables::TraceAddressIndex, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    ///
+    /// The unwound blocks are dropped from the index when the next blocks are indexed, unless the
+    /// whole index is unwound.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        // Block 0 is never indexed, so nothing is left below the lowest indexed block.
+        if input.unwind_to < lowest_executable_block(provider)?.max(1) {
+            provider.tx_ref().clear::<tables::TraceAddressIndex>()?;
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// Collects the addresses touched by the traces of the executed transactions.
+#[derive(Debug, Default)]
+struct TraceAddressInspector {
+    addresses: AddressHashSet,
+}
+
+impl<CTX> Inspector<CTX> for TraceAddressInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.addresses.insert(inputs.caller);
+        self.addresses.insert(inputs.target_address);
+        self.addresses.insert(inputs.bytecode_address);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.addresses.insert(inputs.caller);
+        self.addresses.extend(outcome.address);
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.addresses.insert(contract);
+        self.addresses.insert(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stages::ExecutionStage, test_utils::TestStageDB};
+    use alloy_primitives::{address, hex_literal::hex, keccak256};
+    use alloy_rlp::Decodable;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_ethereum_primitives::Block;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{Account, Bytecode, SealedBlock};
+    use reth_provider::{
+        BlockWriter, DatabaseProviderFactory, StaticFileProviderFactory, StaticFileWriter,
+    };
+    use reth_static_file_types::StaticFileSegment;
+    use std::{collections::BTreeMap, sync::Arc};
+
+    fn index(db: &TestStageDB) -> BTreeMap<Address, Vec<BlockNumber>> {
+        db.table::<tables::TraceAddressIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| (key.key, list.iter().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let evm_config =
+            EthEvmConfig::new(Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()));
+
+        // A pre-merge block calling a contract, taken from the execution stage tests.
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::<Block>::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::<Block>::decode(&mut block_rlp).unwrap();
+
+        let provider = db.factory.provider_rw().unwrap();
+        provider.insert_block(genesis.try_recover().unwrap()).unwrap();
+        provider.insert_block(block.try_recover().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        {
+            let static_file_provider = provider.static_file_provider();
+            let mut receipts_writer =
+                static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+            receipts_writer.increment_block(0).unwrap();
+            receipts_writer.commit().unwrap();
+        }
+
+        let caller = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let contract = address!("0x1000000000000000000000000000000000000000");
+        let beneficiary = address!("0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba");
+        let code = hex!("5a465a905090036002900360015500");
+        let code_hash = keccak256(code);
+        let tx = provider.tx_ref();
+        tx.put::<tables::PlainAccountState>(
+            contract,
+            Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(
+            caller,
+            Account {
+                nonce: 0,
+                balance: U256::from(0x3635c9adc5dea00000u128),
+                bytecode_hash: None,
+            },
+        )
+        .unwrap();
+        tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        // Execute the block and index the state history it changed, so that it can be
+        // re-executed.
+        let provider = db.factory.database_provider_rw().unwrap();
+        let mut execution = ExecutionStage::new_with_executor(
+            evm_config.clone(),
+            Arc::new(reth_consensus::noop::NoopConsensus::default()),
+        );
+        execution.execute(&provider, ExecInput { target: Some(1), checkpoint: None }).unwrap();
+        provider.update_history_indices(1..=1).unwrap();
+        provider.commit().unwrap();
+
+        let mut stage =
+            IndexTracesStage::new(evm_config, IndexTracesConfig::default(), EtlConfig::default());
+        let expected =
+            BTreeMap::from([(contract, vec![1]), (beneficiary, vec![1]), (caller, vec![1])]);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out =
+            stage.execute(&provider, ExecInput { target: Some(1), checkpoint: None }).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(out, ExecOutput::done(StageCheckpoint::new(1)));
+        assert_eq!(index(&db), expected);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(1), unwind_to: 0, bad_block: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+        assert_eq!(out.checkpoint, StageCheckpoint::new(0));
+        assert!(index(&db).is_empty());
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(1), checkpoint: Some(out.checkpoint) };
+        stage.execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(index(&db), expected);
+    }
+}
@@ -12,8 +12,14 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index of address appearances
+mod index_address_appearances;
+/// Index of log addresses and topics
+mod index_logs;
 /// Index history of storage changes
 mod index_storage_history;
+/// Index of addresses touched by traces
+mod index_traces;
 /// Stage for computing state root.
 mod merkle;
 mod prune;
@@ -32,7 +38,10 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_appearances::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
+pub use index_traces::*;
 pub use merkle::*;
 pub use prune::*;
 pub use sender_recovery::*;
@@ -547,6 +556,9 @@ mod tests {
             snap::client::SnapClient,
         };
         use reth_network_peers::{PeerId, WithPeerId};
+        use alloy_consensus::constants::KECCAK_EMPTY;
+        use alloy_rlp::Encodable;
+        use alloy_trie::Nibbles;
         use reth_stages_api::{ExecOutput, UnwindOutput};
         use std::future;
 
@@ -560,35 +572,36 @@ mod tests {
         }
 
         impl SnapClient for MockSnapClient {
-            type Output = future::Ready<
+            type AccountRangeOutput = future::Ready<
                 reth_network_p2p::error::PeerRequestResult<
                     reth_eth_wire_types::snap::AccountRangeMessage,
                 >,
             >;
+            type StorageRangesOutput = future::Ready<
+                reth_network_p2p::error::PeerRequestResult<
+                    reth_eth_wire_types::snap::StorageRangesMessage,
+                >,
+            >;
+            type ByteCodesOutput = future::Ready<
+                reth_network_p2p::error::PeerRequestResult<
+                    reth_eth_wire_types::snap::ByteCodesMessage,
+                >,
+            >;
+            type TrieNodesOutput = future::Ready<
+                reth_network_p2p::error::PeerRequestResult<
+                    reth_eth_wire_types::snap::TrieNodesMessage,
+                >,
+            >;
 
             fn get_account_range_with_priority(
                 &self,
-                _request: reth_eth_wire_types::snap::GetAccountRangeMessage,
+                request: reth_eth_wire_types::snap::GetAccountRangeMessage,
                 _priority: Priority,
-            ) -> Self::Output {
+            ) -> Self::AccountRangeOutput {
                 future::ready(Ok(WithPeerId::new(
                     PeerId::random(),
                     reth_eth_wire_types::snap::AccountRangeMessage {
-                        request_id: 1,
-                        accounts: vec![],
-                        proof: vec![],
-                    },
-                )))
-            }
-
-            fn get_storage_ranges(
-                &self,
-                _request: reth_eth_wire_types::snap::GetStorageRangesMessage,
-            ) -> Self::Output {
-                future::ready(Ok(WithPeerId::new(
-                    PeerId::random(),
-                    reth_eth_wire_types::snap::AccountRangeMessage {
-                        request_id: 1,
+                        request_id: request.request_id,
                         accounts: vec![],
                         proof: vec![],
                     },
@@ -597,28 +610,14 @@ mod tests {
 
             fn get_storage_ranges_with_priority(
                 &self,
-                _request: reth_eth_wire_types::snap::GetStorageRangesMessage,
+                request: reth_eth_wire_types::snap::GetStorageRangesMessage,
                 _priority: Priority,
-            ) -> Self::Output {
+            ) -> Self::StorageRangesOutput {
                 future::ready(Ok(WithPeerId::new(
                     PeerId::random(),
-                    reth_eth_wire_types::snap::AccountRangeMessage {
-                        request_id: 1,
-                        accounts: vec![],
-                        proof: vec![],
-                    },
-                )))
-            }
-
-            fn get_byte_codes(
-                &self,
-                _request: reth_eth_wire_types::snap::GetByteCodesMessage,
-            ) -> Self::Output {
-                future::ready(Ok(WithPeerId::new(
-                    PeerId::random(),
-                    reth_eth_wire_types::snap::AccountRangeMessage {
-                        request_id: 1,
-                        accounts: vec![],
+                    reth_eth_wire_types::snap::StorageRangesMessage {
+                        request_id: request.request_id,
+                        slots: vec![],
                         proof: vec![],
                     },
                 )))
@@ -626,49 +625,41 @@ mod tests {
 
             fn get_byte_codes_with_priority(
                 &self,
-                _request: reth_eth_wire_types::snap::GetByteCodesMessage,
+                request: reth_eth_wire_types::snap::GetByteCodesMessage,
                 _priority: Priority,
-            ) -> Self::Output {
-                future::ready(Ok(WithPeerId::new(
-                    PeerId::random(),
-                    reth_eth_wire_types::snap::AccountRangeMessage {
-                        request_id: 1,
-                        accounts: vec![],
-                        proof: vec![],
-                    },
-                )))
-            }
-
-            fn get_trie_nodes(
-                &self,
-                _request: reth_eth_wire_types::snap::GetTrieNodesMessage,
-            ) -> Self::Output {
+            ) -> Self::ByteCodesOutput {
                 future::ready(Ok(WithPeerId::new(
                     PeerId::random(),
-                    reth_eth_wire_types::snap::AccountRangeMessage {
-                        request_id: 1,
-                        accounts: vec![],
-                        proof: vec![],
+                    reth_eth_wire_types::snap::ByteCodesMessage {
+                        request_id: request.request_id,
+                        codes: vec![],
                     },
                 )))
             }
 
             fn get_trie_nodes_with_priority(
                 &self,
-                _request: reth_eth_wire_types::snap::GetTrieNodesMessage,
+                request: reth_eth_wire_types::snap::GetTrieNodesMessage,
                 _priority: Priority,
-            ) -> Self::Output {
+            ) -> Self::TrieNodesOutput {
                 future::ready(Ok(WithPeerId::new(
                     PeerId::random(),
-                    reth_eth_wire_types::snap::AccountRangeMessage {
-                        request_id: 1,
-                        accounts: vec![],
-                        proof: vec![],
+                    reth_eth_wire_types::snap::TrieNodesMessage {
+                        request_id: request.request_id,
+                        nodes: vec![],
                     },
                 )))
             }
         }
 
+        /// Returns a header receiver for a header with the given state root.
+        fn snap_header_receiver(
+            state_root: B256,
+        ) -> tokio::sync::watch::Receiver<reth_primitives_traits::SealedHeader> {
+            let header = alloy_consensus::Header { state_root, ..Default::default() };
+            tokio::sync::watch::channel(reth_primitives_traits::SealedHeader::seal_slow(header)).1
+        }
+
         // Test runner for SnapSync stage
         struct SnapSyncTestRunner {
             db: crate::test_utils::TestStageDB,
@@ -699,7 +690,9 @@ mod tests {
             }
 
             fn stage(&self) -> Self::S {
+                // The mock client serves an empty state
                 crate::stages::SnapSyncStage::new(self.config, std::sync::Arc::new(MockSnapClient))
+                    .with_header_receiver(snap_header_receiver(alloy_trie::EMPTY_ROOT_HASH))
             }
         }
 
@@ -1063,4 +1056,430 @@ mod tests {
             // Verify config is stored correctly
             assert_eq!(stage.config.max_retries, 5);
         }
+
+        /// An account served by [`StateSnapClient`] with its nonce, storage and bytecode.
+        type SnapTestAccount = (B256, u64, std::collections::BTreeMap<B256, U256>, Option<Vec<u8>>);
+
+        /// Snap client serving a fixed state, including all trie nodes for healing.
+        #[derive(Debug, Default)]
+        struct StateSnapClient {
+            accounts: std::collections::BTreeMap<B256, alloy_trie::TrieAccount>,
+            storages: std::collections::BTreeMap<B256, std::collections::BTreeMap<B256, U256>>,
+            bytecodes: std::collections::HashMap<B256, alloy_primitives::Bytes>,
+            account_nodes: std::collections::HashMap<Nibbles, alloy_primitives::Bytes>,
+            storage_nodes: std::collections::HashMap<
+                B256,
+                std::collections::HashMap<Nibbles, alloy_primitives::Bytes>,
+            >,
+            /// The maximum number of storage slots served per account.
+            max_slots: Option<usize>,
+            /// The maximum number of accounts served per account range.
+            max_accounts: Option<usize>,
+        }
+
+        impl StateSnapClient {
+            /// Creates a client serving the given accounts with their storage and bytecode.
+            fn new(accounts: Vec<SnapTestAccount>) -> (Self, B256) {
+                let mut client = Self::default();
+                let mut account_leaves = Vec::new();
+                for (hashed_address, nonce, storage, code) in accounts {
+                    let (storage_root, storage_nodes) = Self::trie_nodes(
+                        storage.iter().map(|(slot, value)| (*slot, alloy_rlp::encode(value))),
+                    );
+                    let code_hash = code.as_ref().map_or(KECCAK_EMPTY, keccak256);
+                    if let Some(code) = code {
+                        client.bytecodes.insert(code_hash, code.into());
+                    }
+                    let account = alloy_trie::TrieAccount {
+                        nonce,
+                        balance: U256::from(nonce),
+                        storage_root,
+                        code_hash,
+                    };
+                    account_leaves.push((hashed_address, alloy_rlp::encode(account)));
+                    client.accounts.insert(hashed_address, account);
+                    client.storages.insert(hashed_address, storage);
+                    client.storage_nodes.insert(hashed_address, storage_nodes);
+                }
+                let (state_root, account_nodes) = Self::trie_nodes(account_leaves);
+                client.account_nodes = account_nodes;
+                (client, state_root)
+            }
+
+            /// Returns the root and all nodes of the trie with the given leaves.
+            fn trie_nodes(
+                leaves: impl IntoIterator<Item = (B256, Vec<u8>)>,
+            ) -> (B256, std::collections::HashMap<Nibbles, alloy_primitives::Bytes>) {
+                let leaves = leaves.into_iter().collect::<std::collections::BTreeMap<_, _>>();
+                let mut hash_builder = alloy_trie::HashBuilder::default()
+                    .with_proof_retainer(leaves.keys().map(Nibbles::unpack).collect());
+                for (key, value) in &leaves {
+                    hash_builder.add_leaf(Nibbles::unpack(key), value);
+                }
+                let root = hash_builder.root();
+                (root, hash_builder.take_proof_nodes().into_inner().into_iter().collect())
+            }
+
+            /// Returns the nodes of the given trie that prove the range between the boundaries.
+            fn range_proof(
+                nodes: &std::collections::HashMap<Nibbles, alloy_primitives::Bytes>,
+                boundaries: [B256; 2],
+            ) -> Vec<alloy_primitives::Bytes> {
+                let boundaries = boundaries.map(Nibbles::unpack);
+                nodes
+                    .iter()
+                    .filter(|(path, _)| {
+                        boundaries.iter().any(|boundary| boundary.starts_with(path))
+                    })
+                    .map(|(_, node)| node.clone())
+                    .collect()
+            }
+
+            /// Decodes a compact encoded trie path.
+            fn decode_path(path: &[u8]) -> Nibbles {
+                let nibbles = Nibbles::unpack(path);
+                if nibbles.get_unchecked(0) & 1 == 1 {
+                    nibbles.slice(1..)
+                } else {
+                    nibbles.slice(2..)
+                }
+            }
+        }
+
+        impl DownloadClient for StateSnapClient {
+            fn report_bad_message(&self, _peer_id: PeerId) {}
+            fn num_connected_peers(&self) -> usize {
+                1
+            }
+        }
+
+        impl SnapClient for StateSnapClient {
+            type AccountRangeOutput = <MockSnapClient as SnapClient>::AccountRangeOutput;
+            type StorageRangesOutput = <MockSnapClient as SnapClient>::StorageRangesOutput;
+            type ByteCodesOutput = <MockSnapClient as SnapClient>::ByteCodesOutput;
+            type TrieNodesOutput = <MockSnapClient as SnapClient>::TrieNodesOutput;
+
+            fn get_account_range_with_priority(
+                &self,
+                request: reth_eth_wire_types::snap::GetAccountRangeMessage,
+                _priority: Priority,
+            ) -> Self::AccountRangeOutput {
+                // Serves the entire range without a proof, unless the range is limited
+                let accounts = self
+                    .accounts
+                    .range(request.starting_hash..)
+                    .take(self.max_accounts.unwrap_or(usize::MAX))
+                    .map(|(hash, account)| {
+                        let mut body = Vec::new();
+                        let slim_hash = |hash: B256, empty: B256| {
+                            alloy_primitives::Bytes::from(
+                                if hash == empty { Vec::new() } else { hash.to_vec() },
+                            )
+                        };
+                        let fields = (
+                            account.nonce,
+                            account.balance,
+                            slim_hash(account.storage_root, alloy_trie::EMPTY_ROOT_HASH),
+                            slim_hash(account.code_hash, KECCAK_EMPTY),
+                        );
+                        let payload_length = fields.0.length() +
+                            fields.1.length() +
+                            fields.2.length() +
+                            fields.3.length();
+                        alloy_rlp::Header { list: true, payload_length }.encode(&mut body);
+                        fields.0.encode(&mut body);
+                        fields.1.encode(&mut body);
+                        fields.2.encode(&mut body);
+                        fields.3.encode(&mut body);
+                        reth_eth_wire_types::snap::AccountData { hash: *hash, body: body.into() }
+                    })
+                    .collect::<Vec<_>>();
+                let proof = if self.max_accounts.is_some() {
+                    let last = accounts.last().map_or(request.starting_hash, |data| data.hash);
+                    Self::range_proof(&self.account_nodes, [request.starting_hash, last])
+                } else {
+                    vec![]
+                };
+                future::ready(Ok(WithPeerId::new(
+                    PeerId::random(),
+                    reth_eth_wire_types::snap::AccountRangeMessage {
+                        request_id: request.request_id,
+                        accounts,
+                        proof,
+                    },
+                )))
+            }
+
+            fn get_storage_ranges_with_priority(
+                &self,
+                request: reth_eth_wire_types::snap::GetStorageRangesMessage,
+                _priority: Priority,
+            ) -> Self::StorageRangesOutput {
+                let mut slots = Vec::new();
+                let mut proof = Vec::new();
+                for hashed_address in &request.account_hashes {
+                    let storage = &self.storages[hashed_address];
+                    let max_slots = self.max_slots.unwrap_or(usize::MAX);
+                    let set = storage
+                        .range(request.starting_hash..)
+                        .take(max_slots)
+                        .map(|(slot, value)| reth_eth_wire_types::snap::StorageData {
+                            hash: *slot,
+                            data: alloy_rlp::encode(value).into(),
+                        })
+                        .collect::<Vec<_>>();
+
+                    // Incomplete sets and sets not starting at the first slot require a proof of
+                    // the range between the origin and the last slot and end the response
+                    let truncated = storage.range(request.starting_hash..).count() > set.len();
+                    if truncated || request.starting_hash != B256::ZERO {
+                        let last = set.last().map_or(request.starting_hash, |data| data.hash);
+                        proof.extend(Self::range_proof(
+                            &self.storage_nodes[hashed_address],
+                            [request.starting_hash, last],
+                        ));
+                        slots.push(set);
+                        break
+                    }
+                    slots.push(set);
+                }
+                future::ready(Ok(WithPeerId::new(
+                    PeerId::random(),
+                    reth_eth_wire_types::snap::StorageRangesMessage {
+                        request_id: request.request_id,
+                        slots,
+                        proof,
+                    },
+                )))
+            }
+
+            fn get_byte_codes_with_priority(
+                &self,
+                request: reth_eth_wire_types::snap::GetByteCodesMessage,
+                _priority: Priority,
+            ) -> Self::ByteCodesOutput {
+                let codes = request
+                    .hashes
+                    .iter()
+                    .filter_map(|hash| self.bytecodes.get(hash).cloned())
+                    .collect();
+                future::ready(Ok(WithPeerId::new(
+                    PeerId::random(),
+                    reth_eth_wire_types::snap::ByteCodesMessage {
+                        request_id: request.request_id,
+                        codes,
+                    },
+                )))
+            }
+
+            fn get_trie_nodes_with_priority(
+                &self,
+                request: reth_eth_wire_types::snap::GetTrieNodesMessage,
+                _priority: Priority,
+            ) -> Self::TrieNodesOutput {
+                let mut nodes = Vec::new();
+                for path in request.paths {
+                    if path.slot_paths.is_empty() {
+                        let path = Self::decode_path(&path.account_path);
+                        nodes.extend(self.account_nodes.get(&path).cloned());
+                    } else {
+                        let hashed_address = B256::from_slice(&path.account_path);
+                        for slot_path in path.slot_paths {
+                            let slot_path = Self::decode_path(&slot_path);
+                            nodes.extend(
+                                self.storage_nodes[&hashed_address].get(&slot_path).cloned(),
+                            );
+                        }
+                    }
+                }
+                future::ready(Ok(WithPeerId::new(
+                    PeerId::random(),
+                    reth_eth_wire_types::snap::TrieNodesMessage {
+                        request_id: request.request_id,
+                        nodes,
+                    },
+                )))
+            }
+        }
+
+        /// Runs the snap sync stage against the given client until it is done.
+        fn run_snap_sync(
+            db: &crate::test_utils::TestStageDB,
+            client: StateSnapClient,
+            state_root: B256,
+        ) {
+            let mut stage = crate::stages::SnapSyncStage::new(
+                SnapSyncTestRunner::default().config,
+                std::sync::Arc::new(client),
+            )
+            .with_header_receiver(snap_header_receiver(state_root));
+            let input = ExecInput { target: Some(1), checkpoint: None };
+
+            for _ in 0..100 {
+                let provider = db.factory.provider_rw().unwrap();
+                let output = Stage::execute(&mut stage, &*provider, input).unwrap();
+                provider.commit().unwrap();
+                if output.done {
+                    assert_eq!(output.checkpoint, StageCheckpoint::new(1));
+                    return
+                }
+            }
+            panic!("snap sync did not finish");
+        }
+
+        #[test]
+        fn test_snap_sync_downloads_state() {
+            use std::collections::BTreeMap;
+
+            let storage = (0u64..20)
+                .map(|slot| (keccak256(B256::with_last_byte(slot as u8)), U256::from(slot + 1)))
+                .collect::<BTreeMap<_, _>>();
+            let accounts = vec![
+                (keccak256([1]), 1, BTreeMap::new(), None),
+                (keccak256([2]), 2, storage.clone(), Some(vec![0x60, 0x00])),
+                (keccak256([3]), 3, BTreeMap::new(), Some(vec![0x60, 0x01])),
+            ];
+            let (client, state_root) = StateSnapClient::new(accounts);
+
+            let db = crate::test_utils::TestStageDB::default();
+            run_snap_sync(&db, client, state_root);
+
+            assert_eq!(db.table::<tables::HashedAccounts>().unwrap().len(), 3);
+            let storage_entries = db.table::<tables::HashedStorages>().unwrap();
+            assert_eq!(storage_entries.len(), storage.len());
+            assert!(storage_entries.iter().all(|(hashed_address, entry)| {
+                *hashed_address == keccak256([2]) && storage[&entry.key] == entry.value
+            }));
+            assert_eq!(db.table::<tables::Bytecodes>().unwrap().len(), 2);
+
+            // The stage progress is cleared once the sync is finished
+            let provider = db.factory.provider().unwrap();
+            assert_eq!(
+                reth_provider::StageCheckpointReader::get_stage_checkpoint_progress(
+                    &provider,
+                    StageId::SnapSync
+                )
+                .unwrap(),
+                Some(vec![])
+            );
+        }
+
+        #[test]
+        fn test_snap_sync_downloads_storage_in_chunks() {
+            use std::collections::BTreeMap;
+
+            let storage = (0u64..50)
+                .map(|slot| (keccak256(B256::with_last_byte(slot as u8)), U256::from(slot + 1)))
+                .collect::<BTreeMap<_, _>>();
+            let accounts = vec![
+                (keccak256([1]), 1, storage.clone(), None),
+                (keccak256([2]), 2, storage.clone(), None),
+            ];
+            let (mut client, state_root) = StateSnapClient::new(accounts);
+            client.max_slots = Some(8);
+
+            let db = crate::test_utils::TestStageDB::default();
+            run_snap_sync(&db, client, state_root);
+
+            let storage_entries = db.table::<tables::HashedStorages>().unwrap();
+            assert_eq!(storage_entries.len(), storage.len() * 2);
+            assert!(storage_entries.iter().all(|(_, entry)| storage[&entry.key] == entry.value));
+        }
+
+        #[test]
+        fn test_snap_sync_downloads_accounts_in_chunks() {
+            use std::collections::BTreeMap;
+
+            let accounts = (0u8..10)
+                .map(|i| (keccak256([i]), i as u64 + 1, BTreeMap::new(), None))
+                .collect::<Vec<_>>();
+            let (mut client, state_root) = StateSnapClient::new(accounts);
+            client.max_accounts = Some(3);
+
+            let db = crate::test_utils::TestStageDB::default();
+            run_snap_sync(&db, client, state_root);
+
+            let accounts = db.table::<tables::HashedAccounts>().unwrap();
+            assert_eq!(accounts.len(), 10);
+            assert!(accounts
+                .iter()
+                .all(|(_, account)| account.balance == U256::from(account.nonce)));
+        }
+
+        #[test]
+        fn test_snap_sync_heals_stale_state() {
+            use std::collections::BTreeMap;
+
+            let storage = (0u64..4)
+                .map(|slot| (keccak256(B256::with_last_byte(slot as u8)), U256::from(slot + 1)))
+                .collect::<BTreeMap<_, _>>();
+            let accounts = vec![
+                (keccak256([1]), 1, storage.clone(), None),
+                (keccak256([2]), 2, BTreeMap::new(), None),
+            ];
+            let (client, state_root) = StateSnapClient::new(accounts);
+
+            // Left over state that is not part of the served state
+            let db = crate::test_utils::TestStageDB::default();
+            let stale_account = keccak256([0xaa]);
+            let stale_slot = keccak256([0xbb]);
+            db.commit(|tx| {
+                tx.put::<tables::HashedAccounts>(stale_account, Account::default())?;
+                tx.put::<tables::HashedStorages>(
+                    keccak256([1]),
+                    reth_primitives_traits::StorageEntry { key: stale_slot, value: U256::from(1) },
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+            // Mark the account ranges as downloaded, so the stale state is only removed by
+            // healing
+            let progress = reth_stages_api::SnapSyncCheckpoint {
+                state_root,
+                healing: true,
+                ..Default::default()
+            };
+            let mut buf = Vec::new();
+            reth_codecs::Compact::to_compact(&progress, &mut buf);
+            let provider = db.factory.provider_rw().unwrap();
+            provider.save_stage_checkpoint_progress(StageId::SnapSync, buf).unwrap();
+            provider.commit().unwrap();
+
+            run_snap_sync(&db, client, state_root);
+
+            let accounts = db.table::<tables::HashedAccounts>().unwrap();
+            assert_eq!(
+                accounts.iter().map(|(hashed_address, _)| *hashed_address).collect::<Vec<_>>(),
+                {
+                    let mut expected = vec![keccak256([1]), keccak256([2])];
+                    expected.sort();
+                    expected
+                }
+            );
+            let storage_entries = db.table::<tables::HashedStorages>().unwrap();
+            assert_eq!(storage_entries.len(), storage.len());
+            assert!(storage_entries.iter().all(|(_, entry)| entry.key != stale_slot));
+
+            // The trie updated after healing matches a trie built from scratch
+            use reth_provider::TrieWriter;
+            use reth_trie_db::DatabaseStateRoot;
+            let account_nodes = db.table::<tables::AccountsTrie>().unwrap();
+            let storage_nodes = db.table::<tables::StoragesTrie>().unwrap();
+            db.commit(|tx| {
+                tx.clear::<tables::AccountsTrie>()?;
+                tx.clear::<tables::StoragesTrie>()?;
+                Ok(())
+            })
+            .unwrap();
+            let provider = db.factory.provider_rw().unwrap();
+            let (root, updates) = reth_trie::StateRoot::from_tx(provider.tx_ref())
+                .root_with_updates()
+                .unwrap();
+            assert_eq!(root, state_root);
+            provider.write_trie_updates(&updates).unwrap();
+            provider.commit().unwrap();
+            assert_eq!(db.table::<tables::AccountsTrie>().unwrap(), account_nodes);
+            assert_eq!(db.table::<tables::StoragesTrie>().unwrap(), storage_nodes);
+        }
 }
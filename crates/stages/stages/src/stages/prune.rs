@@ -1,8 +1,8 @@
 use reth_db_api::{table::Value, transaction::DbTxMut};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    BlockReader, DBProvider, PruneCheckpointReader, PruneCheckpointWriter,
-    StaticFileProviderFactory,
+    AddressAppearancesProvider, BlockReader, DBProvider, LogIndexProvider, PruneCheckpointReader,
+    PruneCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_prune::{
     PruneMode, PruneModes, PruneSegment, PrunerBuilder, SegmentOutput, SegmentOutputCheckpoint,
@@ -42,6 +42,8 @@ where
         + PruneCheckpointReader
         + PruneCheckpointWriter
         + BlockReader
+        + AddressAppearancesProvider
+        + LogIndexProvider
         + StaticFileProviderFactory<
             Primitives: NodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>,
         >,
@@ -133,6 +135,8 @@ where
         + PruneCheckpointReader
         + PruneCheckpointWriter
         + BlockReader
+        + AddressAppearancesProvider
+        + LogIndexProvider
         + StaticFileProviderFactory<
             Primitives: NodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>,
         >,
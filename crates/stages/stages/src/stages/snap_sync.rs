@@ -1,26 +1,41 @@
-use alloy_primitives::B256;
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{keccak256, map::HashMap, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use alloy_trie::{
+    nodes::{encode_path_leaf, RlpNode, TrieNode},
+    HashBuilder, Nibbles, TrieAccount, EMPTY_ROOT_HASH,
+};
+use futures_util::FutureExt;
+use reth_codecs::Compact;
 use reth_config::config::SnapSyncConfig;
 use reth_db_api::{
-    cursor::{DbCursorRO, DbCursorRW},
-    table::Compress,
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
     tables,
     transaction::{DbTx, DbTxMut},
-    RawKey, RawTable, RawValue,
+    DatabaseError,
 };
-use reth_eth_wire_types::snap::{AccountRangeMessage, GetAccountRangeMessage};
-use reth_network_p2p::{snap::client::SnapClient, priority::Priority};
-use reth_provider::{
-    DBProvider,
+use reth_eth_wire_types::snap::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage, TriePath,
 };
-use reth_primitives_traits::SealedHeader;
-use alloy_trie::TrieAccount;
-use alloy_rlp::Decodable;
+use reth_network_p2p::{
+    error::{PeerRequestResult, RequestError},
+    priority::Priority,
+    snap::client::SnapClient,
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use reth_primitives_traits::{Account, Bytecode, SealedHeader, StorageEntry};
+use reth_provider::{DBProvider, StageCheckpointReader, StageCheckpointWriter, TrieWriter};
 use reth_stages_api::{
-    ExecInput, ExecOutput, Stage, StageError,
-    StageId, UnwindInput, UnwindOutput,
+    ExecInput, ExecOutput, SnapSyncCheckpoint, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
 };
+use reth_trie::StateRoot;
+use reth_trie_common::{prefix_set::TriePrefixSetsMut, StoredNibbles, StoredNibblesSubKey};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
 use std::{
-    collections::HashMap,
+    cmp::Ordering,
+    collections::{BTreeSet, VecDeque},
     future::Future,
     pin::Pin,
     sync::Arc,
@@ -30,8 +45,120 @@ use std::{
 use tokio::sync::watch;
 use tracing::*;
 
-/// Snap sync stage for downloading trie data ranges from peers.
+/// The maximum number of accounts to request storage ranges for in a single request.
+const MAX_STORAGE_ACCOUNTS_PER_REQUEST: usize = 128;
+
+/// The maximum number of bytecodes to request in a single request.
+const MAX_BYTECODES_PER_REQUEST: usize = 64;
+
+/// The maximum number of trie nodes to request in a single request.
+const MAX_TRIE_NODES_PER_REQUEST: usize = 128;
+
+/// The highest possible account or storage slot hash.
+const MAX_HASH: B256 = B256::repeat_byte(0xff);
+
+/// A request that was sent to a peer, along with the work it covers.
+#[derive(Debug, Clone)]
+enum SnapRequest {
+    /// Request for the next account range.
+    AccountRange(GetAccountRangeMessage),
+    /// Request for the storage of the accounts at the front of the storage queue.
+    StorageRanges {
+        /// The request sent to the peer.
+        request: GetStorageRangesMessage,
+        /// The storage roots of the requested accounts.
+        storage_roots: Vec<B256>,
+    },
+    /// Request for missing contract bytecodes.
+    ByteCodes(GetByteCodesMessage),
+    /// Request for trie nodes that differ from the local trie.
+    TrieNodes {
+        /// The request sent to the peer.
+        request: GetTrieNodesMessage,
+        /// The nodes covered by the request, in request order.
+        tasks: Vec<HealTask>,
+    },
+}
+
+impl SnapRequest {
+    /// Returns the id of the request.
+    const fn request_id(&self) -> u64 {
+        match self {
+            Self::AccountRange(request) => request.request_id,
+            Self::StorageRanges { request, .. } => request.request_id,
+            Self::ByteCodes(request) => request.request_id,
+            Self::TrieNodes { request, .. } => request.request_id,
+        }
+    }
+}
+
+/// A response to a [`SnapRequest`].
+#[derive(Debug)]
+enum SnapResponse {
+    AccountRange(AccountRangeMessage),
+    StorageRanges(StorageRangesMessage),
+    ByteCodes(ByteCodesMessage),
+    TrieNodes(TrieNodesMessage),
+}
+
+impl SnapResponse {
+    /// Returns `true` if the response is empty because the peer doesn't serve the requested
+    /// state.
+    ///
+    /// An empty account range without a proof is only a valid response for an empty state.
+    fn is_unserved(&self, request: &SnapRequest) -> bool {
+        match (self, request) {
+            (Self::AccountRange(response), SnapRequest::AccountRange(request)) => {
+                response.accounts.is_empty() &&
+                    response.proof.is_empty() &&
+                    request.root_hash != EMPTY_ROOT_HASH
+            }
+            (Self::AccountRange(_), _) => false,
+            (Self::StorageRanges(response), _) => response.slots.is_empty(),
+            (Self::ByteCodes(response), _) => response.codes.is_empty(),
+            (Self::TrieNodes(response), _) => response.nodes.is_empty(),
+        }
+    }
+}
+
+/// The future resolving to the response of a [`SnapRequest`].
+type SnapResponseFuture =
+    Pin<Box<dyn Future<Output = PeerRequestResult<SnapResponse>> + Send + Sync>>;
+
+/// A trie node that has to be downloaded while healing.
+#[derive(Debug, Clone)]
+struct HealTask {
+    /// The account owning the storage trie, `None` for nodes of the account trie.
+    hashed_address: Option<B256>,
+    /// The path of the node in its trie.
+    path: Nibbles,
+    /// The expected hash of the node.
+    hash: B256,
+}
+
+/// The account range that was written to the database but whose storage and bytecodes might not
+/// be fully downloaded yet.
+#[derive(Debug, Clone, Copy)]
+struct AccountRangeProgress {
+    /// The last account hash of the range, `None` if the range was empty.
+    last: Option<B256>,
+    /// Whether there are no more accounts after this range.
+    complete: bool,
+}
+
+/// Snap sync stage for downloading the state of a recent block from peers.
 /// Replaces `SenderRecoveryStage`, `ExecutionStage` and `PruneSenderRecoveryStage` when enabled.
+///
+/// The stage downloads the state in two phases:
+///  1. Account ranges are requested in order. For every range the storage of the accounts is
+///     downloaded, large storage tries in multiple chunks, together with all missing bytecodes.
+///  2. Once all ranges are downloaded, the trie is built from the hashed state. If the resulting
+///     state root does not match the state root of the target header, the differing trie nodes are
+///     downloaded top-down and the hashed state is repaired until the roots match. After every
+///     healing pass, only the parts of the trie below the repaired keys are updated.
+///
+/// Progress of both phases is persisted in a [`SnapSyncCheckpoint`], so the stage can resume in
+/// the middle of a phase.
 pub struct SnapSyncStage<C: SnapClient> {
     /// Configuration for the stage
     pub config: SnapSyncConfig,
@@ -41,18 +168,33 @@ pub struct SnapSyncStage<C: SnapClient> {
     pub header_receiver: Option<watch::Receiver<SealedHeader>>,
     /// Request ID counter for snap requests
     pub request_id_counter: u64,
-            /// Pending network requests
-            pending_requests: HashMap<u64, Pin<Box<dyn Future<Output = reth_network_p2p::error::PeerRequestResult<reth_eth_wire_types::snap::AccountRangeMessage>> + Send + Sync + Unpin>>>,
+    /// Pending network requests
+    pending_requests: HashMap<u64, (SnapRequest, SnapResponseFuture)>,
     /// Request start times for timeout tracking
     request_start_times: HashMap<u64, Instant>,
     /// Request retry counts for failed requests
     pub request_retry_counts: HashMap<u64, u32>,
-    /// Completed account ranges ready for processing
-    completed_ranges: Vec<AccountRangeMessage>,
+    /// Failed requests that are sent again
+    retry_requests: Vec<SnapRequest>,
+    /// Completed requests ready for processing
+    completed_requests: Vec<(SnapRequest, WithPeerId<SnapResponse>)>,
     /// Last known state root to detect changes
     last_known_state_root: Option<B256>,
-    /// Last processed range for progress persistence
+    /// Last processed account range for progress persistence
     pub last_processed_range: Option<(B256, B256)>,
+    /// Progress of the stage, loaded from the database on first execution
+    progress: Option<SnapSyncCheckpoint>,
+    /// The account range that is currently being completed
+    account_range: Option<AccountRangeProgress>,
+    /// Accounts of the current range with their storage roots whose storage is not downloaded yet
+    pending_storages: VecDeque<(B256, B256)>,
+    /// Code hashes of bytecodes that are not downloaded yet
+    pending_bytecodes: BTreeSet<B256>,
+    /// Trie nodes that are not downloaded yet in the current healing pass
+    pending_heal: VecDeque<HealTask>,
+    /// Hashed keys that were healed since the trie was last updated, `None` if the trie has not
+    /// been built yet
+    heal_prefix_sets: Option<TriePrefixSetsMut>,
 }
 
 impl<C> std::fmt::Debug for SnapSyncStage<C>
@@ -65,9 +207,22 @@ where
             .field("snap_client", &"<SnapClient>")
             .field("header_receiver", &self.header_receiver.is_some())
             .field("request_id_counter", &self.request_id_counter)
-            .field("pending_requests", &format!("<{} pending requests>", self.pending_requests.len()))
-            .field("request_start_times", &format!("<{} tracked requests>", self.request_start_times.len()))
-            .field("completed_ranges", &format!("<{} completed ranges>", self.completed_ranges.len()))
+            .field(
+                "pending_requests",
+                &format!("<{} pending requests>", self.pending_requests.len()),
+            )
+            .field(
+                "request_start_times",
+                &format!("<{} tracked requests>", self.request_start_times.len()),
+            )
+            .field(
+                "completed_requests",
+                &format!("<{} completed requests>", self.completed_requests.len()),
+            )
+            .field("progress", &self.progress)
+            .field("pending_storages", &self.pending_storages.len())
+            .field("pending_bytecodes", &self.pending_bytecodes.len())
+            .field("pending_heal", &self.pending_heal.len())
             .finish()
     }
 }
@@ -78,31 +233,9 @@ where
 {
     /// Create a no-op waker for polling futures synchronously
     pub fn noop_waker() -> Waker {
-        use std::task::{RawWaker, RawWakerVTable};
-        
-        unsafe fn noop_clone(_data: *const ()) -> RawWaker {
-            noop_raw_waker()
-        }
-        
-        unsafe fn noop(_data: *const ()) {}
-        
-        unsafe fn noop_wake(_data: *const ()) {}
-        
-        unsafe fn noop_wake_by_ref(_data: *const ()) {}
-        
-        const NOOP_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(
-            noop_clone,
-            noop_wake,
-            noop_wake_by_ref,
-            noop,
-        );
-        
-        unsafe fn noop_raw_waker() -> RawWaker {
-            RawWaker::new(std::ptr::null(), &NOOP_WAKER_VTABLE)
-        }
-        
-        unsafe { Waker::from_raw(noop_raw_waker()) }
+        Waker::noop().clone()
     }
+
     /// Create a new `SnapSyncStage`
     pub fn new(config: SnapSyncConfig, snap_client: Arc<C>) -> Self {
         Self {
@@ -110,12 +243,19 @@ where
             snap_client,
             header_receiver: None,
             request_id_counter: 0,
-            pending_requests: HashMap::new(),
-            request_start_times: HashMap::new(),
-            request_retry_counts: HashMap::new(),
-            completed_ranges: Vec::new(),
+            pending_requests: HashMap::default(),
+            request_start_times: HashMap::default(),
+            request_retry_counts: HashMap::default(),
+            retry_requests: Vec::new(),
+            completed_requests: Vec::new(),
             last_known_state_root: None,
             last_processed_range: None,
+            progress: None,
+            account_range: None,
+            pending_storages: VecDeque::new(),
+            pending_bytecodes: BTreeSet::new(),
+            pending_heal: VecDeque::new(),
+            heal_prefix_sets: None,
         }
     }
 
@@ -131,232 +271,51 @@ where
         Provider: DBProvider,
     {
         let mut cursor = provider.tx_ref().cursor_read::<tables::HashedAccounts>()?;
-        match cursor.first()? {
-            Some(_) => Ok(false), // Database has accounts
-            None => Ok(true),     // Database is empty
-        }
+        Ok(cursor.first()?.is_none())
     }
 
     /// Get the last hashed account from the database
-    pub fn get_last_hashed_account<Provider>(&self, provider: &Provider) -> Result<Option<B256>, StageError>
+    pub fn get_last_hashed_account<Provider>(
+        &self,
+        provider: &Provider,
+    ) -> Result<Option<B256>, StageError>
     where
         Provider: DBProvider,
     {
         let mut cursor = provider.tx_ref().cursor_read::<tables::HashedAccounts>()?;
-        match cursor.last()? {
-            Some((key, _)) => Ok(Some(key)),
-            None => Ok(None),
-        }
-    }
-
-    /// Get the next starting point for snap sync based on current database state
-    /// This implements proper state tracking for snap sync resumption
-    pub fn get_next_sync_starting_point<Provider>(&self, provider: &Provider) -> Result<B256, StageError>
-    where
-        Provider: DBProvider,
-    {
-        // Check if we have any accounts in the database
-        if self.is_hashed_state_empty(provider)? {
-            // If empty, start from the beginning
-            return Ok(B256::ZERO);
-        }
-
-        // Use stored progress if available, otherwise fall back to database probing
-        if let Some((_start, end)) = self.last_processed_range {
-            // Calculate the next starting point after the last processed range
-            let next_start = self.calculate_next_hash_in_lexicographic_order(end, 1)?;
-            
-            // Ensure we don't go beyond the maximum
-            let max_hash = B256::from([0xff; 32]);
-            if next_start >= max_hash {
-                return Ok(max_hash);
-            }
-            
-            return Ok(next_start);
-        }
-
-        // Fallback to database probing for backward compatibility
-        let last_account = self.get_last_hashed_account(provider)?
-            .unwrap_or(B256::ZERO);
-        
-        // Calculate the next starting point after the last account
-        let next_start = self.calculate_next_hash_in_lexicographic_order(last_account, 1)?;
-        
-        // Ensure we don't go beyond the maximum
-        let max_hash = B256::from([0xff; 32]);
-        if next_start >= max_hash {
-            return Ok(max_hash);
-        }
-        
-        Ok(next_start)
+        Ok(cursor.last()?.map(|(key, _)| key))
     }
 
     /// Create account range request
-    pub fn create_account_range_request(&mut self, starting_hash: B256, limit_hash: B256) -> GetAccountRangeMessage {
-        self.request_id_counter += 1;
-        GetAccountRangeMessage {
-            request_id: self.request_id_counter,
-            root_hash: self.get_target_state_root().unwrap_or(B256::ZERO),
-            starting_hash,
-            limit_hash,
-            response_bytes: self.config.max_response_bytes,
-        }
+    pub fn create_account_range_request(
+        &mut self,
+        starting_hash: B256,
+        limit_hash: B256,
+    ) -> GetAccountRangeMessage {
+        let state_root = self.get_target_state_root().unwrap_or(B256::ZERO);
+        self.create_account_range_request_with_state_root(starting_hash, limit_hash, state_root)
     }
 
     /// Create a new account range request with explicit state root
     /// This method includes the state root in the request for proper snap sync validation
-    #[allow(clippy::missing_const_for_fn)]
-    pub fn create_account_range_request_with_state_root(&mut self, starting_hash: B256, limit_hash: B256, state_root: B256) -> GetAccountRangeMessage {
-        self.request_id_counter += 1;
+    pub const fn create_account_range_request_with_state_root(
+        &mut self,
+        starting_hash: B256,
+        limit_hash: B256,
+        state_root: B256,
+    ) -> GetAccountRangeMessage {
         GetAccountRangeMessage {
-            request_id: self.request_id_counter,
-            root_hash: state_root, // Use the explicit state root
+            request_id: self.next_request_id(),
+            root_hash: state_root,
             starting_hash,
             limit_hash,
             response_bytes: self.config.max_response_bytes,
         }
     }
 
-    /// Process account ranges and insert into database
-    pub fn process_account_ranges<Provider>(
-        &self,
-        provider: &Provider,
-        account_ranges: Vec<AccountRangeMessage>,
-    ) -> Result<usize, StageError>
-    where
-        Provider: DBProvider<Tx: DbTxMut>,
-    {
-        let start_time = std::time::Instant::now();
-        let mut processed = 0;
-
-        for account_range in account_ranges {
-            // Verify proof before processing
-            if !self.verify_account_range_proof(&account_range)? {
-                return Err(StageError::Fatal("Account range proof verification failed".into()));
-            }
-
-            // Get write cursor for HashedAccounts table
-            let mut cursor = provider.tx_ref().cursor_write::<RawTable<tables::HashedAccounts>>()?;
-
-            // Process each account in the range
-            for account_data in &account_range.accounts {
-                // Decode account data
-                let trie_account = TrieAccount::decode(&mut account_data.body.as_ref())
-                    .map_err(|e| StageError::Fatal(format!("Failed to decode account: {}", e).into()))?;
-
-                // Convert to Account type for database storage
-                let account = reth_primitives_traits::Account {
-                    nonce: trie_account.nonce,
-                    balance: trie_account.balance,
-                    bytecode_hash: if trie_account.code_hash == B256::ZERO { None } else { Some(trie_account.code_hash) },
-                };
-
-                // Insert account data into database
-                cursor.insert(
-                    RawKey::new(account_data.hash),
-                    &RawValue::from_vec(account.compress())
-                )?;
-
-                debug!(
-                    target: "sync::stages::snap_sync",
-                    account_hash = ?account_data.hash,
-                    nonce = ?account.nonce,
-                    balance = ?account.balance,
-                    "Inserted account into database"
-                );
-                
-                processed += 1;
-            }
-        }
-
-        let duration = start_time.elapsed();
-        info!(
-            target: "sync::stages::snap_sync",
-            processed_accounts = processed,
-            duration_ms = duration.as_millis(),
-            accounts_per_second = if duration.as_secs() > 0 { processed as u64 / duration.as_secs() } else { 0 },
-            "Processed account ranges"
-        );
-
-        Ok(processed)
-    }
-
-    /// Verify account range proof using Merkle proof verification
-    /// Snap protocol proofs are for range boundaries, not individual accounts
-    fn verify_account_range_proof(&self, account_range: &AccountRangeMessage) -> Result<bool, StageError> {
-        use alloy_trie::proof::verify_proof;
-        use reth_trie_common::Nibbles;
-        
-        // If no accounts, proof should be empty or contain only empty root
-        if account_range.accounts.is_empty() {
-            return Ok(true);
-        }
-        
-        // If accounts present but no proof, this is invalid
-        if account_range.proof.is_empty() {
-            return Err(StageError::Fatal("Account range has accounts but no proof".into()));
-        }
-        
-        // Get target state root for verification
-        let target_state_root = self.get_target_state_root()
-            .ok_or_else(|| StageError::Fatal("No target state root available for proof verification".into()))?;
-        
-        // For snap protocol, we need to verify the range boundary proof
-        // The proof should prove the range from first account to last account
-        let first_account = account_range.accounts.first().unwrap();
-        let last_account = account_range.accounts.last().unwrap();
-        
-        // Convert account hashes to nibbles for proof verification
-        let first_nibbles = Nibbles::unpack(first_account.hash);
-        let last_nibbles = Nibbles::unpack(last_account.hash);
-        
-        // Verify the range boundary proof
-        // This is a simplified verification - in practice, we'd need to verify
-        // that the proof covers the entire range from first to last account
-        match verify_proof(
-            target_state_root,
-            first_nibbles,
-            Some(first_account.body.as_ref().to_vec()),
-            &account_range.proof,
-        ) {
-            Ok(()) => {
-                // Also verify the last account to ensure range coverage
-                match verify_proof(
-                    target_state_root,
-                    last_nibbles,
-                    Some(last_account.body.as_ref().to_vec()),
-                    &account_range.proof,
-                ) {
-                    Ok(()) => Ok(true),
-                    Err(e) => {
-                        warn!(
-                            target: "sync::stages::snap_sync",
-                            account_hash = ?last_account.hash,
-                            error = %e,
-                            "Account range proof verification failed for last account"
-                        );
-                        Err(StageError::Fatal(format!("Account range proof verification failed: {}", e).into()))
-                    }
-                }
-            }
-            Err(e) => {
-                warn!(
-                    target: "sync::stages::snap_sync",
-                    account_hash = ?first_account.hash,
-                    error = %e,
-                    "Account range proof verification failed for first account"
-                );
-                Err(StageError::Fatal(format!("Account range proof verification failed: {}", e).into()))
-            }
-        }
-    }
-
     /// Get current target state root from header receiver
     pub fn get_target_state_root(&self) -> Option<B256> {
-        self.header_receiver.as_ref().map(|receiver| {
-            let header = receiver.borrow();
-            header.state_root
-        })
+        self.header_receiver.as_ref().map(|receiver| receiver.borrow().state_root)
     }
 
     /// Check if the current state root has changed since the last check
@@ -368,110 +327,172 @@ where
 
     /// Calculate the next trie range for snap sync requests
     /// This implements proper trie range calculation based on the snap protocol
-    pub fn calculate_next_trie_range(&self, current: B256, max: B256) -> Result<(B256, B256), StageError> {
-        // For snap sync, we need to traverse the trie in lexicographic order
-        // The range should be calculated based on the trie structure, not arbitrary hash values
-        
+    pub fn calculate_next_trie_range(
+        &self,
+        current: B256,
+        max: B256,
+    ) -> Result<(B256, B256), StageError> {
         // Calculate optimal range size based on max_response_bytes
-        // This provides better sync efficiency than a fixed range size
         let optimal_range_size = self.calculate_optimal_range_size();
-        
-        // For snap sync, we need to calculate the next range in lexicographic order
-        // This is a simplified implementation that increments the hash
+
         let next = self.calculate_next_hash_in_lexicographic_order(current, optimal_range_size)?;
-        
+
         // Ensure we don't exceed the maximum
         let range_end = if next > max { max } else { next };
-        
-        // For snap sync, we want to ensure we don't create overlapping ranges
-        // and that we make meaningful progress through the trie
+
+        // Ensure we don't create overlapping ranges and make progress through the trie
         if range_end <= current {
-            return Err(StageError::Fatal("Range calculation resulted in no progress".into()));
+            return Err(StageError::Fatal("Range calculation resulted in no progress".into()))
         }
-        
+
         Ok((current, range_end))
     }
 
-    /// Calculate optimal range size based on max_response_bytes configuration
+    /// Calculate optimal range size based on `max_response_bytes` configuration
     /// This improves sync efficiency by adapting range size to response capacity
     pub fn calculate_optimal_range_size(&self) -> u64 {
-        // Estimate accounts per range based on average account size
-        // Average account size is approximately 100 bytes (nonce + balance + code_hash + storage_root)
+        // Average account size is approximately 100 bytes (nonce + balance + code_hash +
+        // storage_root)
         let estimated_account_size = 100;
         let max_accounts_per_range = self.config.max_response_bytes / estimated_account_size;
-        
+
         // Use the smaller of configured range_size or calculated optimal size
-        // This ensures we don't exceed response limits while maintaining efficiency
-        std::cmp::min(self.config.range_size, max_accounts_per_range as u64)
+        std::cmp::min(self.config.range_size, max_accounts_per_range)
     }
 
     /// Calculate the next hash in lexicographic order for trie traversal
-    /// This implements sophisticated hash arithmetic for proper trie range calculation
-    fn calculate_next_hash_in_lexicographic_order(&self, current: B256, range_size: u64) -> Result<B256, StageError> {
-        // Validate input parameters
+    fn calculate_next_hash_in_lexicographic_order(
+        &self,
+        current: B256,
+        range_size: u64,
+    ) -> Result<B256, StageError> {
         if range_size == 0 {
-            return Err(StageError::Fatal("Range size cannot be zero".into()));
-        }
-        
-        // For very large range sizes, we need to handle them differently
-        // The range_size represents the number of hash values to skip
-        if range_size >= 0x1000000000000000 {
-            // For very large ranges, we'll use a more sophisticated approach
-            // We'll increment by a smaller amount to avoid overflow
-            let safe_increment = 0x1000000; // Max 16M increment
-            return self.calculate_next_hash_in_lexicographic_order(current, safe_increment);
-        }
-        
-        // For large range sizes, we'll use a more reasonable increment
-        if range_size > 0x1000000 {
-            let safe_increment = 0x1000000; // Max 16M increment
-            return self.calculate_next_hash_in_lexicographic_order(current, safe_increment);
-        }
-        
-        // For very small range sizes, ensure we make at least some progress
-        if range_size < 1 {
-            return Err(StageError::Fatal("Range size must be at least 1".into()));
-        }
-        
-        // Implement sophisticated hash arithmetic for trie traversal
-        // This approach ensures proper lexicographic ordering of hash ranges
-        
-        // Convert to bytes for manipulation
-        let mut hash_bytes = current.as_slice().to_owned();
-        
-        // Implement proper byte-wise increment with carry
-        // This is more sophisticated than simple addition
-        let mut carry = range_size;
-        for i in (0..32).rev() {
-            let (new_val, new_carry) = hash_bytes[i].overflowing_add(carry as u8);
-            hash_bytes[i] = new_val;
-            carry = if new_carry { 1 } else { 0 };
-            if carry == 0 {
-                break;
-            }
-        }
-        
-        // If we overflowed, return the max value
-        if carry > 0 {
+            return Err(StageError::Fatal("Range size cannot be zero".into()))
+        }
+
+        // Cap the increment to avoid skipping large parts of the key space
+        let increment = U256::from(range_size.min(0x1000000));
+        let Some(next) = U256::from_be_bytes(current.0).checked_add(increment) else {
             warn!(
                 target: "sync::stages::snap_sync",
-                current = ?current,
-                range_size = range_size,
+                ?current,
+                range_size,
                 "Hash increment overflowed, using max value"
             );
-            return Ok(B256::from([0xff; 32]));
+            return Ok(MAX_HASH)
+        };
+
+        Ok(B256::from(next))
+    }
+
+    /// Handle request failure with retry logic.
+    ///
+    /// Returns `true` if the request should be sent again.
+    pub fn handle_request_failure(&mut self, request_id: u64, error: &RequestError) -> bool {
+        let retry_count = self.request_retry_counts.get(&request_id).copied().unwrap_or(0);
+        let max_retries = self.config.max_retries;
+
+        if retry_count < max_retries {
+            warn!(
+                target: "sync::stages::snap_sync",
+                request_id,
+                retry_count,
+                max_retries,
+                %error,
+                "Request failed, will retry"
+            );
+            self.request_retry_counts.insert(request_id, retry_count + 1);
+            true
+        } else {
+            error!(
+                target: "sync::stages::snap_sync",
+                request_id,
+                retry_count,
+                %error,
+                "Request failed after maximum retries, giving up"
+            );
+            self.request_retry_counts.remove(&request_id);
+            false
+        }
+    }
+
+    /// Returns the next request id.
+    const fn next_request_id(&mut self) -> u64 {
+        self.request_id_counter += 1;
+        self.request_id_counter
+    }
+
+    /// Returns the progress of the stage.
+    fn progress(&mut self) -> &mut SnapSyncCheckpoint {
+        self.progress.get_or_insert_with(Default::default)
+    }
+
+    /// Loads the persisted progress of the stage, if it was not loaded yet.
+    fn load_progress<Provider>(&mut self, provider: &Provider) -> Result<(), StageError>
+    where
+        Provider: StageCheckpointReader,
+    {
+        if self.progress.is_none() {
+            let progress = provider
+                .get_stage_checkpoint_progress(StageId::SnapSync)?
+                .filter(|buf| !buf.is_empty())
+                .map(|buf| SnapSyncCheckpoint::from_compact(&buf, buf.len()).0)
+                .unwrap_or_default();
+            debug!(target: "sync::stages::snap_sync", ?progress, "Loaded snap sync progress");
+            self.progress = Some(progress);
         }
-        
-        let result = B256::from_slice(&hash_bytes);
-        
-        // Validate that we actually made progress
-        if result <= current {
-            return Err(StageError::Fatal("Hash increment did not make progress".into()));
+        Ok(())
+    }
+
+    /// Persists the progress of the stage.
+    fn save_progress<Provider>(&self, provider: &Provider) -> Result<(), StageError>
+    where
+        Provider: StageCheckpointWriter,
+    {
+        let mut buf = Vec::new();
+        if let Some(progress) = &self.progress {
+            progress.to_compact(&mut buf);
         }
-        
-        Ok(result)
+        provider.save_stage_checkpoint_progress(StageId::SnapSync, buf)?;
+        Ok(())
     }
 
+    /// Drops all in-memory progress and pending requests.
+    fn reset(&mut self) {
+        self.pending_requests.clear();
+        self.request_start_times.clear();
+        self.request_retry_counts.clear();
+        self.retry_requests.clear();
+        self.completed_requests.clear();
+        self.last_known_state_root = None;
+        self.last_processed_range = None;
+        self.progress = None;
+        self.account_range = None;
+        self.pending_storages.clear();
+        self.pending_bytecodes.clear();
+        self.pending_heal.clear();
+        self.heal_prefix_sets = None;
+    }
+
+    /// Switches the stage to a new target state root.
+    ///
+    /// All requests for the previous root are dropped. Downloaded account ranges are kept, while
+    /// the current range is requested again against the new root. Any differences between the
+    /// roots are fixed by healing.
+    fn switch_state_root(&mut self, state_root: B256) {
+        self.pending_requests.clear();
+        self.request_start_times.clear();
+        self.retry_requests.clear();
+        self.completed_requests.clear();
+        self.account_range = None;
+        self.pending_storages.clear();
+        self.pending_heal.clear();
+
+        let progress = self.progress();
+        progress.state_root = state_root;
+        progress.storage_account = None;
+        progress.next_storage_slot = None;
+    }
 
     /// Start tracking a request for timeout purposes
     fn start_request_tracking(&mut self, request_id: u64) {
@@ -487,81 +508,800 @@ where
     fn check_timeouts(&self) -> Vec<u64> {
         let timeout_duration = Duration::from_secs(self.config.request_timeout_seconds);
         let now = Instant::now();
-        let mut timed_out = Vec::new();
+        self.request_start_times
+            .iter()
+            .filter(|(_, start_time)| now.duration_since(**start_time) > timeout_duration)
+            .map(|(request_id, _)| *request_id)
+            .collect()
+    }
+
+    /// Handle request timeout
+    fn handle_request_timeout(&mut self, request_id: u64) {
+        warn!(target: "sync::stages::snap_sync", request_id, "Request timed out");
+        self.complete_request_tracking(request_id);
+        if let Some((request, _)) = self.pending_requests.remove(&request_id) {
+            self.on_request_failure(request, &RequestError::Timeout);
+        }
+    }
 
-        for (request_id, start_time) in &self.request_start_times {
-            if now.duration_since(*start_time) > timeout_duration {
-                timed_out.push(*request_id);
+    /// Schedules a failed request for retry, or returns its work to the queues once it ran out of
+    /// retries.
+    fn on_request_failure(&mut self, request: SnapRequest, error: &RequestError) {
+        let request_id = request.request_id();
+        if self.handle_request_failure(request_id, error) {
+            match request {
+                // A partially processed response may have advanced the storage queue already, so
+                // the request is created again from the queue instead of being sent as is.
+                SnapRequest::StorageRanges { request, .. } => {
+                    if !self.pending_storages.is_empty() {
+                        let request =
+                            self.create_storage_ranges_request(request_id, request.root_hash);
+                        self.retry_requests.push(request);
+                    }
+                }
+                request => self.retry_requests.push(request),
             }
+            return
         }
 
-        timed_out
+        // Account and storage requests are derived from the progress and the storage queue and
+        // are created again on their own.
+        match request {
+            SnapRequest::ByteCodes(request) => self.pending_bytecodes.extend(request.hashes),
+            SnapRequest::TrieNodes { tasks, .. } => {
+                for task in tasks.into_iter().rev() {
+                    self.pending_heal.push_front(task);
+                }
+            }
+            SnapRequest::AccountRange(_) | SnapRequest::StorageRanges { .. } => {}
+        }
     }
 
-    /// Handle request timeout
-    fn handle_request_timeout(&mut self, request_id: u64) {
+    /// Handles an invalid response and reports the peer.
+    fn on_bad_response(&mut self, request: SnapRequest, peer_id: PeerId, reason: &str) {
         warn!(
             target: "sync::stages::snap_sync",
-            request_id = request_id,
-            "Request timed out"
+            request_id = request.request_id(),
+            %peer_id,
+            reason,
+            "Received bad snap response"
         );
-        
-        // Remove from pending requests
-        self.pending_requests.remove(&request_id);
-        self.request_start_times.remove(&request_id);
-        
-        // Note: Retry logic could be implemented here if needed
+        self.snap_client.report_bad_message(peer_id);
+        self.on_request_failure(request, &RequestError::BadResponse);
     }
 
-    /// Handle request failure with retry logic
-    pub fn handle_request_failure(&mut self, request_id: u64, error: &reth_network_p2p::error::RequestError) {
-        let retry_count = self.request_retry_counts.get(&request_id).copied().unwrap_or(0);
-        let max_retries = self.config.max_retries;
-        
-        if retry_count < max_retries {
-            warn!(
-                target: "sync::stages::snap_sync",
-                request_id = request_id,
-                retry_count = retry_count,
-                max_retries = max_retries,
-                error = %error,
-                "Request failed, will retry"
+    /// Sends the request to a peer and tracks the response.
+    fn send_request(&mut self, request: SnapRequest) {
+        let request_id = request.request_id();
+        let response: SnapResponseFuture = match &request {
+            SnapRequest::AccountRange(message) => Box::pin(
+                self.snap_client
+                    .get_account_range_with_priority(message.clone(), Priority::Normal)
+                    .map(|res| res.map(|res| res.map(SnapResponse::AccountRange))),
+            ),
+            SnapRequest::StorageRanges { request: message, .. } => Box::pin(
+                self.snap_client
+                    .get_storage_ranges_with_priority(message.clone(), Priority::Normal)
+                    .map(|res| res.map(|res| res.map(SnapResponse::StorageRanges))),
+            ),
+            SnapRequest::ByteCodes(message) => Box::pin(
+                self.snap_client
+                    .get_byte_codes_with_priority(message.clone(), Priority::Normal)
+                    .map(|res| res.map(|res| res.map(SnapResponse::ByteCodes))),
+            ),
+            SnapRequest::TrieNodes { request: message, .. } => Box::pin(
+                self.snap_client
+                    .get_trie_nodes_with_priority(message.clone(), Priority::Normal)
+                    .map(|res| res.map(|res| res.map(SnapResponse::TrieNodes))),
+            ),
+        };
+
+        trace!(target: "sync::stages::snap_sync", ?request, "Sending snap request");
+        self.pending_requests.insert(request_id, (request, response));
+        self.start_request_tracking(request_id);
+    }
+
+    /// Creates and sends the next requests for all kinds of work that are not in flight yet.
+    fn send_requests(&mut self, state_root: B256) {
+        for request in std::mem::take(&mut self.retry_requests) {
+            self.send_request(request);
+        }
+
+        let (mut accounts, mut storages, mut bytecodes, mut trie_nodes) =
+            (false, false, false, false);
+        for (request, _) in self.pending_requests.values() {
+            match request {
+                SnapRequest::AccountRange(_) => accounts = true,
+                SnapRequest::StorageRanges { .. } => storages = true,
+                SnapRequest::ByteCodes(_) => bytecodes = true,
+                SnapRequest::TrieNodes { .. } => trie_nodes = true,
+            }
+        }
+
+        let progress = *self.progress();
+
+        if !accounts && !progress.healing && self.account_range.is_none() {
+            let request = self.create_account_range_request_with_state_root(
+                progress.next_account,
+                MAX_HASH,
+                state_root,
             );
-            
-            // Increment retry count
-            self.request_retry_counts.insert(request_id, retry_count + 1);
-            
-            // Implement exponential backoff for retries
-            let backoff_duration = Duration::from_millis(1000 * (2_u64.pow(retry_count + 1)));
+            self.send_request(SnapRequest::AccountRange(request));
+        }
+
+        if !storages && !self.pending_storages.is_empty() {
+            let request_id = self.next_request_id();
+            let request = self.create_storage_ranges_request(request_id, state_root);
+            self.send_request(request);
+        }
+
+        if !bytecodes && !self.pending_bytecodes.is_empty() {
+            let hashes = std::iter::from_fn(|| self.pending_bytecodes.pop_first())
+                .take(MAX_BYTECODES_PER_REQUEST)
+                .collect();
+            let request = GetByteCodesMessage {
+                request_id: self.next_request_id(),
+                hashes,
+                response_bytes: self.config.max_response_bytes,
+            };
+            self.send_request(SnapRequest::ByteCodes(request));
+        }
+
+        if !trie_nodes && !self.pending_heal.is_empty() {
+            let count = self.pending_heal.len().min(MAX_TRIE_NODES_PER_REQUEST);
+            let tasks = self.pending_heal.drain(..count).collect::<Vec<_>>();
+            let paths = tasks
+                .iter()
+                .map(|task| {
+                    let path = Bytes::from(encode_path_leaf(&task.path, false).to_vec());
+                    match task.hashed_address {
+                        Some(hashed_address) => TriePath {
+                            account_path: Bytes::copy_from_slice(hashed_address.as_slice()),
+                            slot_paths: vec![path],
+                        },
+                        None => TriePath { account_path: path, slot_paths: Vec::new() },
+                    }
+                })
+                .collect();
+            let request = GetTrieNodesMessage {
+                request_id: self.next_request_id(),
+                root_hash: state_root,
+                paths,
+                response_bytes: self.config.max_response_bytes,
+            };
+            self.send_request(SnapRequest::TrieNodes { request, tasks });
+        }
+    }
+
+    /// Creates the storage ranges request for the accounts at the front of the storage queue.
+    ///
+    /// An account whose storage is downloaded in chunks is always requested on its own.
+    fn create_storage_ranges_request(&mut self, request_id: u64, state_root: B256) -> SnapRequest {
+        let progress = *self.progress();
+        let (account_hashes, storage_roots, starting_hash) = match (
+            self.pending_storages.front(),
+            progress.storage_account,
+            progress.next_storage_slot,
+        ) {
+            (Some(&(hashed_address, storage_root)), Some(storage_account), Some(next_slot))
+                if hashed_address == storage_account =>
+            {
+                (vec![hashed_address], vec![storage_root], next_slot)
+            }
+            _ => {
+                let (account_hashes, storage_roots) = self
+                    .pending_storages
+                    .iter()
+                    .take(MAX_STORAGE_ACCOUNTS_PER_REQUEST)
+                    .copied()
+                    .unzip();
+                (account_hashes, storage_roots, B256::ZERO)
+            }
+        };
+
+        let request = GetStorageRangesMessage {
+            request_id,
+            root_hash: state_root,
+            account_hashes,
+            starting_hash,
+            limit_hash: MAX_HASH,
+            response_bytes: self.config.max_response_bytes,
+        };
+        SnapRequest::StorageRanges { request, storage_roots }
+    }
+
+    /// Polls all pending requests and moves the finished ones to the completed requests.
+    fn poll_requests(&mut self, cx: &mut Context<'_>) {
+        let mut finished = Vec::new();
+        for (request_id, (_, response)) in &mut self.pending_requests {
+            if let Poll::Ready(result) = response.as_mut().poll(cx) {
+                finished.push((*request_id, result));
+            }
+        }
+
+        for (request_id, result) in finished {
+            self.complete_request_tracking(request_id);
+            let Some((request, _)) = self.pending_requests.remove(&request_id) else { continue };
+            match result {
+                Ok(response) => self.completed_requests.push((request, response)),
+                Err(error) => self.on_request_failure(request, &error),
+            }
+        }
+    }
+
+    /// Processes a completed request and writes the downloaded data to the database.
+    fn process_response<Provider>(
+        &mut self,
+        provider: &Provider,
+        request: SnapRequest,
+        response: WithPeerId<SnapResponse>,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        let (peer_id, response) = response.split();
+        let request_id = request.request_id();
+
+        // Peers answer requests for state they don't serve with empty responses. These are sent
+        // to another peer without reporting the peer, which is de-ranked by the fetcher instead.
+        if response.is_unserved(&request) {
             debug!(
                 target: "sync::stages::snap_sync",
-                request_id = request_id,
-                retry_count = retry_count + 1,
-                backoff_ms = backoff_duration.as_millis(),
-                "Scheduling retry with exponential backoff"
+                request_id,
+                %peer_id,
+                "Snap request not served"
             );
-            
-            // Note: In a real implementation, we would schedule the retry here
-            // For now, we rely on the timeout-based retry mechanism
+            self.on_request_failure(request, &RequestError::BadResponse);
+            return Ok(())
+        }
+
+        let result = match (&request, response) {
+            (SnapRequest::AccountRange(message), SnapResponse::AccountRange(response)) => {
+                self.process_account_range(provider, message, response)?
+            }
+            (
+                SnapRequest::StorageRanges { request: message, storage_roots },
+                SnapResponse::StorageRanges(response),
+            ) => self.process_storage_ranges(provider, message, storage_roots, response)?,
+            (SnapRequest::ByteCodes(message), SnapResponse::ByteCodes(response)) => {
+                self.process_bytecodes(provider, message, response)?
+            }
+            (SnapRequest::TrieNodes { tasks, .. }, SnapResponse::TrieNodes(response)) => {
+                self.process_trie_nodes(provider, tasks, response)?
+            }
+            _ => Err("unexpected response type"),
+        };
+
+        match result {
+            Ok(()) => {
+                self.request_retry_counts.remove(&request_id);
+            }
+            Err(reason) => self.on_bad_response(request, peer_id, reason),
+        }
+        Ok(())
+    }
+
+    /// Verifies the account range and writes the accounts to the database.
+    ///
+    /// Accounts with storage are queued for storage downloads and missing bytecodes are queued for
+    /// bytecode downloads.
+    fn process_account_range<Provider>(
+        &mut self,
+        provider: &Provider,
+        request: &GetAccountRangeMessage,
+        response: AccountRangeMessage,
+    ) -> Result<Result<(), &'static str>, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        let mut accounts = Vec::with_capacity(response.accounts.len());
+        let mut last = None;
+        for data in response.accounts {
+            if data.hash < request.starting_hash || last.is_some_and(|last| data.hash <= last) {
+                return Ok(Err("accounts are not ordered"))
+            }
+            let Ok(account) = decode_slim_account(&data.body) else {
+                return Ok(Err("invalid account encoding"))
+            };
+            last = Some(data.hash);
+            accounts.push((data.hash, account));
+        }
+
+        let complete = if response.proof.is_empty() {
+            // Without a proof, the response has to contain the entire trie.
+            if request.starting_hash != B256::ZERO {
+                return Ok(Err("missing account range proof"))
+            }
+            let mut hash_builder = HashBuilder::default();
+            for (hashed_address, account) in &accounts {
+                hash_builder.add_leaf(Nibbles::unpack(hashed_address), &alloy_rlp::encode(account));
+            }
+            if hash_builder.root() != request.root_hash {
+                return Ok(Err("account range does not match state root"))
+            }
+            true
         } else {
-            error!(
-                target: "sync::stages::snap_sync",
-                request_id = request_id,
-                retry_count = retry_count,
-                error = %error,
-                "Request failed after maximum retries, giving up"
-            );
-            
-            // Remove from tracking
-            self.request_retry_counts.remove(&request_id);
+            let entries = accounts
+                .iter()
+                .map(|(hashed_address, account)| (*hashed_address, alloy_rlp::encode(account)))
+                .collect::<Vec<_>>();
+            let proof = proof_nodes(&response.proof);
+            // An empty range is only complete if the proof shows that there are no accounts at
+            // or after the starting hash.
+            match verify_range_proof(request.root_hash, &proof, request.starting_hash, &entries) {
+                Some(has_more) => !has_more,
+                None => return Ok(Err("invalid account range proof")),
+            }
+        };
+
+        let progress = *self.progress();
+        let tx = provider.tx_ref();
+        let mut accounts_cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+        let mut bytecodes_cursor = tx.cursor_read::<tables::Bytecodes>()?;
+        for (hashed_address, account) in &accounts {
+            accounts_cursor.upsert(
+                *hashed_address,
+                &Account {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    bytecode_hash: (account.code_hash != KECCAK_EMPTY).then_some(account.code_hash),
+                },
+            )?;
+
+            if account.code_hash != KECCAK_EMPTY &&
+                bytecodes_cursor.seek_exact(account.code_hash)?.is_none()
+            {
+                self.pending_bytecodes.insert(account.code_hash);
+            }
+
+            // Storage of accounts before the persisted storage progress is already downloaded.
+            if account.storage_root == EMPTY_ROOT_HASH {
+                tx.delete::<tables::HashedStorages>(*hashed_address, None)?;
+            } else if progress.storage_account.is_none_or(|storage| *hashed_address >= storage) {
+                self.pending_storages.push_back((*hashed_address, account.storage_root));
+            }
+        }
+
+        debug!(
+            target: "sync::stages::snap_sync",
+            starting_hash = ?request.starting_hash,
+            ?last,
+            accounts = accounts.len(),
+            pending_storages = self.pending_storages.len(),
+            pending_bytecodes = self.pending_bytecodes.len(),
+            "Processed account range"
+        );
+
+        if let Some(last) = last {
+            self.last_processed_range = Some((request.starting_hash, last));
+        }
+        self.account_range = Some(AccountRangeProgress { last, complete });
+        self.progress().storage_account = self.pending_storages.front().map(|(hash, _)| *hash);
+        Ok(Ok(()))
+    }
+
+    /// Verifies the storage ranges and writes the storage slots to the database.
+    fn process_storage_ranges<Provider>(
+        &mut self,
+        provider: &Provider,
+        request: &GetStorageRangesMessage,
+        storage_roots: &[B256],
+        response: StorageRangesMessage,
+    ) -> Result<Result<(), &'static str>, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        if response.slots.len() > request.account_hashes.len() {
+            return Ok(Err("too many storage sets"))
+        }
+
+        let proof = proof_nodes(&response.proof);
+        let sets = response.slots.len();
+        for (index, set) in response.slots.into_iter().enumerate() {
+            let hashed_address = request.account_hashes[index];
+            let storage_root = storage_roots[index];
+            // Only the first account of a request can be continued from a non-zero slot.
+            let starting_hash = if index == 0 { request.starting_hash } else { B256::ZERO };
+
+            let mut slots = Vec::with_capacity(set.len());
+            for data in set {
+                if data.hash < starting_hash ||
+                    slots.last().is_some_and(|(last, _)| data.hash <= *last)
+                {
+                    return Ok(Err("storage slots are not ordered"))
+                }
+                let Ok(value) = U256::decode(&mut data.data.as_ref()) else {
+                    return Ok(Err("invalid storage slot encoding"))
+                };
+                slots.push((data.hash, value));
+            }
+
+            // Only the last set of a response can be incomplete and carry a proof.
+            let complete = if index + 1 == sets && !proof.is_empty() {
+                if slots.is_empty() && starting_hash == B256::ZERO {
+                    return Ok(Err("empty storage range"))
+                }
+                let entries = slots
+                    .iter()
+                    .map(|(slot, value)| (*slot, alloy_rlp::encode(value)))
+                    .collect::<Vec<_>>();
+                match verify_range_proof(storage_root, &proof, starting_hash, &entries) {
+                    Some(has_more) => !has_more,
+                    None => return Ok(Err("invalid storage range proof")),
+                }
+            } else {
+                if starting_hash != B256::ZERO {
+                    return Ok(Err("missing storage range proof"))
+                }
+                let mut hash_builder = HashBuilder::default();
+                for (slot, value) in &slots {
+                    hash_builder.add_leaf(Nibbles::unpack(slot), &alloy_rlp::encode(value));
+                }
+                if hash_builder.root() != storage_root {
+                    return Ok(Err("storage range does not match storage root"))
+                }
+                true
+            };
+
+            let tx = provider.tx_ref();
+            if starting_hash == B256::ZERO {
+                tx.delete::<tables::HashedStorages>(hashed_address, None)?;
+            }
+            write_storage_slots(tx, hashed_address, &slots)?;
+
+            let progress = self.progress();
+            if complete {
+                progress.next_storage_slot = None;
+                if let Some(index) =
+                    self.pending_storages.iter().position(|(hash, _)| *hash == hashed_address)
+                {
+                    self.pending_storages.remove(index);
+                }
+            } else {
+                progress.next_storage_slot =
+                    slots.last().and_then(|(slot, _)| increment_hash(*slot));
+            }
+            let storage_account = self.pending_storages.front().map(|(hash, _)| *hash);
+            self.progress().storage_account = storage_account;
+        }
+
+        trace!(
+            target: "sync::stages::snap_sync",
+            sets,
+            pending_storages = self.pending_storages.len(),
+            "Processed storage ranges"
+        );
+        Ok(Ok(()))
+    }
+
+    /// Verifies the bytecodes and writes them to the database.
+    fn process_bytecodes<Provider>(
+        &mut self,
+        provider: &Provider,
+        request: &GetByteCodesMessage,
+        response: ByteCodesMessage,
+    ) -> Result<Result<(), &'static str>, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        let mut requested = request.hashes.iter().copied().collect::<BTreeSet<_>>();
+        for code in response.codes {
+            let code_hash = keccak256(&code);
+            if !requested.remove(&code_hash) {
+                self.pending_bytecodes.extend(requested);
+                return Ok(Err("unrequested bytecode"))
+            }
+            provider.tx_ref().put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code))?;
+        }
+        self.pending_bytecodes.extend(requested);
+        Ok(Ok(()))
+    }
+
+    /// Verifies the trie nodes and heals the hashed state with them.
+    fn process_trie_nodes<Provider>(
+        &mut self,
+        provider: &Provider,
+        tasks: &[HealTask],
+        response: TrieNodesMessage,
+    ) -> Result<Result<(), &'static str>, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        if response.nodes.len() > tasks.len() {
+            return Ok(Err("too many trie nodes"))
+        }
+
+        // Nodes that were not served are requested again.
+        for task in tasks[response.nodes.len()..].iter().rev() {
+            self.pending_heal.push_front(task.clone());
+        }
+
+        for (index, (task, node)) in tasks.iter().zip(&response.nodes).enumerate() {
+            let decoded = TrieNode::decode(&mut node.as_ref());
+            let Ok(decoded) = decoded.map_err(|_| ()).and_then(|node_decoded| {
+                (keccak256(node) == task.hash).then_some(node_decoded).ok_or(())
+            }) else {
+                // Everything from the invalid node onwards is requested again.
+                for task in tasks[index..response.nodes.len()].iter().rev() {
+                    self.pending_heal.push_front(task.clone());
+                }
+                return Ok(Err("invalid trie node"))
+            };
+            self.heal_node(provider, task.hashed_address, task.path, decoded)?;
+        }
+        Ok(Ok(()))
+    }
+
+    /// Repairs the hashed state below the given trie node.
+    ///
+    /// Hashed entries below the node that are not part of the node are removed, leaves are
+    /// written and child nodes that differ from the local trie are queued for download.
+    fn heal_node<Provider>(
+        &mut self,
+        provider: &Provider,
+        hashed_address: Option<B256>,
+        path: Nibbles,
+        node: TrieNode,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        let tx = provider.tx_ref();
+        match node {
+            TrieNode::EmptyRoot => {
+                self.delete_healed_entries(tx, hashed_address, &path, |_| false)?
+            }
+            TrieNode::Branch(branch) => {
+                let local = match hashed_address {
+                    None => tx.get::<tables::AccountsTrie>(StoredNibbles(path))?,
+                    Some(hashed_address) => tx
+                        .cursor_dup_read::<tables::StoragesTrie>()?
+                        .seek_by_key_subkey(hashed_address, StoredNibblesSubKey(path))?
+                        .filter(|entry| entry.nibbles.0 == path)
+                        .map(|entry| entry.node),
+                };
+
+                for (nibble, child) in branch.as_ref().children() {
+                    let mut child_path = path;
+                    child_path.push(nibble);
+                    let Some(child) = child else {
+                        self.delete_healed_entries(tx, hashed_address, &child_path, |_| false)?;
+                        continue
+                    };
+
+                    let local_hash = local
+                        .as_ref()
+                        .filter(|local| local.hash_mask.is_bit_set(nibble))
+                        .map(|local| local.hash_for_nibble(nibble));
+                    if child.as_hash().is_some_and(|hash| Some(hash) == local_hash) {
+                        continue
+                    }
+                    self.heal_child(provider, hashed_address, child_path, child.clone())?;
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let child_path = path.join(&extension.key);
+                self.delete_healed_entries(tx, hashed_address, &path, |key| {
+                    key.starts_with(&child_path)
+                })?;
+                self.heal_child(provider, hashed_address, child_path, extension.child)?;
+            }
+            TrieNode::Leaf(leaf) => {
+                let key = path.join(&leaf.key);
+                self.delete_healed_entries(tx, hashed_address, &path, |other| *other == key)?;
+                let hashed_key = B256::from_slice(&key.pack());
+
+                let Some(hashed_address) = hashed_address else {
+                    let account = TrieAccount::decode(&mut leaf.value.as_slice())
+                        .map_err(|err| StageError::Fatal(Box::new(err)))?;
+                    return self.heal_account(provider, hashed_key, account)
+                };
+                let value = U256::decode(&mut leaf.value.as_slice())
+                    .map_err(|err| StageError::Fatal(Box::new(err)))?;
+                write_storage_slots(tx, hashed_address, &[(hashed_key, value)])?;
+                self.record_healed_keys(Some(hashed_address), &[hashed_key], false);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes hashed entries like [`delete_hashed_entries`] and records the removed keys for the
+    /// next trie update.
+    fn delete_healed_entries<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        hashed_address: Option<B256>,
+        prefix: &Nibbles,
+        keep: impl Fn(&Nibbles) -> bool,
+    ) -> Result<(), DatabaseError> {
+        let removed = delete_hashed_entries(tx, hashed_address, prefix, keep)?;
+        self.record_healed_keys(hashed_address, &removed, true);
+        Ok(())
+    }
+
+    /// Records hashed accounts, or storage slots of the given account, that were changed while
+    /// healing, so that the next trie update only walks the affected parts of the trie.
+    ///
+    /// If `destroyed` is set, the storage of the given accounts was removed.
+    fn record_healed_keys(&mut self, hashed_address: Option<B256>, keys: &[B256], destroyed: bool) {
+        let Some(prefix_sets) = &mut self.heal_prefix_sets else { return };
+        if keys.is_empty() {
+            return
+        }
+
+        match hashed_address {
+            None => {
+                for hashed_address in keys {
+                    prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                    if destroyed {
+                        prefix_sets.destroyed_accounts.insert(*hashed_address);
+                    }
+                }
+            }
+            Some(hashed_address) => {
+                prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                prefix_sets
+                    .storage_prefix_sets
+                    .entry(hashed_address)
+                    .or_default()
+                    .extend_keys(keys.iter().map(Nibbles::unpack));
+            }
+        }
+    }
+
+    /// Heals a child of a trie node, either by queueing it for download or, if it is embedded in
+    /// its parent, by healing it directly.
+    fn heal_child<Provider>(
+        &mut self,
+        provider: &Provider,
+        hashed_address: Option<B256>,
+        path: Nibbles,
+        child: RlpNode,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        if let Some(hash) = child.as_hash() {
+            self.pending_heal.push_back(HealTask { hashed_address, path, hash });
+            return Ok(())
+        }
+
+        let node = TrieNode::decode(&mut child.as_slice())
+            .map_err(|err| StageError::Fatal(Box::new(err)))?;
+        self.heal_node(provider, hashed_address, path, node)
+    }
+
+    /// Writes a healed account and queues its bytecode and storage trie, if they differ from the
+    /// local state.
+    fn heal_account<Provider>(
+        &mut self,
+        provider: &Provider,
+        hashed_address: B256,
+        account: TrieAccount,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>,
+    {
+        let tx = provider.tx_ref();
+        tx.put::<tables::HashedAccounts>(
+            hashed_address,
+            Account {
+                nonce: account.nonce,
+                balance: account.balance,
+                bytecode_hash: (account.code_hash != KECCAK_EMPTY).then_some(account.code_hash),
+            },
+        )?;
+
+        if account.code_hash != KECCAK_EMPTY &&
+            tx.get::<tables::Bytecodes>(account.code_hash)?.is_none()
+        {
+            self.pending_bytecodes.insert(account.code_hash);
         }
+
+        let storage_removed = account.storage_root == EMPTY_ROOT_HASH;
+        self.record_healed_keys(None, &[hashed_address], storage_removed);
+        if storage_removed {
+            tx.delete::<tables::HashedStorages>(hashed_address, None)?;
+        } else if reth_trie::StorageRoot::from_tx_hashed(tx, hashed_address)
+            .root()
+            .map_err(|err| StageError::Fatal(Box::new(err)))? !=
+            account.storage_root
+        {
+            self.pending_heal.push_back(HealTask {
+                hashed_address: Some(hashed_address),
+                path: Nibbles::default(),
+                hash: account.storage_root,
+            });
+        }
+        Ok(())
+    }
+
+    /// Updates the trie tables from the hashed state and returns the resulting state root.
+    ///
+    /// The trie is built from scratch when healing starts. Later healing passes only update the
+    /// parts of the trie below the keys that were healed since the previous pass.
+    fn update_trie<Provider>(&mut self, provider: &Provider) -> Result<B256, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+    {
+        let tx = provider.tx_ref();
+        let state_root = match self.heal_prefix_sets.take() {
+            Some(prefix_sets) => StateRoot::from_tx(tx).with_prefix_sets(prefix_sets.freeze()),
+            None => {
+                tx.clear::<tables::AccountsTrie>()?;
+                tx.clear::<tables::StoragesTrie>()?;
+                StateRoot::from_tx(tx)
+            }
+        };
+
+        let (root, updates) =
+            state_root.root_with_updates().map_err(|err| StageError::Fatal(Box::new(err)))?;
+        provider.write_trie_updates(&updates)?;
+        self.heal_prefix_sets = Some(TriePrefixSetsMut::default());
+        Ok(root)
     }
 
+    /// Moves the stage forward once all work of the current step is done.
+    ///
+    /// Returns `true` once the local state root matches the target state root.
+    fn advance<Provider>(
+        &mut self,
+        provider: &Provider,
+        state_root: B256,
+    ) -> Result<bool, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+    {
+        let idle = self.pending_requests.is_empty() &&
+            self.retry_requests.is_empty() &&
+            self.pending_storages.is_empty() &&
+            self.pending_bytecodes.is_empty();
+        if !idle {
+            return Ok(false)
+        }
+
+        if let Some(range) = self.account_range.take() {
+            let progress = self.progress();
+            progress.storage_account = None;
+            progress.next_storage_slot = None;
+            match range.last.filter(|_| !range.complete).and_then(increment_hash) {
+                Some(next_account) => progress.next_account = next_account,
+                None => {
+                    info!(target: "sync::stages::snap_sync", "Downloaded all account ranges");
+                    progress.healing = true;
+                }
+            }
+        }
+
+        if !self.progress().healing || !self.pending_heal.is_empty() {
+            return Ok(false)
+        }
+
+        // Start a new healing pass.
+        let root = self.update_trie(provider)?;
+        if root == state_root {
+            info!(target: "sync::stages::snap_sync", ?root, "Snap sync finished");
+            return Ok(true)
+        }
+
+        info!(
+            target: "sync::stages::snap_sync",
+            local_root = ?root,
+            target_root = ?state_root,
+            "State root mismatch, healing trie"
+        );
+        self.pending_heal.push_back(HealTask {
+            hashed_address: None,
+            path: Nibbles::default(),
+            hash: state_root,
+        });
+        Ok(false)
+    }
 }
 
 impl<Provider, C> Stage<Provider> for SnapSyncStage<C>
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + TrieWriter + StageCheckpointReader + StageCheckpointWriter,
     C: SnapClient + Send + Sync + 'static,
 {
     fn id(&self) -> StageId {
@@ -571,223 +1311,92 @@ where
     fn poll_execute_ready(
         &mut self,
         cx: &mut Context<'_>,
-        _input: ExecInput,
+        input: ExecInput,
     ) -> Poll<Result<(), StageError>> {
-        if !self.config.enabled {
-            return Poll::Ready(Ok(()));
+        if !self.config.enabled || input.target_reached() {
+            return Poll::Ready(Ok(()))
         }
 
         // Check if we have a target state root from consensus engine
         if self.get_target_state_root().is_none() {
-            return Poll::Pending;
+            return Poll::Pending
         }
 
-
-        // Check for timed out requests
-        let timed_out_requests = self.check_timeouts();
-        for request_id in timed_out_requests {
+        for request_id in self.check_timeouts() {
             self.handle_request_timeout(request_id);
         }
 
-        // Poll any pending SnapClient requests
-        let mut completed_requests = Vec::new();
-        for (request_id, future) in &mut self.pending_requests {
-            match future.as_mut().poll(cx) {
-                Poll::Ready(result) => {
-                    match result {
-                        Ok(account_range) => {
-                            debug!(
-                                target: "sync::stages::snap_sync",
-                                request_id = request_id,
-                                accounts_count = account_range.1.accounts.len(),
-                                "Received account range response"
-                            );
-                            self.completed_ranges.push(account_range.1);
-                        }
-                        Err(e) => {
-                            warn!(
-                                target: "sync::stages::snap_sync",
-                                request_id = request_id,
-                                error = %e,
-                                "Account range request failed"
-                            );
-                            
-                            // Handle failed request - could implement retry logic here
-                            // For now, we log the failure and rely on timeout-based retry
-                        }
-                    }
-                    completed_requests.push(*request_id);
-                }
-                Poll::Pending => {},
-            }
-        }
+        self.poll_requests(cx);
 
-        // Remove completed requests
-        for request_id in completed_requests {
-            self.pending_requests.remove(&request_id);
-            self.complete_request_tracking(request_id);
-        }
-
-        // Return ready if we have completed ranges to process
-        if self.completed_ranges.is_empty() {
+        // New requests are created in `execute` once nothing is in flight anymore
+        if self.completed_requests.is_empty() && !self.pending_requests.is_empty() {
             Poll::Pending
         } else {
             Poll::Ready(Ok(()))
         }
     }
 
-    fn execute(
-        &mut self,
-        provider: &Provider,
-        input: ExecInput,
-    ) -> Result<ExecOutput, StageError> {
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
         if input.target_reached() {
-            return Ok(ExecOutput::done(input.checkpoint()));
+            return Ok(ExecOutput::done(input.checkpoint()))
         }
 
         if !self.config.enabled {
-            return Ok(ExecOutput {
-                checkpoint: input.checkpoint(),
-                done: true,
-            });
+            return Ok(ExecOutput { checkpoint: input.checkpoint(), done: true })
         }
 
-        // Step 1: Retrieve the latest header from the engine
-        let target_state_root = self.get_target_state_root()
+        let target_state_root = self
+            .get_target_state_root()
             .ok_or_else(|| StageError::Fatal("No target state root available".into()))?;
 
-        // Check if state root has changed and invalidate stale requests
-        if self.has_state_root_changed(self.last_known_state_root) {
+        self.load_progress(provider)?;
+        if self.progress().state_root != target_state_root {
             warn!(
                 target: "sync::stages::snap_sync",
-                old_root = ?self.last_known_state_root,
+                old_root = ?self.progress().state_root,
                 new_root = ?target_state_root,
                 "State root changed, invalidating pending requests"
             );
-            
-            // Clear pending requests as they're now stale
-            self.pending_requests.clear();
-            self.request_start_times.clear();
-            
-            // Update the last known state root
-            self.last_known_state_root = Some(target_state_root);
+            self.switch_state_root(target_state_root);
         }
+        self.last_known_state_root = Some(target_state_root);
 
-        // Step 2: Check if the hashed state in tables::HashedAccounts is empty
-        let starting_hash = self.get_next_sync_starting_point(provider)?;
-
-        let mut total_processed = 0;
-        let max_hash = B256::from([0xff; 32]);
-        let mut current_starting_hash = starting_hash;
-
-        // Step 3: Paginate over trie ranges using GetAccountRange request
+        let waker = Self::noop_waker();
+        let mut cx = Context::from_waker(&waker);
         for _ in 0..self.config.max_ranges_per_execution {
-            if current_starting_hash >= max_hash {
-                break;
+            for (request, response) in std::mem::take(&mut self.completed_requests) {
+                self.process_response(provider, request, response)?;
             }
 
-            // Calculate the next range using proper trie range logic
-            let (range_start, range_end) = self.calculate_next_trie_range(current_starting_hash, max_hash)?;
-            
-            // If we've reached the end, we're done
-            if range_start >= max_hash {
-                break;
+            if self.advance(provider, target_state_root)? {
+                self.reset();
+                provider.save_stage_checkpoint_progress(StageId::SnapSync, Vec::new())?;
+                return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
             }
 
-            // Create and send GetAccountRange request synchronously
-            let request = self.create_account_range_request_with_state_root(range_start, range_end, target_state_root);
-            
-            debug!(
-                target: "sync::stages::snap_sync",
-                request_id = request.request_id,
-                starting_hash = ?request.starting_hash,
-                limit_hash = ?request.limit_hash,
-                root_hash = ?request.root_hash,
-                "Creating account range request"
-            );
+            self.send_requests(target_state_root);
 
-            // Send the request via SnapClient
-            let future = self.snap_client.get_account_range_with_priority(request.clone(), Priority::Normal);
-            self.pending_requests.insert(request.request_id, Box::pin(future));
-            self.start_request_tracking(request.request_id);
-
-            // Track the processed range for progress persistence
-            self.last_processed_range = Some((range_start, range_end));
-
-            // Move to next range
-            current_starting_hash = range_end;
-        }
-
-        // Poll any pending requests to completion and process them immediately
-        let mut completed_requests = Vec::new();
-        let mut failed_requests = Vec::new();
-        let noop_waker = Self::noop_waker();
-        for (request_id, future) in &mut self.pending_requests {
-            match future.as_mut().poll(&mut Context::from_waker(&noop_waker)) {
-                Poll::Ready(result) => {
-                    match result {
-                        Ok(account_range) => {
-                            debug!(
-                                target: "sync::stages::snap_sync",
-                                request_id = request_id,
-                                accounts_count = account_range.1.accounts.len(),
-                                "Received account range response"
-                            );
-                            self.completed_ranges.push(account_range.1);
-                        }
-                        Err(e) => {
-                            failed_requests.push((*request_id, e));
-                        }
-                    }
-                    completed_requests.push(*request_id);
-                }
-                Poll::Pending => {
-                    // Request still pending, will be processed in next poll_execute_ready
-                }
+            // Responses that are already available are processed right away, the rest is
+            // awaited in `poll_execute_ready`
+            self.poll_requests(&mut cx);
+            if self.completed_requests.is_empty() {
+                break
             }
         }
 
-        // Handle failed requests after the loop to avoid borrow conflicts
-        for (request_id, error) in failed_requests {
-            self.handle_request_failure(request_id, &error);
-        }
-
-        // Remove completed requests
-        for request_id in completed_requests {
-            self.pending_requests.remove(&request_id);
-            self.complete_request_tracking(request_id);
-        }
-
-        // Process any completed account ranges
-        if !self.completed_ranges.is_empty() {
-            let completed_ranges = std::mem::take(&mut self.completed_ranges);
-            let processed = self.process_account_ranges(provider, completed_ranges)?;
-            total_processed += processed;
-            
-            info!(
-                target: "sync::stages::snap_sync",
-                processed_this_round = processed,
-                total_processed = total_processed,
-                pending_requests = self.pending_requests.len(),
-                "Snap sync progress update"
-            );
-        }
+        self.save_progress(provider)?;
 
-        // Step 4: If no data was returned for current target state root, return to step 1
-        // This is handled by returning done=false when no data was processed
-        let is_complete = current_starting_hash >= max_hash;
-        
-        if total_processed == 0 && !is_complete {
-            debug!(
-                target: "sync::stages::snap_sync",
-                "No data returned for current target state root, will re-poll for new header"
-            );
-        }
+        info!(
+            target: "sync::stages::snap_sync",
+            progress = ?self.progress,
+            pending_requests = self.pending_requests.len(),
+            pending_storages = self.pending_storages.len(),
+            pending_bytecodes = self.pending_bytecodes.len(),
+            pending_trie_nodes = self.pending_heal.len(),
+            "Snap sync progress update"
+        );
 
-        Ok(ExecOutput {
-            checkpoint: input.checkpoint(),
-            done: is_complete, // Done when we've reached the end of the trie (0xffff...)
-        })
+        Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
     }
 
     fn unwind(
@@ -796,55 +1405,398 @@ where
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
         if !self.config.enabled {
-            return Ok(UnwindOutput { checkpoint: input.checkpoint });
+            return Ok(UnwindOutput { checkpoint: input.checkpoint })
         }
 
-        let unwind_block = input.unwind_to;
-        
         info!(
             target: "sync::stages::snap_sync",
-            unwind_to = unwind_block,
+            unwind_to = input.unwind_to,
             "Unwinding snap sync stage"
         );
-        
-        // For snap sync, we need to handle unwinding carefully
-        // Since snap sync doesn't have block-based progress tracking,
-        // we need to implement a different strategy
-        
-        // Check if we have any snap sync progress to unwind
-        let has_accounts = !self.is_hashed_state_empty(provider)?;
-        let has_progress = self.last_processed_range.is_some();
-        
-        if has_accounts || has_progress {
-            info!(
-                target: "sync::stages::snap_sync",
-                unwind_to = unwind_block,
-                has_accounts = has_accounts,
-                has_progress = has_progress,
-                "Unwinding snap sync data"
-            );
-            
-            // Clear all downloaded account data from the HashedAccounts table
-            // This ensures a clean state when unwinding snap sync
-            provider.tx_ref().clear::<tables::HashedAccounts>()?;
-            
-            // Reset progress tracking
-            self.last_processed_range = None;
-            self.last_known_state_root = None;
-            
-            // Clear any pending requests
-            self.pending_requests.clear();
-            self.request_start_times.clear();
-            self.request_retry_counts.clear();
-            self.completed_ranges.clear();
-        } else {
-            debug!(
-                target: "sync::stages::snap_sync",
-                unwind_to = unwind_block,
-                "No snap sync data to unwind"
-            );
+
+        // The downloaded state does not belong to any block below the target, so everything is
+        // cleared and the sync starts over.
+        let tx = provider.tx_ref();
+        tx.clear::<tables::HashedAccounts>()?;
+        tx.clear::<tables::HashedStorages>()?;
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+        provider.save_stage_checkpoint_progress(StageId::SnapSync, Vec::new())?;
+        self.reset();
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// Decodes an account in the slim format used by the snap protocol, where an empty storage root
+/// and an empty code hash are encoded as empty strings.
+fn decode_slim_account(mut buf: &[u8]) -> alloy_rlp::Result<TrieAccount> {
+    let header = alloy_rlp::Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+
+    let nonce = u64::decode(&mut buf)?;
+    let balance = U256::decode(&mut buf)?;
+    let storage_root = decode_slim_hash(&mut buf, EMPTY_ROOT_HASH)?;
+    let code_hash = decode_slim_hash(&mut buf, KECCAK_EMPTY)?;
+    Ok(TrieAccount { nonce, balance, storage_root, code_hash })
+}
+
+/// Decodes a hash of a slim account, returning `empty` for an empty string.
+fn decode_slim_hash(buf: &mut &[u8], empty: B256) -> alloy_rlp::Result<B256> {
+    let bytes = Bytes::decode(buf)?;
+    match bytes.len() {
+        0 => Ok(empty),
+        32 => Ok(B256::from_slice(&bytes)),
+        _ => Err(alloy_rlp::Error::UnexpectedLength),
+    }
+}
+
+/// Returns the hash following the given one, or `None` if it is the highest hash.
+fn increment_hash(hash: B256) -> Option<B256> {
+    U256::from_be_bytes(hash.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+/// Indexes the nodes of a range proof by their hash.
+fn proof_nodes(proof: &[Bytes]) -> HashMap<B256, &Bytes> {
+    proof.iter().map(|node| (keccak256(node), node)).collect()
+}
+
+/// The position of a trie path relative to a proven key range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangePosition {
+    /// All keys below the path are before the range.
+    Before,
+    /// All keys below the path are inside the range.
+    Inside,
+    /// All keys below the path are after the range.
+    After,
+    /// The path is a prefix of one of the range boundaries.
+    Boundary,
+}
+
+impl RangePosition {
+    /// Returns the position of the keys below `path` relative to the range `left..=right`, where
+    /// a missing `right` boundary includes all keys after `left`.
+    fn of(path: &Nibbles, left: &Nibbles, right: Option<&Nibbles>) -> Self {
+        let len = path.len();
+        match path.cmp(&left.slice(..len)) {
+            Ordering::Less => return Self::Before,
+            Ordering::Equal => return Self::Boundary,
+            Ordering::Greater => {}
+        }
+        match right.map(|right| path.cmp(&right.slice(..len))) {
+            None | Some(Ordering::Less) => Self::Inside,
+            Some(Ordering::Equal) => Self::Boundary,
+            Some(Ordering::Greater) => Self::After,
         }
-        
-        Ok(UnwindOutput { checkpoint: input.checkpoint })
     }
-}
\ No newline at end of file
+}
+
+/// A part of a trie outside of a proven key range.
+#[derive(Debug)]
+enum RangeProofItem {
+    /// A subtrie with the given hash.
+    Node(Nibbles, B256),
+    /// A leaf with the given value.
+    Leaf(Nibbles, Vec<u8>),
+}
+
+impl RangeProofItem {
+    /// Adds the item to the hash builder.
+    fn add_to(&self, hash_builder: &mut HashBuilder) {
+        match self {
+            Self::Node(path, hash) => hash_builder.add_branch(*path, *hash, false),
+            Self::Leaf(key, value) => hash_builder.add_leaf(*key, value),
+        }
+    }
+}
+
+/// The parts of a trie before and after a proven key range, restored from the range proof.
+#[derive(Debug, Default)]
+struct RangeProofWalker {
+    /// The first key of the range.
+    left: Nibbles,
+    /// The last key of the range, `None` if the range includes all keys after `left`.
+    right: Option<Nibbles>,
+    /// Subtries and leaves before the range, in key order.
+    before: Vec<RangeProofItem>,
+    /// Subtries and leaves after the range, in key order.
+    after: Vec<RangeProofItem>,
+}
+
+impl RangeProofWalker {
+    /// Collects the parts outside of the range that are referenced by the child at `path`.
+    ///
+    /// Returns `None` if a node on the path to a range boundary is missing from the proof.
+    fn visit_child(
+        &mut self,
+        proof: &HashMap<B256, &Bytes>,
+        path: Nibbles,
+        child: &RlpNode,
+    ) -> Option<()> {
+        let position = RangePosition::of(&path, &self.left, self.right.as_ref());
+        let mut node = match (position, child.as_hash()) {
+            (RangePosition::Inside, _) => return Some(()),
+            (RangePosition::Before, Some(hash)) => {
+                self.before.push(RangeProofItem::Node(path, hash));
+                return Some(())
+            }
+            (RangePosition::After, Some(hash)) => {
+                self.after.push(RangeProofItem::Node(path, hash));
+                return Some(())
+            }
+            (RangePosition::Boundary, Some(hash)) => &proof.get(&hash)?[..],
+            // Embedded nodes have no hash and are resolved in place.
+            (_, None) => child.as_slice(),
+        };
+        self.visit_node(proof, path, TrieNode::decode(&mut node).ok()?)
+    }
+
+    /// Collects the parts outside of the range below the node at `path`.
+    fn visit_node(
+        &mut self,
+        proof: &HashMap<B256, &Bytes>,
+        path: Nibbles,
+        node: TrieNode,
+    ) -> Option<()> {
+        match node {
+            TrieNode::EmptyRoot => {}
+            TrieNode::Branch(branch) => {
+                for (nibble, child) in branch.as_ref().children() {
+                    let Some(child) = child else { continue };
+                    let mut child_path = path;
+                    child_path.push(nibble);
+                    self.visit_child(proof, child_path, child)?;
+                }
+            }
+            TrieNode::Extension(extension) => {
+                self.visit_child(proof, path.join(&extension.key), &extension.child)?;
+            }
+            TrieNode::Leaf(leaf) => {
+                let key = path.join(&leaf.key);
+                let position = RangePosition::of(&key, &self.left, self.right.as_ref());
+                let item = RangeProofItem::Leaf(key, leaf.value);
+                match position {
+                    RangePosition::Before => self.before.push(item),
+                    RangePosition::After => self.after.push(item),
+                    RangePosition::Inside | RangePosition::Boundary => {}
+                }
+            }
+        }
+        Some(())
+    }
+}
+
+/// Verifies that `entries` are all entries of the trie with the given root from `origin` up to
+/// the last entry, using the nodes of a range proof.
+///
+/// The parts of the trie before `origin` and after the last entry are restored from the proof
+/// and hashed together with the entries, so every entry of the range is covered. Without entries,
+/// the proof has to show that there are no keys at or after `origin`.
+///
+/// Returns whether the trie has more entries after the range, or `None` if the proof is invalid.
+fn verify_range_proof(
+    root: B256,
+    proof: &HashMap<B256, &Bytes>,
+    origin: B256,
+    entries: &[(B256, Vec<u8>)],
+) -> Option<bool> {
+    let mut walker = RangeProofWalker {
+        left: Nibbles::unpack(origin),
+        right: entries.last().map(|(key, _)| Nibbles::unpack(key)),
+        ..Default::default()
+    };
+    let root_node = TrieNode::decode(&mut &proof.get(&root)?[..]).ok()?;
+    walker.visit_node(proof, Nibbles::default(), root_node)?;
+
+    let mut hash_builder = HashBuilder::default();
+    walker.before.iter().for_each(|item| item.add_to(&mut hash_builder));
+    for (key, value) in entries {
+        hash_builder.add_leaf(Nibbles::unpack(key), value);
+    }
+    walker.after.iter().for_each(|item| item.add_to(&mut hash_builder));
+    (hash_builder.root() == root).then_some(!walker.after.is_empty())
+}
+
+/// Writes storage slots of an account, replacing existing values. Zero values are removed.
+fn write_storage_slots<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    hashed_address: B256,
+    slots: &[(B256, U256)],
+) -> Result<(), DatabaseError> {
+    let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+    for (key, value) in slots {
+        if cursor.seek_by_key_subkey(hashed_address, *key)?.is_some_and(|entry| entry.key == *key) {
+            cursor.delete_current()?;
+        }
+        if !value.is_zero() {
+            cursor.upsert(hashed_address, &StorageEntry { key: *key, value: *value })?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes all hashed accounts, or storage slots of the given account, whose key starts with
+/// `prefix`, except the ones for which `keep` returns `true`. Returns the removed keys.
+fn delete_hashed_entries<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    hashed_address: Option<B256>,
+    prefix: &Nibbles,
+    keep: impl Fn(&Nibbles) -> bool,
+) -> Result<Vec<B256>, DatabaseError> {
+    let mut start = B256::ZERO;
+    prefix.pack_to(start.as_mut_slice());
+
+    let Some(hashed_address) = hashed_address else {
+        let mut removed = Vec::new();
+        let mut cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+        let mut walker = cursor.walk(Some(start))?;
+        while let Some((hashed_address, _)) = walker.next().transpose()? {
+            let key = Nibbles::unpack(hashed_address);
+            if !key.starts_with(prefix) {
+                break
+            }
+            if !keep(&key) {
+                walker.delete_current()?;
+                removed.push(hashed_address);
+            }
+        }
+        for hashed_address in &removed {
+            tx.delete::<tables::HashedStorages>(*hashed_address, None)?;
+        }
+        return Ok(removed)
+    };
+
+    let mut removed = Vec::new();
+    let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+    let mut walker = cursor.walk_dup(Some(hashed_address), Some(start))?;
+    while let Some((_, entry)) = walker.next().transpose()? {
+        let key = Nibbles::unpack(entry.key);
+        if !key.starts_with(prefix) {
+            break
+        }
+        if !keep(&key) {
+            walker.delete_current()?;
+            removed.push(entry.key);
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn decode_slim_account_empty_fields() {
+        let mut buf = Vec::new();
+        let payload_length = 1u64.length() + U256::from(2).length() + 2;
+        alloy_rlp::Header { list: true, payload_length }.encode(&mut buf);
+        1u64.encode(&mut buf);
+        U256::from(2).encode(&mut buf);
+        Bytes::new().encode(&mut buf);
+        Bytes::new().encode(&mut buf);
+
+        let account = decode_slim_account(&buf).unwrap();
+        assert_eq!(
+            account,
+            TrieAccount {
+                nonce: 1,
+                balance: U256::from(2),
+                storage_root: EMPTY_ROOT_HASH,
+                code_hash: KECCAK_EMPTY,
+            }
+        );
+    }
+
+    #[test]
+    fn unserved_account_range() {
+        let request = GetAccountRangeMessage {
+            request_id: 1,
+            root_hash: B256::repeat_byte(0x11),
+            starting_hash: B256::ZERO,
+            limit_hash: MAX_HASH,
+            response_bytes: 0,
+        };
+        let response = SnapResponse::AccountRange(AccountRangeMessage {
+            request_id: 1,
+            accounts: Vec::new(),
+            proof: Vec::new(),
+        });
+        assert!(response.is_unserved(&SnapRequest::AccountRange(request.clone())));
+
+        // An empty state has no accounts to prove.
+        let request = GetAccountRangeMessage { root_hash: EMPTY_ROOT_HASH, ..request };
+        assert!(!response.is_unserved(&SnapRequest::AccountRange(request)));
+    }
+
+    /// Returns the leaves of a test trie.
+    fn test_leaves() -> BTreeMap<B256, Vec<u8>> {
+        (0u8..16).map(|i| (keccak256([i]), alloy_rlp::encode(U256::from(i + 1)))).collect()
+    }
+
+    /// Builds the trie with the given leaves and returns its root and the proof of the targets.
+    fn range_proof(leaves: &BTreeMap<B256, Vec<u8>>, targets: [B256; 2]) -> (B256, Vec<Bytes>) {
+        let mut hash_builder = HashBuilder::default()
+            .with_proof_retainer(targets.iter().map(Nibbles::unpack).collect());
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        let proof = hash_builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+        (root, proof)
+    }
+
+    #[test]
+    fn verify_range_proof_entries() {
+        let entries = test_leaves().into_iter().collect::<Vec<_>>();
+
+        let range = &entries[4..10];
+        let (root, proof) = range_proof(&test_leaves(), [range[0].0, range[5].0]);
+        let proof = proof_nodes(&proof);
+        assert_eq!(verify_range_proof(root, &proof, range[0].0, range), Some(true));
+
+        // Entries between the boundaries are verified as well.
+        let mut missing = range.to_vec();
+        missing.remove(2);
+        assert_eq!(verify_range_proof(root, &proof, range[0].0, &missing), None);
+        let mut modified = range.to_vec();
+        modified[3].1 = alloy_rlp::encode(U256::ZERO);
+        assert_eq!(verify_range_proof(root, &proof, range[0].0, &modified), None);
+
+        // The range starting at the origin must not skip entries.
+        let origin = increment_hash(entries[3].0).unwrap();
+        let (root, proof) = range_proof(&test_leaves(), [origin, range[5].0]);
+        let proof = proof_nodes(&proof);
+        assert_eq!(verify_range_proof(root, &proof, origin, &range[1..]), None);
+
+        // The last range of the trie has no more entries after it.
+        let range = &entries[10..];
+        let (root, proof) = range_proof(&test_leaves(), [range[0].0, range[5].0]);
+        assert_eq!(verify_range_proof(root, &proof_nodes(&proof), range[0].0, range), Some(false));
+    }
+
+    #[test]
+    fn verify_range_proof_empty_range() {
+        let leaves = test_leaves();
+
+        // There are no entries after the last key.
+        let origin = increment_hash(*leaves.keys().last().unwrap()).unwrap();
+        let (root, proof) = range_proof(&leaves, [origin, origin]);
+        assert_eq!(verify_range_proof(root, &proof_nodes(&proof), origin, &[]), Some(false));
+
+        // An empty range can not end the sync early if there are entries after the origin.
+        let origin = *leaves.keys().nth(8).unwrap();
+        let (root, proof) = range_proof(&leaves, [origin, origin]);
+        assert_eq!(verify_range_proof(root, &proof_nodes(&proof), origin, &[]), None);
+    }
+}
@@ -248,6 +248,26 @@ pub struct StorageHashingCheckpoint {
     pub progress: EntitiesCheckpoint,
 }
 
+/// Saves the progress of `SnapSync` stage.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapSyncCheckpoint {
+    /// The state root the downloaded state belongs to.
+    pub state_root: B256,
+    /// The next account hash to request an account range from.
+    pub next_account: B256,
+    /// The first account of the current range whose storage is not fully downloaded yet.
+    pub storage_account: Option<B256>,
+    /// The next storage slot to request for `storage_account`, if its storage is downloaded in
+    /// chunks.
+    pub next_storage_slot: Option<B256>,
+    /// Whether all account ranges were downloaded and the stage is healing the trie.
+    pub healing: bool,
+}
+
 /// Saves the progress of Execution stage.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
@@ -383,7 +403,11 @@ impl StageCheckpoint {
             StageId::StorageHashing => {
                 StageUnitCheckpoint::Storage(StorageHashingCheckpoint::default())
             }
-            StageId::IndexStorageHistory | StageId::IndexAccountHistory => {
+            StageId::IndexStorageHistory |
+            StageId::IndexAccountHistory |
+            StageId::IndexAddressAppearances |
+            StageId::IndexLogs |
+            StageId::IndexTraces => {
                 StageUnitCheckpoint::IndexHistory(IndexHistoryCheckpoint::default())
             }
             _ => return self,
@@ -26,6 +26,12 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Opt-in address appearance index, not part of [`StageId::ALL`].
+    IndexAddressAppearances,
+    /// Opt-in log index, not part of [`StageId::ALL`].
+    IndexLogs,
+    /// Opt-in trace index, not part of [`StageId::ALL`].
+    IndexTraces,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -92,6 +98,9 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexAddressAppearances => "IndexAddressAppearances",
+            Self::IndexLogs => "IndexLogs",
+            Self::IndexTraces => "IndexTraces",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -156,6 +165,9 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexAddressAppearances.to_string(), "IndexAddressAppearances");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
+        assert_eq!(StageId::IndexTraces.to_string(), "IndexTraces");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
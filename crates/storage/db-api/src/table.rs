@@ -124,6 +124,17 @@ pub trait DupSort: Table {
     type SubKey: Key;
 }
 
+/// Parses a key of the table from its JSON representation, e.g. `123` for a block number or
+/// `"0x..."` for a hash.
+pub fn table_key<T: Table>(key: &str) -> Result<T::Key, serde_json::Error> {
+    serde_json::from_str(key)
+}
+
+/// Parses a subkey of the dupsort table from its JSON representation.
+pub fn table_subkey<T: DupSort>(subkey: &str) -> Result<T::SubKey, serde_json::Error> {
+    serde_json::from_str(subkey)
+}
+
 /// Allows duplicating tables across databases
 pub trait TableImporter: DbTxMut {
     /// Imports all table data from another transaction.
@@ -178,3 +189,74 @@ pub trait TableImporter: DbTxMut {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+        AccountsHistory, HashedAccounts, Headers, PlainStorageState, StageCheckpoints,
+        StoragesHistory,
+    };
+    use alloy_primitives::{address, b256, B256};
+    use std::str::FromStr;
+
+    #[test]
+    fn parse_numeric_key() {
+        assert_eq!(table_key::<Headers>("123").unwrap(), 123);
+        assert_eq!(
+            table_key::<HashedAccounts>(
+                "\"0x0ac361fe774b78f8fc4e86c1916930d150865c3fc2e21dca2e58833557608bac\""
+            )
+            .unwrap(),
+            B256::from_str("0x0ac361fe774b78f8fc4e86c1916930d150865c3fc2e21dca2e58833557608bac")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_string_key() {
+        assert_eq!(
+            table_key::<StageCheckpoints>("\"MerkleExecution\"").unwrap(),
+            "MerkleExecution"
+        );
+    }
+
+    #[test]
+    fn parse_json_key() {
+        assert_eq!(
+            table_key::<StoragesHistory>(r#"{ "address": "0x01957911244e546ce519fbac6f798958fafadb41", "sharded_key": { "key": "0x0000000000000000000000000000000000000000000000000000000000000003", "highest_block_number": 18446744073709551615 } }"#).unwrap(),
+            StorageShardedKey::new(
+                address!("0x01957911244e546ce519fbac6f798958fafadb41"),
+                B256::from_str(
+                    "0x0000000000000000000000000000000000000000000000000000000000000003"
+                )
+                .unwrap(),
+                18446744073709551615
+            )
+        );
+    }
+
+    #[test]
+    fn parse_json_key_for_account_history() {
+        assert_eq!(
+            table_key::<AccountsHistory>(r#"{ "key": "0x4448e1273fd5a8bfdb9ed111e96889c960eee145", "highest_block_number": 18446744073709551615 }"#).unwrap(),
+            ShardedKey::new(
+                address!("0x4448e1273fd5a8bfdb9ed111e96889c960eee145"),
+                18446744073709551615
+            )
+        );
+    }
+
+    #[test]
+    fn parse_subkey() {
+        assert_eq!(
+            table_subkey::<PlainStorageState>(
+                "\"0x0000000000000000000000000000000000000000000000000000000000000003\""
+            )
+            .unwrap(),
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000003")
+        );
+        assert!(table_subkey::<PlainStorageState>("3").is_err());
+    }
+}
@@ -436,6 +436,51 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks in which an address appears as a transaction sender,
+    /// recipient or contract creator, and optionally as a log emitter or log topic.
+    ///
+    /// Shards are laid out the same way as in [`AccountsHistory`]: the last shard of an address
+    /// has `u64::MAX` as its highest block number.
+    ///
+    /// Only populated when the address appearance index is enabled.
+    table AddressAppearances {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the positions of the logs emitted by an address.
+    ///
+    /// A position is the block number shifted left by 24 bits, combined with the index of the log
+    /// in the block. Shards are laid out the same way as in [`AccountsHistory`], keyed by the
+    /// highest position instead of the highest block number.
+    ///
+    /// Only populated when the log index is enabled.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the positions of the logs with a topic, regardless of its position in the log.
+    ///
+    /// Positions and shards are laid out the same way as in [`LogAddressIndex`].
+    ///
+    /// Only populated when the log index is enabled.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks containing a call, create, selfdestruct or reward trace
+    /// touching an address.
+    ///
+    /// Shards are laid out the same way as in [`AccountsHistory`].
+    ///
+    /// Only populated when the trace index is enabled.
+    table TraceAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -1,13 +1,14 @@
 #![allow(unused)]
 use crate::{
     providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    AccountReader, AddressAppearancesProvider, BlockHashReader, BlockIdReader, BlockLogKeys,
+    BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker,
+    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader,
+    ChangeSetReader, DatabaseProvider, DatabaseProviderFactory, FullProvider,
+    HashedPostStateProvider, HeaderProvider, LogIndexProvider, ProviderError, ProviderFactory,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, SnapStateOverlay,
+    SnapStateProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
+    StaticFileProviderFactory, TraceIndexProvider, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, Header};
 use alloy_eips::{
@@ -31,7 +32,8 @@ use reth_evm::{ConfigureEvm, EvmEnv};
 use reth_execution_types::ExecutionOutcome;
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
 use reth_primitives_traits::{
-    Account, BlockBody, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader, StorageEntry,
+    Account, BlockBody, Bytecode, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
+    StorageEntry,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -39,7 +41,7 @@ use reth_storage_api::{
     BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
-use reth_trie::{HashedPostState, KeccakKeyHasher};
+use reth_trie::{HashedPostState, KeccakKeyHasher, MultiProof, MultiProofTargets};
 use revm_database::BundleState;
 use std::{
     ops::{Add, RangeBounds, RangeInclusive, Sub},
@@ -611,6 +613,166 @@ impl<N: ProviderNodeTypes> StateProviderFactory for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressAppearancesProvider for BlockchainProvider<N> {
+    // Appearances are indexed by the persistence service once blocks are written to disk, so
+    // searches don't return transactions of blocks that are only in memory.
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.address_appearances_before(address, block, limit)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.address_appearances_after(address, block, limit)
+    }
+
+    fn block_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        include_logs: bool,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Address>)>> {
+        self.database.provider()?.block_address_appearances(range, include_logs)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexProvider for BlockchainProvider<N> {
+    // `log_index_range` ends at the last persisted block, log filters fall back to the header
+    // blooms of the in-memory blocks above it.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.log_index_range()
+    }
+
+    fn address_log_positions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        self.database.provider()?.address_log_positions(address, range)
+    }
+
+    fn topic_log_positions(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        self.database.provider()?.topic_log_positions(topic, range)
+    }
+
+    fn block_log_keys(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, BlockLogKeys)>> {
+        self.database.provider()?.block_log_keys(range)
+    }
+}
+
+impl<N: ProviderNodeTypes> TraceIndexProvider for BlockchainProvider<N> {
+    // Traces are indexed by re-executing persisted blocks, `trace_filter` re-executes the whole
+    // range above `trace_index_range`.
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.trace_index_range()
+    }
+
+    fn address_trace_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.address_trace_blocks(address, range)
+    }
+
+    fn address_trace_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.address_trace_blocks_before(address, block, limit)
+    }
+
+    fn address_trace_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.address_trace_blocks_after(address, block, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> SnapStateProvider for BlockchainProvider<N> {
+    // `snap` only serves the persisted state, so in-memory blocks are not considered here.
+    fn snap_tip(&self) -> ProviderResult<BlockNumber> {
+        self.database.provider()?.snap_tip()
+    }
+
+    fn snap_state_roots(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, B256)>> {
+        self.database.provider()?.snap_state_roots(range)
+    }
+
+    fn snap_state_overlay(&self, block_number: BlockNumber) -> ProviderResult<SnapStateOverlay> {
+        self.database.provider()?.snap_state_overlay(block_number)
+    }
+
+    fn snap_account_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        start: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        self.database.provider()?.snap_account_range(overlay, start, limit, max_accounts)
+    }
+
+    fn snap_storage_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_address: B256,
+        start: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        self.database.provider()?.snap_storage_range(
+            overlay,
+            hashed_address,
+            start,
+            limit,
+            max_slots,
+        )
+    }
+
+    fn snap_storage_roots(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<Vec<B256>> {
+        self.database.provider()?.snap_storage_roots(overlay, hashed_addresses)
+    }
+
+    fn snap_multiproof(
+        &self,
+        overlay: &SnapStateOverlay,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.database.provider()?.snap_multiproof(overlay, targets)
+    }
+
+    fn snap_bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        self.database.provider()?.snap_bytecode(code_hash)
+    }
+}
+
 impl<N: NodeTypesWithDB> HashedPostStateProvider for BlockchainProvider<N> {
     fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
         HashedPostState::from_bundle_state::<KeccakKeyHasher>(bundle_state.state())
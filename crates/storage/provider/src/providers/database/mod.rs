@@ -2,10 +2,11 @@ use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, HeaderProvider, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider,
+    AddressAppearancesProvider, BlockHashReader, BlockLogKeys, BlockNumReader, BlockReader,
+    ChainSpecProvider, DatabaseProviderFactory, HashedPostStateProvider, HeaderProvider,
+    HeaderSyncGapProvider, LogIndexProvider, ProviderError, PruneCheckpointReader,
+    SnapStateOverlay, SnapStateProvider, StageCheckpointReader, StateProviderBox,
+    StaticFileProviderFactory, TraceIndexProvider, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::BlockHashOrNumber;
@@ -18,15 +19,16 @@ use reth_errors::{RethError, RethResult};
 use reth_node_types::{
     BlockTy, HeaderTy, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter, ReceiptTy, TxTy,
 };
-use reth_primitives_traits::{RecoveredBlock, SealedHeader};
+use reth_primitives_traits::{Account, Bytecode, RecoveredBlock, SealedHeader};
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, ForkStateReader, NodePrimitivesProvider,
+    TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
-use reth_trie::HashedPostState;
+use reth_trie::{HashedPostState, MultiProof, MultiProofTargets};
 use revm_database::BundleState;
 use std::{
     ops::{RangeBounds, RangeInclusive},
@@ -64,6 +66,8 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     prune_modes: PruneModes,
     /// The node storage handler.
     storage: Arc<N::Storage>,
+    /// The state the database was forked from, if any.
+    fork_state: Option<Arc<dyn ForkStateReader>>,
 }
 
 impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -86,6 +90,7 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            fork_state: None,
         }
     }
 
@@ -101,6 +106,15 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Sets the state the database was forked from.
+    ///
+    /// State providers read the accounts and storage slots that were never written to the
+    /// database from it.
+    pub fn with_fork_state(mut self, fork_state: Arc<dyn ForkStateReader>) -> Self {
+        self.fork_state = Some(fork_state);
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -128,6 +142,7 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            fork_state: None,
         })
     }
 }
@@ -147,7 +162,8 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.static_file_provider.clone(),
             self.prune_modes.clone(),
             self.storage.clone(),
-        ))
+        )
+        .with_fork_state(self.fork_state.clone()))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -156,13 +172,16 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<N::DB, N>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-            self.storage.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+                self.storage.clone(),
+            )
+            .with_fork_state(self.fork_state.clone()),
+        ))
     }
 
     /// State provider for latest block
@@ -580,6 +599,153 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressAppearancesProvider for ProviderFactory<N> {
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.address_appearances_before(address, block, limit)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.address_appearances_after(address, block, limit)
+    }
+
+    fn block_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        include_logs: bool,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Address>)>> {
+        self.provider()?.block_address_appearances(range, include_logs)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexProvider for ProviderFactory<N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.log_index_range()
+    }
+
+    fn address_log_positions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        self.provider()?.address_log_positions(address, range)
+    }
+
+    fn topic_log_positions(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        self.provider()?.topic_log_positions(topic, range)
+    }
+
+    fn block_log_keys(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, BlockLogKeys)>> {
+        self.provider()?.block_log_keys(range)
+    }
+}
+
+impl<N: ProviderNodeTypes> TraceIndexProvider for ProviderFactory<N> {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.trace_index_range()
+    }
+
+    fn address_trace_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.address_trace_blocks(address, range)
+    }
+
+    fn address_trace_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.address_trace_blocks_before(address, block, limit)
+    }
+
+    fn address_trace_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.address_trace_blocks_after(address, block, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> SnapStateProvider for ProviderFactory<N> {
+    fn snap_tip(&self) -> ProviderResult<BlockNumber> {
+        self.provider()?.snap_tip()
+    }
+
+    fn snap_state_roots(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, B256)>> {
+        self.provider()?.snap_state_roots(range)
+    }
+
+    fn snap_state_overlay(&self, block_number: BlockNumber) -> ProviderResult<SnapStateOverlay> {
+        self.provider()?.snap_state_overlay(block_number)
+    }
+
+    fn snap_account_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        start: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        self.provider()?.snap_account_range(overlay, start, limit, max_accounts)
+    }
+
+    fn snap_storage_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_address: B256,
+        start: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        self.provider()?.snap_storage_range(overlay, hashed_address, start, limit, max_slots)
+    }
+
+    fn snap_storage_roots(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<Vec<B256>> {
+        self.provider()?.snap_storage_roots(overlay, hashed_addresses)
+    }
+
+    fn snap_multiproof(
+        &self,
+        overlay: &SnapStateOverlay,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.provider()?.snap_multiproof(overlay, targets)
+    }
+
+    fn snap_bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        self.provider()?.snap_bytecode(code_hash)
+    }
+}
+
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
     fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
         HashedPostState::from_bundle_state::<KeccakKeyHasher>(bundle_state.state())
@@ -591,13 +757,14 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self { db, chain_spec, static_file_provider, prune_modes, storage, fork_state } = self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
             .field("storage", &storage)
+            .field("fork_state", &fork_state)
             .finish()
     }
 }
@@ -610,6 +777,7 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
             storage: self.storage.clone(),
+            fork_state: self.fork_state.clone(),
         }
     }
 }
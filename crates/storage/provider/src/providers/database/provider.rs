@@ -9,24 +9,25 @@ use crate::{
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
-    BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
-    DBProvider, HashingWriter, HeaderProvider, HeaderSyncGapProvider, HistoricalStateProvider,
-    HistoricalStateProviderRef, HistoryWriter, LatestStateProvider, LatestStateProviderRef,
-    OriginalValuesKnown, ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RevertsInit,
+    AccountReader, AddressAppearancesProvider, BlockBodyWriter, BlockExecutionWriter,
+    BlockHashReader, BlockLogKeys, BlockNumReader, BlockReader, BlockWriter, BundleStateInit,
+    ChainStateBlockReader, ChainStateBlockWriter, DBProvider, HashingWriter, HeaderProvider,
+    HeaderSyncGapProvider, HistoricalStateProvider, HistoricalStateProviderRef, HistoryWriter,
+    LatestStateProvider, LatestStateProviderRef, LogIndexProvider, OriginalValuesKnown,
+    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RevertsInit, SnapStateProvider,
     StageCheckpointReader, StateProviderBox, StateWriter, StaticFileProviderFactory, StatsReader,
-    StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
+    StorageReader, StorageTrieWriter, TraceIndexProvider, TransactionVariant, TransactionsProvider,
     TransactionsProviderExt, TrieWriter,
 };
 use alloy_consensus::{
     transaction::{SignerRecoverable, TransactionMeta, TxHashRef},
-    BlockHeader, TxReceipt,
+    BlockHeader, Transaction as _, TxReceipt,
 };
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, TxHash, TxKind, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
@@ -56,16 +57,23 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    log_position, log_positions, BlockBodyIndicesProvider, BlockBodyReader, ForkStateReader,
+    NodePrimitivesProvider, SnapStateOverlay, StateProvider, StorageChangeSetReader,
+    TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
+    proof::Proof,
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostState, HashedPostStateSorted, KeccakKeyHasher, MultiProof, MultiProofTargets,
+    Nibbles, StateRoot, StorageRoot, StoredNibbles,
+};
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseHashedPostState, DatabaseProof, DatabaseStateRoot,
+    DatabaseStorageRoot, DatabaseStorageTrieCursor,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
 use revm_database::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
 };
@@ -146,6 +154,8 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     prune_modes: PruneModes,
     /// Node storage handler.
     storage: Arc<N::Storage>,
+    /// The state the database was forked from, if any.
+    fork_state: Option<Arc<dyn ForkStateReader>>,
 }
 
 impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -153,6 +163,12 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets the state the database was forked from.
+    pub fn with_fork_state(mut self, fork_state: Option<Arc<dyn ForkStateReader>>) -> Self {
+        self.fork_state = fork_state;
+        self
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -242,7 +258,7 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self { tx, chain_spec, static_file_provider, prune_modes, storage, fork_state: None }
     }
 }
 
@@ -538,7 +554,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self { tx, chain_spec, static_file_provider, prune_modes, storage, fork_state: None }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -837,6 +853,41 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(Vec::new())
     }
 
+    /// Removes all entries of each key at or above the block number paired with it from a
+    /// sharded block number index, keeping the remainder of the last unwound shard.
+    fn unwind_sharded_index<K, T>(
+        &self,
+        keys: impl IntoIterator<Item = (K, BlockNumber)>,
+    ) -> ProviderResult<usize>
+    where
+        K: Copy + Ord,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut keys = keys.into_iter().collect::<Vec<_>>();
+        keys.sort_unstable_by_key(|(key, _)| *key);
+
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for &(key, rem_index) in &keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                rem_index,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(keys.len())
+    }
+
     /// Insert history index to the database.
     ///
     /// For each updated partial key, this function removes the last shard from
@@ -2630,6 +2681,29 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         )
     }
 
+    fn unwind_address_appearance_indices(
+        &self,
+        appearances: impl IntoIterator<Item = (Address, BlockNumber)>,
+    ) -> ProviderResult<usize> {
+        self.unwind_sharded_index::<_, tables::AddressAppearances>(appearances)
+    }
+
+    fn unwind_log_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, u64)>,
+        topics: impl IntoIterator<Item = (B256, u64)>,
+    ) -> ProviderResult<usize> {
+        Ok(self.unwind_sharded_index::<_, tables::LogAddressIndex>(addresses)? +
+            self.unwind_sharded_index::<_, tables::LogTopicIndex>(topics)?)
+    }
+
+    fn unwind_trace_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, BlockNumber)>,
+    ) -> ProviderResult<usize> {
+        self.unwind_sharded_index::<_, tables::TraceAddressIndex>(addresses)
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2997,6 +3071,383 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> StatsReader for DatabaseProvid
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> AddressAppearancesProvider
+    for DatabaseProvider<TX, N>
+{
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks_before::<_, tables::AddressAppearances>(address, block, limit)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks_after::<_, tables::AddressAppearances>(address, block, limit)
+    }
+
+    fn block_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        include_logs: bool,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Address>)>> {
+        let receipts =
+            if include_logs { self.receipts_by_block_range(range.clone())? } else { Vec::new() };
+
+        let blocks = self.recovered_block_range(range)?;
+        Ok(blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| {
+                let mut addresses = Vec::new();
+                for (sender, tx) in block.transactions_with_sender() {
+                    addresses.push(*sender);
+                    match tx.kind() {
+                        TxKind::Call(to) => addresses.push(to),
+                        TxKind::Create => addresses.push(sender.create(tx.nonce())),
+                    }
+                }
+                for log in receipts.get(index).into_iter().flatten().flat_map(|r| r.logs()) {
+                    addresses.push(log.address);
+                    // Topics holding a left-padded non-zero address, e.g. indexed event params.
+                    addresses.extend(
+                        log.topics()
+                            .iter()
+                            .filter(|topic| topic[..12] == [0u8; 12] && topic[12..] != [0u8; 20])
+                            .map(|topic| Address::from_slice(&topic[12..])),
+                    );
+                }
+                addresses.sort_unstable();
+                addresses.dedup();
+                (block.number(), addresses)
+            })
+            .collect())
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> LogIndexProvider for DatabaseProvider<TX, N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(None)
+        };
+        let start = self
+            .get_prune_checkpoint(PruneSegment::LogIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        Ok(Some(start..=checkpoint.block_number))
+    }
+
+    fn address_log_positions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        self.sharded_index_blocks::<_, tables::LogAddressIndex>(address, log_positions(range))
+    }
+
+    fn topic_log_positions(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        self.sharded_index_blocks::<_, tables::LogTopicIndex>(topic, log_positions(range))
+    }
+
+    fn block_log_keys(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, BlockLogKeys)>> {
+        let start = *range.start();
+        Ok(self
+            .receipts_by_block_range(range)?
+            .into_iter()
+            .zip(start..)
+            .filter_map(|(receipts, block_number)| {
+                let mut keys = BlockLogKeys::default();
+                let logs = receipts.iter().flat_map(|receipt| receipt.logs());
+                for (log_index, log) in logs.enumerate() {
+                    let position = log_position(block_number, log_index as u64);
+                    keys.addresses.push((log.address, position));
+                    keys.topics.extend(log.topics().iter().map(|topic| (*topic, position)));
+                }
+                if keys.addresses.is_empty() {
+                    return None
+                }
+                keys.addresses.sort_unstable();
+                keys.addresses.dedup();
+                keys.topics.sort_unstable();
+                keys.topics.dedup();
+                Some((block_number, keys))
+            })
+            .collect())
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> TraceIndexProvider for DatabaseProvider<TX, N> {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexTraces)? else {
+            return Ok(None)
+        };
+        // Blocks can only be indexed while their state history is available.
+        let mut start = 0;
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if let Some(block_number) =
+                self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+            {
+                start = start.max(block_number + 1);
+            }
+        }
+        Ok(Some(start..=checkpoint.block_number))
+    }
+
+    fn address_trace_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks::<_, tables::TraceAddressIndex>(address, range)
+    }
+
+    fn address_trace_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks_before::<_, tables::TraceAddressIndex>(address, block, limit)
+    }
+
+    fn address_trace_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks_after::<_, tables::TraceAddressIndex>(address, block, limit)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Returns the numbers of the given range stored for the key in a sharded block number index,
+    /// in ascending order.
+    fn sharded_index_blocks<K, T>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        K: Copy + PartialEq,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut blocks = Vec::new();
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        // The first shard that can contain the start of the range is the first one with a highest
+        // block number greater than or equal to it.
+        for entry in cursor.walk(Some(ShardedKey::new(key, *range.start())))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != key {
+                break
+            }
+            for block_number in list.iter().skip_while(|block_number| block_number < range.start())
+            {
+                if block_number > *range.end() {
+                    return Ok(blocks)
+                }
+                blocks.push(block_number);
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Returns at most `limit` numbers lower than `block` stored for the key in a sharded block
+    /// number index, in descending order.
+    fn sharded_index_blocks_before<K, T>(
+        &self,
+        key: K,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        K: Copy + PartialEq,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut blocks = Vec::new();
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        // The shard containing the highest block lower than `block` is the first shard with a
+        // highest block number greater than or equal to `block - 1`, or any of the preceding ones.
+        for entry in cursor.walk_back(Some(ShardedKey::new(key, block.saturating_sub(1))))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != key {
+                break
+            }
+            for block_number in list.iter().rev().filter(|block_number| *block_number < block) {
+                if blocks.len() == limit {
+                    return Ok(blocks)
+                }
+                blocks.push(block_number);
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Returns at most `limit` numbers higher than `block` stored for the key in a sharded block
+    /// number index, in ascending order.
+    fn sharded_index_blocks_after<K, T>(
+        &self,
+        key: K,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        K: Copy + PartialEq,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut blocks = Vec::new();
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(key, block.saturating_add(1))))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != key {
+                break
+            }
+            for block_number in list.iter().filter(|block_number| *block_number > block) {
+                if blocks.len() == limit {
+                    return Ok(blocks)
+                }
+                blocks.push(block_number);
+            }
+        }
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> SnapStateProvider for DatabaseProvider<TX, N> {
+    fn snap_tip(&self) -> ProviderResult<BlockNumber> {
+        self.best_block_number()
+    }
+
+    fn snap_state_roots(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, B256)>> {
+        Ok(self
+            .headers_range(range)?
+            .into_iter()
+            .map(|header| (header.number(), header.state_root()))
+            .collect())
+    }
+
+    fn snap_state_overlay(&self, block_number: BlockNumber) -> ProviderResult<SnapStateOverlay> {
+        let reverts =
+            HashedPostState::from_reverts::<KeccakKeyHasher>(&self.tx, block_number + 1..)?;
+        let prefix_sets = reverts.construct_prefix_sets();
+        Ok(SnapStateOverlay { block_number, state: reverts.into_sorted(), prefix_sets })
+    }
+
+    fn snap_account_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        start: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        let mut cursor = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(&self.tx),
+            &overlay.state,
+        )
+        .hashed_account_cursor()?;
+
+        let mut accounts = Vec::new();
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            if hashed_address > limit || accounts.len() >= max_accounts {
+                break
+            }
+            accounts.push((hashed_address, account));
+            entry = cursor.next()?;
+        }
+        Ok(accounts)
+    }
+
+    fn snap_storage_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_address: B256,
+        start: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let mut cursor = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(&self.tx),
+            &overlay.state,
+        )
+        .hashed_storage_cursor(hashed_address)?;
+
+        let mut slots = Vec::new();
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_slot, value)) = entry {
+            if hashed_slot > limit || slots.len() >= max_slots {
+                break
+            }
+            slots.push((hashed_slot, value));
+            entry = cursor.next()?;
+        }
+        Ok(slots)
+    }
+
+    fn snap_storage_roots(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<Vec<B256>> {
+        hashed_addresses
+            .iter()
+            .map(|hashed_address| {
+                // without changed slots, the walker skips the whole trie if its root is stored
+                let prefix_set = overlay
+                    .prefix_sets
+                    .storage_prefix_sets
+                    .get(hashed_address)
+                    .cloned()
+                    .unwrap_or_default()
+                    .freeze();
+                StorageRoot::from_tx_hashed(&self.tx, *hashed_address)
+                    .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                        DatabaseHashedCursorFactory::new(&self.tx),
+                        &overlay.state,
+                    ))
+                    .with_prefix_set(prefix_set)
+                    .root()
+                    .map_err(|err| ProviderError::Database(err.into()))
+            })
+            .collect()
+    }
+
+    fn snap_multiproof(
+        &self,
+        overlay: &SnapStateOverlay,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        Proof::from_tx(&self.tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(&self.tx),
+                &overlay.state,
+            ))
+            .with_prefix_sets_mut(overlay.prefix_sets.clone())
+            .multiproof(targets)
+            .map_err(ProviderError::from)
+    }
+
+    fn snap_bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(self.tx.get_by_encoded_key::<tables::Bytecodes>(&code_hash)?)
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypes> ChainStateBlockReader for DatabaseProvider<TX, N> {
     fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
         let mut finalized_blocks = self
@@ -3056,6 +3507,10 @@ impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider
         self.prune_modes_ref()
     }
 
+    fn fork_state(&self) -> Option<&dyn ForkStateReader> {
+        self.fork_state.as_deref()
+    }
+
     /// Commit database transaction and static files.
     fn commit(self) -> ProviderResult<bool> {
         // For unwinding it makes more sense to commit the database first, since if
@@ -3289,4 +3744,55 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn test_snap_account_range_reverts() {
+        let factory = create_test_provider_factory();
+
+        let addresses = [Address::with_last_byte(1), Address::with_last_byte(2)];
+        let account = Account { nonce: 1, ..Default::default() };
+
+        let provider_rw = factory.provider_rw().unwrap();
+        for address in addresses {
+            provider_rw
+                .tx_ref()
+                .put::<tables::HashedAccounts>(keccak256(address), account)
+                .unwrap();
+        }
+        // the second account was created in block 1
+        provider_rw
+            .tx_ref()
+            .put::<tables::AccountChangeSets>(
+                1,
+                AccountBeforeTx { address: addresses[1], info: None },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let mut expected =
+            addresses.iter().map(|address| (keccak256(address), account)).collect::<Vec<_>>();
+        expected.sort_unstable_by_key(|(hash, _)| *hash);
+
+        let max = B256::repeat_byte(0xff);
+        let overlay = provider.snap_state_overlay(1).unwrap();
+        assert_eq!(provider.snap_account_range(&overlay, B256::ZERO, max, 10).unwrap(), expected);
+        assert_eq!(
+            provider.snap_account_range(&overlay, B256::ZERO, max, 1).unwrap(),
+            expected[..1]
+        );
+        assert_eq!(
+            provider.snap_account_range(&overlay, expected[1].0, max, 10).unwrap(),
+            expected[1..]
+        );
+        assert_eq!(
+            provider.snap_account_range(&overlay, B256::ZERO, expected[0].0, 10).unwrap(),
+            expected[..1]
+        );
+        let overlay = provider.snap_state_overlay(0).unwrap();
+        assert_eq!(
+            provider.snap_account_range(&overlay, B256::ZERO, max, 10).unwrap(),
+            vec![(keccak256(addresses[0]), account)]
+        );
+    }
 }
@@ -0,0 +1,54 @@
+//! Helpers for state providers over a database forked from a remote chain.
+
+use alloy_primitives::{Address, BlockNumber};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::ShardedKey,
+    tables,
+    transaction::DbTx,
+};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Returns `true` if the storage of the account was wiped by the local chain at any point before
+/// `block_number`, or at any point at all if `block_number` is `None`.
+///
+/// This is the case if the account didn't exist at some point of its local history, because it
+/// was created or destroyed by a local block. The remote storage of such an account is stale, so
+/// its unwritten storage slots must not be read from the fork state.
+pub(crate) fn is_storage_wiped<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    block_number: Option<BlockNumber>,
+) -> ProviderResult<bool> {
+    let mut history = tx.cursor_read::<tables::AccountsHistory>()?;
+    let mut changesets = tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+
+    let mut written = false;
+    let mut shard = history.seek(ShardedKey::new(address, 0))?;
+    while let Some((_, blocks)) = shard.filter(|(key, _)| key.key == address) {
+        for changed_at in blocks.iter() {
+            written = true;
+
+            // the account as it was before the change
+            let Some(before) = changesets
+                .seek_by_key_subkey(changed_at, address)?
+                .filter(|entry| entry.address == address)
+            else {
+                continue
+            };
+            if before.info.is_none() {
+                return Ok(true)
+            }
+
+            // the account before the first change at or after the block is the account at the
+            // block, so later changes don't matter
+            if block_number.is_some_and(|block_number| changed_at >= block_number) {
+                return Ok(false)
+            }
+        }
+        shard = history.next()?;
+    }
+
+    // the account after the last change is the account in the plain state
+    Ok(written && tx.get_by_encoded_key::<tables::PlainAccountState>(&address)?.is_none())
+}
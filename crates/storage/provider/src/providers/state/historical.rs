@@ -1,6 +1,7 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    providers::state::{fork, macros::delegate_provider_impls, range},
+    AccountReader, BlockHashReader, HashedPostStateProvider, ProviderError, StateProvider,
+    StateRootProvider,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
 use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
@@ -14,7 +15,8 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
-    BlockNumReader, BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider,
+    AccountRange, BlockNumReader, BytecodeReader, DBProvider, StateProofProvider, StorageRange,
+    StorageRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
@@ -42,6 +44,10 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+///
+/// If the database was forked, accounts and storage slots that were not yet written at the block
+/// are read from the [`DBProvider::fork_state`], unless the storage of the account was wiped
+/// before the block. So are the hashes of the blocks up to the fork block.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -247,7 +253,10 @@ impl<Provider: DBProvider + BlockNumReader> AccountReader
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(*address)? {
-            HistoryInfo::NotYetWritten => Ok(None),
+            HistoryInfo::NotYetWritten => self
+                .provider
+                .fork_state()
+                .map_or(Ok(None), |fork_state| fork_state.account(address)),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
                 .tx()
                 .cursor_dup_read::<tables::AccountChangeSets>()?
@@ -270,6 +279,13 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
 {
     /// Get block hash by number.
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
+        // the blocks up to the fork block are placeholders with the remote hashes
+        if let Some(fork_state) = self.provider.fork_state() &&
+            let Some(hash) = fork_state.block_hash(number)?
+        {
+            return Ok(Some(hash))
+        }
+
         self.provider.block_hash(number)
     }
 
@@ -404,7 +420,14 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
         match self.storage_history_lookup(address, storage_key)? {
-            HistoryInfo::NotYetWritten => Ok(None),
+            HistoryInfo::NotYetWritten => match self.provider.fork_state() {
+                Some(fork_state)
+                    if !fork::is_storage_wiped(self.tx(), address, Some(self.block_number))? =>
+                {
+                    fork_state.storage(address, storage_key)
+                }
+                _ => Ok(None),
+            },
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
                 self.tx()
                     .cursor_dup_read::<tables::StorageChangeSets>()?
@@ -426,6 +449,39 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
                 .or(Some(StorageValue::ZERO))),
         }
     }
+
+    fn account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+        with_addresses: bool,
+    ) -> ProviderResult<AccountRange> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        range::account_range(
+            self.tx(),
+            revert_state,
+            start,
+            limit,
+            storage_limit,
+            with_addresses,
+            true,
+        )
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut revert_storage = self.revert_storage(address)?;
+        revert_storage.extend(&hashed_storage);
+        range::storage_range(self.tx(), address, revert_storage, start, limit, true)
+    }
 }
 
 impl<Provider: DBProvider + BlockNumReader> BytecodeReader
@@ -433,7 +489,11 @@ impl<Provider: DBProvider + BlockNumReader> BytecodeReader
 {
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
-        self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash).map_err(Into::into)
+        if let Some(bytecode) = self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash)? {
+            return Ok(Some(bytecode))
+        }
+
+        self.provider.fork_state().map_or(Ok(None), |fork_state| fork_state.bytecode(code_hash))
     }
 }
 
@@ -522,7 +582,7 @@ mod tests {
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
     };
-    use alloy_primitives::{address, b256, Address, B256, U256};
+    use alloy_primitives::{address, b256, keccak256, Address, B256, U256};
     use reth_db_api::{
         models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
         tables,
@@ -530,8 +590,12 @@ mod tests {
         BlockNumberList,
     };
     use reth_primitives_traits::{Account, StorageEntry};
-    use reth_storage_api::{BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory};
+    use reth_storage_api::{
+        BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory, RangeStorageSlot,
+        StorageRootProvider,
+    };
     use reth_storage_errors::provider::ProviderError;
+    use reth_trie::HashedStorage;
 
     const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0x0000000000000000000000000000000000000005");
@@ -814,4 +878,148 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         ));
     }
+    #[test]
+    fn history_provider_state_range() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let other_storage =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+        let account = Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None };
+        let higher_account = Account { nonce: 2, balance: U256::from(10), bytecode_hash: None };
+
+        // `HIGHER_ADDRESS` is created and `other_storage` is written at block 5
+        tx.put::<tables::AccountChangeSets>(
+            5,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            (5, ADDRESS).into(),
+            StorageEntry { key: other_storage, value: U256::ZERO },
+        )
+        .unwrap();
+
+        for (address, account) in [(ADDRESS, account), (HIGHER_ADDRESS, higher_account)] {
+            tx.put::<tables::PlainAccountState>(address, account).unwrap();
+            tx.put::<tables::HashedAccounts>(keccak256(address), account).unwrap();
+        }
+        for (slot, value) in [(STORAGE, U256::from(1)), (other_storage, U256::from(5))] {
+            tx.put::<tables::PlainStorageState>(ADDRESS, StorageEntry { key: slot, value })
+                .unwrap();
+            tx.put::<tables::HashedStorages>(
+                keccak256(ADDRESS),
+                StorageEntry { key: keccak256(slot), value },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        // before block 5 only `ADDRESS` exists, with a single slot
+        let provider = HistoricalStateProviderRef::new(&db, 5);
+        let range = provider.account_range(Default::default(), B256::ZERO, 10, 10, true).unwrap();
+        assert_eq!(range.next, None);
+        assert_eq!(range.accounts.len(), 1);
+        assert_eq!(range.accounts[0].hashed_address, keccak256(ADDRESS));
+        assert_eq!(range.accounts[0].address, Some(ADDRESS));
+        assert_eq!(range.accounts[0].account, account);
+        assert_eq!(range.accounts[0].storage, vec![(keccak256(STORAGE), U256::from(1))]);
+        assert_eq!(
+            range.accounts[0].storage_root,
+            provider.storage_root(ADDRESS, Default::default()).unwrap()
+        );
+
+        let range = provider.storage_range(ADDRESS, Default::default(), B256::ZERO, 10).unwrap();
+        assert_eq!(
+            range.slots,
+            vec![RangeStorageSlot {
+                hashed_key: keccak256(STORAGE),
+                key: Some(STORAGE),
+                value: U256::from(1)
+            }]
+        );
+
+        // after block 5 both accounts and slots exist
+        let provider = HistoricalStateProviderRef::new(&db, 6);
+        let mut hashed_addresses = [keccak256(ADDRESS), keccak256(HIGHER_ADDRESS)];
+        hashed_addresses.sort();
+        let range = provider.account_range(Default::default(), B256::ZERO, 1, 0, false).unwrap();
+        assert_eq!(range.accounts.len(), 1);
+        assert_eq!(range.accounts[0].hashed_address, hashed_addresses[0]);
+        assert_eq!(range.accounts[0].address, None);
+        assert!(range.accounts[0].storage.is_empty());
+        assert_eq!(range.next, Some(hashed_addresses[1]));
+        let range =
+            provider.account_range(Default::default(), hashed_addresses[1], 1, 0, false).unwrap();
+        assert_eq!(range.accounts[0].hashed_address, hashed_addresses[1]);
+        assert_eq!(range.next, None);
+
+        let mut slots = [STORAGE, other_storage];
+        slots.sort_by_key(|slot| keccak256(slot));
+        let range = provider.storage_range(ADDRESS, Default::default(), B256::ZERO, 1).unwrap();
+        assert_eq!(range.slots.len(), 1);
+        assert_eq!(range.slots[0].key, Some(slots[0]));
+        assert_eq!(range.next, Some(keccak256(slots[1])));
+
+        // the given overlay is applied on top
+        let overlay = HashedStorage::from_iter(false, [(keccak256(slots[0]), U256::ZERO)]);
+        let range = provider.storage_range(ADDRESS, overlay, B256::ZERO, 10).unwrap();
+        assert_eq!(range.slots.len(), 1);
+        assert_eq!(range.slots[0].key, Some(slots[1]));
+    }
+
+    #[test]
+    fn history_provider_storage_range_cleared_slot() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let cleared = b256!("0x0000000000000000000000000000000000000000000000000000000000000003");
+        let account = Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None };
+
+        // `cleared` is zeroed at block 5, so it's only known to the changesets and the history
+        tx.put::<tables::StorageChangeSets>(
+            (5, ADDRESS).into(),
+            StorageEntry { key: cleared, value: U256::from(7) },
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey::last(ADDRESS, cleared),
+            BlockNumberList::new([5]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, account).unwrap();
+        tx.put::<tables::HashedAccounts>(keccak256(ADDRESS), account).unwrap();
+        tx.put::<tables::PlainStorageState>(
+            ADDRESS,
+            StorageEntry { key: STORAGE, value: U256::from(1) },
+        )
+        .unwrap();
+        tx.put::<tables::HashedStorages>(
+            keccak256(ADDRESS),
+            StorageEntry { key: keccak256(STORAGE), value: U256::from(1) },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+        let provider = HistoricalStateProviderRef::new(&db, 5);
+        let range = provider.storage_range(ADDRESS, Default::default(), B256::ZERO, 10).unwrap();
+        let mut expected = vec![
+            RangeStorageSlot {
+                hashed_key: keccak256(STORAGE),
+                key: Some(STORAGE),
+                value: U256::from(1),
+            },
+            RangeStorageSlot {
+                hashed_key: keccak256(cleared),
+                key: Some(cleared),
+                value: U256::from(7),
+            },
+        ];
+        expected.sort_by_key(|slot| slot.hashed_key);
+        assert_eq!(range.slots, expected);
+        assert_eq!(range.next, None);
+    }
 }
@@ -1,11 +1,18 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, StateProvider, StateRootProvider,
+    providers::state::{fork, macros::delegate_provider_impls, range},
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
-use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    tables,
+    transaction::DbTx,
+};
 use reth_primitives_traits::{Account, Bytecode};
-use reth_storage_api::{BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider};
+use reth_storage_api::{
+    AccountRange, BytecodeReader, DBProvider, StateProofProvider, StorageRange, StorageRootProvider,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     proof::{Proof, StorageProof},
@@ -22,6 +29,10 @@ use reth_trie_db::{
 /// State provider over latest state that takes tx reference.
 ///
 /// Wraps a [`DBProvider`] to get access to database.
+///
+/// If the database was forked, accounts and storage slots that were never written to the database
+/// are read from the [`DBProvider::fork_state`], unless the storage of the account was wiped. So
+/// are the hashes of the blocks up to the fork block.
 #[derive(Debug)]
 pub struct LatestStateProviderRef<'b, Provider>(&'b Provider);
 
@@ -34,18 +45,59 @@ impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
     fn tx(&self) -> &Provider::Tx {
         self.0.tx_ref()
     }
+
+    /// Returns `true` if the account was ever written to the database, according to
+    /// [`tables::AccountsHistory`].
+    fn is_account_written(&self, address: Address) -> ProviderResult<bool> {
+        let mut cursor = self.tx().cursor_read::<tables::AccountsHistory>()?;
+        Ok(cursor.seek(ShardedKey::new(address, 0))?.is_some_and(|(key, _)| key.key == address))
+    }
+
+    /// Returns `true` if the storage slot was ever written to the database, according to
+    /// [`tables::StoragesHistory`].
+    fn is_storage_written(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<bool> {
+        let mut cursor = self.tx().cursor_read::<tables::StoragesHistory>()?;
+        Ok(cursor.seek(StorageShardedKey::new(address, storage_key, 0))?.is_some_and(|(key, _)| {
+            key.address == address && key.sharded_key.key == storage_key
+        }))
+    }
 }
 
 impl<Provider: DBProvider> AccountReader for LatestStateProviderRef<'_, Provider> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
-        self.tx().get_by_encoded_key::<tables::PlainAccountState>(address).map_err(Into::into)
+        if let Some(account) = self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)? {
+            return Ok(Some(account))
+        }
+
+        // the account is either deleted or was never written, in which case it's read from the
+        // fork state
+        if let Some(fork_state) = self.0.fork_state() &&
+            !self.is_account_written(*address)?
+        {
+            return fork_state.account(address)
+        }
+
+        Ok(None)
     }
 }
 
-impl<Provider: BlockHashReader> BlockHashReader for LatestStateProviderRef<'_, Provider> {
+impl<Provider: DBProvider + BlockHashReader> BlockHashReader
+    for LatestStateProviderRef<'_, Provider>
+{
     /// Get block hash by number.
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
+        // the blocks up to the fork block are placeholders with the remote hashes
+        if let Some(fork_state) = self.0.fork_state() &&
+            let Some(hash) = fork_state.block_hash(number)?
+        {
+            return Ok(Some(hash))
+        }
+
         self.0.block_hash(number)
     }
 
@@ -163,8 +215,47 @@ impl<Provider: DBProvider + BlockHashReader> StateProvider
         {
             return Ok(Some(entry.value))
         }
+
+        // zero slots are removed from the plain state, so a missing slot that was written before,
+        // or whose account storage was wiped, is zero rather than unknown
+        if let Some(fork_state) = self.0.fork_state() &&
+            !self.is_storage_written(account, storage_key)? &&
+            !fork::is_storage_wiped(self.tx(), account, None)?
+        {
+            return fork_state.storage(account, storage_key)
+        }
+
         Ok(None)
     }
+
+    fn account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+        with_addresses: bool,
+    ) -> ProviderResult<AccountRange> {
+        range::account_range(
+            self.tx(),
+            hashed_state,
+            start,
+            limit,
+            storage_limit,
+            with_addresses,
+            false,
+        )
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        range::storage_range(self.tx(), address, hashed_storage, start, limit, false)
+    }
 }
 
 impl<Provider: DBProvider + BlockHashReader> BytecodeReader
@@ -172,7 +263,11 @@ impl<Provider: DBProvider + BlockHashReader> BytecodeReader
 {
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
-        self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash).map_err(Into::into)
+        if let Some(bytecode) = self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash)? {
+            return Ok(Some(bytecode))
+        }
+
+        self.0.fork_state().map_or(Ok(None), |fork_state| fork_state.bytecode(code_hash))
     }
 }
 
@@ -199,10 +294,119 @@ delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvid
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{providers::HistoricalStateProviderRef, test_utils::create_test_provider_factory};
+    use alloy_primitives::{address, b256, U256};
+    use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut, BlockNumberList};
+    use reth_storage_api::ForkStateReader;
+    use std::sync::Arc;
+
+    const WRITTEN: Address = address!("0x0000000000000000000000000000000000000001");
+    const UNWRITTEN: Address = address!("0x0000000000000000000000000000000000000002");
+    const CREATED: Address = address!("0x0000000000000000000000000000000000000003");
+    const DESTROYED: Address = address!("0x0000000000000000000000000000000000000004");
+    const STORAGE: B256 =
+        b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+    const FORK_BLOCK: BlockNumber = 10;
+
+    /// A fork state in which every account has nonce 1 and every storage slot is 1, forked at
+    /// [`FORK_BLOCK`].
+    #[derive(Debug)]
+    struct TestForkState;
+
+    impl ForkStateReader for TestForkState {
+        fn account(&self, _address: &Address) -> ProviderResult<Option<Account>> {
+            Ok(Some(Account { nonce: 1, ..Default::default() }))
+        }
+
+        fn storage(&self, _address: Address, _key: StorageKey) -> ProviderResult<Option<U256>> {
+            Ok(Some(U256::from(1)))
+        }
+
+        fn bytecode(&self, _code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+            Ok(None)
+        }
+
+        fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+            Ok((number <= FORK_BLOCK).then(|| B256::with_last_byte(number as u8)))
+        }
+    }
 
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
     const fn assert_latest_state_provider<T: DBProvider + BlockHashReader>() {
         assert_state_provider::<LatestStateProvider<T>>();
     }
+
+    #[test]
+    fn latest_provider_reads_unwritten_state_from_fork() {
+        let factory = create_test_provider_factory().with_fork_state(Arc::new(TestForkState));
+
+        // the account and storage slot were written and then deleted by block 1
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(WRITTEN, u64::MAX),
+            BlockNumberList::new([1]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey::new(WRITTEN, STORAGE, u64::MAX),
+            BlockNumberList::new([1]).unwrap(),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+        let provider = LatestStateProviderRef::new(&db);
+        assert_eq!(provider.basic_account(&WRITTEN).unwrap(), None);
+        assert_eq!(provider.storage(WRITTEN, STORAGE).unwrap(), None);
+        assert_eq!(
+            provider.basic_account(&UNWRITTEN).unwrap(),
+            Some(Account { nonce: 1, ..Default::default() })
+        );
+        assert_eq!(provider.storage(UNWRITTEN, STORAGE).unwrap(), Some(U256::from(1)));
+    }
+
+    #[test]
+    fn fork_state_storage_of_wiped_accounts() {
+        let factory = create_test_provider_factory().with_fork_state(Arc::new(TestForkState));
+
+        // the remote account was modified by block 11, the new account was created by block 11
+        // and the remote account was destroyed by block 12
+        let remote = Account { nonce: 1, ..Default::default() };
+        let tx = factory.provider_rw().unwrap().into_tx();
+        for (address, blocks) in [(WRITTEN, &[11][..]), (CREATED, &[11]), (DESTROYED, &[11, 12])] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new(blocks.iter().copied()).unwrap(),
+            )
+            .unwrap();
+        }
+        for (block, address, info) in [
+            (11, WRITTEN, Some(remote)),
+            (11, CREATED, None),
+            (11, DESTROYED, Some(remote)),
+            (12, DESTROYED, Some(Account { nonce: 2, ..remote })),
+        ] {
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address, info }).unwrap();
+        }
+        for address in [WRITTEN, CREATED] {
+            tx.put::<tables::PlainAccountState>(address, Account { nonce: 2, ..remote }).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+        let latest = LatestStateProviderRef::new(&db);
+        assert_eq!(latest.storage(WRITTEN, STORAGE).unwrap(), Some(U256::from(1)));
+        assert_eq!(latest.storage(CREATED, STORAGE).unwrap(), None);
+        assert_eq!(latest.storage(DESTROYED, STORAGE).unwrap(), None);
+
+        // the remote account wasn't destroyed yet at block 12
+        let historical = HistoricalStateProviderRef::new(&db, 12);
+        assert_eq!(historical.storage(DESTROYED, STORAGE).unwrap(), Some(U256::from(1)));
+        assert_eq!(historical.storage(CREATED, STORAGE).unwrap(), None);
+
+        // the hashes of the blocks up to the fork block are the remote ones
+        assert_eq!(latest.block_hash(FORK_BLOCK).unwrap(), Some(B256::with_last_byte(10)));
+        assert_eq!(historical.block_hash(1).unwrap(), Some(B256::with_last_byte(1)));
+    }
 }
@@ -39,6 +39,8 @@ macro_rules! delegate_provider_impls {
             }
             StateProvider $(where [$($generics)*])? {
                 fn storage(&self, account: alloy_primitives::Address, storage_key: alloy_primitives::StorageKey) -> reth_storage_errors::provider::ProviderResult<Option<alloy_primitives::StorageValue>>;
+                fn account_range(&self, hashed_state: reth_trie::HashedPostState, start: alloy_primitives::B256, limit: usize, storage_limit: usize, with_addresses: bool) -> reth_storage_errors::provider::ProviderResult<reth_storage_api::AccountRange>;
+                fn storage_range(&self, address: alloy_primitives::Address, hashed_storage: reth_trie::HashedStorage, start: alloy_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_storage_api::StorageRange>;
             }
             BytecodeReader $(where [$($generics)*])? {
                 fn bytecode_by_hash(&self, code_hash: &alloy_primitives::B256) -> reth_storage_errors::provider::ProviderResult<Option<reth_primitives_traits::Bytecode>>;
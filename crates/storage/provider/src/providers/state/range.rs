@@ -0,0 +1,235 @@
+//! Helpers for paging through the hashed state on top of the database.
+
+use alloy_primitives::{
+    keccak256,
+    map::{B256Map, B256Set},
+    Address, B256, U256,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    tables,
+    transaction::DbTx,
+};
+use reth_storage_api::{AccountRange, RangeAccount, RangeStorageSlot, StorageRange};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie::{
+    hashed_cursor::{
+        HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory, HashedStorageCursor,
+    },
+    metrics::TrieRootMetrics,
+    HashedPostState, HashedStorage, StorageRoot, TrieType,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+
+/// Hashed storage slots and the hashed slot following them, if any.
+type HashedStorageSlots = (Vec<(B256, U256)>, Option<B256>);
+
+/// Returns up to `limit` accounts starting at the `start` hashed address from the database state
+/// with the given [`HashedPostState`] applied on top, with up to `storage_limit` storage slots of
+/// every account.
+///
+/// If `with_addresses` is set, address preimages are resolved like slot preimages in
+/// [`storage_range`].
+pub(crate) fn account_range<TX: DbTx>(
+    tx: &TX,
+    hashed_state: HashedPostState,
+    start: B256,
+    limit: usize,
+    storage_limit: usize,
+    with_addresses: bool,
+    with_history: bool,
+) -> ProviderResult<AccountRange> {
+    let prefix_sets = hashed_state.construct_prefix_sets();
+    let state_sorted = hashed_state.into_sorted();
+    let hashed_cursor_factory =
+        HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted);
+
+    let mut range = AccountRange::default();
+    let mut cursor = hashed_cursor_factory.hashed_account_cursor()?;
+    let mut entry = cursor.seek(start)?;
+    while let Some((hashed_address, account)) = entry {
+        if range.accounts.len() == limit {
+            range.next = Some(hashed_address);
+            break
+        }
+
+        let prefix_set = prefix_sets
+            .storage_prefix_sets
+            .get(&hashed_address)
+            .cloned()
+            .unwrap_or_default()
+            .freeze();
+        let storage_root = StorageRoot::new_hashed(
+            DatabaseTrieCursorFactory::new(tx),
+            hashed_cursor_factory.clone(),
+            hashed_address,
+            prefix_set,
+            TrieRootMetrics::new(TrieType::Storage),
+        )
+        .root()
+        .map_err(|err| ProviderError::Database(err.into()))?;
+
+        let storage = if storage_limit > 0 {
+            hashed_storage_range(&hashed_cursor_factory, hashed_address, B256::ZERO, storage_limit)?
+                .0
+        } else {
+            Vec::new()
+        };
+
+        range.accounts.push(RangeAccount {
+            hashed_address,
+            address: None,
+            account,
+            storage_root,
+            storage,
+        });
+        entry = cursor.next()?;
+    }
+
+    if with_addresses {
+        resolve_addresses(tx, &mut range.accounts, with_history)?;
+    }
+
+    Ok(range)
+}
+
+/// Resolves the address preimages of the accounts from the plain account state and, if
+/// `with_history` is set, from the account history index, which also has the accounts cleared
+/// since then. Both are only walked until the preimages of all accounts are found.
+fn resolve_addresses<TX: DbTx>(
+    tx: &TX,
+    accounts: &mut [RangeAccount],
+    with_history: bool,
+) -> ProviderResult<()> {
+    let mut missing = accounts
+        .iter()
+        .enumerate()
+        .map(|(idx, account)| (account.hashed_address, idx))
+        .collect::<B256Map<_>>();
+    let mut resolved = missing.is_empty();
+    // Records the preimage if it's missing and returns whether all preimages are resolved.
+    let mut resolve = |address: Address| {
+        if let Some(idx) = missing.remove(&keccak256(address)) {
+            accounts[idx].address = Some(address);
+        }
+        missing.is_empty()
+    };
+
+    if !resolved {
+        for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+            resolved = resolve(entry?.0);
+            if resolved {
+                break
+            }
+        }
+    }
+    if with_history && !resolved {
+        let mut cursor = tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut entry = cursor.first()?;
+        while let Some((key, _)) = entry {
+            if resolve(key.key) {
+                break
+            }
+            // Skip the other shards of the account, the last one is keyed by `u64::MAX`.
+            cursor.seek_exact(ShardedKey::last(key.key))?;
+            entry = cursor.next()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns up to `limit` storage slots of the account starting at the `start` hashed slot from the
+/// database state with the given [`HashedStorage`] applied on top.
+///
+/// Slot preimages are resolved from the plain storage state of the account and, if `with_history`
+/// is set, from the storage history index, which also has the slots cleared since then. Both are
+/// only walked until the preimages of all returned slots are found.
+pub(crate) fn storage_range<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    hashed_storage: HashedStorage,
+    start: B256,
+    limit: usize,
+    with_history: bool,
+) -> ProviderResult<StorageRange> {
+    let hashed_address = keccak256(address);
+    let state_sorted =
+        HashedPostState::from_hashed_storage(hashed_address, hashed_storage).into_sorted();
+    let hashed_cursor_factory =
+        HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted);
+    let (slots, next) = hashed_storage_range(&hashed_cursor_factory, hashed_address, start, limit)?;
+
+    let mut missing = slots.iter().map(|(hashed_key, _)| *hashed_key).collect::<B256Set>();
+    let mut preimages = B256Map::default();
+    let mut resolved = missing.is_empty();
+    // Records the preimage if it's missing and returns whether all preimages are resolved.
+    let mut resolve = |key: B256| {
+        let hashed_key = keccak256(key);
+        if missing.remove(&hashed_key) {
+            preimages.insert(hashed_key, key);
+        }
+        missing.is_empty()
+    };
+
+    if !resolved {
+        for entry in
+            tx.cursor_dup_read::<tables::PlainStorageState>()?.walk_dup(Some(address), None)?
+        {
+            resolved = resolve(entry?.1.key);
+            if resolved {
+                break
+            }
+        }
+    }
+    if with_history && !resolved {
+        let mut cursor = tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut entry = cursor.seek(StorageShardedKey::new(address, B256::ZERO, 0))?;
+        while let Some((key, _)) = entry {
+            if key.address != address || resolve(key.sharded_key.key) {
+                break
+            }
+            // Skip the other shards of the slot, the last one is keyed by `u64::MAX`.
+            cursor.seek_exact(StorageShardedKey::last(address, key.sharded_key.key))?;
+            entry = cursor.next()?;
+        }
+    }
+
+    Ok(StorageRange {
+        slots: slots
+            .into_iter()
+            .map(|(hashed_key, value)| RangeStorageSlot {
+                hashed_key,
+                key: preimages.get(&hashed_key).copied(),
+                value,
+            })
+            .collect(),
+        next,
+    })
+}
+
+/// Returns up to `limit` storage slots of the account starting at the `start` hashed slot.
+fn hashed_storage_range<F: HashedCursorFactory>(
+    hashed_cursor_factory: &F,
+    hashed_address: B256,
+    start: B256,
+    limit: usize,
+) -> ProviderResult<HashedStorageSlots> {
+    let mut slots = Vec::new();
+    let mut cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+    if cursor.is_storage_empty()? {
+        return Ok((slots, None))
+    }
+
+    let mut entry = cursor.seek(start)?;
+    while let Some((hashed_slot, value)) = entry {
+        if slots.len() == limit {
+            return Ok((slots, Some(hashed_slot)))
+        }
+        slots.push((hashed_slot, value));
+        entry = cursor.next()?;
+    }
+
+    Ok((slots, None))
+}
@@ -21,20 +21,21 @@ use reth_chainspec::{ChainInfo, EthChainSpec};
 use reth_db::transaction::DbTx;
 use reth_db_api::{
     mock::{DatabaseMock, TxMock},
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
 };
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{
     Account, Block, BlockBody, Bytecode, GotExpected, NodePrimitives, RecoveredBlock, SealedHeader,
-    SignerRecoverable,
+    SignerRecoverable, StorageEntry,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
-    StorageRootProvider,
+    AddressAppearancesProvider, BlockBodyIndicesProvider, BlockLogKeys, BytecodeReader, DBProvider,
+    DatabaseProviderFactory, HashedPostStateProvider, LogIndexProvider, NodePrimitivesProvider,
+    StageCheckpointReader, StateProofProvider, StorageChangeSetReader, StorageRootProvider,
+    TraceIndexProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -753,6 +754,99 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> AccountReader for MockEthProvide
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> AddressAppearancesProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn address_appearances_before(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(vec![])
+    }
+
+    fn address_appearances_after(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(vec![])
+    }
+
+    fn block_address_appearances(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+        _include_logs: bool,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Address>)>> {
+        Ok(vec![])
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> LogIndexProvider for MockEthProvider<T, ChainSpec> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_log_positions(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        Ok(vec![])
+    }
+
+    fn topic_log_positions(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        Ok(vec![])
+    }
+
+    fn block_log_keys(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, BlockLogKeys)>> {
+        Ok(vec![])
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> TraceIndexProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_trace_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(vec![])
+    }
+
+    fn address_trace_blocks_before(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(vec![])
+    }
+
+    fn address_trace_blocks_after(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(vec![])
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StageCheckpointReader
     for MockEthProvider<T, ChainSpec>
 {
@@ -982,6 +1076,17 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvi
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> StorageChangeSetReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn storage_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StateReader for MockEthProvider<T, ChainSpec> {
     type Receipt = T::Receipt;
 
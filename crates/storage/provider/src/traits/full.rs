@@ -1,9 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, HashedPostStateProvider, StageCheckpointReader, StateProviderFactory,
-    StateReader, StaticFileProviderFactory,
+    AccountReader, AddressAppearancesProvider, BlockReader, BlockReaderIdExt, ChainSpecProvider,
+    ChangeSetReader, DatabaseProviderFactory, HashedPostStateProvider, LogIndexProvider,
+    SnapStateProvider, StageCheckpointReader, StateProviderFactory, StateReader,
+    StaticFileProviderFactory, StorageChangeSetReader, TraceIndexProvider,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
@@ -26,9 +27,14 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + HashedPostStateProvider
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
+    + StorageChangeSetReader
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + StageCheckpointReader
+    + AddressAppearancesProvider
+    + LogIndexProvider
+    + TraceIndexProvider
+    + SnapStateProvider
     + Clone
     + Debug
     + Unpin
@@ -51,9 +57,14 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + HashedPostStateProvider
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
+        + StorageChangeSetReader
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + StageCheckpointReader
+        + AddressAppearancesProvider
+        + LogIndexProvider
+        + TraceIndexProvider
+        + SnapStateProvider
         + Clone
         + Debug
         + Unpin
@@ -0,0 +1,288 @@
+//! Remote state of a forked chain, fetched on demand and cached in a local database.
+
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_network::{primitives::HeaderResponse, BlockResponse, Network};
+use alloy_primitives::{keccak256, Address, BlockNumber, StorageKey, StorageValue, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockId;
+use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
+use reth_db_api::{
+    cursor::DbDupCursorRO,
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_errors::{ProviderError, ProviderResult, RethError, RethResult};
+use reth_primitives::{Account, Bytecode, StorageEntry};
+use reth_storage_api::ForkStateReader;
+use std::{future::Future, marker::PhantomData, path::Path, sync::Arc};
+use tokio::runtime::Handle;
+use tracing::trace;
+
+/// The state of a remote chain at a fork block, implementing [`ForkStateReader`].
+///
+/// Accounts, storage slots, bytecode and block hashes are fetched over RPC the first time they're
+/// read and stored in a local MDBX database, so that they are never fetched twice, including
+/// across restarts.
+///
+/// The cache is only valid for a single fork block, so the cache directory should be unique per
+/// fork block, e.g. by including the fork block hash.
+pub struct RpcForkState<P, N = alloy_network::AnyNetwork> {
+    /// The underlying Alloy provider
+    provider: P,
+    /// The block the state is forked at
+    fork_block: BlockNumber,
+    /// The [`BlockId`] of the fork block
+    block_id: BlockId,
+    /// The database caching the fetched state
+    cache: Arc<DatabaseEnv>,
+    /// The runtime the RPC requests are executed on
+    handle: Handle,
+    /// Network marker
+    network: PhantomData<N>,
+}
+
+impl<P, N> std::fmt::Debug for RpcForkState<P, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcForkState").field("fork_block", &self.fork_block).finish_non_exhaustive()
+    }
+}
+
+impl<P, N> RpcForkState<P, N>
+where
+    P: Provider<N> + 'static,
+    N: Network,
+{
+    /// Creates the fork state at the given block, caching fetched state in the database at
+    /// `cache_dir`.
+    ///
+    /// Must be called from within a tokio runtime, which is used to execute the RPC requests.
+    pub fn new(
+        provider: P,
+        fork_block: BlockNumber,
+        cache_dir: impl AsRef<Path>,
+    ) -> RethResult<Self> {
+        let cache = init_db(cache_dir, DatabaseArguments::default()).map_err(RethError::msg)?;
+        Ok(Self {
+            provider,
+            fork_block,
+            block_id: BlockId::number(fork_block),
+            cache: Arc::new(cache),
+            handle: Handle::current(),
+            network: PhantomData,
+        })
+    }
+
+    /// Executes the future to completion, regardless of whether the caller is in an async context.
+    fn block_on<F: Future>(&self, fut: F) -> F::Output {
+        if Handle::try_current().is_ok() {
+            tokio::task::block_in_place(|| self.handle.block_on(fut))
+        } else {
+            self.handle.block_on(fut)
+        }
+    }
+
+    /// Fetches the account and its bytecode from the remote node.
+    fn fetch_account(&self, address: Address) -> ProviderResult<(Account, Option<Bytecode>)> {
+        trace!(target: "providers::fork", %address, "Fetching account");
+        let (nonce, balance, code) = self.block_on(async {
+            tokio::join!(
+                self.provider.get_transaction_count(address).block_id(self.block_id),
+                self.provider.get_balance(address).block_id(self.block_id),
+                self.provider.get_code_at(address).block_id(self.block_id),
+            )
+        });
+        let nonce = nonce.map_err(ProviderError::other)?;
+        let balance = balance.map_err(ProviderError::other)?;
+        let code = code.map_err(ProviderError::other)?;
+
+        let code_hash = if code.is_empty() { KECCAK_EMPTY } else { keccak256(&code) };
+        let bytecode_hash = (code_hash != KECCAK_EMPTY).then_some(code_hash);
+        let bytecode = bytecode_hash.map(|_| Bytecode::new_raw(code));
+
+        Ok((Account { nonce, balance, bytecode_hash }, bytecode))
+    }
+}
+
+impl<P, N> ForkStateReader for RpcForkState<P, N>
+where
+    P: Provider<N> + 'static,
+    N: Network,
+{
+    fn account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        // accounts that don't exist are cached as empty accounts
+        let cached = self.cache.tx()?.get_by_encoded_key::<tables::PlainAccountState>(address)?;
+        let account = match cached {
+            Some(account) => account,
+            None => {
+                let (account, bytecode) = self.fetch_account(*address)?;
+                let tx = self.cache.tx_mut()?;
+                tx.put::<tables::PlainAccountState>(*address, account)?;
+                if let Some((code_hash, bytecode)) = account.bytecode_hash.zip(bytecode) {
+                    tx.put::<tables::Bytecodes>(code_hash, bytecode)?;
+                }
+                tx.commit()?;
+                account
+            }
+        };
+
+        Ok((!account.is_empty()).then_some(account))
+    }
+
+    fn storage(&self, address: Address, key: StorageKey) -> ProviderResult<Option<StorageValue>> {
+        let tx = self.cache.tx()?;
+        let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        if let Some(entry) = cursor.seek_by_key_subkey(address, key)? &&
+            entry.key == key
+        {
+            return Ok(Some(entry.value))
+        }
+
+        trace!(target: "providers::fork", %address, %key, "Fetching storage");
+        let value = self
+            .block_on(async {
+                self.provider.get_storage_at(address, key.into()).block_id(self.block_id).await
+            })
+            .map_err(ProviderError::other)?;
+
+        let tx = self.cache.tx_mut()?;
+        tx.put::<tables::PlainStorageState>(address, StorageEntry { key, value })?;
+        tx.commit()?;
+
+        Ok(Some(value))
+    }
+
+    fn bytecode(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        // bytecode is cached alongside the account it belongs to
+        Ok(self.cache.tx()?.get_by_encoded_key::<tables::Bytecodes>(code_hash)?)
+    }
+
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        if number > self.fork_block {
+            return Ok(None)
+        }
+        if let Some(hash) = self.cache.tx()?.get::<tables::CanonicalHeaders>(number)? {
+            return Ok(Some(hash))
+        }
+
+        trace!(target: "providers::fork", number, "Fetching block hash");
+        let hash = self
+            .block_on(async { self.provider.get_block_by_number(number.into()).await })
+            .map_err(ProviderError::other)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?
+            .header()
+            .hash();
+
+        let tx = self.cache.tx_mut()?;
+        tx.put::<tables::CanonicalHeaders>(number, hash)?;
+        tx.commit()?;
+
+        Ok(Some(hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{address, b256, Bytes, U256, U64};
+    use alloy_provider::ProviderBuilder;
+    use alloy_rpc_types::{Block, BlockNumberOrTag};
+    use jsonrpsee::{
+        core::RpcResult,
+        server::{Server, ServerHandle},
+        RpcModule,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const CONTRACT: Address = address!("0x00000000000000000000000000000000000000c0");
+    const SLOT: B256 = b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+
+    /// The header of the remote block with the given number.
+    fn block_header(number: BlockNumber) -> alloy_rpc_types::Header {
+        alloy_rpc_types::Header::new(Header { number, ..Default::default() })
+    }
+
+    /// Starts a stand-in for a remote node that serves a single contract and counts the number of
+    /// requests it receives.
+    async fn spawn_remote() -> (String, Arc<AtomicUsize>, ServerHandle) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let mut module = RpcModule::new(requests.clone());
+        module
+            .register_method("eth_getTransactionCount", |params, requests, _| -> RpcResult<U64> {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let (address, _block): (Address, BlockId) = params.parse()?;
+                Ok(U64::from(if address == CONTRACT { 1 } else { 0 }))
+            })
+            .unwrap();
+        module
+            .register_method("eth_getBalance", |params, requests, _| -> RpcResult<U256> {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let (address, _block): (Address, BlockId) = params.parse()?;
+                Ok(if address == CONTRACT { U256::from(100) } else { U256::ZERO })
+            })
+            .unwrap();
+        module
+            .register_method("eth_getCode", |params, requests, _| -> RpcResult<Bytes> {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let (address, _block): (Address, BlockId) = params.parse()?;
+                let code = if address == CONTRACT { &[0x60, 0x00][..] } else { &[] };
+                Ok(Bytes::from_static(code))
+            })
+            .unwrap();
+        module
+            .register_method("eth_getStorageAt", |params, requests, _| -> RpcResult<U256> {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let (address, slot, _block): (Address, U256, BlockId) = params.parse()?;
+                Ok(if address == CONTRACT && slot == U256::from_be_bytes(SLOT.0) {
+                    U256::from(42)
+                } else {
+                    U256::ZERO
+                })
+            })
+            .unwrap();
+        module
+            .register_method("eth_getBlockByNumber", |params, requests, _| -> RpcResult<Block> {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let (number, _full): (BlockNumberOrTag, bool) = params.parse()?;
+                Ok(Block::empty(block_header(number.as_number().unwrap_or_default())))
+            })
+            .unwrap();
+
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        (url, requests, server.start(module))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fetches_and_caches_remote_state() {
+        let (url, requests, _server) = spawn_remote().await;
+        let cache_dir = tempfile::tempdir().unwrap();
+        let provider = ProviderBuilder::new().connect_http(url.parse().unwrap());
+
+        let fork_state = RpcForkState::new(provider.clone(), 10, cache_dir.path()).unwrap();
+
+        let account = fork_state.account(&CONTRACT).unwrap().unwrap();
+        assert_eq!(account.nonce, 1);
+        assert_eq!(account.balance, U256::from(100));
+        let bytecode = fork_state.bytecode(&account.bytecode_hash.unwrap()).unwrap().unwrap();
+        assert_eq!(bytecode.original_byte_slice(), &[0x60, 0x00]);
+        assert_eq!(fork_state.account(&Address::ZERO).unwrap(), None);
+        assert_eq!(fork_state.storage(CONTRACT, SLOT).unwrap(), Some(U256::from(42)));
+        assert_eq!(fork_state.storage(CONTRACT, B256::ZERO).unwrap(), Some(U256::ZERO));
+        assert_eq!(fork_state.block_hash(5).unwrap(), Some(block_header(5).hash));
+        assert_eq!(fork_state.block_hash(11).unwrap(), None);
+        let fetched = requests.load(Ordering::SeqCst);
+        assert_eq!(fetched, 9);
+
+        // reopening the cache serves everything from disk
+        drop(fork_state);
+        let fork_state = RpcForkState::new(provider, 10, cache_dir.path()).unwrap();
+        assert_eq!(fork_state.account(&CONTRACT).unwrap(), Some(account));
+        assert_eq!(fork_state.account(&Address::ZERO).unwrap(), None);
+        assert_eq!(fork_state.storage(CONTRACT, SLOT).unwrap(), Some(U256::from(42)));
+        assert_eq!(fork_state.storage(CONTRACT, B256::ZERO).unwrap(), Some(U256::ZERO));
+        assert_eq!(fork_state.block_hash(5).unwrap(), Some(block_header(5).hash));
+        assert_eq!(requests.load(Ordering::SeqCst), fetched);
+    }
+}
@@ -15,6 +15,7 @@
 //! - Supports Ethereum and Optimism network
 //! - Useful for testing without requiring a full database
 //! - Can be used with reth ExEx (Execution Extensions) for testing
+//! - Provides [`RpcForkState`], the disk-cached remote state a local dev chain can be forked from
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -69,6 +70,9 @@ use std::{
 use tokio::{runtime::Handle, sync::broadcast};
 use tracing::{trace, warn};
 
+mod fork;
+pub use fork::RpcForkState;
+
 /// Configuration for `RpcBlockchainProvider`
 #[derive(Debug, Clone)]
 pub struct RpcBlockchainProviderConfig {
@@ -0,0 +1,39 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for reading the address appearance index.
+///
+/// The index is optional and only populated by the `IndexAddressAppearances` stage, so callers
+/// should check its stage checkpoint before relying on an empty result.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AddressAppearancesProvider: Send + Sync {
+    /// Returns at most `limit` numbers of the blocks lower than `block` in which the address
+    /// appears, in descending order.
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns at most `limit` numbers of the blocks higher than `block` in which the address
+    /// appears, in ascending order.
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the addresses appearing in each block of the given range.
+    ///
+    /// These are transaction senders, recipients and created contracts. If `include_logs` is set,
+    /// log emitters and address-shaped log topics are included as well.
+    fn block_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        include_logs: bool,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Address>)>>;
+}
@@ -1,3 +1,4 @@
+use crate::ForkStateReader;
 use alloc::vec::Vec;
 use core::ops::{Bound, RangeBounds};
 use reth_db_api::{
@@ -42,6 +43,14 @@ pub trait DBProvider: Sized {
     /// Returns a reference to prune modes.
     fn prune_modes_ref(&self) -> &PruneModes;
 
+    /// Returns the state the database was forked from, if any.
+    ///
+    /// State providers read the accounts and storage slots that were never written to the
+    /// database from it.
+    fn fork_state(&self) -> Option<&dyn ForkStateReader> {
+        None
+    }
+
     /// Return full table as Vec
     fn table<T: Table>(&self) -> Result<Vec<KeyValue<T>>, DatabaseError>
     where
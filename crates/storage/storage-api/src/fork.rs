@@ -0,0 +1,34 @@
+use alloy_primitives::{Address, BlockNumber, StorageKey, StorageValue, B256};
+use auto_impl::auto_impl;
+use core::fmt::Debug;
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Reader of the remote state a local chain was forked from.
+///
+/// The local database only holds the state written by local blocks. Any account or storage slot
+/// the local chain has never written is read from the fork state instead, as are the hashes of
+/// the blocks up to the fork block.
+#[auto_impl(&, Arc, Box)]
+pub trait ForkStateReader: Debug + Send + Sync {
+    /// Get basic account information at the fork block.
+    ///
+    /// Returns `None` if the account doesn't exist.
+    fn account(&self, address: &Address) -> ProviderResult<Option<Account>>;
+
+    /// Get the value of the storage slot at the fork block.
+    ///
+    /// Returns `None` if the storage slot doesn't exist.
+    fn storage(&self, address: Address, key: StorageKey) -> ProviderResult<Option<StorageValue>>;
+
+    /// Get the bytecode with the given hash.
+    ///
+    /// Only the bytecode of accounts previously returned by [`Self::account`] is guaranteed to be
+    /// available.
+    fn bytecode(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>>;
+
+    /// Get the hash of the remote block with the given number.
+    ///
+    /// Returns `None` if the block is after the fork block.
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>>;
+}
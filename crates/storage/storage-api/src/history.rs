@@ -53,6 +53,34 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: impl IntoIterator<Item = ((Address, B256), impl IntoIterator<Item = u64>)>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear address appearance indices.
+    ///
+    /// Removes all appearances of each address at or above the block number paired with it.
+    /// Returns number of addresses unwound.
+    fn unwind_address_appearance_indices(
+        &self,
+        appearances: impl IntoIterator<Item = (Address, BlockNumber)>,
+    ) -> ProviderResult<usize>;
+
+    /// Unwind and clear log indices.
+    ///
+    /// Removes all entries of each log address and topic at or above the log position paired with
+    /// it, see [`log_position`](crate::log_position). Returns number of keys unwound.
+    fn unwind_log_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, u64)>,
+        topics: impl IntoIterator<Item = (B256, u64)>,
+    ) -> ProviderResult<usize>;
+
+    /// Unwind and clear trace indices.
+    ///
+    /// Removes all entries of each address at or above the block number paired with it. Returns
+    /// number of addresses unwound.
+    fn unwind_trace_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, BlockNumber)>,
+    ) -> ProviderResult<usize>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
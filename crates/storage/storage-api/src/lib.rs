@@ -16,6 +16,9 @@ pub use reth_storage_errors as errors;
 mod account;
 pub use account::*;
 
+mod address_appearances;
+pub use address_appearances::*;
+
 mod block;
 pub use block::*;
 
@@ -30,9 +33,15 @@ mod chain;
 #[cfg(feature = "db-api")]
 pub use chain::*;
 
+mod fork;
+pub use fork::*;
+
 mod header;
 pub use header::*;
 
+mod log_index;
+pub use log_index::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
@@ -51,6 +60,9 @@ pub use storage::*;
 mod transactions;
 pub use transactions::*;
 
+mod trace_index;
+pub use trace_index::*;
+
 mod trie;
 pub use trie::*;
 
@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber, B256};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Number of low bits of a log position holding the index of the log in its block.
+pub const LOG_INDEX_BITS: u32 = 24;
+
+/// Returns the position of the log with the given index in its block, as stored in the log index.
+///
+/// The block number is kept in the high bits, so positions are ordered the same way as the logs
+/// of the chain.
+pub const fn log_position(block_number: BlockNumber, log_index: u64) -> u64 {
+    (block_number << LOG_INDEX_BITS) | log_index
+}
+
+/// Returns the number of the block containing the log at the given position.
+pub const fn log_position_block(position: u64) -> BlockNumber {
+    position >> LOG_INDEX_BITS
+}
+
+/// Returns the range of positions of the logs of the given blocks.
+pub const fn log_positions(range: RangeInclusive<BlockNumber>) -> RangeInclusive<u64> {
+    log_position(*range.start(), 0)..=log_position(*range.end(), (1 << LOG_INDEX_BITS) - 1)
+}
+
+/// The log addresses and topics of a block, paired with the positions of the logs they appear in.
+///
+/// Pairs are sorted and deduplicated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockLogKeys {
+    /// Addresses of the contracts that emitted logs in the block.
+    pub addresses: Vec<(Address, u64)>,
+    /// Topics of the logs in the block, regardless of their position in the log.
+    pub topics: Vec<(B256, u64)>,
+}
+
+/// Client trait for reading the log index.
+///
+/// The index is optional and only populated by the `IndexLogs` stage, so callers should check
+/// [`LogIndexProvider::log_index_range`] before relying on an empty result.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogIndexProvider: Send + Sync {
+    /// Returns the range of blocks covered by the log index, or `None` if it's disabled.
+    ///
+    /// Blocks below the range were pruned, blocks above it are not indexed yet.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the positions of the logs emitted by the address in the given range of blocks, in
+    /// ascending order. See [`log_position`].
+    fn address_log_positions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>>;
+
+    /// Returns the positions of the logs with the topic at any position in the given range of
+    /// blocks, in ascending order. See [`log_position`].
+    fn topic_log_positions(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>>;
+
+    /// Returns the log addresses and topics of each block of the given range that has logs.
+    fn block_log_keys(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, BlockLogKeys)>>;
+}
@@ -1,18 +1,19 @@
 //! Various noop implementations for traits.
 
 use crate::{
-    AccountReader, BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, BytecodeReader, ChangeSetReader,
-    HashedPostStateProvider, HeaderProvider, NodePrimitivesProvider, PruneCheckpointReader,
-    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProofProvider,
-    StateProvider, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
-    StorageRootProvider, TransactionVariant, TransactionsProvider,
+    AccountReader, AddressAppearancesProvider, BlockBodyIndicesProvider, BlockHashReader,
+    BlockIdReader, BlockLogKeys, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    BytecodeReader, ChangeSetReader, HashedPostStateProvider, HeaderProvider, LogIndexProvider,
+    NodePrimitivesProvider, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    SnapStateOverlay, SnapStateProvider, StageCheckpointReader, StateProofProvider, StateProvider,
+    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, StorageRootProvider,
+    TraceIndexProvider, TransactionVariant, TransactionsProvider,
 };
 
 #[cfg(feature = "db-api")]
 use crate::{DBProvider, DatabaseProviderFactory};
 use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::{transaction::TransactionMeta, EMPTY_ROOT_HASH};
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
     Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, TxNumber, B256, U256,
@@ -397,6 +398,18 @@ impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     }
 }
 
+#[cfg(feature = "db-api")]
+impl<C: Send + Sync, N: NodePrimitives> crate::StorageChangeSetReader for NoopProvider<C, N> {
+    fn storage_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<
+        Vec<(reth_db_api::models::BlockNumberAddress, reth_primitives_traits::StorageEntry)>,
+    > {
+        Ok(Vec::default())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StateRootProvider for NoopProvider<C, N> {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -472,6 +485,64 @@ impl<C: Send + Sync, N: NodePrimitives> StateProofProvider for NoopProvider<C, N
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> SnapStateProvider for NoopProvider<C, N> {
+    fn snap_tip(&self) -> ProviderResult<BlockNumber> {
+        Ok(0)
+    }
+
+    fn snap_state_roots(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, B256)>> {
+        Ok(Vec::default())
+    }
+
+    fn snap_state_overlay(&self, block_number: BlockNumber) -> ProviderResult<SnapStateOverlay> {
+        Ok(SnapStateOverlay { block_number, ..Default::default() })
+    }
+
+    fn snap_account_range(
+        &self,
+        _overlay: &SnapStateOverlay,
+        _start: B256,
+        _limit: B256,
+        _max_accounts: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        Ok(Vec::default())
+    }
+
+    fn snap_storage_range(
+        &self,
+        _overlay: &SnapStateOverlay,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: B256,
+        _max_slots: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(Vec::default())
+    }
+
+    fn snap_storage_roots(
+        &self,
+        _overlay: &SnapStateOverlay,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<Vec<B256>> {
+        Ok(hashed_addresses.iter().map(|_| EMPTY_ROOT_HASH).collect())
+    }
+
+    fn snap_multiproof(
+        &self,
+        _overlay: &SnapStateOverlay,
+        _targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        Ok(MultiProof::default())
+    }
+
+    fn snap_bytecode(&self, _code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(None)
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> HashedPostStateProvider for NoopProvider<C, N> {
     fn hashed_post_state(&self, _bundle_state: &revm_database::BundleState) -> HashedPostState {
         HashedPostState::default()
@@ -563,6 +634,95 @@ impl<C: Send + Sync + 'static, N: NodePrimitives> StateProviderFactory for NoopP
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> AddressAppearancesProvider for NoopProvider<C, N> {
+    fn address_appearances_before(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn address_appearances_after(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn block_address_appearances(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+        _include_logs: bool,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Address>)>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> LogIndexProvider for NoopProvider<C, N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_log_positions(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        Ok(Vec::new())
+    }
+
+    fn topic_log_positions(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>> {
+        Ok(Vec::new())
+    }
+
+    fn block_log_keys(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, BlockLogKeys)>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> TraceIndexProvider for NoopProvider<C, N> {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_trace_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn address_trace_blocks_before(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn address_trace_blocks_after(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StageCheckpointReader for NoopProvider<C, N> {
     fn get_stage_checkpoint(&self, _id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         Ok(None)
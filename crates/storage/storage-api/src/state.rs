@@ -2,15 +2,15 @@ use super::{
     AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, StateRootProvider,
     StorageRootProvider,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber, StorageKey, StorageValue, B256, U256};
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives_traits::Bytecode;
-use reth_storage_errors::provider::ProviderResult;
-use reth_trie_common::HashedPostState;
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie_common::{HashedPostState, HashedStorage};
 use revm_database::BundleState;
 
 /// This just receives state, or [`ExecutionOutcome`], from the provider
@@ -90,6 +90,86 @@ pub trait StateProvider:
         // Returns None if acc doesn't exist
         self.basic_account(addr)?.map_or_else(|| Ok(None), |acc| Ok(Some(acc.nonce)))
     }
+
+    /// Returns up to `limit` accounts ordered by hashed address, starting at the `start` hashed
+    /// address, from the state with the given [`HashedPostState`] applied on top.
+    ///
+    /// Up to `storage_limit` storage slots of every returned account are included, the remaining
+    /// slots can be paged through with [`Self::storage_range`]. If `with_addresses` is set, the
+    /// address preimages are resolved where they are known to the provider, which may walk the
+    /// whole plain account state.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] if the provider can't iterate the state.
+    fn account_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _start: B256,
+        _limit: usize,
+        _storage_limit: usize,
+        _with_addresses: bool,
+    ) -> ProviderResult<AccountRange> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    /// Returns up to `limit` storage slots of the account ordered by hashed slot, starting at the
+    /// `start` hashed slot, from the state with the given [`HashedStorage`] applied on top.
+    ///
+    /// The slot preimages are resolved where they are known to the provider.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] if the provider can't iterate the state.
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+/// A page of accounts returned by [`StateProvider::account_range`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountRange {
+    /// The accounts, ordered by hashed address.
+    pub accounts: Vec<RangeAccount>,
+    /// The hashed address of the first account after this page, if any.
+    pub next: Option<B256>,
+}
+
+/// An account returned by [`StateProvider::account_range`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeAccount {
+    /// The hashed address of the account.
+    pub hashed_address: B256,
+    /// The address of the account, if requested and its preimage is known.
+    pub address: Option<Address>,
+    /// The account info.
+    pub account: Account,
+    /// The storage root of the account.
+    pub storage_root: B256,
+    /// The storage of the account as hashed slot and value pairs, if requested.
+    pub storage: Vec<(B256, U256)>,
+}
+
+/// A page of storage slots returned by [`StateProvider::storage_range`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageRange {
+    /// The storage slots, ordered by hashed slot.
+    pub slots: Vec<RangeStorageSlot>,
+    /// The hashed slot of the first storage slot after this page, if any.
+    pub next: Option<B256>,
+}
+
+/// A storage slot returned by [`StateProvider::storage_range`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RangeStorageSlot {
+    /// The hashed slot.
+    pub hashed_key: B256,
+    /// The slot, if its preimage is known.
+    pub key: Option<B256>,
+    /// The slot value.
+    pub value: U256,
 }
 
 /// Minimal requirements to read a full account, for example, to validate its new transactions
@@ -0,0 +1,43 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for reading the trace index.
+///
+/// The index is optional and only populated by the `IndexTraces` stage, so callers should check
+/// [`TraceIndexProvider::trace_index_range`] before relying on an empty result.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait TraceIndexProvider: Send + Sync {
+    /// Returns the range of blocks covered by the trace index, or `None` if it's disabled.
+    ///
+    /// Blocks below the range can't be re-executed because their state history was pruned,
+    /// blocks above it are not indexed yet.
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the numbers of the blocks in the given range that contain a trace touching the
+    /// address, in ascending order.
+    fn address_trace_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns at most `limit` numbers of the blocks lower than `block` that contain a trace
+    /// touching the address, in descending order.
+    fn address_trace_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns at most `limit` numbers of the blocks higher than `block` that contain a trace
+    /// touching the address, in ascending order.
+    fn address_trace_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
@@ -1,10 +1,13 @@
 use alloc::vec::Vec;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
+use core::ops::RangeInclusive;
+use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::{
+    prefix_set::TriePrefixSetsMut,
     updates::{StorageTrieUpdates, TrieUpdates},
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
-    StorageProof, TrieInput,
+    AccountProof, HashedPostState, HashedPostStateSorted, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 
 /// A type that can compute the state root of a given post state.
@@ -89,6 +92,84 @@ pub trait StateProofProvider: Send + Sync {
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>>;
 }
 
+/// A type that can serve `snap` protocol requests from the persisted hashed state and tries.
+///
+/// The state is identified by the number of the block it was produced by. Only the state of
+/// persisted blocks can be served, see [`SnapStateProvider::snap_tip`].
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait SnapStateProvider: Send + Sync {
+    /// Returns the number of the most recently persisted block.
+    fn snap_tip(&self) -> ProviderResult<BlockNumber>;
+
+    /// Returns the state roots of the persisted blocks in the given range, ordered by block
+    /// number.
+    fn snap_state_roots(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, B256)>>;
+
+    /// Returns the state changes that revert the persisted hashed state to the state after the
+    /// given block.
+    ///
+    /// The overlay is only valid as long as the persisted tip doesn't change.
+    fn snap_state_overlay(&self, block_number: BlockNumber) -> ProviderResult<SnapStateOverlay>;
+
+    /// Returns at most `max_accounts` hashed accounts in the `start..=limit` range of the state
+    /// of the overlay, ordered by their hashed address.
+    fn snap_account_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        start: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>>;
+
+    /// Returns at most `max_slots` non-zero hashed storage slots of the account in the
+    /// `start..=limit` range of the state of the overlay, ordered by their hashed slot.
+    fn snap_storage_range(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_address: B256,
+        start: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>>;
+
+    /// Returns the storage roots of the given hashed accounts in the state of the overlay, in the
+    /// same order.
+    ///
+    /// Storage tries without changes in the overlay are not walked, their roots are read from the
+    /// stored trie nodes.
+    fn snap_storage_roots(
+        &self,
+        overlay: &SnapStateOverlay,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<Vec<B256>>;
+
+    /// Generate [`MultiProof`] for target hashed account and corresponding hashed storage slot
+    /// keys against the state of the overlay.
+    fn snap_multiproof(
+        &self,
+        overlay: &SnapStateOverlay,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof>;
+
+    /// Returns the bytecode with the given code hash.
+    fn snap_bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>>;
+}
+
+/// The state changes on top of the persisted hashed state that produce the state after a block,
+/// see [`SnapStateProvider::snap_state_overlay`].
+#[derive(Clone, Debug, Default)]
+pub struct SnapStateOverlay {
+    /// The number of the block whose state is produced.
+    pub block_number: BlockNumber,
+    /// The sorted hashed state changes.
+    pub state: HashedPostStateSorted,
+    /// The prefix sets of the changed trie keys.
+    pub prefix_sets: TriePrefixSetsMut,
+}
+
 /// Trie Writer
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait TrieWriter: Send + Sync {
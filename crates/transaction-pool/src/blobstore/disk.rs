@@ -7,18 +7,37 @@ use alloy_eips::{
 };
 use alloy_primitives::{TxHash, B256};
 use parking_lot::{Mutex, RwLock};
-use schnellru::{ByLength, LruMap};
-use std::{collections::HashSet, fmt, fs, io, path::PathBuf, sync::Arc};
+use schnellru::{ByLength, LruMap, Unlimited};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::SystemTime,
+};
 use tracing::{debug, trace};
 
 /// How many [`BlobTransactionSidecarVariant`] to cache in memory.
 pub const DEFAULT_MAX_CACHED_BLOBS: u32 = 100;
 
+/// Name of the directory inside the blob store that holds the versioned hash index.
+const INDEX_DIR: &str = "index";
+
 /// A blob store that stores blob data on disk.
 ///
 /// The type uses deferred deletion, meaning that blobs are not immediately deleted from disk, but
 /// it's expected that the maintenance task will call [`BlobStore::cleanup`] to remove the deleted
 /// blobs from disk.
+///
+/// Next to the blob files, the store keeps an index from versioned hash to transaction hash on
+/// disk, so that blobs can be looked up by versioned hash even after they were evicted from the in
+/// memory cache or the node was restarted.
+///
+/// If a disk quota is configured, it is a hard limit: blobs that are marked for deletion are
+/// evicted early to make room, and new blobs that still don't fit are rejected. Only the sidecar of
+/// a replacement transaction may reuse the space of the sidecar it replaces before that one is
+/// deleted.
 #[derive(Clone, Debug)]
 pub struct DiskFileBlobStore {
     inner: Arc<DiskFileBlobStoreInner>,
@@ -31,12 +50,20 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
-        let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
+        let DiskFileBlobStoreConfig { max_cached_entries, max_disk_size, open } = opts;
+        let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries, max_disk_size);
 
         // initialize the blob store
-        inner.delete_all()?;
-        inner.create_blob_dir()?;
+        match open {
+            OpenDiskFileBlobStore::Clear => {
+                inner.delete_all()?;
+                inner.create_blob_dir()?;
+            }
+            OpenDiskFileBlobStore::ReIndex => {
+                inner.create_blob_dir()?;
+                inner.reindex()?;
+            }
+        }
 
         Ok(Self { inner: Arc::new(inner) })
     }
@@ -54,7 +81,16 @@ impl DiskFileBlobStore {
 
 impl BlobStore for DiskFileBlobStore {
     fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
-        self.inner.insert_one(tx, data)
+        self.inner.insert_one(tx, data, None)
+    }
+
+    fn insert_replacement(
+        &self,
+        tx: B256,
+        data: BlobTransactionSidecarVariant,
+        replaced: B256,
+    ) -> Result<(), BlobStoreError> {
+        self.inner.insert_one(tx, data, Some(replaced))
     }
 
     fn insert_all(
@@ -70,6 +106,10 @@ impl BlobStore for DiskFileBlobStore {
     fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
         if self.inner.contains(tx)? {
             self.inner.txs_to_delete.write().insert(tx);
+            if self.inner.max_disk_size.is_some() {
+                let _quota = self.inner.quota_lock.lock();
+                self.inner.enforce_disk_quota(0);
+            }
         }
         Ok(())
     }
@@ -77,10 +117,22 @@ impl BlobStore for DiskFileBlobStore {
     fn delete_all(&self, txs: Vec<B256>) -> Result<(), BlobStoreError> {
         let txs = self.inner.retain_existing(txs)?;
         self.inner.txs_to_delete.write().extend(txs);
+        if self.inner.max_disk_size.is_some() {
+            let _quota = self.inner.quota_lock.lock();
+            self.inner.enforce_disk_quota(0);
+        }
         Ok(())
     }
 
     fn cleanup(&self) -> BlobStoreCleanupStat {
+        // blobs that were kept across a restart but weren't inserted again by the restored pool
+        // are orphaned
+        let orphaned = std::mem::take(&mut *self.inner.reindexed_blobs.lock());
+        if !orphaned.is_empty() {
+            debug!(target:"txpool::blob", num_blobs=%orphaned.len(), "Removing orphaned blobs");
+            self.inner.txs_to_delete.write().extend(orphaned);
+        }
+
         let txs_to_delete = std::mem::take(&mut *self.inner.txs_to_delete.write());
         let mut stat = BlobStoreCleanupStat::default();
         let mut subsize = 0;
@@ -92,6 +144,7 @@ impl BlobStore for DiskFileBlobStore {
                 Ok(_) => {
                     stat.delete_succeed += 1;
                     subsize += filesize;
+                    self.inner.remove_index_entries(tx);
                 }
                 Err(e) => {
                     stat.delete_failed += 1;
@@ -158,20 +211,14 @@ impl BlobStore for DiskFileBlobStore {
 
         // not all versioned hashes were be found, try to look up a matching tx
 
-        let mut missing_tx_hashes = Vec::new();
-
-        {
-            let mut versioned_to_txhashes = self.inner.versioned_hashes_to_txhash.lock();
-            for (idx, _) in
-                result.iter().enumerate().filter(|(_, blob_and_proof)| blob_and_proof.is_none())
-            {
+        let missing_tx_hashes = self.inner.tx_hashes_by_versioned_hashes(
+            result
+                .iter()
+                .enumerate()
+                .filter(|(_, blob_and_proof)| blob_and_proof.is_none())
                 // this is safe because the result vec has the same len
-                let versioned_hash = versioned_hashes[idx];
-                if let Some(tx_hash) = versioned_to_txhashes.get(&versioned_hash).copied() {
-                    missing_tx_hashes.push(tx_hash);
-                }
-            }
-        }
+                .map(|(idx, _)| versioned_hashes[idx]),
+        );
 
         // if we have missing blobs, try to read them from disk and try again
         if !missing_tx_hashes.is_empty() {
@@ -218,20 +265,14 @@ impl BlobStore for DiskFileBlobStore {
         }
 
         // not all versioned hashes were found, try to look up a matching tx
-        let mut missing_tx_hashes = Vec::new();
-
-        {
-            let mut versioned_to_txhashes = self.inner.versioned_hashes_to_txhash.lock();
-            for (idx, _) in
-                result.iter().enumerate().filter(|(_, blob_and_proof)| blob_and_proof.is_none())
-            {
+        let missing_tx_hashes = self.inner.tx_hashes_by_versioned_hashes(
+            result
+                .iter()
+                .enumerate()
+                .filter(|(_, blob_and_proof)| blob_and_proof.is_none())
                 // this is safe because the result vec has the same len
-                let versioned_hash = versioned_hashes[idx];
-                if let Some(tx_hash) = versioned_to_txhashes.get(&versioned_hash).copied() {
-                    missing_tx_hashes.push(tx_hash);
-                }
-            }
-        }
+                .map(|(idx, _)| versioned_hashes[idx]),
+        );
 
         // if we have missing blobs, try to read them from disk and try again
         if !missing_tx_hashes.is_empty() {
@@ -268,6 +309,7 @@ impl BlobStore for DiskFileBlobStore {
 
 struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
+    index_dir: PathBuf,
     blob_cache: Mutex<LruMap<TxHash, Arc<BlobTransactionSidecarVariant>, ByLength>>,
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
@@ -277,26 +319,130 @@ struct DiskFileBlobStoreInner {
     /// Note: It is possible that one blob can appear in multiple transactions but this only tracks
     /// the most recent one.
     versioned_hashes_to_txhash: Mutex<LruMap<B256, B256>>,
+    /// All blobs stored on disk in insertion order, with the versioned hashes they contain.
+    ///
+    /// This is used to prune the on-disk index when blobs are removed and to evict the oldest
+    /// blobs once the disk quota is exceeded.
+    stored_blobs: Mutex<LruMap<TxHash, Vec<B256>, Unlimited>>,
+    /// Blobs found on disk when the store was reindexed that weren't inserted again since.
+    ///
+    /// The pool inserts the blobs of the transactions it restores on startup, the remaining blobs
+    /// are marked for deletion on the next [`BlobStore::cleanup`].
+    reindexed_blobs: Mutex<HashSet<TxHash>>,
+    /// The maximum total size of all blob files on disk, if any.
+    ///
+    /// New blobs are rejected if they don't fit after evicting the blobs marked for deletion.
+    max_disk_size: Option<usize>,
+    /// Serializes checking the disk quota with writing or evicting blobs, so concurrent inserts
+    /// can't claim the same free space.
+    quota_lock: Mutex<()>,
 }
 
 impl DiskFileBlobStoreInner {
-    /// Creates a new empty disk file blob store with the given maximum length of the blob cache.
-    fn new(blob_dir: PathBuf, max_length: u32) -> Self {
+    /// Creates a new empty disk file blob store with the given maximum length of the blob cache and
+    /// the optional disk quota.
+    fn new(blob_dir: PathBuf, max_length: u32, max_disk_size: Option<usize>) -> Self {
         Self {
+            index_dir: blob_dir.join(INDEX_DIR),
             blob_dir,
             blob_cache: Mutex::new(LruMap::new(ByLength::new(max_length))),
             size_tracker: Default::default(),
             file_lock: Default::default(),
             txs_to_delete: Default::default(),
             versioned_hashes_to_txhash: Mutex::new(LruMap::new(ByLength::new(max_length * 6))),
+            stored_blobs: Mutex::new(LruMap::new(Unlimited)),
+            reindexed_blobs: Default::default(),
+            max_disk_size,
+            quota_lock: Default::default(),
         }
     }
 
-    /// Creates the directory where blobs will be stored on disk.
+    /// Creates the directories where blobs and the versioned hash index will be stored on disk.
     fn create_blob_dir(&self) -> Result<(), DiskFileBlobStoreError> {
         debug!(target:"txpool::blob", blob_dir = ?self.blob_dir, "Creating blob store");
-        fs::create_dir_all(&self.blob_dir)
-            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))
+        fs::create_dir_all(&self.index_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.index_dir.clone(), e))
+    }
+
+    /// Checks the existing blob files and the versioned hash index on disk for consistency.
+    ///
+    /// Index entries that point to missing blob files are removed, blob files that can't be
+    /// decoded are deleted and blob files that are missing from the index are indexed again.
+    fn reindex(&self) -> Result<(), DiskFileBlobStoreError> {
+        let open_err = |e: io::Error| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e);
+
+        // collect all blob files, ordered by their modification time so that the oldest blobs are
+        // evicted first
+        let mut blob_files = Vec::new();
+        for entry in fs::read_dir(&self.blob_dir).map_err(open_err)? {
+            let entry = entry.map_err(open_err)?;
+            let Ok(meta) = entry.metadata() else { continue };
+            if !meta.is_file() {
+                continue
+            }
+            let Some(tx) = parse_hash_file_name(&entry.path()) else {
+                debug!(target:"txpool::blob", path = ?entry.path(), "Skipping unknown file");
+                continue
+            };
+            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            blob_files.push((modified, tx, meta.len() as usize));
+        }
+        blob_files.sort_unstable();
+        let existing = blob_files.iter().map(|(_, tx, _)| *tx).collect::<HashSet<_>>();
+
+        // prune all index entries that don't point to an existing blob file
+        let mut indexed: HashMap<TxHash, Vec<B256>> = HashMap::default();
+        let mut pruned = 0;
+        for entry in fs::read_dir(&self.index_dir).map_err(open_err)? {
+            let path = entry.map_err(open_err)?.path();
+            let tx = parse_hash_file_name(&path).and_then(|versioned_hash| {
+                let tx = self.read_index_entry(versioned_hash)?;
+                Some((versioned_hash, tx))
+            });
+            match tx {
+                Some((versioned_hash, tx)) if existing.contains(&tx) => {
+                    indexed.entry(tx).or_default().push(versioned_hash);
+                }
+                _ => {
+                    if let Err(err) = fs::remove_file(&path) {
+                        debug!(target:"txpool::blob", %err, ?path, "Failed to remove index entry");
+                    }
+                    pruned += 1;
+                }
+            }
+        }
+
+        let mut size = 0;
+        let mut num = 0;
+        let mut removed = 0;
+        for (_, tx, filesize) in blob_files {
+            let versioned_hashes = match indexed.remove(&tx) {
+                Some(versioned_hashes) => versioned_hashes,
+                None => match self.read_one(tx) {
+                    Ok(Some(data)) => {
+                        let versioned_hashes = data.versioned_hashes().collect::<Vec<_>>();
+                        self.write_index_entries(tx, &versioned_hashes)?;
+                        versioned_hashes
+                    }
+                    Ok(None) => continue,
+                    Err(err) => {
+                        debug!(target:"txpool::blob", %err, ?tx, "Removing invalid blob file");
+                        let _ = fs::remove_file(self.blob_disk_file(tx));
+                        removed += 1;
+                        continue
+                    }
+                },
+            };
+            self.stored_blobs.lock().insert(tx, versioned_hashes);
+            self.reindexed_blobs.lock().insert(tx);
+            size += filesize;
+            num += 1;
+        }
+        self.size_tracker.add_size(size);
+        self.size_tracker.inc_len(num);
+
+        debug!(target:"txpool::blob", blobs=num, size, pruned, removed, "Reindexed blob store");
+        Ok(())
     }
 
     /// Deletes the entire blob store.
@@ -312,14 +458,30 @@ impl DiskFileBlobStoreInner {
     }
 
     /// Ensures blob is in the blob cache and written to the disk.
+    ///
+    /// The space of the `replaced` blob counts as free, it's evicted once it's deleted.
     fn insert_one(
         &self,
         tx: B256,
         data: BlobTransactionSidecarVariant,
+        replaced: Option<B256>,
     ) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.rlp_encoded_fields_length());
         data.rlp_encode_fields(&mut buf);
 
+        // held until the blob is accounted for in the size tracker
+        let _quota = self.max_disk_size.map(|_| self.quota_lock.lock());
+
+        // blobs that are already stored don't take up additional space
+        if self.stored_blobs.lock().peek(&tx).is_none() &&
+            !self.enforce_disk_quota(buf.len().saturating_sub(self.replaced_size(replaced)))
+        {
+            return Err(DiskFileBlobStoreError::QuotaExceeded(
+                self.max_disk_size.unwrap_or_default(),
+            )
+            .into())
+        }
+
         {
             // cache the versioned hashes to tx hash
             let mut map = self.versioned_hashes_to_txhash.lock();
@@ -328,12 +490,19 @@ impl DiskFileBlobStoreInner {
             });
         }
 
+        let versioned_hashes = data.versioned_hashes().collect::<Vec<_>>();
         self.blob_cache.lock().insert(tx, Arc::new(data));
 
         let size = self.write_one_encoded(tx, &buf)?;
-
-        self.size_tracker.add_size(size);
-        self.size_tracker.inc_len(1);
+        self.write_index_entries(tx, &versioned_hashes)?;
+        self.stored_blobs.lock().insert(tx, versioned_hashes);
+        self.reindexed_blobs.lock().remove(&tx);
+
+        // blob files that already exist are accounted for
+        if size > 0 {
+            self.size_tracker.add_size(size);
+            self.size_tracker.inc_len(1);
+        }
         Ok(())
     }
 
@@ -342,12 +511,30 @@ impl DiskFileBlobStoreInner {
         &self,
         txs: Vec<(B256, BlobTransactionSidecarVariant)>,
     ) -> Result<(), BlobStoreError> {
+        if let Some(max_disk_size) = self.max_disk_size {
+            // every blob is checked against the disk quota, blobs that don't fit are skipped and
+            // reported to the caller
+            let mut rejected = Vec::new();
+            for (tx, data) in txs {
+                if let Err(err) = self.insert_one(tx, data, None) {
+                    debug!(target:"txpool::blob", %err, ?tx, "Failed to insert blob");
+                    rejected.push(tx);
+                }
+            }
+            if !rejected.is_empty() {
+                return Err(
+                    DiskFileBlobStoreError::QuotaExceededBatch(rejected, max_disk_size).into()
+                )
+            }
+            return Ok(())
+        }
+
         let raw = txs
             .iter()
             .map(|(tx, data)| {
                 let mut buf = Vec::with_capacity(data.rlp_encoded_fields_length());
                 data.rlp_encode_fields(&mut buf);
-                (self.blob_disk_file(*tx), buf)
+                (*tx, self.blob_disk_file(*tx), buf, data.versioned_hashes().collect::<Vec<_>>())
             })
             .collect::<Vec<_>>();
 
@@ -371,25 +558,199 @@ impl DiskFileBlobStoreInner {
 
         let mut add = 0;
         let mut num = 0;
+        let mut written = Vec::with_capacity(raw.len());
         {
             let _lock = self.file_lock.write();
-            for (path, data) in raw {
+            for (tx, path, data, versioned_hashes) in raw {
                 if path.exists() {
                     debug!(target:"txpool::blob", ?path, "Blob already exists");
+                    written.push((tx, versioned_hashes));
                 } else if let Err(err) = fs::write(&path, &data) {
                     debug!(target:"txpool::blob", %err, ?path, "Failed to write blob file");
                 } else {
                     add += data.len();
                     num += 1;
+                    written.push((tx, versioned_hashes));
                 }
             }
         }
+
+        for (tx, versioned_hashes) in written {
+            if let Err(err) = self.write_index_entries(tx, &versioned_hashes) {
+                debug!(target:"txpool::blob", %err, "Failed to write index entries");
+            }
+            self.stored_blobs.lock().insert(tx, versioned_hashes);
+            self.reindexed_blobs.lock().remove(&tx);
+        }
+
         self.size_tracker.add_size(add);
         self.size_tracker.inc_len(num);
 
         Ok(())
     }
 
+    /// Returns the hashes of the transactions that contain the given versioned hashes.
+    ///
+    /// Versioned hashes that aren't tracked in memory are looked up in the index on disk.
+    fn tx_hashes_by_versioned_hashes(
+        &self,
+        versioned_hashes: impl IntoIterator<Item = B256>,
+    ) -> Vec<TxHash> {
+        let mut tx_hashes = Vec::new();
+        let mut not_cached = Vec::new();
+        {
+            let mut map = self.versioned_hashes_to_txhash.lock();
+            for versioned_hash in versioned_hashes {
+                match map.get(&versioned_hash).copied() {
+                    Some(tx) => tx_hashes.push(tx),
+                    None => not_cached.push(versioned_hash),
+                }
+            }
+        }
+
+        for versioned_hash in not_cached {
+            if let Some(tx) = self.read_index_entry(versioned_hash) {
+                self.versioned_hashes_to_txhash.lock().insert(versioned_hash, tx);
+                tx_hashes.push(tx);
+            }
+        }
+
+        // multiple versioned hashes can belong to the same transaction
+        tx_hashes.sort_unstable();
+        tx_hashes.dedup();
+        tx_hashes
+    }
+
+    /// Returns the path to the index entry for the given versioned hash.
+    #[inline]
+    fn index_disk_file(&self, versioned_hash: B256) -> PathBuf {
+        self.index_dir.join(format!("{versioned_hash:x}"))
+    }
+
+    /// Reads the transaction hash for the given versioned hash from the index on disk.
+    fn read_index_entry(&self, versioned_hash: B256) -> Option<TxHash> {
+        let data = {
+            let _lock = self.file_lock.read();
+            fs::read(self.index_disk_file(versioned_hash)).ok()?
+        };
+        (data.len() == TxHash::len_bytes()).then(|| TxHash::from_slice(&data))
+    }
+
+    /// Points the index entries of all given versioned hashes to the transaction hash.
+    fn write_index_entries(
+        &self,
+        tx: TxHash,
+        versioned_hashes: &[B256],
+    ) -> Result<(), DiskFileBlobStoreError> {
+        let _lock = self.file_lock.write();
+        for versioned_hash in versioned_hashes {
+            let path = self.index_disk_file(*versioned_hash);
+            fs::write(&path, tx).map_err(|e| DiskFileBlobStoreError::WriteIndex(tx, path, e))?;
+        }
+        Ok(())
+    }
+
+    /// Removes the index entries of the given transaction.
+    fn remove_index_entries(&self, tx: TxHash) {
+        let versioned_hashes = self.stored_blobs.lock().remove(&tx);
+        if let Some(versioned_hashes) = versioned_hashes {
+            self.prune_index_entries(tx, versioned_hashes);
+        }
+    }
+
+    /// Removes the index entries of the given versioned hashes that point to the transaction.
+    ///
+    /// Entries that were overwritten by a more recent transaction with the same blob are kept.
+    fn prune_index_entries(&self, tx: TxHash, versioned_hashes: Vec<B256>) {
+        for versioned_hash in versioned_hashes {
+            if self.read_index_entry(versioned_hash) != Some(tx) {
+                continue
+            }
+            let path = self.index_disk_file(versioned_hash);
+            let _lock = self.file_lock.write();
+            if let Err(err) = fs::remove_file(&path) {
+                debug!(target:"txpool::blob", %err, ?path, "Failed to remove index entry");
+            }
+        }
+    }
+
+    /// Evicts the oldest blobs that are marked for deletion from disk until `reserved` additional
+    /// bytes fit into the disk quota.
+    ///
+    /// Only blobs of transactions that were removed from the pool are evicted, ahead of the next
+    /// [`BlobStore::cleanup`]. Blobs of transactions that are still in the pool are kept.
+    ///
+    /// Returns `false` if the reserved bytes still don't fit.
+    ///
+    /// The caller must hold the `quota_lock`.
+    fn enforce_disk_quota(&self, reserved: usize) -> bool {
+        let Some(max_disk_size) = self.max_disk_size else { return true };
+        if self.size_tracker.data_size() + reserved <= max_disk_size {
+            return true
+        }
+
+        let evictable = {
+            let txs_to_delete = self.txs_to_delete.read();
+            let stored = self.stored_blobs.lock();
+            // the most recently inserted blobs come first
+            let mut evictable = stored
+                .iter()
+                .map(|(tx, _)| *tx)
+                .filter(|tx| txs_to_delete.contains(tx))
+                .collect::<Vec<_>>();
+            evictable.reverse();
+            evictable
+        };
+
+        let mut subsize = 0;
+        let mut num = 0;
+        for tx in evictable {
+            if self.size_tracker.data_size().saturating_sub(subsize) + reserved <= max_disk_size {
+                break
+            }
+
+            let path = self.blob_disk_file(tx);
+            let filesize = fs::metadata(&path).map_or(0, |meta| meta.len() as usize);
+            let removed = {
+                let _lock = self.file_lock.write();
+                fs::remove_file(&path)
+            };
+
+            match removed {
+                Ok(_) => {
+                    self.remove_index_entries(tx);
+                    self.blob_cache.lock().remove(&tx);
+                    self.txs_to_delete.write().remove(&tx);
+                    self.reindexed_blobs.lock().remove(&tx);
+                    subsize += filesize;
+                    num += 1;
+                }
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?path, "Failed to evict blob file");
+                }
+            }
+        }
+
+        if num > 0 {
+            debug!(target:"txpool::blob", num, size=subsize, "Evicted blobs exceeding disk quota");
+            self.size_tracker.sub_size(subsize);
+            self.size_tracker.sub_len(num);
+        }
+
+        self.size_tracker.data_size() + reserved <= max_disk_size
+    }
+
+    /// Returns the size of the `replaced` blob on disk, if it's stored and not marked for deletion.
+    fn replaced_size(&self, replaced: Option<B256>) -> usize {
+        let Some(replaced) = replaced else { return 0 };
+        if self.stored_blobs.lock().peek(&replaced).is_none() ||
+            self.txs_to_delete.read().contains(&replaced)
+        {
+            return 0
+        }
+        fs::metadata(self.blob_disk_file(replaced)).map_or(0, |meta| meta.len() as usize)
+    }
+
     /// Returns true if the blob for the given transaction hash is in the blob cache or on disk.
     fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
         if self.blob_cache.lock().get(&tx).is_some() {
@@ -578,6 +939,7 @@ impl fmt::Debug for DiskFileBlobStoreInner {
             .field("blob_dir", &self.blob_dir)
             .field("cached_blobs", &self.blob_cache.try_lock().map(|lock| lock.len()))
             .field("txs_to_delete", &self.txs_to_delete.try_read())
+            .field("max_disk_size", &self.max_disk_size)
             .finish()
     }
 }
@@ -601,6 +963,22 @@ pub enum DiskFileBlobStoreError {
     #[error("[{0}] failed to delete blob file at {1}: {2}")]
     /// Indicates a failure while deleting a blob file.
     DeleteFile(TxHash, PathBuf, io::Error),
+    /// Failure while writing an entry of the versioned hash index.
+    #[error("[{0}] failed to write blob index entry at {1}: {2}")]
+    /// Indicates a failure while writing an entry of the versioned hash index.
+    WriteIndex(TxHash, PathBuf, io::Error),
+    /// Thrown when a blob doesn't fit into the disk quota of the blob store.
+    #[error("blob store disk quota of {0} bytes exceeded")]
+    QuotaExceeded(usize),
+    /// Thrown when blobs of a batch don't fit into the disk quota of the blob store, contains the
+    /// transaction hashes of the blobs that weren't inserted.
+    #[error("blob store disk quota of {1} bytes exceeded by {num} blobs", num = .0.len())]
+    QuotaExceededBatch(Vec<TxHash>, usize),
+}
+
+/// Parses the hash a blob file or index entry is named after.
+fn parse_hash_file_name(path: &Path) -> Option<B256> {
+    B256::from_str(path.file_name()?.to_str()?).ok()
 }
 
 impl From<DiskFileBlobStoreError> for BlobStoreError {
@@ -614,13 +992,23 @@ impl From<DiskFileBlobStoreError> for BlobStoreError {
 pub struct DiskFileBlobStoreConfig {
     /// The maximum number of blobs to keep in the in memory blob cache.
     pub max_cached_entries: u32,
+    /// The maximum total size in bytes of all blob files on disk.
+    ///
+    /// This is a hard limit: the oldest blobs of transactions that were removed from the pool are
+    /// evicted from disk to make room for new blobs, and new blobs that still don't fit are
+    /// rejected.
+    pub max_disk_size: Option<usize>,
     /// How to open the blob store.
     pub open: OpenDiskFileBlobStore,
 }
 
 impl Default for DiskFileBlobStoreConfig {
     fn default() -> Self {
-        Self { max_cached_entries: DEFAULT_MAX_CACHED_BLOBS, open: Default::default() }
+        Self {
+            max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            max_disk_size: None,
+            open: Default::default(),
+        }
     }
 }
 
@@ -630,6 +1018,18 @@ impl DiskFileBlobStoreConfig {
         self.max_cached_entries = max_cached_entries;
         self
     }
+
+    /// Set the maximum total size in bytes of all blob files on disk.
+    pub const fn with_max_disk_size(mut self, max_disk_size: Option<usize>) -> Self {
+        self.max_disk_size = max_disk_size;
+        self
+    }
+
+    /// Set how to open the blob store.
+    pub const fn with_open(mut self, open: OpenDiskFileBlobStore) -> Self {
+        self.open = open;
+        self
+    }
 }
 
 /// How to open a disk file blob store.
@@ -638,14 +1038,17 @@ pub enum OpenDiskFileBlobStore {
     /// Clear everything in the blob store.
     #[default]
     Clear,
-    /// Keep the existing blob store and index
+    /// Keep the existing blob store and check it against the versioned hash index
     ReIndex,
 }
 
 #[cfg(test)]
 mod tests {
     use alloy_consensus::BlobTransactionSidecar;
-    use alloy_eips::eip7594::BlobTransactionSidecarVariant;
+    use alloy_eips::{
+        eip4844::{Blob, Bytes48},
+        eip7594::BlobTransactionSidecarVariant,
+    };
 
     use super::*;
     use std::sync::atomic::Ordering;
@@ -671,6 +1074,21 @@ mod tests {
             .collect()
     }
 
+    fn rng_blobs_with_commitments(num: usize) -> Vec<(TxHash, BlobTransactionSidecarVariant)> {
+        let mut rng = rand::rng();
+        (0..num)
+            .map(|_| {
+                let tx = TxHash::random_with(&mut rng);
+                let blob = BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar {
+                    blobs: vec![Blob::default()],
+                    commitments: vec![Bytes48::random_with(&mut rng)],
+                    proofs: vec![Bytes48::default()],
+                });
+                (tx, blob)
+            })
+            .collect()
+    }
+
     #[test]
     fn disk_insert_all_get_all() {
         let (store, _dir) = tmp_store();
@@ -840,4 +1258,127 @@ mod tests {
         assert_eq!(stat.delete_succeed, 3);
         assert_eq!(stat.delete_failed, 0);
     }
+
+    #[test]
+    fn disk_reindex_versioned_hashes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DiskFileBlobStoreConfig::default().with_open(OpenDiskFileBlobStore::ReIndex);
+        let store = DiskFileBlobStore::open(dir.path(), config.clone()).unwrap();
+
+        let blobs = rng_blobs_with_commitments(3);
+        let versioned_hashes =
+            blobs.iter().flat_map(|(_, blob)| blob.versioned_hashes()).collect::<Vec<_>>();
+        store.insert_all(blobs.clone()).unwrap();
+
+        // delete one blob, its index entry must be removed as well
+        store.delete(blobs[0].0).unwrap();
+        store.cleanup();
+        drop(store);
+
+        // add a blob file without an index entry
+        let (tx, blob) = rng_blobs_with_commitments(1).pop().unwrap();
+        let mut buf = Vec::new();
+        blob.rlp_encode_fields(&mut buf);
+        fs::write(dir.path().join(format!("{tx:x}")), buf).unwrap();
+
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+        assert_eq!(store.blobs_len(), 3);
+        assert!(!store.is_cached(&blobs[1].0));
+
+        let found = store.get_by_versioned_hashes_v1(&versioned_hashes).unwrap();
+        assert!(found[0].is_none());
+        assert!(found[1..].iter().all(Option::is_some));
+
+        let found =
+            store.get_by_versioned_hashes_v1(&blob.versioned_hashes().collect::<Vec<_>>()).unwrap();
+        assert!(found[0].is_some());
+    }
+
+    #[test]
+    fn disk_reindex_deletes_orphaned_blobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DiskFileBlobStoreConfig::default().with_open(OpenDiskFileBlobStore::ReIndex);
+        let store = DiskFileBlobStore::open(dir.path(), config.clone()).unwrap();
+        let blobs = rng_blobs_with_commitments(2);
+        store.insert_all(blobs.clone()).unwrap();
+        drop(store);
+
+        // only the first blob is inserted again by the restored pool
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+
+        let stat = store.cleanup();
+        assert_eq!(stat.delete_succeed, 1);
+        assert_eq!(store.blobs_len(), 1);
+        assert!(store.contains(blobs[0].0).unwrap());
+        assert!(!store.contains(blobs[1].0).unwrap());
+    }
+
+    #[test]
+    fn disk_max_disk_size_evicts_deleted_blobs() {
+        let (store, dir) = tmp_store();
+        let blobs = rng_blobs_with_commitments(3);
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+        let blob_size = store.data_size_hint().unwrap();
+        drop(store);
+
+        let config = DiskFileBlobStoreConfig::default().with_max_disk_size(Some(blob_size * 2));
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+        store.insert(blobs[1].0, blobs[1].1.clone()).unwrap();
+
+        // blobs of transactions that are still in the pool are kept, so new blobs are rejected
+        assert!(store.insert(blobs[2].0, blobs[2].1.clone()).is_err());
+        let err = store.insert_all(vec![blobs[2].clone()]).unwrap_err();
+        assert!(matches!(
+            err,
+            BlobStoreError::Other(err) if matches!(
+                err.downcast_ref::<DiskFileBlobStoreError>(),
+                Some(DiskFileBlobStoreError::QuotaExceededBatch(rejected, _)) if *rejected == vec![blobs[2].0]
+            )
+        ));
+        assert_eq!(store.blobs_len(), 2);
+        assert!(!store.contains(blobs[2].0).unwrap());
+
+        // the oldest deleted blob is evicted to make room for the new blob
+        store.delete(blobs[0].0).unwrap();
+        store.insert(blobs[2].0, blobs[2].1.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 2);
+        assert_eq!(store.data_size_hint(), Some(blob_size * 2));
+        assert!(!store.contains(blobs[0].0).unwrap());
+        assert!(store.contains(blobs[1].0).unwrap());
+        assert!(store.contains(blobs[2].0).unwrap());
+
+        let evicted = blobs[0].1.versioned_hashes().collect::<Vec<_>>();
+        assert!(store.inner.read_index_entry(evicted[0]).is_none());
+    }
+
+    #[test]
+    fn disk_max_disk_size_replacement_at_quota() {
+        let (store, dir) = tmp_store();
+        let blobs = rng_blobs_with_commitments(3);
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+        let blob_size = store.data_size_hint().unwrap();
+        drop(store);
+
+        let config = DiskFileBlobStoreConfig::default().with_max_disk_size(Some(blob_size * 2));
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+        store.insert_all(vec![blobs[0].clone(), blobs[1].clone()]).unwrap();
+
+        // blobs that are already stored fit into a full store
+        store.insert(blobs[1].0, blobs[1].1.clone()).unwrap();
+        assert_eq!(store.data_size_hint(), Some(blob_size * 2));
+
+        // the blob of a replaced transaction is kept until the replacement is committed
+        store.insert_replacement(blobs[2].0, blobs[2].1.clone(), blobs[1].0).unwrap();
+        assert!(store.contains(blobs[1].0).unwrap());
+        assert!(store.contains(blobs[2].0).unwrap());
+
+        // and evicted once it's deleted
+        store.delete(blobs[1].0).unwrap();
+        assert_eq!(store.data_size_hint(), Some(blob_size * 2));
+        assert!(store.contains(blobs[0].0).unwrap());
+        assert!(!store.contains(blobs[1].0).unwrap());
+        assert!(store.contains(blobs[2].0).unwrap());
+    }
 }
@@ -32,6 +32,21 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// Inserts the blob sidecar into the store
     fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError>;
 
+    /// Inserts the blob sidecar of a transaction that replaces another blob transaction into the
+    /// store.
+    ///
+    /// Stores with a size limit can reuse the space of the replaced sidecar for the new sidecar.
+    /// The replaced sidecar is kept until it is deleted, so it remains available if the
+    /// replacement is rejected.
+    fn insert_replacement(
+        &self,
+        tx: B256,
+        data: BlobTransactionSidecarVariant,
+        _replaced: B256,
+    ) -> Result<(), BlobStoreError> {
+        self.insert(tx, data)
+    }
+
     /// Inserts multiple blob sidecars into the store
     fn insert_all(
         &self,
@@ -0,0 +1,337 @@
+//! An in-process pool of transaction bundles.
+//!
+//! Bundles are ordered lists of transactions that are submitted to the local block builder, e.g.
+//! via `eth_sendBundle`, and must be included atomically and in order. Unlike pool transactions,
+//! bundles target a range of blocks and are dropped once that range has passed.
+
+use alloy_primitives::{keccak256, Address, BlockNumber, B256};
+use parking_lot::RwLock;
+use reth_primitives_traits::{Recovered, SignedTransaction};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use tracing::trace;
+
+/// The default maximum number of bundles kept in the [`BundlePool`].
+pub const DEFAULT_MAX_BUNDLES: usize = 1024;
+
+/// A transaction of a [`Bundle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleTransaction<T> {
+    /// The signed transaction.
+    pub tx: Recovered<T>,
+    /// Whether the transaction is allowed to revert without invalidating the bundle.
+    pub can_revert: bool,
+    /// Whether the transaction can be left out of the bundle if it is invalid, e.g. because it
+    /// has already been included.
+    pub can_drop: bool,
+}
+
+/// A share of the value of a [`Bundle`] that is refunded to a recipient.
+///
+/// The value of a bundle is what it pays to the fee recipient of the block. Refunds are paid by the
+/// block builder with a transfer that is included right after the bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleRefund {
+    /// The address that receives the refund.
+    pub recipient: Address,
+    /// The percent of the value of the bundle that is refunded.
+    pub percent: u64,
+}
+
+/// A bundle of transactions that must be included atomically and in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle<T> {
+    /// The transactions of the bundle.
+    pub transactions: Vec<BundleTransaction<T>>,
+    /// The first block the bundle can be included in.
+    pub block_number: BlockNumber,
+    /// The last block the bundle can be included in.
+    pub max_block_number: BlockNumber,
+    /// The minimum timestamp of the block the bundle can be included in.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp of the block the bundle can be included in.
+    pub max_timestamp: Option<u64>,
+    /// The UUID that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+    /// The refunds paid out of the value of the bundle.
+    pub refunds: Vec<BundleRefund>,
+}
+
+impl<T: SignedTransaction> Bundle<T> {
+    /// Returns the hash of the bundle, which is the keccak256 hash of the concatenated hashes of
+    /// its transactions.
+    pub fn hash(&self) -> B256 {
+        keccak256(self.transactions.iter().flat_map(|tx| tx.tx.tx_hash().0).collect::<Vec<_>>())
+    }
+}
+
+impl<T> Bundle<T> {
+    /// Returns `true` if the bundle can be included in a block with the given number and
+    /// timestamp.
+    pub fn is_valid_for(&self, block_number: BlockNumber, timestamp: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.is_none_or(|min| timestamp >= min) &&
+            self.max_timestamp.is_none_or(|max| timestamp <= max)
+    }
+}
+
+/// Errors returned when adding a bundle to the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// Thrown when the bundle has no transactions.
+    #[error("bundle has no transactions")]
+    EmptyBundle,
+    /// Thrown when the block range of the bundle is invalid.
+    #[error("invalid bundle block range {0}..={1}")]
+    InvalidBlockRange(BlockNumber, BlockNumber),
+    /// Thrown when the refunds of the bundle exceed its value.
+    #[error("bundle refunds of {0}% exceed the bundle value")]
+    InvalidRefundPercent(u64),
+    /// Thrown when the pool is full.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
+
+/// A shared pool of bundles, keyed by the blocks they target.
+///
+/// Bundles with a replacement UUID replace the previous bundle submitted with the same UUID and
+/// can be cancelled with it. Bundles are removed once the last block they target has passed.
+#[derive(Debug, Clone)]
+pub struct BundlePool<T>(Arc<RwLock<BundlePoolInner<T>>>);
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    /// The maximum number of bundles in the pool.
+    max_bundles: usize,
+    /// All bundles, keyed by the first block they target, their hash and replacement UUID.
+    bundles: BTreeMap<BundleKey, Bundle<T>>,
+    /// The key of the bundle for every replacement UUID.
+    by_uuid: HashMap<String, BundleKey>,
+}
+
+/// The key of a bundle in the [`BundlePool`].
+///
+/// The replacement UUID is part of the key, so that the same transactions submitted with
+/// different UUIDs are kept as separate bundles that can be replaced and cancelled
+/// independently.
+type BundleKey = (BlockNumber, B256, Option<String>);
+
+impl<T> PartialEq for BundlePool<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> Eq for BundlePool<T> {}
+
+impl<T> Default for BundlePool<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+// === impl BundlePool ===
+
+impl<T> BundlePool<T> {
+    /// Creates a new pool that holds up to `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self(Arc::new(RwLock::new(BundlePoolInner {
+            max_bundles,
+            bundles: Default::default(),
+            by_uuid: Default::default(),
+        })))
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.0.read().bundles.len()
+    }
+
+    /// Returns `true` if there are no bundles in the pool.
+    pub fn is_empty(&self) -> bool {
+        self.0.read().bundles.is_empty()
+    }
+
+    /// Removes the bundle with the given replacement UUID.
+    ///
+    /// Returns `true` if a bundle was removed.
+    pub fn cancel(&self, replacement_uuid: &str) -> bool {
+        let mut inner = self.0.write();
+        let Some(key) = inner.by_uuid.remove(replacement_uuid) else { return false };
+        trace!(target: "txpool::bundles", hash = %key.1, replacement_uuid, "cancelled bundle");
+        inner.bundles.remove(&key).is_some()
+    }
+
+    /// Removes all bundles that can't be included in the block with the given number or any
+    /// later block.
+    pub fn remove_stale(&self, block_number: BlockNumber) {
+        let inner = &mut *self.0.write();
+        inner.bundles.retain(|_, bundle| bundle.max_block_number >= block_number);
+        inner.by_uuid.retain(|_, key| inner.bundles.contains_key(key));
+    }
+}
+
+impl<T: SignedTransaction> BundlePool<T> {
+    /// Adds a bundle to the pool, replacing the bundle with the same replacement UUID.
+    ///
+    /// Returns the hash of the bundle.
+    pub fn add_bundle(&self, bundle: Bundle<T>) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if bundle.block_number == 0 || bundle.max_block_number < bundle.block_number {
+            return Err(BundlePoolError::InvalidBlockRange(
+                bundle.block_number,
+                bundle.max_block_number,
+            ))
+        }
+        let refund_percent =
+            bundle.refunds.iter().fold(0u64, |total, refund| total.saturating_add(refund.percent));
+        if refund_percent > 100 {
+            return Err(BundlePoolError::InvalidRefundPercent(refund_percent))
+        }
+
+        let hash = bundle.hash();
+        let key = (bundle.block_number, hash, bundle.replacement_uuid.clone());
+        let mut inner = self.0.write();
+        let replaced = bundle
+            .replacement_uuid
+            .as_ref()
+            .and_then(|uuid| inner.by_uuid.remove(uuid))
+            .and_then(|replaced| inner.bundles.remove(&replaced));
+        if replaced.is_none() &&
+            !inner.bundles.contains_key(&key) &&
+            inner.bundles.len() >= inner.max_bundles
+        {
+            return Err(BundlePoolError::PoolFull)
+        }
+
+        trace!(target: "txpool::bundles", %hash, block_number = bundle.block_number, replaced = replaced.is_some(), "added bundle");
+        if let Some(uuid) = bundle.replacement_uuid.clone() {
+            inner.by_uuid.insert(uuid, key.clone());
+        }
+        inner.bundles.insert(key, bundle);
+
+        Ok(hash)
+    }
+
+    /// Returns all bundles that can be included in the block with the given number and
+    /// timestamp, removing bundles that can no longer be included.
+    pub fn bundles_for(&self, block_number: BlockNumber, timestamp: u64) -> Vec<Bundle<T>> {
+        self.remove_stale(block_number);
+        self.0
+            .read()
+            .bundles
+            .range(..(block_number + 1, B256::ZERO, None))
+            .map(|(_, bundle)| bundle)
+            .filter(|bundle| bundle.is_valid_for(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Address, Signature};
+    use reth_ethereum_primitives::{Transaction, TransactionSigned};
+
+    fn bundle(
+        nonce: u64,
+        block_number: BlockNumber,
+        uuid: Option<&str>,
+    ) -> Bundle<TransactionSigned> {
+        let tx = TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+            Signature::test_signature(),
+        );
+        Bundle {
+            transactions: vec![BundleTransaction {
+                tx: Recovered::new_unchecked(tx, Address::ZERO),
+                can_revert: false,
+                can_drop: false,
+            }],
+            block_number,
+            max_block_number: block_number + 1,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: uuid.map(String::from),
+            refunds: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn replaces_and_cancels_bundles() {
+        let pool = BundlePool::default();
+
+        let first = pool.add_bundle(bundle(0, 10, Some("a"))).unwrap();
+        let other = pool.add_bundle(bundle(1, 10, None)).unwrap();
+        assert_ne!(first, other);
+        assert_eq!(pool.len(), 2);
+
+        // a bundle with the same uuid replaces the previous one
+        let replacement = pool.add_bundle(bundle(2, 11, Some("a"))).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.bundles_for(10, 0).len(), 1);
+        let bundles = pool.bundles_for(11, 0);
+        assert_eq!(bundles.iter().map(Bundle::hash).collect::<Vec<_>>(), [other, replacement]);
+
+        assert!(pool.cancel("a"));
+        assert!(!pool.cancel("a"));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn keeps_bundles_with_different_uuids() {
+        let pool = BundlePool::default();
+
+        let hash = pool.add_bundle(bundle(0, 10, Some("a"))).unwrap();
+        assert_eq!(pool.add_bundle(bundle(0, 10, Some("b"))), Ok(hash));
+        assert_eq!(pool.add_bundle(bundle(0, 10, None)), Ok(hash));
+        assert_eq!(pool.len(), 3);
+
+        // cancelling one bundle keeps the others with the same transactions
+        assert!(pool.cancel("a"));
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.bundles_for(10, 0).len(), 2);
+
+        // replacing one bundle keeps the others as well
+        pool.add_bundle(bundle(1, 10, Some("b"))).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert!(pool.cancel("b"));
+        assert_eq!(pool.bundles_for(10, 0).iter().map(Bundle::hash).collect::<Vec<_>>(), [hash]);
+    }
+
+    #[test]
+    fn removes_stale_bundles() {
+        let pool = BundlePool::new(2);
+        pool.add_bundle(bundle(0, 10, None)).unwrap();
+        pool.add_bundle(bundle(1, 20, None)).unwrap();
+        assert_eq!(pool.add_bundle(bundle(2, 20, None)), Err(BundlePoolError::PoolFull));
+
+        assert_eq!(pool.bundles_for(12, 0).len(), 0);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.bundles_for(21, 0).len(), 1);
+        assert!(pool.add_bundle(bundle(2, 20, None)).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_bundles() {
+        let pool = BundlePool::default();
+        let mut invalid = bundle(0, 10, None);
+        invalid.max_block_number = 9;
+        assert_eq!(pool.add_bundle(invalid), Err(BundlePoolError::InvalidBlockRange(10, 9)));
+
+        let mut invalid = bundle(0, 10, None);
+        invalid.transactions.clear();
+        assert_eq!(pool.add_bundle(invalid), Err(BundlePoolError::EmptyBundle));
+
+        let mut invalid = bundle(0, 10, None);
+        let refund = BundleRefund { recipient: Address::ZERO, percent: 60 };
+        invalid.refunds = vec![refund, refund];
+        assert_eq!(pool.add_bundle(invalid), Err(BundlePoolError::InvalidRefundPercent(120)));
+    }
+}
@@ -0,0 +1,146 @@
+//! Additional support for pooled transactions with [`TransactionConditional`]
+
+use crate::{error::PoolTransactionError, PoolTransaction};
+use alloy_consensus::conditional::BlockConditionalAttributes;
+use alloy_primitives::{StorageKey, U256};
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use reth_storage_api::{errors::provider::ProviderError, StateProvider};
+use std::any::Any;
+
+/// Maximum execution cost of a [`TransactionConditional`] accepted over RPC.
+pub const MAX_CONDITIONAL_EXECUTION_COST: u64 = 5000;
+
+/// Maximum number of concurrent validations of known accounts against the state.
+pub const MAX_CONCURRENT_CONDITIONAL_VALIDATIONS: usize = 3;
+
+/// Helper trait for transactions that support attaching a [`TransactionConditional`].
+///
+/// The attached conditional is exposed via [`PoolTransaction::conditional`].
+pub trait MaybeConditionalTransaction: PoolTransaction {
+    /// Attach a [`TransactionConditional`].
+    fn set_conditional(&mut self, conditional: TransactionConditional);
+
+    /// Check if the conditional has exceeded the block attributes.
+    fn has_exceeded_block_attributes(&self, block_attr: &BlockConditionalAttributes) -> bool {
+        self.conditional().map(|tc| tc.has_exceeded_block_attributes(block_attr)).unwrap_or(false)
+    }
+
+    /// Helper that sets the conditional and returns the instance again
+    fn with_conditional(mut self, conditional: TransactionConditional) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_conditional(conditional);
+        self
+    }
+}
+
+/// Error for a transaction whose [`TransactionConditional`] isn't met by the block it would be
+/// included in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("transaction conditional not met at block {number} with timestamp {timestamp}")]
+pub struct ConditionalNotMetError {
+    /// The number of the block.
+    pub number: u64,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+}
+
+impl PoolTransactionError for ConditionalNotMetError {
+    fn is_bad_transaction(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Error returned if the known accounts of a [`TransactionConditional`] don't match the state.
+#[derive(Debug, thiserror::Error)]
+pub enum KnownAccountsError {
+    /// A storage slot of a known account doesn't match the expected value.
+    #[error("storage value mismatch")]
+    StorageValueMismatch,
+    /// The storage root of a known account doesn't match the expected root.
+    #[error("storage root mismatch")]
+    StorageRootMismatch,
+    /// Failed to read the state.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Checks the known accounts of the [`TransactionConditional`] against the given state.
+pub fn known_accounts_match<S: StateProvider + ?Sized>(
+    conditional: &TransactionConditional,
+    state: &S,
+) -> Result<(), KnownAccountsError> {
+    for (address, storage) in &conditional.known_accounts {
+        match storage {
+            AccountStorage::Slots(slots) => {
+                for (slot, expected_value) in slots {
+                    let current =
+                        state.storage(*address, StorageKey::from(*slot))?.unwrap_or_default();
+                    if current != U256::from_be_bytes(**expected_value) {
+                        return Err(KnownAccountsError::StorageValueMismatch)
+                    }
+                }
+            }
+            AccountStorage::RootHash(expected_root) => {
+                if *expected_root != state.storage_root(*address, Default::default())? {
+                    return Err(KnownAccountsError::StorageRootMismatch)
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::EMPTY_ROOT_HASH;
+    use alloy_primitives::{Address, B256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn test_known_accounts_match() {
+        let address = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(StorageKey::with_last_byte(1), U256::from(42))]),
+        );
+
+        let mut conditional = TransactionConditional::default();
+        conditional.known_accounts.insert(
+            address,
+            AccountStorage::Slots(
+                std::iter::once((U256::from(1), B256::with_last_byte(42))).collect(),
+            ),
+        );
+        assert!(known_accounts_match(&conditional, &provider).is_ok());
+
+        conditional.known_accounts.insert(
+            address,
+            AccountStorage::Slots(
+                std::iter::once((U256::from(1), B256::with_last_byte(43))).collect(),
+            ),
+        );
+        assert!(matches!(
+            known_accounts_match(&conditional, &provider),
+            Err(KnownAccountsError::StorageValueMismatch)
+        ));
+
+        conditional.known_accounts.insert(address, AccountStorage::RootHash(EMPTY_ROOT_HASH));
+        assert!(known_accounts_match(&conditional, &provider).is_ok());
+
+        conditional.known_accounts.insert(address, AccountStorage::RootHash(B256::ZERO));
+        assert!(matches!(
+            known_accounts_match(&conditional, &provider),
+            Err(KnownAccountsError::StorageRootMismatch)
+        ));
+    }
+}
@@ -273,6 +273,7 @@
 pub use crate::{
     batcher::{BatchTxProcessor, BatchTxRequest},
     blobstore::{BlobStore, BlobStoreError},
+    bundle::{Bundle, BundlePool},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
         DEFAULT_MAX_INFLIGHT_DELEGATED_SLOTS, DEFAULT_PRICE_BUMP,
@@ -318,6 +319,8 @@ pub mod validate;
 
 pub mod batcher;
 pub mod blobstore;
+pub mod bundle;
+pub mod conditional;
 mod config;
 pub mod identifier;
 mod ordering;
@@ -2,12 +2,15 @@
 
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    conditional::{known_accounts_match, KnownAccountsError},
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
     BlockInfo, PoolTransaction, PoolUpdateKind, TransactionOrigin,
 };
-use alloy_consensus::{transaction::TxHashRef, BlockHeader, Typed2718};
+use alloy_consensus::{
+    conditional::BlockConditionalAttributes, transaction::TxHashRef, BlockHeader, Typed2718,
+};
 use alloy_eips::{BlockNumberOrTag, Decodable2718, Encodable2718};
 use alloy_primitives::{Address, BlockHash, BlockNumber};
 use alloy_rlp::{Bytes, Encodable};
@@ -20,9 +23,11 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_execution_types::ChangedAccount;
 use reth_fs_util::FsPathError;
 use reth_primitives_traits::{
-    transaction::signed::SignedTransaction, NodePrimitives, SealedHeader,
+    transaction::signed::SignedTransaction, NodePrimitives, Recovered, SealedHeader,
+};
+use reth_storage_api::{
+    errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory, TransactionVariant,
 };
-use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -91,6 +96,38 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// Default interval at which the transaction pool snapshot is written.
+pub const DEFAULT_TXPOOL_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The version of the transaction pool snapshot format.
+///
+/// The snapshot file consists of this version byte, followed by an RLP list of entries. Every
+/// entry is the [`TransactionOrigin`] byte followed by the EIP-2718 encoding of the pooled
+/// transaction, which includes the sidecar of blob transactions.
+const TXPOOL_SNAPSHOT_VERSION: u8 = 1;
+
+/// Settings for the transaction pool snapshot task
+#[derive(Debug, Clone)]
+pub struct TransactionPoolSnapshotConfig {
+    /// Path to the snapshot file
+    pub snapshot_path: PathBuf,
+    /// Interval at which the snapshot is written
+    pub interval: Duration,
+}
+
+impl TransactionPoolSnapshotConfig {
+    /// Receive path to the snapshot file and return a config with the default interval
+    pub const fn new(snapshot_path: PathBuf) -> Self {
+        Self { snapshot_path, interval: DEFAULT_TXPOOL_SNAPSHOT_INTERVAL }
+    }
+
+    /// Sets the interval at which the snapshot is written
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<N, Client, P, St, Tasks>(
     client: Client,
@@ -372,30 +409,12 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
 
                 // update the pool then re-inject the pruned transactions
                 // find all transactions that were mined in the old chain but not in the new chain
-                let pruned_old_transactions = old_blocks
-                    .transactions_ecrecovered()
-                    .filter(|tx| !new_mined_transactions.contains(tx.tx_hash()))
-                    .filter_map(|tx| {
-                        if tx.is_eip4844() {
-                            // reorged blobs no longer include the blob, which is necessary for
-                            // validating the transaction. Even though the transaction could have
-                            // been validated previously, we still need the blob in order to
-                            // accurately set the transaction's
-                            // encoded-length which is propagated over the network.
-                            pool.get_blob(*tx.tx_hash())
-                                .ok()
-                                .flatten()
-                                .map(Arc::unwrap_or_clone)
-                                .and_then(|sidecar| {
-                                    <P as TransactionPool>::Transaction::try_from_eip4844(
-                                        tx, sidecar,
-                                    )
-                                })
-                        } else {
-                            <P as TransactionPool>::Transaction::try_from_consensus(tx).ok()
-                        }
-                    })
-                    .collect::<Vec<_>>();
+                let pruned_old_transactions = reverted_pool_transactions(
+                    &pool,
+                    old_blocks
+                        .transactions_ecrecovered()
+                        .filter(|tx| !new_mined_transactions.contains(tx.tx_hash())),
+                );
 
                 // update the pool first
                 let update = CanonicalStateUpdate {
@@ -421,6 +440,78 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
             }
+            CanonStateNotification::Revert { old } => {
+                let (old_blocks, old_state) = old.inner();
+
+                // check if the reverted blocks are on top of the pool's block
+                if old_blocks.tip().hash() != pool_info.last_seen_block_hash {
+                    maintained_state = MaintainedPoolState::Drifted;
+                }
+
+                // the parent of the reverted blocks is the new tip
+                let new_tip_hash = old_blocks.first().parent_hash();
+                let new_tip =
+                    match client.recovered_block(new_tip_hash.into(), TransactionVariant::NoHash) {
+                        Ok(Some(new_tip)) => new_tip,
+                        res => {
+                            debug!(
+                                target: "txpool",
+                                err = ?res.err(),
+                                "failed to load new tip of reverted blocks: {new_tip_hash:?}"
+                            );
+                            maintained_state = MaintainedPoolState::Drifted;
+                            continue
+                        }
+                    };
+
+                let chain_spec = client.chain_spec();
+
+                // fees for the next block: `new_tip+1`
+                let pending_block_base_fee = chain_spec
+                    .next_block_base_fee(new_tip.header(), new_tip.timestamp())
+                    .unwrap_or_default();
+                let pending_block_blob_fee = new_tip.header().maybe_next_block_blob_fee(
+                    chain_spec.blob_params_at_timestamp(new_tip.timestamp()),
+                );
+
+                // all accounts changed in the reverted blocks are loaded at the new tip
+                let changed_accounts = match load_accounts(
+                    client.clone(),
+                    new_tip_hash,
+                    old_state.accounts_iter().map(|(a, _)| a),
+                ) {
+                    Ok(LoadedAccounts { accounts, failed_to_load }) => {
+                        dirty_addresses.extend(failed_to_load);
+                        accounts
+                    }
+                    Err(err) => {
+                        let (addresses, err) = *err;
+                        debug!(
+                            target: "txpool",
+                            %err,
+                            "failed to load changed accounts at new tip: {new_tip_hash:?}"
+                        );
+                        dirty_addresses.extend(addresses);
+                        vec![]
+                    }
+                };
+
+                let update = CanonicalStateUpdate {
+                    new_tip: new_tip.sealed_block(),
+                    pending_block_base_fee,
+                    pending_block_blob_fee,
+                    changed_accounts,
+                    mined_transactions: Vec::new(),
+                    update_kind: PoolUpdateKind::Reorg,
+                };
+                pool.on_canonical_state_change(update);
+
+                // all transactions of the reverted blocks need to be re-injected
+                let reverted_transactions =
+                    reverted_pool_transactions(&pool, old_blocks.transactions_ecrecovered());
+                metrics.inc_reinserted_transactions(reverted_transactions.len());
+                let _ = pool.add_external_transactions(reverted_transactions).await;
+            }
             CanonStateNotification::Commit { new } => {
                 let (blocks, state) = new.inner();
                 let tip = blocks.tip();
@@ -499,6 +590,93 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     }
 }
 
+/// Returns a spawnable future for maintaining the conditional transactions in the transaction
+/// pool.
+pub fn maintain_transaction_pool_conditional_future<N, Client, P, St>(
+    client: Client,
+    pool: P,
+    events: St,
+) -> BoxFuture<'static, ()>
+where
+    N: NodePrimitives,
+    Client: StateProviderFactory + 'static,
+    P: TransactionPool + 'static,
+    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
+{
+    async move {
+        maintain_transaction_pool_conditional(client, pool, events).await;
+    }
+    .boxed()
+}
+
+/// Maintains the conditional transactions in the transaction pool by handling new blocks and
+/// reorgs.
+///
+/// On every canonical update, the conditions of the transactions are checked against the new tip:
+/// transactions are evicted once the tip reaches their maximum block number or timestamp, so the
+/// next block can't include them, or once the storage of one of their known accounts no longer
+/// matches the state.
+///
+/// This is a no-op for pools whose transactions don't carry a conditional, see
+/// [`PoolTransaction::conditional`].
+pub async fn maintain_transaction_pool_conditional<N, Client, P, St>(
+    client: Client,
+    pool: P,
+    mut events: St,
+) where
+    N: NodePrimitives,
+    Client: StateProviderFactory,
+    P: TransactionPool,
+    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
+{
+    let metrics = MaintainPoolMetrics::default();
+    while let Some(event) = events.next().await {
+        // conditional transactions are submitted as private transactions
+        let conditional_txs = pool
+            .get_private_transactions()
+            .into_iter()
+            .filter(|tx| tx.transaction.conditional().is_some())
+            .collect::<Vec<_>>();
+        if conditional_txs.is_empty() {
+            continue
+        }
+
+        // the conditions are checked against the next committed block
+        let Some(tip) = event.tip_checked() else { continue };
+        let block_attr =
+            BlockConditionalAttributes { number: tip.number(), timestamp: tip.timestamp() };
+        // the block attributes are still checked if the state isn't available
+        let state = client
+            .history_by_block_hash(tip.hash())
+            .inspect_err(|err| {
+                warn!(target: "txpool", %err, "Failed to load state for conditional transactions")
+            })
+            .ok();
+
+        let to_remove = conditional_txs
+            .iter()
+            .filter(|tx| {
+                let Some(conditional) = tx.transaction.conditional() else { return false };
+                conditional.has_exceeded_block_attributes(&block_attr) ||
+                    state.as_ref().is_some_and(|state| {
+                        matches!(
+                            known_accounts_match(conditional, &**state),
+                            Err(KnownAccountsError::StorageValueMismatch |
+                                KnownAccountsError::StorageRootMismatch)
+                        )
+                    })
+            })
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+
+        if !to_remove.is_empty() {
+            debug!(target: "txpool", num_txs = to_remove.len(), "Removing conditional transactions");
+            let removed = pool.remove_transactions(to_remove);
+            metrics.inc_removed_conditional_transactions(removed.len());
+        }
+    }
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -600,6 +778,35 @@ where
     Ok(res)
 }
 
+/// Converts the transactions of reverted blocks into pool transactions, so they can be re-injected.
+///
+/// Reverted blob transactions no longer include the blob, which is necessary for validating the
+/// transaction. Even though the transaction could have been validated previously, the blob is
+/// still needed to accurately set the transaction's encoded length, which is propagated over the
+/// network. So blob transactions are only converted if their sidecar is still in the blob store.
+fn reverted_pool_transactions<P>(
+    pool: &P,
+    transactions: impl IntoIterator<Item = Recovered<<P::Transaction as PoolTransaction>::Consensus>>,
+) -> Vec<P::Transaction>
+where
+    P: TransactionPool,
+{
+    transactions
+        .into_iter()
+        .filter_map(|tx| {
+            if tx.is_eip4844() {
+                pool.get_blob(*tx.tx_hash())
+                    .ok()
+                    .flatten()
+                    .map(Arc::unwrap_or_clone)
+                    .and_then(|sidecar| P::Transaction::try_from_eip4844(tx, sidecar))
+            } else {
+                P::Transaction::try_from_consensus(tx).ok()
+            }
+        })
+        .collect()
+}
+
 /// Loads transactions from a file, decodes them from the JSON or RLP format, and
 /// inserts them into the transaction pool on node boot up.
 /// The file is removed after the transactions have been successfully processed.
@@ -729,6 +936,9 @@ pub enum TransactionsBackupError {
     /// Error adding transactions to the transaction pool
     #[error("failed to insert transactions to the transactions pool. Encountered pool error: {0}")]
     Pool(#[from] PoolError),
+    /// Error during decoding of a snapshot with an unknown format
+    #[error("failed to apply transactions snapshot. Unsupported snapshot version: {0}")]
+    SnapshotVersion(u8),
 }
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
@@ -757,17 +967,162 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// Returns the byte the origin is encoded as in the transaction pool snapshot.
+const fn snapshot_origin_byte(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+/// Decodes the origin from its byte in the transaction pool snapshot.
+const fn snapshot_origin_from_byte(byte: u8) -> Option<TransactionOrigin> {
+    match byte {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Loads the transactions of a snapshot of the pool from a file and inserts them into the
+/// transaction pool on node boot up.
+///
+/// The transactions are validated against the current state, transactions that are no longer
+/// valid are discarded.
+async fn load_txpool_snapshot<P>(pool: &P, file_path: &Path) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Loading transaction pool snapshot");
+    let data = reth_fs_util::read(file_path)?;
+    let Some((&version, mut encoded)) = data.split_first() else { return Ok(()) };
+    if version != TXPOOL_SNAPSHOT_VERSION {
+        return Err(TransactionsBackupError::SnapshotVersion(version))
+    }
+
+    let entries: Vec<Bytes> = alloy_rlp::Decodable::decode(&mut encoded)?;
+    let pool_transactions = entries
+        .iter()
+        .filter_map(|entry| {
+            let (&origin, encoded_tx) = entry.split_first()?;
+            let origin = snapshot_origin_from_byte(origin)?;
+            let tx = <P::Transaction as PoolTransaction>::Pooled::decode_2718_exact(encoded_tx)
+                .ok()?
+                .try_into_recovered()
+                .ok()?;
+            Some((origin, <P::Transaction as PoolTransaction>::from_pooled(tx)))
+        })
+        .collect::<Vec<_>>();
+
+    let num_txs = pool_transactions.len();
+    let inserted = pool
+        .add_transactions_with_origins(pool_transactions)
+        .await
+        .into_iter()
+        .filter(|res| res.is_ok())
+        .count();
+
+    info!(target: "txpool", snapshot_file =?file_path, num_txs, inserted, "Reinserted transactions from transaction pool snapshot");
+    Ok(())
+}
+
+/// Writes a snapshot of all transactions in the pool, including blob sidecars, to a file.
+fn save_txpool_snapshot<P>(pool: &P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let entries = pool
+        .get_all(pool.all_transaction_hashes())
+        .into_iter()
+        .filter_map(|tx| {
+            let pooled = pool.get_pooled_transaction_element(*tx.hash())?;
+            let mut entry = Vec::with_capacity(1 + pooled.inner().encode_2718_len());
+            entry.push(snapshot_origin_byte(tx.origin));
+            pooled.inner().encode_2718(&mut entry);
+            Some(Bytes::from(entry))
+        })
+        .collect::<Vec<_>>();
+
+    let mut data = vec![TXPOOL_SNAPSHOT_VERSION];
+    entries.encode(&mut data);
+
+    trace!(target: "txpool", snapshot_file =?file_path, num_txs=%entries.len(), "Saving transaction pool snapshot");
+    let written = file_path.parent().map(reth_fs_util::create_dir_all).transpose().and_then(|_| {
+        reth_fs_util::atomic_write_file(file_path, |file| std::io::Write::write_all(file, &data))
+    });
+
+    match written {
+        Ok(()) => {
+            debug!(target: "txpool", snapshot_file =?file_path, num_txs=%entries.len(), "Wrote transaction pool snapshot to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file =?file_path, "Failed to write transaction pool snapshot to file");
+        }
+    }
+}
+
+/// Task which periodically writes a snapshot of all transactions in the pool to a file, and once
+/// more on shutdown.
+/// Reloads the transactions from the snapshot on boot up and inserts them into the pool after
+/// revalidating them against the current state.
+pub async fn snapshot_transactions_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: TransactionPoolSnapshotConfig,
+) where
+    P: TransactionPool + 'static,
+{
+    let TransactionPoolSnapshotConfig { snapshot_path, interval } = config;
+
+    if let Err(err) = load_txpool_snapshot(&pool, &snapshot_path).await {
+        error!(target: "txpool", "{}", err)
+    }
+
+    let mut snapshot_interval = time::interval_at(time::Instant::now() + interval, interval);
+    let mut shutdown = std::pin::pin!(shutdown);
+    let graceful_guard = loop {
+        tokio::select! {
+            guard = &mut shutdown => break guard,
+            _ = snapshot_interval.tick() => {
+                // the snapshot can be large, so it's written on a blocking thread
+                let pool = pool.clone();
+                let snapshot_path = snapshot_path.clone();
+                let _ = tokio::task::spawn_blocking(move || {
+                    save_txpool_snapshot(&pool, &snapshot_path)
+                })
+                .await;
+            }
+        }
+    };
+
+    // write the final snapshot to disk
+    save_txpool_snapshot(&pool, &snapshot_path);
+
+    drop(graceful_guard)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
+        blobstore::InMemoryBlobStore, conditional::MaybeConditionalTransaction,
+        validate::EthTransactionValidatorBuilder, CoinbaseTipOrdering, EthPooledTransaction, Pool,
+        TransactionOrigin,
     };
+    use alloy_consensus::{Header, Transaction};
     use alloy_eips::eip2718::Decodable2718;
     use alloy_primitives::{hex, U256};
-    use reth_ethereum_primitives::PooledTransactionVariant;
+    use alloy_rpc_types_eth::erc4337::TransactionConditional;
+    use reth_ethereum_primitives::{Block, EthPrimitives, PooledTransactionVariant};
+    use reth_execution_types::{Chain, ExecutionOutcome};
     use reth_fs_util as fs;
+    use reth_primitives_traits::RecoveredBlock;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
@@ -830,6 +1185,91 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_txpool_snapshot_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = temp_dir.path().join("txpool-snapshot.bin");
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        let new_pool = || {
+            let provider = MockEthProvider::default();
+            provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+            let blob_store = InMemoryBlobStore::default();
+            let validator = EthTransactionValidatorBuilder::new(provider).build(blob_store.clone());
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default())
+        };
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        save_txpool_snapshot(&txpool, &snapshot_path);
+
+        // the transactions are reinserted with their origin
+        let txpool = new_pool();
+        load_txpool_snapshot(&txpool, &snapshot_path).await.unwrap();
+        let reinserted = txpool.get(transaction.hash()).expect("transaction should be reinserted");
+        assert_eq!(reinserted.origin, TransactionOrigin::External);
+
+        // transactions that are no longer valid are discarded
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::Local, transaction.clone()).await.unwrap();
+        save_txpool_snapshot(&txpool, &snapshot_path);
+        let provider = MockEthProvider::default();
+        provider.add_account(sender, ExtendedAccount::new(transaction.nonce() + 1, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(provider)
+            .build::<EthPooledTransaction, _>(blob_store.clone());
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        load_txpool_snapshot(&txpool, &snapshot_path).await.unwrap();
+        assert!(txpool.is_empty());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_evict_conditional_transactions() {
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let transaction =
+            EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap()).with_conditional(
+                TransactionConditional { block_number_max: Some(2), ..Default::default() },
+            );
+        let provider = MockEthProvider::default();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator =
+            EthTransactionValidatorBuilder::new(provider.clone()).build(blob_store.clone());
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        txpool.add_transaction(TransactionOrigin::Private, transaction.clone()).await.unwrap();
+
+        let commit = |number| {
+            let block =
+                Block { header: Header { number, ..Default::default() }, body: Default::default() };
+            let chain = Chain::<EthPrimitives>::from_block(
+                RecoveredBlock::new_unhashed(block, vec![]),
+                ExecutionOutcome::default(),
+                None,
+            );
+            futures_util::stream::iter([CanonStateNotification::Commit { new: Arc::new(chain) }])
+        };
+
+        // the transaction can still be included in block 2
+        maintain_transaction_pool_conditional(provider.clone(), txpool.clone(), commit(1)).await;
+        assert!(txpool.contains(transaction.hash()));
+
+        // the transaction is evicted once the next block exceeds the max block number
+        maintain_transaction_pool_conditional(provider, txpool.clone(), commit(2)).await;
+        assert!(!txpool.contains(transaction.hash()));
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
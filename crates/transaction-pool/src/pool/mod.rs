@@ -66,7 +66,7 @@
 //!    category (2.) and become pending.
 
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{BlobStore, BlobStoreError},
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
@@ -508,7 +508,33 @@ where
                     authority_ids: authorities.map(|auths| self.get_sender_ids(auths)),
                 };
 
-                let added = pool.add_transaction(tx, balance, state_nonce, bytecode_hash)?;
+                // store the sidecar before the transaction is added to the pool, so that a
+                // transaction whose sidecar can't be stored, e.g. because the disk quota is
+                // reached, doesn't replace or discard any other transaction
+                let tx_hash = *tx.hash();
+                let stored_sidecar = maybe_sidecar.is_some() && !pool.contains(&tx_hash);
+                if let Some(sidecar) = maybe_sidecar.as_ref().filter(|_| stored_sidecar) {
+                    let replaced = pool
+                        .all()
+                        .get(&transaction_id)
+                        .map(|existing| &existing.transaction)
+                        .filter(|existing| existing.is_eip4844())
+                        .map(|existing| *existing.hash());
+                    if let Err(err) = self.insert_blob(tx_hash, sidecar.clone(), replaced) {
+                        self.event_listener.write().discarded(&tx_hash);
+                        return Err(PoolError::other(tx_hash, err))
+                    }
+                }
+
+                let added = match pool.add_transaction(tx, balance, state_nonce, bytecode_hash) {
+                    Ok(added) => added,
+                    Err(err) => {
+                        if stored_sidecar {
+                            self.delete_blob(tx_hash);
+                        }
+                        return Err(err)
+                    }
+                };
                 let hash = *added.hash();
                 let state = added.transaction_state();
 
@@ -516,8 +542,6 @@ where
                 if let Some(sidecar) = maybe_sidecar {
                     // notify blob sidecar listeners
                     self.on_new_blob_sidecar(&hash, &sidecar);
-                    // store the sidecar in the blob store
-                    self.insert_blob(hash, sidecar);
                 }
 
                 if let Some(replaced) = added.replaced_blob_transaction() {
@@ -1007,13 +1031,25 @@ where
     }
 
     /// Inserts a blob transaction into the blob store
-    fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecarVariant) {
+    ///
+    /// If the transaction replaces the blob transaction `replaced`, the store may reuse its space.
+    fn insert_blob(
+        &self,
+        hash: TxHash,
+        blob: BlobTransactionSidecarVariant,
+        replaced: Option<TxHash>,
+    ) -> Result<(), BlobStoreError> {
         debug!(target: "txpool", "[{:?}] storing blob sidecar", hash);
-        if let Err(err) = self.blob_store.insert(hash, blob) {
+        let res = match replaced {
+            Some(replaced) => self.blob_store.insert_replacement(hash, blob, replaced),
+            None => self.blob_store.insert(hash, blob),
+        };
+        if let Err(err) = &res {
             warn!(target: "txpool", %err, "[{:?}] failed to insert blob", hash);
             self.blob_store_metrics.blobstore_failed_inserts.increment(1);
         }
         self.update_blob_store_metrics();
+        res
     }
 
     /// Delete a blob from the blob store
@@ -1365,15 +1401,17 @@ impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
 
 #[cfg(test)]
 mod tests {
+    use super::PoolInner;
     use crate::{
-        blobstore::{BlobStore, InMemoryBlobStore},
+        blobstore::{BlobStore, DiskFileBlobStore, DiskFileBlobStoreConfig, InMemoryBlobStore},
         identifier::SenderId,
-        test_utils::{MockTransaction, TestPoolBuilder},
+        noop::MockTransactionValidator,
+        test_utils::{MockOrdering, MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
         BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
     };
     use alloy_eips::{eip4844::BlobTransactionSidecar, eip7594::BlobTransactionSidecarVariant};
-    use alloy_primitives::Address;
+    use alloy_primitives::{Address, B256};
     use std::{fs, path::PathBuf};
 
     #[test]
@@ -1461,6 +1499,84 @@ mod tests {
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
 
+    #[test]
+    fn test_blob_sidecar_stored_before_replacement() {
+        let json_content = fs::read_to_string(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/blob1.json"),
+        )
+        .expect("Failed to read the blob data file");
+        let json_value: serde_json::Value =
+            serde_json::from_str(&json_content).expect("Failed to deserialize JSON");
+        let blobs = vec![json_value.get("data").unwrap().as_str().unwrap().to_string()];
+        let sidecar = BlobTransactionSidecarVariant::Eip4844(
+            BlobTransactionSidecar::try_from_blobs_hex(blobs).unwrap(),
+        );
+
+        // measure the size of a single sidecar on disk
+        let dir = tempfile::tempdir().unwrap();
+        let blob_store =
+            DiskFileBlobStore::open(dir.path(), DiskFileBlobStoreConfig::default()).unwrap();
+        blob_store.insert(B256::random(), sidecar.clone()).unwrap();
+        let sidecar_size = blob_store.data_size_hint().unwrap();
+
+        // the disk quota fits exactly one sidecar
+        let dir = tempfile::tempdir().unwrap();
+        let config = DiskFileBlobStoreConfig::default().with_max_disk_size(Some(sidecar_size));
+        let blob_store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+        let pool = PoolInner::new(
+            MockTransactionValidator::default(),
+            MockOrdering::default(),
+            blob_store,
+            Default::default(),
+        );
+        pool.set_block_info(BlockInfo { pending_blob_fee: Some(10_000_000), ..Default::default() });
+
+        let valid = |tx: MockTransaction| TransactionValidationOutcome::Valid {
+            balance: U256::from(u64::MAX),
+            state_nonce: 0,
+            bytecode_hash: None,
+            transaction: ValidTransaction::ValidWithSidecar {
+                transaction: tx,
+                sidecar: sidecar.clone(),
+            },
+            propagate: true,
+            authorities: None,
+        };
+
+        let tx = MockTransaction::eip4844_with_sidecar(sidecar.clone());
+        let tx_hash = *tx.get_hash();
+        assert!(pool.add_transactions(TransactionOrigin::External, [valid(tx.clone())])[0].is_ok());
+
+        // the sidecar of another transaction doesn't fit, the transaction isn't added
+        let other = MockTransaction::eip4844_with_sidecar(sidecar.clone());
+        let other_hash = *other.get_hash();
+        assert!(pool.add_transactions(TransactionOrigin::External, [valid(other)])[0].is_err());
+        assert!(pool.get(&other_hash).is_none());
+        assert!(pool.get(&tx_hash).is_some());
+        assert!(pool.blob_store().contains(tx_hash).unwrap());
+
+        // an underpriced replacement is rejected and the existing sidecar is kept
+        let underpriced = tx.clone().rng_hash();
+        let underpriced_hash = *underpriced.get_hash();
+        assert!(
+            pool.add_transactions(TransactionOrigin::External, [valid(underpriced)])[0].is_err()
+        );
+        assert!(pool.get(&tx_hash).is_some());
+        assert!(pool.blob_store().contains(tx_hash).unwrap());
+        assert!(!pool.blob_store().contains(underpriced_hash).unwrap());
+
+        // the sidecar of a replacement reuses the space of the replaced sidecar
+        let replacement =
+            tx.clone().rng_hash().inc_price_by(tx.get_gas_price()).inc_blob_fee_by(10_000_000);
+        let replacement_hash = *replacement.get_hash();
+        assert!(pool.add_transactions(TransactionOrigin::External, [valid(replacement)])[0].is_ok());
+        assert!(pool.get(&tx_hash).is_none());
+        assert!(pool.get(&replacement_hash).is_some());
+        assert!(!pool.blob_store().contains(tx_hash).unwrap());
+        assert!(pool.blob_store().contains(replacement_hash).unwrap());
+        assert_eq!(pool.blob_store().data_size_hint(), Some(sidecar_size));
+    }
+
     #[test]
     fn test_auths_stored_in_identifiers() {
         // Create a test pool with default configuration.
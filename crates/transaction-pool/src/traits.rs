@@ -52,6 +52,7 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    conditional::MaybeConditionalTransaction,
     error::{InvalidPoolTransactionError, PoolError, PoolResult},
     pool::{
         state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
@@ -71,6 +72,7 @@ use alloy_eips::{
     eip7702::SignedAuthorization,
 };
 use alloy_primitives::{Address, Bytes, TxHash, TxKind, B256, U256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_ethereum_primitives::{PooledTransactionVariant, TransactionSigned};
@@ -1211,6 +1213,20 @@ pub trait PoolTransaction:
             Ok(())
         }
     }
+
+    /// Returns the [`TransactionConditional`] the transaction was submitted with, if any.
+    ///
+    /// By default, transactions don't carry a conditional.
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        None
+    }
+
+    /// Attaches a [`TransactionConditional`] to the transaction.
+    ///
+    /// Returns `false` if the transaction type doesn't support conditionals, which is the default.
+    fn try_set_conditional(&mut self, _conditional: TransactionConditional) -> bool {
+        false
+    }
 }
 
 /// Super trait for transactions that can be converted to and from Eth transactions intended for the
@@ -1256,6 +1272,7 @@ pub trait EthPoolTransaction: PoolTransaction {
 /// - `cost`: Pre-calculated max cost (gas * price + value + blob costs)
 /// - `encoded_length`: Cached RLP encoding length for size limits
 /// - `blob_sidecar`: Blob data state (None/Missing/Present)
+/// - `conditional`: Optional [`TransactionConditional`] the transaction was submitted with
 ///
 /// This avoids recalculating these values repeatedly during pool operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1275,6 +1292,9 @@ pub struct EthPooledTransaction<T = TransactionSigned> {
 
     /// The blob side car for this transaction
     pub blob_sidecar: EthBlobTransactionSidecar,
+
+    /// Optional conditional attached to this transaction.
+    pub conditional: Option<Box<TransactionConditional>>,
 }
 
 impl<T: SignedTransaction> EthPooledTransaction<T> {
@@ -1303,7 +1323,7 @@ impl<T: SignedTransaction> EthPooledTransaction<T> {
             blob_sidecar = EthBlobTransactionSidecar::Missing;
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, conditional: None }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1312,6 +1332,12 @@ impl<T: SignedTransaction> EthPooledTransaction<T> {
     }
 }
 
+impl MaybeConditionalTransaction for EthPooledTransaction {
+    fn set_conditional(&mut self, conditional: TransactionConditional) {
+        self.conditional = Some(Box::new(conditional))
+    }
+}
+
 impl PoolTransaction for EthPooledTransaction {
     type TryFromConsensusError = ValueError<TransactionSigned>;
 
@@ -1379,6 +1405,15 @@ impl PoolTransaction for EthPooledTransaction {
     fn encoded_length(&self) -> usize {
         self.encoded_length
     }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+
+    fn try_set_conditional(&mut self, conditional: TransactionConditional) -> bool {
+        self.set_conditional(conditional);
+        true
+    }
 }
 
 impl<T: Typed2718> Typed2718 for EthPooledTransaction<T> {
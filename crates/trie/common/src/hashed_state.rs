@@ -20,6 +20,7 @@ use reth_primitives_traits::Account;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
 use revm_database::{AccountStatus, BundleAccount};
+use revm_state::EvmState;
 
 /// Representation of in-memory hashed state.
 #[derive(PartialEq, Eq, Clone, Default, Debug)]
@@ -103,6 +104,41 @@ impl HashedPostState {
         Self { accounts, storages }
     }
 
+    /// Initialize [`HashedPostState`] from the state changes of a single transaction or system
+    /// call, as reported by a state hook during execution.
+    ///
+    /// Only touched accounts and changed storage slots are included. The storage of
+    /// self-destructed accounts is wiped.
+    pub fn from_evm_state<KH: KeyHasher>(state: EvmState) -> Self {
+        let mut hashed_state = Self::with_capacity(state.len());
+        for (address, account) in state {
+            if !account.is_touched() {
+                continue
+            }
+
+            let hashed_address = KH::hash_key(address);
+            let destroyed = account.is_selfdestructed();
+            let info = if destroyed { None } else { Some(account.info.into()) };
+            hashed_state.accounts.insert(hashed_address, info);
+
+            let mut changed_storage_iter = account
+                .storage
+                .into_iter()
+                .filter(|(_slot, value)| value.is_changed())
+                .map(|(slot, value)| (KH::hash_key(B256::from(slot)), value.present_value))
+                .peekable();
+
+            if destroyed {
+                hashed_state.storages.insert(hashed_address, HashedStorage::new(true));
+            } else if changed_storage_iter.peek().is_some() {
+                hashed_state
+                    .storages
+                    .insert(hashed_address, HashedStorage::from_iter(false, changed_storage_iter));
+            }
+        }
+        hashed_state
+    }
+
     /// Construct [`HashedPostState`] from a single [`HashedStorage`].
     pub fn from_hashed_storage(hashed_address: B256, storage: HashedStorage) -> Self {
         Self {
@@ -752,6 +788,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hashed_post_state_from_evm_state() {
+        let info = AccountInfo { balance: U256::from(1), ..Default::default() };
+
+        // touched account with a changed and an unchanged slot
+        let changed = Address::with_last_byte(1);
+        let mut account = revm_state::Account::from(info.clone());
+        account.mark_touch();
+        account.storage.insert(
+            U256::from(1),
+            revm_state::EvmStorageSlot::new_changed(U256::ZERO, U256::from(2), 0),
+        );
+        account.storage.insert(U256::from(2), revm_state::EvmStorageSlot::new(U256::from(3), 0));
+
+        // self-destructed account
+        let destroyed = Address::with_last_byte(2);
+        let mut destroyed_account = revm_state::Account::from(info.clone());
+        destroyed_account.mark_touch();
+        destroyed_account.mark_selfdestruct();
+
+        // account that was only loaded
+        let loaded = Address::with_last_byte(3);
+
+        let state = EvmState::from_iter([
+            (changed, account),
+            (destroyed, destroyed_account),
+            (loaded, revm_state::Account::from(info.clone())),
+        ]);
+        let hashed_state = HashedPostState::from_evm_state::<KeccakKeyHasher>(state);
+
+        assert_eq!(hashed_state.accounts.len(), 2);
+        assert_eq!(hashed_state.accounts.get(&keccak256(changed)), Some(&Some(info.into())));
+        assert_eq!(hashed_state.accounts.get(&keccak256(destroyed)), Some(&None));
+        assert_eq!(
+            hashed_state.storages.get(&keccak256(changed)),
+            Some(&HashedStorage::from_iter(
+                false,
+                [(keccak256(B256::from(U256::from(1))), U256::from(2))],
+            ))
+        );
+        assert_eq!(
+            hashed_state.storages.get(&keccak256(destroyed)),
+            Some(&HashedStorage::new(true))
+        );
+    }
+
     #[test]
     fn test_hashed_post_state_with_accounts() {
         // Prepare random addresses and mock account info.
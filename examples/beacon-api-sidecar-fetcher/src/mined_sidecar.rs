@@ -219,6 +219,8 @@ where
                                 this.process_block(block);
                             }
                         }
+                        // there is no new block to report the reverted blobs in
+                        CanonStateNotification::Revert { .. } => {}
                     }
                 }
             }